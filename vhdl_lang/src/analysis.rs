@@ -32,5 +32,6 @@ mod types;
 pub(crate) mod tests;
 
 pub(crate) use root::{Library, LockedUnit};
+pub(crate) use static_expression::bit_string_to_string;
 
-pub use self::root::{DesignRoot, EntHierarchy};
+pub use self::root::{DesignRoot, EntHierarchy, HoverInfo, ReferenceKind};
@@ -13,7 +13,7 @@ use crate::named_entity::*;
 use crate::syntax::TokenAccess;
 use crate::TokenSpan;
 use fnv::FnvHashSet;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 
 /// Indicates that a circular dependency is found at the position denoted by `reference`.
@@ -160,6 +160,9 @@ pub(super) struct AnalyzeContext<'a, 't> {
     uses: RefCell<FnvHashSet<UnitId>>,
     missing_unit: RefCell<FnvHashSet<(Symbol, Symbol, Option<Symbol>)>>,
     uses_library_all: RefCell<FnvHashSet<Symbol>>,
+    // The actual that is being analyzed when it is not known to be read,
+    // such as the actual of a formal of mode out, which is updated instead
+    pub(super) unread_actual: Cell<Option<TokenSpan>>,
     pub ctx: &'t dyn TokenAccess,
 }
 
@@ -187,6 +190,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             uses: RefCell::new(FnvHashSet::default()),
             missing_unit: RefCell::new(FnvHashSet::default()),
             uses_library_all: RefCell::new(FnvHashSet::default()),
+            unread_actual: Cell::new(None),
             ctx,
         }
     }
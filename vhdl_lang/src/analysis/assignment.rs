@@ -44,10 +44,19 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
             AssignmentRightHand::Selected(selection) => {
                 let Selection {
+                    is_matching,
                     expression,
                     alternatives,
                 } = selection;
                 let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                if *is_matching {
+                    self.check_matching_selection_type(
+                        ctyp,
+                        expression,
+                        "matching selected assignment",
+                        diagnostics,
+                    );
+                }
                 for Alternative {
                     choices,
                     item,
@@ -94,10 +103,19 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
             AssignmentRightHand::Selected(selection) => {
                 let Selection {
+                    is_matching,
                     expression,
                     alternatives,
                 } = selection;
                 let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                if *is_matching {
+                    self.check_matching_selection_type(
+                        ctyp,
+                        expression,
+                        "matching selected assignment",
+                        diagnostics,
+                    );
+                }
                 for Alternative {
                     choices,
                     item,
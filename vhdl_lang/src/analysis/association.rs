@@ -499,14 +499,31 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                     actual.span,
                                     diagnostics,
                                 )?;
+                            } else if formal_region.typ == InterfaceType::Port {
+                                self.check_view_port_interface(
+                                    resolved_formal,
+                                    expr,
+                                    scope,
+                                    actual.span,
+                                    diagnostics,
+                                )?;
+                            }
+                            if matches!(
+                                resolved_formal.iface.kind(),
+                                AnyEntKind::Object(object)
+                                    if object.mode() == Some(&InterfaceMode::Simple(Mode::Out))
+                            ) {
+                                self.unread_actual.set(Some(actual.span));
                             }
-                            self.expr_pos_with_ttyp(
+                            let result = self.expr_pos_with_ttyp(
                                 scope,
                                 resolved_formal.type_mark,
                                 actual.span,
                                 expr,
                                 diagnostics,
-                            )?;
+                            );
+                            self.unread_actual.set(None);
+                            result?;
                         } else {
                             self.expr_pos_unknown_ttyp(scope, actual.span, expr, diagnostics)?;
                         }
@@ -600,6 +617,71 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    // LRM 6.5.6.3: The actual of a port of a view mode must be a name denoting a signal.
+    // Elements that are driven by the formal may not be inputs of the actual
+    fn check_view_port_interface(
+        &self,
+        resolved_formal: &ResolvedFormal<'a>,
+        expr: &mut Expression,
+        scope: &Scope<'a>,
+        actual_pos: TokenSpan,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        if resolved_formal.is_partial || resolved_formal.is_converted {
+            return Ok(());
+        }
+        let AnyEntKind::Object(Object {
+            iface: Some(ObjectInterface::Port(InterfaceMode::View(view))),
+            ..
+        }) = resolved_formal.iface.kind()
+        else {
+            return Ok(());
+        };
+        let Some(name) = as_fatal(self.expression_as_name(expr, scope, actual_pos, diagnostics))?
+        else {
+            diagnostics.add(
+                actual_pos.pos(self.ctx),
+                "Expression must be a name denoting a signal",
+                ErrorCode::InterfaceModeMismatch,
+            );
+            return Ok(());
+        };
+        let ResolvedName::ObjectName(ObjectName { base, .. }) = name else {
+            diagnostics.add(
+                actual_pos.pos(self.ctx),
+                "Name must denote a signal name",
+                ErrorCode::InterfaceModeMismatch,
+            );
+            return Ok(());
+        };
+        if base.class() != ObjectClass::Signal {
+            diagnostics.add(
+                actual_pos.pos(self.ctx),
+                "Name must denote a signal name",
+                ErrorCode::InterfaceModeMismatch,
+            );
+            return Ok(());
+        }
+        let Expression::Name(name) = expr else {
+            return Ok(());
+        };
+        if let Some(actual_mode) = base.mode_of(name) {
+            if drives_input(ViewElementMode::Record(*view), actual_mode) {
+                diagnostics.add(
+                    actual_pos.pos(self.ctx),
+                    format!(
+                        "{} may not be associated with port '{}' of mode view {}, which drives it",
+                        base.describe_class(),
+                        resolved_formal.iface.designator(),
+                        view.ent.designator()
+                    ),
+                    ErrorCode::InterfaceModeMismatch,
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn expression_as_name(
         &self,
         expr: &mut Expression,
@@ -617,6 +699,30 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     }
 }
 
+/// Returns true when a formal of some mode may drive an element of an actual
+/// that is only an input
+fn drives_input(formal: ViewElementMode<'_>, actual: ViewElementMode<'_>) -> bool {
+    match (formal, actual) {
+        (ViewElementMode::Simple(Mode::In), _) => false,
+        (ViewElementMode::Simple(_), actual) => !actual.is_writable(),
+        (
+            ViewElementMode::Record(formal) | ViewElementMode::Array(formal),
+            ViewElementMode::Simple(_),
+        ) => formal
+            .elements()
+            .values()
+            .any(|element| drives_input(*element, actual)),
+        (
+            ViewElementMode::Record(formal) | ViewElementMode::Array(formal),
+            ViewElementMode::Record(actual) | ViewElementMode::Array(actual),
+        ) => formal.elements().iter().any(|(designator, element)| {
+            actual
+                .element_mode(designator)
+                .is_some_and(|actual| drives_input(*element, actual))
+        }),
+    }
+}
+
 fn to_formal_conversion_argument(
     parameters: &mut [AssociationElement],
 ) -> Option<(TokenSpan, &mut Box<Name>)> {
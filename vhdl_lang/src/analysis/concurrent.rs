@@ -176,6 +176,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         Selection {
                             ref mut expression,
                             ref mut alternatives,
+                            ..
                         },
                     end_label_pos: _,
                     ..
@@ -344,7 +344,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
                 ResolvedName::Final(ent) => {
                     if let Some(ent) = ViewEnt::from_any(ent) {
-                        AnyEntKind::View(*ent.subtype())
+                        AnyEntKind::View(*ent.subtype(), ent.elements().clone())
                     } else {
                         // @TODO some of these can probably be aliased
                         return Err(EvalError::Unknown);
@@ -598,21 +598,33 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 let ent = self.define(
                     &mut instance.ident,
                     parent,
-                    AnyEntKind::Design(Design::PackageInstance(Region::default())),
+                    AnyEntKind::Design(Design::PackageInstance(
+                        Region::default(),
+                        GenericActuals::default(),
+                    )),
                     src_span,
                 );
 
-                if let Some(pkg_region) =
+                if let Some((pkg_region, actuals)) =
                     as_fatal(self.generic_package_instance(scope, ent, instance, diagnostics))?
                 {
-                    let kind = AnyEntKind::Design(Design::PackageInstance(pkg_region));
+                    let kind = AnyEntKind::Design(Design::PackageInstance(pkg_region, actuals));
                     unsafe {
                         ent.set_kind(kind);
                     }
                     scope.add(ent, diagnostics);
                 }
             }
-            Declaration::Configuration(..) => {}
+            Declaration::Configuration(config) => {
+                let component =
+                    self.analyze_component_specification(scope, &mut config.spec, diagnostics)?;
+                self.analyze_binding_indication(
+                    scope,
+                    component,
+                    &mut config.bind_ind,
+                    diagnostics,
+                )?;
+            }
             Declaration::View(view) => {
                 if let Some(view) = as_fatal(self.analyze_view_declaration(
                     scope,
@@ -661,7 +673,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
         };
         let mut unassociated: HashSet<_> = record_region.elems.iter().collect();
+        let mut elements = FnvHashMap::default();
         for element in view.elements.iter_mut() {
+            let mode = as_fatal(self.analyze_element_mode(scope, &mut element.mode, diagnostics))?;
             for name in element.names.iter_mut() {
                 let desi = Designator::Identifier(name.tree.item.clone());
                 let Some(record_element) = record_region.lookup(&desi) else {
@@ -674,6 +688,10 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 };
                 name.decl.set_unique_reference(&record_element);
                 unassociated.remove(&record_element);
+                if let Some(mode) = mode {
+                    self.check_element_view_type(record_element, &mode, &element.mode, diagnostics);
+                    elements.insert(desi, mode);
+                }
             }
         }
         if !unassociated.is_empty() {
@@ -683,7 +701,71 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 ErrorCode::Unassociated,
             );
         }
-        Ok(self.define(&mut view.ident, parent, AnyEntKind::View(typ), src_span))
+        Ok(self.define(
+            &mut view.ident,
+            parent,
+            AnyEntKind::View(typ, elements),
+            src_span,
+        ))
+    }
+
+    /// Resolves the mode of the elements of a view declaration.
+    /// Record and array elements refer to the view of their subelements
+    fn analyze_element_mode(
+        &self,
+        scope: &Scope<'a>,
+        mode: &mut ElementMode,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<ViewElementMode<'a>> {
+        match mode {
+            ElementMode::Simple(mode) => Ok(ViewElementMode::Simple(mode.item)),
+            ElementMode::Record(name) => {
+                let resolved = self.name_resolve(scope, name.span, &mut name.item, diagnostics)?;
+                let view = self.resolve_view_ent(&resolved, diagnostics, name.span)?;
+                Ok(ViewElementMode::Record(view))
+            }
+            ElementMode::Array(name) => {
+                let resolved = self.name_resolve(scope, name.span, &mut name.item, diagnostics)?;
+                let view = self.resolve_view_ent(&resolved, diagnostics, name.span)?;
+                Ok(ViewElementMode::Array(view))
+            }
+        }
+    }
+
+    /// Checks that the view of a record or array element is declared for the type of the element
+    fn check_element_view_type(
+        &self,
+        record_element: RecordElement<'a>,
+        mode: &ViewElementMode<'a>,
+        ast_mode: &ElementMode,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let (view, name, elem_type) = match (mode, ast_mode) {
+            (ViewElementMode::Record(view), ElementMode::Record(name)) => {
+                (view, name, Some(record_element.type_mark()))
+            }
+            (ViewElementMode::Array(view), ElementMode::Array(name)) => {
+                let elem_type = match record_element.type_mark().base_type().kind() {
+                    Type::Array { elem_type, .. } => Some(*elem_type),
+                    _ => None,
+                };
+                (view, name, elem_type)
+            }
+            _ => return,
+        };
+        let view_type = view.subtype().type_mark();
+        if elem_type.map(|typ| typ.base_type()) != Some(view_type.base_type()) {
+            diagnostics.add(
+                name.pos(self.ctx),
+                format!(
+                    "{} is declared for {}, which does not match the element '{}'",
+                    view.ent.describe(),
+                    view_type.describe(),
+                    record_element.designator()
+                ),
+                ErrorCode::TypeMismatch,
+            );
+        }
     }
 
     fn find_deferred_constant_declaration(
@@ -931,16 +1013,20 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 vec![ent]
             }
             InterfaceDeclaration::Package(ref mut instance) => {
-                let package_region = self.analyze_package_instance_name(
+                let (package, package_region) = self.analyze_package_instance_name(
                     scope,
                     &mut instance.package_name,
                     diagnostics,
                 )?;
+                let actuals = GenericActuals {
+                    package: Some(package),
+                    types: FnvHashMap::default(),
+                };
 
                 vec![self.define(
                     &mut instance.ident,
                     parent,
-                    AnyEntKind::Design(Design::InterfacePackageInstance(package_region)),
+                    AnyEntKind::Design(Design::InterfacePackageInstance(package_region, actuals)),
                     span,
                 )]
             }
@@ -1252,11 +1338,11 @@ fn get_entity_class(ent: EntRef<'_>) -> Option<EntityClass> {
             // Should never be target of attribute
             Design::PackageBody(..) => None,
             Design::UninstPackage(_, _) => None,
-            Design::PackageInstance(_) => None,
-            Design::InterfacePackageInstance(_) => None,
+            Design::PackageInstance(..) => None,
+            Design::InterfacePackageInstance(..) => None,
             Design::Context(_) => None,
         },
-        AnyEntKind::View(_) => None,
+        AnyEntKind::View(..) => None,
     }
 }
 
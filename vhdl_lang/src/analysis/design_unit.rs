@@ -99,9 +99,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         self.add_implicit_context_clause(&root_region)?;
         self.analyze_context_clause(&root_region, &mut unit.context_clause, diagnostics)?;
 
-        if let Some(named_entity) =
-            as_fatal(self.lookup_entity_for_configuration(&root_region, unit, diagnostics))?
-        {
+        let entity =
+            as_fatal(self.lookup_entity_for_configuration(&root_region, unit, diagnostics))?;
+        if let Some(named_entity) = entity {
             if let Some(primary_pos) = named_entity.decl_pos() {
                 let secondary_pos = unit.ident_pos(self.ctx);
                 if primary_pos.source == secondary_pos.source
@@ -120,6 +120,21 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
         };
 
+        match entity {
+            Some(entity) => self.analyze_architecture_configuration(
+                &root_region,
+                entity,
+                &mut unit.block_config,
+                diagnostics,
+            )?,
+            None => self.analyze_configuration_items(
+                &root_region,
+                &mut unit.block_config.items,
+                false,
+                diagnostics,
+            )?,
+        }
+
         self.define(
             &mut unit.ident,
             self.work_library(),
@@ -186,7 +201,10 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let ent = self.arena.explicit(
             unit.name().clone(),
             self.work_library(),
-            AnyEntKind::Design(Design::PackageInstance(Region::default())),
+            AnyEntKind::Design(Design::PackageInstance(
+                Region::default(),
+                GenericActuals::default(),
+            )),
             Some(unit.ident_pos(self.ctx)),
             unit.span(),
             Some(self.source()),
@@ -198,10 +216,10 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 
         self.analyze_context_clause(&root_scope, &mut unit.context_clause, diagnostics)?;
 
-        if let Some(pkg_region) =
+        if let Some((pkg_region, actuals)) =
             as_fatal(self.generic_package_instance(&root_scope, ent, unit, diagnostics))?
         {
-            let kind = AnyEntKind::Design(Design::PackageInstance(pkg_region));
+            let kind = AnyEntKind::Design(Design::PackageInstance(pkg_region, actuals));
 
             unsafe {
                 ent.set_kind(kind);
@@ -467,6 +485,417 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
     }
 
+    /// Resolves the architecture of the block configuration of an entity.
+    /// The items of the block configuration are resolved within the architecture.
+    fn analyze_architecture_configuration(
+        &self,
+        scope: &Scope<'a>,
+        entity: DesignEnt<'a>,
+        block_config: &mut BlockConfiguration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let block_spec_pos = block_config.block_spec.pos(self.ctx);
+        let architecture = match (
+            &mut block_config.block_spec.item,
+            entity.designator(),
+            entity.library_name(),
+        ) {
+            (
+                Name::Designator(designator),
+                Designator::Identifier(entity_name),
+                Some(library_name),
+            ) => match &designator.item {
+                Designator::Identifier(architecture_name) => {
+                    let architecture = as_fatal(self.get_architecture(
+                        diagnostics,
+                        library_name,
+                        &block_spec_pos,
+                        entity_name,
+                        architecture_name,
+                    ))?;
+                    if let Some(architecture) = architecture {
+                        designator.set_unique_reference(&architecture);
+                    }
+                    architecture
+                }
+                _ => None,
+            },
+            (Name::Designator(_), ..) => None,
+            _ => {
+                diagnostics.add(
+                    &block_spec_pos,
+                    format!("Expected an architecture of {}", entity.describe()),
+                    ErrorCode::MismatchedKinds,
+                );
+                None
+            }
+        };
+
+        let Some(Design::Architecture(visibility, region, _)) =
+            architecture.map(|architecture| architecture.kind())
+        else {
+            return self.analyze_configuration_items(
+                scope,
+                &mut block_config.items,
+                false,
+                diagnostics,
+            );
+        };
+        let architecture_scope = Scope::extend(
+            region,
+            Some(&Scope::new(Region::with_visibility(visibility.clone())).with_parent(scope)),
+        );
+        for use_clause in block_config.use_clauses.iter_mut() {
+            self.analyze_use_clause(&architecture_scope, use_clause, diagnostics)?;
+        }
+        self.analyze_configuration_items(
+            &architecture_scope,
+            &mut block_config.items,
+            true,
+            diagnostics,
+        )
+    }
+
+    /// Resolves the components and the bindings of the items of a block configuration.
+    /// Within the block configuration of an architecture the components must be visible,
+    /// whereas the declarations of nested blocks and generate statements are not known,
+    /// so components that are not visible are ignored.
+    fn analyze_configuration_items(
+        &self,
+        scope: &Scope<'a>,
+        items: &mut [ConfigurationItem],
+        is_architecture: bool,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        for item in items.iter_mut() {
+            match item {
+                ConfigurationItem::Block(block_config) => {
+                    self.resolve_block_specification(scope, &mut block_config.block_spec);
+                    for use_clause in block_config.use_clauses.iter_mut() {
+                        self.analyze_use_clause(scope, use_clause, diagnostics)?;
+                    }
+                    self.analyze_configuration_items(
+                        scope,
+                        &mut block_config.items,
+                        false,
+                        diagnostics,
+                    )?;
+                }
+                ConfigurationItem::Component(component_config) => {
+                    let component = if is_architecture {
+                        self.analyze_component_specification(
+                            scope,
+                            &mut component_config.spec,
+                            diagnostics,
+                        )?
+                    } else {
+                        self.analyze_component_specification(
+                            scope,
+                            &mut component_config.spec,
+                            &mut NullDiagnostics,
+                        )?
+                    };
+                    let entity = match component_config.bind_ind {
+                        Some(ref mut bind_ind) => self.analyze_binding_indication(
+                            scope,
+                            component,
+                            bind_ind,
+                            diagnostics,
+                        )?,
+                        None => None,
+                    };
+                    if let Some(ref mut block_config) = component_config.block_config {
+                        match entity {
+                            Some(entity) => self.analyze_architecture_configuration(
+                                scope,
+                                entity,
+                                block_config,
+                                diagnostics,
+                            )?,
+                            None => self.analyze_configuration_items(
+                                scope,
+                                &mut block_config.items,
+                                false,
+                                diagnostics,
+                            )?,
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the label of a block or generate statement of a block configuration
+    /// when the statement is declared by the architecture
+    fn resolve_block_specification(&self, scope: &Scope<'a>, block_spec: &mut WithTokenSpan<Name>) {
+        let designator = match &mut block_spec.item {
+            Name::Designator(designator) => designator,
+            Name::CallOrIndexed(call) => match &mut call.name.item {
+                Name::Designator(designator) => designator,
+                _ => return,
+            },
+            Name::Slice(prefix, _) => match &mut prefix.item {
+                Name::Designator(designator) => designator,
+                _ => return,
+            },
+            _ => return,
+        };
+        if let Some(NamedEntities::Single(ent)) = scope.lookup_immediate(&designator.item) {
+            if matches!(ent.kind(), AnyEntKind::Concurrent(..)) {
+                designator.set_unique_reference(ent);
+            }
+        }
+    }
+
+    /// Resolves the component of the component specification
+    /// of a configuration specification or a component configuration
+    pub fn analyze_component_specification(
+        &self,
+        scope: &Scope<'a>,
+        spec: &mut ComponentSpecification,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<EntRef<'a>>> {
+        let component_name = &mut spec.component_name;
+        let Some(resolved) = as_fatal(self.name_resolve(
+            scope,
+            component_name.span,
+            &mut component_name.item,
+            diagnostics,
+        ))?
+        else {
+            return Ok(None);
+        };
+        match resolved {
+            ResolvedName::Final(ent) if matches!(ent.kind(), AnyEntKind::Component(_)) => {
+                Ok(Some(ent))
+            }
+            other => {
+                diagnostics
+                    .push(other.kind_error(component_name.suffix_pos().pos(self.ctx), "component"));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Resolves the entity or configuration of a binding indication and checks
+    /// the binding of the generics and ports of the component to those of the entity.
+    /// Returns the entity that the component is bound to.
+    pub fn analyze_binding_indication(
+        &self,
+        scope: &Scope<'a>,
+        component: Option<EntRef<'a>>,
+        bind_ind: &mut BindingIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<DesignEnt<'a>>> {
+        let entity =
+            match bind_ind.entity_aspect {
+                Some(EntityAspect::Entity(ref mut entity_name, ref mut architecture_name)) => self
+                    .analyze_entity_aspect(scope, entity_name, architecture_name, diagnostics)?
+                    .map(|entity| (entity, entity_name.pos(self.ctx))),
+                Some(EntityAspect::Configuration(ref mut config_name)) => {
+                    if let Some(resolved) = as_fatal(self.name_resolve(
+                        scope,
+                        config_name.span,
+                        &mut config_name.item,
+                        diagnostics,
+                    ))? {
+                        match resolved {
+                            ResolvedName::Design(ent)
+                                if matches!(ent.kind(), Design::Configuration) => {}
+                            other => diagnostics.push(other.kind_error(
+                                config_name.suffix_pos().pos(self.ctx),
+                                "configuration",
+                            )),
+                        }
+                    }
+                    None
+                }
+                Some(EntityAspect::Open) | None => None,
+            };
+
+        // The actuals of the binding are the generics and ports of the component
+        let Some(AnyEntKind::Component(component_region)) =
+            component.map(|component| component.kind())
+        else {
+            return Ok(entity.map(|(entity, _)| entity));
+        };
+        let local_scope = Scope::extend(component_region, Some(scope));
+        let Some((entity, entity_pos)) = entity else {
+            self.analyze_map_aspect(&local_scope, &mut bind_ind.generic_map, diagnostics)?;
+            self.analyze_map_aspect(&local_scope, &mut bind_ind.port_map, diagnostics)?;
+            return Ok(None);
+        };
+        let Design::Entity(_, entity_region) = entity.kind() else {
+            return Ok(Some(entity));
+        };
+
+        let (generic_region, port_region) = entity_region.to_entity_formal();
+        let (local_ports, local_generics) = component_region.ports_and_generics();
+        let (ports, generics) = entity_region.ports_and_generics();
+        for (map, formal_region, locals, formals) in [
+            (
+                &mut bind_ind.generic_map,
+                &generic_region,
+                local_generics,
+                generics,
+            ),
+            (&mut bind_ind.port_map, &port_region, local_ports, ports),
+        ] {
+            match map {
+                Some(map) => {
+                    self.check_association(
+                        &entity_pos,
+                        formal_region,
+                        &local_scope,
+                        map.list.items.as_mut_slice(),
+                        diagnostics,
+                    )?;
+                }
+                None => self.check_default_binding(
+                    &entity_pos,
+                    component,
+                    entity,
+                    formal_region.typ,
+                    &locals,
+                    &formals,
+                    diagnostics,
+                ),
+            }
+        }
+        Ok(Some(entity))
+    }
+
+    /// Checks that the generics or ports of a component can be bound to those of an entity
+    /// without an explicit map aspect, where they are associated by their name
+    #[allow(clippy::too_many_arguments)]
+    fn check_default_binding(
+        &self,
+        pos: &SrcPos,
+        component: Option<EntRef<'a>>,
+        entity: DesignEnt<'a>,
+        typ: InterfaceType,
+        locals: &[InterfaceEnt<'a>],
+        formals: &[InterfaceEnt<'a>],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(component) = component else {
+            return;
+        };
+        let class = match typ {
+            InterfaceType::Generic => "generic",
+            _ => "port",
+        };
+        for local in locals.iter() {
+            let Some(formal) = formals
+                .iter()
+                .find(|formal| formal.designator() == local.designator())
+            else {
+                diagnostics.push(
+                    Diagnostic::new(
+                        pos,
+                        format!(
+                            "No {class} '{}' within {} for the default binding of {}",
+                            local.designator(),
+                            entity.describe(),
+                            component.describe()
+                        ),
+                        ErrorCode::InvalidFormal,
+                    )
+                    .opt_related(local.decl_pos(), "Defined here"),
+                );
+                continue;
+            };
+            if let (AnyEntKind::Object(_), AnyEntKind::Object(_)) = (local.kind(), formal.kind()) {
+                if local.base() != formal.base() {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            pos,
+                            format!(
+                                "{} '{}' of {} is of {}, but the {class} of {} is of {}",
+                                capitalize(class),
+                                local.designator(),
+                                component.describe(),
+                                local.type_mark().describe(),
+                                entity.describe(),
+                                formal.type_mark().describe()
+                            ),
+                            ErrorCode::TypeMismatch,
+                        )
+                        .opt_related(formal.decl_pos(), "Defined here"),
+                    );
+                }
+            }
+        }
+        for formal in formals.iter() {
+            let is_bound = locals
+                .iter()
+                .any(|local| local.designator() == formal.designator());
+            // Defaults and output ports may be unconnected
+            if !(is_bound
+                || formal.has_default()
+                || (typ == InterfaceType::Port && formal.is_out_or_inout_signal()))
+            {
+                diagnostics.push(
+                    Diagnostic::new(
+                        pos,
+                        format!(
+                            "No association of {class} '{}' in the default binding of {}",
+                            formal.designator(),
+                            component.describe()
+                        ),
+                        ErrorCode::Unassociated,
+                    )
+                    .opt_related(formal.decl_pos(), "Defined here"),
+                );
+            }
+        }
+    }
+
+    fn analyze_entity_aspect(
+        &self,
+        scope: &Scope<'a>,
+        entity_name: &mut WithTokenSpan<Name>,
+        architecture_name: &mut Option<WithRef<Ident>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<DesignEnt<'a>>> {
+        let Some(resolved) = as_fatal(self.name_resolve(
+            scope,
+            entity_name.span,
+            &mut entity_name.item,
+            diagnostics,
+        ))?
+        else {
+            return Ok(None);
+        };
+        match resolved {
+            ResolvedName::Design(ent) if matches!(ent.kind(), Design::Entity(..)) => {
+                if let (
+                    Some(architecture_name),
+                    Designator::Identifier(entity_ident),
+                    Some(library_name),
+                ) = (architecture_name, ent.designator(), ent.library_name())
+                {
+                    if let Some(arch) = as_fatal(self.get_architecture(
+                        diagnostics,
+                        library_name,
+                        self.ctx.get_pos(architecture_name.item.token),
+                        entity_ident,
+                        &architecture_name.item.item,
+                    ))? {
+                        architecture_name.set_unique_reference(&arch);
+                    }
+                }
+                Ok(Some(ent))
+            }
+            other => {
+                diagnostics
+                    .push(other.kind_error(entity_name.suffix_pos().pos(self.ctx), "entity"));
+                Ok(None)
+            }
+        }
+    }
+
     fn resolve_context_item_prefix(
         &self,
         diagnostics: &mut dyn DiagnosticHandler,
@@ -681,8 +1110,8 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                             ));
                         }
                         Design::Package(_, ref primary_region)
-                        | Design::PackageInstance(ref primary_region)
-                        | Design::InterfacePackageInstance(ref primary_region) => {
+                        | Design::PackageInstance(ref primary_region, _)
+                        | Design::InterfacePackageInstance(ref primary_region, _) => {
                             scope.make_all_potentially_visible(
                                 Some(&name.pos(self.ctx)),
                                 primary_region,
@@ -711,13 +1140,13 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
-    /// Returns a reference to the uninstantiated package
+    /// Returns the id and the region of the uninstantiated package
     pub fn analyze_package_instance_name(
         &self,
         scope: &Scope<'a>,
         package_name: &mut WithTokenSpan<Name>,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> EvalResult<Region<'a>> {
+    ) -> EvalResult<(EntityId, Region<'a>)> {
         let name = self.name_resolve(
             scope,
             package_name.span,
@@ -726,7 +1155,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         )?;
         if let ResolvedName::Design(ref unit) = name {
             if let AnyEntKind::Design(Design::UninstPackage(_, package_region)) = &unit.kind {
-                return Ok(package_region.clone());
+                return Ok((unit.id(), package_region.clone()));
             }
         }
         diagnostics.add(
@@ -491,6 +491,27 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             Expression::Parenthesized(expr) => {
                 self.expr_pos_type(scope, expr.span, &mut expr.item, diagnostics)
             }
+            Expression::Conditional(ref mut conditionals) => {
+                let Conditionals {
+                    conditionals,
+                    else_item,
+                } = conditionals.as_mut();
+                let mut typ = Err(EvalError::Unknown);
+                for conditional in conditionals.iter_mut() {
+                    self.boolean_expr(scope, &mut conditional.condition, diagnostics)?;
+                    let item_typ = self.expr_type(scope, &mut conditional.item, diagnostics);
+                    if typ.is_err() {
+                        typ = item_typ;
+                    }
+                }
+                if let Some((ref mut expr, _)) = else_item {
+                    let item_typ = self.expr_type(scope, expr, diagnostics);
+                    if typ.is_err() {
+                        typ = item_typ;
+                    }
+                }
+                typ
+            }
             Expression::Literal(ref mut literal) => match literal {
                 Literal::Physical(PhysicalLiteral { ref mut unit, .. }) => {
                     match self.resolve_physical_unit(scope, unit) {
@@ -881,6 +902,19 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     diagnostics,
                 )?;
             }
+            Expression::Conditional(ref mut conditionals) => {
+                let Conditionals {
+                    conditionals,
+                    else_item,
+                } = conditionals.as_mut();
+                for conditional in conditionals.iter_mut() {
+                    self.boolean_expr(scope, &mut conditional.condition, diagnostics)?;
+                    self.expr_with_ttyp(scope, target_type, &mut conditional.item, diagnostics)?;
+                }
+                if let Some((ref mut expr, _)) = else_item {
+                    self.expr_with_ttyp(scope, target_type, expr, diagnostics)?;
+                }
+            }
         }
 
         Ok(())
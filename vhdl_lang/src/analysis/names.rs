@@ -46,20 +46,41 @@ impl<'a> ObjectBase<'a> {
         }
     }
 
-    /// Check that this object is a writable object and not constant or input only
-    pub fn can_be_assigned_to(&self) -> bool {
+    /// The mode of the part of this object that is denoted by a name.
+    /// For an object of a view mode, selecting a record element yields the mode
+    /// of that element in the view
+    pub fn mode_of(&self, name: &Name) -> Option<ViewElementMode<'a>> {
+        match name {
+            Name::Selected(prefix, suffix) => match self.mode_of(&prefix.item)? {
+                ViewElementMode::Record(view) | ViewElementMode::Array(view) => {
+                    view.element_mode(&suffix.item.item)
+                }
+                mode => Some(mode),
+            },
+            Name::Slice(prefix, _) => self.mode_of(&prefix.item),
+            Name::CallOrIndexed(call) => self.mode_of(&call.name.item),
+            _ => match self {
+                ObjectBase::Object(_) => match self.mode()? {
+                    InterfaceMode::Simple(mode) => Some(ViewElementMode::Simple(*mode)),
+                    InterfaceMode::View(view) => Some(ViewElementMode::Record(*view)),
+                },
+                // An alias may denote an element of an object of a view mode
+                ObjectBase::ObjectAlias(..) => match self.mode()? {
+                    InterfaceMode::Simple(mode) => Some(ViewElementMode::Simple(*mode)),
+                    InterfaceMode::View(_) => None,
+                },
+                ObjectBase::DeferredConstant(..) | ObjectBase::ExternalName(_) => None,
+            },
+        }
+    }
+
+    /// Check that the part of this object that is denoted by a name is a writable object
+    /// and not constant or input only
+    pub fn can_be_assigned_to(&self, name: &Name) -> bool {
         if self.class() == ObjectClass::Constant {
             return false;
         }
-        match self.mode() {
-            None => true,
-            Some(InterfaceMode::Simple(Mode::In)) => false,
-            Some(InterfaceMode::Simple(_)) => true,
-            // This is triggered when assigning, e.g.,
-            // using foo.bar <= baz where `foo` is a view.
-            // TODO: check, that this assignment is legal.
-            Some(InterfaceMode::View(_)) => true,
-        }
+        self.mode_of(name).is_none_or(|mode| mode.is_writable())
     }
 
     /// Check that a signal is not the target of a variable assignment and vice-versa
@@ -185,7 +206,7 @@ impl<'a> ResolvedName<'a> {
                 type_mark: Some(subtype.type_mark()),
             }),
             AnyEntKind::Type(_) => ResolvedName::Type(TypeEnt::from_any(ent).unwrap()),
-            AnyEntKind::View(_) => ResolvedName::Final(ent),
+            AnyEntKind::View(..) => ResolvedName::Final(ent),
             AnyEntKind::Overloaded(_) => {
                 return Err((
                     "Internal error. Unreachable as overloaded is handled outside".to_owned(),
@@ -255,7 +276,7 @@ impl<'a> ResolvedName<'a> {
                 ));
             }
             AnyEntKind::File(_)
-            | AnyEntKind::View(_)
+            | AnyEntKind::View(..)
             | AnyEntKind::InterfaceFile(_)
             | AnyEntKind::Component(_)
             | AnyEntKind::Concurrent(_)
@@ -798,6 +819,38 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
     }
 
+    /// LRM 6.5.2: Creates the view denoted by `view'converse`,
+    /// where the mode of each element is replaced by its converse
+    fn converse_view(&self, view: ViewEnt<'a>) -> ViewEnt<'a> {
+        let elements = view
+            .elements()
+            .iter()
+            .map(|(designator, mode)| {
+                let mode = match mode {
+                    ViewElementMode::Simple(mode) => ViewElementMode::Simple(match mode {
+                        Mode::In => Mode::Out,
+                        Mode::Out | Mode::Buffer => Mode::In,
+                        Mode::InOut => Mode::InOut,
+                        Mode::Linkage => Mode::Linkage,
+                    }),
+                    ViewElementMode::Record(view) => {
+                        ViewElementMode::Record(self.converse_view(*view))
+                    }
+                    ViewElementMode::Array(view) => {
+                        ViewElementMode::Array(self.converse_view(*view))
+                    }
+                };
+                (designator.clone(), mode)
+            })
+            .collect();
+        let ent = self.arena.implicit(
+            view.ent,
+            view.ent.designator().clone(),
+            AnyEntKind::View(*view.subtype(), elements),
+        );
+        ViewEnt { ent }
+    }
+
     pub(crate) fn resolve_view_ent(
         &self,
         resolved: &ResolvedName<'a>,
@@ -1080,10 +1133,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 .map(|typ| ResolvedName::Type(typ.base().into())),
             AttributeDesignator::Converse => {
                 let view = self.resolve_view_ent(prefix, diagnostics, prefix_pos)?;
-                // Since we do not check the actual mode of the view,
-                // this does not actually converse anything at the moment.
-                // We only check that the selected name is a view and return that view.
-                Ok(ResolvedName::Final(view.ent))
+                Ok(ResolvedName::Final(self.converse_view(view).ent))
             }
         }
     }
@@ -1685,6 +1735,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     ) -> EvalResult<DisambiguatedType<'a>> {
         let resolved =
             self.name_resolve_with_suffixes(scope, span, name, None, false, diagnostics)?;
+        self.check_read_of_out_port(span, &resolved, diagnostics);
         match self.name_to_type(span, name.suffix_reference_mut(), resolved) {
             Ok(Some(typ)) => Ok(typ),
             Ok(None) => Err(EvalError::Unknown),
@@ -1695,6 +1746,30 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
     }
 
+    /// Before VHDL-2008, an interface object of mode out may be updated but not read
+    fn check_read_of_out_port(
+        &self,
+        span: TokenSpan,
+        resolved: &ResolvedName<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if self.is_vhdl2008() || self.unread_actual.get() == Some(span) {
+            return;
+        }
+        if let ResolvedName::ObjectName(object_name) = resolved {
+            if object_name.base.mode() == Some(&InterfaceMode::Simple(Mode::Out)) {
+                diagnostics.add(
+                    span.pos(self.ctx),
+                    format!(
+                        "{} may not be read before VHDL-2008",
+                        object_name.base.describe_class()
+                    ),
+                    ErrorCode::IncompatibleStandard,
+                );
+            }
+        }
+    }
+
     /// Analyze a name that is part of an expression that must be unambiguous
     pub fn expression_name_with_ttyp(
         &self,
@@ -1712,6 +1787,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             false,
             diagnostics,
         ))? {
+            self.check_read_of_out_port(span, &resolved, diagnostics);
             // @TODO target_type already used above, functions could probably be simplified
             match self.name_to_unambiguous_type(span, &resolved, ttyp, name.suffix_reference_mut())
             {
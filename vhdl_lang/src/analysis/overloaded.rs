@@ -248,9 +248,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         for assoc in assocs.iter_mut() {
             match &mut assoc.actual.item {
                 ActualPart::Expression(expr) => {
+                    // The formal is not known yet, reads are checked with the chosen subprogram
+                    self.unread_actual.set(Some(assoc.actual.span));
                     let actual_type =
-                        self.expr_pos_type(scope, assoc.actual.span, expr, diagnostics)?;
-                    actual_types.push(Some(actual_type));
+                        self.expr_pos_type(scope, assoc.actual.span, expr, diagnostics);
+                    self.unread_actual.set(None);
+                    actual_types.push(Some(actual_type?));
                 }
                 ActualPart::Open => {
                     actual_types.push(None);
@@ -4,7 +4,7 @@
 //!
 //! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use vhdl_lang::SrcPos;
 
 use super::analyze::*;
@@ -30,14 +30,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         package_ent: EntRef<'a>,
         unit: &mut PackageInstantiation,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> EvalResult<Region<'a>> {
+    ) -> EvalResult<(Region<'a>, GenericActuals<'a>)> {
         let PackageInstantiation {
             package_name,
             generic_map,
             ..
         } = unit;
 
-        let package_region =
+        let (package, package_region) =
             self.analyze_package_instance_name(scope, package_name, diagnostics)?;
         self.generic_instance(
             package_ent,
@@ -47,19 +47,28 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             generic_map,
             diagnostics,
         )
-        .map(|(region, _)| region)
+        .map(|(region, types)| {
+            (
+                region,
+                GenericActuals {
+                    package: Some(package),
+                    types,
+                },
+            )
+        })
     }
 
     pub fn generic_map(
         &self,
         scope: &Scope<'a>,
+        decl_pos: &SrcPos,
         generics: GpkgRegion<'a>,
         generic_map: &mut [AssociationElement],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<FnvHashMap<EntityId, TypeEnt<'a>>> {
         let mut mapping = FnvHashMap::default();
+        let mut associated = FnvHashSet::default();
 
-        // @TODO check missing associations
         for (idx, assoc) in generic_map.iter_mut().enumerate() {
             let formal = if let Some(formal) = &mut assoc.formal {
                 let formal_pos = formal.pos(self.ctx);
@@ -92,6 +101,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 );
                 continue;
             };
+            associated.insert(formal.id());
 
             match &mut assoc.actual.item {
                 ActualPart::Expression(expr) => match formal {
@@ -213,9 +223,56 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                             ErrorCode::MismatchedKinds,
                         ),
                     },
-                    GpkgInterfaceEnt::Package(_) => match expr {
+                    GpkgInterfaceEnt::Package(iface) => match expr {
                         Expression::Name(name) => {
-                            self.name_resolve(scope, assoc.actual.span, name, diagnostics)?;
+                            let resolved =
+                                self.name_resolve(scope, assoc.actual.span, name, diagnostics)?;
+                            let actuals = match resolved {
+                                ResolvedName::Design(ref design) => match design.kind() {
+                                    Design::PackageInstance(_, actuals)
+                                    | Design::InterfacePackageInstance(_, actuals) => Some(actuals),
+                                    _ => None,
+                                },
+                                _ => None,
+                            };
+                            let Some(actuals) = actuals else {
+                                diagnostics.add(
+                                    assoc.actual.pos(self.ctx),
+                                    format!(
+                                        "Cannot map {} to package generic",
+                                        resolved.describe()
+                                    ),
+                                    ErrorCode::MismatchedKinds,
+                                );
+                                continue;
+                            };
+                            if let (
+                                AnyEntKind::Design(Design::InterfacePackageInstance(
+                                    _,
+                                    GenericActuals {
+                                        package: Some(expected),
+                                        ..
+                                    },
+                                )),
+                                Some(package),
+                            ) = (iface.kind(), actuals.package)
+                            {
+                                if *expected != package {
+                                    diagnostics.add(
+                                        assoc.actual.pos(self.ctx),
+                                        format!(
+                                            "Cannot map {} to package generic, expected an instance of {}",
+                                            resolved.describe(),
+                                            self.arena.get(*expected).describe()
+                                        ),
+                                        ErrorCode::MismatchedKinds,
+                                    );
+                                    continue;
+                                }
+                            }
+                            // Types of the interface package refer to the generics
+                            // of the uninstantiated package
+                            mapping.extend(actuals.types.iter().map(|(id, typ)| (*id, *typ)));
                         }
                         _ => diagnostics.add(
                             assoc.actual.pos(self.ctx),
@@ -229,6 +286,22 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 }
             }
         }
+
+        for generic in generics.iter() {
+            let has_default = match generic {
+                GpkgInterfaceEnt::Constant(obj) => obj.object().has_default,
+                // The default of an interface subprogram is not known here
+                GpkgInterfaceEnt::Subprogram(_) => true,
+                GpkgInterfaceEnt::Type(_) | GpkgInterfaceEnt::Package(_) => false,
+            };
+            if !has_default && !associated.contains(&generic.id()) {
+                diagnostics.add(
+                    decl_pos,
+                    format!("No association of {}", generic.describe()),
+                    ErrorCode::Unassociated,
+                );
+            }
+        }
         Ok(mapping)
     }
 
@@ -244,16 +317,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let nested = scope.nested().in_package_declaration();
         let (generics, other) = uninst_region.to_package_generic();
 
-        let mapping = if let Some(generic_map) = generic_map {
-            self.generic_map(
-                &nested,
-                generics,
-                generic_map.list.items.as_mut_slice(),
-                diagnostics,
-            )?
-        } else {
-            FnvHashMap::default()
+        let associations = match generic_map {
+            Some(generic_map) => generic_map.list.items.as_mut_slice(),
+            None => &mut [],
         };
+        let mapping = self.generic_map(&nested, decl_pos, generics, associations, diagnostics)?;
 
         for uninst in other {
             match self.instantiate(Some(ent), &mapping, uninst, &nested) {
@@ -373,12 +441,16 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
             AnyEntKind::Library => AnyEntKind::Library,
             AnyEntKind::Design(design) => match design {
-                Design::PackageInstance(region) => AnyEntKind::Design(Design::PackageInstance(
-                    self.map_region(parent, mapping, region, scope)?,
-                )),
-                Design::InterfacePackageInstance(region) => {
+                Design::PackageInstance(region, actuals) => {
+                    AnyEntKind::Design(Design::PackageInstance(
+                        self.map_region(parent, mapping, region, scope)?,
+                        self.map_actuals(mapping, actuals, scope),
+                    ))
+                }
+                Design::InterfacePackageInstance(region, actuals) => {
                     AnyEntKind::Design(Design::InterfacePackageInstance(
                         self.map_region(parent, mapping, region, scope)?,
+                        self.map_actuals(mapping, actuals, scope),
                     ))
                 }
                 _ => {
@@ -391,10 +463,28 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                     ));
                 }
             },
-            AnyEntKind::View(typ) => AnyEntKind::View(self.map_subtype(mapping, *typ, scope)),
+            AnyEntKind::View(typ, elements) => {
+                AnyEntKind::View(self.map_subtype(mapping, *typ, scope), elements.clone())
+            }
         })
     }
 
+    fn map_actuals(
+        &self,
+        mapping: &FnvHashMap<EntityId, TypeEnt<'a>>,
+        actuals: &GenericActuals<'a>,
+        scope: &Scope<'a>,
+    ) -> GenericActuals<'a> {
+        GenericActuals {
+            package: actuals.package,
+            types: actuals
+                .types
+                .iter()
+                .map(|(id, typ)| (*id, self.map_type_ent(mapping, *typ, scope)))
+                .collect(),
+        }
+    }
+
     fn map_overloaded(
         &self,
         parent: Option<EntRef<'a>>,
@@ -618,10 +708,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         subtype: Subtype<'a>,
         scope: &Scope<'a>,
     ) -> Subtype<'a> {
-        let Subtype { type_mark } = subtype;
+        let Subtype {
+            type_mark,
+            resolved,
+        } = subtype;
 
         Subtype {
             type_mark: self.map_type_ent(mapping, type_mark, scope),
+            resolved,
         }
     }
 }
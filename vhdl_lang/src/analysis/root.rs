@@ -6,6 +6,7 @@
 
 use super::analyze::*;
 use super::lock::*;
+use super::standard::is_vhdl2008_declaration;
 use super::standard::StandardTypes;
 use super::standard::UniversalTypes;
 use crate::named_entity::*;
@@ -14,7 +15,8 @@ use crate::ast::search::*;
 use crate::ast::*;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
-use crate::syntax::{Symbols, Token, TokenAccess};
+use crate::standard::VHDLStandard;
+use crate::syntax::{Kind, Symbols, Token, TokenAccess};
 use crate::{HasTokenSpan, TokenSpan};
 use fnv::{FnvHashMap, FnvHashSet};
 use parking_lot::RwLock;
@@ -291,6 +293,8 @@ impl Library {
 /// dependencies between design units.
 pub struct DesignRoot {
     pub(super) symbols: Arc<Symbols>,
+    /// The revision of the language, which decides the contents of the standard package
+    pub(super) standard: VHDLStandard,
     pub(super) standard_pkg_id: Option<EntityId>,
     pub(super) standard_arena: Option<FinalArena>,
     pub(super) universal: Option<UniversalTypes>,
@@ -315,8 +319,9 @@ pub struct DesignRoot {
 }
 
 impl DesignRoot {
-    pub fn new(symbols: Arc<Symbols>) -> DesignRoot {
+    pub fn new(symbols: Arc<Symbols>, standard: VHDLStandard) -> DesignRoot {
         DesignRoot {
+            standard,
             universal: None,
             standard_pkg_id: None,
             standard_arena: None,
@@ -331,6 +336,11 @@ impl DesignRoot {
         }
     }
 
+    /// The revision of the language that the design is analyzed with
+    pub(crate) fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
     /// Create library if it does not exist or return existing
     fn get_or_create_library(&mut self, name: Symbol) -> &mut Library {
         match self.libraries.entry(name) {
@@ -442,8 +452,44 @@ impl DesignRoot {
         Some(ent)
     }
 
+    /// Find the definition of the named entity referenced at the cursor.
+    ///
+    /// In contrast to [DesignRoot::find_definition_of], the instance of a component is resolved
+    /// to the unit that the instance is bound to, i.e., the unit named by a configuration
+    /// specification or else the entity with the same name in the library of the instantiating unit.
+    pub fn find_definition_at(&self, source: &Source, cursor: Position) -> Option<EntRef<'_>> {
+        let (pos, decl) = self.item_at_cursor(source, cursor)?;
+        if let (AnyEntKind::Component(_), Designator::Identifier(ident)) =
+            (decl.kind(), decl.designator())
+        {
+            for unit in self.units_by_source(source) {
+                let mut searcher = FindComponentBinding::new(decl.id(), &pos);
+                let _ = unit
+                    .unit
+                    .expect_analyzed()
+                    .search(&unit.tokens, &mut searcher);
+
+                if searcher.label.is_some() {
+                    if let Some(bound) = searcher.bound_unit() {
+                        return Some(self.get_ent(bound));
+                    }
+                    return Some(
+                        self.get_design_entity(unit.unit_id().library_name(), ident)
+                            .map(EntRef::from)
+                            .unwrap_or(decl),
+                    );
+                }
+            }
+        }
+        self.find_definition_of(decl)
+    }
+
     pub fn find_definition_of<'a>(&'a self, decl: EntRef<'a>) -> Option<EntRef<'a>> {
-        if decl.is_protected_type()
+        if let AnyEntKind::Overloaded(Overloaded::Alias(aliased)) = decl.kind() {
+            // An alias of a subprogram or an enumeration literal
+            // is defined by the named entity that it denotes
+            self.find_definition_of((*aliased).into())
+        } else if decl.is_protected_type()
             || decl.is_subprogram_decl()
             || decl.kind().is_deferred_constant()
         {
@@ -451,6 +497,16 @@ impl DesignRoot {
             let _ = self.search(&mut searcher);
 
             Some(searcher.result.unwrap_or(decl))
+        } else if let (AnyEntKind::Component(_), Designator::Identifier(ident), Some(library)) =
+            (decl.kind(), decl.designator(), decl.library_name())
+        {
+            // A component is defined by the entity that it is bound to by default,
+            // i.e., the entity with the same name in the same library
+            Some(
+                self.get_design_entity(library, ident)
+                    .map(EntRef::from)
+                    .unwrap_or(decl),
+            )
         } else {
             // The definition is the same as the declaration
             Some(decl)
@@ -462,10 +518,27 @@ impl DesignRoot {
             if let Some(library_name) = ent.library_name() {
                 match ent.kind() {
                     // Find entity with same name as component in the library
+                    // and the entities that configurations bind the component to
                     AnyEntKind::Component(_) => {
-                        if let Some(design) = self.get_design_entity(library_name, ident) {
-                            return vec![design.into()];
+                        let mut result: Vec<EntRef<'a>> = self
+                            .get_design_entity(library_name, ident)
+                            .map(EntRef::from)
+                            .into_iter()
+                            .collect();
+                        let mut searcher = FindComponentsByName::new(ent.designator());
+                        let _ = self.search_library(library_name, &mut searcher);
+                        for id in searcher.bound {
+                            if !result.iter().any(|design| design.id == id) {
+                                result.push(self.get_ent(id));
+                            }
                         }
+                        return result;
+                    }
+                    // Find the body of a package
+                    AnyEntKind::Design(Design::Package(..) | Design::UninstPackage(..)) => {
+                        let mut searcher = FindAllEnt::new(self, |body| body.is_declared_by(ent));
+                        let _ = self.search_library(library_name, &mut searcher);
+                        return searcher.result;
                     }
                     // Find components and architectures to entity
                     AnyEntKind::Design(Design::Entity(..)) => {
@@ -517,6 +590,18 @@ impl DesignRoot {
         }
     }
 
+    /// Returns what is shown when hovering over the name at the cursor, i.e.,
+    /// the kind and type of the named entity and its declaration.
+    pub fn hover(&self, source: &Source, cursor: Position) -> Option<HoverInfo<'_>> {
+        let ent = self.search_reference(source, cursor)?.declaration();
+        Some(HoverInfo {
+            ent,
+            kind: ent.kind().describe().to_owned(),
+            type_mark: type_of(ent).map(|typ| typ.designator().to_string()),
+            declaration: self.format_declaration(ent),
+        })
+    }
+
     /// Search for all references to the declaration at decl_pos
     pub fn find_all_references(&self, ent: EntRef<'_>) -> Vec<SrcPos> {
         let mut searcher = FindAllReferences::new(self, ent);
@@ -524,12 +609,214 @@ impl DesignRoot {
         searcher.references
     }
 
+    /// Find the references to a named entity that use it,
+    /// i.e., the references that are not declarations or end names
+    pub fn find_all_usages(&self, ent: EntRef<'_>) -> Vec<SrcPos> {
+        let mut searcher = FindAllUsages::new(self, ent);
+        let _ = self.search(&mut searcher);
+        let mut usages = searcher.usages;
+        usages.sort();
+        usages
+    }
+
+    /// Find the instantiations of an entity or a component.
+    /// An entity is also instantiated through the components that are bound to it.
+    pub fn find_instantiations(&self, ent: EntRef<'_>) -> Vec<SrcPos> {
+        let mut units = FnvHashSet::default();
+        units.insert(ent.id());
+        if let AnyEntKind::Design(Design::Entity(..)) = ent.kind() {
+            units.extend(
+                self.find_implementation(ent)
+                    .into_iter()
+                    .filter(|ent| matches!(ent.kind(), AnyEntKind::Component(_)))
+                    .map(|ent| ent.id()),
+            );
+        }
+        let mut searcher = FindInstantiations::new(units);
+        let _ = self.search(&mut searcher);
+        let mut instantiations = searcher.instantiations;
+        instantiations.sort();
+        instantiations
+    }
+
+    /// The named entities that are explicitly declared in a source
+    pub fn find_all_declarations_in_source(&self, source: &Source) -> Vec<EntRef<'_>> {
+        let mut searcher = FindAllEnt::new(self, |ent| {
+            ent.decl_pos().is_some_and(|pos| pos.source() == source)
+        });
+        let _ = self.search_source(source, &mut searcher);
+        let mut declarations = searcher.result;
+        declarations.sort_by_key(|ent| ent.decl_pos().map(|pos| pos.start()));
+        declarations
+    }
+
+    /// Find all references that change when renaming the named entity.
+    /// Besides the references to the named entity itself, renaming an entity
+    /// also renames the component declarations of its library that have the same name,
+    /// including their component configurations.
+    /// These components are bound to the entity by the default binding (LRM 7.3.3).
+    pub fn find_all_rename_references(&self, ent: EntRef<'_>) -> Vec<SrcPos> {
+        let mut references = self.find_all_references(ent);
+        if let AnyEntKind::Design(Design::Entity(..)) = ent.kind() {
+            if let Some(library_name) = ent.library_name() {
+                let mut searcher = FindComponentsByName::new(ent.designator());
+                let _ = self.search_library(library_name, &mut searcher);
+                for id in searcher.components {
+                    // The references of each component include the component configurations
+                    for pos in self.find_all_references(self.get_ent(id)) {
+                        if !references.contains(&pos) {
+                            references.push(pos);
+                        }
+                    }
+                }
+            }
+        }
+        references
+    }
+
+    /// Find the type that a subtype or an alias of a type is declared from.
+    /// The base type of a subtype is found by following the chain of supertypes.
+    pub fn find_supertypes<'a>(&'a self, typ: EntRef<'a>) -> Vec<EntRef<'a>> {
+        match typ.kind() {
+            AnyEntKind::Type(Type::Subtype(subtype)) => vec![subtype.type_mark().into()],
+            AnyEntKind::Type(Type::Alias(base)) => vec![(*base).into()],
+            _ => vec![],
+        }
+    }
+
+    /// Find the subtypes and aliases that are declared from a type
+    pub fn find_subtypes<'a>(&'a self, typ: EntRef<'a>) -> Vec<EntRef<'a>> {
+        let mut searcher = FindAllEnt::new(self, |ent| {
+            self.find_supertypes(ent)
+                .iter()
+                .any(|supertype| supertype.id() == typ.id())
+        });
+        let _ = self.search(&mut searcher);
+        searcher.result
+    }
+
+    /// Find the calls of a subprogram, grouped by the subprogram body,
+    /// process or design unit that they are called from.
+    pub fn find_incoming_calls<'a>(
+        &'a self,
+        subprogram: EntRef<'a>,
+    ) -> Vec<(EntRef<'a>, Vec<SrcPos>)> {
+        let mut searcher = FindAllCalls::new(self);
+        let _ = self.search(&mut searcher);
+        group_calls(
+            searcher
+                .calls
+                .into_iter()
+                .filter(|call| is_reference(subprogram, call.callee))
+                .map(|call| (call.caller, call.pos)),
+        )
+    }
+
+    /// Find the subprograms that are called from within a subprogram body,
+    /// process or design unit, grouped by the called subprogram.
+    pub fn find_outgoing_calls<'a>(&'a self, caller: EntRef<'a>) -> Vec<(EntRef<'a>, Vec<SrcPos>)> {
+        // The calls are made from the body of a subprogram
+        let Some(caller) = self.find_definition_of(caller) else {
+            return vec![];
+        };
+        let Some(decl_pos) = caller.decl_pos() else {
+            return vec![];
+        };
+        let mut searcher = FindAllCalls::new(self);
+        let _ = self.search_source(decl_pos.source(), &mut searcher);
+        group_calls(
+            searcher
+                .calls
+                .into_iter()
+                .filter(|call| call.caller.id() == caller.id())
+                .map(|call| (call.callee.declaration(), call.pos)),
+        )
+    }
+
+    /// Find all references to a named entity within a source, together with how the
+    /// named entity is referred to. The references to objects either read or write them.
+    pub fn find_all_references_in_source_with_kind(
+        &self,
+        source: &Source,
+        ent: EntRef<'_>,
+    ) -> Vec<(SrcPos, ReferenceKind)> {
+        let references = self.find_all_references_in_source(source, ent);
+        if !matches!(
+            ent.kind(),
+            AnyEntKind::Object(_)
+                | AnyEntKind::ObjectAlias { .. }
+                | AnyEntKind::ExternalAlias { .. }
+        ) {
+            return references
+                .into_iter()
+                .map(|pos| (pos, ReferenceKind::Text))
+                .collect();
+        }
+        let mut searcher = FindAssignmentTargets::default();
+        let _ = self.search_source(source, &mut searcher);
+        references
+            .into_iter()
+            .map(|pos| {
+                let kind = if Some(&pos) == ent.decl_pos() {
+                    ReferenceKind::Text
+                } else if searcher.positions.contains(&pos) {
+                    ReferenceKind::Write
+                } else {
+                    ReferenceKind::Read
+                };
+                (pos, kind)
+            })
+            .collect()
+    }
+
     pub fn find_all_references_in_source(&self, source: &Source, ent: EntRef<'_>) -> Vec<SrcPos> {
         let mut searcher = FindAllReferences::new(self, ent);
         let _ = self.search_source(source, &mut searcher);
         searcher.references
     }
 
+    /// All identifiers of a source that declare or refer to a named entity,
+    /// ordered by their position
+    pub fn find_all_identifiers_in_source(&self, source: &Source) -> Vec<(SrcPos, EntRef<'_>)> {
+        let mut searcher = FindAllIdentifiers::new(self);
+        let _ = self.search_source(source, &mut searcher);
+        let mut identifiers = searcher.identifiers;
+        identifiers.retain(|(pos, _)| &pos.source == source);
+        identifiers.sort_by_key(|(pos, _)| (pos.start(), pos.end()));
+        identifiers.dedup_by(|(a, _), (b, _)| a.range() == b.range());
+        identifiers
+    }
+
+    /// The spans of the constructs that contain the cursor,
+    /// from the outermost to the innermost construct
+    pub fn find_enclosing_spans(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        let mut searcher = FindEnclosingSpans::new(self, cursor);
+        let _ = self.search_source(source, &mut searcher);
+        let mut spans = searcher.spans;
+        // A construct that starts earlier or ends later encloses the other construct
+        spans.sort_by(|a, b| a.start().cmp(&b.start()).then(b.end().cmp(&a.end())));
+        let mut enclosing: Vec<SrcPos> = Vec::with_capacity(spans.len());
+        for span in spans {
+            // Adjacent constructs may both touch the cursor, keep only the nested ones
+            if enclosing.last().is_none_or(|outer| {
+                outer.range() != span.range()
+                    && outer.start() <= span.start()
+                    && span.end() <= outer.end()
+            }) {
+                enclosing.push(span);
+            }
+        }
+        enclosing
+    }
+
+    /// The name of the construct at the cursor and the name at its end,
+    /// which are edited together
+    pub fn find_linked_names(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        let mut searcher = FindLinkedNames::new(self, cursor);
+        let _ = self.search_source(source, &mut searcher);
+        searcher.names
+    }
+
     pub fn public_symbols<'a>(&'a self) -> Box<dyn Iterator<Item = EntRef<'a>> + 'a> {
         Box::new(self.libraries.values().flat_map(|library| {
             std::iter::once(self.arenas.get(library.id)).chain(library.units.values().flat_map(
@@ -696,7 +983,7 @@ impl DesignRoot {
         NotFound
     }
 
-    fn units_by_source<'a>(
+    pub(crate) fn units_by_source<'a>(
         &'a self,
         source: &'a Source,
     ) -> impl Iterator<Item = &'a LockedUnit> + 'a {
@@ -753,39 +1040,25 @@ impl DesignRoot {
         let context = AnalyzeContext::new(self, unit_id, source, &arena, ctx);
 
         let mut diagnostics = Vec::new();
-        let mut has_circular_dependency = false;
-
         let result = match unit.deref_mut() {
-            AnyDesignUnit::Primary(unit) => {
-                if let Err(err) = context.analyze_primary_unit(unit, &mut diagnostics) {
-                    has_circular_dependency = true;
-                    err.push_into(&mut diagnostics);
-                };
-
-                AnalysisData {
-                    arena: arena.finalize(),
-                    diagnostics,
-                    has_circular_dependency,
-                }
-            }
-
+            AnyDesignUnit::Primary(unit) => context.analyze_primary_unit(unit, &mut diagnostics),
             AnyDesignUnit::Secondary(unit) => {
-                let mut diagnostics = Vec::new();
-
-                if let Err(err) = context.analyze_secondary_unit(unit, &mut diagnostics) {
-                    has_circular_dependency = true;
-                    err.push_into(&mut diagnostics);
-                };
-
-                AnalysisData {
-                    arena: arena.finalize(),
-                    diagnostics,
-                    has_circular_dependency,
-                }
+                context.analyze_secondary_unit(unit, &mut diagnostics)
             }
         };
+        let has_circular_dependency = if let Err(err) = result {
+            err.push_into(&mut diagnostics);
+            true
+        } else {
+            false
+        };
+        arena.set_documentation(|ent| documentation_of(ctx, ent));
 
-        unit.finish(result);
+        unit.finish(AnalysisData {
+            arena: arena.finalize(),
+            diagnostics,
+            has_circular_dependency,
+        });
     }
 
     pub(super) fn get_analysis<'a>(&self, locked_unit: &'a LockedUnit) -> UnitReadGuard<'a> {
@@ -1035,6 +1308,7 @@ impl DesignRoot {
             &arena,
             standard_pkg,
             &mut std_package.decl,
+            self.standard,
         ));
 
         let context = AnalyzeContext::new(
@@ -1070,6 +1344,9 @@ impl DesignRoot {
         }
 
         for decl in std_package.decl.iter_mut() {
+            if self.standard < VHDLStandard::VHDL2008 && is_vhdl2008_declaration(&decl.item) {
+                continue;
+            }
             if let Declaration::Type(ref mut type_decl) = decl.item {
                 context
                     .analyze_type_declaration(
@@ -1245,6 +1522,47 @@ fn get_all_affected(
     all_affected
 }
 
+/// The information about a named entity that is shown when hovering over its name
+#[derive(Debug)]
+pub struct HoverInfo<'a> {
+    pub ent: EntRef<'a>,
+    /// The kind of the named entity, i.e., `signal` or `function`
+    pub kind: String,
+    /// The type of an object or the return type of a function
+    pub type_mark: Option<String>,
+    /// The pretty-printed declaration of the named entity
+    pub declaration: Option<String>,
+}
+
+/// How a named entity is referred to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// A reference that neither reads nor writes, such as the declaration
+    Text,
+    /// The value of an object is read
+    Read,
+    /// An object is the target of an assignment
+    Write,
+}
+
+/// Returns the type of a named entity that has one, i.e., the type of an object,
+/// the return type of a function or the type mark of a subtype.
+fn type_of(ent: EntRef<'_>) -> Option<TypeEnt<'_>> {
+    match ent.kind() {
+        AnyEntKind::Object(object) => Some(object.subtype.type_mark()),
+        AnyEntKind::ObjectAlias { type_mark, .. } => Some(*type_mark),
+        AnyEntKind::ElementDeclaration(subtype) | AnyEntKind::DeferredConstant(subtype) => {
+            Some(subtype.type_mark())
+        }
+        AnyEntKind::LoopParameter(typ) => typ.map(TypeEnt::from),
+        AnyEntKind::PhysicalLiteral(typ) => Some(*typ),
+        AnyEntKind::Overloaded(overloaded) => overloaded.signature().return_type(),
+        AnyEntKind::Type(Type::Subtype(subtype)) => Some(subtype.type_mark()),
+        AnyEntKind::Type(Type::Alias(typ)) => Some(*typ),
+        _ => None,
+    }
+}
+
 pub struct EntHierarchy<'a> {
     pub ent: EntRef<'a>,
     pub children: Vec<EntHierarchy<'a>>,
@@ -1312,6 +1630,85 @@ fn public_symbols<'a>(ent: EntRef<'a>) -> Box<dyn Iterator<Item = EntRef<'a>> +
     }
 }
 
+/// Groups the positions of calls by a named entity, in the order of the first call
+fn group_calls<'a>(
+    calls: impl Iterator<Item = (EntRef<'a>, SrcPos)>,
+) -> Vec<(EntRef<'a>, Vec<SrcPos>)> {
+    let mut result: Vec<(EntRef<'a>, Vec<SrcPos>)> = Vec::new();
+    for (ent, pos) in calls {
+        if let Some((_, positions)) = result.iter_mut().find(|(other, _)| other.id() == ent.id()) {
+            positions.push(pos);
+        } else {
+            result.push((ent, vec![pos]));
+        }
+    }
+    result
+}
+
+/// Returns the comments directly above the declaration of a named entity,
+/// without an empty line in between.
+fn documentation_of(ctx: &dyn TokenAccess, ent: &AnyEnt<'_>) -> Option<String> {
+    let decl_pos = ent.decl_pos()?;
+    if !ent.is_explicit() || ent.src_span.start_token > ent.src_span.end_token {
+        return None;
+    }
+    // Entities that are copied from another unit, e.g., into a package instance,
+    // refer to the tokens of that unit
+    ctx.get_token(ent.src_span.end_token)?;
+    let tokens = ctx.get_token_slice(ent.src_span.start_token, ent.src_span.end_token);
+    let mut start = tokens.iter().position(|token| &token.pos == decl_pos)?;
+    // The comments precede the first token of the declaration, e.g., `constant` in
+    // `constant foo : natural`, or the first identifier of an identifier list
+    while start > 0 && starts_declaration(tokens[start - 1].kind) {
+        start -= 1;
+    }
+    let comments = &tokens[start].comments.as_ref()?.leading;
+    let mut line = tokens[start].pos.start().line;
+    let mut lines = Vec::new();
+    for comment in comments.iter().rev() {
+        if comment.range.end.line + 1 < line {
+            break;
+        }
+        line = comment.range.start.line;
+        lines.push(comment.value.trim());
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+/// Tokens that may precede the designator of a declaration
+fn starts_declaration(kind: Kind) -> bool {
+    use Kind::*;
+    matches!(
+        kind,
+        Identifier
+            | Comma
+            | Signal
+            | Constant
+            | Variable
+            | Shared
+            | File
+            | Type
+            | Subtype
+            | Alias
+            | Attribute
+            | Component
+            | Function
+            | Procedure
+            | Pure
+            | Impure
+            | Entity
+            | Architecture
+            | Package
+            | Body
+            | Context
+            | Configuration
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1487,4 +1884,342 @@ end configuration;
         assert_eq!(root.entity_id_from_raw(ent.id.to_raw()), Some(ent.id));
         assert_eq!(root.entity_id_from_raw(0xFFFF << 32), None);
     }
+
+    #[test]
+    fn doc_comments_are_the_comments_directly_above_a_declaration() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  -- Not part of the documentation
+
+  -- The width of the bus
+  -- in bits
+  constant width : natural := 8;
+  constant depth : natural := 4; -- trailing comment
+  /* The first and the second value */
+  signal first, second : bit;
+end package;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let doc = |name: &str| {
+            let ent = root
+                .search_reference(code.source(), code.s1(&format!("{name} :")).start())
+                .unwrap();
+            ent.documentation().map(str::to_owned)
+        };
+
+        assert_eq!(
+            doc("width"),
+            Some("The width of the bus\nin bits".to_owned())
+        );
+        assert_eq!(doc("depth"), None);
+        assert_eq!(
+            doc("second"),
+            Some("The first and the second value".to_owned())
+        );
+    }
+
+    #[test]
+    fn references_to_objects_are_reads_or_writes() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : bit;
+  end record;
+  signal sig : bit_vector(1 downto 0);
+  signal rec : rec_t;
+begin
+  sig(0) <= sig(1);
+  rec.field <= '1';
+  process
+    variable var : bit;
+  begin
+    (var, sig(1)) := sig;
+    var := not var;
+    wait;
+  end process;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let references = |name: &str| {
+            let ent = root
+                .search_reference(code.source(), code.s1(&format!("{name} :")).start())
+                .unwrap();
+            root.find_all_references_in_source_with_kind(code.source(), ent)
+        };
+
+        assert_eq!(
+            references("sig"),
+            vec![
+                (code.s1("sig :").s1("sig").pos(), ReferenceKind::Text),
+                (code.s1("sig(0)").s1("sig").pos(), ReferenceKind::Write),
+                (code.s1("<= sig").s1("sig").pos(), ReferenceKind::Read),
+                (code.s1("sig(1))").s1("sig").pos(), ReferenceKind::Write),
+                (code.s1(":= sig").s1("sig").pos(), ReferenceKind::Read),
+            ]
+        );
+        assert_eq!(
+            references("var"),
+            vec![
+                (code.s1("var :").s1("var").pos(), ReferenceKind::Text),
+                (code.s1("(var").s1("var").pos(), ReferenceKind::Write),
+                (code.s1("var := not").s1("var").pos(), ReferenceKind::Write),
+                (code.s1("not var").s1("var").pos(), ReferenceKind::Read),
+            ]
+        );
+        assert_eq!(
+            references("field"),
+            vec![
+                (code.s("field", 1).pos(), ReferenceKind::Text),
+                (code.s("field", 2).pos(), ReferenceKind::Text),
+            ]
+        );
+    }
+
+    #[test]
+    fn identifiers_are_ordered_by_position() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (clk : in bit);
+end entity ent;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let identifiers: Vec<(SrcPos, String)> = root
+            .find_all_identifiers_in_source(code.source())
+            .into_iter()
+            .map(|(pos, ent)| (pos, ent.designator().to_string()))
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                (
+                    code.s1("entity ent").s1(" ent").s1("ent").pos(),
+                    "ent".to_owned()
+                ),
+                (code.s1("clk").pos(), "clk".to_owned()),
+                (code.s1("bit").pos(), "BIT".to_owned()),
+                (
+                    code.s1("entity ent;").s1(" ent").s1("ent").pos(),
+                    "ent".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_instantiations_and_usages() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  function f(x : natural) return natural;
+end package;
+
+package body pkg is
+  function f(x : natural) return natural is
+  begin
+    return x;
+  end function f;
+end package body;
+
+entity child is
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  component child is
+  end component;
+  constant c : natural := f(0);
+begin
+  inst0: entity work.child;
+  inst1: component child;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let child = root
+            .search_reference(code.source(), code.s1("entity child").s1("child").start())
+            .unwrap();
+        assert_eq!(
+            root.find_instantiations(child),
+            vec![code.s1("inst0").pos(), code.s1("inst1").pos()]
+        );
+
+        let pkg = root
+            .search_reference(code.source(), code.s1("pkg").start())
+            .unwrap();
+        assert_eq!(
+            root.find_all_usages(pkg),
+            vec![code.s1("work.pkg").s1("pkg").pos()]
+        );
+
+        let f = root
+            .search_reference(code.source(), code.s1("f(x").start())
+            .unwrap();
+        assert_eq!(root.find_all_usages(f), vec![code.s1("f(0)").s1("f").pos()]);
+
+        let declarations: Vec<String> = root
+            .find_all_declarations_in_source(code.source())
+            .into_iter()
+            .filter(|ent| {
+                matches!(
+                    ent.kind(),
+                    AnyEntKind::Design(Design::Entity(..) | Design::Package(..))
+                )
+            })
+            .map(|ent| ent.designator().to_string())
+            .collect();
+        assert_eq!(declarations, vec!["pkg", "child", "ent"]);
+    }
+
+    #[test]
+    fn finds_linked_names_of_constructs() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity ent;
+
+architecture a of ent is
+begin
+  proc: process
+  begin
+    lp: loop
+      exit lp;
+    end loop lp;
+  end process proc;
+end architecture a;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let source = code.source();
+        assert_eq!(
+            root.find_linked_names(source, code.s1("entity ent").s1(" ent").end()),
+            vec![
+                code.s1("entity ent").s1(" ent").s1("ent").pos(),
+                code.s1("entity ent;").s1(" ent").s1("ent").pos()
+            ]
+        );
+        assert_eq!(
+            root.find_linked_names(source, code.s1("architecture a;").s1(" a").end()),
+            vec![
+                code.s1("a of").s1("a").pos(),
+                code.s1("architecture a;").s1(" a").s1("a").pos()
+            ]
+        );
+        assert_eq!(
+            root.find_linked_names(source, code.s1("proc:").start()),
+            vec![
+                code.s1("proc").pos(),
+                code.s1("process proc").s1(" proc").s1("proc").pos()
+            ]
+        );
+        assert_eq!(
+            root.find_linked_names(source, code.s1("loop lp").s1("lp").start()),
+            vec![code.s1("lp").pos(), code.s1("loop lp").s1("lp").pos()]
+        );
+        assert_eq!(
+            root.find_linked_names(source, code.s1("exit lp").s1("lp").start()),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn enclosing_spans_are_ordered_from_outermost_to_innermost() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable x, y, z : natural;
+  begin
+    x := y + z;
+    wait;
+  end process;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let spans = root.find_enclosing_spans(code.source(), code.s1("+ z").s1("z").start());
+        assert_eq!(
+            spans,
+            vec![
+                code.between("architecture", "end architecture;").pos(),
+                code.between("process", "end process;").pos(),
+                code.s1("x := y + z;").pos(),
+                code.s1("y + z").pos(),
+                code.s1("+ z").s1("z").pos(),
+            ]
+        );
+    }
+
+    #[test]
+    fn hover_shows_the_kind_type_and_declaration() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  subtype byte_t is bit_vector(7 downto 0);
+  signal data : byte_t;
+  function parity(value : byte_t) return bit;
+end package;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let hover = root.hover(code.source(), code.s1("data").start()).unwrap();
+        assert_eq!(hover.kind, "signal");
+        assert_eq!(hover.type_mark.as_deref(), Some("byte_t"));
+        assert_eq!(hover.declaration.as_deref(), Some("signal data : byte_t;"));
+
+        let hover = root
+            .hover(code.source(), code.s1("parity").start())
+            .unwrap();
+        assert_eq!(hover.kind, "function");
+        assert_eq!(hover.type_mark.as_deref(), Some("BIT"));
+
+        let hover = root
+            .hover(code.source(), code.s1("byte_t").start())
+            .unwrap();
+        assert_eq!(hover.kind, "subtype");
+        assert_eq!(hover.type_mark.as_deref(), Some("BIT_VECTOR"));
+
+        assert!(root
+            .hover(code.source(), code.s1("package").start())
+            .is_none());
+    }
 }
@@ -44,6 +44,36 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Ok(())
     }
 
+    /// LRM 10.9: The expression of a matching case statement or a matching selected
+    /// assignment must be of type BIT or STD_ULOGIC or of a one-dimensional array of these
+    pub fn check_matching_selection_type(
+        &self,
+        ctyp: Option<TypeEnt<'a>>,
+        expression: &WithTokenSpan<Expression>,
+        statement: &str,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let Some(ctyp) = ctyp else {
+            return;
+        };
+        let is_matching_type = match ctyp.base_type().kind() {
+            Type::Array {
+                indexes, elem_type, ..
+            } => indexes.len() == 1 && self.has_matching_op(elem_type.base_type()),
+            _ => self.has_matching_op(ctyp.base_type()),
+        };
+        if !is_matching_type {
+            diagnostics.add(
+                expression.pos(self.ctx),
+                format!(
+                    "The expression of a {statement} must be of type BIT or STD_ULOGIC or a one-dimensional array of these, not {}",
+                    ctyp.describe()
+                ),
+                ErrorCode::TypeMismatch,
+            );
+        }
+    }
+
     pub fn analyze_assoc_elems(
         &self,
         scope: &Scope<'a>,
@@ -241,11 +241,20 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
             SequentialStatement::Case(ref mut case_stmt) => {
                 let CaseStatement {
+                    is_matching,
                     expression,
                     alternatives,
                     ..
                 } = case_stmt;
                 let ctyp = as_fatal(self.expr_unambiguous_type(scope, expression, diagnostics))?;
+                if *is_matching {
+                    self.check_matching_selection_type(
+                        ctyp,
+                        expression,
+                        "matching case statement",
+                        diagnostics,
+                    );
+                }
                 for alternative in alternatives.iter_mut() {
                     let Alternative {
                         choices,
@@ -9,7 +9,9 @@ use crate::ast::Designator;
 use crate::ast::Mode;
 use crate::ast::ObjectClass;
 use crate::ast::Operator;
+use crate::ast::SubprogramDesignator;
 use crate::data::DiagnosticHandler;
+use crate::standard::VHDLStandard;
 use crate::syntax::Symbols;
 use crate::HasTokenSpan;
 use vhdl_lang::ast::token_range::WithTokenSpan;
@@ -51,9 +53,32 @@ impl UniversalTypes {
     }
 }
 
+/// Returns true for the declarations of the standard package that were added in VHDL-2008
+/// and are not part of the standard package of earlier revisions
+pub(crate) fn is_vhdl2008_declaration(decl: &Declaration) -> bool {
+    let name = match decl {
+        Declaration::Type(type_decl) => type_decl.ident.tree.item.name(),
+        Declaration::SubprogramDeclaration(subpgm) => match &subpgm.subpgm_designator().item {
+            SubprogramDesignator::Identifier(symbol) => symbol.name(),
+            SubprogramDesignator::OperatorSymbol(_) => return false,
+        },
+        _ => return false,
+    };
+    matches!(
+        name.bytes.as_slice(),
+        b"BOOLEAN_VECTOR"
+            | b"INTEGER_VECTOR"
+            | b"REAL_VECTOR"
+            | b"TIME_VECTOR"
+            | b"RISING_EDGE"
+            | b"FALLING_EDGE"
+    )
+}
+
 pub(crate) struct StandardTypes {
     pub boolean: EntityId,
-    pub boolean_vector: EntityId,
+    /// BOOLEAN_VECTOR is only declared since VHDL-2008
+    pub boolean_vector: Option<EntityId>,
     pub bit: EntityId,
     pub bit_vector: EntityId,
     pub character: EntityId,
@@ -73,6 +98,7 @@ impl StandardTypes {
         arena: &'a Arena,
         standard_pkg: EntRef<'a>,
         decls: &mut [WithTokenSpan<Declaration>],
+        standard: VHDLStandard,
     ) -> Self {
         let mut boolean = None;
         let mut boolean_vector = None;
@@ -90,6 +116,9 @@ impl StandardTypes {
 
         // Reserve space in the arena for the standard types
         for decl in decls.iter_mut() {
+            if standard < VHDLStandard::VHDL2008 && is_vhdl2008_declaration(&decl.item) {
+                continue;
+            }
             if let Declaration::Type(ref mut type_decl) = decl.item {
                 let id = arena
                     .alloc(
@@ -153,7 +182,7 @@ impl StandardTypes {
 
         Self {
             boolean: boolean.unwrap(),
-            boolean_vector: boolean_vector.unwrap(),
+            boolean_vector,
             bit: bit.unwrap(),
             bit_vector: bit_vector.unwrap(),
             character: character.unwrap(),
@@ -174,6 +203,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         Designator::Identifier(self.root.symbol_utf8(name))
     }
 
+    /// Returns true when the analyzed revision predefines the declarations added in VHDL-2008
+    pub(crate) fn is_vhdl2008(&self) -> bool {
+        self.root.standard >= VHDLStandard::VHDL2008
+    }
+
     fn standard_types(&self) -> &StandardTypes {
         self.root.standard_types.as_ref().unwrap()
     }
@@ -186,8 +220,10 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         self.arena.get_type(self.standard_types().boolean)
     }
 
-    pub(crate) fn boolean_vector(&self) -> TypeEnt<'a> {
-        self.arena.get_type(self.standard_types().boolean_vector)
+    pub(crate) fn boolean_vector(&self) -> Option<TypeEnt<'a>> {
+        self.standard_types()
+            .boolean_vector
+            .map(|id| self.arena.get_type(id))
     }
 
     pub(crate) fn bit(&self) -> TypeEnt<'a> {
@@ -476,6 +512,41 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             implicit.push(ent.into());
         }
 
+        // procedure READ (file F: FT; VALUE: out TM; LENGTH: out NATURAL);
+        if matches!(type_mark.base().kind(), Type::Array { indexes, .. } if indexes.len() == 1) {
+            let ent = self.implicit_subpgm(
+                file_type,
+                self.ident("READ"),
+                [
+                    (self.ident("F"), AnyEntKind::InterfaceFile(file_type)),
+                    (
+                        self.ident("VALUE"),
+                        AnyEntKind::Object(Object {
+                            class: ObjectClass::Variable,
+                            iface: Some(ObjectInterface::Parameter(InterfaceMode::Simple(
+                                Mode::Out,
+                            ))),
+                            subtype: Subtype::new(type_mark),
+                            has_default: false,
+                        }),
+                    ),
+                    (
+                        self.ident("LENGTH"),
+                        AnyEntKind::Object(Object {
+                            class: ObjectClass::Variable,
+                            iface: Some(ObjectInterface::Parameter(InterfaceMode::Simple(
+                                Mode::Out,
+                            ))),
+                            subtype: Subtype::new(self.natural()),
+                            has_default: false,
+                        }),
+                    ),
+                ],
+                None,
+            );
+            implicit.push(ent.into());
+        }
+
         // procedure WRITE (file F: FT; VALUE: in TM);
         {
             let ent = self.implicit_subpgm(
@@ -494,7 +565,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         }
 
         // procedure FLUSH (file F: FT);
-        {
+        if self.is_vhdl2008() {
             let ent = self.implicit_subpgm(
                 file_type,
                 self.ident("FLUSH"),
@@ -584,38 +655,44 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     ) -> impl Iterator<Item = EntRef<'a>> {
         let integer = self.integer();
 
-        [
-            self.minimum(typ),
-            self.maximum(typ),
-            self.create_to_string(typ),
-            self.symmetric_unary(Operator::Minus, typ),
-            self.symmetric_unary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Minus, typ),
-            // 9.2.7 Multiplying operators
-            self.symmetric_binary(Operator::Times, typ),
-            self.symmetric_binary(Operator::Div, typ),
-            // 9.2.8 Miscellaneous operators
-            self.symmetric_unary(Operator::Abs, typ),
-            self.binary(Operator::Pow, typ, typ, integer, typ),
-        ]
-        .into_iter()
-        .chain(
-            if kind == UniversalType::Integer {
-                Some(
-                    [
-                        self.symmetric_binary(Operator::Mod, typ),
-                        self.symmetric_binary(Operator::Rem, typ),
-                    ]
-                    .into_iter(),
-                )
-            } else {
-                None
-            }
+        self.is_vhdl2008()
+            .then(|| {
+                [
+                    self.minimum(typ),
+                    self.maximum(typ),
+                    self.create_to_string(typ),
+                ]
+            })
             .into_iter()
-            .flatten(),
-        )
-        .chain(self.comparators(typ))
+            .flatten()
+            .chain([
+                self.symmetric_unary(Operator::Minus, typ),
+                self.symmetric_unary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Minus, typ),
+                // 9.2.7 Multiplying operators
+                self.symmetric_binary(Operator::Times, typ),
+                self.symmetric_binary(Operator::Div, typ),
+                // 9.2.8 Miscellaneous operators
+                self.symmetric_unary(Operator::Abs, typ),
+                self.binary(Operator::Pow, typ, typ, integer, typ),
+            ])
+            .chain(
+                if kind == UniversalType::Integer {
+                    Some(
+                        [
+                            self.symmetric_binary(Operator::Mod, typ),
+                            self.symmetric_binary(Operator::Rem, typ),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                }
+                .into_iter()
+                .flatten(),
+            )
+            .chain(self.comparators(typ))
     }
 
     pub fn universal_implicits(
@@ -623,67 +700,75 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         kind: UniversalType,
         typ: TypeEnt<'a>,
     ) -> impl Iterator<Item = EntRef<'a>> {
-        [
-            self.minimum(typ),
-            self.maximum(typ),
-            self.create_to_string(typ),
-            self.symmetric_unary(Operator::Minus, typ),
-            self.symmetric_unary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Minus, typ),
-            // 9.2.7 Multiplying operators
-            self.symmetric_binary(Operator::Times, typ),
-            self.symmetric_binary(Operator::Div, typ),
-            // 9.2.8 Miscellaneous operators
-            self.symmetric_unary(Operator::Abs, typ),
-            self.binary(
-                Operator::Pow,
-                typ,
-                typ,
-                self.universal_integer().into(),
-                typ,
-            ),
-        ]
-        .into_iter()
-        .chain(match kind {
-            UniversalType::Integer => itertools::Either::Left(
+        self.is_vhdl2008()
+            .then(|| {
                 [
-                    self.symmetric_binary(Operator::Mod, typ),
-                    self.symmetric_binary(Operator::Rem, typ),
+                    self.minimum(typ),
+                    self.maximum(typ),
+                    self.create_to_string(typ),
                 ]
-                .into_iter(),
-            ),
-            UniversalType::Real => {
-                // Universal real
-                itertools::Either::Right(
+            })
+            .into_iter()
+            .flatten()
+            .chain([
+                self.symmetric_unary(Operator::Minus, typ),
+                self.symmetric_unary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Minus, typ),
+                // 9.2.7 Multiplying operators
+                self.symmetric_binary(Operator::Times, typ),
+                self.symmetric_binary(Operator::Div, typ),
+                // 9.2.8 Miscellaneous operators
+                self.symmetric_unary(Operator::Abs, typ),
+                self.binary(
+                    Operator::Pow,
+                    typ,
+                    typ,
+                    self.universal_integer().into(),
+                    typ,
+                ),
+                // The exponent of the universal types may also be of type INTEGER
+                self.binary(Operator::Pow, typ, typ, self.integer(), typ),
+            ])
+            .chain(match kind {
+                UniversalType::Integer => itertools::Either::Left(
                     [
-                        self.binary(
-                            Operator::Times,
-                            typ,
-                            typ,
-                            self.universal_integer().into(),
-                            typ,
-                        ),
-                        self.binary(
-                            Operator::Times,
-                            typ,
-                            self.universal_integer().into(),
-                            typ,
-                            typ,
-                        ),
-                        self.binary(
-                            Operator::Div,
-                            typ,
-                            typ,
-                            self.universal_integer().into(),
-                            typ,
-                        ),
+                        self.symmetric_binary(Operator::Mod, typ),
+                        self.symmetric_binary(Operator::Rem, typ),
                     ]
                     .into_iter(),
-                )
-            }
-        })
-        .chain(self.comparators(typ))
+                ),
+                UniversalType::Real => {
+                    // Universal real
+                    itertools::Either::Right(
+                        [
+                            self.binary(
+                                Operator::Times,
+                                typ,
+                                typ,
+                                self.universal_integer().into(),
+                                typ,
+                            ),
+                            self.binary(
+                                Operator::Times,
+                                typ,
+                                self.universal_integer().into(),
+                                typ,
+                                typ,
+                            ),
+                            self.binary(
+                                Operator::Div,
+                                typ,
+                                typ,
+                                self.universal_integer().into(),
+                                typ,
+                            ),
+                        ]
+                        .into_iter(),
+                    )
+                }
+            })
+            .chain(self.comparators(typ))
     }
 
     pub fn physical_implicits(&self, typ: TypeEnt<'a>) -> impl Iterator<Item = EntRef<'a>> {
@@ -691,8 +776,6 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         let real = self.real();
 
         [
-            self.minimum(typ),
-            self.maximum(typ),
             self.symmetric_unary(Operator::Minus, typ),
             self.symmetric_unary(Operator::Plus, typ),
             self.symmetric_unary(Operator::Abs, typ),
@@ -712,10 +795,27 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                 typ,
                 self.universal_integer().into(),
             ),
-            self.symmetric_binary(Operator::Mod, typ),
-            self.symmetric_binary(Operator::Rem, typ),
         ]
         .into_iter()
+        .chain(
+            self.is_vhdl2008()
+                .then(|| {
+                    [
+                        self.minimum(typ),
+                        self.maximum(typ),
+                        // Physical types have MOD and REM since VHDL-2008
+                        self.symmetric_binary(Operator::Mod, typ),
+                        self.symmetric_binary(Operator::Rem, typ),
+                    ]
+                })
+                .into_iter()
+                .flatten(),
+        )
+        .chain(
+            // TO_STRING of TIME is declared at the end of package STANDARD after STRING
+            (self.is_vhdl2008() && typ.id() != self.standard_types().time)
+                .then(|| self.create_to_string(typ)),
+        )
         .chain(self.comparators(typ))
     }
 
@@ -724,32 +824,36 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         typ: TypeEnt<'a>,
         matching_op: bool,
     ) -> impl Iterator<Item = EntRef<'a>> {
-        [
-            self.create_to_string(typ),
-            self.minimum(typ),
-            self.maximum(typ),
-        ]
-        .into_iter()
-        .chain(self.comparators(typ))
-        .chain(
-            if matching_op {
-                Some(
-                    [
-                        self.symmetric_binary(Operator::QueEQ, typ),
-                        self.symmetric_binary(Operator::QueNE, typ),
-                        self.symmetric_binary(Operator::QueGT, typ),
-                        self.symmetric_binary(Operator::QueGTE, typ),
-                        self.symmetric_binary(Operator::QueLT, typ),
-                        self.symmetric_binary(Operator::QueLTE, typ),
-                    ]
-                    .into_iter(),
-                )
-            } else {
-                None
-            }
+        self.is_vhdl2008()
+            .then(|| {
+                [
+                    self.create_to_string(typ),
+                    self.minimum(typ),
+                    self.maximum(typ),
+                ]
+            })
             .into_iter()
-            .flatten(),
-        )
+            .flatten()
+            .chain(self.comparators(typ))
+            .chain(
+                if matching_op && self.is_vhdl2008() {
+                    Some(
+                        [
+                            self.symmetric_binary(Operator::QueEQ, typ),
+                            self.symmetric_binary(Operator::QueNE, typ),
+                            self.symmetric_binary(Operator::QueGT, typ),
+                            self.symmetric_binary(Operator::QueGTE, typ),
+                            self.symmetric_binary(Operator::QueLT, typ),
+                            self.symmetric_binary(Operator::QueLTE, typ),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                }
+                .into_iter()
+                .flatten(),
+            )
     }
 
     pub fn record_implicits(&self, typ: TypeEnt<'a>) -> impl Iterator<Item = EntRef<'a>> {
@@ -810,6 +914,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         );
 
         let is_one_dimensional = indexes.len() == 1;
+        let is_bit_or_boolean_elem = {
+            let standard_types = self.standard_types();
+            let elem_id = elem_type.base().id();
+            elem_id == standard_types.bit || elem_id == standard_types.boolean
+        };
         let is_character_elem = matches!(elem_type.base().kind(), Type::Enum(designators) if designators.iter().all(|des| matches!(des, Designator::Character(_))));
 
         [
@@ -817,12 +926,14 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             self.comparison(Operator::NE, typ),
         ]
         .into_iter()
-        .chain(if is_one_dimensional && is_character_elem {
-            // To string is only defined for 1d array types with character elements
-            Some(self.create_to_string(typ)).into_iter()
-        } else {
-            None.into_iter()
-        })
+        .chain(
+            if is_one_dimensional && is_character_elem && self.is_vhdl2008() {
+                // To string is only defined for 1d array types with character elements
+                Some(self.create_to_string(typ)).into_iter()
+            } else {
+                None.into_iter()
+            },
+        )
         .chain(
             (if is_one_dimensional {
                 Some(self.concatenations(typ, *elem_type))
@@ -832,6 +943,27 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             .into_iter()
             .flatten(),
         )
+        .chain(
+            // A op INTEGER -> A
+            (if is_one_dimensional && is_bit_or_boolean_elem {
+                Some(
+                    [
+                        Operator::SLL,
+                        Operator::SRL,
+                        Operator::SLA,
+                        Operator::SRA,
+                        Operator::ROL,
+                        Operator::ROR,
+                    ]
+                    .map(|op| self.binary(op, typ, typ, self.integer(), typ))
+                    .into_iter(),
+                )
+            } else {
+                None
+            })
+            .into_iter()
+            .flatten(),
+        )
         .chain(
             (if is_scalar {
                 Some(
@@ -840,6 +972,19 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                         self.comparison(Operator::GTE, typ),
                         self.comparison(Operator::LT, typ),
                         self.comparison(Operator::LTE, typ),
+                    ]
+                    .into_iter(),
+                )
+            } else {
+                None
+            })
+            .into_iter()
+            .flatten(),
+        )
+        .chain(
+            (if is_scalar && self.is_vhdl2008() {
+                Some(
+                    [
                         self.elementwise_min_or_maximum("MINIMUM", typ, *elem_type),
                         self.elementwise_min_or_maximum("MAXIMUM", typ, *elem_type),
                     ]
@@ -852,15 +997,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             .flatten(),
         )
         .chain(
-            if matching_op {
+            // LRM 9.2.3: Only the matching equality operators are defined for arrays
+            if matching_op && self.is_vhdl2008() {
                 Some(
                     [
                         self.binary(Operator::QueEQ, typ, typ, typ, *elem_type),
                         self.binary(Operator::QueNE, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueGT, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueGTE, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueLT, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueLTE, typ, typ, typ, *elem_type),
                     ]
                     .into_iter(),
                 )
@@ -887,7 +1029,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
         region: &mut Region<'a>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) {
-        {
+        if self.is_vhdl2008() {
             let time = self.time();
             let to_string = self.create_to_string(time);
 
@@ -917,10 +1059,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
         }
 
-        for (styp, atyp) in [
-            (self.boolean(), self.boolean_vector()),
-            (self.bit(), self.bit_vector()),
-        ] {
+        let vectors = [
+            self.boolean_vector().map(|atyp| (self.boolean(), atyp)),
+            Some((self.bit(), self.bit_vector())),
+        ];
+        for (styp, atyp) in vectors.into_iter().flatten() {
             let ops = [
                 Operator::And,
                 Operator::Or,
@@ -933,22 +1076,23 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
 
             let implicits = ops.iter().flat_map(|op| {
                 let op = *op;
-                [
-                    // A op A -> A
-                    self.symmetric_binary(op, atyp),
-                    if op == Operator::Not {
-                        // op A -> A
-                        self.unary(op, atyp, atyp)
-                    } else {
-                        // op A -> S
-                        self.unary(op, atyp, styp)
-                    },
+                // A op A -> A
+                let mut implicits = vec![self.symmetric_binary(op, atyp)];
+                if op == Operator::Not {
+                    // op A -> A
+                    implicits.push(self.unary(op, atyp, atyp));
+                } else if self.is_vhdl2008() {
+                    // op A -> S
+                    implicits.push(self.unary(op, atyp, styp));
+                }
+                // The operators with a scalar operand are only predefined since VHDL-2008
+                if self.is_vhdl2008() {
                     // A op S -> A
-                    self.binary(op, atyp, atyp, styp, atyp),
+                    implicits.push(self.binary(op, atyp, atyp, styp, atyp));
                     // S op A -> A
-                    self.binary(op, atyp, styp, atyp, atyp),
-                ]
-                .into_iter()
+                    implicits.push(self.binary(op, atyp, styp, atyp, atyp));
+                }
+                implicits
             });
 
             for ent in implicits {
@@ -960,6 +1104,11 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             }
         }
 
+        // The remaining declarations are only predefined since VHDL-2008
+        if !self.is_vhdl2008() {
+            return;
+        }
+
         // Predefined overloaded TO_STRING operations
         // function TO_STRING (VALUE: REAL; DIGITS: NATURAL) return STRING;
         {
@@ -47,13 +47,22 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             ErrorCode::MismatchedKinds,
             diagnostics,
         )?;
-        if !object_name.base.can_be_assigned_to() {
-            diagnostics.add(
-                target_pos.pos(self.ctx),
-                format!(
-                    "{} may not be the target of an assignment",
+        if !object_name.base.can_be_assigned_to(target) {
+            let described = match (
+                object_name.base.mode(),
+                &*target,
+                object_name.base.mode_of(target),
+            ) {
+                (Some(InterfaceMode::View(_)), Name::Selected(_, suffix), Some(mode)) => format!(
+                    "element '{}' of mode {mode} of {}",
+                    suffix.item.item,
                     object_name.base.describe_class()
                 ),
+                _ => object_name.base.describe_class(),
+            };
+            diagnostics.add(
+                target_pos.pos(self.ctx),
+                format!("{described} may not be the target of an assignment"),
                 ErrorCode::MismatchedKinds,
             );
         } else if !object_name.base.is_valid_assignment_type(assignment_type) {
@@ -6,6 +6,7 @@
 
 use super::*;
 use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
 
 #[test]
 fn overloaded_name_may_not_be_assignment_target() {
@@ -680,3 +681,69 @@ end package body;
     );
     check_no_diagnostics(&builder.analyze())
 }
+
+#[test]
+fn out_ports_may_not_be_read_before_vhdl2008() {
+    let source = "
+entity sub is
+  port (d : in bit; q : out bit);
+end entity;
+
+architecture a of sub is
+begin
+end architecture;
+
+entity ent is
+  port (d : in bit; q : out bit_vector(1 downto 0));
+end entity;
+
+architecture a of ent is
+  signal s : bit;
+
+  procedure proc(signal o : out bit) is
+  begin
+    o <= '1';
+  end procedure;
+
+  procedure proc(signal o : out bit_vector) is
+  begin
+    o <= (others => '1');
+  end procedure;
+begin
+  q(0) <= d;
+  s <= q(1);
+  inst0: entity work.sub port map (d => d, q => q(1));
+  inst1: entity work.sub port map (d => q(0), q => open);
+
+  main: process (d)
+  begin
+    proc(q);
+    if q'length = 2 then
+      proc(q(0));
+    end if;
+  end process;
+end architecture;
+";
+
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+    let code = builder.code("libname", source);
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("s <= q(1)").s1("q(1)"),
+                "interface signal 'q' of mode out may not be read before VHDL-2008",
+                ErrorCode::IncompatibleStandard,
+            ),
+            Diagnostic::new(
+                code.s1("d => q(0)").s1("q(0)"),
+                "interface signal 'q' of mode out may not be read before VHDL-2008",
+                ErrorCode::IncompatibleStandard,
+            ),
+        ],
+    );
+
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2008);
+    builder.code("libname", source);
+    check_no_diagnostics(&builder.analyze());
+}
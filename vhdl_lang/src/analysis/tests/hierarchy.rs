@@ -152,14 +152,14 @@ package ipkg is new work.pkg generic map(type_t => integer, value => 0);
         .search_reference(code.source(), code.s1("ipkg").start())
         .unwrap();
 
-    let instances: Vec<_> = if let AnyEntKind::Design(Design::PackageInstance(region)) = ipkg.kind()
-    {
-        let mut symbols: Vec<_> = region.immediates().collect();
-        symbols.sort_by_key(|ent| ent.decl_pos());
-        symbols.into_iter().map(|ent| ent.path_name()).collect()
-    } else {
-        panic!("Expected instantiated package");
-    };
+    let instances: Vec<_> =
+        if let AnyEntKind::Design(Design::PackageInstance(region, _)) = ipkg.kind() {
+            let mut symbols: Vec<_> = region.immediates().collect();
+            symbols.sort_by_key(|ent| ent.decl_pos());
+            symbols.into_iter().map(|ent| ent.path_name()).collect()
+        } else {
+            panic!("Expected instantiated package");
+        };
 
     assert_eq!(instances, vec!["libname.ipkg.c0", "libname.ipkg.fun0"]);
 }
@@ -326,6 +326,75 @@ end architecture;
     assert_eq!(root.find_implementation(comp), vec![ent]);
 }
 
+#[test]
+fn find_implementation_of_configured_component_and_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent0 is
+end entity;
+
+architecture a of ent0 is
+begin
+end architecture;
+
+entity other is
+end entity;
+
+architecture a of other is
+begin
+end architecture;
+
+entity ent1 is
+end entity;
+
+architecture a of ent1 is
+  component ent0 is
+  end component;
+begin
+  inst: ent0;
+end architecture;
+
+configuration cfg of ent1 is
+  for a
+    for inst : ent0
+      use entity work.other;
+    end for;
+  end for;
+end configuration;
+
+package pkg is
+end package;
+
+package body pkg is
+end package body;
+      ",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent0 = root
+        .search_reference(code.source(), code.s1("ent0").start())
+        .unwrap();
+    let other = root
+        .search_reference(code.source(), code.s1("other").start())
+        .unwrap();
+    let comp = root
+        .search_reference(code.source(), code.sa("component ", "ent0").start())
+        .unwrap();
+    let pkg = root
+        .search_reference(code.source(), code.s1("pkg").start())
+        .unwrap();
+    let body = root
+        .search_reference(code.source(), code.s("pkg", 2).start())
+        .unwrap();
+
+    assert_eq!(root.find_implementation(comp), vec![ent0, other]);
+    assert_eq!(root.find_implementation(pkg), vec![body]);
+}
+
 #[test]
 fn exit_and_next_outside_of_loop() {
     let mut builder = LibraryBuilder::new();
@@ -424,3 +493,159 @@ end architecture;
         ],
     );
 }
+
+#[test]
+fn find_incoming_and_outgoing_calls() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function incr(value : natural) return natural;
+  procedure count(signal cnt : inout natural);
+end package;
+
+package body pkg is
+  function incr(value : natural) return natural is
+  begin
+    return value + 1;
+  end function;
+
+  procedure count(signal cnt : inout natural) is
+  begin
+    cnt <= incr(incr(cnt));
+  end procedure;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal cnt : natural := incr(0);
+begin
+  main : process
+  begin
+    count(cnt);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent_at = |code: Code| root.search_reference(code.source(), code.start()).unwrap();
+    let incr = ent_at(code.s1("incr"));
+    let count = ent_at(code.s1("count"));
+    let count_body = ent_at(code.s("count", 2));
+    let main = ent_at(code.s1("main"));
+    let arch = ent_at(code.s1("a of"));
+
+    let incoming = root.find_incoming_calls(incr);
+    assert_eq!(
+        incoming,
+        vec![
+            (
+                count_body,
+                vec![code.s("incr", 3).pos(), code.s("incr", 4).pos()]
+            ),
+            (arch, vec![code.s("incr", 5).pos()]),
+        ]
+    );
+    assert_eq!(
+        root.find_incoming_calls(count),
+        vec![(main, vec![code.s("count", 3).pos()])]
+    );
+
+    // The outgoing calls of a subprogram are the calls from within its body
+    assert_eq!(
+        root.find_outgoing_calls(count),
+        vec![(incr, vec![code.s("incr", 3).pos(), code.s("incr", 4).pos()])]
+    );
+    assert_eq!(
+        root.find_outgoing_calls(main),
+        vec![(count, vec![code.s("count", 3).pos()])]
+    );
+}
+
+#[test]
+fn find_incoming_calls_through_alias() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function incr(value : natural) return natural;
+  alias increment is incr [natural return natural];
+end package;
+
+package body pkg is
+  function incr(value : natural) return natural is
+  begin
+    return value + 1;
+  end function;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal cnt : natural := increment(0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent_at = |code: Code| root.search_reference(code.source(), code.start()).unwrap();
+    let incr = ent_at(code.s1("incr"));
+    let pkg = ent_at(code.s1("pkg"));
+    let arch = ent_at(code.s1("a of"));
+
+    assert_eq!(
+        root.find_incoming_calls(incr),
+        vec![
+            (pkg, vec![code.s1("is incr").s1("incr").pos()]),
+            (arch, vec![code.s1("increment(0)").s1("increment").pos()]),
+        ]
+    );
+}
+
+#[test]
+fn find_supertypes_and_subtypes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type word_t is array (natural range <>) of bit;
+  subtype byte_t is word_t(7 downto 0);
+  subtype low_t is byte_t;
+  alias word_alias_t is word_t;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent_at = |code: Code| root.search_reference(code.source(), code.start()).unwrap();
+    let word = ent_at(code.s1("word_t"));
+    let byte = ent_at(code.s1("byte_t"));
+    let low = ent_at(code.s1("low_t"));
+    let word_alias = ent_at(code.s1("word_alias_t"));
+
+    assert_eq!(root.find_supertypes(low), vec![byte]);
+    assert_eq!(root.find_supertypes(byte), vec![word]);
+    assert!(root.find_supertypes(word).is_empty());
+
+    assert_eq!(root.find_subtypes(word), vec![byte, word_alias]);
+    assert_eq!(root.find_subtypes(byte), vec![low]);
+}
@@ -175,7 +175,13 @@ end package;
     );
 
     let diagnostics = builder.analyze();
-    check_diagnostics(diagnostics, duplicates(&code, &["a1", "b1"]));
+    let mut expected = duplicates(&code, &["a1", "b1"]);
+    expected.push(Diagnostic::new(
+        code.s1("proc(").s1("proc"),
+        "Missing body for procedure proc[NATURAL, NATURAL, NATURAL] of protected type 'prot_t'",
+        ErrorCode::MissingSubprogramBody,
+    ));
+    check_diagnostics(diagnostics, expected);
 }
 
 #[test]
@@ -1,5 +1,39 @@
 use super::*;
 use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
+
+#[test]
+fn vhdl2008_declarations_are_not_predefined_in_vhdl1993() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  alias alias1 is to_string[integer return string];
+  alias alias2 is minimum[integer, integer return integer];
+  constant c0 : boolean_vector(0 to 1) := (others => false);
+  constant c1 : bit_vector(0 to 1) := not \"01\" and \"11\";
+  constant c2 : bit_vector(0 to 1) := \"01\" and '1';
+  constant c3 : bit_vector(0 to 1) := \"01\" sll 1;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing(&code, "to_string", 1),
+            missing(&code, "minimum", 1),
+            missing(&code, "boolean_vector", 1),
+            Diagnostic::new(
+                code.s("and", 2),
+                "Found no match for operator \"and\"",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
 
 #[test]
 fn adds_to_string_for_standard_types() {
@@ -47,6 +81,66 @@ end package body;
     );
 }
 
+// procedure READ (file F: FT; VALUE: out TM; LENGTH: out NATURAL);
+#[test]
+fn adds_read_with_length_for_array_file_types() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+end package;
+
+package body pkg is
+  type text_file_t is file of string;
+
+  procedure proc is
+    file f : text_file_t;
+    variable line : string(1 to 80);
+    variable len : natural;
+  begin
+    file_open(f, \"foo.txt\");
+    read(f, line, len);
+    read(f, value => line, length => len);
+    read(f, line);
+    file_close(f);
+  end procedure;
+end package body;
+",
+    );
+}
+
+#[test]
+fn read_with_length_is_not_defined_for_scalar_file_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  type binary_file_t is file of character;
+
+  procedure proc is
+    file f : binary_file_t;
+    variable char : character;
+    variable len : natural;
+  begin
+    read(f, char, len);
+  end procedure;
+end package body;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("char, len").s1("len"),
+            "Unexpected extra argument",
+            ErrorCode::TooManyArguments,
+        )],
+    );
+}
+
 #[test]
 fn adds_to_string_for_integer_types() {
     check_code_with_no_diagnostics(
@@ -216,7 +310,41 @@ end package;
         ),
         Some(
             "\
--- function TO_STRING[enum_t return STRING]
+-- function TO_STRING (VALUE: enum_t) return STRING;
+
+-- Implicitly defined by:
+type enum_t is (alpha, beta);
+"
+            .to_owned()
+        )
+    );
+}
+
+#[test]
+fn hover_for_implicit_operator() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+type enum_t is (alpha, beta);
+alias eq is \"=\"[enum_t, enum_t return boolean];
+alias neg is \"not\"[boolean return boolean];
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let declaration = |code: Code| {
+        root.format_declaration(root.search_reference(code.source(), code.start()).unwrap())
+    };
+    assert_eq!(
+        declaration(code.s1("\"=\"")),
+        Some(
+            "\
+-- function \"=\" (anonymous, anonymous: enum_t) return BOOLEAN;
 
 -- Implicitly defined by:
 type enum_t is (alpha, beta);
@@ -224,6 +352,29 @@ type enum_t is (alpha, beta);
             .to_owned()
         )
     );
+    assert!(declaration(code.s1("\"not\""))
+        .unwrap()
+        .starts_with("-- function \"not\" (anonymous: BOOLEAN) return BOOLEAN;"));
+}
+
+#[test]
+fn named_association_is_not_allowed_for_implicit_operators() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+type enum_t is (alpha, beta);
+constant good : boolean := \"=\"(alpha, beta);
+constant bad : boolean := \"=\"(L => alpha, R => beta);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].pos, code.s("\"=\"", 2).pos());
+    assert_eq!(diagnostics[0].code, ErrorCode::AmbiguousCall);
 }
 
 #[test]
@@ -245,9 +396,234 @@ package pkg is
     constant good5 : time_t := c0 - c0;
     constant good6 : time_t := minimum(c0, c0);
     constant good7 : time_t := maximum(c0, c0);
+    constant good8 : string := to_string(c0);
+end package;
+",
+    );
+}
+
+#[test]
+fn multiplying_operators_on_physical_types() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+    constant clk_div : integer := 4;
+    constant factor : real := 1.5;
+    constant period : time := 10 ns * clk_div;
+
+    constant good1 : time := clk_div * 10 ns;
+    constant good2 : time := 10 ns * factor;
+    constant good3 : time := factor * 10 ns;
+    constant good4 : time := period / clk_div;
+    constant good5 : time := period / factor;
+    constant good6 : integer := period / 1 ns;
+    constant good7 : real := real(period / 1 ps) / 1000.0;
+    constant good8 : time := 10 ns * (clk_div + 1);
+    constant good9 : time := 1 sec / 100;
+end package;
+",
+    );
+}
+
+#[test]
+fn physical_types_are_multiplied_by_integer_and_real_only() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    type int_t is range 0 to 10;
+    constant t0 : time := 10 ns;
+    constant bad1 : time := t0 * int_t'(3);
+    constant bad2 : time := t0 * t0;
+end package;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("t0 * int_t").s1("*"),
+                "Found no match for operator \"*\"",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("t0 * t0").s1("*"),
+                "Found no match for operator \"*\"",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn multiplying_operators_and_exponentiation_on_numeric_types() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+    type int_t is range 0 to 100;
+    type real_t is range 0.0 to 100.0;
+
+    constant n : integer := 3;
+    constant i0 : int_t := 3;
+    constant r0 : real_t := 2.0;
+
+    constant good1 : int_t := i0 * i0;
+    constant good2 : int_t := i0 / i0;
+    constant good3 : int_t := i0 mod 2;
+    constant good4 : int_t := i0 rem 2;
+    constant good5 : int_t := i0 ** n;
+    constant good6 : int_t := i0 ** 2;
+    constant good7 : real_t := r0 * r0;
+    constant good8 : real_t := r0 / 2.0;
+    constant good9 : real_t := r0 ** n;
+    constant good10 : real := 1.5 * 2;
+    constant good11 : real := 3.0 / 2;
+    constant good12 : integer := 2 ** n;
+    constant good13 : integer := integer(2.0 ** n);
+    constant good14 : real := 2.0 ** n * 2;
+end package;
+",
+    );
+}
+
+#[test]
+fn numeric_operators_do_not_mix_integer_and_real_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant n : integer := 3;
+    constant bad1 : real := 2.0 mod 1.0;
+    constant bad2 : real := 2.0 ** 1.0;
+    constant bad3 : integer := n * 2.0;
+end package;
+",
+    );
+
+    let no_match = |op: &str, occurence: usize| {
+        Diagnostic::new(
+            code.s(op, occurence),
+            format!("Found no match for operator \"{op}\""),
+            ErrorCode::Unresolved,
+        )
+    };
+    check_diagnostics(
+        builder.analyze(),
+        vec![no_match("mod", 1), no_match("**", 1), no_match("*", 3)],
+    );
+}
+
+#[test]
+fn shift_operators_on_bit_and_boolean_vectors() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+    constant n : natural := 2;
+    constant bv : bit_vector(0 to 7) := x\"0F\";
+    constant bo : boolean_vector(0 to 1) := (true, false);
+
+    constant good1 : bit_vector(0 to 7) := bv sll 1;
+    constant good2 : bit_vector(0 to 7) := bv srl n;
+    constant good3 : bit_vector(0 to 7) := bv sla -1;
+    constant good4 : bit_vector(0 to 7) := bv sra 1;
+    constant good5 : bit_vector(0 to 7) := bv rol n + 1;
+    constant good6 : bit_vector(0 to 7) := (bv ror 1) and bv;
+    constant good7 : boolean_vector(0 to 1) := bo sll 1;
+    constant good8 : boolean_vector(0 to 1) := bo ror n;
+end package;
+",
+    );
+}
+
+#[test]
+fn shift_operators_on_user_declared_arrays_of_bit_and_boolean() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+    type my_bv is array (natural range <>) of bit;
+    type my_flags is array (0 to 3) of boolean;
+    subtype my_bit is bit;
+    type my_sub_bv is array (natural range <>) of my_bit;
+
+    constant x : my_bv(0 to 3) := \"0101\";
+    constant y : my_flags := (others => true);
+    constant z : my_sub_bv(0 to 3) := \"0101\";
+
+    constant good1 : my_bv(0 to 3) := x sll 1;
+    constant good2 : my_bv(0 to 3) := x ror 2;
+    constant good3 : my_flags := y srl 1;
+    constant good4 : my_sub_bv(0 to 3) := z sra 1;
+end package;
+",
+    );
+}
+
+#[test]
+fn shift_operators_are_not_defined_for_other_arrays() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    type int_vec is array (natural range <>) of integer;
+    type bit_matrix is array (natural range <>, natural range <>) of bit;
+    constant v : int_vec(0 to 1) := (1, 2);
+    constant m : bit_matrix(0 to 1, 0 to 1) := (others => (others => '0'));
+    constant bad1 : int_vec(0 to 1) := v sll 1;
+    constant bad2 : bit_matrix(0 to 1, 0 to 1) := m rol 1;
+end package;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("sll"),
+                "Found no match for operator \"sll\"",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("rol"),
+                "Found no match for operator \"rol\"",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn shift_operators_require_a_vector_and_an_integer() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    constant bv : bit_vector(0 to 7) := x\"0F\";
+    constant bad1 : bit_vector(0 to 7) := bv sll bv;
+    constant bad2 : integer := 1 rol 1;
 end package;
 ",
     );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("sll"),
+                "Found no match for operator \"sll\"",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("rol"),
+                "Found no match for operator \"rol\"",
+                ErrorCode::Unresolved,
+            ),
+        ],
+    );
 }
 
 #[test]
@@ -9,6 +9,7 @@ use crate::analysis::DesignRoot;
 use crate::ast::search::*;
 use crate::data::SrcPos;
 use crate::named_entity::{EntityId, Reference};
+use crate::standard::VHDLStandard;
 use crate::syntax::TokenAccess;
 use fnv::FnvHashSet;
 use pretty_assertions::assert_eq;
@@ -158,7 +159,7 @@ end architecture;
         "libname",
         "
 configuration cfg of ent is
-for rtl
+for a
 end for;
 end configuration;
 ",
@@ -264,10 +265,10 @@ fn check_incremental_analysis(builder: LibraryBuilder, expected_diagnostics: Vec
 
     // Generate all combinations of removing and adding source
     for i in 0..codes.len() {
-        let mut fresh_root = DesignRoot::new(symbols.clone());
+        let mut fresh_root = DesignRoot::new(symbols.clone(), VHDLStandard::default());
         add_standard_library(symbols.clone(), &mut fresh_root);
 
-        let mut root = DesignRoot::new(symbols.clone());
+        let mut root = DesignRoot::new(symbols.clone(), VHDLStandard::default());
         add_standard_library(symbols.clone(), &mut root);
 
         for (j, (library_name, code)) in codes.iter().enumerate() {
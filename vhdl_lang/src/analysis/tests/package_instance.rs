@@ -119,6 +119,11 @@ package ipkg3 is new work.gpkg
                 "No declaration of 'missing'",
                 ErrorCode::Unresolved,
             ),
+            Diagnostic::new(
+                code.s1("ipkg1"),
+                "No association of type 'type_t'",
+                ErrorCode::Unassociated,
+            ),
         ],
     );
 
@@ -685,3 +690,140 @@ end architecture;
     let diag = builder.analyze();
     check_no_diagnostics(&diag);
 }
+
+#[test]
+fn error_on_missing_generic_associations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (
+    type type_t;
+    constant width : natural;
+    constant depth : natural := 4
+  );
+end package;
+
+package ipkg0 is new work.gpkg;
+
+package ipkg1 is new work.gpkg
+  generic map (width => 3);
+
+package ipkg2 is new work.gpkg
+  generic map (type_t => integer, width => 3);
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("ipkg0"),
+                "No association of type 'type_t'",
+                ErrorCode::Unassociated,
+            ),
+            Diagnostic::new(
+                code.s1("ipkg0"),
+                "No association of generic 'width'",
+                ErrorCode::Unassociated,
+            ),
+            Diagnostic::new(
+                code.s1("ipkg1"),
+                "No association of type 'type_t'",
+                ErrorCode::Unassociated,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn interface_package_actual_must_be_an_instance_of_the_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg_base is
+  generic (type type_t);
+end package;
+
+package gpkg_other is
+  generic (type type_t);
+end package;
+
+package gpkg is
+  generic (
+    package iface_pkg is new work.gpkg_base generic map (<>)
+  );
+end package;
+
+package pkg is
+end package;
+
+package ipkg_base is new work.gpkg_base generic map (type_t => integer);
+package ipkg_other is new work.gpkg_other generic map (type_t => integer);
+
+package ipkg0 is new work.gpkg generic map (iface_pkg => work.ipkg_base);
+package ipkg1 is new work.gpkg generic map (iface_pkg => work.ipkg_other);
+package ipkg2 is new work.gpkg generic map (iface_pkg => work.pkg);
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::mismatched_kinds(
+                code.s1("=> work.ipkg_other").s1("work.ipkg_other"),
+                "Cannot map package instance 'ipkg_other' to package generic, expected an instance of uninstantiated package 'gpkg_base'",
+            ),
+            Diagnostic::mismatched_kinds(
+                code.s1("=> work.pkg").s1("work.pkg"),
+                "Cannot map package 'pkg' to package generic",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn types_of_interface_packages_are_mapped_to_the_actual_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg_base is
+  generic (type type_t);
+end package;
+
+package gpkg is
+  generic (
+    package iface_pkg is new work.gpkg_base generic map (<>)
+  );
+  function get return iface_pkg.type_t;
+end package;
+
+package ipkg_base is new work.gpkg_base generic map (type_t => integer);
+package ipkg is new work.gpkg generic map (iface_pkg => work.ipkg_base);
+
+package pkg is
+  constant good : integer := work.ipkg.get;
+  constant bad : boolean := work.ipkg.get;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("work.ipkg.get", 2).s1("get"),
+            "Invalid call to 'get'",
+            ErrorCode::InvalidCall,
+        )
+        .related(
+            code.s1("get"),
+            "Does not match return type of function get[return INTEGER]",
+        )],
+    );
+}
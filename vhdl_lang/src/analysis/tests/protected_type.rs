@@ -361,3 +361,112 @@ end package body;
         2
     );
 }
+
+#[test]
+fn error_on_missing_protected_subprogram_body() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+    procedure with_body;
+    function without_body return natural;
+  end protected;
+
+  type prot_t is protected body
+    procedure with_body is
+    begin
+    end procedure;
+
+    procedure private;
+  end protected body;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("without_body"),
+                "Missing body for function without_body[return NATURAL] of protected type 'prot_t'",
+                ErrorCode::MissingSubprogramBody,
+            ),
+            Diagnostic::new(
+                code.s1("private"),
+                "Missing body for procedure private[] of protected type 'prot_t'",
+                ErrorCode::MissingSubprogramBody,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn protected_methods_are_resolved() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  type counter_t is protected
+    procedure increment;
+    impure function get return natural;
+  end protected;
+
+  type counter_t is protected body
+    variable count : natural := 0;
+
+    procedure increment is
+    begin
+      count := count + 1;
+    end procedure;
+
+    impure function get return natural is
+    begin
+      return count;
+    end function;
+
+    procedure private is
+    begin
+    end procedure;
+  end protected body;
+
+  shared variable counter : counter_t;
+begin
+  process
+  begin
+    counter.increment;
+    report integer'image(counter.get);
+    counter.private;
+    wait;
+  end process;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s("private", 2),
+            "No declaration of 'private' within protected type 'counter_t'",
+            ErrorCode::Unresolved,
+        )],
+    );
+
+    let increment = root
+        .search_reference(code.source(), code.s("increment", 3).start())
+        .unwrap();
+    assert_eq!(increment.decl_pos(), Some(&code.s1("increment").pos()));
+    assert_eq!(
+        root.find_definition_of(increment).unwrap().decl_pos(),
+        Some(&code.s("increment", 2).pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("get", 3).start()),
+        Some(code.s1("get").pos())
+    );
+}
@@ -22,6 +22,10 @@ end configuration;
 
 entity ent is
 end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
 ",
     );
 
@@ -58,6 +62,42 @@ end configuration;
     );
 }
 
+#[test]
+fn error_on_missing_entity_in_configuration_binding() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  component comp is
+  end component;
+begin
+  inst : comp;
+end architecture;
+
+configuration cfg of ent is
+for rtl
+  for inst : comp
+    use entity work.missing;
+  end for;
+end for;
+end configuration;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::new(
+            code.s1("missing"),
+            "No primary unit 'missing' within library 'libname'",
+            ErrorCode::Unresolved,
+        )],
+    );
+}
+
 #[test]
 fn good_configurations() {
     let mut builder = LibraryBuilder::new();
@@ -67,6 +107,10 @@ fn good_configurations() {
 entity ent is
 end entity;
 
+architecture rtl of ent is
+begin
+end architecture;
+
 configuration cfg_good1 of ent is
 for rtl
 end for;
@@ -126,6 +170,10 @@ fn search_reference_from_configuration_to_entity() {
 entity decl is
 end entity;
 
+architecture rtl of decl is
+begin
+end architecture;
+
 configuration cfg_good1 of decl is
 for rtl
 end for;
@@ -311,6 +359,56 @@ end architecture;
     );
 }
 
+#[test]
+fn rename_references_of_entity_include_bound_components() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ename is
+end entity ename;
+
+architecture a of ename is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component ename is
+  end component ename;
+  component other is
+  end component;
+begin
+  inst1 : component ename;
+  inst2 : entity work.ename;
+end architecture;
+
+configuration cfg of top is
+  for a
+    for inst1 : ename
+      use entity work.ename(a);
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let ent = root
+        .search_reference(code.source(), code.s1("ename").start())
+        .unwrap();
+    assert_eq_unordered(
+        &root.find_all_rename_references(ent),
+        &(1..=9)
+            .map(|occurence| code.s("ename", occurence).pos())
+            .collect::<Vec<_>>(),
+    );
+}
+
 #[test]
 fn resolves_component_instance() {
     check_missing(
@@ -365,12 +463,19 @@ fn search_configuration_instance() {
 entity ent is
 end entity;
 
+architecture a of ent is
+begin
+end architecture;
+
 configuration decl of ent is
   for a
   end for;
 end configuration;
 
-architecture a of ent is
+entity top is
+end entity;
+
+architecture a of top is
 begin
   inst : configuration work.decl;
 end architecture;
@@ -548,3 +653,235 @@ end architecture;
         Some(&code.s1("empty").pos())
     );
 }
+
+#[test]
+fn search_component_of_configurations() {
+    check_search_reference(
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component decl is
+  end component;
+  for inst1 : decl use entity work.ent(a);
+begin
+  inst1 : component decl;
+  inst2 : component decl;
+end architecture;
+
+configuration cfg of top is
+  for a
+    for inst2 : decl
+      use entity work.ent;
+    end for;
+  end for;
+end configuration;
+",
+    );
+}
+
+#[test]
+fn search_architecture_of_block_configuration() {
+    check_search_reference(
+        "
+entity ent is
+end entity;
+
+architecture decl of ent is
+begin
+end architecture;
+
+configuration cfg of ent is
+  for decl
+  end for;
+end configuration;
+",
+    );
+}
+
+#[test]
+fn error_on_missing_component_in_configuration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+  for inst : missing use entity work.ent;
+begin
+end architecture;
+
+configuration cfg of ent is
+  for a
+    for inst : sig
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of 'missing'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("inst : sig").s1("sig"),
+                "Expected component, got signal 'sig'",
+                ErrorCode::MismatchedKinds,
+            )
+            .related(code.s1("sig :").s1("sig"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn default_binding_of_component_must_match_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : natural);
+  port (
+    clk : in bit;
+    data : in bit_vector(width - 1 downto 0);
+    q : out bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    generic (width : natural);
+    port (
+      clk : in bit;
+      data : in bit_vector(width - 1 downto 0);
+      q : out bit
+    );
+  end component;
+
+  component bad is
+    port (
+      clk : in boolean;
+      extra : in bit
+    );
+  end component;
+
+  for good_inst : comp use entity work.ent;
+  for bad_inst : bad use entity work.ent(a);
+begin
+  good_inst : comp generic map (width => 8) port map (clk => '0', data => x\"00\", q => open);
+  bad_inst : bad port map (clk => false, extra => '0');
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s("work.ent", 2),
+                "No association of generic 'width' in the default binding of component 'bad'",
+                ErrorCode::Unassociated,
+            )
+            .related(code.s1("width"), "Defined here"),
+            Diagnostic::new(
+                code.s("work.ent", 2),
+                "Port 'clk' of component 'bad' is of type 'BOOLEAN', but the port of entity 'ent' is of type 'BIT'",
+                ErrorCode::TypeMismatch,
+            )
+            .related(code.s1("clk"), "Defined here"),
+            Diagnostic::new(
+                code.s("work.ent", 2),
+                "No port 'extra' within entity 'ent' for the default binding of component 'bad'",
+                ErrorCode::InvalidFormal,
+            )
+            .related(code.s1("extra"), "Defined here"),
+            Diagnostic::new(
+                code.s("work.ent", 2),
+                "No association of port 'data' in the default binding of component 'bad'",
+                ErrorCode::Unassociated,
+            )
+            .related(code.s1("data"), "Defined here"),
+        ],
+    );
+}
+
+#[test]
+fn binding_indication_maps_component_to_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit;
+    rst : in bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component comp is
+    port (clock : in bit);
+  end component;
+begin
+  inst : comp port map (clock => '0');
+end architecture;
+
+configuration cfg of top is
+  for a
+    for inst : comp
+      use entity work.ent
+        port map (clk => clock, rst => '0', missing => clock);
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("missing"),
+            "No declaration of 'missing'",
+            ErrorCode::Unresolved,
+        )],
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("=> clock").s1("clock").start()),
+        Some(code.s1("clock").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("clk =>").start()),
+        Some(code.s1("clk").pos())
+    );
+}
@@ -1185,6 +1185,74 @@ end package body;
     );
 }
 
+#[test]
+fn alias_with_signature_resolves_to_aliased_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type color_t is (red, green);
+  type light_t is (red, off);
+  function func(arg : integer) return boolean;
+  function func(arg : boolean) return boolean;
+end package;
+
+package body pkg is
+  function func(arg : integer) return boolean is
+  begin
+    return arg > 0;
+  end function;
+
+  function func(arg : boolean) return boolean is
+  begin
+    return arg;
+  end function;
+end package body;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  alias f is work.pkg.func [boolean return boolean];
+  alias my_red is work.pkg.red [return work.pkg.light_t];
+  constant c0 : boolean := f(true);
+  constant c1 : work.pkg.light_t := my_red;
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    // The name of the alias refers to the overload that matches the signature
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.sa("work.pkg.", "func").start()),
+        Some(code.s1("func(arg : boolean)").s1("func").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.sa("work.pkg.", "red").start()),
+        Some(code.s("red", 2).pos())
+    );
+
+    // The definition of an alias is the definition of the aliased entity
+    let definition = |code: Code| {
+        root.find_definition_of(root.search_reference(code.source(), code.start()).unwrap())
+            .unwrap()
+            .decl_pos()
+            .cloned()
+    };
+    assert_eq!(
+        definition(code.s1("f(true)")),
+        Some(code.s("func(arg : boolean)", 2).s1("func").pos())
+    );
+    assert_eq!(
+        definition(code.s1(":= my_red").s1("my_red")),
+        Some(code.s("red", 2).pos())
+    );
+}
+
 #[test]
 fn overloaded_name_can_be_selected() {
     let mut builder = LibraryBuilder::new();
@@ -1827,6 +1895,195 @@ end;
     );
 }
 
+#[test]
+fn component_declaration_differs_from_its_definition() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+    component ent is
+    end component;
+begin
+    inst : ent;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let reference = root
+        .search_reference(code.source(), code.sa("inst : ", "ent").start())
+        .unwrap();
+
+    assert_eq!(
+        reference.declaration().decl_pos(),
+        Some(&code.sa("component ", "ent").pos())
+    );
+    assert_eq!(
+        root.find_definition_of(reference).unwrap().decl_pos(),
+        Some(&code.sa("entity ", "ent").pos())
+    );
+}
+
+#[test]
+fn component_definition_is_the_entity_in_the_library_of_the_instance() {
+    let mut builder = LibraryBuilder::new();
+    let other = builder.code(
+        "otherlib",
+        "
+entity ent is
+end entity;
+
+package pkg is
+    component ent is
+    end component;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+library otherlib;
+use otherlib.pkg.all;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+    inst : ent;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let cursor = code.sa("inst : ", "ent").start();
+    assert_eq!(
+        root.search_reference(code.source(), cursor)
+            .unwrap()
+            .decl_pos(),
+        Some(&other.sa("component ", "ent").pos())
+    );
+    assert_eq!(
+        root.find_definition_at(code.source(), cursor)
+            .unwrap()
+            .decl_pos(),
+        Some(&code.sa("entity ", "ent").pos())
+    );
+}
+
+#[test]
+fn component_definition_follows_configuration_specifications() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+entity other is
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+    component ent is
+    end component;
+
+    for inst2 : ent use entity work.other;
+    for others : ent use entity work.ent(rtl);
+begin
+    inst1 : ent;
+    inst2 : ent;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.find_definition_at(code.source(), code.sa("inst1 : ", "ent").start())
+            .unwrap()
+            .decl_pos(),
+        Some(&code.sa("architecture ", "rtl").pos())
+    );
+    assert_eq!(
+        root.find_definition_at(code.source(), code.s("inst2 : ent", 2).s1("ent").start())
+            .unwrap()
+            .decl_pos(),
+        Some(&code.sa("entity ", "other").pos())
+    );
+}
+
+#[test]
+fn subprogram_call_declaration_differs_from_its_definition() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+    function myfun(arg : natural) return natural;
+end package;
+
+package body pkg is
+    function myfun(arg : natural) return natural is
+    begin
+        return arg;
+    end function;
+end package body;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+    constant c0 : natural := work.pkg.myfun(0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let cursor = code.sa("work.pkg.", "myfun").start();
+    assert_eq!(
+        root.search_reference(code.source(), cursor)
+            .unwrap()
+            .declaration()
+            .decl_pos(),
+        Some(&code.s("myfun", 1).pos())
+    );
+    assert_eq!(
+        root.find_definition_at(code.source(), cursor)
+            .unwrap()
+            .decl_pos(),
+        Some(&code.s("myfun", 2).pos())
+    );
+}
+
 #[test]
 fn find_all_references_of_deferred_constant() {
     let mut builder = LibraryBuilder::new();
@@ -1979,7 +2236,7 @@ package body pkg is
 end package body pkg;
 
 configuration cfg1 of ent1 is
-  for rtl(0)
+  for a1
   end for;
 end configuration cfg1;
 
@@ -2185,3 +2442,84 @@ end package;
     );
     check_no_diagnostics(&builder.analyze())
 }
+
+#[test]
+fn find_all_references_in_associations_attributes_aliases_and_configurations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+    port (clk : in bit);
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+    signal sig : bit;
+    attribute keep : boolean;
+    attribute keep of sig : signal is true;
+    alias sig_alias is sig;
+    component ent is
+        port (clk : in bit);
+    end component;
+begin
+    inst : ent port map (clk => sig);
+    inst2 : entity work.ent port map (clk => sig);
+end architecture;
+
+configuration cfg of top is
+    for a
+        for inst : ent
+            use entity work.ent(rtl);
+        end for;
+    end for;
+end configuration;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let references = |decl: Code| {
+        root.find_all_references(root.search_reference(code.source(), decl.start()).unwrap())
+    };
+
+    assert_eq_unordered(
+        &references(code.s1("sig :")),
+        &[
+            code.s1("sig :").s1("sig").pos(),
+            code.s1("keep of sig").s1("sig").pos(),
+            code.s1("is sig").s1("sig").pos(),
+            code.s("=> sig", 1).s1("sig").pos(),
+            code.s("=> sig", 2).s1("sig").pos(),
+        ],
+    );
+    assert_eq_unordered(
+        &references(code.s1("keep :")),
+        &[code.s("keep", 1).pos(), code.s("keep", 2).pos()],
+    );
+    assert_eq_unordered(
+        &references(code.s("clk :", 1)),
+        &[code.s("clk", 1).pos(), code.s("clk", 4).pos()],
+    );
+    assert_eq_unordered(
+        &references(code.s("clk :", 2)),
+        &[code.s("clk", 2).pos(), code.s("clk", 3).pos()],
+    );
+    assert_eq_unordered(
+        &references(code.s1("rtl")),
+        &[code.s("rtl", 1).pos(), code.s("rtl", 2).pos()],
+    );
+    assert_eq_unordered(
+        &references(code.sa("component ", "ent")),
+        &[
+            code.sa("component ", "ent").pos(),
+            code.sa("inst : ", "ent").pos(),
+            code.sa("for inst : ", "ent").pos(),
+        ],
+    );
+}
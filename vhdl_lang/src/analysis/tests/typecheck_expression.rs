@@ -7,6 +7,7 @@
 use super::*;
 use std::vec;
 use vhdl_lang::data::error_codes::ErrorCode;
+use vhdl_lang::VHDLStandard;
 
 #[test]
 fn test_integer_literal_expression_typecheck() {
@@ -1928,10 +1929,6 @@ fn bit_vector_matching_operators() {
         "
 constant good1 : bit := \"01\" ?= \"10\";
 constant good2 : bit := \"01\" ?/= \"10\";
-constant good3 : bit := \"01\" ?< \"10\";
-constant good4 : bit := \"01\" ?<= \"10\";
-constant good5 : bit := \"01\" ?> \"10\";
-constant good6 : bit := \"01\" ?>= \"10\";
         ",
     );
 
@@ -1939,6 +1936,34 @@ constant good6 : bit := \"01\" ?>= \"10\";
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn matching_ordering_operators_are_not_defined_for_arrays() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.in_declarative_region(
+        "
+constant bad : bit := \"01\" ?< \"10\";
+        ",
+    );
+
+    // Only the matching operators of BIT remain as candidates
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("\"01\""),
+                "string literal does not match type 'BIT'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("\"10\""),
+                "string literal does not match type 'BIT'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
 #[test]
 fn std_ulogic_matching_operators() {
     let mut builder = LibraryBuilder::new();
@@ -1959,10 +1984,6 @@ package pkg is
 
     constant good1v : std_ulogic := \"10\" ?= \"10\";
     constant good2v : std_ulogic := \"10\" ?/= \"10\";
-    constant good3v : std_ulogic := \"10\" ?< \"10\";
-    constant good4v : std_ulogic := \"10\" ?<= \"10\";
-    constant good5v : std_ulogic := \"10\" ?> \"10\";
-    constant good6v : std_ulogic := \"10\" ?>= \"10\";
 end package;        
 ",
     );
@@ -1971,6 +1992,64 @@ end package;
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn matching_case_and_selected_assignment_types() {
+    let mut builder = LibraryBuilder::new();
+    builder.add_std_logic_1164();
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+    signal b : bit;
+    signal slv : std_logic_vector(1 downto 0);
+    signal i : integer;
+    signal o : bit;
+begin
+    process (b, slv, i)
+    begin
+        case? b is
+            when '1' => null;
+            when others => null;
+        end case?;
+        case? slv is
+            when \"1-\" => null;
+            when others => null;
+        end case?;
+        case? i is
+            when 0 => null;
+            when others => null;
+        end case?;
+    end process;
+
+    with slv select? o <= '1' when \"-1\", '0' when others;
+    with i select? o <= '1' when 0, '0' when others;
+end architecture;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("case? i is").s1(" i").s1("i"),
+                "The expression of a matching case statement must be of type BIT or STD_ULOGIC or a one-dimensional array of these, not integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("with i select").s1(" i").s1("i"),
+                "The expression of a matching selected assignment must be of type BIT or STD_ULOGIC or a one-dimensional array of these, not integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
 // Issue #317
 #[test]
 fn type_mismatch_in_binary_expression() {
@@ -1994,3 +2073,43 @@ end package;",
         )],
     );
 }
+
+#[test]
+fn conditional_expressions_are_checked_against_the_target_type() {
+    let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2019);
+    let code = builder.code(
+        "libname",
+        "\
+package pkg is
+    constant cond : boolean := true;
+    constant good : natural := 1 when cond else 2 when not cond else 3;
+    constant bad1 : natural := 1 when cond else 'a';
+    constant bad2 : natural := 1 when 3 else 2;
+
+    function abs_of(x : integer) return natural;
+end package;
+
+package body pkg is
+    function abs_of(x : integer) return natural is
+    begin
+        return x when x > 0 else -x;
+    end function;
+end package body;",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("'a'"),
+                "character literal does not match subtype 'NATURAL'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("when 3").s1("3"),
+                "type universal_integer cannot be implicitly converted to type 'BOOLEAN'. Operator ?? is not defined for this type.",
+                ErrorCode::NoImplicitConversion,
+            ),
+        ],
+    );
+}
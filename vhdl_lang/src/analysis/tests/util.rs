@@ -8,7 +8,9 @@ use crate::analysis::DesignRoot;
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
 use crate::syntax::test::*;
-use crate::syntax::Symbols;
+use crate::syntax::{
+    is_verilog_file, parse_verilog_source, ConditionalAnalysis, Symbols, VHDLParser,
+};
 use pretty_assertions::assert_eq;
 use std::collections::{hash_map::Entry, HashMap};
 use std::sync::Arc;
@@ -70,23 +72,45 @@ end architecture;"
         )
     }
 
+    /// Adds a source file with the given file name, e.g., for Verilog files
+    pub fn code_with_file_name(
+        &mut self,
+        library_name: &str,
+        file_name: &Path,
+        code: &str,
+    ) -> Code {
+        let code = self.code_builder.code_with_file_name(file_name, code);
+        self.add_code(library_name, code.clone());
+        code
+    }
+
     pub fn add_std_logic_1164(&mut self) {
         let std_logic_1164 = self.code_builder.code_from_source(std_logic_1164_package());
         self.add_code("ieee", std_logic_1164);
     }
 
     pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
-        let mut root = DesignRoot::new(self.code_builder.symbols.clone());
+        let mut root = DesignRoot::new(
+            self.code_builder.symbols.clone(),
+            self.code_builder.standard,
+        );
         let mut diagnostics = Vec::new();
 
         add_standard_library(self.symbols(), &mut root);
 
         for (library_name, codes) in self.libraries.iter() {
             for code in codes {
-                root.add_design_file(
-                    library_name.clone(),
-                    code.design_file_diagnostics(&mut diagnostics),
-                );
+                let design_file = if is_verilog_file(code.source().file_name()) {
+                    let parser = VHDLParser {
+                        symbols: self.symbols(),
+                        standard: VHDLStandard::default(),
+                        conditional_analysis: ConditionalAnalysis::default(),
+                    };
+                    parse_verilog_source(&parser, code.source(), &mut diagnostics)
+                } else {
+                    code.design_file_diagnostics(&mut diagnostics)
+                };
+                root.add_design_file(library_name.clone(), design_file);
             }
         }
         root.analyze(&mut diagnostics);
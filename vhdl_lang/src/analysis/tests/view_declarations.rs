@@ -421,13 +421,10 @@ end entity;
     ",
     );
     let diag = builder.analyze();
-    // The mode of views is not analyzed at the moment, so the 'converse attribute
-    // should simply not show any diagnostics.
     check_no_diagnostics(&diag);
 }
 
 #[test]
-#[ignore]
 fn view_declaration_in_cannot_be_assigned_to() {
     let mut builder = LibraryBuilder::with_standard(VHDL2019);
     let code = builder.code(
@@ -460,12 +457,209 @@ end arch;
         diag,
         vec![Diagnostic::new(
             code.s1("y.x"),
-            "interface signal 'y' of mode in may not be the target of an assignment",
+            "element 'x' of mode in of interface signal 'y' of mode view foo may not be the target of an assignment",
             ErrorCode::MismatchedKinds,
         )],
     );
 }
 
+#[test]
+fn assignments_to_elements_of_views() {
+    let mut builder = LibraryBuilder::with_standard(VHDL2019);
+    let code = builder.code(
+        "libname",
+        "\
+package my_pkg is
+    type inner_t is record
+        req: bit;
+        ack: bit;
+    end record;
+
+    view inner_view of inner_t is
+        req: out;
+        ack: in;
+    end view;
+
+    type bar is record
+        x: bit;
+        y: bit;
+        inner: inner_t;
+    end record;
+
+    view foo of bar is
+        x: in;
+        y: out;
+        inner: view inner_view;
+    end view;
+end my_pkg;
+
+use work.my_pkg.all;
+
+entity my_ent is
+port (
+    p: view foo;
+    c: view foo'converse
+);
+end entity;
+
+architecture arch of my_ent is
+begin
+    p.y <= p.x;
+    p.inner.req <= '1';
+    p.inner.ack <= '1';
+    p <= c;
+    c.x <= '1';
+    c.y <= '1';
+    c.inner.ack <= '1';
+end arch;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("p.inner.ack"),
+                "element 'ack' of mode in of interface signal 'p' of mode view foo may not be the target of an assignment",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("p <= c").s1("p"),
+                "interface signal 'p' of mode view foo may not be the target of an assignment",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("c.y"),
+                "element 'y' of mode in of interface signal 'c' of mode view foo may not be the target of an assignment",
+                ErrorCode::MismatchedKinds,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn element_views_must_match_the_element_type() {
+    let mut builder = LibraryBuilder::with_standard(VHDL2019);
+    let code = builder.in_declarative_region(
+        "\
+type inner_t is record
+    req: bit;
+end record;
+
+type inner_array_t is array (natural range <>) of inner_t;
+
+view inner_view of inner_t is
+    req: out;
+end view;
+
+type bar is record
+    good: inner_t;
+    good_array: inner_array_t(0 to 1);
+    bad: bit;
+    bad_array: bit_vector(0 to 1);
+end record;
+
+view foo of bar is
+    good: view inner_view;
+    good_array: view (inner_view);
+    bad: view inner_view;
+    bad_array: view (inner_view);
+end view;
+
+view not_a_view of bar is
+    good, good_array, bad_array: in;
+    bad: view bit;
+end view;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("bad: view inner_view").s1("inner_view"),
+                "view 'inner_view' is declared for record type 'inner_t', which does not match the element 'bad'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("bad_array: view (inner_view)").s1("inner_view"),
+                "view 'inner_view' is declared for record type 'inner_t', which does not match the element 'bad_array'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::mismatched_kinds(code.s1("view bit").s1("bit"), "type 'BIT' is not a view"),
+        ],
+    );
+}
+
+#[test]
+fn association_of_ports_with_view_modes() {
+    let mut builder = LibraryBuilder::with_standard(VHDL2019);
+    let code = builder.code(
+        "libname",
+        "\
+package my_pkg is
+    type bar is record
+        x: bit;
+        y: bit;
+    end record;
+
+    view foo of bar is
+        x: in;
+        y: out;
+    end view;
+
+    constant bar_init : bar := ('0', '0');
+end my_pkg;
+
+use work.my_pkg.all;
+
+entity sub is
+port (
+    p: view foo
+);
+end entity;
+
+use work.my_pkg.all;
+
+entity my_ent is
+port (
+    fwd: view foo;
+    rev: view foo'converse;
+    i: in bar
+);
+end entity;
+
+architecture arch of my_ent is
+    signal s: bar;
+begin
+    inst0: entity work.sub port map (p => s);
+    inst1: entity work.sub port map (p => fwd);
+    inst2: entity work.sub port map (p => rev);
+    inst3: entity work.sub port map (p => i);
+    inst4: entity work.sub port map (p => bar_init);
+end arch;
+    ",
+    );
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            Diagnostic::new(
+                code.s1("p => rev").s1("rev"),
+                "interface signal 'rev' of mode view foo may not be associated with port 'p' of mode view foo, which drives it",
+                ErrorCode::InterfaceModeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("p => i").s1("i"),
+                "interface signal 'i' of mode in may not be associated with port 'p' of mode view foo, which drives it",
+                ErrorCode::InterfaceModeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("bar_init);").s1("bar_init"),
+                "Name must denote a signal name",
+                ErrorCode::InterfaceModeMismatch,
+            ),
+        ],
+    );
+}
+
 // GitHub issue #324
 #[test]
 fn view_in_generic_package() {
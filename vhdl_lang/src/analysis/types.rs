@@ -21,9 +21,9 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     ) -> EvalResult<Subtype<'a>> {
         // @TODO more
         let SubtypeIndication {
+            resolution,
             type_mark,
             constraint,
-            ..
         } = subtype_indication;
 
         let base_type = self.type_name(scope, type_mark.span, &mut type_mark.item, diagnostics)?;
@@ -38,7 +38,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
             )?;
         }
 
-        Ok(Subtype::new(base_type))
+        Ok(Subtype::new(base_type).with_resolution(resolution.is_some()))
     }
 
     pub(crate) fn analyze_type_declaration(
@@ -140,7 +140,12 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
                                             diagnostics,
                                         )?;
 
-                                        let kind = Type::Protected(region.into_region(), true);
+                                        let region = region.into_region();
+                                        region.check_protected_subprograms_have_body(
+                                            ent,
+                                            diagnostics,
+                                        );
+                                        let kind = Type::Protected(region, true);
                                         unsafe {
                                             ptype_body.set_kind(AnyEntKind::Type(kind));
                                         }
@@ -521,7 +526,7 @@ impl<'a, 't> AnalyzeContext<'a, 't> {
     }
 
     /// The matching operators such as ?= are defined for 1d arrays of bit and std_ulogic element type
-    fn has_matching_op(&self, typ: TypeEnt<'a>) -> bool {
+    pub(crate) fn has_matching_op(&self, typ: TypeEnt<'a>) -> bool {
         if self.is_std_logic_1164 {
             // Within the std_logic_1164 we do not have efficient access to the types
             typ.designator() == &Designator::Identifier(self.root.symbol_utf8("std_ulogic"))
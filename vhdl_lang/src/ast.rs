@@ -302,6 +302,9 @@ pub enum Expression {
     /// LRM 9.3.7 Allocators
     New(Box<WithTokenSpan<Allocator>>),
     Parenthesized(Box<WithTokenSpan<Expression>>),
+
+    /// VHDL-2019 conditional expressions, i.e., `a when cond else b`
+    Conditional(Box<Conditionals<WithTokenSpan<Expression>>>),
 }
 
 /// An identifier together with the lexical source location it occurs in.
@@ -1064,6 +1067,8 @@ pub struct Alternative<T> {
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Selection<T> {
+    /// True for the matching selected assignments of VHDL-2008 (`select?`)
+    pub is_matching: bool,
     pub expression: WithTokenSpan<Expression>,
     pub alternatives: Vec<Alternative<T>>,
 }
@@ -1438,7 +1443,7 @@ pub enum InstantiationList {
 /// LRM 7.3.2 Binding indication
 #[derive(PartialEq, Debug, Clone)]
 pub enum EntityAspect {
-    Entity(WithTokenSpan<Name>, Option<Ident>),
+    Entity(WithTokenSpan<Name>, Option<WithRef<Ident>>),
     Configuration(WithTokenSpan<Name>),
     Open,
 }
@@ -1641,6 +1646,28 @@ impl AnyDesignUnit {
     pub fn is_entity(&self) -> bool {
         matches!(self, AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(_)))
     }
+
+    /// The context clause that precedes the design unit.
+    /// Context declarations do not have a context clause of their own.
+    pub fn context_clause(&self) -> Option<&ContextClause> {
+        match self {
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(unit)) => Some(&unit.context_clause),
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(unit)) => {
+                Some(&unit.context_clause)
+            }
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Package(unit)) => Some(&unit.context_clause),
+            AnyDesignUnit::Primary(AnyPrimaryUnit::PackageInstance(unit)) => {
+                Some(&unit.context_clause)
+            }
+            AnyDesignUnit::Primary(AnyPrimaryUnit::Context(_)) => None,
+            AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(unit)) => {
+                Some(&unit.context_clause)
+            }
+            AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(unit)) => {
+                Some(&unit.context_clause)
+            }
+        }
+    }
 }
 
 #[derive(PartialEq, Debug, Clone, Default)]
@@ -453,6 +453,19 @@ impl Display for Expression {
             Expression::Literal(ref literal) => write!(f, "{literal}"),
             Expression::New(ref alloc) => write!(f, "new {alloc}"),
             Expression::Parenthesized(expr) => write!(f, "({expr})"),
+            Expression::Conditional(conditionals) => {
+                for conditional in conditionals.conditionals.iter() {
+                    write!(
+                        f,
+                        "{} when {} else ",
+                        conditional.item, conditional.condition
+                    )?;
+                }
+                if let Some((expr, _)) = &conditionals.else_item {
+                    write!(f, "{expr}")?;
+                }
+                Ok(())
+            }
         }
     }
 }
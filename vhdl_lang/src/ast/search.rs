@@ -7,8 +7,11 @@
 
 use super::*;
 use crate::analysis::DesignRoot;
-use crate::named_entity::{EntRef, HasEntityId, Reference};
+use crate::named_entity::{
+    AnyEntKind, Concurrent, EntRef, EntityId, HasEntityId, Overloaded, OverloadedEnt, Reference,
+};
 use crate::syntax::{HasTokenSpan, TokenAccess};
+use fnv::FnvHashSet;
 
 #[must_use]
 #[derive(PartialEq, Debug)]
@@ -114,6 +117,29 @@ pub trait Searcher {
     fn search_with_pos(&mut self, _ctx: &dyn TokenAccess, _pos: &SrcPos) -> SearchState {
         NotFinished
     }
+
+    /// Search a function call, a procedure call or an indexed name
+    fn search_call(&mut self, _ctx: &dyn TokenAccess, _call: &CallOrIndexed) -> SearchState {
+        NotFinished
+    }
+
+    /// Search a declaration of a declarative part together with its token span
+    fn search_declaration(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        _decl: &WithTokenSpan<Declaration>,
+    ) -> SearchState {
+        NotFinished
+    }
+
+    /// Search the target of an assignment
+    fn search_target(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        _target: &WithTokenSpan<Target>,
+    ) -> SearchState {
+        NotFinished
+    }
 }
 
 pub trait Search {
@@ -216,6 +242,7 @@ fn search_selection<T: Search>(
     let Selection {
         expression,
         alternatives,
+        ..
     } = selection;
     return_if_found!(expression.search(ctx, searcher));
     return_if_found!(search_alternatives(
@@ -247,6 +274,7 @@ fn search_assignment<T: Search>(
             let Selection {
                 expression,
                 alternatives,
+                ..
             } = selection;
             // expression comes before target
             return_if_found!(expression.search(ctx, searcher));
@@ -281,6 +309,7 @@ impl Search for WithTokenSpan<ElementAssociation> {
 
 impl Search for WithTokenSpan<Target> {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_finished!(searcher.search_target(ctx, self));
         match self.item {
             Target::Name(ref name) => search_pos_name(&self.pos(ctx), name, searcher, ctx),
             Target::Aggregate(ref assocs) => assocs.search(ctx, searcher),
@@ -694,6 +723,7 @@ impl Search for WithTokenSpan<ElementConstraint> {
 
 impl Search for WithTokenSpan<Declaration> {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_finished!(searcher.search_declaration(ctx, self));
         self.item.search(ctx, searcher)
     }
 }
@@ -941,6 +971,9 @@ fn search_pos_expr(
         Expression::Parenthesized(expr) => {
             search_pos_expr(ctx, &expr.span.pos(ctx), &expr.item, searcher)
         }
+        Expression::Conditional(conditionals) => {
+            search_conditionals(conditionals, true, searcher, ctx)
+        }
     }
 }
 
@@ -992,6 +1025,7 @@ impl Search for AssociationElement {
 
 impl Search for CallOrIndexed {
     fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_finished!(searcher.search_call(ctx, self));
         let CallOrIndexed { name, parameters } = self;
         return_if_found!(name.search(ctx, searcher));
         return_if_found!(parameters.search(ctx, searcher));
@@ -1201,8 +1235,9 @@ impl Search for Declaration {
                 return_if_found!(package_instance.search(ctx, searcher));
             }
 
-            Declaration::Configuration(_) => {
-                // @TODO
+            Declaration::Configuration(config) => {
+                return_if_found!(config.spec.search(ctx, searcher));
+                return_if_found!(config.bind_ind.search(ctx, searcher));
             }
             Declaration::View(view) => {
                 return_if_found!(searcher
@@ -1517,7 +1552,67 @@ impl Search for ConfigurationDeclaration {
                 FoundDeclaration::new(&self.ident.decl, DeclarationItem::Configuration(self))
             )
             .or_not_found());
-        self.entity_name.search(ctx, searcher)
+        return_if_found!(self.entity_name.search(ctx, searcher));
+        self.block_config.search(ctx, searcher)
+    }
+}
+
+impl Search for BlockConfiguration {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        return_if_found!(self.block_spec.search(ctx, searcher));
+        for item in self.items.iter() {
+            match item {
+                ConfigurationItem::Block(block_config) => {
+                    return_if_found!(block_config.search(ctx, searcher));
+                }
+                ConfigurationItem::Component(component_config) => {
+                    return_if_found!(component_config.spec.search(ctx, searcher));
+                    if let Some(bind_ind) = &component_config.bind_ind {
+                        return_if_found!(bind_ind.search(ctx, searcher));
+                    }
+                    if let Some(block_config) = &component_config.block_config {
+                        return_if_found!(block_config.search(ctx, searcher));
+                    }
+                }
+            }
+        }
+        NotFound
+    }
+}
+
+impl Search for ComponentSpecification {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        self.component_name.search(ctx, searcher)
+    }
+}
+
+impl Search for BindingIndication {
+    fn search(&self, ctx: &dyn TokenAccess, searcher: &mut impl Searcher) -> SearchResult {
+        match &self.entity_aspect {
+            Some(EntityAspect::Entity(entity_name, architecture_name)) => {
+                return_if_found!(entity_name.search(ctx, searcher));
+                if let Some(architecture_name) = architecture_name {
+                    return_if_found!(searcher
+                        .search_pos_with_ref(
+                            ctx,
+                            architecture_name.item.pos(ctx),
+                            &architecture_name.reference
+                        )
+                        .or_not_found());
+                }
+            }
+            Some(EntityAspect::Configuration(config_name)) => {
+                return_if_found!(config_name.search(ctx, searcher));
+            }
+            Some(EntityAspect::Open) | None => {}
+        }
+        if let Some(map) = &self.generic_map {
+            return_if_found!(map.search(ctx, searcher));
+        }
+        if let Some(map) = &self.port_map {
+            return_if_found!(map.search(ctx, searcher));
+        }
+        NotFound
     }
 }
 
@@ -1732,10 +1827,12 @@ impl<'a> Searcher for FormatDeclaration<'a> {
 
         if self.ent.is_implicit_of(id) {
             // Implicit
+            let description = OverloadedEnt::from_any(self.ent)
+                .and_then(|ent| ent.describe_implicit_declaration())
+                .unwrap_or_else(|| self.ent.describe());
             self.result = Some(format!(
                 "-- {}\n\n-- Implicitly defined by:\n{}\n",
-                self.ent.describe(),
-                decl.ast,
+                description, decl.ast,
             ));
             return Finished(Found);
         } else if self.ent.id() == id {
@@ -1813,8 +1910,556 @@ impl<'a> Searcher for FindAllReferences<'a> {
     }
 }
 
+/// Search for the names that are assigned to, i.e., the names of the targets of assignments.
+/// Of an indexed or sliced name, the prefix is assigned to.
+#[derive(Default)]
+pub struct FindAssignmentTargets {
+    pub positions: FnvHashSet<SrcPos>,
+}
+
+impl FindAssignmentTargets {
+    fn add_name(&mut self, ctx: &dyn TokenAccess, pos: SrcPos, name: &Name) {
+        match name {
+            Name::Designator(_) => {
+                self.positions.insert(pos);
+            }
+            Name::Selected(prefix, suffix) => {
+                self.positions.insert(suffix.pos(ctx).clone());
+                self.add_name(ctx, prefix.pos(ctx), &prefix.item);
+            }
+            Name::Slice(prefix, _) => self.add_name(ctx, prefix.pos(ctx), &prefix.item),
+            Name::CallOrIndexed(fcall) => self.add_name(ctx, fcall.name.pos(ctx), &fcall.name.item),
+            Name::SelectedAll(_) | Name::Attribute(_) | Name::External(_) => {}
+        }
+    }
+
+    fn add_aggregate(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        assocs: &[WithTokenSpan<ElementAssociation>],
+    ) {
+        for assoc in assocs {
+            let (ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr)) =
+                &assoc.item;
+            match &expr.item {
+                Expression::Name(name) => self.add_name(ctx, expr.pos(ctx), name),
+                Expression::Aggregate(assocs) => self.add_aggregate(ctx, assocs),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Searcher for FindAssignmentTargets {
+    fn search_target(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        target: &WithTokenSpan<Target>,
+    ) -> SearchState {
+        match &target.item {
+            Target::Name(name) => self.add_name(ctx, target.pos(ctx), name),
+            Target::Aggregate(assocs) => self.add_aggregate(ctx, assocs),
+        }
+        NotFinished
+    }
+}
+
+/// A call of a subprogram
+pub struct Call<'a> {
+    /// The innermost subprogram body, process or design unit that contains the call
+    pub caller: EntRef<'a>,
+    /// The called subprogram
+    pub callee: EntRef<'a>,
+    /// The position of the name of the called subprogram
+    pub pos: SrcPos,
+}
+
+/// Search for all calls of subprograms
+pub struct FindAllCalls<'a> {
+    root: &'a DesignRoot,
+    /// The constructs that contain the current position of the search,
+    /// from the outermost to the innermost construct
+    enclosing: Vec<(EntRef<'a>, SrcPos)>,
+    pub calls: Vec<Call<'a>>,
+}
+
+impl<'a> FindAllCalls<'a> {
+    pub fn new(root: &'a DesignRoot) -> FindAllCalls<'a> {
+        FindAllCalls {
+            root,
+            enclosing: Vec::new(),
+            calls: Vec::new(),
+        }
+    }
+
+    /// As the search proceeds in the order of the source,
+    /// the constructs that do not contain `pos` have been left
+    fn leave_until(&mut self, pos: &SrcPos) {
+        while let Some((_, enclosing)) = self.enclosing.last() {
+            if enclosing.source == pos.source && enclosing.contains(pos.start()) {
+                break;
+            }
+            self.enclosing.pop();
+        }
+    }
+}
+
+fn is_caller(ent: EntRef<'_>) -> bool {
+    matches!(
+        ent.kind(),
+        AnyEntKind::Overloaded(Overloaded::Subprogram(..) | Overloaded::UninstSubprogram(..))
+            | AnyEntKind::Concurrent(Some(Concurrent::Process))
+            | AnyEntKind::Design(_)
+    )
+}
+
+fn is_callee(ent: EntRef<'_>) -> bool {
+    matches!(
+        ent.kind(),
+        AnyEntKind::Overloaded(
+            Overloaded::Subprogram(..)
+                | Overloaded::SubprogramDecl(..)
+                | Overloaded::UninstSubprogram(..)
+                | Overloaded::UninstSubprogramDecl(..)
+                | Overloaded::InterfaceSubprogram(..)
+        )
+    )
+}
+
+impl<'a> Searcher for FindAllCalls<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            let ent = self.root.get_ent(id);
+            if is_caller(ent) {
+                let pos = ent.src_span.pos(ctx);
+                self.leave_until(&pos);
+                self.enclosing.push((ent, pos));
+            }
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            // Calls through an alias call the aliased subprogram
+            let callee = self.root.get_ent(id).as_actual();
+            if is_callee(callee) {
+                self.leave_until(pos);
+                if let Some((caller, _)) = self.enclosing.last() {
+                    self.calls.push(Call {
+                        caller,
+                        callee,
+                        pos: pos.clone(),
+                    });
+                }
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Search for all identifiers that declare or refer to a named entity
+pub struct FindAllIdentifiers<'a> {
+    root: &'a DesignRoot,
+    pub identifiers: Vec<(SrcPos, EntRef<'a>)>,
+}
+
+impl<'a> FindAllIdentifiers<'a> {
+    pub fn new(root: &'a DesignRoot) -> FindAllIdentifiers<'a> {
+        FindAllIdentifiers {
+            root,
+            identifiers: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Searcher for FindAllIdentifiers<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            let ent = self.root.get_ent(id);
+            if let Some(decl_pos) = ent.decl_pos() {
+                self.identifiers.push((decl_pos.clone(), ent));
+            }
+            if let Some(pos) = decl.end_ident_pos() {
+                self.identifiers.push((ctx.get_pos(pos).clone(), ent));
+            }
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.identifiers.push((pos.clone(), self.root.get_ent(id)));
+        }
+        NotFinished
+    }
+}
+
+/// Search for the spans of all constructs that contain a cursor,
+/// such as identifiers, expressions, statements and declarations
+pub struct FindEnclosingSpans<'a> {
+    root: &'a DesignRoot,
+    cursor: Position,
+    pub spans: Vec<SrcPos>,
+}
+
+impl<'a> FindEnclosingSpans<'a> {
+    pub fn new(root: &'a DesignRoot, cursor: Position) -> FindEnclosingSpans<'a> {
+        FindEnclosingSpans {
+            root,
+            cursor,
+            spans: Vec::new(),
+        }
+    }
+
+    fn add_if_enclosing(&mut self, pos: SrcPos) {
+        if pos.start() <= self.cursor && self.cursor <= pos.end() {
+            self.spans.push(pos);
+        }
+    }
+}
+
+impl<'a> Searcher for FindEnclosingSpans<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            let ent = self.root.get_ent(id);
+            if ent.is_explicit() {
+                self.add_if_enclosing(ent.src_span.pos(ctx));
+            }
+            if let Some(decl_pos) = ent.decl_pos() {
+                self.add_if_enclosing(decl_pos.clone());
+            }
+        }
+        match decl.ast {
+            // Statements without a label do not have a named entity
+            DeclarationItem::SequentialStatement(stmt) => {
+                self.add_if_enclosing(stmt.statement.pos(ctx));
+            }
+            DeclarationItem::ConcurrentStatement(stmt) => {
+                self.add_if_enclosing(stmt.statement.pos(ctx));
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+
+    fn search_with_pos(&mut self, _ctx: &dyn TokenAccess, pos: &SrcPos) -> SearchState {
+        self.add_if_enclosing(pos.clone());
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        _reference: &Reference,
+    ) -> SearchState {
+        self.add_if_enclosing(pos.clone());
+        NotFinished
+    }
+
+    fn search_target(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        target: &WithTokenSpan<Target>,
+    ) -> SearchState {
+        self.add_if_enclosing(target.pos(ctx));
+        NotFinished
+    }
+}
+
+/// Search for the name of a construct and the name at its end,
+/// e.g., the label of a process and the label after `end process`,
+/// when the cursor is at one of them
+pub struct FindLinkedNames<'a> {
+    root: &'a DesignRoot,
+    cursor: Position,
+    pub names: Vec<SrcPos>,
+}
+
+impl<'a> FindLinkedNames<'a> {
+    pub fn new(root: &'a DesignRoot, cursor: Position) -> FindLinkedNames<'a> {
+        FindLinkedNames {
+            root,
+            cursor,
+            names: Vec::new(),
+        }
+    }
+
+    fn labels(
+        ctx: &dyn TokenAccess,
+        label: &Option<WithToken<Symbol>>,
+        end: Option<&SrcPos>,
+    ) -> Option<(SrcPos, SrcPos)> {
+        Some((label.as_ref()?.pos(ctx).clone(), end?.clone()))
+    }
+}
+
+impl<'a> Searcher for FindLinkedNames<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let names = match decl.ast {
+            DeclarationItem::ConcurrentStatement(stmt) => {
+                Self::labels(ctx, &stmt.label.tree, stmt.statement.item.end_label_pos())
+            }
+            DeclarationItem::SequentialStatement(stmt) => {
+                Self::labels(ctx, &stmt.label.tree, stmt.statement.item.end_label_pos())
+            }
+            _ => decl.ent_id().and_then(|id| {
+                let decl_pos = self.root.get_ent(id).decl_pos()?;
+                let end_pos = ctx.get_pos(decl.end_ident_pos()?);
+                Some((decl_pos.clone(), end_pos.clone()))
+            }),
+        };
+        if let Some((name, end_name)) = names {
+            let is_at_cursor =
+                |pos: &SrcPos| pos.start() <= self.cursor && self.cursor <= pos.end();
+            if is_at_cursor(&name) || is_at_cursor(&end_name) {
+                self.names = vec![name, end_name];
+                return Finished(Found);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Search for the references to a named entity that are not declarations,
+/// i.e., without the declarations of bodies and the names at the end of constructs
+pub struct FindAllUsages<'a> {
+    root: &'a DesignRoot,
+    ent: EntRef<'a>,
+    pub usages: Vec<SrcPos>,
+}
+
+impl<'a> FindAllUsages<'a> {
+    pub fn new(root: &'a DesignRoot, ent: EntRef<'a>) -> FindAllUsages<'a> {
+        FindAllUsages {
+            root,
+            ent,
+            usages: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Searcher for FindAllUsages<'a> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            if is_reference(self.ent, self.root.get_ent(id)) {
+                self.usages.push(pos.clone());
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Search for the instantiation statements that instantiate one of a set of
+/// entities or components
+pub struct FindInstantiations {
+    units: FnvHashSet<EntityId>,
+    pub instantiations: Vec<SrcPos>,
+}
+
+impl FindInstantiations {
+    pub fn new(units: FnvHashSet<EntityId>) -> FindInstantiations {
+        FindInstantiations {
+            units,
+            instantiations: Vec::new(),
+        }
+    }
+}
+
+impl Searcher for FindInstantiations {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::ConcurrentStatement(stmt) = decl.ast {
+            if let ConcurrentStatement::Instance(inst) = &stmt.statement.item {
+                if inst
+                    .entity_reference()
+                    .is_some_and(|id| self.units.contains(&id))
+                {
+                    let pos = match &stmt.label.tree {
+                        Some(label) => label.pos(ctx).clone(),
+                        None => stmt.statement.pos(ctx),
+                    };
+                    self.instantiations.push(pos);
+                }
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Search for the binding of the instance of a component at a given position, i.e.,
+/// the label of that instance and the configuration specifications of the component.
+pub struct FindComponentBinding<'a> {
+    component: EntityId,
+    cursor: &'a SrcPos,
+    /// The label of the instance at the cursor, if the instance was found
+    pub label: Option<Option<Symbol>>,
+    /// The instances named by each configuration specification of the component
+    /// together with the entity, architecture or configuration that they are bound to
+    pub specifications: Vec<(InstantiationList, Option<EntityId>)>,
+}
+
+impl<'a> FindComponentBinding<'a> {
+    pub fn new(component: EntityId, cursor: &'a SrcPos) -> FindComponentBinding<'a> {
+        FindComponentBinding {
+            component,
+            cursor,
+            label: None,
+            specifications: Vec::new(),
+        }
+    }
+
+    /// The entity, architecture or configuration that the instance at the cursor is bound to
+    /// by a configuration specification.
+    /// Labeled specifications take precedence over `others` and `all`.
+    pub fn bound_unit(&self) -> Option<EntityId> {
+        let label = self.label.as_ref()?.as_ref();
+        self.specifications
+            .iter()
+            .find(|(list, _)| {
+                matches!(list, InstantiationList::Labels(labels)
+                    if labels.iter().any(|ident| Some(&ident.item) == label))
+            })
+            .or_else(|| {
+                self.specifications.iter().find(|(list, _)| {
+                    matches!(list, InstantiationList::Others | InstantiationList::All)
+                })
+            })
+            .and_then(|(_, bound)| *bound)
+    }
+}
+
+impl<'a> Searcher for FindComponentBinding<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::ConcurrentStatement(stmt) = decl.ast {
+            // An instance without generic or port map is parsed as a procedure call
+            let name = match &stmt.statement.item {
+                ConcurrentStatement::Instance(InstantiationStatement {
+                    unit: InstantiatedUnit::Component(name),
+                    ..
+                }) => Some(name),
+                ConcurrentStatement::ProcedureCall(pcall) => Some(&pcall.call.item.name),
+                _ => None,
+            };
+            if let Some(name) = name {
+                if name.item.get_suffix_reference() == Some(self.component)
+                    && name.pos(ctx).contains(self.cursor.start())
+                {
+                    self.label = Some(stmt.label.tree.as_ref().map(|label| label.item.clone()));
+                }
+            }
+        }
+        NotFinished
+    }
+
+    fn search_declaration(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        decl: &WithTokenSpan<Declaration>,
+    ) -> SearchState {
+        if let Declaration::Configuration(config) = &decl.item {
+            if config.spec.component_name.item.get_suffix_reference() == Some(self.component) {
+                let bound = match &config.bind_ind.entity_aspect {
+                    Some(EntityAspect::Entity(entity_name, architecture_name)) => architecture_name
+                        .as_ref()
+                        .and_then(|name| name.reference.get())
+                        .or_else(|| entity_name.item.get_suffix_reference()),
+                    Some(EntityAspect::Configuration(config_name)) => {
+                        config_name.item.get_suffix_reference()
+                    }
+                    Some(EntityAspect::Open) | None => None,
+                };
+                self.specifications
+                    .push((config.spec.instantiation_list.clone(), bound));
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Search for all component declarations with a given name
+/// as well as the component configurations that refer to a component with that name.
+pub struct FindComponentsByName<'a> {
+    designator: &'a Designator,
+    pub components: Vec<EntityId>,
+    /// The entities that the component configurations bind the components to
+    pub bound: Vec<EntityId>,
+}
+
+impl<'a> FindComponentsByName<'a> {
+    pub fn new(designator: &'a Designator) -> FindComponentsByName<'a> {
+        FindComponentsByName {
+            designator,
+            components: Vec::new(),
+            bound: Vec::new(),
+        }
+    }
+
+    fn search_block_config(&mut self, block_config: &BlockConfiguration) {
+        for item in block_config.items.iter() {
+            match item {
+                ConfigurationItem::Block(block_config) => {
+                    self.search_block_config(block_config);
+                }
+                ConfigurationItem::Component(component_config) => {
+                    let component_name = &component_config.spec.component_name;
+                    if let Name::Designator(designator) = &component_name.item {
+                        if designator.item == *self.designator {
+                            if let Some(BindingIndication {
+                                entity_aspect: Some(EntityAspect::Entity(entity_name, _)),
+                                ..
+                            }) = &component_config.bind_ind
+                            {
+                                self.bound.extend(entity_name.item.get_suffix_reference());
+                            }
+                        }
+                    }
+                    if let Some(block_config) = &component_config.block_config {
+                        self.search_block_config(block_config);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Searcher for FindComponentsByName<'a> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Component(component) => {
+                if matches!(self.designator, Designator::Identifier(sym) if *sym == component.ident.tree.item)
+                {
+                    if let Some(id) = decl.ent_id() {
+                        self.components.push(id);
+                    }
+                }
+            }
+            DeclarationItem::Configuration(config) => {
+                self.search_block_config(&config.block_config);
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
 impl<'a> FoundDeclaration<'a> {
-    fn end_ident_pos(&self) -> Option<TokenId> {
+    pub(crate) fn end_ident_pos(&self) -> Option<TokenId> {
         match &self.ast {
             DeclarationItem::InterfaceObject(_) => None,
             DeclarationItem::ForIndex(..) => None,
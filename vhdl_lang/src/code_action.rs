@@ -0,0 +1,345 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Code actions are refactorings and quick fixes that can be applied to a source file.
+//! A code action does not change the design directly. Instead, it describes the textual
+//! changes to be performed in a [WorkspaceEdit] that a client can apply.
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{NotFinished, Search, SearchState, Searcher};
+use crate::ast::Name;
+use crate::code_action::add_use_clause::add_use_clause_actions;
+use crate::code_action::align_interface::align_interface_actions;
+use crate::code_action::case_choices::case_choices_actions;
+use crate::code_action::change_port_mode::change_port_mode_actions;
+use crate::code_action::component_to_entity::component_to_entity_actions;
+use crate::code_action::create_design_unit::create_design_unit_actions;
+use crate::code_action::declare_missing_object::declare_missing_object_actions;
+use crate::code_action::extract_entity::extract_entity_actions;
+use crate::code_action::extract_procedure::extract_procedure_actions;
+use crate::code_action::generate_architecture::generate_architecture_actions;
+use crate::code_action::generate_component::generate_component_actions;
+use crate::code_action::inline::inline_actions;
+use crate::code_action::missing_associations::missing_association_actions;
+use crate::code_action::move_to_package::move_to_package_actions;
+use crate::code_action::normalize_case::normalize_case_actions;
+use crate::code_action::organize_imports::organize_imports_actions;
+use crate::code_action::remove_unused::remove_unused_actions;
+use crate::code_action::sensitivity_list::sensitivity_list_actions;
+use crate::config::Config;
+use crate::named_entity::Reference;
+use crate::{EntityId, Position, Range, Source, SrcPos, TokenAccess};
+use fnv::{FnvHashMap, FnvHashSet};
+use std::path::{Path, PathBuf};
+
+mod add_use_clause;
+mod align_interface;
+mod case_choices;
+mod change_port_mode;
+mod component_to_entity;
+mod create_design_unit;
+mod declare_missing_object;
+mod extract_entity;
+mod extract_procedure;
+mod generate_architecture;
+mod generate_component;
+mod inline;
+mod missing_associations;
+mod move_to_package;
+mod normalize_case;
+mod organize_imports;
+mod remove_unused;
+mod sensitivity_list;
+mod synchronize_instances;
+
+pub use normalize_case::normalize_case;
+pub use synchronize_instances::{
+    synchronize_instantiations, InstanceSynchronization, StaleAssociation,
+};
+
+/// The kind of code action.
+/// This directly corresponds to the code action kinds that are defined in the LSP specification.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CodeActionKind {
+    /// Refactoring that moves declarations to another location,
+    /// e.g., from an architecture to a package
+    RefactorMove,
+    /// Refactoring that replaces uses of a named entity with its definition
+    RefactorInline,
+    /// Refactoring that extracts code into a new design unit or subprogram
+    RefactorExtract,
+    /// Refactoring that rewrites or generates code
+    RefactorRewrite,
+    /// Fixes a problem in the code, e.g., one that is reported by a diagnostic
+    QuickFix,
+    /// Source action that organizes the use clauses of a file
+    SourceOrganizeImports,
+}
+
+/// Replace the text at `range` with `new_text`.
+/// An empty range denotes an insertion, an empty text denotes a deletion.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range, new_text: impl Into<String>) -> TextEdit {
+        TextEdit {
+            range,
+            new_text: new_text.into(),
+        }
+    }
+
+    pub fn insert(pos: Position, new_text: impl Into<String>) -> TextEdit {
+        TextEdit::new(Range::new(pos, pos), new_text)
+    }
+
+    pub fn delete(range: Range) -> TextEdit {
+        TextEdit::new(range, "")
+    }
+}
+
+/// A set of text edits that may span multiple files.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct WorkspaceEdit {
+    /// New, empty files that are created before any text edit is applied
+    pub created_files: Vec<PathBuf>,
+    pub changes: FnvHashMap<PathBuf, Vec<TextEdit>>,
+}
+
+impl WorkspaceEdit {
+    pub fn create(&mut self, file_name: &Path) {
+        self.created_files.push(file_name.to_owned());
+    }
+
+    pub fn add(&mut self, file_name: &Path, edit: TextEdit) {
+        self.changes
+            .entry(file_name.to_owned())
+            .or_default()
+            .push(edit);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodeAction {
+    /// A short, human-readable title for this code action
+    pub title: String,
+    pub kind: CodeActionKind,
+    pub edit: WorkspaceEdit,
+}
+
+/// Main entry point for code actions. Given a source-file and a selected range,
+/// lists all code actions that are applicable to the selection.
+pub fn list_code_actions(
+    root: &DesignRoot,
+    config: &Config,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut actions = Vec::new();
+    actions.extend(missing_association_actions(root, source, range));
+    actions.extend(move_to_package_actions(root, source, range));
+    actions.extend(inline_actions(root, source, range));
+    actions.extend(generate_architecture_actions(
+        root,
+        config.code_actions(),
+        source,
+        range,
+    ));
+    actions.extend(create_design_unit_actions(root, source, range));
+    actions.extend(generate_component_actions(root, source, range));
+    actions.extend(component_to_entity_actions(root, source, range));
+    actions.extend(declare_missing_object_actions(root, source, range));
+    actions.extend(add_use_clause_actions(root, source, range));
+    actions.extend(remove_unused_actions(root, source, range));
+    actions.extend(case_choices_actions(root, source, range));
+    actions.extend(change_port_mode_actions(root, source, range));
+    actions.extend(align_interface_actions(
+        root,
+        config.code_actions(),
+        source,
+        range,
+    ));
+    actions.extend(extract_entity_actions(root, source, range));
+    actions.extend(extract_procedure_actions(root, source, range));
+    actions.extend(sensitivity_list_actions(
+        root,
+        config.standard(),
+        source,
+        range,
+    ));
+    actions.extend(normalize_case_actions(root, source, range));
+    actions.extend(organize_imports_actions(root, source));
+    actions
+}
+
+/// The indentation used for generated code
+pub(crate) const INDENT: &str = "    ";
+
+/// Collects all named entities that are referenced in some part of the AST
+struct ReferenceCollector {
+    references: FnvHashSet<EntityId>,
+}
+
+impl Searcher for ReferenceCollector {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        _pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.references.insert(id);
+        }
+        NotFinished
+    }
+}
+
+/// Returns all named entities that are referenced in some part of the AST
+pub(crate) fn referenced_entities(
+    ctx: &dyn TokenAccess,
+    node: &impl Search,
+) -> FnvHashSet<EntityId> {
+    let mut collector = ReferenceCollector {
+        references: FnvHashSet::default(),
+    };
+    let _ = node.search(ctx, &mut collector);
+    collector.references
+}
+
+/// Returns the named entity of the object that is the base of a name,
+/// e.g., `foo` for the target `foo(0).bar`
+pub(crate) fn base_reference(name: &Name) -> Option<EntityId> {
+    match name {
+        Name::Designator(designator) => designator.reference.get(),
+        Name::Selected(prefix, _) | Name::SelectedAll(prefix) | Name::Slice(prefix, _) => {
+            base_reference(&prefix.item)
+        }
+        Name::CallOrIndexed(call) => base_reference(&call.name.item),
+        Name::Attribute(..) | Name::External(..) => None,
+    }
+}
+
+/// Returns the text of the source inside the given range.
+pub(crate) fn text_at(source: &Source, range: Range) -> String {
+    let contents = source.contents();
+    let mut result = String::new();
+    for lineno in range.start.line..=range.end.line {
+        let Some(line) = contents.get_line(lineno as usize) else {
+            break;
+        };
+        let mut character = 0;
+        for chr in line.chars() {
+            let pos = Position::new(lineno, character);
+            if range.start <= pos && pos < range.end {
+                result.push(chr);
+            }
+            character += chr.len_utf16() as u32;
+        }
+    }
+    result
+}
+
+/// Returns the leading whitespace of the line with the given line number.
+pub(crate) fn indentation_of_line(source: &Source, lineno: u32) -> String {
+    source
+        .contents()
+        .get_line(lineno as usize)
+        .map(|line| {
+            line.chars()
+                .take_while(|chr| *chr == ' ' || *chr == '\t')
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the start of the next line after the given position
+pub(crate) fn start_of_next_line(pos: Position) -> Position {
+    Position::new(pos.line + 1, 0)
+}
+
+/// Extends the given range to cover whole lines if the range is the only
+/// content of these lines (disregarding whitespace).
+/// This avoids leaving empty lines behind when removing code.
+pub(crate) fn removal_range(source: &Source, range: Range) -> Range {
+    let before = text_at(
+        source,
+        Range::new(Position::new(range.start.line, 0), range.start),
+    );
+    let after = text_at(source, Range::new(range.end, start_of_next_line(range.end)));
+    if before.trim().is_empty() && after.trim().is_empty() {
+        Range::new(
+            Position::new(range.start.line, 0),
+            start_of_next_line(range.end),
+        )
+    } else {
+        range
+    }
+}
+
+/// Changes the indentation of a (potentially multi-line) piece of code
+/// that was originally indented using `old_indent` to use `new_indent`.
+/// The first line is assumed to not contain any indentation.
+pub(crate) fn reindent(text: &str, old_indent: &str, new_indent: &str) -> String {
+    let mut result = String::new();
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        result.push_str(new_indent);
+        if i > 0 {
+            result.push_str(line.strip_prefix(old_indent).unwrap_or(line.trim_start()));
+        } else {
+            result.push_str(line);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Applies all edits of the code action to the given source
+    /// and returns the resulting contents of the file.
+    pub fn apply_edits(source: &Source, action: &CodeAction) -> String {
+        let mut edits = action
+            .edit
+            .changes
+            .get(source.file_name())
+            .cloned()
+            .unwrap_or_default();
+        // Apply edits back to front so that positions of earlier edits stay valid
+        edits.sort_by_key(|edit| edit.range.start);
+        let contents = source.contents();
+        let mut code = String::new();
+        for lineno in 0..contents.num_lines() {
+            code.push_str(contents.get_line(lineno).unwrap());
+        }
+        drop(contents);
+        let result = Source::inline(source.file_name(), &code);
+        for edit in edits.iter().rev() {
+            result.change(Some(&edit.range), &edit.new_text);
+        }
+        let contents = result.contents();
+        (0..contents.num_lines())
+            .map(|lineno| contents.get_line(lineno).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn reindents_code() {
+        assert_eq!(
+            reindent("function foo return bit is\n  begin\n  end;", "  ", "    "),
+            "    function foo return bit is\n    begin\n    end;"
+        );
+    }
+}
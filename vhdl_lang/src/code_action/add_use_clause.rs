@@ -0,0 +1,302 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, LockedUnit};
+use crate::ast::search::{Finished, Found, NotFinished, Search, SearchState, Searcher};
+use crate::ast::*;
+use crate::code_action::{indentation_of_line, text_at, CodeAction, CodeActionKind};
+use crate::code_action::{TextEdit, WorkspaceEdit};
+use crate::data::Symbol;
+use crate::named_entity::{AnyEntKind, Design, Reference};
+use crate::{HasTokenSpan, Position, Range, Source, SrcPos, TokenAccess};
+use std::ops::Deref;
+
+/// Finds a name at the cursor that could not be resolved.
+/// Names in the context clause are ignored.
+struct UnresolvedNameSearcher {
+    start: Position,
+    cursor: Position,
+    result: Option<SrcPos>,
+}
+
+impl Searcher for UnresolvedNameSearcher {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if pos.start() >= self.start
+            && reference.get().is_none()
+            && pos.range().contains(self.cursor)
+        {
+            self.result = Some(pos.clone());
+            return Finished(Found);
+        }
+        NotFinished
+    }
+}
+
+/// Returns whether the context clause already contains a library clause for a library
+fn has_library_clause(items: &[ContextItem], library: &Symbol) -> bool {
+    items.iter().any(|item| match item {
+        ContextItem::Library(clause) => clause
+            .name_list
+            .iter()
+            .any(|name| &name.item.item == library),
+        _ => false,
+    })
+}
+
+fn add_use_clause_action(
+    unit: &LockedUnit,
+    source: &Source,
+    library: &Symbol,
+    package: &Symbol,
+) -> Option<CodeAction> {
+    let data = unit.unit.get()?;
+    let ctx: &dyn TokenAccess = &unit.tokens;
+    let items = data
+        .context_clause()
+        .map(|items| items.as_slice())
+        .unwrap_or(&[]);
+    let unit_start = data.get_pos(ctx).start();
+
+    // The library of the design unit and the std library are always visible
+    let mut clauses = Vec::new();
+    let prefix = if library == unit.unit_id().library_name() {
+        "work".to_owned()
+    } else {
+        let name = library.name_utf8();
+        if name != "std" && !has_library_clause(items, library) {
+            clauses.push(format!("library {name};"));
+        }
+        name
+    };
+    clauses.push(format!("use {prefix}.{package}.all;"));
+
+    let indent = indentation_of_line(source, unit_start.line);
+    // The clauses are added after the existing context items
+    let position = match items.last() {
+        Some(item) => Position::new(item.get_pos(ctx).end().line + 1, 0),
+        None => Position::new(unit_start.line, 0),
+    };
+    let text: String = clauses
+        .iter()
+        .map(|clause| format!("{indent}{clause}\n"))
+        .collect();
+
+    let mut edit = WorkspaceEdit::default();
+    edit.add(source.file_name(), TextEdit::insert(position, text));
+    Some(CodeAction {
+        title: format!("Add '{}'", clauses.join(" ").trim_end_matches(';')),
+        kind: CodeActionKind::QuickFix,
+        edit,
+    })
+}
+
+/// Makes a name that could not be resolved visible by adding a use clause
+/// for each package of any library that declares the name.
+/// A library clause is added as well when the library is not yet visible.
+pub(super) fn add_use_clause_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let cursor = range.start;
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let ctx: &dyn TokenAccess = &unit.tokens;
+        let pos = data.get_pos(ctx);
+        if !pos.range().contains(cursor) {
+            continue;
+        }
+        let mut searcher = UnresolvedNameSearcher {
+            start: pos.start(),
+            cursor,
+            result: None,
+        };
+        let _ = data.deref().search(ctx, &mut searcher);
+        let Some(name_pos) = searcher.result else {
+            return vec![];
+        };
+        let name = text_at(source, name_pos.range());
+        if !name.starts_with(|chr: char| chr.is_ascii_alphabetic()) {
+            return vec![];
+        }
+        let designator = Designator::Identifier(root.symbol_utf8(&name));
+
+        let mut packages: Vec<(Symbol, Symbol)> = Vec::new();
+        for ent in root.public_symbols() {
+            if ent.designator() != &designator {
+                continue;
+            }
+            let Some(package) = ent.parent else {
+                continue;
+            };
+            if !matches!(package.kind(), AnyEntKind::Design(Design::Package(..))) {
+                continue;
+            }
+            let (Some(library), Designator::Identifier(package)) =
+                (package.library_name(), package.designator())
+            else {
+                continue;
+            };
+            let key = (library.clone(), package.clone());
+            if !packages.contains(&key) {
+                packages.push(key);
+            }
+        }
+        packages.sort_by_key(|(library, package)| (library.name_utf8(), package.name_utf8()));
+
+        return packages
+            .iter()
+            .filter_map(|(library, package)| add_use_clause_action(unit, source, library, package))
+            .collect();
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeActionKind};
+    use crate::syntax::test::Code;
+    use crate::{Config, Range};
+
+    fn use_clause_titles(root: &crate::analysis::DesignRoot, code: &Code) -> Vec<String> {
+        let cursor = code.s1("value").start();
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(cursor, cursor),
+        )
+        .into_iter()
+        .filter(|action| action.kind == CodeActionKind::QuickFix)
+        .filter(|action| action.title.starts_with("Add '"))
+        .map(|action| action.title)
+        .collect()
+    }
+
+    #[test]
+    fn adds_use_clause_for_package_that_declares_name() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+package pkg is
+  constant value : natural := 0;
+end package;",
+        );
+        builder.code(
+            "otherlib",
+            "\
+package other_pkg is
+  constant value : natural := 1;
+end package;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := value;
+begin
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        assert_eq!(
+            use_clause_titles(&root, &code),
+            vec![
+                "Add 'use work.pkg.all'".to_owned(),
+                "Add 'library otherlib; use otherlib.other_pkg.all'".to_owned(),
+            ]
+        );
+
+        let cursor = code.s1("value").start();
+        let actions = list_code_actions(
+            &root,
+            &Config::default(),
+            code.source(),
+            Range::new(cursor, cursor),
+        );
+        let action = actions
+            .iter()
+            .find(|action| action.title == "Add 'library otherlib; use otherlib.other_pkg.all'")
+            .unwrap();
+        // The architecture has no context clause of its own
+        assert_eq!(
+            apply_edits(code.source(), action),
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+library otherlib;
+use otherlib.other_pkg.all;
+architecture a of ent is
+  constant c : natural := value;
+begin
+end architecture;"
+        );
+    }
+
+    #[test]
+    fn adds_use_clause_after_context_clause() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "otherlib",
+            "\
+package pkg is
+  constant value : natural := 1;
+end package;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+library otherlib;
+
+entity ent is
+  generic (g : natural := value);
+end entity;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let cursor = code.s1("value").start();
+        let actions = list_code_actions(
+            &root,
+            &Config::default(),
+            code.source(),
+            Range::new(cursor, cursor),
+        );
+        let action = actions
+            .iter()
+            .find(|action| action.title == "Add 'use otherlib.pkg.all'")
+            .unwrap();
+        assert_eq!(
+            apply_edits(code.source(), action),
+            "\
+library otherlib;
+use otherlib.pkg.all;
+
+entity ent is
+  generic (g : natural := value);
+end entity;"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::{
+    indentation_of_line, start_of_next_line, text_at, CodeAction, CodeActionKind, TextEdit,
+    WorkspaceEdit,
+};
+use crate::config::CodeActionConfig;
+use crate::{HasTokenSpan, Position, Range, Source, TokenAccess};
+
+/// A declaration of a generic or port clause that is written on a single line
+struct Row {
+    /// Comment and empty lines directly above the declaration
+    leading: String,
+    names: String,
+    mode: Option<String>,
+    /// The subtype indication and the default value
+    rest: String,
+    comment: Option<String>,
+    group: &'static str,
+}
+
+/// Splits the text after a declaration into the code before a comment and the comment
+fn split_comment(text: &str) -> (&str, Option<String>) {
+    match text.find("--") {
+        Some(idx) => (text[..idx].trim(), Some(text[idx..].trim_end().to_owned())),
+        None => (text.trim(), None),
+    }
+}
+
+/// The group that a port belongs to when ports are reordered
+fn port_group(names: &str, mode: Option<Mode>) -> &'static str {
+    let name = names.to_lowercase();
+    match mode.unwrap_or_default() {
+        Mode::In if ["clk", "clock"].iter().any(|clk| name.contains(clk)) => "clock",
+        Mode::In if ["rst", "reset"].iter().any(|rst| name.contains(rst)) => "reset",
+        Mode::In => "in",
+        Mode::Out => "out",
+        Mode::InOut => "inout",
+        Mode::Buffer => "buffer",
+        Mode::Linkage => "linkage",
+    }
+}
+
+/// The declarations of a generic or port clause
+struct Interface {
+    /// The lines of the declarations
+    range: Range,
+    rows: Vec<Row>,
+    /// The code after the last declaration, e.g., the closing parenthesis
+    suffix: String,
+}
+
+/// The declarations of an interface list, or `None` when the declarations
+/// are not written on lines of their own
+fn interface(ctx: &dyn TokenAccess, source: &Source, list: &InterfaceList) -> Option<Interface> {
+    let mut rows = Vec::new();
+    let mut suffix = String::new();
+    let mut prev_line = None;
+    for (i, item) in list.items.iter().enumerate() {
+        let InterfaceDeclaration::Object(object) = item else {
+            return None;
+        };
+        let pos = object.span.pos(ctx);
+        if pos.start().line != pos.end().line {
+            return None;
+        }
+        let line = pos.start().line;
+        let leading = match prev_line {
+            None => {
+                let before = Range::new(Position::new(line, 0), pos.start());
+                if !text_at(source, before).trim().is_empty() {
+                    return None;
+                }
+                String::new()
+            }
+            Some(prev_line) if prev_line < line => text_at(
+                source,
+                Range::new(Position::new(prev_line + 1, 0), Position::new(line, 0)),
+            ),
+            Some(_) => return None,
+        };
+        prev_line = Some(line);
+
+        let trailing = text_at(source, Range::new(pos.end(), start_of_next_line(pos.end())));
+        let (code, comment) = split_comment(&trailing);
+        if i + 1 < list.items.len() {
+            if code != ";" {
+                return None;
+            }
+        } else {
+            suffix = code.to_owned();
+        }
+
+        let (mode, rest_start) = match &object.mode {
+            ModeIndication::Simple(SimpleModeIndication {
+                mode: Some(mode), ..
+            }) => (Some(mode), mode.token + 1),
+            _ => (None, object.colon_token + 1),
+        };
+        let names = ctx.get_span(object.span.start_token, object.colon_token - 1);
+        let names = text_at(source, names.range());
+        let rest = ctx.get_span(rest_start, object.span.end_token);
+        rows.push(Row {
+            leading,
+            group: port_group(&names, mode.map(|mode| mode.item)),
+            names,
+            mode: mode.map(|mode| text_at(source, mode.token.pos(ctx).range())),
+            rest: text_at(source, rest.range()),
+            comment,
+        });
+    }
+    let first = list.items.first()?.get_pos(ctx).start();
+    let last = list.items.last()?.get_pos(ctx).end();
+    Some(Interface {
+        range: Range::new(Position::new(first.line, 0), start_of_next_line(last)),
+        rows,
+        suffix,
+    })
+}
+
+/// Writes the declarations with vertically aligned colons, modes and subtypes
+fn aligned_text(rows: &[&Row], indent: &str, suffix: &str) -> String {
+    let names_width = rows.iter().map(|row| row.names.len()).max().unwrap_or(0);
+    let mode_width = rows
+        .iter()
+        .filter_map(|row| row.mode.as_ref().map(|mode| mode.len()))
+        .max();
+    let mut text = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        text.push_str(&row.leading);
+        text.push_str(&format!("{indent}{:names_width$} : ", row.names));
+        if let Some(mode_width) = mode_width {
+            let mode = row.mode.as_deref().unwrap_or("");
+            text.push_str(&format!("{mode:mode_width$} "));
+        }
+        text.push_str(&row.rest);
+        text.push_str(if i + 1 < rows.len() { ";" } else { suffix });
+        if let Some(comment) = &row.comment {
+            text.push(' ');
+            text.push_str(comment);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn align_action(source: &Source, range: Range, text: String, title: String) -> Option<CodeAction> {
+    if text == text_at(source, range) {
+        return None;
+    }
+    let mut edit = WorkspaceEdit::default();
+    edit.add(source.file_name(), TextEdit::new(range, text));
+    Some(CodeAction {
+        title,
+        kind: CodeActionKind::RefactorRewrite,
+        edit,
+    })
+}
+
+fn interface_list_actions(
+    ctx: &dyn TokenAccess,
+    source: &Source,
+    config: &CodeActionConfig,
+    list: &InterfaceList,
+) -> Vec<CodeAction> {
+    let Some(interface) = interface(ctx, source, list) else {
+        return vec![];
+    };
+    let indent = indentation_of_line(source, interface.range.start.line);
+    let kind = match list.interface_type {
+        InterfaceType::Port => "port",
+        InterfaceType::Generic => "generic",
+        InterfaceType::Parameter => "parameter",
+    };
+    let rows: Vec<&Row> = interface.rows.iter().collect();
+    let mut actions = Vec::new();
+    actions.extend(align_action(
+        source,
+        interface.range,
+        aligned_text(&rows, &indent, &interface.suffix),
+        format!("Align {kind} declarations"),
+    ));
+
+    if list.interface_type == InterfaceType::Port {
+        let order: Vec<&str> = config.port_order.split(',').map(str::trim).collect();
+        let mut reordered = rows.clone();
+        reordered.sort_by_key(|row| {
+            order
+                .iter()
+                .position(|group| *group == row.group)
+                .unwrap_or(order.len())
+        });
+        if reordered
+            .iter()
+            .zip(rows.iter())
+            .any(|(a, b)| !std::ptr::eq(*a, *b))
+        {
+            actions.extend(align_action(
+                source,
+                interface.range,
+                aligned_text(&reordered, &indent, &interface.suffix),
+                "Align and reorder port declarations".to_owned(),
+            ));
+        }
+    }
+    actions
+}
+
+/// Finds the generic or port clause of an entity or component at the cursor
+struct InterfaceListSearcher<'a> {
+    source: &'a Source,
+    config: &'a CodeActionConfig,
+    cursor: Position,
+    result: Vec<CodeAction>,
+}
+
+impl InterfaceListSearcher<'_> {
+    fn check_lists(&mut self, ctx: &dyn TokenAccess, lists: [&Option<InterfaceList>; 2]) -> bool {
+        for list in lists.into_iter().flatten() {
+            if list.span.pos(ctx).range().contains(self.cursor) {
+                self.result = interface_list_actions(ctx, self.source, self.config, list);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Searcher for InterfaceListSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let found = match decl.ast {
+            DeclarationItem::Entity(entity) => {
+                self.check_lists(ctx, [&entity.generic_clause, &entity.port_clause])
+            }
+            DeclarationItem::Component(component) => {
+                self.check_lists(ctx, [&component.generic_list, &component.port_list])
+            }
+            _ => false,
+        };
+        if found {
+            Finished(Found)
+        } else {
+            NotFinished
+        }
+    }
+}
+
+/// Vertically aligns the colons, modes and subtypes of the generic or port declarations
+/// of an entity or component at the cursor.
+/// Ports can additionally be reordered by groups, e.g., clocks first, then inputs and outputs,
+/// as configured by the `port_order` setting.
+/// Declarations that share a line with other code are left as they are.
+pub(super) fn align_interface_actions(
+    root: &DesignRoot,
+    config: &CodeActionConfig,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut searcher = InterfaceListSearcher {
+        source,
+        config,
+        cursor: range.start,
+        result: Vec::new(),
+    };
+    let _ = root.search_source(source, &mut searcher);
+    searcher.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::align_interface_actions;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::config::CodeActionConfig;
+    use crate::Range;
+
+    #[test]
+    fn aligns_and_reorders_port_declarations() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  generic (
+    width : natural := 8; -- The width
+    g : boolean
+  );
+  port (
+    data_out : out bit_vector(width-1 downto 0);
+    -- The clock
+    clk : in bit;
+    rst, en : bit);
+end entity;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let config = CodeActionConfig::default();
+        let cursor = code.s1("data_out").start();
+        let actions =
+            align_interface_actions(&root, &config, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].title, "Align port declarations");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  generic (
+    width : natural := 8; -- The width
+    g : boolean
+  );
+  port (
+    data_out : out bit_vector(width-1 downto 0);
+    -- The clock
+    clk      : in  bit;
+    rst, en  :     bit);
+end entity;"
+        );
+        assert_eq!(actions[1].title, "Align and reorder port declarations");
+        assert_eq!(
+            apply_edits(code.source(), &actions[1]),
+            "\
+entity ent is
+  generic (
+    width : natural := 8; -- The width
+    g : boolean
+  );
+  port (
+    -- The clock
+    clk      : in  bit;
+    rst, en  :     bit;
+    data_out : out bit_vector(width-1 downto 0));
+end entity;"
+        );
+
+        let cursor = code.s1("width").start();
+        let actions =
+            align_interface_actions(&root, &config, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Align generic declarations");
+        assert!(apply_edits(code.source(), &actions[0])
+            .contains("    width : natural := 8; -- The width\n    g     : boolean\n  );"));
+    }
+
+    #[test]
+    fn ignores_declarations_that_share_a_line() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (a : in bit;
+    bb : out bit);
+end entity;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("bb").start();
+        let actions = align_interface_actions(
+            &root,
+            &CodeActionConfig::default(),
+            code.source(),
+            Range::new(cursor, cursor),
+        );
+        assert!(actions.is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::*;
+use crate::code_action::{
+    indentation_of_line, text_at, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit, INDENT,
+};
+use crate::completion::enum_literals;
+use crate::data::HasSource;
+use crate::{HasTokenSpan, Position, Range, Source, TokenAccess};
+
+/// The designator of a choice that is a single enumeration literal
+fn choice_designator(root: &DesignRoot, choice: &Choice) -> Option<Designator> {
+    match choice {
+        Choice::Expression(Expression::Name(name)) => match name.as_ref() {
+            Name::Designator(designator) => Some(match designator.reference.get() {
+                Some(id) => root.get_ent(id).designator().clone(),
+                None => designator.item.clone(),
+            }),
+            _ => None,
+        },
+        Choice::Expression(Expression::Literal(Literal::Character(chr))) => {
+            Some(Designator::Character(*chr))
+        }
+        _ => None,
+    }
+}
+
+/// The alternatives that are inserted into a case statement
+struct MissingChoices {
+    /// The literals that are not covered, as they are written in their declaration
+    literals: Vec<String>,
+    /// Where the alternatives are inserted, i.e., the start of the `end case` line
+    position: Position,
+    when_indent: String,
+    body_indent: String,
+}
+
+impl MissingChoices {
+    fn alternative(&self, choice: &str) -> String {
+        format!(
+            "{}when {choice} =>\n{}null;\n",
+            self.when_indent, self.body_indent
+        )
+    }
+}
+
+/// Finds the innermost case statement at the cursor whose expression
+/// has an enumeration type and that does not cover all literals
+struct CaseStatementSearcher<'a> {
+    root: &'a DesignRoot,
+    cursor: Position,
+    result: Option<MissingChoices>,
+}
+
+impl CaseStatementSearcher<'_> {
+    fn missing_choices(
+        &self,
+        ctx: &dyn TokenAccess,
+        stmt: &LabeledSequentialStatement,
+        case: &CaseStatement,
+    ) -> Option<MissingChoices> {
+        let Expression::Name(name) = &case.expression.item else {
+            return None;
+        };
+        let ent = self.root.get_ent(name.get_suffix_reference()?);
+        let literals = enum_literals(ent)?;
+
+        let mut covered = Vec::new();
+        for alternative in case.alternatives.iter() {
+            for choice in alternative.choices.iter() {
+                // Ranges and others cannot be compared to single literals
+                covered.push(choice_designator(self.root, &choice.item)?);
+            }
+        }
+        let literals: Vec<String> = literals
+            .into_iter()
+            .filter(|literal| !covered.contains(literal.designator()))
+            .map(|literal| match literal.decl_pos() {
+                Some(pos) => text_at(pos.source(), pos.range()),
+                None => literal.designator().to_string(),
+            })
+            .collect();
+        if literals.is_empty() {
+            return None;
+        }
+
+        let source = stmt.statement.span.pos(ctx).source().clone();
+        let case_indent = indentation_of_line(&source, stmt.get_pos(ctx).start().line);
+        let first = case.alternatives.first();
+        let when_line = first.map(|alternative| alternative.span.pos(ctx).start().line);
+        let when_indent = match when_line {
+            Some(line) => indentation_of_line(&source, line),
+            None => format!("{case_indent}{INDENT}"),
+        };
+        // The statements of an alternative may be written on the line of its choices
+        let body_line = first
+            .and_then(|alternative| alternative.item.first())
+            .map(|stmt| stmt.get_pos(ctx).start().line)
+            .filter(|line| Some(*line) != when_line);
+        let body_indent = match body_line {
+            Some(line) => indentation_of_line(&source, line),
+            None => {
+                // Use the same indentation step as from the case statement to its alternatives
+                let step = match when_indent.strip_prefix(&case_indent) {
+                    Some(step) if !step.is_empty() => step,
+                    _ => INDENT,
+                };
+                format!("{when_indent}{step}")
+            }
+        };
+        Some(MissingChoices {
+            literals,
+            position: Position::new(case.end_token.pos(ctx).start().line, 0),
+            when_indent,
+            body_indent,
+        })
+    }
+}
+
+impl Searcher for CaseStatementSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::SequentialStatement(stmt) = decl.ast else {
+            return NotFinished;
+        };
+        let SequentialStatement::Case(case) = &stmt.statement.item else {
+            return NotFinished;
+        };
+        // Nested case statements are visited after the case statement that contains them
+        if stmt.get_pos(ctx).range().contains(self.cursor) {
+            self.result = self.missing_choices(ctx, stmt, case);
+        }
+        NotFinished
+    }
+}
+
+/// Completes a case statement over an enumeration type that does not cover all literals,
+/// either with an alternative for each missing literal or with an `others` alternative
+pub(super) fn case_choices_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut searcher = CaseStatementSearcher {
+        root,
+        cursor: range.start,
+        result: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    let Some(missing) = searcher.result else {
+        return vec![];
+    };
+
+    let action = |title: String, text: String| {
+        let mut edit = WorkspaceEdit::default();
+        edit.add(source.file_name(), TextEdit::insert(missing.position, text));
+        CodeAction {
+            title,
+            kind: CodeActionKind::QuickFix,
+            edit,
+        }
+    };
+    let text = missing
+        .literals
+        .iter()
+        .map(|literal| missing.alternative(literal))
+        .collect();
+    vec![
+        action("Add missing case choices".to_owned(), text),
+        action(
+            "Add 'when others' choice".to_owned(),
+            missing.alternative("others"),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::case_choices_actions;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::Range;
+
+    #[test]
+    fn adds_missing_case_choices() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  type State_t is (Idle, Busy, Done);
+  signal state : State_t;
+begin
+  process
+  begin
+    case state is
+      when idle =>
+        state <= busy;
+    end case;
+    wait;
+  end process;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("case state").start();
+        let actions = case_choices_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].title, "Add missing case choices");
+        assert!(apply_edits(code.source(), &actions[0]).contains(
+            "\
+      when idle =>
+        state <= busy;
+      when Busy =>
+        null;
+      when Done =>
+        null;
+    end case;"
+        ));
+        assert_eq!(actions[1].title, "Add 'when others' choice");
+        assert!(apply_edits(code.source(), &actions[1]).contains(
+            "\
+        state <= busy;
+      when others =>
+        null;
+    end case;"
+        ));
+    }
+
+    #[test]
+    fn adds_missing_character_literals() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  type tri_t is ('0', '1', 'Z');
+begin
+  process
+    variable v : tri_t;
+  begin
+    case v is
+      when '0' | '1' => null;
+    end case;
+    case v is
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("case v").start();
+        let actions = case_choices_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 2);
+        assert!(apply_edits(code.source(), &actions[0])
+            .contains("      when '0' | '1' => null;\n      when 'Z' =>\n        null;\n"));
+
+        // Case statements with an others choice are complete
+        let cursor = code.s("case v", 2).start();
+        assert!(case_choices_actions(&root, code.source(), Range::new(cursor, cursor)).is_empty());
+    }
+}
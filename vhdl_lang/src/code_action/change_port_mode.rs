@@ -0,0 +1,383 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::{base_reference, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use crate::data::HasSource;
+use crate::named_entity::InterfaceMode;
+use crate::standard::VHDLStandard;
+use crate::{AnyEntKind, Design, EntRef, EntityId, Position, Range, Source, TokenAccess};
+
+/// Finds the target of a signal assignment at the cursor
+struct TargetSearcher {
+    cursor: Position,
+    result: Option<EntityId>,
+}
+
+impl TargetSearcher {
+    fn check_target(&mut self, ctx: &dyn TokenAccess, target: &WithTokenSpan<Target>) {
+        if let Target::Name(name) = &target.item {
+            if target.pos(ctx).range().contains(self.cursor) {
+                self.result = base_reference(name);
+            }
+        }
+    }
+}
+
+impl Searcher for TargetSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::ConcurrentStatement(stmt) => {
+                if let ConcurrentStatement::Assignment(assignment) = &stmt.statement.item {
+                    self.check_target(ctx, &assignment.assignment.target);
+                }
+            }
+            DeclarationItem::SequentialStatement(stmt) => match &stmt.statement.item {
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.check_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalForceAssignment(assignment) => {
+                    self.check_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalReleaseAssignment(assignment) => {
+                    self.check_target(ctx, &assignment.target)
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        if self.result.is_some() {
+            Finished(Found)
+        } else {
+            NotFinished
+        }
+    }
+}
+
+/// Finds the edit position of the mode in the declaration of a port.
+/// The mode is replaced when it is written explicitly and inserted otherwise.
+struct ModeSearcher {
+    port: EntityId,
+    result: Option<Range>,
+}
+
+impl Searcher for ModeSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::InterfaceObject(object) = decl.ast else {
+            return NotFinished;
+        };
+        if decl.reference.get() != Some(self.port) {
+            return NotFinished;
+        }
+        // Changing the mode would also change the mode of the other ports of the declaration
+        if object.idents.len() != 1 {
+            return Finished(Found);
+        }
+        if let ModeIndication::Simple(mode) = &object.mode {
+            self.result = Some(match &mode.mode {
+                Some(mode) => mode.token.pos(ctx).range(),
+                None => {
+                    let start = mode
+                        .subtype_indication
+                        .type_mark
+                        .span
+                        .start_token
+                        .pos(ctx)
+                        .start();
+                    Range::new(start, start)
+                }
+            });
+        }
+        Finished(Found)
+    }
+}
+
+/// Returns the port when the named entity is a port of the given mode of an entity
+fn port_of_entity(ent: EntRef<'_>, mode: Mode) -> Option<EntRef<'_>> {
+    let AnyEntKind::Object(object) = ent.kind() else {
+        return None;
+    };
+    if object.mode() != Some(&InterfaceMode::Simple(mode)) {
+        return None;
+    }
+    if !matches!(ent.parent?.kind(), AnyEntKind::Design(Design::Entity(..))) {
+        return None;
+    }
+    Some(ent)
+}
+
+/// Returns the port of mode `out` of an entity that is read at the cursor
+fn read_out_port<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<EntRef<'a>> {
+    let (pos, ent) = root.item_at_cursor(source, cursor)?;
+    if ent.decl_pos() == Some(&pos) {
+        return None;
+    }
+    port_of_entity(ent, Mode::Out)
+}
+
+/// Offers to change the mode of a port of an entity when it is used illegally:
+/// an `in` port that is the target of a signal assignment becomes `out`, `buffer` or `inout`
+/// and an `out` port that is read before VHDL-2008 becomes `buffer` or `inout`.
+pub(super) fn change_port_mode_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut searcher = TargetSearcher {
+        cursor: range.start,
+        result: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    let (port, modes) = match searcher.result {
+        Some(id) => (
+            port_of_entity(root.get_ent(id), Mode::In),
+            [Mode::Out, Mode::Buffer, Mode::InOut].as_slice(),
+        ),
+        None if root.standard() < VHDLStandard::VHDL2008 => (
+            read_out_port(root, source, range.start),
+            [Mode::Buffer, Mode::InOut].as_slice(),
+        ),
+        None => return vec![],
+    };
+    let Some(port) = port else {
+        return vec![];
+    };
+    let Some(decl_pos) = port.decl_pos() else {
+        return vec![];
+    };
+    let mut searcher = ModeSearcher {
+        port: port.id(),
+        result: None,
+    };
+    let _ = root.search_source(decl_pos.source(), &mut searcher);
+    let Some(mode_range) = searcher.result else {
+        return vec![];
+    };
+    let explicit = mode_range.start != mode_range.end;
+    modes
+        .iter()
+        .map(|mode| {
+            let mut edit = WorkspaceEdit::default();
+            let new_text = if explicit {
+                mode.to_string()
+            } else {
+                format!("{mode} ")
+            };
+            edit.add(
+                decl_pos.source().file_name(),
+                TextEdit::new(mode_range, new_text),
+            );
+            CodeAction {
+                title: format!("Change mode of port '{}' to {mode}", port.designator()),
+                kind: CodeActionKind::QuickFix,
+                edit,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction};
+    use crate::syntax::test::Code;
+    use crate::{Config, Range, VHDLStandard};
+
+    fn mode_actions(root: &crate::analysis::DesignRoot, code: &Code) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(code.start(), code.start()),
+        )
+        .into_iter()
+        .filter(|action| action.title.starts_with("Change mode"))
+        .collect()
+    }
+
+    #[test]
+    fn changes_explicit_mode_of_port() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    a : in bit;
+    b : in bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  process
+  begin
+    a <= b;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = mode_actions(&root, &code.s("a", 3));
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Change mode of port 'a' to out",
+                "Change mode of port 'a' to buffer",
+                "Change mode of port 'a' to inout"
+            ]
+        );
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  port (
+    a : out bit;
+    b : in bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  process
+  begin
+    a <= b;
+    wait;
+  end process;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn inserts_mode_of_port_with_implicit_mode() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    a : bit_vector(1 downto 0)
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  a(0) <= '1';
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = mode_actions(&root, &code.s1("a(0)"));
+        assert_eq!(
+            apply_edits(code.source(), &actions[1]),
+            "\
+entity ent is
+  port (
+    a : buffer bit_vector(1 downto 0)
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  a(0) <= '1';
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn no_action_for_ports_sharing_a_declaration() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    a, b : in bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  a <= '1';
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(mode_actions(&root, &code.s("a", 2)).is_empty());
+    }
+
+    #[test]
+    fn changes_mode_of_out_port_that_is_read_before_vhdl2008() {
+        let source = "\
+entity ent is
+  port (
+    q : out bit;
+    r : out bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  q <= '1';
+  r <= not q;
+end architecture;
+";
+        let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+        let code = builder.code("libname", source);
+        let (root, _) = builder.get_analyzed_root();
+
+        assert!(mode_actions(&root, &code.s("q", 2)).is_empty());
+        assert!(mode_actions(&root, &code.s1("r <=")).is_empty());
+        let actions = mode_actions(&root, &code.s("q", 3));
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Change mode of port 'q' to buffer",
+                "Change mode of port 'q' to inout"
+            ]
+        );
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  port (
+    q : buffer bit;
+    r : out bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  q <= '1';
+  r <= not q;
+end architecture;
+"
+        );
+
+        // Reading a port of mode out is allowed since VHDL-2008
+        let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2008);
+        let code = builder.code("libname", source);
+        let (root, _) = builder.get_analyzed_root();
+        assert!(mode_actions(&root, &code.s("q", 3)).is_empty());
+    }
+}
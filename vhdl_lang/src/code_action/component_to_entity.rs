@@ -0,0 +1,264 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::generate_component::ComponentDeclarationSearcher;
+use crate::code_action::{removal_range, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use crate::data::{HasSource, Symbol};
+use crate::syntax::Kind;
+use crate::{AnyEntKind, EntityId, HasTokenSpan, Position, Range, Source, TokenAccess};
+use std::ops::Deref;
+
+/// An instantiation of a component at the cursor
+struct ComponentInstance {
+    component: EntityId,
+    /// The range of the instantiated unit, including the optional `component` keyword
+    range: Range,
+}
+
+struct ComponentInstanceSearcher<'a> {
+    root: &'a DesignRoot,
+    cursor: Position,
+    result: Option<ComponentInstance>,
+}
+
+impl Searcher for ComponentInstanceSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::ConcurrentStatement(stmt) = decl.ast else {
+            return NotFinished;
+        };
+        let ConcurrentStatement::Instance(inst) = &stmt.statement.item else {
+            return NotFinished;
+        };
+        if !stmt.get_pos(ctx).range().contains(self.cursor) {
+            return NotFinished;
+        }
+        let InstantiatedUnit::Component(name) = &inst.unit else {
+            return Finished(Found);
+        };
+        let Some(component) = name.item.get_suffix_reference() else {
+            return Finished(Found);
+        };
+        if !matches!(
+            self.root.get_ent(component).kind(),
+            AnyEntKind::Component(_)
+        ) {
+            return Finished(Found);
+        }
+        let keyword = name.span.start_token - 1;
+        let start = if ctx.index(keyword).kind == Kind::Component {
+            keyword
+        } else {
+            name.span.start_token
+        };
+        self.result = Some(ComponentInstance {
+            component,
+            range: ctx.get_span(start, name.span.end_token).range(),
+        });
+        Finished(Found)
+    }
+}
+
+/// Returns the name of the only architecture of an entity
+fn only_architecture(root: &DesignRoot, library: &Symbol, entity: &Symbol) -> Option<Symbol> {
+    let mut architectures = root
+        .get_lib(library)?
+        .secondary_units(entity)
+        .filter(|unit| matches!(unit.kind(), AnyKind::Secondary(SecondaryKind::Architecture)))
+        .filter_map(|unit| unit.unit_id().secondary_name().cloned());
+    let architecture = architectures.next()?;
+    architectures.next().is_none().then_some(architecture)
+}
+
+/// Rewrites the instantiation of a component at the cursor into a direct instantiation of
+/// the entity that the component is bound to by default, i.e., the entity of the same name
+/// in the working library.
+/// The architecture is added when the entity has a single architecture.
+/// When the instantiation is the only use of a component that is declared in the same file,
+/// a second action also removes the component declaration.
+pub(super) fn component_to_entity_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let ctx: &dyn TokenAccess = &unit.tokens;
+        if !data.get_pos(ctx).range().contains(range.start) {
+            continue;
+        }
+        let mut searcher = ComponentInstanceSearcher {
+            root,
+            cursor: range.start,
+            result: None,
+        };
+        let _ = data.deref().search(ctx, &mut searcher);
+        let Some(instance) = searcher.result else {
+            return vec![];
+        };
+
+        let component = root.get_ent(instance.component);
+        let Designator::Identifier(name) = component.designator() else {
+            return vec![];
+        };
+        let library = unit.unit_id().library_name();
+        let Some(entity) = root.get_design_entity(library, name) else {
+            return vec![];
+        };
+        let mut text = format!("entity work.{}", entity.designator());
+        if let Some(architecture) = only_architecture(root, library, name) {
+            text.push_str(&format!("({architecture})"));
+        }
+
+        let mut edit = WorkspaceEdit::default();
+        edit.add(source.file_name(), TextEdit::new(instance.range, text));
+        let mut actions = vec![CodeAction {
+            title: "Convert to entity instantiation".to_owned(),
+            kind: CodeActionKind::RefactorRewrite,
+            edit: edit.clone(),
+        }];
+
+        let decl_pos = component.decl_pos().filter(|pos| pos.source() == source);
+        if decl_pos.is_some() && root.find_all_usages(component).len() == 1 {
+            let mut searcher = ComponentDeclarationSearcher {
+                component: instance.component,
+                result: None,
+            };
+            let _ = root.search_source(source, &mut searcher);
+            if let Some(pos) = searcher.result {
+                edit.add(
+                    source.file_name(),
+                    TextEdit::delete(removal_range(source, pos.range())),
+                );
+                actions.push(CodeAction {
+                    title: format!(
+                        "Convert to entity instantiation and remove component '{}'",
+                        component.designator()
+                    ),
+                    kind: CodeActionKind::RefactorRewrite,
+                    edit,
+                });
+            }
+        }
+        return actions;
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::component_to_entity_actions;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::Range;
+
+    #[test]
+    fn converts_component_instantiation_to_entity_instantiation() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (clk : in bit);
+end entity;
+
+architecture rtl of child is
+begin
+end architecture;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+  component child is
+    port (clk : in bit);
+  end component;
+begin
+  inst0: component child port map (clk => clk);
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("inst0").start();
+        let actions = component_to_entity_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].title, "Convert to entity instantiation");
+        assert!(apply_edits(code.source(), &actions[0])
+            .contains("inst0: entity work.child(rtl) port map (clk => clk);"));
+        assert_eq!(
+            actions[1].title,
+            "Convert to entity instantiation and remove component 'child'"
+        );
+        assert_eq!(
+            apply_edits(code.source(), &actions[1]),
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+begin
+  inst0: entity work.child(rtl) port map (clk => clk);
+end architecture;"
+        );
+    }
+
+    #[test]
+    fn keeps_component_that_is_used_elsewhere() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (clk : in bit);
+end entity;
+
+architecture rtl of child is
+begin
+end architecture;
+
+architecture sim of child is
+begin
+end architecture;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+  component child is
+    port (clk : in bit);
+  end component;
+begin
+  inst0: child port map (clk => clk);
+  inst1: child port map (clk => clk);
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("inst1").start();
+        let actions = component_to_entity_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        // The architecture is ambiguous
+        assert!(apply_edits(code.source(), &actions[0])
+            .contains("inst1: entity work.child port map (clk => clk);"));
+    }
+}
@@ -0,0 +1,356 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use crate::data::{HasSource, Symbol};
+use crate::{HasTokenSpan, Position, Range, Source, TokenAccess};
+use fnv::FnvHashMap;
+use std::ops::Deref;
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum UnitKind {
+    Entity,
+    Package,
+}
+
+impl UnitKind {
+    fn describe(&self) -> &'static str {
+        match self {
+            UnitKind::Entity => "entity",
+            UnitKind::Package => "package",
+        }
+    }
+}
+
+/// A primary unit that is referred to but does not exist in its library
+struct MissingUnit {
+    library: Symbol,
+    name: Symbol,
+    kind: UnitKind,
+}
+
+/// Returns the library and unit name of a name such as `lib.foo`
+/// if the library exists but does not contain a primary unit `foo`.
+fn missing_unit(
+    root: &DesignRoot,
+    work_library: &Symbol,
+    name: &Name,
+    kind: UnitKind,
+) -> Option<MissingUnit> {
+    let Name::Selected(prefix, suffix) = name else {
+        return None;
+    };
+    let Name::Designator(library) = &prefix.item else {
+        return None;
+    };
+    let Designator::Identifier(library) = &library.item else {
+        return None;
+    };
+    let Designator::Identifier(name) = &suffix.item.item else {
+        return None;
+    };
+    let library = if *library == root.symbol_utf8("work") {
+        work_library.clone()
+    } else {
+        library.clone()
+    };
+    if root.get_lib(&library)?.primary_unit(name).is_some() {
+        return None;
+    }
+    Some(MissingUnit {
+        library,
+        name: name.clone(),
+        kind,
+    })
+}
+
+/// Finds the entity instantiation at the cursor that refers to a missing entity
+struct InstanceSearcher<'a> {
+    root: &'a DesignRoot,
+    work_library: &'a Symbol,
+    cursor: Position,
+    result: Option<MissingUnit>,
+}
+
+impl Searcher for InstanceSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::ConcurrentStatement(stmt) = decl.ast else {
+            return NotFinished;
+        };
+        let ConcurrentStatement::Instance(inst) = &stmt.statement.item else {
+            return NotFinished;
+        };
+        let InstantiatedUnit::Entity(name, _) = &inst.unit else {
+            return NotFinished;
+        };
+        if !name.pos(ctx).range().contains(self.cursor) {
+            return NotFinished;
+        }
+        self.result = missing_unit(self.root, self.work_library, &name.item, UnitKind::Entity);
+        Finished(Found)
+    }
+}
+
+/// Finds a use clause of the context clause at the cursor that refers to a missing package,
+/// i.e., `use lib.pkg.all` or `use lib.pkg.name`
+fn missing_package_in_context_clause(
+    root: &DesignRoot,
+    work_library: &Symbol,
+    ctx: &dyn TokenAccess,
+    context_clause: &ContextClause,
+    cursor: Position,
+) -> Option<MissingUnit> {
+    for item in context_clause {
+        let ContextItem::Use(use_clause) = item else {
+            continue;
+        };
+        for name in use_clause.name_list.iter() {
+            if !name.pos(ctx).range().contains(cursor) {
+                continue;
+            }
+            let package_name = match &name.item {
+                Name::SelectedAll(prefix) => &prefix.item,
+                Name::Selected(prefix, _) => &prefix.item,
+                _ => continue,
+            };
+            return missing_unit(root, work_library, package_name, UnitKind::Package);
+        }
+    }
+    None
+}
+
+/// The directory where new units of a library are placed.
+/// This is the directory of the current file when the unit is created in its library.
+/// Otherwise, it is the directory that contains most of the files of the library.
+fn library_directory(
+    root: &DesignRoot,
+    library: &Symbol,
+    work_library: &Symbol,
+    source: &Source,
+) -> Option<PathBuf> {
+    let own_directory = source.file_name().parent()?.to_owned();
+    if library == work_library {
+        return Some(own_directory);
+    }
+    let mut directories: FnvHashMap<PathBuf, usize> = FnvHashMap::default();
+    for unit in root.get_lib(library)?.units() {
+        if let Some(directory) = unit.source().file_name().parent() {
+            *directories.entry(directory.to_owned()).or_default() += 1;
+        }
+    }
+    Some(
+        directories
+            .into_iter()
+            .max_by(|(lhs_dir, lhs), (rhs_dir, rhs)| lhs.cmp(rhs).then(rhs_dir.cmp(lhs_dir)))
+            .map(|(directory, _)| directory)
+            .unwrap_or(own_directory),
+    )
+}
+
+fn stub_text(unit: &MissingUnit) -> String {
+    let kind = unit.kind.describe();
+    format!("{kind} {name} is\nend {kind} {name};\n", name = unit.name)
+}
+
+/// Creates a stub entity or package for an entity instantiation or use clause
+/// that refers to a primary unit that does not exist.
+/// The new file is placed in the directory of the library of the missing unit.
+/// Note that the new file is only analyzed if it is part of the project configuration.
+pub(super) fn create_design_unit_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        if !data.get_pos(&unit.tokens).range().contains(range.start)
+            && !data.context_clause().is_some_and(|items| {
+                items
+                    .iter()
+                    .any(|item| item.get_pos(&unit.tokens).range().contains(range.start))
+            })
+        {
+            continue;
+        }
+        let work_library = unit.unit_id().library_name();
+        let missing = data
+            .context_clause()
+            .and_then(|items| {
+                missing_package_in_context_clause(
+                    root,
+                    work_library,
+                    &unit.tokens,
+                    items,
+                    range.start,
+                )
+            })
+            .or_else(|| {
+                let mut searcher = InstanceSearcher {
+                    root,
+                    work_library,
+                    cursor: range.start,
+                    result: None,
+                };
+                let _ = data.deref().search(&unit.tokens, &mut searcher);
+                searcher.result
+            });
+        let Some(missing) = missing else {
+            return vec![];
+        };
+        let Some(directory) = library_directory(root, &missing.library, work_library, source)
+        else {
+            return vec![];
+        };
+        let extension = source
+            .file_name()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("vhd");
+        let file_name = directory.join(format!("{}.{extension}", missing.name));
+        if file_name.exists() {
+            return vec![];
+        }
+        let mut edit = WorkspaceEdit::default();
+        edit.create(&file_name);
+        edit.add(
+            &file_name,
+            TextEdit::insert(Position::new(0, 0), stub_text(&missing)),
+        );
+        return vec![CodeAction {
+            title: format!(
+                "Create {} '{}' in library '{}'",
+                missing.kind.describe(),
+                missing.name,
+                missing.library
+            ),
+            kind: CodeActionKind::QuickFix,
+            edit,
+        }];
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::code_action::{list_code_actions, CodeAction, CodeActionKind, TextEdit};
+    use crate::syntax::test::Code;
+    use crate::{Config, Position, Range};
+
+    fn create_actions(root: &crate::analysis::DesignRoot, code: &Code) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(code.start(), code.start()),
+        )
+        .into_iter()
+        .filter(|action| action.kind == CodeActionKind::QuickFix)
+        .collect()
+    }
+
+    #[test]
+    fn creates_missing_entity() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  inst : entity work.missing;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = create_actions(&root, &code.s1("missing"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Create entity 'missing' in library 'libname'"
+        );
+        let file_name = code.source().file_name().with_file_name("missing.vhd");
+        assert_eq!(actions[0].edit.created_files, vec![file_name.clone()]);
+        assert_eq!(
+            actions[0].edit.changes[&file_name],
+            vec![TextEdit::insert(
+                Position::new(0, 0),
+                "entity missing is\nend entity missing;\n"
+            )]
+        );
+    }
+
+    #[test]
+    fn creates_missing_package_in_other_library() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "otherlib",
+            "\
+package existing is
+end package;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+library otherlib;
+use otherlib.missing_pkg.all;
+
+entity top is
+end entity;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = create_actions(&root, &code.s1("missing_pkg"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Create package 'missing_pkg' in library 'otherlib'"
+        );
+        assert_eq!(
+            actions[0].edit.created_files,
+            vec![code.source().file_name().with_file_name("missing_pkg.vhd")]
+        );
+    }
+
+    #[test]
+    fn no_action_for_existing_unit() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  inst : entity work.ent;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(create_actions(&root, &code.s("ent", 4)).is_empty());
+    }
+}
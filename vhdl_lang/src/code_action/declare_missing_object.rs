@@ -0,0 +1,725 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::{
+    indentation_of_line, text_at, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit, INDENT,
+};
+use crate::data::HasSource;
+use crate::named_entity::{Reference, TypeEnt};
+use crate::{
+    AnyEntKind, EntRef, EntityId, HasTokenSpan, Position, Range, Source, SrcPos, TokenAccess,
+    TokenId, TokenSpan, Type,
+};
+
+/// A declarative part that contains the cursor
+struct DeclarativeRegion {
+    /// Processes and subprograms contain sequential statements
+    /// and may declare variables, but not signals
+    sequential: bool,
+    /// The line of the `begin` keyword that ends the declarative part
+    begin_line: u32,
+    indent: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Usage {
+    SignalTarget,
+    VariableTarget,
+    Expression,
+}
+
+/// What the type of the missing object is inferred from
+#[derive(Clone, Copy)]
+enum TypeHint<'a> {
+    /// An object of the same type
+    Object(EntRef<'a>),
+    Integer,
+    Real,
+}
+
+struct MissingName<'a> {
+    pos: SrcPos,
+    usage: Usage,
+    hint: Option<TypeHint<'a>>,
+}
+
+fn is_unresolved(name: &Name) -> bool {
+    matches!(name, Name::Designator(designator) if designator.reference.get().is_none())
+}
+
+fn is_relational(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::EQ
+            | Operator::NE
+            | Operator::LT
+            | Operator::LTE
+            | Operator::GT
+            | Operator::GTE
+            | Operator::QueEQ
+            | Operator::QueNE
+            | Operator::QueLT
+            | Operator::QueLTE
+            | Operator::QueGT
+            | Operator::QueGTE
+    )
+}
+
+/// Operators whose operands have the type of the result
+fn preserves_type(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::And
+            | Operator::Or
+            | Operator::Nand
+            | Operator::Nor
+            | Operator::Xor
+            | Operator::Xnor
+            | Operator::Plus
+            | Operator::Minus
+            | Operator::Times
+            | Operator::Div
+            | Operator::Mod
+            | Operator::Rem
+    )
+}
+
+struct MissingObjectSearcher<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    cursor: Position,
+    regions: Vec<DeclarativeRegion>,
+    missing: Option<MissingName<'a>>,
+}
+
+impl<'a> MissingObjectSearcher<'a> {
+    fn add_region(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        span: TokenSpan,
+        sequential: bool,
+        decl: &[WithTokenSpan<Declaration>],
+        begin_token: TokenId,
+        first_statement: Option<SrcPos>,
+    ) {
+        if !span.pos(ctx).range().contains(self.cursor) {
+            return;
+        }
+        let begin_line = ctx.get_pos(begin_token).start().line;
+        // Declarations are indented like the existing declarations or statements
+        let indent = match decl.first().map(|decl| decl.pos(ctx)).or(first_statement) {
+            Some(pos) => indentation_of_line(self.source, pos.start().line),
+            None => format!("{}{INDENT}", indentation_of_line(self.source, begin_line)),
+        };
+        self.regions.push(DeclarativeRegion {
+            sequential,
+            begin_line,
+            indent,
+        });
+    }
+
+    fn object_of(&self, reference: &Reference) -> Option<EntRef<'a>> {
+        let ent = self.root.get_ent(reference.get()?);
+        matches!(ent.kind(), AnyEntKind::Object(_)).then_some(ent)
+    }
+
+    /// Infers the type of an expression from the objects and literals that it consists of
+    fn infer(&self, expr: &Expression) -> Option<TypeHint<'a>> {
+        match expr {
+            Expression::Name(name) => match name.as_ref() {
+                Name::Designator(designator) => {
+                    self.object_of(&designator.reference).map(TypeHint::Object)
+                }
+                _ => None,
+            },
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(_))) => {
+                Some(TypeHint::Integer)
+            }
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Real(_))) => {
+                Some(TypeHint::Real)
+            }
+            Expression::Binary(op, lhs, rhs) if preserves_type(op.item.item) => {
+                self.infer(&lhs.item).or_else(|| self.infer(&rhs.item))
+            }
+            Expression::Unary(op, operand) if preserves_type(op.item.item) => {
+                self.infer(&operand.item)
+            }
+            Expression::Parenthesized(expr) => self.infer(&expr.item),
+            _ => None,
+        }
+    }
+
+    /// Finds the type hint of an unresolved name at the cursor within an expression.
+    /// `context` is the type that the expression is expected to have.
+    fn hint_within(
+        &self,
+        ctx: &dyn TokenAccess,
+        expr: &WithTokenSpan<Expression>,
+        context: Option<TypeHint<'a>>,
+    ) -> Option<Option<TypeHint<'a>>> {
+        if !expr.pos(ctx).range().contains(self.cursor) {
+            return None;
+        }
+        match &expr.item {
+            Expression::Name(name) if is_unresolved(name) => Some(context),
+            Expression::Binary(op, lhs, rhs) => {
+                let op = op.item.item;
+                let context = if is_relational(op) {
+                    None
+                } else if preserves_type(op) {
+                    context
+                } else {
+                    return None;
+                };
+                self.hint_within(ctx, lhs, self.infer(&rhs.item).or(context))
+                    .or_else(|| self.hint_within(ctx, rhs, self.infer(&lhs.item).or(context)))
+            }
+            Expression::Unary(op, operand) if preserves_type(op.item.item) => {
+                self.hint_within(ctx, operand, context)
+            }
+            Expression::Parenthesized(expr) => self.hint_within(ctx, expr, context),
+            _ => None,
+        }
+    }
+
+    fn search_assignment<T>(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        target: &WithTokenSpan<Target>,
+        rhs: &AssignmentRightHand<T>,
+        value: impl Fn(&T) -> Option<&WithTokenSpan<Expression>>,
+        usage: Usage,
+    ) {
+        let values: Vec<&WithTokenSpan<Expression>> = match rhs {
+            AssignmentRightHand::Simple(item) => value(item).into_iter().collect(),
+            AssignmentRightHand::Conditional(conditionals) => conditionals
+                .conditionals
+                .iter()
+                .map(|conditional| &conditional.item)
+                .chain(conditionals.else_item.as_ref().map(|(item, _)| item))
+                .filter_map(&value)
+                .collect(),
+            AssignmentRightHand::Selected(selection) => selection
+                .alternatives
+                .iter()
+                .filter_map(|alternative| value(&alternative.item))
+                .collect(),
+        };
+
+        let Target::Name(target_name) = &target.item else {
+            return;
+        };
+        if is_unresolved(target_name) && target.pos(ctx).range().contains(self.cursor) {
+            self.missing = Some(MissingName {
+                pos: target.pos(ctx),
+                usage,
+                hint: values.iter().find_map(|value| self.infer(&value.item)),
+            });
+            return;
+        }
+
+        let context = match target_name {
+            Name::Designator(designator) => self.object_of(&designator.reference),
+            _ => None,
+        }
+        .map(TypeHint::Object);
+        for value in values {
+            if let Some(hint) = self.hint_within(ctx, value, context) {
+                if let Some(pos) = self.unresolved_pos_at(ctx, value) {
+                    self.missing = Some(MissingName {
+                        pos,
+                        usage: Usage::Expression,
+                        hint,
+                    });
+                }
+                return;
+            }
+        }
+    }
+
+    /// The position of the innermost unresolved name of an expression at the cursor
+    fn unresolved_pos_at(
+        &self,
+        ctx: &dyn TokenAccess,
+        expr: &WithTokenSpan<Expression>,
+    ) -> Option<SrcPos> {
+        if !expr.pos(ctx).range().contains(self.cursor) {
+            return None;
+        }
+        match &expr.item {
+            Expression::Name(_) => Some(expr.pos(ctx)),
+            Expression::Binary(_, lhs, rhs) => self
+                .unresolved_pos_at(ctx, lhs)
+                .or_else(|| self.unresolved_pos_at(ctx, rhs)),
+            Expression::Unary(_, operand) => self.unresolved_pos_at(ctx, operand),
+            Expression::Parenthesized(expr) => self.unresolved_pos_at(ctx, expr),
+            _ => None,
+        }
+    }
+
+    /// Returns true when the text at the position is a name of its own
+    /// rather than part of a selected name, a call, an indexed name or a formal
+    fn is_standalone_identifier(&self, pos: &SrcPos) -> bool {
+        let range = pos.range();
+        if range.start.line != range.end.line {
+            return false;
+        }
+        let text = text_at(self.source, range);
+        let before = text_at(
+            self.source,
+            Range::new(Position::new(range.start.line, 0), range.start),
+        );
+        let after = text_at(
+            self.source,
+            Range::new(range.end, Position::new(range.end.line, u32::MAX)),
+        );
+        let after = after.trim_start();
+        text.starts_with(|chr: char| chr.is_ascii_alphabetic())
+            && !before.trim_end().ends_with('.')
+            && !after.starts_with(['.', '('])
+            && !after.starts_with("=>")
+    }
+}
+
+impl<'a> Searcher for MissingObjectSearcher<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Architecture(arch) => {
+                self.add_region(
+                    ctx,
+                    arch.span,
+                    false,
+                    &arch.decl,
+                    arch.begin_token,
+                    arch.statements.first().map(|stmt| stmt.get_pos(ctx)),
+                );
+            }
+            DeclarationItem::Subprogram(body) => {
+                self.add_region(
+                    ctx,
+                    body.span,
+                    true,
+                    &body.declarations,
+                    body.begin_token,
+                    body.statements.first().map(|stmt| stmt.get_pos(ctx)),
+                );
+            }
+            DeclarationItem::ConcurrentStatement(stmt) => match &stmt.statement.item {
+                ConcurrentStatement::Process(process) => {
+                    self.add_region(
+                        ctx,
+                        stmt.statement.span,
+                        true,
+                        &process.decl,
+                        process.begin_token,
+                        process.statements.first().map(|stmt| stmt.get_pos(ctx)),
+                    );
+                }
+                ConcurrentStatement::Block(block) => {
+                    self.add_region(
+                        ctx,
+                        stmt.statement.span,
+                        false,
+                        &block.decl,
+                        block.begin_token,
+                        block.statements.first().map(|stmt| stmt.get_pos(ctx)),
+                    );
+                }
+                ConcurrentStatement::Assignment(assign)
+                    if self.missing.is_none()
+                        && stmt.statement.pos(ctx).range().contains(self.cursor) =>
+                {
+                    let assignment = &assign.assignment;
+                    self.search_assignment(
+                        ctx,
+                        &assignment.target,
+                        &assignment.rhs,
+                        waveform_value,
+                        Usage::SignalTarget,
+                    );
+                }
+                _ => {}
+            },
+            DeclarationItem::SequentialStatement(stmt) => {
+                if self.missing.is_some() || !stmt.statement.pos(ctx).range().contains(self.cursor)
+                {
+                    return NotFinished;
+                }
+                match &stmt.statement.item {
+                    SequentialStatement::SignalAssignment(assignment) => {
+                        self.search_assignment(
+                            ctx,
+                            &assignment.target,
+                            &assignment.rhs,
+                            waveform_value,
+                            Usage::SignalTarget,
+                        );
+                    }
+                    SequentialStatement::VariableAssignment(assignment) => {
+                        self.search_assignment(
+                            ctx,
+                            &assignment.target,
+                            &assignment.rhs,
+                            expression_value,
+                            Usage::VariableTarget,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if self.missing.is_none()
+            && reference.get().is_none()
+            && pos.range().contains(self.cursor)
+            && self.is_standalone_identifier(pos)
+        {
+            self.missing = Some(MissingName {
+                pos: pos.clone(),
+                usage: Usage::Expression,
+                hint: None,
+            });
+            return Finished(Found);
+        }
+        NotFinished
+    }
+}
+
+fn waveform_value(waveform: &Waveform) -> Option<&WithTokenSpan<Expression>> {
+    match waveform {
+        Waveform::Elements(elements) => elements.first().map(|element| &element.value),
+        Waveform::Unaffected(_) => None,
+    }
+}
+
+fn expression_value(expr: &WithTokenSpan<Expression>) -> Option<&WithTokenSpan<Expression>> {
+    Some(expr)
+}
+
+/// Finds the subtype indication of an object as it was written in its declaration
+struct SubtypeSearcher {
+    object: EntityId,
+    result: Option<String>,
+}
+
+impl Searcher for SubtypeSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if decl.reference.get() != Some(self.object) {
+            return NotFinished;
+        }
+        let subtype = match decl.ast {
+            DeclarationItem::Object(object) => &object.subtype_indication,
+            DeclarationItem::InterfaceObject(object) => match &object.mode {
+                ModeIndication::Simple(mode) => &mode.subtype_indication,
+                ModeIndication::View(_) => return Finished(Found),
+            },
+            _ => return NotFinished,
+        };
+        let end_token = match &subtype.constraint {
+            Some(constraint) => constraint.span.end_token,
+            None => subtype.type_mark.span.end_token,
+        };
+        let pos = TokenSpan::new(subtype.type_mark.span.start_token, end_token).pos(ctx);
+        self.result = Some(text_at(pos.source(), pos.range()));
+        Finished(Found)
+    }
+}
+
+/// Returns the subtype indication of an object as it was written in its declaration
+pub(super) fn declared_subtype(root: &DesignRoot, ent: EntRef<'_>) -> Option<String> {
+    let mut searcher = SubtypeSearcher {
+        object: ent.id(),
+        result: None,
+    };
+    let _ = root.search_source(ent.decl_pos()?.source(), &mut searcher);
+    searcher.result
+}
+
+/// A value of a type that can be used to initialize a constant
+fn default_value(typ: TypeEnt<'_>) -> Option<String> {
+    let base = typ.base_type();
+    match base.kind() {
+        Type::Integer => Some("0".to_owned()),
+        Type::Real => Some("0.0".to_owned()),
+        Type::Enum(_) => Some(format!("{}'left", base.designator())),
+        Type::Array { elem_type, .. } => {
+            default_value(*elem_type).map(|value| format!("(others => {value})"))
+        }
+        _ => None,
+    }
+}
+
+impl TypeHint<'_> {
+    /// The subtype indication and a default value of the missing object
+    fn subtype_and_value(&self, root: &DesignRoot) -> Option<(String, Option<String>)> {
+        match self {
+            TypeHint::Object(ent) => {
+                let AnyEntKind::Object(object) = ent.kind() else {
+                    return None;
+                };
+                Some((
+                    declared_subtype(root, ent)?,
+                    default_value(object.subtype.type_mark()),
+                ))
+            }
+            TypeHint::Integer => Some(("integer".to_owned(), Some("0".to_owned()))),
+            TypeHint::Real => Some(("real".to_owned(), Some("0.0".to_owned()))),
+        }
+    }
+}
+
+fn declare_action(
+    source: &Source,
+    region: &DeclarativeRegion,
+    class: &str,
+    name: &str,
+    declaration: String,
+) -> CodeAction {
+    let mut edit = WorkspaceEdit::default();
+    edit.add(
+        source.file_name(),
+        TextEdit::insert(
+            Position::new(region.begin_line, 0),
+            format!("{}{declaration}\n", region.indent),
+        ),
+    );
+    CodeAction {
+        title: format!("Declare {class} '{name}'"),
+        kind: CodeActionKind::QuickFix,
+        edit,
+    }
+}
+
+/// Declares a name that is not declared as a signal, variable or constant.
+/// Assignment targets are declared as signals or variables depending on the kind of assignment.
+/// Names that are read may be declared as any kind of object.
+/// The declaration is added to the innermost declarative part that may contain it.
+/// The subtype is copied from an object that the name is assigned from, assigned to
+/// or combined with. Otherwise, it is assumed to be an integer.
+pub(super) fn declare_missing_object_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut searcher = MissingObjectSearcher {
+        root,
+        source,
+        cursor: range.start,
+        regions: Vec::new(),
+        missing: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    let Some(missing) = searcher.missing else {
+        return Vec::new();
+    };
+    let name = text_at(source, missing.pos.range());
+    let (subtype, value) = missing
+        .hint
+        .and_then(|hint| hint.subtype_and_value(root))
+        .unwrap_or_else(|| ("integer".to_owned(), Some("0".to_owned())));
+
+    let concurrent = searcher
+        .regions
+        .iter()
+        .rev()
+        .find(|region| !region.sequential);
+    let sequential = searcher
+        .regions
+        .iter()
+        .rev()
+        .find(|region| region.sequential);
+    let mut actions = Vec::new();
+    if matches!(missing.usage, Usage::SignalTarget | Usage::Expression) {
+        if let Some(region) = concurrent {
+            let declaration = format!("signal {name} : {subtype};");
+            actions.push(declare_action(source, region, "signal", &name, declaration));
+        }
+    }
+    if matches!(missing.usage, Usage::VariableTarget | Usage::Expression) {
+        if let Some(region) = sequential {
+            let declaration = format!("variable {name} : {subtype};");
+            actions.push(declare_action(
+                source,
+                region,
+                "variable",
+                &name,
+                declaration,
+            ));
+        }
+    }
+    if missing.usage == Usage::Expression {
+        if let (Some(region), Some(value)) = (searcher.regions.last(), value) {
+            let declaration = format!("constant {name} : {subtype} := {value};");
+            actions.push(declare_action(
+                source,
+                region,
+                "constant",
+                &name,
+                declaration,
+            ));
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::code_action::tests::apply_edits;
+
+    fn actions_at(code: &str, cursor: &str) -> Vec<(String, String)> {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code("libname", code);
+        let (root, _) = builder.get_analyzed_root();
+        let cursor = code.s1(cursor).start();
+        let actions =
+            declare_missing_object_actions(&root, code.source(), Range::new(cursor, cursor));
+        actions
+            .iter()
+            .map(|action| (action.title.clone(), apply_edits(code.source(), action)))
+            .collect()
+    }
+
+    #[test]
+    fn declares_assigned_signal_with_subtype_of_value() {
+        let actions = actions_at(
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal data : bit_vector(7 downto 0);
+begin
+  copy <= data;
+end architecture;",
+            "copy",
+        );
+        assert_eq!(
+            actions,
+            vec![(
+                "Declare signal 'copy'".to_owned(),
+                "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal data : bit_vector(7 downto 0);
+  signal copy : bit_vector(7 downto 0);
+begin
+  copy <= data;
+end architecture;"
+                    .to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn declares_variable_target_in_process() {
+        let actions = actions_at(
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+  begin
+    count := 1;
+    wait;
+  end process;
+end architecture;",
+            "count",
+        );
+        assert_eq!(
+            actions,
+            vec![(
+                "Declare variable 'count'".to_owned(),
+                "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable count : integer;
+  begin
+    count := 1;
+    wait;
+  end process;
+end architecture;"
+                    .to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn declares_read_name_with_type_of_target() {
+        let actions = actions_at(
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : bit;
+begin
+  process
+  begin
+    s <= s xor enable;
+    wait;
+  end process;
+end architecture;",
+            "enable",
+        );
+        let titles: Vec<&str> = actions.iter().map(|(title, _)| title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Declare signal 'enable'",
+                "Declare variable 'enable'",
+                "Declare constant 'enable'"
+            ]
+        );
+        assert!(actions[0]
+            .1
+            .contains("  signal s : bit;\n  signal enable : bit;\nbegin"));
+        assert!(actions[2]
+            .1
+            .contains("  process\n    constant enable : bit := BIT'left;\n  begin"));
+    }
+
+    #[test]
+    fn ignores_declared_names() {
+        let actions = actions_at(
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s0, s1 : bit;
+begin
+  s0 <= s1;
+end architecture;",
+            "s1;",
+        );
+        assert_eq!(actions, vec![]);
+    }
+}
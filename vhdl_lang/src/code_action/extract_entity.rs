@@ -0,0 +1,709 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, LockedUnit};
+use crate::ast::search::{
+    DeclarationItem, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::{
+    base_reference, indentation_of_line, referenced_entities, reindent, removal_range, text_at,
+    CodeAction, CodeActionKind, TextEdit, WorkspaceEdit, INDENT,
+};
+use crate::data::HasSource;
+use crate::named_entity::InterfaceMode;
+use crate::{
+    AnyEntKind, Design, EntRef, EntityId, HasTokenSpan, Range, Source, SrcPos, TokenAccess,
+    TokenSpan,
+};
+use fnv::FnvHashSet;
+use std::ops::Deref;
+
+/// Collects the objects that may be written by some statements
+struct WrittenObjects<'a> {
+    root: &'a DesignRoot,
+    written: FnvHashSet<EntityId>,
+}
+
+impl WrittenObjects<'_> {
+    fn add_target(&mut self, target: &WithTokenSpan<Target>) {
+        if let Target::Name(name) = &target.item {
+            self.written.extend(base_reference(name));
+        }
+    }
+
+    /// The actual of an association is potentially written unless
+    /// it is associated with a formal of mode `in`
+    fn add_associations(&mut self, associations: &[AssociationElement]) {
+        for assoc in associations {
+            let is_input = assoc
+                .formal
+                .as_ref()
+                .and_then(|formal| formal.item.get_suffix_reference())
+                .is_some_and(|id| match self.root.get_ent(id).kind() {
+                    AnyEntKind::Object(object) => {
+                        matches!(object.mode(), Some(InterfaceMode::Simple(Mode::In)))
+                    }
+                    _ => false,
+                });
+            if is_input {
+                continue;
+            }
+            if let ActualPart::Expression(Expression::Name(name)) = &assoc.actual.item {
+                self.written.extend(base_reference(name));
+            }
+        }
+    }
+
+    fn add_map_aspect(&mut self, map_aspect: &Option<MapAspect>) {
+        if let Some(map_aspect) = map_aspect {
+            self.add_associations(&map_aspect.list.items);
+        }
+    }
+}
+
+impl Searcher for WrittenObjects<'_> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::ConcurrentStatement(stmt) => match &stmt.statement.item {
+                ConcurrentStatement::Assignment(assignment) => {
+                    self.add_target(&assignment.assignment.target)
+                }
+                ConcurrentStatement::Instance(instance) => self.add_map_aspect(&instance.port_map),
+                ConcurrentStatement::Block(block) => self.add_map_aspect(&block.header.port_map),
+                ConcurrentStatement::ProcedureCall(call) => {
+                    self.add_associations(&call.call.item.parameters.items)
+                }
+                _ => {}
+            },
+            DeclarationItem::SequentialStatement(stmt) => match &stmt.statement.item {
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.add_target(&assignment.target)
+                }
+                SequentialStatement::SignalForceAssignment(assignment) => {
+                    self.add_target(&assignment.target)
+                }
+                SequentialStatement::SignalReleaseAssignment(assignment) => {
+                    self.add_target(&assignment.target)
+                }
+                SequentialStatement::ProcedureCall(call) => {
+                    self.add_associations(&call.item.parameters.items)
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// A signal that crosses the boundary of the selection and becomes a port of the new entity
+struct Port {
+    name: String,
+    mode: Mode,
+    subtype: String,
+}
+
+/// A signal of the architecture that is only used by the selected statements.
+/// Its declaration is moved to the new architecture.
+struct MovedSignal {
+    decl_pos: SrcPos,
+    text: String,
+}
+
+/// Returns the selected concurrent statements of the architecture.
+/// Without a selection, this is the generate statement at the cursor.
+fn selected_statements<'a>(
+    ctx: &dyn TokenAccess,
+    arch: &'a ArchitectureBody,
+    range: Range,
+) -> Vec<&'a LabeledConcurrentStatement> {
+    if range.start == range.end {
+        arch.statements
+            .iter()
+            .filter(|stmt| {
+                matches!(
+                    stmt.statement.item,
+                    ConcurrentStatement::ForGenerate(_)
+                        | ConcurrentStatement::IfGenerate(_)
+                        | ConcurrentStatement::CaseGenerate(_)
+                ) && stmt.get_pos(ctx).range().contains(range.start)
+            })
+            .collect()
+    } else {
+        arch.statements
+            .iter()
+            .filter(|stmt| {
+                let pos = stmt.get_pos(ctx);
+                range.start <= pos.start() && pos.end() <= range.end
+            })
+            .collect()
+    }
+}
+
+/// Returns the text of the subtype indication of an object declaration
+/// unless it references named entities of the design units
+/// that would not be visible in the new entity.
+fn subtype_text(
+    ctx: &dyn TokenAccess,
+    source: &Source,
+    subtype: &SubtypeIndication,
+    is_local: &impl Fn(EntityId) -> bool,
+) -> Option<String> {
+    if referenced_entities(ctx, subtype).into_iter().any(is_local) {
+        return None;
+    }
+    let end_token = match &subtype.constraint {
+        Some(constraint) => constraint.span.end_token,
+        None => subtype.type_mark.span.end_token,
+    };
+    let pos = TokenSpan::new(subtype.type_mark.span.start_token, end_token).pos(ctx);
+    Some(text_at(source, pos.range()))
+}
+
+/// Returns the text of the context clause items of a design unit
+fn context_items(unit: &LockedUnit) -> Vec<String> {
+    let Some(data) = unit.unit.get() else {
+        return Vec::new();
+    };
+    data.context_clause()
+        .map(|items| {
+            items
+                .iter()
+                .map(|item| text_at(unit.source(), item.get_pos(&unit.tokens).range()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+struct Extraction<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    unit: &'a LockedUnit,
+    arch: &'a ArchitectureBody,
+    statements: Vec<&'a LabeledConcurrentStatement>,
+}
+
+impl<'a> Extraction<'a> {
+    fn ctx(&self) -> &'a dyn TokenAccess {
+        &self.unit.tokens
+    }
+
+    fn entity_unit(&self) -> Option<&'a LockedUnit> {
+        self.root
+            .get_lib(self.unit.unit_id().library_name())?
+            .primary_unit(self.unit.unit_id().primary_name())
+    }
+
+    /// The name of the new entity is the label of a single extracted statement.
+    /// A suffix is added if the name is already taken by another primary unit.
+    fn entity_name(&self) -> Option<String> {
+        let base = match self.statements.as_slice() {
+            [stmt] => stmt
+                .label
+                .tree
+                .as_ref()
+                .map(|label| label.item.to_string())
+                .unwrap_or_else(|| "extracted".to_owned()),
+            _ => "extracted".to_owned(),
+        };
+        let library = self.root.get_lib(self.unit.unit_id().library_name())?;
+        let is_free = |name: &str| library.primary_unit(&self.root.symbol_utf8(name)).is_none();
+        if is_free(&base) {
+            return Some(base);
+        }
+        (1..)
+            .map(|i| format!("{base}_{i}"))
+            .find(|name| is_free(name))
+    }
+
+    /// Determines the signals and ports of the architecture that cross the boundary
+    /// of the selection. Returns `None` when the statements reference other declarations of
+    /// the architecture or entity, such as constants, types or components.
+    fn boundary(&self) -> Option<(Vec<Port>, Vec<MovedSignal>)> {
+        let ctx = self.ctx();
+        let arch_ent = self.root.get_ent(self.arch.ident.decl.get()?);
+        let AnyEntKind::Design(Design::Architecture(_, _, entity_ent)) = arch_ent.kind() else {
+            return None;
+        };
+        let entity_id = entity_ent.id();
+        let arch_id = arch_ent.id();
+        let is_local = |id: EntityId| {
+            self.root
+                .get_ent(id)
+                .parent
+                .is_some_and(|parent| parent.id() == arch_id || parent.id() == entity_id)
+        };
+
+        let mut used = FnvHashSet::default();
+        let mut written = WrittenObjects {
+            root: self.root,
+            written: FnvHashSet::default(),
+        };
+        for stmt in self.statements.iter() {
+            used.extend(referenced_entities(ctx, *stmt));
+            let _ = stmt.search(ctx, &mut written);
+        }
+        let mut used_outside = FnvHashSet::default();
+        for stmt in self.arch.statements.iter() {
+            if !self
+                .statements
+                .iter()
+                .any(|selected| std::ptr::eq(*selected, stmt))
+            {
+                used_outside.extend(referenced_entities(ctx, stmt));
+            }
+        }
+        for decl in self.arch.decl.iter() {
+            used_outside.extend(referenced_entities(ctx, decl));
+        }
+
+        let mut crossing: Vec<EntRef<'_>> = Vec::new();
+        for id in used {
+            let ent = self.root.get_ent(id);
+            let Some(parent) = ent.parent else {
+                continue;
+            };
+            if parent.id() != arch_id && parent.id() != entity_id {
+                continue;
+            }
+            match ent.kind() {
+                AnyEntKind::Concurrent(_) | AnyEntKind::Sequential(_) => {}
+                AnyEntKind::Object(object)
+                    if object.class == ObjectClass::Signal
+                        && (parent.id() == arch_id
+                            || matches!(object.mode(), Some(InterfaceMode::Simple(_)))) =>
+                {
+                    crossing.push(ent)
+                }
+                _ => return None,
+            }
+        }
+        crossing.sort_by_key(|ent| ent.decl_pos().map(|pos| pos.start()));
+
+        let mut ports = Vec::new();
+        let mut moved = Vec::new();
+        for ent in crossing {
+            let AnyEntKind::Object(object) = ent.kind() else {
+                continue;
+            };
+            if ent.parent?.id() == arch_id {
+                let (decl, object_decl) =
+                    self.arch.decl.iter().find_map(|decl| match &decl.item {
+                        Declaration::Object(object_decl)
+                            if object_decl
+                                .idents
+                                .iter()
+                                .any(|ident| ident.decl.get() == Some(ent.id())) =>
+                        {
+                            Some((decl, object_decl))
+                        }
+                        _ => None,
+                    })?;
+                if !used_outside.contains(&ent.id()) && object_decl.idents.len() == 1 {
+                    if referenced_entities(ctx, decl).into_iter().any(is_local) {
+                        return None;
+                    }
+                    let pos = decl.pos(ctx);
+                    let indent = indentation_of_line(self.source, pos.start().line);
+                    moved.push(MovedSignal {
+                        text: reindent(&text_at(self.source, pos.range()), &indent, INDENT),
+                        decl_pos: pos,
+                    });
+                    continue;
+                }
+                let subtype =
+                    subtype_text(ctx, self.source, &object_decl.subtype_indication, &is_local)?;
+                ports.push(Port {
+                    name: ent.designator().to_string(),
+                    mode: if written.written.contains(&ent.id()) {
+                        Mode::Out
+                    } else {
+                        Mode::In
+                    },
+                    subtype,
+                });
+            } else {
+                let subtype = self.port_subtype(ent, &is_local)?;
+                let mode = match object.mode() {
+                    Some(InterfaceMode::Simple(mode)) if written.written.contains(&ent.id()) => {
+                        *mode
+                    }
+                    _ => Mode::In,
+                };
+                ports.push(Port {
+                    name: ent.designator().to_string(),
+                    mode,
+                    subtype,
+                });
+            }
+        }
+        Some((ports, moved))
+    }
+
+    /// Returns the subtype of a port of the entity of the architecture
+    fn port_subtype(
+        &self,
+        port: EntRef<'_>,
+        is_local: &impl Fn(EntityId) -> bool,
+    ) -> Option<String> {
+        let unit = self.entity_unit()?;
+        let data = unit.unit.get()?;
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() else {
+            return None;
+        };
+        entity
+            .port_clause
+            .as_ref()?
+            .items
+            .iter()
+            .find_map(|item| match item {
+                InterfaceDeclaration::Object(object)
+                    if object
+                        .idents
+                        .iter()
+                        .any(|ident| ident.decl.get() == Some(port.id())) =>
+                {
+                    let ModeIndication::Simple(mode) = &object.mode else {
+                        return None;
+                    };
+                    subtype_text(
+                        &unit.tokens,
+                        unit.source(),
+                        &mode.subtype_indication,
+                        is_local,
+                    )
+                }
+                _ => None,
+            })
+    }
+
+    fn new_units_text(&self, name: &str, ports: &[Port], moved: &[MovedSignal]) -> String {
+        let mut context: Vec<String> = Vec::new();
+        for item in self
+            .entity_unit()
+            .map(context_items)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(context_items(self.unit))
+        {
+            if !context.contains(&item) {
+                context.push(item);
+            }
+        }
+        let mut text = String::new();
+        for item in context {
+            text.push_str(&item);
+            text.push('\n');
+        }
+        text.push_str(&format!("entity {name} is\n"));
+        if !ports.is_empty() {
+            let ports: Vec<String> = ports
+                .iter()
+                .map(|port| {
+                    format!(
+                        "{INDENT}{INDENT}{} : {} {}",
+                        port.name, port.mode, port.subtype
+                    )
+                })
+                .collect();
+            text.push_str(&format!(
+                "{INDENT}port (\n{}\n{INDENT});\n",
+                ports.join(";\n")
+            ));
+        }
+        text.push_str(&format!("end entity {name};\n\n"));
+        text.push_str(&format!("architecture rtl of {name} is\n"));
+        for signal in moved {
+            text.push_str(&signal.text);
+            text.push('\n');
+        }
+        text.push_str("begin\n");
+        for stmt in self.statements.iter() {
+            let pos = stmt.get_pos(self.ctx());
+            let indent = indentation_of_line(self.source, pos.start().line);
+            text.push_str(&reindent(
+                &text_at(self.source, pos.range()),
+                &indent,
+                INDENT,
+            ));
+            text.push('\n');
+        }
+        text.push_str("end architecture rtl;");
+        text
+    }
+
+    fn instance_text(&self, name: &str, ports: &[Port], indent: &str) -> String {
+        let mut text = format!("{name}_inst : entity work.{name}");
+        if !ports.is_empty() {
+            let associations: Vec<String> = ports
+                .iter()
+                .map(|port| format!("{indent}{INDENT}{INDENT}{} => {}", port.name, port.name))
+                .collect();
+            text.push_str(&format!(
+                "\n{indent}{INDENT}port map (\n{}\n{indent}{INDENT})",
+                associations.join(",\n")
+            ));
+        }
+        text.push(';');
+        text
+    }
+
+    fn action(&self) -> Option<CodeAction> {
+        let (ports, moved) = self.boundary()?;
+        let name = self.entity_name()?;
+        let ctx = self.ctx();
+        let file_name = self.source.file_name();
+        let mut edit = WorkspaceEdit::default();
+
+        let (first, rest) = self.statements.split_first()?;
+        let first_pos = first.get_pos(ctx);
+        let indent = indentation_of_line(self.source, first_pos.start().line);
+        edit.add(
+            file_name,
+            TextEdit::new(
+                first_pos.range(),
+                self.instance_text(&name, &ports, &indent),
+            ),
+        );
+        for stmt in rest {
+            edit.add(
+                file_name,
+                TextEdit::delete(removal_range(self.source, stmt.get_pos(ctx).range())),
+            );
+        }
+        for signal in moved.iter() {
+            edit.add(
+                file_name,
+                TextEdit::delete(removal_range(self.source, signal.decl_pos.range())),
+            );
+        }
+        edit.add(
+            file_name,
+            TextEdit::insert(
+                self.arch.span.pos(ctx).end(),
+                format!("\n\n{}", self.new_units_text(&name, &ports, &moved)),
+            ),
+        );
+        Some(CodeAction {
+            title: format!("Extract into entity '{name}'"),
+            kind: CodeActionKind::RefactorExtract,
+            edit,
+        })
+    }
+}
+
+/// Extracts the selected concurrent statements or the generate statement at the cursor
+/// into a new entity with an architecture that is instantiated instead.
+/// The signals that cross the boundary of the selection become ports of the new entity.
+/// Signals that are written by the extracted statements become ports of mode `out`,
+/// all others become ports of mode `in`.
+/// Signals that are only used by the extracted statements are moved to the new architecture.
+pub(super) fn extract_entity_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = data.deref() else {
+            continue;
+        };
+        if !arch.span.pos(&unit.tokens).range().contains(range.start) {
+            continue;
+        }
+        let statements = selected_statements(&unit.tokens, arch, range);
+        if statements.is_empty() {
+            return vec![];
+        }
+        let extraction = Extraction {
+            root,
+            source,
+            unit,
+            arch,
+            statements,
+        };
+        return extraction.action().into_iter().collect();
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction, CodeActionKind};
+    use crate::syntax::test::Code;
+    use crate::{Config, Range};
+
+    fn extract_actions(
+        root: &crate::analysis::DesignRoot,
+        code: &Code,
+        range: Range,
+    ) -> Vec<CodeAction> {
+        list_code_actions(root, &Config::default(), code.source(), range)
+            .into_iter()
+            .filter(|action| action.kind == CodeActionKind::RefactorExtract)
+            .collect()
+    }
+
+    #[test]
+    fn extracts_generate_statement() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    din : in bit_vector(3 downto 0);
+    dout : out bit_vector(3 downto 0)
+  );
+end entity;
+
+architecture a of ent is
+  signal tmp : bit_vector(3 downto 0);
+begin
+  gen_inv: for i in 0 to 3 generate
+    tmp(i) <= not din(i);
+  end generate;
+  dout <= tmp;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("gen_inv").start();
+        let actions = extract_actions(&root, &code, Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Extract into entity 'gen_inv'");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  port (
+    din : in bit_vector(3 downto 0);
+    dout : out bit_vector(3 downto 0)
+  );
+end entity;
+
+architecture a of ent is
+  signal tmp : bit_vector(3 downto 0);
+begin
+  gen_inv_inst : entity work.gen_inv
+      port map (
+          din => din,
+          tmp => tmp
+      );
+  dout <= tmp;
+end architecture;
+
+entity gen_inv is
+    port (
+        din : in bit_vector(3 downto 0);
+        tmp : out bit_vector(3 downto 0)
+    );
+end entity gen_inv;
+
+architecture rtl of gen_inv is
+begin
+    gen_inv: for i in 0 to 3 generate
+      tmp(i) <= not din(i);
+    end generate;
+end architecture rtl;
+"
+        );
+    }
+
+    #[test]
+    fn moves_signals_only_used_by_selection() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    a : in bit;
+    b : out bit
+  );
+end entity;
+
+architecture a of ent is
+  signal tmp : bit;
+begin
+  tmp <= not a;
+  b <= tmp;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let range = Range::new(code.s1("tmp <=").start(), code.s1("b <= tmp;").end());
+        let actions = extract_actions(&root, &code, range);
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  port (
+    a : in bit;
+    b : out bit
+  );
+end entity;
+
+architecture a of ent is
+begin
+  extracted_inst : entity work.extracted
+      port map (
+          a => a,
+          b => b
+      );
+end architecture;
+
+entity extracted is
+    port (
+        a : in bit;
+        b : out bit
+    );
+end entity extracted;
+
+architecture rtl of extracted is
+    signal tmp : bit;
+begin
+    tmp <= not a;
+    b <= tmp;
+end architecture rtl;
+"
+        );
+    }
+
+    #[test]
+    fn no_extraction_when_referencing_local_constants() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : bit := '1';
+  signal s : bit;
+begin
+  gen: if true generate
+    s <= c;
+  end generate;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let cursor = code.s1("gen").start();
+        assert!(extract_actions(&root, &code, Range::new(cursor, cursor)).is_empty());
+    }
+}
@@ -0,0 +1,503 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::declare_missing_object::declared_subtype;
+use crate::code_action::{
+    base_reference, indentation_of_line, reindent, removal_range, text_at, CodeAction,
+    CodeActionKind, TextEdit, WorkspaceEdit, INDENT,
+};
+use crate::named_entity::{InterfaceMode, OverloadedEnt, Reference};
+use crate::{
+    AnyEntKind, Design, EntRef, EntityId, HasTokenSpan, Position, Range, Source, SrcPos,
+    TokenAccess, TokenId,
+};
+use fnv::FnvHashSet;
+
+/// Returns the statements of a statement list that are completely selected.
+/// When no statement of the list is selected, the statements nested in the statement
+/// that contains the selection are searched instead.
+fn selected_statements<'a>(
+    ctx: &dyn TokenAccess,
+    statements: &'a [LabeledSequentialStatement],
+    range: Range,
+) -> Option<&'a [LabeledSequentialStatement]> {
+    let is_selected = |stmt: &LabeledSequentialStatement| {
+        let pos = stmt.get_pos(ctx);
+        range.start <= pos.start() && pos.end() <= range.end
+    };
+    if let Some(first) = statements.iter().position(is_selected) {
+        let last = statements.iter().rposition(is_selected)?;
+        return Some(&statements[first..=last]);
+    }
+    let stmt = statements
+        .iter()
+        .find(|stmt| stmt.get_pos(ctx).range().contains(range.start))?;
+    match &stmt.statement.item {
+        SequentialStatement::If(if_stmt) => if_stmt
+            .conds
+            .conditionals
+            .iter()
+            .map(|cond| &cond.item)
+            .chain(if_stmt.conds.else_item.as_ref().map(|(item, _)| item))
+            .find_map(|body| selected_statements(ctx, body, range)),
+        SequentialStatement::Case(case_stmt) => case_stmt
+            .alternatives
+            .iter()
+            .find_map(|alternative| selected_statements(ctx, &alternative.item, range)),
+        SequentialStatement::Loop(loop_stmt) => {
+            selected_statements(ctx, &loop_stmt.statements, range)
+        }
+        _ => None,
+    }
+}
+
+/// Collects how the objects are accessed by the extracted statements
+struct AccessCollector<'a> {
+    root: &'a DesignRoot,
+    /// The start positions of assignment targets, which are not read
+    target_starts: FnvHashSet<Position>,
+    written: FnvHashSet<EntityId>,
+    read: FnvHashSet<EntityId>,
+    /// The referenced objects in order of their first reference
+    objects: Vec<EntityId>,
+    /// Statements that jump out of the extracted statements cannot be extracted
+    has_return: bool,
+    has_loop: bool,
+    has_exit: bool,
+}
+
+impl AccessCollector<'_> {
+    fn add_target(&mut self, ctx: &dyn TokenAccess, target: &WithTokenSpan<Target>) {
+        if let Target::Name(name) = &target.item {
+            self.target_starts.insert(target.pos(ctx).start());
+            self.written.extend(base_reference(name));
+        }
+    }
+
+    /// The actual of an association with a formal of mode `out` or `inout` is written
+    fn add_call(&mut self, call: &CallOrIndexed) {
+        let Some(subprogram) = call.name.item.get_suffix_reference() else {
+            return;
+        };
+        let Some(subprogram) = OverloadedEnt::from_any(self.root.get_ent(subprogram)) else {
+            return;
+        };
+        let formals = subprogram.formals();
+        for (i, assoc) in call.parameters.items.iter().enumerate() {
+            let formal = match &assoc.formal {
+                Some(formal) => formal
+                    .item
+                    .get_suffix_reference()
+                    .map(|id| self.root.get_ent(id)),
+                None => formals.nth(i).map(|formal| formal.inner()),
+            };
+            let is_written = formal.is_some_and(|formal| match formal.kind() {
+                AnyEntKind::Object(object) => matches!(
+                    object.mode(),
+                    Some(InterfaceMode::Simple(Mode::Out | Mode::InOut))
+                ),
+                _ => false,
+            });
+            if !is_written {
+                continue;
+            }
+            if let ActualPart::Expression(Expression::Name(name)) = &assoc.actual.item {
+                self.written.extend(base_reference(name));
+            }
+        }
+    }
+}
+
+impl Searcher for AccessCollector<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::SequentialStatement(stmt) = decl.ast {
+            match &stmt.statement.item {
+                SequentialStatement::VariableAssignment(assignment) => {
+                    self.add_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.add_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalForceAssignment(assignment) => {
+                    self.add_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalReleaseAssignment(assignment) => {
+                    self.add_target(ctx, &assignment.target)
+                }
+                SequentialStatement::ProcedureCall(call) => self.add_call(&call.item),
+                SequentialStatement::Return(_) => self.has_return = true,
+                SequentialStatement::Loop(_) => self.has_loop = true,
+                SequentialStatement::Next(_) | SequentialStatement::Exit(_) => self.has_exit = true,
+                _ => {}
+            }
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        let Some(id) = reference.get() else {
+            return NotFinished;
+        };
+        if !matches!(self.root.get_ent(id).kind(), AnyEntKind::Object(_)) {
+            return NotFinished;
+        }
+        if !self.objects.contains(&id) {
+            self.objects.push(id);
+        }
+        if !self.target_starts.contains(&pos.start()) {
+            self.read.insert(id);
+        }
+        NotFinished
+    }
+}
+
+/// Objects of packages are visible wherever the procedure is declared
+fn is_declared_in_package(ent: EntRef<'_>) -> bool {
+    let mut parent = ent.parent;
+    while let Some(ent) = parent {
+        if matches!(
+            ent.kind(),
+            AnyEntKind::Design(
+                Design::Package(..) | Design::PackageBody(..) | Design::UninstPackage(..)
+            )
+        ) {
+            return true;
+        }
+        parent = ent.parent;
+    }
+    false
+}
+
+/// A signal or variable that is passed to the extracted procedure
+struct Parameter {
+    class: ObjectClass,
+    name: String,
+    mode: Mode,
+    subtype: String,
+}
+
+impl Parameter {
+    fn text(&self) -> String {
+        let class = match self.class {
+            ObjectClass::Signal => "signal",
+            _ => "variable",
+        };
+        format!("{class} {} : {} {}", self.name, self.mode, self.subtype)
+    }
+}
+
+/// The declarative part that the procedure is added to and the statements that are extracted
+struct Extraction<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    ctx: &'a dyn TokenAccess,
+    decl: &'a [WithTokenSpan<Declaration>],
+    begin_token: TokenId,
+    statements: &'a [LabeledSequentialStatement],
+}
+
+impl Extraction<'_> {
+    fn parameters(&self, collector: &AccessCollector<'_>) -> Option<Vec<Parameter>> {
+        let mut parameters = Vec::new();
+        for id in collector.objects.iter() {
+            let ent = self.root.get_ent(*id);
+            let AnyEntKind::Object(object) = ent.kind() else {
+                continue;
+            };
+            let class = match object.class {
+                ObjectClass::Signal => ObjectClass::Signal,
+                ObjectClass::Variable | ObjectClass::SharedVariable => ObjectClass::Variable,
+                ObjectClass::Constant => continue,
+            };
+            if is_declared_in_package(ent) {
+                continue;
+            }
+            let mode = match (collector.read.contains(id), collector.written.contains(id)) {
+                (true, true) => Mode::InOut,
+                (false, true) => Mode::Out,
+                _ => Mode::In,
+            };
+            parameters.push(Parameter {
+                class,
+                name: ent.designator().to_string(),
+                mode,
+                subtype: declared_subtype(self.root, ent)?,
+            });
+        }
+        Some(parameters)
+    }
+
+    fn procedure_text(&self, name: &str, parameters: &[Parameter], indent: &str) -> String {
+        let mut text = format!("{indent}procedure {name}");
+        if !parameters.is_empty() {
+            let parameters: Vec<String> = parameters
+                .iter()
+                .map(|parameter| format!("{indent}{INDENT}{}", parameter.text()))
+                .collect();
+            text.push_str(&format!("(\n{}\n{indent})", parameters.join(";\n")));
+        }
+        text.push_str(" is\n");
+        text.push_str(&format!("{indent}begin\n"));
+        for stmt in self.statements {
+            let pos = stmt.get_pos(self.ctx);
+            let old_indent = indentation_of_line(self.source, pos.start().line);
+            text.push_str(&reindent(
+                &text_at(self.source, pos.range()),
+                &old_indent,
+                &format!("{indent}{INDENT}"),
+            ));
+            text.push('\n');
+        }
+        text.push_str(&format!("{indent}end procedure {name};\n"));
+        text
+    }
+
+    fn action(&self) -> Option<CodeAction> {
+        let mut collector = AccessCollector {
+            root: self.root,
+            target_starts: FnvHashSet::default(),
+            written: FnvHashSet::default(),
+            read: FnvHashSet::default(),
+            objects: Vec::new(),
+            has_return: false,
+            has_loop: false,
+            has_exit: false,
+        };
+        for stmt in self.statements {
+            let _ = stmt.search(self.ctx, &mut collector);
+        }
+        if collector.has_return || (collector.has_exit && !collector.has_loop) {
+            return None;
+        }
+        let parameters = self.parameters(&collector)?;
+        let name = "extracted";
+
+        let begin_line = self.ctx.get_pos(self.begin_token).start().line;
+        let indent = match self.decl.first() {
+            Some(decl) => indentation_of_line(self.source, decl.pos(self.ctx).start().line),
+            None => format!("{}{INDENT}", indentation_of_line(self.source, begin_line)),
+        };
+
+        let file_name = self.source.file_name();
+        let mut edit = WorkspaceEdit::default();
+        edit.add(
+            file_name,
+            TextEdit::insert(
+                Position::new(begin_line, 0),
+                self.procedure_text(name, &parameters, &indent),
+            ),
+        );
+        let (first, rest) = self.statements.split_first()?;
+        let call = if parameters.is_empty() {
+            format!("{name};")
+        } else {
+            let actuals: Vec<&str> = parameters.iter().map(|p| p.name.as_str()).collect();
+            format!("{name}({});", actuals.join(", "))
+        };
+        edit.add(
+            file_name,
+            TextEdit::new(first.get_pos(self.ctx).range(), call),
+        );
+        for stmt in rest {
+            edit.add(
+                file_name,
+                TextEdit::delete(removal_range(self.source, stmt.get_pos(self.ctx).range())),
+            );
+        }
+        Some(CodeAction {
+            title: "Extract statements into procedure".to_owned(),
+            kind: CodeActionKind::RefactorExtract,
+            edit,
+        })
+    }
+}
+
+/// Finds the innermost process or subprogram body that contains the selection
+struct ExtractionSearcher<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    range: Range,
+    result: Option<CodeAction>,
+}
+
+impl ExtractionSearcher<'_> {
+    fn add_region(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        decl: &[WithTokenSpan<Declaration>],
+        begin_token: TokenId,
+        statements: &[LabeledSequentialStatement],
+    ) {
+        let Some(selected) = selected_statements(ctx, statements, self.range) else {
+            return;
+        };
+        let extraction = Extraction {
+            root: self.root,
+            source: self.source,
+            ctx,
+            decl,
+            begin_token,
+            statements: selected,
+        };
+        self.result = extraction.action();
+    }
+}
+
+impl Searcher for ExtractionSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Subprogram(body)
+                if body.span.pos(ctx).range().contains(self.range.start) =>
+            {
+                self.add_region(ctx, &body.declarations, body.begin_token, &body.statements);
+            }
+            DeclarationItem::ConcurrentStatement(stmt)
+                if stmt.get_pos(ctx).range().contains(self.range.start) =>
+            {
+                if let ConcurrentStatement::Process(process) = &stmt.statement.item {
+                    self.add_region(ctx, &process.decl, process.begin_token, &process.statements);
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Extracts the selected sequential statements of a process or subprogram into a new
+/// procedure that is declared in the declarative part of the process or subprogram.
+/// The signals and variables of the design unit that the statements reference are
+/// passed as parameters whose modes follow from whether they are read or written.
+pub(super) fn extract_procedure_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    if range.start == range.end {
+        return vec![];
+    }
+    let mut searcher = ExtractionSearcher {
+        root,
+        source,
+        range,
+        result: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    searcher.result.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_procedure_actions;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::Range;
+
+    #[test]
+    fn extracts_statements_with_parameters() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    a : in natural;
+    q : out natural
+  );
+end entity;
+
+architecture a of ent is
+begin
+  proc: process (a)
+    variable acc : natural;
+    variable tmp : natural;
+  begin
+    acc := 0;
+    tmp := a + 1;
+    acc := acc + tmp;
+    q <= acc;
+  end process;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let range = Range::new(
+            code.s1("tmp := a + 1").start(),
+            code.s1("acc := acc + tmp;").end(),
+        );
+        let actions = extract_procedure_actions(&root, code.source(), range);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  port (
+    a : in natural;
+    q : out natural
+  );
+end entity;
+
+architecture a of ent is
+begin
+  proc: process (a)
+    variable acc : natural;
+    variable tmp : natural;
+    procedure extracted(
+        variable tmp : inout natural;
+        signal a : in natural;
+        variable acc : inout natural
+    ) is
+    begin
+        tmp := a + 1;
+        acc := acc + tmp;
+    end procedure extracted;
+  begin
+    acc := 0;
+    extracted(tmp, a, acc);
+    q <= acc;
+  end process;
+end architecture;"
+        );
+    }
+
+    #[test]
+    fn does_not_extract_return_statements() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+end package;
+
+package body pkg is
+  function f(x : natural) return natural is
+  begin
+    if x = 0 then
+      return 1;
+    end if;
+    return x;
+  end function;
+end package body;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let range = Range::new(code.s1("if x = 0").start(), code.s1("end if;").end());
+        assert!(extract_procedure_actions(&root, code.source(), range).is_empty());
+    }
+}
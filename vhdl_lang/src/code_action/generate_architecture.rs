@@ -0,0 +1,234 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::*;
+use crate::code_action::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use crate::config::CodeActionConfig;
+use crate::{Position, Range, Source};
+use std::ops::Deref;
+use std::path::PathBuf;
+
+fn architecture_text(entity_name: &str, architecture_name: &str) -> String {
+    format!(
+        "architecture {architecture_name} of {entity_name} is\nbegin\nend architecture {architecture_name};\n"
+    )
+}
+
+/// The sibling file for the architecture has the same extension as the file of the entity
+fn architecture_file_name(
+    config: &CodeActionConfig,
+    source: &Source,
+    entity_name: &str,
+    architecture_name: &str,
+) -> Option<PathBuf> {
+    let entity_file = source.file_name();
+    let stem = config
+        .architecture_file_name
+        .replace("{entity}", entity_name)
+        .replace("{architecture}", architecture_name);
+    let extension = entity_file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("vhd");
+    Some(entity_file.parent()?.join(format!("{stem}.{extension}")))
+}
+
+/// Creates an empty architecture for an entity that does not have any architecture yet.
+/// The architecture is either placed directly after the entity or in a new file
+/// next to the file of the entity.
+/// Note that the new file is only analyzed if it is part of the project configuration.
+pub(super) fn generate_architecture_actions(
+    root: &DesignRoot,
+    config: &CodeActionConfig,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() else {
+            continue;
+        };
+        let entity_pos = entity.span.pos(&unit.tokens);
+        if !entity_pos.range().contains(range.start) {
+            continue;
+        }
+        let Some(library) = root.get_lib(unit.unit_id().library_name()) else {
+            return vec![];
+        };
+        if library
+            .secondary_units(&entity.ident.tree.item)
+            .next()
+            .is_some()
+        {
+            return vec![];
+        }
+
+        let entity_name = entity.ident.tree.item.to_string();
+        let architecture_name = config.architecture_name.replace("{entity}", &entity_name);
+        let text = architecture_text(&entity_name, &architecture_name);
+        let mut actions = Vec::new();
+
+        let mut edit = WorkspaceEdit::default();
+        edit.add(
+            source.file_name(),
+            TextEdit::insert(entity_pos.end(), format!("\n\n{}", text.trim_end())),
+        );
+        actions.push(CodeAction {
+            title: format!("Create architecture '{architecture_name}' of '{entity_name}'"),
+            kind: CodeActionKind::RefactorRewrite,
+            edit,
+        });
+
+        if let Some(file_name) =
+            architecture_file_name(config, source, &entity_name, &architecture_name)
+        {
+            if !file_name.exists() {
+                let mut edit = WorkspaceEdit::default();
+                edit.create(&file_name);
+                edit.add(&file_name, TextEdit::insert(Position::new(0, 0), text));
+                actions.push(CodeAction {
+                    title: format!(
+                        "Create architecture '{architecture_name}' of '{entity_name}' in '{}'",
+                        file_name.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    kind: CodeActionKind::RefactorRewrite,
+                    edit,
+                });
+            }
+        }
+        return actions;
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction, CodeActionKind, TextEdit};
+    use crate::config::CodeActionConfig;
+    use crate::syntax::test::Code;
+    use crate::{Config, Position, Range};
+    use std::path::Path;
+
+    fn generate_actions(
+        root: &crate::analysis::DesignRoot,
+        config: &Config,
+        code: &Code,
+    ) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            config,
+            code.source(),
+            Range::new(code.start(), code.start()),
+        )
+        .into_iter()
+        .filter(|action| action.title.starts_with("Create architecture"))
+        .collect()
+    }
+
+    #[test]
+    fn creates_architecture_after_entity() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = generate_actions(&root, &Config::default(), &code.s1("ent"));
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].title, "Create architecture 'rtl' of 'ent'");
+        assert_eq!(actions[0].kind, CodeActionKind::RefactorRewrite);
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture rtl;
+"
+        );
+    }
+
+    #[test]
+    fn creates_architecture_in_sibling_file() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let config = Config::from_str(
+            "
+[libraries]
+
+[code_actions]
+architecture_name = '{entity}_arch'
+architecture_file_name = 'arch_{architecture}'
+",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(
+            config.code_actions(),
+            &CodeActionConfig {
+                architecture_name: "{entity}_arch".to_owned(),
+                architecture_file_name: "arch_{architecture}".to_owned(),
+                ..CodeActionConfig::default()
+            }
+        );
+
+        let actions = generate_actions(&root, &config, &code.s1("ent"));
+        let file_name = code
+            .source()
+            .file_name()
+            .with_file_name("arch_ent_arch.vhd");
+        assert_eq!(
+            actions[1].title,
+            "Create architecture 'ent_arch' of 'ent' in 'arch_ent_arch.vhd'"
+        );
+        assert_eq!(actions[1].edit.created_files, vec![file_name.clone()]);
+        assert_eq!(
+            actions[1].edit.changes[&file_name],
+            vec![TextEdit::insert(
+                Position::new(0, 0),
+                "architecture ent_arch of ent is\nbegin\nend architecture ent_arch;\n"
+            )]
+        );
+    }
+
+    #[test]
+    fn no_architecture_is_created_when_one_exists() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(generate_actions(&root, &Config::default(), &code.s1("ent")).is_empty());
+    }
+}
@@ -0,0 +1,540 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, LockedUnit};
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::{
+    indentation_of_line, reindent, removal_range, text_at, CodeAction, CodeActionKind, TextEdit,
+    WorkspaceEdit, INDENT,
+};
+use crate::data::{HasSource, Symbol};
+use crate::TokenAccess;
+use crate::{AnyEntKind, EntityId, HasTokenSpan, Position, Range, Source, SrcPos};
+use std::ops::Deref;
+
+/// What the cursor is placed on
+enum Target {
+    /// The declaration of an entity
+    Entity(Symbol),
+    /// An instantiation of an entity or of a component that is not declared
+    Instance(Symbol),
+    /// A declaration of a component or an instantiation of it
+    Component(EntityId),
+}
+
+/// The declarative part of the architecture that contains the cursor
+struct ArchitectureRegion {
+    begin_line: u32,
+    indent: String,
+}
+
+struct ComponentTargetSearcher<'a> {
+    root: &'a DesignRoot,
+    cursor: Position,
+    architecture: Option<ArchitectureRegion>,
+    target: Option<Target>,
+}
+
+fn designator_symbol(name: &Name) -> Option<Symbol> {
+    match name {
+        Name::Designator(designator) => match &designator.item {
+            Designator::Identifier(symbol) => Some(symbol.clone()),
+            _ => None,
+        },
+        Name::Selected(_, suffix) => match &suffix.item.item {
+            Designator::Identifier(symbol) => Some(symbol.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl ComponentTargetSearcher<'_> {
+    fn instance_target(&self, inst: &InstantiationStatement) -> Option<Target> {
+        match &inst.unit {
+            InstantiatedUnit::Entity(name, _) => {
+                let ent = self.root.get_ent(name.item.get_suffix_reference()?);
+                match ent.designator() {
+                    Designator::Identifier(symbol) => Some(Target::Instance(symbol.clone())),
+                    _ => None,
+                }
+            }
+            InstantiatedUnit::Component(name) => match name.item.get_suffix_reference() {
+                Some(id) if matches!(self.root.get_ent(id).kind(), AnyEntKind::Component(_)) => {
+                    Some(Target::Component(id))
+                }
+                Some(_) => None,
+                None => designator_symbol(&name.item).map(Target::Instance),
+            },
+            InstantiatedUnit::Configuration(_) => None,
+        }
+    }
+}
+
+impl Searcher for ComponentTargetSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Entity(entity)
+                if entity.ident.tree.pos(ctx).range().contains(self.cursor) =>
+            {
+                self.target = Some(Target::Entity(entity.ident.tree.item.clone()));
+            }
+            DeclarationItem::Architecture(arch)
+                if arch.span.pos(ctx).range().contains(self.cursor) =>
+            {
+                let begin_line = ctx.get_pos(arch.begin_token).start().line;
+                let source = arch.span.pos(ctx).source().clone();
+                let indent = match arch.decl.first() {
+                    Some(decl) => indentation_of_line(&source, decl.pos(ctx).start().line),
+                    None => format!("{}{INDENT}", indentation_of_line(&source, begin_line)),
+                };
+                self.architecture = Some(ArchitectureRegion { begin_line, indent });
+            }
+            DeclarationItem::Component(component)
+                if component.span.pos(ctx).range().contains(self.cursor) =>
+            {
+                self.target = decl.reference.get().map(Target::Component);
+            }
+            DeclarationItem::ConcurrentStatement(stmt)
+                if stmt.get_pos(ctx).range().contains(self.cursor) =>
+            {
+                if let ConcurrentStatement::Instance(inst) = &stmt.statement.item {
+                    self.target = self.instance_target(inst);
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Finds the position of the declaration of a component
+pub(super) struct ComponentDeclarationSearcher {
+    pub(super) component: EntityId,
+    pub(super) result: Option<SrcPos>,
+}
+
+impl Searcher for ComponentDeclarationSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::Component(component) = decl.ast {
+            if decl.reference.get() == Some(self.component) {
+                self.result = Some(component.span.pos(ctx));
+                return Finished(Found);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// The text of a generic or port clause of an entity, indented for a component declaration.
+/// The clause keeps its indentation relative to the entity declaration.
+fn clause_text(
+    ctx: &dyn TokenAccess,
+    clause: &Option<InterfaceList>,
+    entity_indent: &str,
+    indent: &str,
+) -> Option<String> {
+    let pos = clause.as_ref()?.span.pos(ctx);
+    let old_indent = indentation_of_line(pos.source(), pos.start().line);
+    let step = match old_indent.strip_prefix(entity_indent) {
+        Some(step) if !step.is_empty() => step,
+        _ => INDENT,
+    };
+    Some(reindent(
+        &text_at(pos.source(), pos.range()),
+        &old_indent,
+        &format!("{indent}{step}"),
+    ))
+}
+
+/// Generates the declaration of a component with the same name, generics and ports as an entity.
+/// The generic and port clauses are copied from the entity declaration as they were written.
+fn component_declaration(unit: &LockedUnit, indent: &str) -> Option<String> {
+    let data = unit.unit.get()?;
+    let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() else {
+        return None;
+    };
+    let ctx: &dyn TokenAccess = &unit.tokens;
+    let ident = entity.ident.tree.pos(ctx);
+    let name = text_at(ident.source(), ident.range());
+    let entity_indent = indentation_of_line(ident.source(), ident.start().line);
+    let mut text = format!("{indent}component {name} is\n");
+    for clause in [&entity.generic_clause, &entity.port_clause] {
+        if let Some(clause) = clause_text(ctx, clause, &entity_indent, indent) {
+            text.push_str(&clause);
+            text.push('\n');
+        }
+    }
+    text.push_str(&format!("{indent}end component;\n"));
+    Some(text)
+}
+
+/// Compares declarations disregarding whitespace and case
+fn normalized(text: &str) -> String {
+    text.chars()
+        .filter(|chr| !chr.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn entity_unit<'a>(
+    root: &'a DesignRoot,
+    library: &Symbol,
+    name: &Symbol,
+) -> Option<&'a LockedUnit> {
+    let unit = root.get_lib(library)?.primary_unit(name)?;
+    let data = unit.unit.get()?;
+    matches!(
+        data.deref(),
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(_))
+    )
+    .then_some(unit)
+}
+
+fn declare_action(title: String, file_name: &std::path::Path, edit: TextEdit) -> CodeAction {
+    let mut workspace_edit = WorkspaceEdit::default();
+    workspace_edit.add(file_name, edit);
+    CodeAction {
+        title,
+        kind: CodeActionKind::RefactorRewrite,
+        edit: workspace_edit,
+    }
+}
+
+/// Replaces the declaration of a component with one that matches the entity of the same name.
+/// Returns `None` when the component already matches.
+fn synchronize_component_action(
+    root: &DesignRoot,
+    library: &Symbol,
+    component: EntityId,
+) -> Option<CodeAction> {
+    let ent = root.get_ent(component);
+    let Designator::Identifier(name) = ent.designator() else {
+        return None;
+    };
+    let entity = entity_unit(root, library, name)?;
+    let source = ent.decl_pos()?.source();
+    let mut searcher = ComponentDeclarationSearcher {
+        component,
+        result: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    let pos = searcher.result?;
+    let indent = indentation_of_line(source, pos.start().line);
+    let text = component_declaration(entity, &indent)?;
+    let range = removal_range(source, pos.range());
+    if normalized(&text) == normalized(&text_at(source, range)) {
+        return None;
+    }
+    // The component does not span whole lines, e.g., when followed by a comment
+    let text = if range == pos.range() {
+        text.trim().to_owned()
+    } else {
+        text
+    };
+    Some(declare_action(
+        format!("Synchronize component '{name}' with entity"),
+        source.file_name(),
+        TextEdit::new(range, text),
+    ))
+}
+
+/// Declares the component in each package of the library that does not already declare it
+fn declare_in_packages(
+    root: &DesignRoot,
+    library: &Symbol,
+    name: &Symbol,
+    entity: &LockedUnit,
+) -> Vec<CodeAction> {
+    let Some(lib) = root.get_lib(library) else {
+        return vec![];
+    };
+    let mut actions = Vec::new();
+    for unit in lib.primary_units() {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)) = data.deref() else {
+            continue;
+        };
+        let already_declared = package.decl.iter().any(|decl| {
+            matches!(&decl.item, Declaration::Component(component) if &component.ident.tree.item == name)
+        });
+        if already_declared {
+            continue;
+        }
+        let ctx: &dyn TokenAccess = &unit.tokens;
+        let end_pos = ctx.get_pos(package.end_token);
+        // The component is indented like the existing declarations of the package
+        let indent = match package.decl.first() {
+            Some(decl) => indentation_of_line(end_pos.source(), decl.pos(ctx).start().line),
+            None => format!(
+                "{}{INDENT}",
+                indentation_of_line(end_pos.source(), end_pos.start().line)
+            ),
+        };
+        let Some(text) = component_declaration(entity, &indent) else {
+            continue;
+        };
+        actions.push((
+            package.ident.tree.item.name_utf8(),
+            declare_action(
+                format!(
+                    "Declare component '{name}' in package '{}'",
+                    package.ident.tree.item
+                ),
+                end_pos.source().file_name(),
+                TextEdit::insert(Position::new(end_pos.start().line, 0), text),
+            ),
+        ));
+    }
+    actions.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    actions.into_iter().map(|(_, action)| action).collect()
+}
+
+/// Generates the component declaration of an entity from the entity declaration
+/// or from an instantiation of it.
+/// The component is declared in the architecture of the instantiation
+/// or in a package of the library.
+/// A component that no longer matches its entity can be synchronized with it.
+pub(super) fn generate_component_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let ctx: &dyn TokenAccess = &unit.tokens;
+        if !data.get_pos(ctx).range().contains(range.start) {
+            continue;
+        }
+        let mut searcher = ComponentTargetSearcher {
+            root,
+            cursor: range.start,
+            architecture: None,
+            target: None,
+        };
+        let _ = data.deref().search(ctx, &mut searcher);
+        let library = unit.unit_id().library_name();
+
+        let name = match searcher.target {
+            Some(Target::Component(id)) => {
+                return synchronize_component_action(root, library, id)
+                    .into_iter()
+                    .collect();
+            }
+            Some(Target::Entity(name)) | Some(Target::Instance(name)) => name,
+            None => return vec![],
+        };
+        let Some(entity) = entity_unit(root, library, &name) else {
+            return vec![];
+        };
+        let mut actions = Vec::new();
+        if let Some(region) = &searcher.architecture {
+            if let Some(text) = component_declaration(entity, &region.indent) {
+                actions.push(declare_action(
+                    format!("Declare component '{name}' in architecture"),
+                    source.file_name(),
+                    TextEdit::insert(Position::new(region.begin_line, 0), text),
+                ));
+            }
+        }
+        actions.extend(declare_in_packages(root, library, &name, entity));
+        return actions;
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction};
+    use crate::syntax::test::Code;
+    use crate::{Config, Position, Range};
+
+    fn component_actions(
+        root: &crate::analysis::DesignRoot,
+        code: &Code,
+        cursor: Position,
+    ) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(cursor, cursor),
+        )
+        .into_iter()
+        .filter(|action| {
+            action.title.starts_with("Declare component")
+                || action.title.starts_with("Synchronize component")
+        })
+        .collect()
+    }
+
+    #[test]
+    fn declares_component_of_instantiated_entity() {
+        let mut builder = LibraryBuilder::new();
+        let child = builder.code(
+            "libname",
+            "\
+entity child is
+  generic (width : natural := 1);
+  port (
+    clk : in bit;
+    data : out bit_vector(width - 1 downto 0)
+  );
+end entity;
+
+package pkg is
+  constant c : natural := 0;
+end package;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+begin
+  inst: entity work.child
+    port map (clk => clk, data => open);
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = component_actions(&root, &code, code.s1("inst").start());
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Declare component 'child' in architecture",
+                "Declare component 'child' in package 'pkg'",
+            ]
+        );
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+  component child is
+    generic (width : natural := 1);
+    port (
+      clk : in bit;
+      data : out bit_vector(width - 1 downto 0)
+    );
+  end component;
+begin
+  inst: entity work.child
+    port map (clk => clk, data => open);
+end architecture;"
+        );
+
+        // From the entity declaration, the component can only be declared in a package
+        let actions = component_actions(&root, &child, child.s1("child").start());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            apply_edits(child.source(), &actions[0]),
+            "\
+entity child is
+  generic (width : natural := 1);
+  port (
+    clk : in bit;
+    data : out bit_vector(width - 1 downto 0)
+  );
+end entity;
+
+package pkg is
+  constant c : natural := 0;
+  component child is
+    generic (width : natural := 1);
+    port (
+      clk : in bit;
+      data : out bit_vector(width - 1 downto 0)
+    );
+  end component;
+end package;"
+        );
+    }
+
+    #[test]
+    fn synchronizes_component_with_entity() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (clk, rst : in bit);
+end entity;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  component child is
+    port (clk : in bit);
+  end component;
+  signal clk : bit;
+begin
+  inst: child port map (clk => clk);
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = component_actions(&root, &code, code.s1("inst").start());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Synchronize component 'child' with entity"
+        );
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  component child is
+    port (clk, rst : in bit);
+  end component;
+  signal clk : bit;
+begin
+  inst: child port map (clk => clk);
+end architecture;"
+        );
+
+        // A component that matches its entity needs no synchronization
+        let code = builder.code(
+            "libname",
+            "\
+entity top2 is
+end entity;
+
+architecture a of top2 is
+  component child is
+    port (clk, rst : in bit);
+  end component;
+begin
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(component_actions(&root, &code, code.s1("component").start()).is_empty());
+    }
+}
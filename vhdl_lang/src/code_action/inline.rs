@@ -0,0 +1,732 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::{
+    referenced_entities, removal_range, start_of_next_line, text_at, CodeAction, CodeActionKind,
+    TextEdit, WorkspaceEdit,
+};
+use crate::named_entity::{InterfaceMode, Object};
+use crate::standard::VHDLStandard;
+use crate::syntax::TokenAccess;
+use crate::{AnyEntKind, Design, EntRef, EntityId, HasTokenSpan, Range, Source, SrcPos, Token};
+use fnv::FnvHashSet;
+use std::ops::Deref;
+
+/// The default expression of an object declaration
+struct DefaultValue {
+    pos: SrcPos,
+    type_mark: SrcPos,
+    is_object_name: bool,
+    is_parenthesized: bool,
+    references: FnvHashSet<EntityId>,
+}
+
+/// The value of a concurrent signal assignment when it simply copies another object
+struct CopiedValue {
+    pos: SrcPos,
+    ent: EntityId,
+    references: FnvHashSet<EntityId>,
+}
+
+/// A concurrent signal assignment with the object as target
+struct Driver {
+    pos: SrcPos,
+    copied: Option<CopiedValue>,
+}
+
+/// Finds the declaration of the object and all statements
+/// that (potentially) write the object.
+struct ObjectUsage<'a> {
+    root: &'a DesignRoot,
+    ent: EntRef<'a>,
+    /// The number of identifiers declared by the object declaration
+    /// and the default expression if any
+    declaration: Option<(usize, Option<DefaultValue>)>,
+    drivers: Vec<Driver>,
+    /// Set when the object is used in a way where it may be written
+    /// that is not a concurrent signal assignment
+    other_writes: bool,
+    /// All named entities that are declared in the design unit
+    declared: Vec<EntRef<'a>>,
+}
+
+impl<'a> ObjectUsage<'a> {
+    fn mentions(&self, ctx: &dyn TokenAccess, node: &impl Search) -> bool {
+        referenced_entities(ctx, node).contains(&self.ent.id())
+    }
+
+    fn is_object(&self, id: Option<EntityId>) -> bool {
+        id.is_some_and(|id| matches!(self.root.get_ent(id).kind(), AnyEntKind::Object(_)))
+    }
+
+    /// Named associations to a formal of mode `in` can only read the actual
+    fn is_read_only_association(&self, assoc: &AssociationElement) -> bool {
+        let Some(formal) = assoc.formal.as_ref() else {
+            return false;
+        };
+        let Some(id) = formal.item.get_suffix_reference() else {
+            return false;
+        };
+        match self.root.get_ent(id).kind() {
+            AnyEntKind::Object(object) => {
+                matches!(object.mode(), Some(InterfaceMode::Simple(Mode::In)))
+            }
+            _ => false,
+        }
+    }
+
+    fn search_map_aspect(&mut self, ctx: &dyn TokenAccess, map_aspect: &Option<MapAspect>) {
+        let Some(map_aspect) = map_aspect else {
+            return;
+        };
+        for assoc in map_aspect.list.items.iter() {
+            if self.mentions(ctx, assoc) && !self.is_read_only_association(assoc) {
+                self.other_writes = true;
+            }
+        }
+    }
+
+    fn default_value(
+        &self,
+        ctx: &dyn TokenAccess,
+        decl: &ObjectDeclaration,
+    ) -> Option<DefaultValue> {
+        let expr = decl.expression.as_ref()?;
+        Some(DefaultValue {
+            pos: expr.pos(ctx),
+            type_mark: decl.subtype_indication.type_mark.pos(ctx),
+            is_object_name: match &expr.item {
+                Expression::Name(name) => self.is_object(name.get_suffix_reference()),
+                Expression::Qualified(_) => true,
+                _ => false,
+            },
+            is_parenthesized: matches!(
+                expr.item,
+                Expression::Aggregate(_) | Expression::Parenthesized(_)
+            ),
+            references: referenced_entities(ctx, expr),
+        })
+    }
+
+    /// Returns the copied value when the assignment is an unconditional
+    /// assignment of another object without any delay.
+    fn copied_value(
+        &self,
+        ctx: &dyn TokenAccess,
+        assignment: &ConcurrentSignalAssignment,
+    ) -> Option<CopiedValue> {
+        if assignment.guarded || assignment.assignment.delay_mechanism.is_some() {
+            return None;
+        }
+        let Target::Name(Name::Designator(_)) = &assignment.assignment.target.item else {
+            return None;
+        };
+        let AssignmentRightHand::Simple(Waveform::Elements(elements)) = &assignment.assignment.rhs
+        else {
+            return None;
+        };
+        let [element] = elements.as_slice() else {
+            return None;
+        };
+        if element.after.is_some() {
+            return None;
+        }
+        let Expression::Name(name) = &element.value.item else {
+            return None;
+        };
+        let id = name.get_suffix_reference()?;
+        if !self.is_object(Some(id)) {
+            return None;
+        }
+        Some(CopiedValue {
+            pos: element.value.pos(ctx),
+            ent: id,
+            references: referenced_entities(ctx, &element.value),
+        })
+    }
+}
+
+impl Searcher for ObjectUsage<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let Some(id) = decl.reference.get() {
+            self.declared.push(self.root.get_ent(id));
+        }
+        match decl.ast {
+            DeclarationItem::Object(object)
+                if object
+                    .idents
+                    .iter()
+                    .any(|ident| ident.decl.get() == Some(self.ent.id())) =>
+            {
+                self.declaration = Some((object.idents.len(), self.default_value(ctx, object)));
+            }
+            DeclarationItem::ConcurrentStatement(stmt) => match &stmt.statement.item {
+                ConcurrentStatement::Assignment(assignment)
+                    if self.mentions(ctx, &assignment.assignment.target) =>
+                {
+                    self.drivers.push(Driver {
+                        pos: stmt.get_pos(ctx),
+                        copied: self.copied_value(ctx, assignment),
+                    });
+                }
+                ConcurrentStatement::Instance(instance) => {
+                    self.search_map_aspect(ctx, &instance.port_map);
+                }
+                ConcurrentStatement::Block(block) => {
+                    self.search_map_aspect(ctx, &block.header.port_map);
+                }
+                ConcurrentStatement::ProcedureCall(call) if self.mentions(ctx, &call.call.item) => {
+                    self.other_writes = true;
+                }
+                _ => {}
+            },
+            DeclarationItem::SequentialStatement(stmt) => {
+                let writes = match &stmt.statement.item {
+                    SequentialStatement::SignalAssignment(assignment) => {
+                        self.mentions(ctx, &assignment.target)
+                    }
+                    SequentialStatement::SignalForceAssignment(assignment) => {
+                        self.mentions(ctx, &assignment.target)
+                    }
+                    SequentialStatement::SignalReleaseAssignment(assignment) => {
+                        self.mentions(ctx, &assignment.target)
+                    }
+                    SequentialStatement::VariableAssignment(assignment) => {
+                        self.mentions(ctx, &assignment.target)
+                    }
+                    SequentialStatement::ProcedureCall(call) => self.mentions(ctx, &call.item),
+                    _ => false,
+                };
+                if writes {
+                    self.other_writes = true;
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// The subtype indication of an object declaration, as written and with the named entities
+/// that it refers to. Objects with equal subtype indications have the same subtype.
+#[derive(PartialEq, Eq)]
+struct SubtypeOf {
+    text: String,
+    references: FnvHashSet<EntityId>,
+}
+
+/// Finds the subtype indication of the declaration of an object
+struct SubtypeSearcher {
+    ent: EntityId,
+    result: Option<SubtypeOf>,
+}
+
+impl Searcher for SubtypeSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if decl.reference.get() != Some(self.ent) {
+            return NotFinished;
+        }
+        let subtype_indication = match decl.ast {
+            DeclarationItem::Object(object) => &object.subtype_indication,
+            DeclarationItem::InterfaceObject(object) => match &object.mode {
+                ModeIndication::Simple(mode) => &mode.subtype_indication,
+                ModeIndication::View(_) => return Finished(Found),
+            },
+            _ => return Finished(Found),
+        };
+        self.result = Some(SubtypeOf {
+            text: subtype_indication.to_string().to_lowercase(),
+            references: referenced_entities(ctx, subtype_indication),
+        });
+        Finished(Found)
+    }
+}
+
+fn subtype_of(root: &DesignRoot, ent: EntRef<'_>) -> Option<SubtypeOf> {
+    let mut searcher = SubtypeSearcher {
+        ent: ent.id(),
+        result: None,
+    };
+    let _ = root.search_source(&ent.decl_pos()?.source, &mut searcher);
+    searcher.result
+}
+
+/// Returns the architecture that the named entity is declared in
+fn enclosing_architecture(ent: EntRef<'_>) -> Option<EntRef<'_>> {
+    let mut current = ent.parent;
+    while let Some(parent) = current {
+        if let AnyEntKind::Design(design) = parent.kind() {
+            return if matches!(design, Design::Architecture(..)) {
+                Some(parent)
+            } else {
+                None
+            };
+        }
+        current = parent.parent;
+    }
+    None
+}
+
+/// Replacing a name with an expression is only possible when the name
+/// is not used as a prefix, i.e., it is not indexed, sliced, selected or has an attribute.
+/// Even if the replacement is also a name, the index range of the inlined object might differ.
+fn is_used_as_prefix(source: &Source, range: Range) -> bool {
+    text_at(source, Range::new(range.end, start_of_next_line(range.end)))
+        .trim_start()
+        .starts_with(['(', '\'', '.'])
+}
+
+struct Inline<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    tokens: &'a Vec<Token>,
+    ent: EntRef<'a>,
+    usage: ObjectUsage<'a>,
+}
+
+impl Inline<'_> {
+    /// The named entities that the replacement refers to must resolve to the same
+    /// named entities at all places where the object is used.
+    /// As a conservative approximation, the design unit must not contain any other
+    /// declaration with the same name.
+    fn is_unambiguous(&self, references: &FnvHashSet<EntityId>) -> bool {
+        references
+            .iter()
+            .map(|id| self.root.get_ent(*id))
+            .all(|referenced| {
+                !self.usage.declared.iter().any(|declared| {
+                    declared.id() != referenced.id()
+                        && declared.designator() == referenced.designator()
+                })
+            })
+    }
+
+    /// The replacement for the constant is the default expression, qualified with the type
+    /// of the constant unless it is the name of another object. This keeps overload resolution
+    /// unaffected by the change.
+    fn constant_replacement(&self, value: &DefaultValue) -> Option<String> {
+        if !self.is_unambiguous(&value.references) {
+            return None;
+        }
+        let text = text_at(self.source, value.pos.range());
+        let type_mark = text_at(self.source, value.type_mark.range());
+        Some(if value.is_object_name {
+            text
+        } else if value.is_parenthesized {
+            format!("{type_mark}'{text}")
+        } else {
+            format!("{type_mark}'({text})")
+        })
+    }
+
+    /// A signal can be inlined if it is driven by a single, unconditional concurrent
+    /// signal assignment from another signal of the same subtype and is not written anywhere else.
+    /// Note that removing the signal also removes the delta cycle introduced by the assignment.
+    fn signal_replacement(&self, object: &Object<'_>) -> Option<String> {
+        if object.has_default || self.usage.other_writes {
+            return None;
+        }
+        let [driver] = self.usage.drivers.as_slice() else {
+            return None;
+        };
+        let copied = driver.copied.as_ref()?;
+        let copied_ent = self.root.get_ent(copied.ent);
+        let AnyEntKind::Object(copied_object) = copied_ent.kind() else {
+            return None;
+        };
+        if copied.ent == self.ent.id() || !self.is_unambiguous(&copied.references) {
+            return None;
+        }
+        // A different subtype would change the constraints and the resolution of the signal
+        if copied_object.subtype.type_mark().id() != object.subtype.type_mark().id()
+            || subtype_of(self.root, copied_ent)? != subtype_of(self.root, self.ent)?
+        {
+            return None;
+        }
+        // The uses of the signal would read the port instead, which is illegal before VHDL-2008
+        if copied_object.mode() == Some(&InterfaceMode::Simple(Mode::Out))
+            && self.root.standard() < VHDLStandard::VHDL2008
+        {
+            return None;
+        }
+        Some(text_at(self.source, copied.pos.range()))
+    }
+
+    fn code_action(&self) -> Option<CodeAction> {
+        let AnyEntKind::Object(object) = self.ent.kind() else {
+            return None;
+        };
+        let (num_idents, value) = self.usage.declaration.as_ref()?;
+        if object.iface.is_some() || *num_idents != 1 {
+            return None;
+        }
+
+        let mut edit = WorkspaceEdit::default();
+        let mut skipped = vec![self.ent.src_span.pos(self.tokens).range()];
+        let (replacement, title) = match object.class {
+            ObjectClass::Constant => (
+                self.constant_replacement(value.as_ref()?)?,
+                format!("Inline constant '{}'", self.ent.designator()),
+            ),
+            ObjectClass::Signal => {
+                let replacement = self.signal_replacement(object)?;
+                skipped.push(self.usage.drivers[0].pos.range());
+                (
+                    replacement,
+                    format!("Inline signal '{}'", self.ent.designator()),
+                )
+            }
+            _ => return None,
+        };
+
+        for pos in self
+            .root
+            .find_all_references_in_source(self.source, self.ent)
+        {
+            let range = pos.range();
+            if skipped.iter().any(|skip| skip.contains(range.start)) {
+                continue;
+            }
+            if is_used_as_prefix(self.source, range) {
+                return None;
+            }
+            edit.add(
+                self.source.file_name(),
+                TextEdit::new(range, replacement.clone()),
+            );
+        }
+        for range in skipped {
+            edit.add(
+                self.source.file_name(),
+                TextEdit::delete(removal_range(self.source, range)),
+            );
+        }
+
+        Some(CodeAction {
+            title,
+            kind: CodeActionKind::RefactorInline,
+            edit,
+        })
+    }
+}
+
+/// Replaces all uses of a constant (or a signal that simply copies another signal)
+/// with its value and removes the declaration.
+pub(super) fn inline_actions(root: &DesignRoot, source: &Source, range: Range) -> Vec<CodeAction> {
+    let Some((_, ent)) = root.item_at_cursor(source, range.start) else {
+        return vec![];
+    };
+    // Only objects declared inside an architecture are inlined.
+    // All references are then guaranteed to be inside the same design unit.
+    let Some(arch) = enclosing_architecture(ent) else {
+        return vec![];
+    };
+    if arch.source.as_ref() != Some(source) {
+        return vec![];
+    }
+    let Some(unit) = root.units_by_source(source).find(|unit| {
+        unit.unit.get().is_some_and(|data| match data.deref() {
+            AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(body)) => {
+                body.ident.decl.get() == Some(arch.id())
+            }
+            _ => false,
+        })
+    }) else {
+        return vec![];
+    };
+
+    let mut usage = ObjectUsage {
+        root,
+        ent,
+        declaration: None,
+        drivers: Vec::new(),
+        other_writes: false,
+        declared: Vec::new(),
+    };
+    let _ = unit.unit.expect_analyzed().search(&unit.tokens, &mut usage);
+
+    let inline = Inline {
+        root,
+        source,
+        tokens: &unit.tokens,
+        ent,
+        usage,
+    };
+    inline.code_action().into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::analysis::DesignRoot;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction, CodeActionKind};
+    use crate::syntax::test::Code;
+    use crate::Config;
+    use crate::Range;
+    use crate::VHDLStandard;
+
+    fn inline_actions(root: &DesignRoot, code: &Code) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(code.start(), code.start()),
+        )
+        .into_iter()
+        .filter(|action| action.kind == CodeActionKind::RefactorInline)
+        .collect()
+    }
+
+    #[test]
+    fn inlines_constant() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := 4;
+  constant d : natural := c;
+  signal s : bit_vector(0 to c - 1);
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = inline_actions(&root, &code.s1("c - 1"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Inline constant 'c'");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant d : natural := natural'(4);
+  signal s : bit_vector(0 to natural'(4) - 1);
+begin
+end architecture;
+"
+        );
+
+        // A constant that is just the name of another constant needs no qualification
+        let actions = inline_actions(&root, &code.s1("constant d").s1("d"));
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := 4;
+  signal s : bit_vector(0 to c - 1);
+begin
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn does_not_inline_constant_used_as_prefix() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : bit_vector(0 to 1) := \"01\";
+  constant d : bit := c(0);
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        assert!(inline_actions(&root, &code.s1("constant c").s1("c")).is_empty());
+    }
+
+    #[test]
+    fn does_not_inline_when_replacement_is_shadowed() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant x : natural := 1;
+  constant c : natural := x;
+begin
+  process
+    constant x : natural := 2;
+    variable v : natural;
+  begin
+    v := c;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        assert!(inline_actions(&root, &code.s1("constant c").s1("c")).is_empty());
+    }
+
+    #[test]
+    fn inlines_signal_wired_to_other_signal() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    din : in bit;
+    dout : out bit);
+end entity;
+
+architecture a of ent is
+  signal s : bit;
+begin
+  s <= din;
+  dout <= not s;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = inline_actions(&root, &code.s1("not s").s1("s"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Inline signal 's'");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+  port (
+    din : in bit;
+    dout : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  dout <= not din;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn does_not_inline_signal_with_other_writes() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    din : in bit;
+    dout : out bit);
+end entity;
+
+architecture a of ent is
+  signal s, t : bit;
+begin
+  s <= din;
+  t <= din after 1 ns;
+  dout <= s and t;
+
+  process
+  begin
+    s <= '1';
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(inline_actions(&root, &code.s1("s and").s1("s")).is_empty());
+        assert!(inline_actions(&root, &code.s1("and t").s1("t")).is_empty());
+    }
+
+    #[test]
+    fn does_not_inline_signal_of_other_subtype() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    a : in integer;
+    b : in bit_vector(3 downto 0);
+    c : in bit_vector(3 downto 0);
+    q : out bit_vector(3 downto 0));
+end entity;
+
+architecture a of ent is
+  signal n : natural;
+  signal v : bit_vector(0 to 3);
+  signal w : bit_vector(3 downto 0);
+begin
+  n <= a;
+  v <= b;
+  w <= c;
+  q <= v and w when n > 0 else w;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        assert!(inline_actions(&root, &code.s1("n >").s1("n")).is_empty());
+        assert!(inline_actions(&root, &code.s1("v and").s1("v")).is_empty());
+        assert_eq!(
+            inline_actions(&root, &code.s1("and w").s1("w")).len(),
+            1,
+            "The subtypes of 'w' and 'c' are the same"
+        );
+    }
+
+    #[test]
+    fn does_not_inline_signal_that_copies_out_port_before_vhdl2008() {
+        let source = "\
+entity ent is
+  port (
+    q : out bit;
+    r : out bit);
+end entity;
+
+architecture a of ent is
+  signal s : bit;
+begin
+  q <= '1';
+  s <= q;
+  r <= not s;
+end architecture;
+";
+        let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL1993);
+        let code = builder.code("libname", source);
+        let (root, _) = builder.get_analyzed_root();
+        assert!(inline_actions(&root, &code.s1("not s").s1("s")).is_empty());
+
+        let mut builder = LibraryBuilder::with_standard(VHDLStandard::VHDL2008);
+        let code = builder.code("libname", source);
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(inline_actions(&root, &code.s1("not s").s1("s")).len(), 1);
+    }
+}
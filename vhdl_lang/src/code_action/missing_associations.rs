@@ -0,0 +1,615 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::{
+    indentation_of_line, referenced_entities, text_at, CodeAction, CodeActionKind, TextEdit,
+    WorkspaceEdit, INDENT,
+};
+use crate::data::HasSource;
+use crate::named_entity::{InterfaceEnt, InterfaceMode};
+use crate::{
+    AnyEntKind, Design, EntRef, HasTokenSpan, Position, Range, Source, TokenAccess, TokenSpan,
+};
+use fnv::FnvHashSet;
+use std::ops::Deref;
+
+/// Where to put the associations for the missing ports
+pub(super) enum InsertionPoint {
+    /// After the last element of an existing port map.
+    /// The indentation is `None` when the port map is written on a single line.
+    AfterLastElement(Position, Option<String>),
+    /// Before the semicolon of an instantiation without a port map
+    NewPortMap(Position, String),
+}
+
+/// An instantiation statement with ports that are not associated
+struct IncompleteInstance<'a> {
+    label: Option<String>,
+    missing: Vec<InterfaceEnt<'a>>,
+    insertion_point: InsertionPoint,
+}
+
+/// Finds the instantiation at the cursor and the ports that it does not associate
+struct InstanceSearcher<'a> {
+    root: &'a DesignRoot,
+    cursor: Position,
+    result: Option<IncompleteInstance<'a>>,
+}
+
+/// Returns the ports of the instantiated unit that are not associated by an instantiation
+pub(super) fn missing_ports<'a>(
+    root: &'a DesignRoot,
+    ctx: &dyn TokenAccess,
+    inst: &InstantiationStatement,
+) -> Option<Vec<InterfaceEnt<'a>>> {
+    let region = match root.get_ent(inst.entity_reference()?).kind() {
+        AnyEntKind::Component(region) => region,
+        AnyEntKind::Design(Design::Entity(_, region)) => region,
+        _ => return None,
+    };
+    let (ports, _) = region.ports_and_generics();
+    let Some(port_map) = &inst.port_map else {
+        return Some(ports);
+    };
+    let positional = port_map
+        .list
+        .items
+        .iter()
+        .take_while(|assoc| assoc.formal.is_none())
+        .count();
+    let named: FnvHashSet<_> = port_map
+        .list
+        .items
+        .iter()
+        .filter_map(|assoc| assoc.formal.as_ref())
+        .flat_map(|formal| referenced_entities(ctx, formal))
+        .collect();
+    Some(
+        ports
+            .into_iter()
+            .skip(positional)
+            .filter(|port| !named.contains(&port.id()))
+            .collect(),
+    )
+}
+
+/// Returns where associations for missing ports are added to an instantiation
+pub(super) fn insertion_point(
+    ctx: &dyn TokenAccess,
+    stmt: &LabeledConcurrentStatement,
+    inst: &InstantiationStatement,
+) -> InsertionPoint {
+    let stmt_pos = stmt.get_pos(ctx);
+    let source = stmt_pos.source();
+    match inst.port_map.as_ref().and_then(|map| map.list.items.last()) {
+        Some(last) => {
+            let map_pos = inst.port_map.as_ref().unwrap().get_pos(ctx);
+            let start = match &last.formal {
+                Some(formal) => formal.pos(ctx).start(),
+                None => last.actual.pos(ctx).start(),
+            };
+            let indent = if map_pos.start().line == map_pos.end().line {
+                None
+            } else {
+                Some(indentation_of_line(source, start.line))
+            };
+            InsertionPoint::AfterLastElement(last.actual.pos(ctx).end(), indent)
+        }
+        None => {
+            let semicolon = stmt.get_end_token().pos(ctx).start();
+            InsertionPoint::NewPortMap(
+                semicolon,
+                indentation_of_line(source, stmt_pos.start().line),
+            )
+        }
+    }
+}
+
+/// Returns the edit that adds the given associations at the insertion point
+pub(super) fn associations_edit(
+    insertion_point: &InsertionPoint,
+    associations: impl Iterator<Item = String>,
+) -> TextEdit {
+    match insertion_point {
+        InsertionPoint::AfterLastElement(pos, Some(indent)) => TextEdit::insert(
+            *pos,
+            associations
+                .map(|assoc| format!(",\n{indent}{assoc}"))
+                .collect::<String>(),
+        ),
+        InsertionPoint::AfterLastElement(pos, None) => TextEdit::insert(
+            *pos,
+            associations
+                .map(|assoc| format!(", {assoc}"))
+                .collect::<String>(),
+        ),
+        InsertionPoint::NewPortMap(pos, indent) => TextEdit::insert(
+            *pos,
+            format!(
+                "\n{indent}{INDENT}port map (\n{}\n{indent}{INDENT})",
+                associations
+                    .map(|assoc| format!("{indent}{INDENT}{INDENT}{assoc}"))
+                    .collect::<Vec<_>>()
+                    .join(",\n")
+            ),
+        ),
+    }
+}
+
+impl<'a> Searcher for InstanceSearcher<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::ConcurrentStatement(stmt) = decl.ast else {
+            return NotFinished;
+        };
+        let ConcurrentStatement::Instance(inst) = &stmt.statement.item else {
+            return NotFinished;
+        };
+        if !stmt.get_span(ctx).contains(self.cursor) {
+            return NotFinished;
+        }
+        if let Some(missing) = missing_ports(self.root, ctx, inst) {
+            // Ports with a default value do not have to be associated
+            let missing: Vec<_> = missing
+                .into_iter()
+                .filter(|port| !port.has_default())
+                .collect();
+            if !missing.is_empty() {
+                self.result = Some(IncompleteInstance {
+                    label: stmt.label.tree.as_ref().map(|label| label.item.to_string()),
+                    missing,
+                    insertion_point: insertion_point(ctx, stmt, inst),
+                });
+            }
+        }
+        Finished(Found)
+    }
+}
+
+/// Finds the subtype indication of a port as it was written in the interface declaration
+struct SubtypeSearcher<'a> {
+    root: &'a DesignRoot,
+    port: EntRef<'a>,
+    result: Option<String>,
+}
+
+impl SubtypeSearcher<'_> {
+    /// Returns `true` when the subtype refers to generics of the instantiated unit.
+    /// Such a subtype cannot be copied to the architecture.
+    fn depends_on_generics(&self, ctx: &dyn TokenAccess, subtype: &SubtypeIndication) -> bool {
+        referenced_entities(ctx, subtype).into_iter().any(|id| {
+            let referenced = self.root.get_ent(id);
+            referenced.parent.map(|parent| parent.id())
+                == self.port.parent.map(|parent| parent.id())
+        })
+    }
+}
+
+impl Searcher for SubtypeSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::InterfaceObject(object) = decl.ast else {
+            return NotFinished;
+        };
+        if decl.reference.get() != Some(self.port.id()) {
+            return NotFinished;
+        }
+        if let ModeIndication::Simple(mode) = &object.mode {
+            let subtype = &mode.subtype_indication;
+            if subtype.resolution.is_none() && !self.depends_on_generics(ctx, subtype) {
+                let end_token = match &subtype.constraint {
+                    Some(constraint) => constraint.span.end_token,
+                    None => subtype.type_mark.span.end_token,
+                };
+                let pos = TokenSpan::new(subtype.type_mark.span.start_token, end_token).pos(ctx);
+                self.result = Some(text_at(pos.source(), pos.range()));
+            }
+        }
+        Finished(Found)
+    }
+}
+
+struct MissingAssociations<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    arch: &'a ArchitectureBody,
+    tokens: &'a dyn TokenAccess,
+    instance: IncompleteInstance<'a>,
+}
+
+impl MissingAssociations<'_> {
+    fn associations_edit(&self, actuals: &[String]) -> TextEdit {
+        let associations = self
+            .instance
+            .missing
+            .iter()
+            .zip(actuals)
+            .map(|(port, actual)| format!("{} => {actual}", port.designator()));
+        associations_edit(&self.instance.insertion_point, associations)
+    }
+
+    /// Returns `None` when a port may not be left open,
+    /// i.e., a port of mode `in` without a default value (LRM 6.5.6.3)
+    fn connect_to_open(&self) -> Option<CodeAction> {
+        let is_in_port = |port: &InterfaceEnt<'_>| match port.kind() {
+            AnyEntKind::Object(obj) => matches!(obj.mode(), Some(InterfaceMode::Simple(Mode::In))),
+            _ => false,
+        };
+        if self.instance.missing.iter().any(is_in_port) {
+            return None;
+        }
+        let actuals = vec!["open".to_owned(); self.instance.missing.len()];
+        let mut edit = WorkspaceEdit::default();
+        edit.add(self.source.file_name(), self.associations_edit(&actuals));
+        Some(CodeAction {
+            title: "Associate missing ports with 'open'".to_owned(),
+            kind: CodeActionKind::QuickFix,
+            edit,
+        })
+    }
+
+    /// Returns the subtype indication of the port, if it can be used
+    /// for a signal declaration in the architecture
+    fn subtype_of(&self, port: &InterfaceEnt<'_>) -> Option<String> {
+        let decl_pos = port.decl_pos()?;
+        let mut searcher = SubtypeSearcher {
+            root: self.root,
+            port: port.deref(),
+            result: None,
+        };
+        let _ = self.root.search_source(decl_pos.source(), &mut searcher);
+        searcher.result
+    }
+
+    fn connect_to_new_signals(&self) -> Option<CodeAction> {
+        let arch_ent = self.root.get_ent(self.arch.ident.decl.get()?);
+        let AnyEntKind::Design(Design::Architecture(_, region, entity)) = arch_ent.kind() else {
+            return None;
+        };
+        let Design::Entity(_, entity_region) = entity.kind() else {
+            return None;
+        };
+        let is_declared = |name: &str| {
+            let designator = Designator::Identifier(self.root.symbol_utf8(name));
+            region.lookup_immediate(&designator).is_some()
+                || entity_region.lookup_immediate(&designator).is_some()
+        };
+
+        let mut names: Vec<String> = Vec::new();
+        let mut declarations = String::new();
+        let indent = match self.arch.decl.first() {
+            Some(decl) => indentation_of_line(self.source, decl.get_pos(self.tokens).start().line),
+            None => INDENT.to_owned(),
+        };
+        for port in self.instance.missing.iter() {
+            let port_name = port.designator().to_string();
+            let candidates = std::iter::once(port_name.clone()).chain(
+                self.instance
+                    .label
+                    .as_ref()
+                    .map(|label| format!("{label}_{port_name}")),
+            );
+            let name = candidates
+                .into_iter()
+                .find(|name| !is_declared(name) && !names.contains(name))?;
+            let subtype = self.subtype_of(port)?;
+            declarations.push_str(&format!("{indent}signal {name} : {subtype};\n"));
+            names.push(name);
+        }
+
+        let begin_line = self.arch.begin_token.pos(self.tokens).start().line;
+        let mut edit = WorkspaceEdit::default();
+        edit.add(
+            self.source.file_name(),
+            TextEdit::insert(Position::new(begin_line, 0), declarations),
+        );
+        edit.add(self.source.file_name(), self.associations_edit(&names));
+        Some(CodeAction {
+            title: "Associate missing ports with new signals".to_owned(),
+            kind: CodeActionKind::QuickFix,
+            edit,
+        })
+    }
+}
+
+/// Adds associations for all ports that are not associated in an instantiation
+pub(super) fn missing_association_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = data.deref() else {
+            continue;
+        };
+        if !arch.get_span(&unit.tokens).contains(range.start) {
+            continue;
+        }
+        let mut searcher = InstanceSearcher {
+            root,
+            cursor: range.start,
+            result: None,
+        };
+        let _ = arch.search(&unit.tokens, &mut searcher);
+        let Some(instance) = searcher.result else {
+            return vec![];
+        };
+        let fix = MissingAssociations {
+            root,
+            source,
+            arch,
+            tokens: &unit.tokens,
+            instance,
+        };
+        return fix
+            .connect_to_open()
+            .into_iter()
+            .chain(fix.connect_to_new_signals())
+            .collect();
+    }
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::analysis::DesignRoot;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction, CodeActionKind};
+    use crate::syntax::test::Code;
+    use crate::Config;
+    use crate::Range;
+
+    fn quick_fixes(root: &DesignRoot, code: &Code) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(code.start(), code.start()),
+        )
+        .into_iter()
+        .filter(|action| action.kind == CodeActionKind::QuickFix)
+        .collect()
+    }
+
+    fn builder_with_entity() -> LibraryBuilder {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  generic (width : natural := 8);
+  port (
+    clk : in bit;
+    data : in bit_vector(width - 1 downto 0);
+    valid : in bit;
+    result : out bit);
+end entity;
+",
+        );
+        builder
+    }
+
+    #[test]
+    fn associates_missing_ports_with_open() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (
+    clk : in bit;
+    enable : in bit := '1';
+    result : out bit;
+    status : inout bit);
+end entity;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+begin
+  inst: entity work.child
+    port map (
+      clk => clk);
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        // The port 'enable' has a default value and does not have to be associated
+        let actions = quick_fixes(&root, &code.s1("work.child"));
+        assert_eq!(actions[0].title, "Associate missing ports with 'open'");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+begin
+  inst: entity work.child
+    port map (
+      clk => clk,
+      result => open,
+      status => open);
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn does_not_leave_in_ports_without_default_open() {
+        let mut builder = builder_with_entity();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+begin
+  inst: entity work.child port map (clk => clk, data => open);
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = quick_fixes(&root, &code.s1("work.child"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Associate missing ports with new signals");
+    }
+
+    #[test]
+    fn does_not_declare_signals_with_generic_subtypes() {
+        let mut builder = builder_with_entity();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  inst: entity work.child port map (clk => open);
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        // The subtype of port 'data' depends on a generic and cannot be copied,
+        // and the input ports may not be left open
+        assert!(quick_fixes(&root, &code.s1("work.child")).is_empty());
+    }
+
+    #[test]
+    fn associates_missing_ports_with_new_signals() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (
+    clk : in bit;
+    valid : in bit;
+    result : out bit_vector(1 downto 0));
+end entity;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, valid : bit;
+begin
+  inst: entity work.child port map (clk);
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        // The input port 'valid' may not be left open
+        let actions = quick_fixes(&root, &code.s1("work.child"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Associate missing ports with new signals");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, valid : bit;
+  signal inst_valid : bit;
+  signal result : bit_vector(1 downto 0);
+begin
+  inst: entity work.child port map (clk, valid => inst_valid, result => result);
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn adds_port_map_to_component_instance() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp is
+    port (a, b : out bit);
+  end component;
+begin
+  inst: component comp;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let actions = quick_fixes(&root, &code.s1("inst"));
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp is
+    port (a, b : out bit);
+  end component;
+begin
+  inst: component comp
+      port map (
+          a => open,
+          b => open
+      );
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn no_fix_when_all_ports_are_associated() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp is
+    port (a : in bit);
+  end component;
+  signal s : bit;
+begin
+  inst: comp port map (a => s);
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(quick_fixes(&root, &code.s1("inst")).is_empty());
+    }
+}
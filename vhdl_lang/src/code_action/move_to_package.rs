@@ -0,0 +1,753 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, LockedUnit};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::{
+    indentation_of_line, referenced_entities, reindent, removal_range, start_of_next_line, text_at,
+    CodeAction, CodeActionKind, TextEdit, WorkspaceEdit, INDENT,
+};
+use crate::data::{HasSource, Symbol};
+use crate::syntax::TokenAccess;
+use crate::{
+    AnyEntKind, Design, EntRef, EntityId, HasTokenSpan, Position, Range, Related, Source, Token,
+};
+use itertools::Itertools;
+use std::ops::Deref;
+
+/// Returns `true` when the two ranges share at least one position
+fn overlaps(a: Range, b: Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+/// Only declarations that are legal in a package declaration or
+/// package body can be moved.
+fn is_movable(decl: &Declaration) -> bool {
+    match decl {
+        Declaration::Object(obj) => obj.class == ObjectClass::Constant && obj.expression.is_some(),
+        Declaration::Type(_)
+        | Declaration::Component(_)
+        | Declaration::Alias(_)
+        | Declaration::SubprogramDeclaration(_)
+        | Declaration::SubprogramBody(_) => true,
+        _ => false,
+    }
+}
+
+/// The packages that are named by a `use` clause in the context clause,
+/// and whether all of their declarations are made visible by `.all`
+fn used_packages(context_clause: &ContextClause) -> Vec<(EntityId, bool)> {
+    context_clause
+        .iter()
+        .filter_map(|item| match item {
+            ContextItem::Use(use_clause) => Some(&use_clause.name_list),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|name| match &name.item {
+            Name::SelectedAll(prefix) => Some((prefix.item.get_suffix_reference()?, true)),
+            Name::Selected(prefix, _) => Some((prefix.item.get_suffix_reference()?, false)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn context_clause_text(context_clause: &ContextClause, ctx: &dyn TokenAccess) -> Vec<String> {
+    context_clause
+        .iter()
+        .map(|item| {
+            let pos = item.get_pos(ctx);
+            text_at(pos.source(), pos.range())
+        })
+        .collect()
+}
+
+/// The text of the declarations that go to the package declaration
+/// and those that go to the package body, without any indentation.
+struct MovedText {
+    declarations: Vec<String>,
+    bodies: Vec<String>,
+}
+
+impl MovedText {
+    fn declarations_with_indent(&self, indent: &str) -> String {
+        self.declarations
+            .iter()
+            .map(|text| format!("{}\n", reindent(text, "", indent)))
+            .join("")
+    }
+
+    fn bodies_with_indent(&self, indent: &str) -> String {
+        self.bodies
+            .iter()
+            .map(|text| format!("{}\n", reindent(text, "", indent)))
+            .join("\n")
+    }
+}
+
+struct MoveToPackage<'a> {
+    root: &'a DesignRoot,
+    source: &'a Source,
+    tokens: &'a Vec<Token>,
+    library_name: &'a Symbol,
+    arch: &'a ArchitectureBody,
+    entity: EntRef<'a>,
+    selected: Vec<&'a WithTokenSpan<Declaration>>,
+}
+
+impl<'a> MoveToPackage<'a> {
+    fn range_of(&self, decl: &WithTokenSpan<Declaration>) -> Range {
+        decl.pos(self.tokens).range()
+    }
+
+    /// Returns `true` when the entity is declared inside one of the selected declarations.
+    fn is_moved(&self, ent: EntRef<'_>) -> bool {
+        let Some(decl_pos) = ent.decl_pos() else {
+            return false;
+        };
+        decl_pos.source() == self.source
+            && self
+                .selected
+                .iter()
+                .any(|decl| self.range_of(decl).contains(decl_pos.start()))
+    }
+
+    /// Returns `true` when the entity is declared inside the architecture or its entity.
+    /// Such an entity is not visible from a package.
+    fn is_local(&self, ent: EntRef<'_>) -> bool {
+        let arch_id = self.arch.ident.decl.get();
+        let mut current = Some(ent);
+        while let Some(ent) = current {
+            if Some(ent.id()) == arch_id || ent.id() == self.entity.id() {
+                return true;
+            }
+            current = ent.parent;
+        }
+        false
+    }
+
+    /// Checks that moving the selection does not leave any dangling references behind
+    fn is_self_contained(&self) -> bool {
+        if self
+            .selected
+            .iter()
+            .flat_map(|decl| referenced_entities(self.tokens, *decl))
+            .map(|id| self.root.get_ent(id))
+            .any(|ent| self.is_local(ent) && !self.is_moved(ent))
+        {
+            return false;
+        }
+
+        // A subprogram body and the corresponding subprogram declaration must be moved together
+        for decl in self.arch.decl.iter() {
+            let Declaration::SubprogramBody(_) = decl.item else {
+                continue;
+            };
+            for id in decl.item.declarations() {
+                if let Related::DeclaredBy(other) = self.root.get_ent(id).related {
+                    let body_moved = self.selected.iter().any(|sel| std::ptr::eq(*sel, decl));
+                    if body_moved != self.is_moved(other) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn moved_text(&self) -> MovedText {
+        let mut declarations = Vec::new();
+        let mut bodies = Vec::new();
+        for decl in self.selected.iter() {
+            let range = self.range_of(decl);
+            let old_indent = indentation_of_line(self.source, range.start.line);
+            let text = reindent(&text_at(self.source, range), &old_indent, "");
+            if let Declaration::SubprogramBody(body) = &decl.item {
+                let spec_range = body.specification.get_pos(self.tokens).range();
+                let spec = reindent(&text_at(self.source, spec_range), &old_indent, "");
+                declarations.push(format!("{spec};"));
+                bodies.push(text);
+            } else {
+                declarations.push(text);
+            }
+        }
+        MovedText {
+            declarations,
+            bodies,
+        }
+    }
+
+    /// Remove the selected declarations from the architecture
+    fn remove_selected(&self, edit: &mut WorkspaceEdit) {
+        for decl in self.selected.iter() {
+            let range = removal_range(self.source, self.range_of(decl));
+            edit.add(self.source.file_name(), TextEdit::delete(range));
+        }
+    }
+
+    /// The position where a use clause for the architecture can be inserted
+    fn use_clause_pos(&self) -> Position {
+        let pos = self.arch.span.start_token.pos(self.tokens).start();
+        Position::new(pos.line, 0)
+    }
+
+    fn use_clause(package_name: &str) -> String {
+        format!("use work.{package_name}.all;\n")
+    }
+
+    fn entity_unit(&self) -> Option<&'a LockedUnit> {
+        let lib = self.root.get_lib(self.library_name)?;
+        lib.primary_unit(self.entity.designator().as_identifier()?)
+    }
+
+    fn move_to_new_package(&self) -> Option<CodeAction> {
+        let package_name = format!("{}_pkg", self.arch.entity_name.item.item);
+        let lib = self.root.get_lib(self.library_name)?;
+        if lib
+            .primary_unit(&self.root.symbol_utf8(&package_name))
+            .is_some()
+        {
+            return None;
+        }
+
+        let mut context = Vec::new();
+        if let Some(unit) = self.entity_unit() {
+            if let Some(data) = unit.unit.get() {
+                if let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() {
+                    context.extend(context_clause_text(&entity.context_clause, &unit.tokens));
+                }
+            }
+        }
+        context.extend(context_clause_text(&self.arch.context_clause, self.tokens));
+        let context = context
+            .into_iter()
+            .unique()
+            .map(|item| format!("{item}\n"))
+            .join("");
+        let separator = if context.is_empty() { "" } else { "\n" };
+
+        let moved = self.moved_text();
+        let mut text = format!(
+            "{context}{separator}package {package_name} is\n{}end package {package_name};\n\n",
+            moved.declarations_with_indent(INDENT)
+        );
+        if !moved.bodies.is_empty() {
+            text.push_str(&format!(
+                "package body {package_name} is\n{}end package body {package_name};\n\n",
+                moved.bodies_with_indent(INDENT)
+            ));
+        }
+
+        let mut edit = WorkspaceEdit::default();
+        let use_clause_pos = self.use_clause_pos();
+        if let Some(item) = self.arch.context_clause.first() {
+            let pos = item.get_pos(self.tokens).start();
+            edit.add(
+                self.source.file_name(),
+                TextEdit::insert(Position::new(pos.line, 0), text),
+            );
+            edit.add(
+                self.source.file_name(),
+                TextEdit::insert(use_clause_pos, Self::use_clause(&package_name)),
+            );
+        } else {
+            text.push_str(&Self::use_clause(&package_name));
+            edit.add(
+                self.source.file_name(),
+                TextEdit::insert(use_clause_pos, text),
+            );
+        }
+        self.remove_selected(&mut edit);
+
+        Some(CodeAction {
+            title: format!("Move to new package '{package_name}'"),
+            kind: CodeActionKind::RefactorMove,
+            edit,
+        })
+    }
+
+    /// Returns `true` if the package already declares a named entity
+    /// with the same name as one of the moved entities.
+    fn conflicts_with(&self, package: EntRef<'_>) -> bool {
+        let AnyEntKind::Design(Design::Package(_, region)) = package.kind() else {
+            return true;
+        };
+        self.selected
+            .iter()
+            .flat_map(|decl| decl.item.declarations())
+            .any(|id| {
+                region
+                    .lookup_immediate(self.root.get_ent(id).designator())
+                    .is_some()
+            })
+    }
+
+    /// Returns `true` when all entities that the moved declarations refer to are also
+    /// visible in the package, either as its own declarations or through its context clause.
+    fn is_visible_in(&self, package: EntRef<'_>) -> bool {
+        let AnyEntKind::Design(Design::Package(visibility, _)) = package.kind() else {
+            return false;
+        };
+        self.selected
+            .iter()
+            .flat_map(|decl| referenced_entities(self.tokens, *decl))
+            .map(|id| self.root.get_ent(id))
+            .filter(|ent| !self.is_moved(ent))
+            .all(|ent| {
+                let is_declared_in_package = std::iter::successors(Some(ent), |ent| ent.parent)
+                    .any(|ent| ent.id() == package.id());
+                let is_work_library = matches!(ent.kind(), AnyEntKind::Library)
+                    && ent.designator().as_identifier() == Some(self.library_name);
+                is_declared_in_package || is_work_library || visibility.is_visible(ent)
+            })
+    }
+
+    fn move_to_existing_package(
+        &self,
+        unit: &LockedUnit,
+        package: &PackageDeclaration,
+        is_used: bool,
+    ) -> Option<CodeAction> {
+        if package.generic_clause.is_some() {
+            return None;
+        }
+        let package_ent = self.root.get_ent(package.ident.decl.get()?);
+        if self.conflicts_with(package_ent) || !self.is_visible_in(package_ent) {
+            return None;
+        }
+        let package_name = package.ident.tree.item.to_string();
+        let package_source = unit.source();
+        let moved = self.moved_text();
+        let mut edit = WorkspaceEdit::default();
+
+        let indent = match package.decl.first() {
+            Some(decl) => indentation_of_line(package_source, decl.pos(&unit.tokens).start().line),
+            None => INDENT.to_owned(),
+        };
+        let end_line = package.end_token.pos(&unit.tokens).start().line;
+        edit.add(
+            package_source.file_name(),
+            TextEdit::insert(
+                Position::new(end_line, 0),
+                moved.declarations_with_indent(&indent),
+            ),
+        );
+
+        if !moved.bodies.is_empty() {
+            let lib = self.root.get_lib(self.library_name)?;
+            let body = lib
+                .secondary_units(&package.ident.tree.item)
+                .find_map(|unit| {
+                    let data = unit.unit.get()?;
+                    if let AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(body)) =
+                        data.deref()
+                    {
+                        Some((
+                            unit.source().clone(),
+                            body.end_token.pos(&unit.tokens).start().line,
+                        ))
+                    } else {
+                        None
+                    }
+                });
+            match body {
+                Some((body_source, end_line)) => edit.add(
+                    body_source.file_name(),
+                    TextEdit::insert(
+                        Position::new(end_line, 0),
+                        moved.bodies_with_indent(&indent),
+                    ),
+                ),
+                None => {
+                    let end = package.span.end_token.pos(&unit.tokens).end();
+                    edit.add(
+                        package_source.file_name(),
+                        TextEdit::insert(
+                            start_of_next_line(end),
+                            format!(
+                                "\npackage body {package_name} is\n{}end package body {package_name};\n",
+                                moved.bodies_with_indent(&indent)
+                            ),
+                        ),
+                    )
+                }
+            }
+        }
+
+        if !is_used {
+            edit.add(
+                self.source.file_name(),
+                TextEdit::insert(self.use_clause_pos(), Self::use_clause(&package_name)),
+            );
+        }
+        self.remove_selected(&mut edit);
+
+        Some(CodeAction {
+            title: format!("Move to package '{package_name}'"),
+            kind: CodeActionKind::RefactorMove,
+            edit,
+        })
+    }
+
+    /// Candidates are packages in the same library that are either declared in the same
+    /// file or that are already used by the architecture or its entity.
+    fn move_to_existing_packages(&self) -> Vec<CodeAction> {
+        let Some(lib) = self.root.get_lib(self.library_name) else {
+            return vec![];
+        };
+        let mut used = used_packages(&self.arch.context_clause);
+        if let Some(unit) = self.entity_unit() {
+            if let Some(data) = unit.unit.get() {
+                if let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() {
+                    used.extend(used_packages(&entity.context_clause));
+                }
+            }
+        }
+
+        let mut actions = Vec::new();
+        for unit in lib.primary_units() {
+            let Some(data) = unit.unit.get() else {
+                continue;
+            };
+            let AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)) = data.deref() else {
+                continue;
+            };
+            let Some(id) = package.ident.decl.get() else {
+                continue;
+            };
+            // A selective `use` of some declarations does not make the moved ones visible
+            let is_named = used.iter().any(|(used_id, _)| *used_id == id);
+            let is_used = used.contains(&(id, true));
+            if !is_named && unit.source() != self.source {
+                continue;
+            }
+            actions.extend(self.move_to_existing_package(unit, package, is_used));
+        }
+        actions.sort_by(|a, b| a.title.cmp(&b.title));
+        actions
+    }
+}
+
+/// Moves constants, types and subprograms from the declarative part of an architecture
+/// into a new or an existing package and makes the package visible to the architecture.
+pub(super) fn move_to_package_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut actions = Vec::new();
+    for unit in root.units_by_source(source) {
+        let Some(data) = unit.unit.get() else {
+            continue;
+        };
+        let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = data.deref() else {
+            continue;
+        };
+        let selected = arch
+            .decl
+            .iter()
+            .filter(|decl| overlaps(decl.pos(&unit.tokens).range(), range))
+            .collect_vec();
+        if selected.is_empty() || !selected.iter().all(|decl| is_movable(&decl.item)) {
+            continue;
+        }
+        let Some(arch_id) = arch.ident.decl.get() else {
+            continue;
+        };
+        let AnyEntKind::Design(Design::Architecture(_, _, entity)) = root.get_ent(arch_id).kind()
+        else {
+            continue;
+        };
+
+        let move_to_package = MoveToPackage {
+            root,
+            source,
+            tokens: &unit.tokens,
+            library_name: unit.unit_id().library_name(),
+            arch,
+            entity: entity.deref(),
+            selected,
+        };
+        if !move_to_package.is_self_contained() {
+            continue;
+        }
+        actions.extend(move_to_package.move_to_existing_packages());
+        actions.extend(move_to_package.move_to_new_package());
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction};
+    use crate::syntax::test::Code;
+    use crate::Config;
+    use crate::Range;
+
+    fn move_actions(root: &crate::analysis::DesignRoot, code: &Code) -> Vec<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(code.start(), code.end()),
+        )
+        .into_iter()
+        .filter(|action| action.title.starts_with("Move to"))
+        .collect()
+    }
+
+    #[test]
+    fn moves_constant_to_new_package() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := 1;
+  signal s : natural := c;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = move_actions(&root, &code.s1("constant c"));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Move to new package 'ent_pkg'");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+package ent_pkg is
+    constant c : natural := 1;
+end package ent_pkg;
+
+use work.ent_pkg.all;
+architecture a of ent is
+  signal s : natural := c;
+begin
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn moves_subprogram_body_to_package_body() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  constant k : natural := 0;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  function f(x : natural) return natural is
+  begin
+    return x + k;
+  end function;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = move_actions(&root, &code.s1("function f"));
+        let action = actions
+            .iter()
+            .find(|action| action.title == "Move to package 'pkg'")
+            .unwrap();
+        assert_eq!(
+            apply_edits(code.source(), action),
+            "\
+package pkg is
+  constant k : natural := 0;
+  function f(x : natural) return natural;
+end package;
+
+package body pkg is
+  function f(x : natural) return natural is
+  begin
+    return x + k;
+  end function;
+end package body pkg;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn adds_use_clause_when_package_is_used_selectively() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  constant k : natural := 0;
+end package;
+
+use work.pkg.k;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := k;
+  signal s : natural := c;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let actions = move_actions(&root, &code.s1("constant c"));
+        let action = actions
+            .iter()
+            .find(|action| action.title == "Move to package 'pkg'")
+            .unwrap();
+        assert_eq!(
+            apply_edits(code.source(), action),
+            "\
+package pkg is
+  constant k : natural := 0;
+  constant c : natural := k;
+end package;
+
+use work.pkg.k;
+
+entity ent is
+end entity;
+
+use work.pkg.all;
+architecture a of ent is
+  signal s : natural := c;
+begin
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn does_not_move_declarations_depending_on_local_declarations() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  generic (width : natural);
+end entity;
+
+architecture a of ent is
+  constant c : natural := width;
+  constant d : natural := 2;
+  constant e : natural := d;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        assert!(move_actions(&root, &code.s1("constant c")).is_empty());
+        assert!(move_actions(&root, &code.s1("constant e")).is_empty());
+        assert_eq!(
+            move_actions(&root, &code.between("constant d", "d;")).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn does_not_move_to_packages_where_referenced_declarations_are_not_visible() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  constant k : natural := 0;
+end package;
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+package logic_pkg is
+  constant one : std_logic := '1';
+end package;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use work.pkg.all;
+use work.logic_pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : std_logic := '0';
+  signal s : std_logic := c;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let titles = move_actions(&root, &code.s1("constant c"))
+            .into_iter()
+            .map(|action| action.title)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            vec![
+                "Move to package 'logic_pkg'",
+                "Move to new package 'ent_pkg'"
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_move_signals() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(move_actions(&root, &code.s1("signal s")).is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{FoundDeclaration, NotFinished, Search, SearchState, Searcher};
+use crate::ast::ObjectClass;
+use crate::code_action::{text_at, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit};
+use crate::config::{Casing, Config};
+use crate::data::HasSource;
+use crate::named_entity::{HasEntityId, Reference};
+use crate::{AnyEntKind, EntRef, EntityId, Range, Source, SrcPos, TokenAccess};
+use std::ops::Deref;
+
+/// Libraries that are never changed, even when they are part of the project
+const STANDARD_LIBRARIES: [&str; 2] = ["std", "ieee"];
+
+/// Collects the positions of all declarations and references of named entities
+struct NameCollector<'a> {
+    root: &'a DesignRoot,
+    names: Vec<(SrcPos, EntityId)>,
+}
+
+impl Searcher for NameCollector<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            if let Some(pos) = self.root.get_ent(id).decl_pos() {
+                self.names.push((pos.clone(), id));
+            }
+            if let Some(token) = decl.end_ident_pos() {
+                self.names.push((ctx.get_pos(token).clone(), id));
+            }
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.names.push((pos.clone(), id));
+        }
+        NotFinished
+    }
+}
+
+/// Returns true for basic identifiers, whose case is not significant
+fn is_basic_identifier(text: &str) -> bool {
+    text.starts_with(|chr: char| chr.is_ascii_alphabetic())
+        && text
+            .chars()
+            .all(|chr| chr.is_ascii_alphanumeric() || chr == '_')
+}
+
+fn is_constant(ent: EntRef<'_>) -> bool {
+    match ent.kind() {
+        AnyEntKind::Object(object) => object.class == ObjectClass::Constant,
+        AnyEntKind::DeferredConstant(..) => true,
+        _ => false,
+    }
+}
+
+/// The spelling of the declaration of a named entity
+fn declared_spelling(ent: EntRef<'_>) -> Option<String> {
+    let pos = ent.decl_pos()?;
+    Some(text_at(pos.source(), pos.range()))
+}
+
+/// Returns the text with the given casing, or `None` when the text stays as it is
+fn recased(text: &str, casing: Casing, ent: Option<EntRef<'_>>) -> Option<String> {
+    if !is_basic_identifier(text) {
+        return None;
+    }
+    let new_text = match casing {
+        Casing::Keep => return None,
+        Casing::Lower => text.to_lowercase(),
+        Casing::Upper => text.to_uppercase(),
+        Casing::Declaration => declared_spelling(ent?)?,
+    };
+    (new_text != text && new_text.eq_ignore_ascii_case(text)).then_some(new_text)
+}
+
+fn into_workspace_edit(mut edits: Vec<(SrcPos, String)>) -> WorkspaceEdit {
+    edits.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    edits.dedup_by(|(lhs, _), (rhs, _)| lhs == rhs);
+    let mut edit = WorkspaceEdit::default();
+    for (pos, new_text) in edits {
+        edit.add(pos.file_name(), TextEdit::new(pos.range(), new_text));
+    }
+    edit
+}
+
+/// Rewrites keywords and identifiers of the project to the casing that is configured
+/// by the `keyword_case`, `constant_case` and `identifier_case` settings.
+/// Third-party libraries and the standard libraries are left as they are.
+pub fn normalize_case(root: &DesignRoot, config: &Config) -> WorkspaceEdit {
+    let rules = config.code_actions();
+    let keywords = config.standard().keywords();
+    let mut edits = Vec::new();
+    for library in root.libraries() {
+        let name = library.name().name_utf8();
+        if STANDARD_LIBRARIES.contains(&name.as_str())
+            || config
+                .get_library(&name)
+                .is_none_or(|library| library.is_third_party)
+        {
+            continue;
+        }
+        for unit in library.units() {
+            let Some(data) = unit.unit.get() else {
+                continue;
+            };
+            for token in unit.tokens.iter() {
+                if keywords.contains(&token.kind) {
+                    let text = text_at(token.pos.source(), token.pos.range());
+                    if let Some(new_text) = recased(&text, rules.keyword_case, None) {
+                        edits.push((token.pos.clone(), new_text));
+                    }
+                }
+            }
+
+            let mut collector = NameCollector {
+                root,
+                names: Vec::new(),
+            };
+            let _ = data.deref().search(&unit.tokens, &mut collector);
+            for (pos, id) in collector.names {
+                // Declarations in other units are changed when their own unit is visited
+                if pos.source() != unit.source() {
+                    continue;
+                }
+                let ent = root.get_ent(id);
+                let casing = if is_constant(ent) {
+                    rules.constant_case
+                } else {
+                    rules.identifier_case
+                };
+                let text = text_at(pos.source(), pos.range());
+                if let Some(new_text) = recased(&text, casing, Some(ent)) {
+                    edits.push((pos, new_text));
+                }
+            }
+        }
+    }
+    into_workspace_edit(edits)
+}
+
+/// Rewrites all references to the named entity at the cursor
+/// to the spelling of its declaration
+pub(super) fn normalize_case_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let Some(ent) = root.search_reference(source, range.start) else {
+        return vec![];
+    };
+    let Some(spelling) = declared_spelling(ent) else {
+        return vec![];
+    };
+    if !is_basic_identifier(&spelling) {
+        return vec![];
+    }
+    let edits: Vec<(SrcPos, String)> = root
+        .find_all_references(ent)
+        .into_iter()
+        .filter_map(|pos| {
+            let new_text = recased(
+                &text_at(pos.source(), pos.range()),
+                Casing::Declaration,
+                Some(ent),
+            )?;
+            Some((pos, new_text))
+        })
+        .collect();
+    if edits.is_empty() {
+        return vec![];
+    }
+    vec![CodeAction {
+        title: format!("Use the casing of the declaration '{spelling}' everywhere"),
+        kind: CodeActionKind::RefactorRewrite,
+        edit: into_workspace_edit(edits),
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use std::path::Path;
+
+    fn config(code_actions: &str) -> Config {
+        Config::from_str(
+            &format!("[libraries]\nlibname.files = []\n\n[code_actions]\n{code_actions}"),
+            Path::new(""),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn normalizes_case_of_project() {
+        let mut builder = LibraryBuilder::new();
+        let pkg = builder.code(
+            "libname",
+            "\
+package Pkg is
+  type Word is range 0 to 255;
+  CONSTANT Width : word := 8;
+END PACKAGE;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal Data : word := width;
+begin
+  DATA <= 0;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let edit = normalize_case(
+            &root,
+            &config("keyword_case = 'lower'\nconstant_case = 'upper'"),
+        );
+        let action = CodeAction {
+            title: String::new(),
+            kind: CodeActionKind::RefactorRewrite,
+            edit,
+        };
+        assert_eq!(
+            apply_edits(pkg.source(), &action),
+            "\
+package Pkg is
+  type Word is range 0 to 255;
+  constant WIDTH : Word := 8;
+end package;"
+        );
+        assert_eq!(
+            apply_edits(code.source(), &action),
+            "\
+use work.Pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal Data : Word := WIDTH;
+begin
+  Data <= 0;
+end architecture;"
+        );
+    }
+
+    #[test]
+    fn uses_casing_of_declaration_for_references() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal myData : bit;
+begin
+  MYDATA <= mydata;
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("MYDATA").start();
+        let actions = normalize_case_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Use the casing of the declaration 'myData' everywhere"
+        );
+        assert!(apply_edits(code.source(), &actions[0]).contains("  myData <= myData;"));
+
+        let cursor = code.s1("myData").start();
+        let actions = normalize_case_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+    }
+}
@@ -0,0 +1,462 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, LockedUnit};
+use crate::ast::search::{NotFinished, Search, SearchState, Searcher};
+use crate::ast::*;
+use crate::code_action::{indentation_of_line, text_at, CodeAction, CodeActionKind};
+use crate::code_action::{TextEdit, WorkspaceEdit};
+use crate::named_entity::{AnyEntKind, Design, Reference};
+use crate::{EntRef, EntityId, HasTokenSpan, Position, Range, Source, SrcPos, TokenAccess};
+use fnv::FnvHashSet;
+use std::ops::Deref;
+
+/// Collects all named entities that are referenced after a given position.
+/// This is used to ignore the references of the context clause itself.
+struct UsageCollector {
+    start: Position,
+    references: FnvHashSet<EntityId>,
+}
+
+impl Searcher for UsageCollector {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if pos.start() >= self.start {
+            if let Some(id) = reference.get() {
+                self.references.insert(id);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Adds the named entities that are referenced by a design unit, disregarding its context clause.
+fn collect_usages(unit: &LockedUnit, references: &mut FnvHashSet<EntityId>) {
+    let Some(data) = unit.unit.get() else {
+        return;
+    };
+    let mut collector = UsageCollector {
+        start: data.get_pos(&unit.tokens).start(),
+        references: std::mem::take(references),
+    };
+    let _ = data.deref().search(&unit.tokens, &mut collector);
+    *references = collector.references;
+}
+
+fn is_declared_in(ent: EntRef<'_>, region: EntRef<'_>) -> bool {
+    let mut parent = ent.parent;
+    while let Some(ent) = parent {
+        if ent.id() == region.id() {
+            return true;
+        }
+        parent = ent.parent;
+    }
+    false
+}
+
+/// Returns whether any of the referenced named entities is made visible by the use clause.
+/// Names that could not be resolved are conservatively considered used.
+fn is_used(root: &DesignRoot, name: &Name, references: &[EntRef<'_>]) -> bool {
+    match name {
+        Name::SelectedAll(prefix) => {
+            let Some(id) = prefix.item.get_suffix_reference() else {
+                return true;
+            };
+            let package = root.get_ent(id);
+            if !matches!(package.kind(), AnyEntKind::Design(Design::Package(..))) {
+                return true;
+            }
+            references.iter().any(|ent| is_declared_in(ent, package))
+        }
+        Name::Selected(..) => {
+            let Some(id) = name.get_suffix_reference() else {
+                return true;
+            };
+            let used = root.get_ent(id);
+            // A use clause makes all overloaded homographs visible
+            references.iter().any(|ent| {
+                ent.id() == used.id()
+                    || (ent.designator() == used.designator()
+                        && ent.parent.map(|parent| parent.id())
+                            == used.parent.map(|parent| parent.id()))
+            })
+        }
+        _ => true,
+    }
+}
+
+/// Removes use clauses that do not make any referenced named entity visible
+/// and library clauses of libraries that are not referenced,
+/// merges duplicate clauses and sorts the remaining use clauses of a context clause.
+/// The library clauses and context references are kept in their original order
+/// in front of the use clauses.
+/// Returns `None` when nothing would change.
+fn organize_context_clause(
+    root: &DesignRoot,
+    unit: &LockedUnit,
+    source: &Source,
+) -> Option<TextEdit> {
+    let data = unit.unit.get()?;
+    let items = data.context_clause()?;
+    let ctx: &dyn TokenAccess = &unit.tokens;
+    let range = Range::new(
+        items.first()?.get_pos(ctx).start(),
+        items.last()?.get_pos(ctx).end(),
+    );
+    let old_text = text_at(source, range);
+    // Rewriting the context clause would lose any comments in between
+    if old_text.contains("--") || old_text.contains("/*") {
+        return None;
+    }
+
+    let mut references: FnvHashSet<EntityId> = FnvHashSet::default();
+    collect_usages(unit, &mut references);
+    if let AnyDesignUnit::Primary(..) = data.deref() {
+        // The context clause of a primary unit also applies to its secondary units
+        if let Some(library) = root.get_lib(unit.unit_id().library_name()) {
+            for secondary in library.secondary_units(unit.unit_id().primary_name()) {
+                collect_usages(secondary, &mut references);
+            }
+        }
+    }
+    let references: Vec<EntRef<'_>> = references.into_iter().map(|id| root.get_ent(id)).collect();
+
+    let mut seen = FnvHashSet::default();
+    let mut libraries = Vec::new();
+    let mut contexts = Vec::new();
+    let mut uses = Vec::new();
+    for item in items {
+        match item {
+            ContextItem::Library(clause) => {
+                for name in clause.name_list.iter() {
+                    let text = text_at(source, name.item.pos(ctx).range());
+                    if seen.insert(format!("library {}", text.to_lowercase())) {
+                        libraries.push((name, text));
+                    }
+                }
+            }
+            ContextItem::Context(clause) => {
+                for name in clause.name_list.iter() {
+                    let text = text_at(source, name.pos(ctx).range());
+                    if seen.insert(format!("context {}", text.to_lowercase())) {
+                        contexts.push(text);
+                    }
+                }
+            }
+            ContextItem::Use(clause) => {
+                for name in clause.name_list.iter() {
+                    if is_used(root, &name.item, &references) {
+                        let text = text_at(source, name.pos(ctx).range());
+                        let key: String = text
+                            .chars()
+                            .filter(|chr| !chr.is_whitespace())
+                            .collect::<String>()
+                            .to_lowercase();
+                        if seen.insert(format!("use {key}")) {
+                            uses.push((key, text));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // A selected name is redundant when all names of its prefix are already visible
+    let all_prefixes: FnvHashSet<String> = uses
+        .iter()
+        .filter_map(|(key, _)| key.strip_suffix(".all").map(|prefix| prefix.to_owned()))
+        .collect();
+    uses.retain(|(key, _)| {
+        key.ends_with(".all")
+            || key
+                .rsplit_once('.')
+                .map(|(prefix, _)| !all_prefixes.contains(prefix))
+                .unwrap_or(true)
+    });
+    uses.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    // A library clause is needed by the remaining use clauses and context references
+    // and by selected names in the design unit that start with the library name
+    let prefixes: FnvHashSet<String> = uses
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .chain(contexts.iter().map(|text| text.as_str()))
+        .filter_map(|name| name.split('.').next())
+        .map(|library| library.trim().to_lowercase())
+        .collect();
+    libraries.retain(|(name, text)| {
+        prefixes.contains(&text.to_lowercase())
+            || name
+                .reference
+                .get()
+                .is_none_or(|id| references.iter().any(|ent| ent.id() == id))
+    });
+
+    let indent = indentation_of_line(source, range.start.line);
+    let new_text = libraries
+        .into_iter()
+        .map(|(_, text)| format!("library {text};"))
+        .chain(contexts.into_iter().map(|text| format!("context {text};")))
+        .chain(uses.into_iter().map(|(_, text)| format!("use {text};")))
+        .collect::<Vec<_>>()
+        .join(&format!("\n{indent}"));
+    if new_text == old_text {
+        None
+    } else {
+        Some(TextEdit::new(range, new_text))
+    }
+}
+
+/// Organizes the context clauses of all design units in the source file.
+pub(super) fn organize_imports_actions(root: &DesignRoot, source: &Source) -> Vec<CodeAction> {
+    let mut edit = WorkspaceEdit::default();
+    for unit in root.units_by_source(source) {
+        if let Some(text_edit) = organize_context_clause(root, unit, source) {
+            edit.add(source.file_name(), text_edit);
+        }
+    }
+    if edit.changes.is_empty() {
+        return vec![];
+    }
+    vec![CodeAction {
+        title: "Organize context clauses".to_owned(),
+        kind: CodeActionKind::SourceOrganizeImports,
+        edit,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{list_code_actions, CodeAction, CodeActionKind};
+    use crate::syntax::test::Code;
+    use crate::{Config, Range};
+
+    fn organize_action(root: &crate::analysis::DesignRoot, code: &Code) -> Option<CodeAction> {
+        list_code_actions(
+            root,
+            &Config::default(),
+            code.source(),
+            Range::new(code.start(), code.start()),
+        )
+        .into_iter()
+        .find(|action| action.kind == CodeActionKind::SourceOrganizeImports)
+    }
+
+    #[test]
+    fn removes_unused_use_clauses_and_sorts() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+package pkg1 is
+  constant c1 : natural := 0;
+end package;
+
+package pkg2 is
+  constant c2 : natural := 0;
+end package;
+
+package pkg3 is
+  constant c3 : natural := 0;
+end package;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+library libname;
+use work.pkg3.all, work.pkg1.all;
+use work.pkg2.all;
+use work.pkg3.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := c1 + c3;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let action = organize_action(&root, &code).unwrap();
+        assert_eq!(action.title, "Organize context clauses");
+        assert_eq!(
+            apply_edits(code.source(), &action),
+            "\
+use work.pkg1.all;
+use work.pkg3.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := c1 + c3;
+begin
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn removes_selected_names_covered_by_all() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+package pkg is
+  constant c1 : natural := 0;
+  constant c2 : natural := 0;
+end package;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+use work.pkg.c1;
+use work.pkg.all;
+use work.pkg.c2;
+
+package other is
+  constant c : natural := c1 + c2;
+end package;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let action = organize_action(&root, &code).unwrap();
+        assert_eq!(
+            apply_edits(code.source(), &action),
+            "\
+use work.pkg.all;
+
+package other is
+  constant c : natural := c1 + c2;
+end package;
+"
+        );
+    }
+
+    #[test]
+    fn removes_unused_library_clauses() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "lib1",
+            "\
+package pkg is
+  constant c : natural := 0;
+end package;
+",
+        );
+        builder.code(
+            "lib2",
+            "\
+package pkg is
+  constant c : natural := 0;
+end package;
+",
+        );
+        builder.code(
+            "lib3",
+            "\
+package pkg is
+end package;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+library lib3, lib2;
+library lib1;
+use lib1.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c2 : natural := lib2.pkg.c + c;
+begin
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let action = organize_action(&root, &code).unwrap();
+        assert_eq!(
+            apply_edits(code.source(), &action),
+            "\
+library lib2;
+library lib1;
+use lib1.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c2 : natural := lib2.pkg.c + c;
+begin
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn no_action_when_organized() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg1 is
+  constant c1 : natural := 0;
+end package;
+
+use work.pkg1.all;
+
+package pkg2 is
+  constant c2 : natural := c1;
+end package;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(organize_action(&root, &code), None);
+    }
+
+    #[test]
+    fn keeps_context_clause_with_comments() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+package pkg is
+end package;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+-- Not used
+use work.pkg.all; -- Not used either
+use work.pkg.all;
+
+entity ent is
+end entity;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert_eq!(organize_action(&root, &code), None);
+    }
+}
@@ -0,0 +1,216 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{NotFinished, SearchState, Searcher};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::{
+    removal_range, start_of_next_line, text_at, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit,
+};
+use crate::data::HasSource;
+use crate::lint::dead_code::find_unused_declarations;
+use crate::{HasTokenSpan, Position, Range, Source, SrcPos, TokenAccess};
+
+/// The code that is removed for an unused named entity
+enum Removal {
+    /// The complete declaration
+    Declaration(Range),
+    /// The identifier in a declaration of several objects, including its separating comma
+    Identifier(Range),
+}
+
+/// Finds the code to remove for the declaration of a named entity
+struct DeclarationRangeSearcher<'a> {
+    decl_pos: &'a SrcPos,
+    result: Option<Removal>,
+}
+
+impl Searcher for DeclarationRangeSearcher<'_> {
+    fn search_declaration(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        decl: &WithTokenSpan<Declaration>,
+    ) -> SearchState {
+        let range = decl.pos(ctx).range();
+        if !range.contains(self.decl_pos.start()) {
+            return NotFinished;
+        }
+        // Nested declarations are visited after the declaration that contains them
+        self.result = Some(Removal::Declaration(range));
+        if let Declaration::Object(object) = &decl.item {
+            let positions: Vec<&SrcPos> = object
+                .idents
+                .iter()
+                .map(|ident| ident.tree.pos(ctx))
+                .collect();
+            if positions.len() > 1 {
+                if let Some(i) = positions.iter().position(|pos| *pos == self.decl_pos) {
+                    self.result = Some(Removal::Identifier(if i + 1 < positions.len() {
+                        Range::new(positions[i].start(), positions[i + 1].start())
+                    } else {
+                        Range::new(positions[i - 1].end(), positions[i].end())
+                    }));
+                }
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Extends the range of a declaration with a comment that follows it on the same line
+fn with_trailing_comment(source: &Source, range: Range) -> Range {
+    let trailing = text_at(source, Range::new(range.end, start_of_next_line(range.end)));
+    let code = trailing.trim_end_matches(['\n', '\r']);
+    if code.trim_start().starts_with("--") {
+        let end = range.end.character + code.encode_utf16().count() as u32;
+        Range::new(range.start, Position::new(range.end.line, end))
+    } else {
+        range
+    }
+}
+
+/// Removes a declaration that is reported as unused, together with a comment
+/// on the same line.
+/// Assignments to a signal are references, so an unused signal is never assigned
+/// and no statements have to be removed along with its declaration.
+pub(super) fn remove_unused_actions(
+    root: &DesignRoot,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let Some(ent) = root.search_reference(source, range.start) else {
+        return vec![];
+    };
+    let Some(decl_pos) = ent.decl_pos() else {
+        return vec![];
+    };
+    if decl_pos.source() != source || !decl_pos.range().contains(range.start) {
+        return vec![];
+    }
+    let Some(unit_id) = root
+        .units_by_source(source)
+        .find(|unit| {
+            unit.unit
+                .get()
+                .is_some_and(|data| data.get_pos(&unit.tokens).range().contains(range.start))
+        })
+        .map(|unit| unit.unit_id().clone())
+    else {
+        return vec![];
+    };
+    let Some(library) = root.get_lib(unit_id.library_name()) else {
+        return vec![];
+    };
+    if !find_unused_declarations(root, library, unit_id.primary_name()).contains(&ent) {
+        return vec![];
+    }
+
+    let mut searcher = DeclarationRangeSearcher {
+        decl_pos,
+        result: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    let decl_range = match searcher.result {
+        Some(Removal::Declaration(range)) => {
+            removal_range(source, with_trailing_comment(source, range))
+        }
+        Some(Removal::Identifier(range)) => range,
+        None => return vec![],
+    };
+
+    let mut edit = WorkspaceEdit::default();
+    edit.add(source.file_name(), TextEdit::delete(decl_range));
+    vec![CodeAction {
+        title: format!("Remove unused declaration of '{}'", ent.designator()),
+        kind: CodeActionKind::QuickFix,
+        edit,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_unused_actions;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::code_action::tests::apply_edits;
+    use crate::Range;
+
+    #[test]
+    fn removes_unused_declaration_with_trailing_comment() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal used : bit;
+  signal unused : bit; -- Not needed
+  signal a, b, c : bit;
+begin
+  used <= a or c;
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let cursor = code.s1("unused").start();
+        let actions = remove_unused_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Remove unused declaration of 'unused'");
+        assert_eq!(
+            apply_edits(code.source(), &actions[0]),
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal used : bit;
+  signal a, b, c : bit;
+begin
+  used <= a or c;
+end architecture;"
+        );
+
+        let cursor = code.s1("b,").start();
+        let actions = remove_unused_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        assert!(apply_edits(code.source(), &actions[0]).contains("  signal a, c : bit;"));
+
+        // Used declarations are kept
+        let cursor = code.s1("used :").start();
+        assert!(remove_unused_actions(&root, code.source(), Range::new(cursor, cursor)).is_empty());
+    }
+
+    #[test]
+    fn removes_innermost_unused_declaration() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  function f return natural is
+    variable v : natural;
+    constant c : natural := 0;
+  begin
+    return c;
+  end function;
+begin
+  assert f = 0;
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let cursor = code.s1("v :").start();
+        let actions = remove_unused_actions(&root, code.source(), Range::new(cursor, cursor));
+        assert_eq!(actions.len(), 1);
+        assert!(!apply_edits(code.source(), &actions[0]).contains("variable"));
+        assert!(apply_edits(code.source(), &actions[0]).contains("constant c"));
+    }
+}
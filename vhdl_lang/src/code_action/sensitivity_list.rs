@@ -0,0 +1,316 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::{
+    base_reference, text_at, CodeAction, CodeActionKind, TextEdit, WorkspaceEdit,
+};
+use crate::lint::sensitivity::{is_clocked, read_signals};
+use crate::{EntityId, HasTokenSpan, Position, Range, Source, TokenAccess, VHDLStandard};
+use fnv::FnvHashSet;
+
+fn sensitivity_list_action(
+    title: &str,
+    kind: CodeActionKind,
+    source: &Source,
+    edit: TextEdit,
+) -> CodeAction {
+    let mut workspace_edit = WorkspaceEdit::default();
+    workspace_edit.add(source.file_name(), edit);
+    CodeAction {
+        title: title.to_owned(),
+        kind,
+        edit: workspace_edit,
+    }
+}
+
+/// Adds the signals that are read by a combinational process but missing from
+/// its explicit sensitivity list
+fn add_missing_signals(
+    root: &DesignRoot,
+    ctx: &dyn TokenAccess,
+    source: &Source,
+    process: &ProcessStatement,
+    names: &[WithTokenSpan<Name>],
+) -> Option<CodeAction> {
+    let last = names.last()?;
+    if is_clocked(ctx, process) {
+        return None;
+    }
+    let listed: FnvHashSet<EntityId> = names
+        .iter()
+        .filter_map(|name| base_reference(&name.item))
+        .collect();
+    let missing: Vec<String> = read_signals(root, ctx, process)
+        .into_iter()
+        .filter(|(id, _)| !listed.contains(id))
+        .map(|(_, pos)| text_at(source, pos.range()))
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+    Some(sensitivity_list_action(
+        "Add missing signals to sensitivity list",
+        CodeActionKind::QuickFix,
+        source,
+        TextEdit::insert(last.pos(ctx).end(), format!(", {}", missing.join(", "))),
+    ))
+}
+
+/// Returns the actions that rewrite the sensitivity list of a process
+fn sensitivity_list_rewrites(
+    root: &DesignRoot,
+    ctx: &dyn TokenAccess,
+    standard: VHDLStandard,
+    source: &Source,
+    process: &ProcessStatement,
+) -> Vec<CodeAction> {
+    let Some(sensitivity_list) = process.sensitivity_list.as_ref() else {
+        return vec![];
+    };
+    let range = sensitivity_list.pos(ctx).range();
+    let mut actions = Vec::new();
+    match &sensitivity_list.item {
+        SensitivityList::Names(names) => {
+            actions.extend(add_missing_signals(root, ctx, source, process, names));
+            if standard >= VHDLStandard::VHDL2008 {
+                actions.push(sensitivity_list_action(
+                    "Replace sensitivity list with 'all'",
+                    CodeActionKind::RefactorRewrite,
+                    source,
+                    TextEdit::new(range, "(all)".to_owned()),
+                ));
+            }
+        }
+        SensitivityList::All => {
+            let signals = read_signals(root, ctx, process);
+            if !signals.is_empty() {
+                let names: Vec<String> = signals
+                    .iter()
+                    .map(|(_, pos)| text_at(source, pos.range()))
+                    .collect();
+                actions.push(sensitivity_list_action(
+                    "Expand sensitivity list 'all' to explicit signals",
+                    CodeActionKind::RefactorRewrite,
+                    source,
+                    TextEdit::new(range, format!("({})", names.join(", "))),
+                ));
+            }
+        }
+    }
+    actions
+}
+
+/// Finds the process statement at the cursor and rewrites its sensitivity list
+struct ProcessSearcher<'a> {
+    root: &'a DesignRoot,
+    standard: VHDLStandard,
+    source: &'a Source,
+    cursor: Position,
+    result: Vec<CodeAction>,
+}
+
+impl Searcher for ProcessSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::ConcurrentStatement(stmt) = decl.ast else {
+            return NotFinished;
+        };
+        let ConcurrentStatement::Process(process) = &stmt.statement.item else {
+            return NotFinished;
+        };
+        if !stmt.get_pos(ctx).range().contains(self.cursor) {
+            return NotFinished;
+        }
+        self.result =
+            sensitivity_list_rewrites(self.root, ctx, self.standard, self.source, process);
+        Finished(Found)
+    }
+}
+
+/// Rewrites the sensitivity list of the process at the cursor.
+/// Signals that are read by a combinational process are added to its explicit list.
+/// An explicit list is replaced by `all` when the configured standard is VHDL-2008 or later.
+/// A list of `all` is expanded to the signals that are read by the process
+/// so that the code can be used with VHDL-93 tools.
+pub(super) fn sensitivity_list_actions(
+    root: &DesignRoot,
+    standard: VHDLStandard,
+    source: &Source,
+    range: Range,
+) -> Vec<CodeAction> {
+    let mut searcher = ProcessSearcher {
+        root,
+        standard,
+        source,
+        cursor: range.start,
+        result: Vec::new(),
+    };
+    let _ = root.search_source(source, &mut searcher);
+    searcher.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sensitivity_list_actions;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::code_action::tests::apply_edits;
+    use crate::{Range, VHDLStandard};
+
+    const CODE: &str = "\
+entity ent is
+  port (
+    clk : in bit;
+    a, b : in bit;
+    q : out bit
+  );
+end entity;
+
+architecture a of ent is
+  signal tmp : bit;
+begin
+  comb: process (a, b)
+  begin
+    tmp <= a and b;
+  end process;
+
+  seq: process (all)
+    variable v : bit;
+  begin
+    if clk'event and clk = '1' then
+      v := tmp;
+      q <= v xor a;
+    end if;
+  end process;
+end architecture;
+";
+
+    #[test]
+    fn replaces_explicit_sensitivity_list_with_all() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code("libname", CODE);
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("comb").start();
+        let actions = sensitivity_list_actions(
+            &root,
+            VHDLStandard::VHDL2008,
+            code.source(),
+            Range::new(cursor, cursor),
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Replace sensitivity list with 'all'");
+        assert!(apply_edits(code.source(), &actions[0]).contains("comb: process (all)"));
+
+        assert!(sensitivity_list_actions(
+            &root,
+            VHDLStandard::VHDL1993,
+            code.source(),
+            Range::new(cursor, cursor),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn expands_all_to_read_signals() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code("libname", CODE);
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("v := tmp").start();
+        let actions = sensitivity_list_actions(
+            &root,
+            VHDLStandard::VHDL2008,
+            code.source(),
+            Range::new(cursor, cursor),
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Expand sensitivity list 'all' to explicit signals"
+        );
+        assert!(apply_edits(code.source(), &actions[0]).contains("seq: process (clk, tmp, a)"));
+    }
+
+    #[test]
+    fn adds_missing_signals_to_combinational_process() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (
+    clk, rst : in bit;
+    a, b, c : in bit;
+    q : out bit
+  );
+end entity;
+
+architecture a of ent is
+  signal tmp : bit;
+begin
+  comb: process (a)
+  begin
+    tmp <= a and b and c;
+  end process;
+
+  seq: process (clk, rst)
+  begin
+    if rst = '1' then
+      q <= '0';
+    elsif rising_edge(clk) then
+      q <= tmp;
+    end if;
+  end process;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("comb").start();
+        let actions = sensitivity_list_actions(
+            &root,
+            VHDLStandard::VHDL1993,
+            code.source(),
+            Range::new(cursor, cursor),
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Add missing signals to sensitivity list");
+        assert!(apply_edits(code.source(), &actions[0]).contains("comb: process (a, b, c)"));
+
+        // The signals that are read on the clock edge do not belong in the list
+        let cursor = code.s1("seq").start();
+        assert!(sensitivity_list_actions(
+            &root,
+            VHDLStandard::VHDL1993,
+            code.source(),
+            Range::new(cursor, cursor),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn no_action_outside_of_processes() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code("libname", CODE);
+        let (root, _) = builder.get_analyzed_root();
+        let cursor = code.s1("signal tmp").start();
+        assert!(sensitivity_list_actions(
+            &root,
+            VHDLStandard::VHDL2008,
+            code.source(),
+            Range::new(cursor, cursor),
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::code_action::missing_associations::{associations_edit, insertion_point, missing_ports};
+use crate::code_action::{referenced_entities, text_at, WorkspaceEdit};
+use crate::data::HasSource;
+use crate::{AnyEntKind, Design, HasTokenSpan, SrcPos, TokenAccess};
+use std::ops::Deref;
+
+/// An association of an instantiation that no longer matches
+/// the interface of the instantiated unit
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StaleAssociation {
+    pub pos: SrcPos,
+    pub message: String,
+}
+
+/// The result of synchronizing all instantiations of a project with the
+/// interfaces of the instantiated units
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct InstanceSynchronization {
+    /// Adds the ports that are not associated
+    pub edit: WorkspaceEdit,
+    /// Associations that cannot be fixed automatically,
+    /// e.g., because the associated port or generic was removed
+    pub stale: Vec<StaleAssociation>,
+}
+
+struct InstanceSynchronizer<'a> {
+    root: &'a DesignRoot,
+    result: InstanceSynchronization,
+}
+
+impl InstanceSynchronizer<'_> {
+    /// Flags named associations whose formal does not exist
+    /// and positional associations that exceed the number of formals
+    fn check_map_aspect(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        unit_name: &str,
+        map_aspect: &Option<MapAspect>,
+        num_formals: usize,
+    ) {
+        let Some(map_aspect) = map_aspect else {
+            return;
+        };
+        for (i, assoc) in map_aspect.list.items.iter().enumerate() {
+            match &assoc.formal {
+                Some(formal) if referenced_entities(ctx, formal).is_empty() => {
+                    let pos = formal.pos(ctx);
+                    self.result.stale.push(StaleAssociation {
+                        message: format!(
+                            "'{}' is not a formal of '{unit_name}'",
+                            text_at(pos.source(), pos.range())
+                        ),
+                        pos,
+                    });
+                }
+                Some(_) => {}
+                None if i >= num_formals => self.result.stale.push(StaleAssociation {
+                    pos: assoc.actual.pos(ctx),
+                    message: format!("Too many positional associations for '{unit_name}'"),
+                }),
+                None => {}
+            }
+        }
+    }
+
+    fn synchronize(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        stmt: &LabeledConcurrentStatement,
+        inst: &InstantiationStatement,
+    ) {
+        let Some(id) = inst.entity_reference() else {
+            return;
+        };
+        let unit = self.root.get_ent(id);
+        let region = match unit.kind() {
+            AnyEntKind::Component(region) => region,
+            AnyEntKind::Design(Design::Entity(_, region)) => region,
+            _ => return,
+        };
+        let unit_name = unit.designator().to_string();
+        let (ports, generics) = region.ports_and_generics();
+        self.check_map_aspect(ctx, &unit_name, &inst.generic_map, generics.len());
+        self.check_map_aspect(ctx, &unit_name, &inst.port_map, ports.len());
+
+        let Some(missing) = missing_ports(self.root, ctx, inst) else {
+            return;
+        };
+        // Ports with a default value may be left unassociated on purpose
+        let missing: Vec<_> = missing
+            .into_iter()
+            .filter(|port| !port.has_default())
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        let associations = missing
+            .iter()
+            .map(|port| format!("{} => open", port.designator()));
+        self.result.edit.add(
+            stmt.get_pos(ctx).source().file_name(),
+            associations_edit(&insertion_point(ctx, stmt, inst), associations),
+        );
+    }
+}
+
+impl Searcher for InstanceSynchronizer<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::ConcurrentStatement(stmt) = decl.ast {
+            if let ConcurrentStatement::Instance(inst) = &stmt.statement.item {
+                self.synchronize(ctx, stmt, inst);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Finds all instantiations in the design whose associations no longer match
+/// the interface of the instantiated entity or component, e.g., after ports were
+/// added or removed manually.
+/// Ports without a default value that are not associated are associated with `open`.
+/// Associations with formals that do not exist anymore are reported as stale
+/// since removing them could silently change the behavior of the design.
+pub fn synchronize_instantiations(root: &DesignRoot) -> InstanceSynchronization {
+    let mut synchronizer = InstanceSynchronizer {
+        root,
+        result: InstanceSynchronization::default(),
+    };
+    for library in root.libraries() {
+        for unit in library.units() {
+            let Some(data) = unit.unit.get() else {
+                continue;
+            };
+            if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = data.deref() {
+                let _ = arch.search(&unit.tokens, &mut synchronizer);
+            }
+        }
+    }
+    synchronizer
+        .result
+        .stale
+        .sort_by(|lhs, rhs| lhs.pos.cmp(&rhs.pos));
+    synchronizer.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{CodeAction, CodeActionKind};
+
+    fn apply(source: &crate::Source, result: &InstanceSynchronization) -> String {
+        apply_edits(
+            source,
+            &CodeAction {
+                title: String::new(),
+                kind: CodeActionKind::QuickFix,
+                edit: result.edit.clone(),
+            },
+        )
+    }
+
+    #[test]
+    fn adds_missing_ports_to_all_instances() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (
+    clk : in bit;
+    enable : in bit := '1';
+    data : in bit;
+    result : out bit
+  );
+end entity;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, q : bit;
+begin
+  inst0: entity work.child
+    port map (
+      clk => clk,
+      result => q
+    );
+
+  gen: if true generate
+    inst1: entity work.child port map (clk, '0', '1', q);
+    inst2: entity work.child;
+  end generate;
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let result = synchronize_instantiations(&root);
+        assert_eq!(result.stale, vec![]);
+        assert_eq!(
+            apply(code.source(), &result),
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, q : bit;
+begin
+  inst0: entity work.child
+    port map (
+      clk => clk,
+      result => q,
+      data => open
+    );
+
+  gen: if true generate
+    inst1: entity work.child port map (clk, '0', '1', q);
+    inst2: entity work.child
+        port map (
+            clk => open,
+            data => open,
+            result => open
+        );
+  end generate;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn reports_removed_formals() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "\
+entity child is
+  port (
+    clk : in bit
+  );
+end entity;
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : bit;
+begin
+  inst0: entity work.child
+    port map (
+      clk => clk,
+      removed => clk
+    );
+  inst1: entity work.child port map (clk, clk);
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let result = synchronize_instantiations(&root);
+        assert!(result.edit.changes.is_empty());
+        assert_eq!(
+            result.stale,
+            vec![
+                StaleAssociation {
+                    pos: code.s1("removed").pos(),
+                    message: "'removed' is not a formal of 'child'".to_owned(),
+                },
+                StaleAssociation {
+                    pos: code.s("clk", 6).pos(),
+                    message: "Too many positional associations for 'child'".to_owned(),
+                },
+            ]
+        );
+    }
+}
@@ -5,27 +5,50 @@
 // Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
 use crate::analysis::DesignRoot;
+use crate::ast::ObjectClass;
 use crate::ast::{AttributeDesignator, Designator};
 use crate::completion::attributes::completions_for_attribute_name;
+use crate::completion::end_label::completions_for_end_label;
+use crate::completion::entity_instantiation::completions_for_architectures;
+use crate::completion::external_name::{completions_for_external_path, external_path_prefix};
 use crate::completion::generic::generic_completions;
-use crate::completion::libraries::list_all_libraries;
+use crate::completion::libraries::{list_all_libraries, list_contexts_of_library};
+use crate::completion::literals::{case_expression_of_choice, completions_for_enum_literals};
 use crate::completion::map_aspect::completions_for_map_aspect;
-use crate::completion::selected::completions_for_selected_name;
+use crate::completion::ranking::rank_completions;
+use crate::completion::selected::{completions_for_indexed_name, completions_for_selected_name};
 use crate::completion::tokenizer::tokenize_input;
+use crate::completion::unimported::unimported_completions;
 use crate::syntax::Kind;
-use crate::{EntRef, Position, Source};
+use crate::syntax::Value;
+use crate::{kind_str, AnyEntKind, EntRef, EntityId, Overloaded, Position, Source, TextEdit};
 
 mod attributes;
+mod end_label;
 mod entity_instantiation;
+mod external_name;
 mod generic;
 mod libraries;
+mod literals;
 mod map_aspect;
+mod ranking;
 mod region;
 mod selected;
+mod signature_help;
+mod snippets;
+mod subprogram;
 mod tokenizer;
+mod unimported;
+
+pub use entity_instantiation::instantiation_snippet;
+pub(crate) use literals::enum_literals;
+pub use map_aspect::formal_detail;
+pub use signature_help::{signature_help, SignatureHelp, SignatureInformation};
+pub use snippets::Snippet;
+pub use subprogram::subprogram_call_snippet;
 
 #[derive(Debug, PartialEq, Clone)]
-pub enum CompletionItem<'a> {
+pub enum CompletionOption<'a> {
     /// Simply complete the entities
     /// e.g., `use std.` should simply list all elements in the std library
     Simple(EntRef<'a>),
@@ -39,7 +62,7 @@ pub enum CompletionItem<'a> {
     Keyword(Kind),
     /// Complete the 'work' library.
     /// This is handled in a special manner because the
-    /// actual work library (using [CompletionItem::Simple] would complete the actual name
+    /// actual work library (using [CompletionOption::Simple] would complete the actual name
     /// of the library, not the string 'work'.
     Work,
     /// Entity or component instantiation, i.e.,
@@ -61,6 +84,186 @@ pub enum CompletionItem<'a> {
     Instantiation(EntRef<'a>, Vec<EntRef<'a>>),
     /// Complete an attribute designator (i.e. `'range`, `'stable`, ...)
     Attribute(AttributeDesignator),
+    /// A subprogram. Every overload of a subprogram is a separate item
+    /// that can be completed as a call that associates the parameters by name.
+    Subprogram(EntRef<'a>),
+    /// A template of a statement, i.e., a clocked process
+    Snippet(&'static Snippet),
+    /// A declaration of a package that is not visible at the cursor.
+    /// The text edit inserts the use clause that makes the declaration visible.
+    Unimported(EntRef<'a>, TextEdit),
+}
+
+impl<'a> CompletionOption<'a> {
+    /// The named entity that is completed, if any.
+    /// Keywords, attributes, snippets, the 'work' library and overloaded names
+    /// do not refer to a single entity.
+    pub fn entity(&self) -> Option<EntRef<'a>> {
+        match self {
+            CompletionOption::Simple(ent)
+            | CompletionOption::Formal(ent)
+            | CompletionOption::Instantiation(ent, _)
+            | CompletionOption::Subprogram(ent)
+            | CompletionOption::Unimported(ent, _) => Some(*ent),
+            CompletionOption::Overloaded(..)
+            | CompletionOption::Keyword(_)
+            | CompletionOption::Work
+            | CompletionOption::Attribute(_)
+            | CompletionOption::Snippet(_) => None,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            CompletionOption::Simple(ent)
+            | CompletionOption::Formal(ent)
+            | CompletionOption::Subprogram(ent)
+            | CompletionOption::Unimported(ent, _) => ent.designator().to_string(),
+            CompletionOption::Instantiation(ent, _) => {
+                format!("{} instantiation", ent.designator())
+            }
+            CompletionOption::Overloaded(designator, _) => designator.to_string(),
+            CompletionOption::Keyword(kind) => kind_str(*kind).to_owned(),
+            CompletionOption::Work => "work".to_owned(),
+            CompletionOption::Attribute(attribute) => attribute.to_string(),
+            CompletionOption::Snippet(snippet) => snippet.label.to_owned(),
+        }
+    }
+
+    fn kind(&self) -> CompletionKind {
+        match self {
+            CompletionOption::Simple(ent)
+            | CompletionOption::Formal(ent)
+            | CompletionOption::Subprogram(ent)
+            | CompletionOption::Unimported(ent, _) => CompletionKind::of_entity(ent),
+            CompletionOption::Instantiation(ent, _) => match ent.kind() {
+                AnyEntKind::Component(_) => CompletionKind::Component,
+                _ => CompletionKind::DesignUnit,
+            },
+            CompletionOption::Overloaded(Designator::OperatorSymbol(_), _) => {
+                CompletionKind::Operator
+            }
+            CompletionOption::Overloaded(Designator::Identifier(_), _) => {
+                CompletionKind::Subprogram
+            }
+            CompletionOption::Overloaded(..) => CompletionKind::EnumLiteral,
+            CompletionOption::Keyword(_) => CompletionKind::Keyword,
+            CompletionOption::Work => CompletionKind::Library,
+            CompletionOption::Attribute(_) => CompletionKind::Attribute,
+            CompletionOption::Snippet(_) => CompletionKind::Snippet,
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            CompletionOption::Simple(ent)
+            | CompletionOption::Subprogram(ent)
+            | CompletionOption::Instantiation(ent, _) => ent.describe(),
+            CompletionOption::Formal(ent) => formal_detail(ent),
+            CompletionOption::Unimported(ent, edit) => {
+                // The use clause is the last line of the inserted text
+                let use_clause = edit.new_text.trim_end().lines().last().unwrap_or_default();
+                format!("{} (requires {use_clause})", ent.describe())
+            }
+            CompletionOption::Overloaded(_, count) => format!("+{count} overloaded"),
+            CompletionOption::Keyword(kind) => kind_str(*kind).to_owned(),
+            CompletionOption::Work => "work library".to_owned(),
+            CompletionOption::Attribute(attribute) => attribute.to_string(),
+            CompletionOption::Snippet(snippet) => snippet.detail.to_owned(),
+        }
+    }
+}
+
+/// The kind of a completion item, e.g., to choose the icon that is shown next to it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CompletionKind {
+    Keyword,
+    Library,
+    /// An entity, architecture, package, configuration or context
+    DesignUnit,
+    Component,
+    /// The label of a statement
+    Label,
+    Type,
+    Signal,
+    Constant,
+    Variable,
+    File,
+    Subprogram,
+    Operator,
+    EnumLiteral,
+    PhysicalUnit,
+    Alias,
+    RecordElement,
+    Attribute,
+    View,
+    Snippet,
+}
+
+impl CompletionKind {
+    fn of_entity(ent: EntRef<'_>) -> CompletionKind {
+        match ent.kind() {
+            AnyEntKind::ExternalAlias { .. } | AnyEntKind::ObjectAlias { .. } => {
+                CompletionKind::Alias
+            }
+            AnyEntKind::File(_) | AnyEntKind::InterfaceFile(_) => CompletionKind::File,
+            AnyEntKind::Component(_) => CompletionKind::Component,
+            AnyEntKind::Attribute(_) => CompletionKind::Attribute,
+            AnyEntKind::Overloaded(overloaded) => match overloaded {
+                Overloaded::SubprogramDecl(_)
+                | Overloaded::Subprogram(_)
+                | Overloaded::UninstSubprogramDecl(..)
+                | Overloaded::UninstSubprogram(..)
+                | Overloaded::InterfaceSubprogram(_) => CompletionKind::Subprogram,
+                Overloaded::EnumLiteral(_) => CompletionKind::EnumLiteral,
+                Overloaded::Alias(_) => CompletionKind::Alias,
+            },
+            AnyEntKind::Type(_) => CompletionKind::Type,
+            AnyEntKind::ElementDeclaration(_) => CompletionKind::RecordElement,
+            AnyEntKind::Concurrent(_) | AnyEntKind::Sequential(_) => CompletionKind::Label,
+            AnyEntKind::Object(object) => match object.class {
+                ObjectClass::Signal => CompletionKind::Signal,
+                ObjectClass::Constant => CompletionKind::Constant,
+                ObjectClass::Variable | ObjectClass::SharedVariable => CompletionKind::Variable,
+            },
+            AnyEntKind::LoopParameter(_) | AnyEntKind::DeferredConstant(_) => {
+                CompletionKind::Constant
+            }
+            AnyEntKind::PhysicalLiteral(_) => CompletionKind::PhysicalUnit,
+            AnyEntKind::Library => CompletionKind::Library,
+            AnyEntKind::Design(_) => CompletionKind::DesignUnit,
+            AnyEntKind::View(..) => CompletionKind::View,
+        }
+    }
+}
+
+/// A completion option together with the information that is shown for it
+#[derive(Debug, PartialEq, Clone)]
+pub struct CompletionItem<'a> {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// A short description, e.g., the type of an object or the signature of a subprogram
+    pub detail: String,
+    /// The comments above the declaration of the completed entity
+    pub documentation: Option<String>,
+    /// The completed entity, if the option refers to a single one
+    pub id: Option<EntityId>,
+    /// What is inserted when the item is selected
+    pub option: CompletionOption<'a>,
+}
+
+impl<'a> From<CompletionOption<'a>> for CompletionItem<'a> {
+    fn from(option: CompletionOption<'a>) -> Self {
+        let ent = option.entity();
+        CompletionItem {
+            label: option.label(),
+            kind: option.kind(),
+            detail: option.detail(),
+            documentation: ent.and_then(|ent| ent.documentation()).map(str::to_owned),
+            id: ent.map(|ent| ent.id()),
+            option,
+        }
+    }
 }
 
 macro_rules! kind {
@@ -70,14 +273,30 @@ macro_rules! kind {
 }
 
 /// Main entry point for completion. Given a source-file and a cursor position,
-/// lists available completion options at the cursor position.
+/// lists available completion items at the cursor position.
 pub fn list_completion_options<'a>(
     root: &'a DesignRoot,
     source: &Source,
     cursor: Position,
 ) -> Vec<CompletionItem<'a>> {
+    completion_options(root, source, cursor)
+        .into_iter()
+        .map(CompletionItem::from)
+        .collect()
+}
+
+/// Lists the options that can be completed at the cursor position
+pub(crate) fn completion_options<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Vec<CompletionOption<'a>> {
     use crate::syntax::Kind::*;
     let tokens = tokenize_input(root.symbols(), source, cursor);
+    if let Some(prefix) = external_path_prefix(&tokens) {
+        // The pathname of an external name, i.e., `<< signal dut.`
+        return completions_for_external_path(root, source, cursor, &prefix);
+    }
     match &tokens[..] {
         // With the current implementation of completions, this is annoying, rather than helpful.
         // SemiColons will try to complete the ';' character, which when pressing enter will cause
@@ -88,14 +307,44 @@ pub fn list_completion_options<'a>(
         | [.., kind!(Library), kind!(Identifier)]
         | [.., kind!(Use)]
         | [.., kind!(Use), kind!(Identifier)] => list_all_libraries(root),
+        [.., kind!(Context), token @ kind!(Identifier), kind!(Dot)]
+        | [.., kind!(Context), token @ kind!(Identifier), kind!(Dot), kind!(Identifier)] => {
+            // A context reference, i.e., `context ieee.`
+            root.item_at_cursor(source, token.pos.start())
+                .map(|(_, ent)| list_contexts_of_library(root, ent))
+                .unwrap_or_default()
+        }
+        [.., kind!(RightPar), kind!(Dot)]
+        | [.., kind!(RightPar), kind!(Dot), kind!(Identifier)] => {
+            // An element of an array, i.e., `foo(0).`
+            let Some(prefix) = prefix_of_indexed_name(&tokens) else {
+                return vec![];
+            };
+            if let Some((_, ent)) = root.item_at_cursor(source, prefix.pos.start()) {
+                completions_for_indexed_name(root, ent)
+            } else {
+                vec![]
+            }
+        }
         [.., token, kind!(Dot)] | [.., token, kind!(Dot), kind!(Identifier)] => {
             // get the entity before the token.
             // We rely on the syntax parsing to be resilient enough for this to yield a reasonable value.
             // Otherwise, we just return an empty value.
-            if let Some((_, ent)) = root.item_at_cursor(source, token.pos.start()) {
-                completions_for_selected_name(root, ent)
+            let Some((_, ent)) = root.item_at_cursor(source, token.pos.start()) else {
+                return vec![];
+            };
+            let completions = completions_for_selected_name(root, ent);
+            if is_in_use_clause(&tokens) {
+                // Subprograms are not called in use clauses, i.e., `use work.pkg.`
+                completions
+                    .into_iter()
+                    .map(|item| match item {
+                        CompletionOption::Subprogram(ent) => CompletionOption::Simple(ent),
+                        item => item,
+                    })
+                    .collect()
             } else {
-                vec![]
+                completions
             }
         }
         [.., token, kind!(Tick)] | [.., token, kind!(Tick), kind!(Identifier)] => {
@@ -105,9 +354,170 @@ pub fn list_completion_options<'a>(
                 vec![]
             }
         }
+        [.., kind!(Is | SemiColon), kind!(When)]
+        | [.., kind!(Is | SemiColon), kind!(When), kind!(Identifier)] => {
+            // A choice of a case statement, i.e., `case foo is when`
+            case_expression_of_choice(&tokens)
+                .and_then(|token| root.item_at_cursor(source, token.pos.start()))
+                .and_then(|(_, ent)| completions_for_enum_literals(ent))
+                .unwrap_or_else(|| all_completions(root, source, cursor, &tokens))
+        }
+        [.., token @ kind!(Identifier), kind!(LTE | ColonEq)]
+        | [.., token @ kind!(Identifier), kind!(LTE | ColonEq), kind!(Identifier)] => {
+            // The right hand side of an assignment, i.e., `foo <=`
+            root.item_at_cursor(source, token.pos.start())
+                .and_then(|(_, ent)| completions_for_enum_literals(ent))
+                .unwrap_or_else(|| all_completions(root, source, cursor, &tokens))
+        }
+        [.., kind!(End)]
+        | [.., kind!(End), kind!(Identifier)]
+        | [.., kind!(End), kind!(
+            Entity
+                | Architecture
+                | Package
+                | Process
+                | Block
+                | Generate
+                | Loop
+                | If
+                | Case
+                | Function
+                | Procedure
+                | Component
+                | Configuration
+                | Context
+                | Record
+                | Units
+                | View
+                | Protected
+        )]
+        | [.., kind!(End), kind!(
+            Entity
+                | Architecture
+                | Package
+                | Process
+                | Block
+                | Generate
+                | Loop
+                | If
+                | Case
+                | Function
+                | Procedure
+                | Component
+                | Configuration
+                | Context
+                | Record
+                | Units
+                | View
+                | Protected
+        ), kind!(Identifier)]
+        | [.., kind!(End), kind!(Package | Protected | Postponed), kind!(Body | Process)]
+        | [.., kind!(End), kind!(Package | Protected | Postponed), kind!(Body | Process), kind!(Identifier)] =>
+        {
+            // The label or name that closes a construct, i.e., `end process`
+            completions_for_end_label(root, source, cursor)
+        }
+        [.., kind!(Context)] | [.., kind!(Context), kind!(Identifier)] => list_all_libraries(root),
+        [.., kind!(Entity), token @ kind!(Identifier), kind!(LeftPar)]
+        | [.., kind!(Entity), token @ kind!(Identifier), kind!(LeftPar), kind!(Identifier)]
+        | [.., kind!(Entity), kind!(Identifier), kind!(Dot), token @ kind!(Identifier), kind!(LeftPar)]
+        | [.., kind!(Entity), kind!(Identifier), kind!(Dot), token @ kind!(Identifier), kind!(LeftPar), kind!(Identifier)]
+        | [.., kind!(Configuration), kind!(Identifier), kind!(Of), token @ kind!(Identifier), kind!(Is), kind!(For)]
+        | [.., kind!(Configuration), kind!(Identifier), kind!(Of), token @ kind!(Identifier), kind!(Is), kind!(For), kind!(Identifier)] =>
+        {
+            // The architecture of an entity, i.e., `entity work.foo(` or `configuration cfg of foo is for`
+            if let Some((_, ent)) = root.item_at_cursor(source, token.pos.start()) {
+                completions_for_architectures(root, ent)
+            } else {
+                vec![]
+            }
+        }
         [.., kind!(LeftPar | Comma)] | [.., kind!(LeftPar | Comma), kind!(Identifier)] => {
             completions_for_map_aspect(root, cursor, source)
         }
-        _ => generic_completions(root, cursor, source),
+        _ => all_completions(root, source, cursor, &tokens),
+    }
+}
+
+/// Completes the names that are visible at the cursor, keywords and,
+/// when a name is partially typed, the names that would require a use clause.
+/// The completions are ordered by their relevance.
+fn all_completions<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+    tokens: &[crate::syntax::Token],
+) -> Vec<CompletionOption<'a>> {
+    let mut completions = generic_completions(
+        root,
+        cursor,
+        source,
+        is_start_of_declaration_or_statement(tokens),
+    );
+    let pattern = match tokens {
+        [.., crate::syntax::Token {
+            kind: crate::syntax::Kind::Identifier,
+            value: Value::Identifier(pattern),
+            ..
+        }] => Some(pattern.name_utf8()),
+        _ => None,
+    };
+    if let Some(pattern) = &pattern {
+        completions.extend(unimported_completions(
+            root,
+            source,
+            cursor,
+            pattern,
+            &completions,
+        ));
+    }
+    rank_completions(completions, pattern.as_deref(), source)
+}
+
+/// Returns whether the cursor is at the start of a declaration or statement, i.e.,
+/// after the token that terminates the previous one, optionally followed by a partial identifier.
+fn is_start_of_declaration_or_statement(tokens: &[crate::syntax::Token]) -> bool {
+    use crate::syntax::Kind::*;
+    let tokens = match tokens {
+        [tokens @ .., kind!(Identifier)] => tokens,
+        _ => tokens,
+    };
+    matches!(
+        tokens,
+        [
+            ..,
+            kind!(SemiColon | Is | Begin | Then | Else | Loop | Generate | Process)
+        ]
+    )
+}
+
+/// Returns whether the cursor is within a use clause, i.e., `use work.pkg.`
+fn is_in_use_clause(tokens: &[crate::syntax::Token]) -> bool {
+    use crate::syntax::Kind::*;
+    tokens
+        .iter()
+        .rev()
+        .find(|token| matches!(token.kind, Use | SemiColon | Is | Begin))
+        .is_some_and(|token| token.kind == Use)
+}
+
+/// Returns the token before the parenthesis that matches the last
+/// closing parenthesis before the cursor, i.e., `foo` in `foo(bar(0)).`
+fn prefix_of_indexed_name(tokens: &[crate::syntax::Token]) -> Option<&crate::syntax::Token> {
+    use crate::syntax::Kind::*;
+    let end = tokens.iter().rposition(|token| token.kind == RightPar)?;
+    let mut depth = 0;
+    for idx in (0..=end).rev() {
+        match tokens[idx].kind {
+            RightPar => depth += 1,
+            LeftPar => {
+                depth -= 1;
+                if depth == 0 {
+                    return tokens[..idx].last();
+                }
+            }
+            _ => {}
+        }
     }
+    None
 }
@@ -4,13 +4,14 @@
 //
 // Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
 use crate::ast::{AttributeDesignator, ObjectClass, RangeAttribute, TypeAttribute};
-use crate::{named_entity, AnyEntKind, CompletionItem, EntRef, Object};
+use crate::named_entity::TypeEnt;
+use crate::{named_entity, AnyEntKind, CompletionOption, EntRef};
 
 /// Produces completions for an attribute name, i.e.,
 /// `foo'`
 /// The provided ent is the entity directly before the tick, i.e.,
 /// `foo` in the example above.
-pub(crate) fn completions_for_attribute_name(ent: EntRef<'_>) -> Vec<CompletionItem<'_>> {
+pub(crate) fn completions_for_attribute_name(ent: EntRef<'_>) -> Vec<CompletionOption<'_>> {
     let mut attributes: Vec<AttributeDesignator> = Vec::new();
     attributes.extend([
         AttributeDesignator::SimpleName,
@@ -19,19 +20,42 @@ pub(crate) fn completions_for_attribute_name(ent: EntRef<'_>) -> Vec<CompletionI
     ]);
 
     match ent.kind() {
-        AnyEntKind::Type(typ) => extend_attributes_of_type(typ, &mut attributes),
-        AnyEntKind::Object(obj) => extend_attributes_of_objects(obj, &mut attributes),
-        AnyEntKind::View(_) => attributes.push(AttributeDesignator::Converse),
+        AnyEntKind::Type(_) => {
+            // Subtypes have the attributes of their base type
+            if let Some(typ) = TypeEnt::from_any(ent) {
+                extend_attributes_of_type(typ.base_type().kind(), &mut attributes)
+            }
+        }
+        AnyEntKind::Object(obj) => {
+            extend_attributes_of_objects(obj.subtype.type_mark(), obj.class, &mut attributes)
+        }
+        AnyEntKind::ObjectAlias {
+            base_object,
+            type_mark,
+        } => extend_attributes_of_objects(*type_mark, base_object.class(), &mut attributes),
+        // Elements of records, i.e., `foo.bar'`.
+        // The class of the record object is not known from the element.
+        AnyEntKind::ElementDeclaration(subtype) | AnyEntKind::DeferredConstant(subtype) => {
+            extend_attributes_of_objects(
+                subtype.type_mark(),
+                ObjectClass::Constant,
+                &mut attributes,
+            )
+        }
+        AnyEntKind::LoopParameter(Some(typ)) => {
+            extend_attributes_of_type(typ.kind(), &mut attributes)
+        }
+        AnyEntKind::View(..) => attributes.push(AttributeDesignator::Converse),
         _ => {}
     }
     attributes
         .into_iter()
-        .map(CompletionItem::Attribute)
+        .map(CompletionOption::Attribute)
         .chain(
             ent.attrs
                 .values()
                 .map(|(_, b)| b)
-                .map(|ent| CompletionItem::Simple(ent.ent)),
+                .map(|ent| CompletionOption::Simple(ent.ent)),
         )
         .collect()
 }
@@ -62,11 +86,15 @@ fn extend_attributes_of_type(
     }
 }
 
-/// Extends applicable attributes when the attribute name is an object.
-fn extend_attributes_of_objects(obj: &Object<'_>, attributes: &mut Vec<AttributeDesignator>) {
-    extend_attributes_of_type(obj.subtype.type_mark().base_type().kind(), attributes);
+/// Extends applicable attributes when the attribute name is an object of the given type.
+fn extend_attributes_of_objects(
+    type_mark: TypeEnt<'_>,
+    class: ObjectClass,
+    attributes: &mut Vec<AttributeDesignator>,
+) {
+    extend_attributes_of_type(type_mark.base_type().kind(), attributes);
     attributes.push(AttributeDesignator::Type(TypeAttribute::Subtype));
-    if obj.class == ObjectClass::Signal {
+    if class == ObjectClass::Signal {
         use crate::ast::SignalAttribute::*;
         attributes.extend(
             [
@@ -91,9 +119,9 @@ fn extend_attributes_of_objects(obj: &Object<'_>, attributes: &mut Vec<Attribute
 mod tests {
     use crate::analysis::tests::LibraryBuilder;
     use crate::ast::RangeAttribute;
-    use crate::list_completion_options;
+    use crate::completion::completion_options;
     use crate::syntax::test::assert_eq_unordered;
-    use crate::CompletionItem;
+    use crate::CompletionOption;
 
     #[test]
     pub fn completes_attributes() {
@@ -113,7 +141,7 @@ end package;
 
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s1("foo'").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let expected_options = [
             Type(Element),
@@ -130,7 +158,7 @@ end package;
             SimpleName,
             PathName,
         ]
-        .map(CompletionItem::Attribute);
+        .map(CompletionOption::Attribute);
 
         assert_eq_unordered(&options, &expected_options);
     }
@@ -154,7 +182,7 @@ end package;
 
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s1("foo'").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let expected_options = [
             Type(Element),
@@ -182,8 +210,59 @@ end package;
             SimpleName,
             PathName,
         ]
-        .map(CompletionItem::Attribute);
+        .map(CompletionOption::Attribute);
+
+        assert_eq_unordered(&options, &expected_options);
+    }
+
+    #[test]
+    pub fn completes_attributes_of_subtypes_and_aliases() {
+        use crate::ast::AttributeDesignator::*;
+        use crate::ast::SignalAttribute::*;
+        use crate::ast::TypeAttribute::*;
+
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libA",
+            "\
+package my_pkg is
+    constant foo : NATURAL := NATURAL'
+    signal sig : BIT_VECTOR(0 to 1);
+    alias sig_alias is sig;
+    constant bar : NATURAL := sig_alias'
+end package;
+",
+        );
+
+        let (root, _) = builder.get_analyzed_root();
+        let cursor = code.s1("NATURAL'").end();
+        let options = completion_options(&root, code.source(), cursor);
 
+        let expected_options = [
+            Left,
+            Right,
+            Low,
+            High,
+            Ascending,
+            Image,
+            Value,
+            Pos,
+            Val,
+            Succ,
+            Pred,
+            LeftOf,
+            RightOf,
+            InstanceName,
+            SimpleName,
+            PathName,
+        ]
+        .map(CompletionOption::Attribute);
         assert_eq_unordered(&options, &expected_options);
+
+        let cursor = code.s1("sig_alias'").end();
+        let options = completion_options(&root, code.source(), cursor);
+        for attribute in [Length, Type(Element), Signal(Event), Signal(Stable)] {
+            assert!(options.contains(&CompletionOption::Attribute(attribute)));
+        }
     }
 }
@@ -0,0 +1,131 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use crate::analysis::DesignRoot;
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::Designator;
+use crate::{
+    CompletionOption, EntityId, HasEntityId, HasTokenSpan, Position, Source, TokenAccess, TokenSpan,
+};
+
+/// Completes the label or name that closes a construct, i.e.,
+/// `foo` in `end process foo` or `end entity foo`.
+pub(crate) fn completions_for_end_label<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Vec<CompletionOption<'a>> {
+    let mut searcher = EndLabelSearcher {
+        cursor,
+        innermost: None,
+    };
+    let _ = root.search_source(source, &mut searcher);
+    searcher
+        .innermost
+        .map(|id| root.get_ent(id))
+        // Statements without a label are anonymous
+        .filter(|ent| !matches!(ent.designator(), Designator::Anonymous(_)))
+        .map(CompletionOption::Simple)
+        .into_iter()
+        .collect()
+}
+
+/// Finds the innermost construct that contains the cursor and that is closed with an `end`.
+/// As the search proceeds from the outer to the inner constructs, the last construct
+/// that contains the cursor is the innermost one.
+struct EndLabelSearcher {
+    cursor: Position,
+    /// The entity of the innermost construct
+    innermost: Option<EntityId>,
+}
+
+impl Searcher for EndLabelSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let span = match &decl.ast {
+            DeclarationItem::Entity(unit) => unit.span,
+            DeclarationItem::Architecture(unit) => unit.span,
+            DeclarationItem::Package(unit) => unit.span,
+            DeclarationItem::PackageBody(unit) => unit.span,
+            DeclarationItem::Configuration(unit) => unit.span,
+            DeclarationItem::Context(unit) => unit.span,
+            DeclarationItem::Component(component) => component.span,
+            DeclarationItem::Subprogram(subprogram) => subprogram.span,
+            DeclarationItem::Type(typ) => typ.span,
+            DeclarationItem::ConcurrentStatement(statement) => {
+                TokenSpan::new(statement.get_start_token(), statement.get_end_token())
+            }
+            DeclarationItem::SequentialStatement(statement) => {
+                TokenSpan::new(statement.get_start_token(), statement.get_end_token())
+            }
+            _ => return NotFinished,
+        };
+        if span.get_pos(ctx).contains(self.cursor) {
+            self.innermost = decl.ent_id();
+        }
+        NotFinished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::{completion::completion_options, CompletionOption};
+
+    #[test]
+    fn completes_the_label_of_the_enclosing_construct() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity my_ent is
+end entity my_ent;
+
+architecture rtl of my_ent is
+  function incr(value : natural) return natural is
+  begin
+    return value + 1;
+  end function incr;
+begin
+  main : process
+  begin
+    inner : loop
+    end loop inner;
+    if true then
+    end if;
+  end process main;
+end architecture rtl;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let decl = |name: &str| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s1(name).start())
+                    .unwrap(),
+            )
+        };
+
+        for (end, name) in [
+            ("end entity ", "my_ent"),
+            ("end architecture ", "rtl"),
+            ("end function ", "incr"),
+            ("end loop ", "inner"),
+            ("end process ", "main"),
+        ] {
+            let cursor = code.s1(end).end();
+            let options = completion_options(&root, code.source(), cursor);
+            assert_eq!(options, vec![decl(name)], "after '{end}'");
+        }
+
+        // A partially typed label
+        let cursor = code.s1("end process m").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq!(options, vec![decl("main")]);
+
+        // Statements without a label have nothing to complete
+        let cursor = code.s1("end if").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq!(options, vec![]);
+    }
+}
@@ -6,7 +6,8 @@
 use crate::analysis::DesignRoot;
 use crate::completion::region::any_ent_to_completion_item;
 use crate::named_entity::DesignEnt;
-use crate::{AnyEntKind, CompletionItem, Design, EntRef, EntityId, HasEntityId};
+use crate::named_entity::InterfaceEnt;
+use crate::{AnyEntKind, CompletionOption, Design, EntRef, EntityId, HasEntityId};
 use itertools::Itertools;
 use std::collections::HashSet;
 
@@ -15,7 +16,7 @@ use std::collections::HashSet;
 pub(crate) fn get_visible_entities_from_architecture<'a>(
     root: &'a DesignRoot,
     ent: &DesignEnt<'a>,
-) -> Vec<CompletionItem<'a>> {
+) -> Vec<CompletionOption<'a>> {
     let mut entities: HashSet<EntityId> = HashSet::new();
     if let Design::Architecture(vis, _, ent_of_arch) = ent.kind() {
         for ent_ref in vis.visible() {
@@ -69,10 +70,89 @@ pub(crate) fn get_architectures_for_entity<'a>(
         .collect()
 }
 
+/// Completes the architectures of an entity, i.e., in `entity work.foo(`
+/// or in the block configuration of `configuration cfg of foo is for`.
+pub(crate) fn completions_for_architectures<'a>(
+    root: &'a DesignRoot,
+    ent: EntRef<'a>,
+) -> Vec<CompletionOption<'a>> {
+    if !matches!(ent.kind(), AnyEntKind::Design(Design::Entity(..))) {
+        return vec![];
+    }
+    get_architectures_for_entity(ent, root)
+        .into_iter()
+        .filter(|arch| matches!(arch.kind(), AnyEntKind::Design(Design::Architecture(..))))
+        .map(CompletionOption::Simple)
+        .collect()
+}
+
+/// Returns a snippet that instantiates an entity or a component with
+/// a `generic map` and a `port map` that associates every formal.
+/// The library of an entity is chosen from `library_names`,
+/// the architecture from `architectures` if there are several.
+pub fn instantiation_snippet(
+    ent: EntRef<'_>,
+    architectures: &[EntRef<'_>],
+    library_names: &[String],
+) -> Option<String> {
+    let region = match ent.kind() {
+        AnyEntKind::Design(Design::Entity(_, region)) | AnyEntKind::Component(region) => region,
+        _ => return None,
+    };
+    // The next tab stop of the snippet
+    let mut placeholder = 2;
+    let mut snippet = if matches!(ent.kind(), AnyEntKind::Component(_)) {
+        format!("${{1:{}_inst}}: {}", ent.designator, ent.designator)
+    } else {
+        placeholder += 1;
+        format!(
+            "${{1:{}_inst}}: entity ${{2|{}|}}.{}",
+            ent.designator,
+            library_names.join(","),
+            ent.designator
+        )
+    };
+    if architectures.len() > 1 {
+        placeholder += 1;
+        snippet.push_str(&format!(
+            "(${{{}|{}|}})",
+            placeholder - 1,
+            architectures
+                .iter()
+                .map(|architecture| architecture.designator().to_string())
+                .join(",")
+        ));
+    }
+
+    let (ports, generics) = region.ports_and_generics();
+    let mut add_map = |snippet: &mut String, elements: Vec<InterfaceEnt<'_>>, purpose: &str| {
+        if elements.is_empty() {
+            return;
+        }
+        let associations = elements
+            .iter()
+            .map(|element| {
+                placeholder += 1;
+                format!(
+                    "    {} => ${{{}:{}}}",
+                    element.designator,
+                    placeholder - 1,
+                    element.designator
+                )
+            })
+            .join(",\n");
+        snippet.push_str(&format!("\n  {purpose} map (\n{associations}\n  )"));
+    };
+    add_map(&mut snippet, generics, "generic");
+    add_map(&mut snippet, ports, "port");
+    snippet.push(';');
+    Some(snippet)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::analysis::tests::{assert_eq_unordered, check_no_diagnostics, LibraryBuilder};
-    use crate::{list_completion_options, CompletionItem};
+    use crate::{completion::completion_options, instantiation_snippet, CompletionOption};
     use itertools::Itertools;
 
     #[test]
@@ -98,7 +178,7 @@ end arch;
 
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s1("begin").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let my_ent = root
             .search_reference(code.source(), code.s1("my_ent").start())
@@ -108,8 +188,8 @@ end arch;
             .search_reference(code.source(), code.s1("my_other_ent").start())
             .unwrap();
 
-        assert!(options.contains(&CompletionItem::Instantiation(my_ent, vec![])));
-        assert!(options.contains(&CompletionItem::Instantiation(my_other_ent, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(my_ent, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(my_other_ent, vec![])));
     }
 
     #[test]
@@ -136,7 +216,7 @@ end arch;
 
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s("is", 4).end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let my_ent = root
             .search_reference(code.source(), code.s1("my_ent").start())
@@ -146,8 +226,8 @@ end arch;
             .search_reference(code.source(), code.s1("my_other_ent").start())
             .unwrap();
 
-        assert!(!options.contains(&CompletionItem::Instantiation(my_ent, vec![])));
-        assert!(!options.contains(&CompletionItem::Instantiation(my_other_ent, vec![])));
+        assert!(!options.contains(&CompletionOption::Instantiation(my_ent, vec![])));
+        assert!(!options.contains(&CompletionOption::Instantiation(my_other_ent, vec![])));
     }
 
     #[test]
@@ -199,27 +279,27 @@ end arch;
         let (root, diag) = builder.get_analyzed_root();
         check_no_diagnostics(&diag[..]);
         let cursor = code2.s1("begin").end();
-        let options = list_completion_options(&root, code2.source(), cursor);
+        let options = completion_options(&root, code2.source(), cursor);
 
         let my_ent2 = root
             .search_reference(code2.source(), code2.s1("my_ent2").start())
             .unwrap();
 
-        assert!(options.contains(&CompletionItem::Instantiation(my_ent2, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(my_ent2, vec![])));
 
         let ent1 = root
             .search_reference(code1.source(), code1.s1("my_ent").start())
             .unwrap();
 
         let cursor = code3.s1("begin").end();
-        let options = list_completion_options(&root, code3.source(), cursor);
+        let options = completion_options(&root, code3.source(), cursor);
 
         let my_ent2 = root
             .search_reference(code3.source(), code3.s1("my_ent2").start())
             .unwrap();
 
-        assert!(options.contains(&CompletionItem::Instantiation(my_ent2, vec![])));
-        assert!(options.contains(&CompletionItem::Instantiation(ent1, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(my_ent2, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(ent1, vec![])));
     }
 
     #[test]
@@ -256,7 +336,7 @@ end arch;
         let (root, diag) = builder.get_analyzed_root();
         check_no_diagnostics(&diag[..]);
         let cursor = code2.s("begin", 1).end();
-        let options = list_completion_options(&root, code2.source(), cursor);
+        let options = completion_options(&root, code2.source(), cursor);
 
         let ent = root
             .search_reference(code1.source(), code1.s1("my_ent").start())
@@ -273,7 +353,7 @@ end arch;
         let applicable_options = options
             .into_iter()
             .filter_map(|option| match option {
-                CompletionItem::Instantiation(ent, architectures) => Some((ent, architectures)),
+                CompletionOption::Instantiation(ent, architectures) => Some((ent, architectures)),
                 _ => None,
             })
             .collect_vec();
@@ -317,13 +397,13 @@ end arch1;
         let (root, diag) = builder.get_analyzed_root();
         check_no_diagnostics(&diag[..]);
         let cursor = code.s1("begin").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         for component in ["comp_A", "comp_B", "comp_C"] {
             let entity = root
                 .search_reference(code.source(), code.s1(component).start())
                 .unwrap();
-            assert!(options.contains(&CompletionItem::Instantiation(entity, vec![])))
+            assert!(options.contains(&CompletionOption::Instantiation(entity, vec![])))
         }
     }
 
@@ -353,7 +433,7 @@ end arch;
 
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s("begin", 2).end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let my_ent = root
             .search_reference(code.source(), code.s1("my_ent").start())
@@ -363,7 +443,135 @@ end arch;
             .search_reference(code.source(), code.s1("my_other_ent").start())
             .unwrap();
 
-        assert!(options.contains(&CompletionItem::Instantiation(my_ent, vec![])));
-        assert!(options.contains(&CompletionItem::Instantiation(my_other_ent, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(my_ent, vec![])));
+        assert!(options.contains(&CompletionOption::Instantiation(my_other_ent, vec![])));
+    }
+
+    #[test]
+    fn instantiation_snippets_associate_all_formals() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity counter is
+  generic (width : natural; init : natural := 0);
+  port (clk : in bit; count : out bit_vector(width - 1 downto 0));
+end entity;
+
+architecture rtl of counter is
+begin
+end architecture;
+
+architecture sim of counter is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component leaf is
+    port (o : out bit);
+  end component;
+begin
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let options = completion_options(&root, code.source(), code.s("begin", 3).end());
+        let counter = root
+            .search_reference(code.source(), code.s1("counter").start())
+            .unwrap();
+        let leaf = root
+            .search_reference(code.source(), code.s1("leaf").start())
+            .unwrap();
+        let architectures = options
+            .iter()
+            .find_map(|option| match option {
+                CompletionOption::Instantiation(ent, architectures) if *ent == counter => {
+                    Some(architectures.clone())
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert!(options.contains(&CompletionOption::Instantiation(leaf, vec![])));
+
+        let mut architectures = architectures;
+        architectures.sort_by_key(|architecture| architecture.decl_pos().cloned());
+        assert_eq!(
+            instantiation_snippet(
+                counter,
+                &architectures,
+                &["work".to_owned(), "libname".to_owned()]
+            )
+            .unwrap(),
+            "\
+${1:counter_inst}: entity ${2|work,libname|}.counter(${3|rtl,sim|})
+  generic map (
+    width => ${4:width},
+    init => ${5:init}
+  )
+  port map (
+    clk => ${6:clk},
+    count => ${7:count}
+  );"
+        );
+        assert_eq!(
+            instantiation_snippet(leaf, &[], &[]).unwrap(),
+            "\
+${1:leaf_inst}: leaf
+  port map (
+    o => ${2:o}
+  );"
+        );
+    }
+
+    #[test]
+    fn completes_architectures_of_an_entity() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity my_ent is
+end my_ent;
+
+architecture rtl of my_ent is
+begin
+end rtl;
+
+architecture sim of my_ent is
+begin
+end sim;
+
+entity top is
+end top;
+
+architecture arch of top is
+begin
+  inst: entity work.my_ent(rtl);
+end arch;
+
+configuration cfg of my_ent is
+  for sim
+  end for;
+end configuration;
+",
+        );
+
+        let (root, _) = builder.get_analyzed_root();
+        let arch = |name: &str| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s1(name).start())
+                    .unwrap(),
+            )
+        };
+
+        let cursor = code.s1("work.my_ent(").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(&options, &[arch("rtl"), arch("sim")]);
+
+        let cursor = code.s1("  for").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(&options, &[arch("rtl"), arch("sim")]);
     }
 }
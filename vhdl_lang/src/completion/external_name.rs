@@ -0,0 +1,442 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Completion of the path of external names, i.e., `dut.u1.counter` in
+//! `<< signal dut.u1.counter : natural >>`.
+//! The path is resolved by following the instantiations of the design, starting from the
+//! architecture that contains the external name or from the root entity of an absolute path.
+
+use crate::analysis::DesignRoot;
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::{
+    AnyDesignUnit, AnySecondaryUnit, ConcurrentStatement, Declaration, GenerateBody,
+    InstantiatedUnit, LabeledConcurrentStatement, ObjectClass,
+};
+use crate::data::{HasSrcPos, Symbol};
+use crate::named_entity::{NamedEntities, ObjectEnt};
+use crate::syntax::{Kind, Token, Value};
+use crate::{AnyEntKind, CompletionOption, Design, EntRef, HasEntityId, Position, Source};
+
+/// The part of an external pathname that precedes the cursor
+pub(crate) struct ExternalPathPrefix<'t> {
+    class: ObjectClass,
+    /// Whether the path starts at the root of the design, i.e., `.tb.dut`
+    absolute: bool,
+    /// The number of `^.` that go up the design hierarchy
+    up_levels: usize,
+    /// The completed path elements, without the partially typed name at the cursor
+    elements: Vec<&'t Symbol>,
+}
+
+/// Returns the prefix of the external pathname at the cursor,
+/// or `None` if the cursor is not within the pathname of an external name.
+pub(crate) fn external_path_prefix(tokens: &[Token]) -> Option<ExternalPathPrefix<'_>> {
+    use Kind::*;
+    let start = tokens.iter().rposition(|token| token.kind == LtLt)?;
+    let class = match tokens.get(start + 1)?.kind {
+        Signal => ObjectClass::Signal,
+        Constant => ObjectClass::Constant,
+        Variable => ObjectClass::SharedVariable,
+        _ => return None,
+    };
+    let mut path = &tokens[start + 2..];
+    let absolute = matches!(path.first(), Some(Token { kind: Dot, .. }));
+    if absolute {
+        path = &path[1..];
+    }
+    let mut up_levels = 0;
+    while let [Token { kind: Circ, .. }, Token { kind: Dot, .. }, rest @ ..] = path {
+        up_levels += 1;
+        path = rest;
+    }
+    let mut elements = Vec::new();
+    loop {
+        match path {
+            []
+            | [Token {
+                kind: Identifier, ..
+            }] => break,
+            [Token {
+                kind: Identifier,
+                value: Value::Identifier(name),
+                ..
+            }, rest @ ..] => {
+                // The index of a generate statement, i.e., `gen(0).`
+                let rest = match rest {
+                    [Token { kind: LeftPar, .. }, ..] => {
+                        let end = rest.iter().position(|token| token.kind == RightPar)?;
+                        &rest[end + 1..]
+                    }
+                    _ => rest,
+                };
+                let [Token { kind: Dot, .. }, rest @ ..] = rest else {
+                    return None;
+                };
+                elements.push(name);
+                path = rest;
+            }
+            _ => return None,
+        }
+    }
+    Some(ExternalPathPrefix {
+        class,
+        absolute,
+        up_levels,
+        elements,
+    })
+}
+
+/// Completes the next element of an external pathname
+pub(crate) fn completions_for_external_path<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+    prefix: &ExternalPathPrefix<'_>,
+) -> Vec<CompletionOption<'a>> {
+    // Going up the hierarchy requires knowing which instance contains the current design unit
+    if prefix.up_levels > 0 {
+        return vec![];
+    }
+    let Some((library_name, architecture)) = enclosing_architecture(root, source, cursor) else {
+        return vec![];
+    };
+    if !prefix.absolute {
+        let Some((entity, architecture_name)) = architecture else {
+            return vec![];
+        };
+        return complete_in_architecture(
+            root,
+            entity,
+            Some(&architecture_name),
+            &prefix.elements,
+            prefix.class,
+        );
+    }
+    let Some(library) = root.get_lib(&library_name) else {
+        return vec![];
+    };
+    match prefix.elements.split_first() {
+        // The root of the design
+        None => library
+            .primary_units()
+            .filter_map(|unit| unit.unit.get())
+            .filter(|unit| unit.is_entity())
+            .filter_map(|unit| unit.ent_id())
+            .map(|id| CompletionOption::Simple(root.get_ent(id)))
+            .collect(),
+        Some((name, rest)) => {
+            let Some(entity) = library
+                .primary_unit(name)
+                .and_then(|unit| unit.unit.get())
+                .filter(|unit| unit.is_entity())
+                .and_then(|unit| unit.ent_id())
+            else {
+                return vec![];
+            };
+            complete_in_architecture(root, root.get_ent(entity), None, rest, prefix.class)
+        }
+    }
+}
+
+/// Returns the library of the design unit that contains the cursor and,
+/// if it is an architecture, its entity and name
+fn enclosing_architecture<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<(Symbol, Option<(EntRef<'a>, Symbol)>)> {
+    for library in root.libraries() {
+        for unit in library.units() {
+            if &unit.pos().source != source {
+                continue;
+            }
+            let (Some(first), Some(last)) = (unit.tokens.first(), unit.tokens.last()) else {
+                continue;
+            };
+            if !(first.pos.start() <= cursor && cursor <= last.pos.end()) {
+                continue;
+            }
+            let architecture = unit.unit.get().and_then(|unit| match &*unit {
+                AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(body)) => body
+                    .entity_name
+                    .reference
+                    .get()
+                    .map(|id| (root.get_ent(id), body.ident.tree.item.clone())),
+                _ => None,
+            });
+            return Some((library.name().clone(), architecture));
+        }
+    }
+    None
+}
+
+/// Completes the path `elements` within the architecture `architecture` of `entity`.
+/// If no architecture name is given, the first architecture of the entity is used.
+fn complete_in_architecture<'a>(
+    root: &'a DesignRoot,
+    entity: EntRef<'a>,
+    architecture: Option<&Symbol>,
+    elements: &[&Symbol],
+    class: ObjectClass,
+) -> Vec<CompletionOption<'a>> {
+    let (Some(library), Some(entity_name)) = (
+        entity.library_name().and_then(|name| root.get_lib(name)),
+        entity.designator().as_identifier(),
+    ) else {
+        return vec![];
+    };
+    for unit in library.secondary_units(entity_name) {
+        let Some(unit) = unit.unit.get() else {
+            continue;
+        };
+        let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(body)) = &*unit else {
+            continue;
+        };
+        if architecture.is_some_and(|name| name != &body.ident.tree.item) {
+            continue;
+        }
+        let mut completions = if elements.is_empty() {
+            objects_of_entity(entity, class)
+        } else {
+            vec![]
+        };
+        completions.extend(complete_in_region(
+            root,
+            &body.decl,
+            &body.statements,
+            elements,
+            class,
+        ));
+        return completions;
+    }
+    vec![]
+}
+
+/// Completes the path `elements` within a concurrent region, i.e.,
+/// an architecture, a block or a generate statement.
+fn complete_in_region<'a>(
+    root: &'a DesignRoot,
+    declarations: &[WithTokenSpan<Declaration>],
+    statements: &[LabeledConcurrentStatement],
+    elements: &[&Symbol],
+    class: ObjectClass,
+) -> Vec<CompletionOption<'a>> {
+    let Some((name, rest)) = elements.split_first() else {
+        return objects_of_region(root, declarations, statements, class);
+    };
+    let Some(statement) = statements
+        .iter()
+        .find(|statement| statement.label.tree.as_ref().map(|label| &label.item) == Some(*name))
+    else {
+        return vec![];
+    };
+    let complete_in_body = |body: &GenerateBody| {
+        let declarations = body
+            .decl
+            .as_ref()
+            .map(|(declarations, _)| &declarations[..])
+            .unwrap_or_default();
+        complete_in_region(root, declarations, &body.statements, rest, class)
+    };
+    match &statement.statement.item {
+        ConcurrentStatement::Block(block) => {
+            complete_in_region(root, &block.decl, &block.statements, rest, class)
+        }
+        ConcurrentStatement::ForGenerate(generate) => complete_in_body(&generate.body),
+        ConcurrentStatement::IfGenerate(generate) => generate
+            .conds
+            .conditionals
+            .iter()
+            .map(|conditional| &conditional.item)
+            .chain(generate.conds.else_item.iter().map(|(item, _)| item))
+            .flat_map(complete_in_body)
+            .collect(),
+        ConcurrentStatement::CaseGenerate(generate) => generate
+            .sels
+            .alternatives
+            .iter()
+            .flat_map(|alternative| complete_in_body(&alternative.item))
+            .collect(),
+        ConcurrentStatement::Instance(instance) => {
+            let Some(unit) = instance.entity_reference().map(|id| root.get_ent(id)) else {
+                return vec![];
+            };
+            match (&instance.unit, unit.kind()) {
+                (
+                    InstantiatedUnit::Entity(_, architecture),
+                    AnyEntKind::Design(Design::Entity(..)),
+                ) => complete_in_architecture(
+                    root,
+                    unit,
+                    architecture.as_ref().map(|name| &name.item.item),
+                    rest,
+                    class,
+                ),
+                // A component is bound to the entity of the same name by default
+                (InstantiatedUnit::Component(_), AnyEntKind::Component(_)) => {
+                    let Some(entity) = default_binding(root, unit) else {
+                        return vec![];
+                    };
+                    complete_in_architecture(root, entity, None, rest, class)
+                }
+                _ => vec![],
+            }
+        }
+        _ => vec![],
+    }
+}
+
+/// Returns the entity that has the same name and library as a component
+fn default_binding<'a>(root: &'a DesignRoot, component: EntRef<'a>) -> Option<EntRef<'a>> {
+    let library = root.get_lib(component.library_name()?)?;
+    let id = library
+        .primary_unit(component.designator().as_identifier()?)?
+        .unit
+        .get()
+        .filter(|unit| unit.is_entity())?
+        .ent_id()?;
+    Some(root.get_ent(id))
+}
+
+/// The ports or generics of an entity that are objects of class `class`
+fn objects_of_entity(entity: EntRef<'_>, class: ObjectClass) -> Vec<CompletionOption<'_>> {
+    let AnyEntKind::Design(Design::Entity(_, region)) = entity.kind() else {
+        return vec![];
+    };
+    region
+        .entities
+        .values()
+        .filter_map(|entities| match entities {
+            NamedEntities::Single(ent) => Some(*ent),
+            NamedEntities::Overloaded(_) => None,
+        })
+        .filter(|ent| is_object_of_class(ent, class))
+        .map(CompletionOption::Simple)
+        .collect()
+}
+
+/// The objects of class `class` declared in a concurrent region and the labels
+/// of the statements that may be part of an external pathname
+fn objects_of_region<'a>(
+    root: &'a DesignRoot,
+    declarations: &[WithTokenSpan<Declaration>],
+    statements: &[LabeledConcurrentStatement],
+    class: ObjectClass,
+) -> Vec<CompletionOption<'a>> {
+    let objects = declarations
+        .iter()
+        .flat_map(|decl| decl.item.declarations())
+        .map(|id| root.get_ent(id))
+        .filter(|ent| is_object_of_class(ent, class));
+    let labels = statements
+        .iter()
+        .filter(|statement| {
+            statement.label.tree.is_some()
+                && matches!(
+                    statement.statement.item,
+                    ConcurrentStatement::Block(_)
+                        | ConcurrentStatement::Instance(_)
+                        | ConcurrentStatement::ForGenerate(_)
+                        | ConcurrentStatement::IfGenerate(_)
+                        | ConcurrentStatement::CaseGenerate(_)
+                )
+        })
+        .filter_map(|statement| statement.label.decl.get())
+        .map(|id| root.get_ent(id));
+    objects
+        .chain(labels)
+        .map(CompletionOption::Simple)
+        .collect()
+}
+
+fn is_object_of_class(ent: EntRef<'_>, class: ObjectClass) -> bool {
+    ObjectEnt::from_any(ent).is_some_and(|object| object.class() == class)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{assert_eq_unordered, LibraryBuilder};
+    use crate::{completion::completion_options, CompletionOption};
+
+    #[test]
+    fn completes_the_path_of_external_names() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity counter is
+  port (clk : in bit);
+end entity;
+
+architecture rtl of counter is
+  signal count : natural;
+  constant max : natural := 10;
+begin
+end architecture;
+
+entity dut is
+end entity;
+
+architecture rtl of dut is
+  signal clk : bit;
+begin
+  u1 : entity work.counter port map (clk => clk);
+  gen : for i in 0 to 1 generate
+    u2 : entity work.counter port map (clk => clk);
+  end generate;
+end architecture;
+
+entity tb is
+end entity;
+
+architecture sim of tb is
+begin
+  dut_inst : entity work.dut;
+
+  main : process
+    alias count is << signal dut_inst.u1.count : natural >>;
+    alias nested is << signal dut_inst.gen(0).u2.count : natural >>;
+    alias absolute is << constant .tb.dut_inst.u1.max : natural >>;
+  begin
+    wait;
+  end process;
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let decl = |name: &str, occurence: usize| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s(name, occurence).start())
+                    .unwrap(),
+            )
+        };
+
+        let cursor = code.s1("<< signal dut_inst.").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(
+            &options,
+            &[decl("clk : bit", 1), decl("u1 :", 1), decl("gen :", 1)],
+        );
+
+        let cursor = code.s1("<< signal dut_inst.u1.").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(&options, &[decl("clk : in", 1), decl("count :", 1)]);
+
+        let cursor = code.s1("<< signal dut_inst.gen(0).").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(&options, &[decl("u2 :", 1)]);
+
+        let cursor = code.s1("<< constant .tb.dut_inst.u1.m").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(&options, &[decl("max :", 1)]);
+
+        let cursor = code.s1("<< constant .").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(
+            &options,
+            &[decl("counter is", 1), decl("dut is", 1), decl("tb is", 1)],
+        );
+    }
+}
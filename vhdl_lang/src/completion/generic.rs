@@ -6,22 +6,105 @@
 use crate::ast::search::{
     DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, SearchState, Searcher,
 };
-use crate::ast::ArchitectureBody;
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::{
+    ArchitectureBody, ConcurrentStatement, Declaration, IterationScheme, SequentialStatement,
+    SubprogramSpecification,
+};
 use crate::completion::entity_instantiation::get_visible_entities_from_architecture;
 use crate::completion::region::completion_items_from_region;
-use crate::named_entity::{DesignEnt, Visibility};
-use crate::{CompletionItem, Design, HasTokenSpan, Position, Source, TokenAccess};
+use crate::completion::snippets::{Snippet, CONCURRENT_SNIPPETS, SEQUENTIAL_SNIPPETS};
+use crate::named_entity::{DesignEnt, OverloadedEnt, Visibility};
+use crate::syntax::Kind;
+use crate::{
+    CompletionOption, Design, HasTokenSpan, Position, Source, TokenAccess, TokenId, TokenSpan,
+};
 use itertools::{chain, Itertools};
 use vhdl_lang::analysis::DesignRoot;
 
+/// Produces all symbols visible at the cursor.
+/// When `complete_keywords` is true, the cursor is at the start of a declaration or statement
+/// and the keywords and statement templates that may start one at this position are also produced.
 pub(crate) fn generic_completions<'a>(
     root: &'a DesignRoot,
     cursor: Position,
     source: &Source,
-) -> Vec<CompletionItem<'a>> {
+    complete_keywords: bool,
+) -> Vec<CompletionOption<'a>> {
     let mut searcher = CompletionSearcher::new(cursor, root);
     let _ = root.search_source(source, &mut searcher);
-    searcher.completions
+    let mut completions = searcher.completions;
+    if complete_keywords {
+        if let Some(category) = searcher.category {
+            completions.extend(
+                category
+                    .keywords()
+                    .iter()
+                    .map(|kind| CompletionOption::Keyword(*kind)),
+            );
+            completions.extend(category.snippets().iter().map(CompletionOption::Snippet));
+        }
+    }
+    completions
+}
+
+/// The kind of region that the cursor is in.
+/// This determines which declarations or statements are allowed at the cursor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum RegionCategory {
+    /// A declarative part, e.g., of an architecture or a process
+    Declarative,
+    /// The statements of a process or subprogram
+    Sequential,
+    /// The statements of an architecture, block or generate statement
+    Concurrent,
+}
+
+impl RegionCategory {
+    /// The keywords that start a declaration or statement in this region
+    fn keywords(&self) -> &'static [Kind] {
+        use Kind::*;
+        match self {
+            RegionCategory::Declarative => &[
+                Signal, Constant, Variable, Shared, File, Type, Subtype, Alias, Attribute,
+                Component, Function, Procedure, Pure, Impure, Package, Use,
+            ],
+            RegionCategory::Sequential => &[
+                If, Case, Loop, For, While, Wait, Assert, Report, Return, Next, Exit, Null,
+            ],
+            RegionCategory::Concurrent => &[
+                Process, Block, For, If, Case, With, Assert, Entity, Component, Postponed,
+            ],
+        }
+    }
+
+    /// The templates of the statements that are legal in this region
+    fn snippets(&self) -> &'static [Snippet] {
+        match self {
+            RegionCategory::Declarative => &[],
+            RegionCategory::Sequential => SEQUENTIAL_SNIPPETS,
+            RegionCategory::Concurrent => CONCURRENT_SNIPPETS,
+        }
+    }
+
+    /// The category of a region that has a declarative part followed by
+    /// statements starting at `begin_token`.
+    fn of_declarative_region(
+        ctx: &dyn TokenAccess,
+        cursor: Position,
+        begin_token: TokenId,
+        end_token: TokenId,
+        statements: RegionCategory,
+    ) -> RegionCategory {
+        if TokenSpan::new(begin_token, end_token)
+            .get_pos(ctx)
+            .contains(cursor)
+        {
+            statements
+        } else {
+            RegionCategory::Declarative
+        }
+    }
 }
 
 /// This is the most general-purpose completion provider.
@@ -32,7 +115,9 @@ pub(crate) fn generic_completions<'a>(
 struct CompletionSearcher<'a> {
     root: &'a DesignRoot,
     cursor: Position,
-    completions: Vec<CompletionItem<'a>>,
+    completions: Vec<CompletionOption<'a>>,
+    /// The category of the innermost region that contains the cursor
+    category: Option<RegionCategory>,
 }
 
 impl<'a> CompletionSearcher<'a> {
@@ -41,6 +126,7 @@ impl<'a> CompletionSearcher<'a> {
             root: design_root,
             cursor,
             completions: Vec::new(),
+            category: None,
         }
     }
 }
@@ -57,6 +143,37 @@ impl<'a> CompletionSearcher<'a> {
         self.completions
             .extend(get_visible_entities_from_architecture(self.root, &ent));
     }
+
+    /// Add the items of a declarative part, e.g., the variables of a process
+    fn add_declarations(&mut self, declarations: &[WithTokenSpan<Declaration>]) {
+        self.completions.extend(
+            declarations
+                .iter()
+                .flat_map(|decl| decl.item.declarations())
+                .map(|id| CompletionOption::Simple(self.root.get_ent(id))),
+        );
+    }
+
+    /// Add the parameters of a subprogram
+    fn add_parameters(&mut self, specification: &SubprogramSpecification) {
+        let designator = match specification {
+            SubprogramSpecification::Procedure(procedure) => &procedure.designator,
+            SubprogramSpecification::Function(function) => &function.designator,
+        };
+        let Some(subprogram) = designator
+            .decl
+            .get()
+            .and_then(|id| OverloadedEnt::from_any(self.root.get_ent(id)))
+        else {
+            return;
+        };
+        self.completions.extend(
+            subprogram
+                .formals()
+                .iter()
+                .map(|formal| CompletionOption::Simple(formal.inner())),
+        );
+    }
 }
 
 impl<'a> Searcher for CompletionSearcher<'a> {
@@ -66,6 +183,7 @@ impl<'a> Searcher for CompletionSearcher<'a> {
                 if !ent_decl.get_pos(ctx).contains(self.cursor) {
                     return NotFinished;
                 }
+                self.category = Some(RegionCategory::Declarative);
                 ent_decl.ident.decl.get()
             }
             DeclarationItem::Architecture(body) => {
@@ -74,6 +192,9 @@ impl<'a> Searcher for CompletionSearcher<'a> {
                 }
                 if body.statement_span().get_pos(ctx).contains(self.cursor) {
                     self.add_entity_instantiations(body);
+                    self.category = Some(RegionCategory::Concurrent);
+                } else {
+                    self.category = Some(RegionCategory::Declarative);
                 }
                 body.ident.decl.get()
             }
@@ -81,25 +202,91 @@ impl<'a> Searcher for CompletionSearcher<'a> {
                 if !package.get_pos(ctx).contains(self.cursor) {
                     return NotFinished;
                 }
+                self.category = Some(RegionCategory::Declarative);
                 package.ident.decl.get()
             }
             DeclarationItem::PackageBody(package) => {
                 if !package.get_pos(ctx).contains(self.cursor) {
                     return NotFinished;
                 }
+                self.category = Some(RegionCategory::Declarative);
                 package.ident.decl.get()
             }
             DeclarationItem::Subprogram(subprogram) => {
                 if !subprogram.get_pos(ctx).contains(self.cursor) {
                     return NotFinished;
                 }
-                self.completions.extend(
-                    subprogram
-                        .declarations
-                        .iter()
-                        .flat_map(|decl| decl.item.declarations())
-                        .map(|id| CompletionItem::Simple(self.root.get_ent(id))),
-                );
+                self.add_parameters(&subprogram.specification);
+                self.add_declarations(&subprogram.declarations);
+                self.category = Some(RegionCategory::of_declarative_region(
+                    ctx,
+                    self.cursor,
+                    subprogram.begin_token,
+                    subprogram.end_token,
+                    RegionCategory::Sequential,
+                ));
+                return NotFinished;
+            }
+            // Statements with a declarative part are not design units.
+            // Their declarations are added when the cursor is within the statement.
+            DeclarationItem::ConcurrentStatement(statement) => {
+                if !statement.get_pos(ctx).contains(self.cursor) {
+                    return NotFinished;
+                }
+                match &statement.statement.item {
+                    ConcurrentStatement::Process(process) => {
+                        self.add_declarations(&process.decl);
+                        self.category = Some(RegionCategory::of_declarative_region(
+                            ctx,
+                            self.cursor,
+                            process.begin_token,
+                            process.end_token,
+                            RegionCategory::Sequential,
+                        ));
+                    }
+                    ConcurrentStatement::Block(block) => {
+                        self.add_declarations(&block.decl);
+                        self.category = Some(RegionCategory::of_declarative_region(
+                            ctx,
+                            self.cursor,
+                            block.begin_token,
+                            block.end_token,
+                            RegionCategory::Concurrent,
+                        ));
+                    }
+                    ConcurrentStatement::ForGenerate(generate) => {
+                        if let Some(id) = generate.index_name.decl.get() {
+                            self.completions
+                                .push(CompletionOption::Simple(self.root.get_ent(id)));
+                        }
+                        self.category = Some(RegionCategory::Concurrent);
+                        if let Some((declarations, begin_token)) = &generate.body.decl {
+                            self.add_declarations(declarations);
+                            if self.cursor < ctx.get_pos(*begin_token).start() {
+                                self.category = Some(RegionCategory::Declarative);
+                            }
+                        }
+                    }
+                    ConcurrentStatement::IfGenerate(_) | ConcurrentStatement::CaseGenerate(_) => {
+                        self.category = Some(RegionCategory::Concurrent);
+                    }
+                    _ => {}
+                }
+                return NotFinished;
+            }
+            DeclarationItem::SequentialStatement(statement) => {
+                if !statement.get_pos(ctx).contains(self.cursor) {
+                    return NotFinished;
+                }
+                self.category = Some(RegionCategory::Sequential);
+                if let SequentialStatement::Loop(loop_statement) = &statement.statement.item {
+                    if let Some(IterationScheme::For(index, _)) = &loop_statement.iteration_scheme {
+                        if let Some(id) = index.decl.get() {
+                            self.completions
+                                .push(CompletionOption::Simple(self.root.get_ent(id)));
+                        }
+                    }
+                }
                 return NotFinished;
             }
             _ => return NotFinished,
@@ -119,7 +306,7 @@ impl<'a> Searcher for CompletionSearcher<'a> {
 fn visible_entities_from<'a>(
     root: &'a DesignRoot,
     design: &'a Design<'a>,
-) -> Vec<CompletionItem<'a>> {
+) -> Vec<CompletionOption<'a>> {
     use Design::*;
     match design {
         Entity(visibility, region)
@@ -131,7 +318,7 @@ fn visible_entities_from<'a>(
             completion_items_from_visibility(root, visibility),
         )
         .collect_vec(),
-        PackageInstance(region) | InterfacePackageInstance(region) | Context(region) => {
+        PackageInstance(region, _) | InterfacePackageInstance(region, _) | Context(region) => {
             completion_items_from_region(root, region).collect_vec()
         }
         Configuration => vec![],
@@ -141,14 +328,237 @@ fn visible_entities_from<'a>(
 fn completion_items_from_visibility<'a>(
     root: &'a DesignRoot,
     visibility: &'a Visibility<'a>,
-) -> impl Iterator<Item = CompletionItem<'a>> {
+) -> impl Iterator<Item = CompletionOption<'a>> {
     visibility
         .visible()
         .unique()
-        .map(CompletionItem::Simple)
+        .map(CompletionOption::Simple)
         .chain(
             visibility.all_in_region().flat_map(|visible_region| {
                 completion_items_from_region(root, visible_region.region())
             }),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::completion::snippets::{CONCURRENT_SNIPPETS, SEQUENTIAL_SNIPPETS};
+    use crate::syntax::Kind;
+    use crate::{completion::completion_options, CompletionOption};
+    use crate::{list_completion_options, CompletionKind};
+
+    #[test]
+    fn completion_items_describe_the_completed_entity() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture arch of ent is
+  -- The number of samples
+  signal count : natural;
+begin
+  count <= 0;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let count = root
+            .search_reference(code.source(), code.s1("count").start())
+            .unwrap();
+
+        let items = list_completion_options(&root, code.source(), code.s1("begin").end());
+        let item = items.iter().find(|item| item.label == "count").unwrap();
+        assert_eq!(item.kind, CompletionKind::Signal);
+        assert_eq!(item.detail, count.describe());
+        assert_eq!(item.documentation.as_deref(), Some("The number of samples"));
+        assert_eq!(item.id, Some(count.id()));
+        assert_eq!(item.option, CompletionOption::Simple(count));
+
+        let item = items.iter().find(|item| item.label == "process").unwrap();
+        assert_eq!(item.kind, CompletionKind::Keyword);
+        assert_eq!(item.detail, "process");
+        assert_eq!(item.documentation, None);
+        assert_eq!(item.id, None);
+    }
+
+    #[test]
+    fn completes_declarations_visible_in_processes_and_subprograms() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+  port (clk : in bit);
+end entity;
+
+architecture arch of ent is
+  signal data : natural;
+  constant limit : natural := 1;
+
+  function add_one(param : natural) return natural is
+    variable fun_var : natural;
+  begin
+    fun_var := param;
+    return fun_var;
+  end function;
+begin
+  proc: process
+    variable proc_var : natural;
+  begin
+    for idx in 0 to 3 loop
+      proc_var := idx + add_one(limit);
+    end loop;
+    data <= proc_var;
+    wait;
+  end process;
+
+  other: process
+    variable other_var : natural;
+  begin
+    other_var := 0;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let ent = |name: &str, occurrence: usize| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s(name, occurrence).start())
+                    .unwrap(),
+            )
+        };
+
+        let options = completion_options(&root, code.source(), code.s1("proc_var := ").end());
+        for item in [
+            ent("clk", 1),
+            ent("data", 1),
+            ent("limit", 1),
+            ent("proc_var", 1),
+            ent("idx", 1),
+        ] {
+            assert!(options.contains(&item), "{item:?} is missing");
+        }
+        assert!(options.contains(&CompletionOption::Subprogram(
+            root.search_reference(code.source(), code.s1("add_one").start())
+                .unwrap()
+        )));
+        assert!(!options.contains(&ent("other_var", 1)));
+        assert!(!options.contains(&ent("fun_var", 1)));
+        assert!(!options.contains(&ent("param", 1)));
+
+        let options = completion_options(&root, code.source(), code.s1("data <= ").end());
+        assert!(options.contains(&ent("proc_var", 1)));
+        assert!(!options.contains(&ent("idx", 1)));
+
+        let options = completion_options(&root, code.source(), code.s1("fun_var := ").end());
+        assert!(options.contains(&ent("param", 1)));
+        assert!(options.contains(&ent("fun_var", 1)));
+        assert!(!options.contains(&ent("proc_var", 1)));
+    }
+
+    #[test]
+    fn completes_keywords_of_the_region_at_the_cursor() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal data : natural;
+begin
+  proc: process
+    variable proc_var : natural;
+  begin
+    proc_var := 0;
+    wait;
+  end process;
+
+  data <= 1;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let keyword = |kind| CompletionOption::Keyword(kind);
+
+        let options = completion_options(&root, code.source(), code.s1("signal data").start());
+        assert!(options.contains(&keyword(Kind::Signal)));
+        assert!(!options.contains(&keyword(Kind::Wait)));
+        assert!(!options.contains(&keyword(Kind::Process)));
+
+        let options = completion_options(&root, code.source(), code.s1("variable").start());
+        assert!(options.contains(&keyword(Kind::Variable)));
+        assert!(!options.contains(&keyword(Kind::Wait)));
+
+        let options = completion_options(&root, code.source(), code.s("proc_var", 2).end());
+        assert!(options.contains(&keyword(Kind::Wait)));
+        assert!(options.contains(&keyword(Kind::If)));
+        assert!(!options.contains(&keyword(Kind::Signal)));
+
+        let options = completion_options(&root, code.source(), code.s1("data <=").s1("data").end());
+        assert!(options.contains(&keyword(Kind::Process)));
+        assert!(!options.contains(&keyword(Kind::Wait)));
+
+        // No keywords within a statement
+        let options = completion_options(&root, code.source(), code.s1(":= 0").end());
+        assert!(!options.contains(&keyword(Kind::If)));
+    }
+
+    #[test]
+    fn completes_snippets_of_statements_that_are_legal_at_the_cursor() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal data : natural;
+begin
+  proc: process
+  begin
+    data <= 0;
+    wait;
+  end process;
+
+  data <= 1;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let snippet = |label: &str| {
+            CONCURRENT_SNIPPETS
+                .iter()
+                .chain(SEQUENTIAL_SNIPPETS)
+                .find(|snippet| snippet.label == label)
+                .map(CompletionOption::Snippet)
+                .unwrap()
+        };
+
+        let options =
+            completion_options(&root, code.source(), code.s1("data <= 1").s1("data").end());
+        assert!(options.contains(&snippet("clocked process")));
+        assert!(options.contains(&snippet("for generate")));
+        assert!(!options.contains(&snippet("case")));
+
+        let options = completion_options(&root, code.source(), code.s1("data <=").start());
+        assert!(options.contains(&snippet("case")));
+        assert!(options.contains(&snippet("if elsif else")));
+        assert!(!options.contains(&snippet("process")));
+
+        let options = completion_options(&root, code.source(), code.s1("signal").start());
+        assert!(!options.contains(&snippet("process")));
+        assert!(!options.contains(&snippet("case")));
+    }
+}
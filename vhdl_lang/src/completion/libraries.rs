@@ -4,22 +4,45 @@
 //
 // Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
 use crate::analysis::DesignRoot;
-use crate::CompletionItem;
+use crate::ast::{AnyDesignUnit, AnyPrimaryUnit};
+use crate::{AnyEntKind, CompletionOption, EntRef, HasEntityId};
 use std::iter::once;
 
 /// Produces all available libraries.
-pub(crate) fn list_all_libraries(root: &DesignRoot) -> Vec<CompletionItem<'_>> {
+/// Files that are not mapped to a library are analyzed in a library called 'work'.
+/// This library is not listed, as it is referred to by [CompletionOption::Work].
+pub(crate) fn list_all_libraries(root: &DesignRoot) -> Vec<CompletionOption<'_>> {
     root.libraries()
-        .map(|lib| CompletionItem::Simple(root.get_ent(lib.id())))
-        .chain(once(CompletionItem::Work))
+        .filter(|lib| !lib.name().name_utf8().eq_ignore_ascii_case("work"))
+        .map(|lib| CompletionOption::Simple(root.get_ent(lib.id())))
+        .chain(once(CompletionOption::Work))
+        .collect()
+}
+
+/// Produces the context declarations of a library, i.e., in `context ieee.`
+pub(crate) fn list_contexts_of_library<'a>(
+    root: &'a DesignRoot,
+    library: EntRef<'a>,
+) -> Vec<CompletionOption<'a>> {
+    if !matches!(library.kind(), AnyEntKind::Library) {
+        return vec![];
+    }
+    let Some(lib) = library.library_name().and_then(|name| root.get_lib(name)) else {
+        return vec![];
+    };
+    lib.primary_units()
+        .filter_map(|unit| unit.unit.get())
+        .filter(|unit| matches!(&**unit, AnyDesignUnit::Primary(AnyPrimaryUnit::Context(_))))
+        .filter_map(|unit| unit.ent_id())
+        .map(|id| CompletionOption::Simple(root.get_ent(id)))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::analysis::tests::{Code, LibraryBuilder};
+    use crate::analysis::tests::{check_no_diagnostics, Code, LibraryBuilder};
     use crate::syntax::test::assert_eq_unordered;
-    use crate::{list_completion_options, CompletionItem};
+    use crate::{completion::completion_options, CompletionOption};
 
     #[test]
     pub fn completing_libraries() {
@@ -27,15 +50,56 @@ mod tests {
         let code = Code::new("library ");
         let (root, _) = input.get_analyzed_root();
         let cursor = code.end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
         assert_eq_unordered(
             &options,
             &[
-                CompletionItem::Simple(
+                CompletionOption::Simple(
                     root.get_ent(root.get_lib(&root.symbol_utf8("std")).unwrap().id()),
                 ),
-                CompletionItem::Work,
+                CompletionOption::Work,
             ],
         )
     }
+
+    #[test]
+    pub fn completing_context_declarations() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+end package;
+
+context ctx is
+  library libname;
+  use libname.pkg.all;
+end context;
+
+library libname;
+context libname.ctx;
+
+entity ent is
+end entity;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let cursor = code.s1("context libname.").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq_unordered(
+            &options,
+            &[CompletionOption::Simple(
+                root.search_reference(code.source(), code.s1("ctx").start())
+                    .unwrap(),
+            )],
+        );
+
+        let cursor = code.s1("context libname.ctx").s1("libname").start();
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Work));
+        assert!(options.contains(&CompletionOption::Simple(
+            root.get_ent(root.get_lib(&root.symbol_utf8("libname")).unwrap().id())
+        )));
+    }
 }
@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use crate::named_entity::TypeEnt;
+use crate::syntax::{Kind, Token};
+use crate::{AnyEntKind, CompletionOption, EntRef, Overloaded, Type};
+
+/// Returns the literals of the enumeration type of an object in the order of their declaration.
+/// Returns `None` when the type of the object is not an enumeration type.
+pub(crate) fn enum_literals(ent: EntRef<'_>) -> Option<Vec<EntRef<'_>>> {
+    let type_mark = match ent.kind() {
+        AnyEntKind::Object(object) => object.subtype.type_mark(),
+        AnyEntKind::ObjectAlias { type_mark, .. } => *type_mark,
+        AnyEntKind::ElementDeclaration(subtype) | AnyEntKind::DeferredConstant(subtype) => {
+            subtype.type_mark()
+        }
+        AnyEntKind::LoopParameter(Some(typ)) => TypeEnt::from(*typ),
+        _ => return None,
+    };
+    let base_type = type_mark.base_type();
+    if !matches!(base_type.kind(), Type::Enum(_)) {
+        return None;
+    }
+    Some(
+        base_type
+            .implicits
+            .iter()
+            .filter(|ent| {
+                matches!(
+                    ent.kind(),
+                    AnyEntKind::Overloaded(Overloaded::EnumLiteral(_))
+                )
+            })
+            .copied()
+            .collect(),
+    )
+}
+
+/// Produces the literals of the enumeration type of an object, i.e., the literals
+/// that `foo` can be compared to or assigned in `foo <=` or `case foo is when`.
+/// Returns `None` when the type of the object is not an enumeration type.
+pub(crate) fn completions_for_enum_literals(ent: EntRef<'_>) -> Option<Vec<CompletionOption<'_>>> {
+    Some(
+        enum_literals(ent)?
+            .into_iter()
+            .map(CompletionOption::Simple)
+            .collect(),
+    )
+}
+
+/// Returns the last token of the expression of the case statement
+/// that encloses a choice, i.e., `bar` in `case foo.bar is when`.
+/// Nested case statements that have been closed with `end case` are skipped.
+pub(crate) fn case_expression_of_choice(tokens: &[Token]) -> Option<&Token> {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().rev() {
+        if token.kind != Kind::Case {
+            continue;
+        }
+        let is_end = idx > 0 && tokens[idx - 1].kind == Kind::End;
+        if is_end {
+            depth += 1;
+        } else if depth > 0 {
+            depth -= 1;
+        } else {
+            let is_token = tokens[idx..]
+                .iter()
+                .position(|token| token.kind == Kind::Is)?;
+            return tokens[idx + 1..idx + is_token]
+                .iter()
+                .rev()
+                .find(|token| token.kind == Kind::Identifier);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::{completion::completion_options, CompletionOption};
+
+    #[test]
+    fn completes_enum_literals_in_choices_and_assignments() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture arch of ent is
+  type state_t is (idle, busy, done);
+  signal state : state_t;
+  signal count : natural;
+begin
+  process
+  begin
+    case state is
+      when idle =>
+        state <= busy;
+      when others =>
+        count <= 0;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let literal = |name: &str| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s1(name).start())
+                    .unwrap(),
+            )
+        };
+        let literals = vec![literal("idle"), literal("busy"), literal("done")];
+
+        let cursor = code.s1("when idle").s1("when").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq!(options, literals);
+
+        let cursor = code.s1("when others").s1("when").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq!(options, literals);
+
+        let cursor = code.s1("state <= busy").s1("state <=").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq!(options, literals);
+
+        // Targets of other types complete all visible names
+        let cursor = code.s1("count <= 0").s1("count <=").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Simple(
+            root.search_reference(code.source(), code.s1("count").start())
+                .unwrap()
+        )));
+    }
+}
@@ -8,11 +8,12 @@ use crate::ast::search::{
     DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, SearchState, Searcher,
 };
 use crate::ast::{ConcurrentStatement, MapAspect, ObjectClass};
-use crate::named_entity::{AsUnique, Region};
+use crate::named_entity::{AsUnique, ObjectEnt, ObjectInterface, Region};
 use crate::{
-    named_entity, AnyEntKind, CompletionItem, Design, EntityId, HasTokenSpan, Overloaded, Position,
-    Source, TokenAccess,
+    named_entity, AnyEntKind, CompletionOption, Design, EntRef, EntityId, HasTokenSpan, Overloaded,
+    Position, Source, TokenAccess,
 };
+use itertools::Itertools;
 use std::collections::HashSet;
 
 /// Produces completions for the left hand side of a map aspect, i.e.,
@@ -21,7 +22,7 @@ pub(crate) fn completions_for_map_aspect<'a>(
     root: &'a DesignRoot,
     cursor: Position,
     source: &Source,
-) -> Vec<CompletionItem<'a>> {
+) -> Vec<CompletionOption<'a>> {
     let mut searcher = MapAspectSearcher::new(root, cursor);
     let _ = root.search_source(source, &mut searcher);
     searcher.completions
@@ -33,7 +34,7 @@ pub(crate) fn completions_for_map_aspect<'a>(
 struct MapAspectSearcher<'a> {
     root: &'a DesignRoot,
     cursor: Position,
-    completions: Vec<CompletionItem<'a>>,
+    completions: Vec<CompletionOption<'a>>,
 }
 
 impl<'a> MapAspectSearcher<'a> {
@@ -67,7 +68,7 @@ impl<'a> MapAspectSearcher<'a> {
             self.completions.extend(
                 ids.into_iter()
                     .filter(|id| !formals_in_map.contains(id))
-                    .map(|id| CompletionItem::Formal(self.root.get_ent(id))),
+                    .map(|id| CompletionOption::Formal(self.root.get_ent(id))),
             );
         }
         true
@@ -126,13 +127,33 @@ enum MapAspectKind {
     Generic,
 }
 
+/// Describes a formal of a map aspect, including the mode and type of ports and generics,
+/// e.g., `port 'clk' : in bit`
+pub fn formal_detail(ent: EntRef<'_>) -> String {
+    let Some(object) = ObjectEnt::from_any(ent) else {
+        return ent.describe();
+    };
+    let type_mark = object.type_mark().designator().to_string();
+    match &object.object().iface {
+        Some(ObjectInterface::Port(mode)) => {
+            format!("port '{}' : {mode} {type_mark}", object.designator())
+        }
+        Some(ObjectInterface::Generic) => {
+            format!("generic '{}' : {type_mark}", object.designator())
+        }
+        _ => object.describe(),
+    }
+}
+
 /// From this region, extracts those `AnyEntKind::Object`s where the class of the
 /// object matches the specified class.
+/// The objects are returned in the order of their declaration.
 fn extract_objects_with_class(region: &Region<'_>, object_class: ObjectClass) -> Vec<EntityId> {
     region
         .entities
         .values()
         .filter_map(|ent| ent.as_unique())
+        .sorted_by_key(|ent| ent.decl_pos().map(|pos| pos.range().start))
         .filter_map(|ent| match &ent.kind {
             AnyEntKind::Object(obj) if obj.class == object_class => Some(ent.id),
             AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(_))
@@ -186,7 +207,7 @@ pub fn extract_generic_names(root: &DesignRoot, id: EntityId) -> Vec<EntityId> {
 #[cfg(test)]
 mod tests {
     use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
-    use crate::{list_completion_options, CompletionItem};
+    use crate::{completion::completion_options, formal_detail, CompletionOption};
 
     #[test]
     pub fn complete_component_instantiation_map() {
@@ -223,11 +244,11 @@ mod tests {
         );
         let (root, _) = input.get_analyzed_root();
         let cursor = code.s1("generic map (").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
         let ent = root
             .search_reference(code.source(), code.s1("B").start())
             .unwrap();
-        assert_eq!(options, vec![CompletionItem::Formal(ent)]);
+        assert_eq!(options, vec![CompletionOption::Formal(ent)]);
 
         let rst = root
             .search_reference(code.source(), code.s1("rst").start())
@@ -252,27 +273,27 @@ mod tests {
             .unwrap();
 
         let cursor = code.s1("port map (").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
-        assert!(options.contains(&CompletionItem::Formal(rst)));
-        assert!(options.contains(&CompletionItem::Formal(dout)));
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Formal(rst)));
+        assert!(options.contains(&CompletionOption::Formal(dout)));
         assert_eq!(options.len(), 2);
         let cursor = code
             .s1("port map (
             clk =>")
             .pos()
             .end();
-        let options = list_completion_options(&root, code.source(), cursor);
-        assert!(options.contains(&CompletionItem::Simple(clk_signal)));
-        assert!(options.contains(&CompletionItem::Simple(rst_signal)));
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Simple(clk_signal)));
+        assert!(options.contains(&CompletionOption::Simple(rst_signal)));
 
         let cursor = code
             .s1("port map (
             clk => c")
             .pos()
             .end();
-        let options = list_completion_options(&root, code.source(), cursor);
-        assert!(options.contains(&CompletionItem::Simple(clk_signal)));
-        assert!(options.contains(&CompletionItem::Simple(rst_signal)));
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Simple(clk_signal)));
+        assert!(options.contains(&CompletionOption::Simple(rst_signal)));
     }
 
     #[test]
@@ -307,10 +328,10 @@ mod tests {
             .search_reference(code.source(), code.s1("type T").s1("T").start())
             .unwrap();
         let cursor = code.s1("generic map (").pos().end();
-        let options = list_completion_options(&root, code.source(), cursor);
-        assert!(options.contains(&CompletionItem::Formal(bar_func)));
-        assert!(options.contains(&CompletionItem::Formal(x)));
-        assert!(options.contains(&CompletionItem::Formal(t)));
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Formal(bar_func)));
+        assert!(options.contains(&CompletionOption::Formal(x)));
+        assert!(options.contains(&CompletionOption::Formal(t)));
         pretty_assertions::assert_eq!(options.len(), 3);
     }
 
@@ -337,7 +358,7 @@ end arch;
         let (root, diag) = builder.get_analyzed_root();
         check_no_diagnostics(&diag);
         let cursor = code.s1("begin").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let ent1 = root
             .search_reference(code.source(), code.s1("foo").start())
@@ -347,7 +368,55 @@ end arch;
             .search_reference(code.source(), code.s1("bar").start())
             .unwrap();
 
-        assert!(options.contains(&CompletionItem::Simple(ent1)));
-        assert!(options.contains(&CompletionItem::Simple(ent2)));
+        assert!(options.contains(&CompletionOption::Simple(ent1)));
+        assert!(options.contains(&CompletionOption::Simple(ent2)));
+    }
+
+    #[test]
+    pub fn completes_formals_in_declaration_order_with_detail() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity child is
+  generic (width : natural);
+  port (
+    zeta : in bit;
+    alpha : out bit_vector(0 to 1);
+    mid : inout bit
+  );
+end entity;
+
+entity top is
+end entity;
+
+architecture arch of top is
+begin
+  inst: entity work.child
+    generic map (width => 2)
+    port map (
+      alpha => open,
+    );
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let formal = |name: &str| {
+            root.search_reference(code.source(), code.s1(name).start())
+                .unwrap()
+        };
+
+        let cursor = code.s1("alpha => open,").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert_eq!(
+            options,
+            vec![
+                CompletionOption::Formal(formal("zeta")),
+                CompletionOption::Formal(formal("mid"))
+            ]
+        );
+
+        assert_eq!(formal_detail(formal("mid")), "port 'mid' : inout BIT");
+        assert_eq!(formal_detail(formal("width")), "generic 'width' : NATURAL");
     }
 }
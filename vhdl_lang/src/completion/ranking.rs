@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Filtering and ordering of completion items.
+//! Items are ranked by how well their name matches the partially typed name
+//! and by the distance of their declaration to the cursor.
+
+use crate::syntax::kind_str;
+use crate::{CompletionOption, Source};
+
+/// How well a name matches the partially typed name.
+/// Better matches compare less than worse matches.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub(crate) enum MatchQuality {
+    Exact,
+    Prefix,
+    /// Every typed character starts a word of the name, i.e., `slv` for `std_logic_vector`
+    WordStarts,
+    /// The typed characters appear in the name in the same order
+    Subsequence,
+    /// The name does not match. Such items are still offered, but last,
+    /// because clients may match names differently
+    NoMatch,
+}
+
+/// Matches `name` against the partially typed `pattern`, ignoring case.
+/// Returns `None` if the characters of the pattern do not appear in the name in order.
+pub(crate) fn match_quality(pattern: &str, name: &str) -> Option<MatchQuality> {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    if pattern == name {
+        return Some(MatchQuality::Exact);
+    }
+    if name.starts_with(&pattern) {
+        return Some(MatchQuality::Prefix);
+    }
+    let mut word_starts = true;
+    let mut name_chars = name.chars();
+    let mut previous = None;
+    for pattern_char in pattern.chars() {
+        loop {
+            let name_char = name_chars.next()?;
+            let at_word_start = matches!(previous, None | Some('_'));
+            previous = Some(name_char);
+            if name_char == pattern_char {
+                word_starts &= at_word_start;
+                break;
+            }
+        }
+    }
+    if word_starts {
+        Some(MatchQuality::WordStarts)
+    } else {
+        Some(MatchQuality::Subsequence)
+    }
+}
+
+/// The name that a completion item inserts
+fn name_of(item: &CompletionOption<'_>) -> String {
+    match item {
+        CompletionOption::Simple(ent)
+        | CompletionOption::Formal(ent)
+        | CompletionOption::Instantiation(ent, _)
+        | CompletionOption::Subprogram(ent)
+        | CompletionOption::Unimported(ent, _) => ent.designator().to_string(),
+        CompletionOption::Overloaded(designator, _) => designator.to_string(),
+        CompletionOption::Keyword(kind) => kind_str(*kind).to_owned(),
+        CompletionOption::Work => "work".to_owned(),
+        CompletionOption::Attribute(attribute) => attribute.to_string(),
+        CompletionOption::Snippet(snippet) => snippet.label.to_owned(),
+    }
+}
+
+/// The distance of the declaration of an item to the cursor.
+/// Declarations of the current file are closest, followed by keywords and snippets,
+/// declarations of other design units, the standard libraries and finally
+/// declarations that are not visible at all.
+fn scope_distance(item: &CompletionOption<'_>, source: &Source) -> usize {
+    let ent = match item {
+        CompletionOption::Unimported(..) => return 4,
+        CompletionOption::Keyword(_) | CompletionOption::Snippet(_) => return 1,
+        CompletionOption::Overloaded(..) => return 2,
+        CompletionOption::Work | CompletionOption::Attribute(_) => return 0,
+        CompletionOption::Simple(ent)
+        | CompletionOption::Formal(ent)
+        | CompletionOption::Instantiation(ent, _)
+        | CompletionOption::Subprogram(ent) => ent,
+    };
+    match ent.decl_pos() {
+        Some(pos) if &pos.source == source => 0,
+        _ if matches!(
+            ent.library_name().map(|name| name.name_utf8()).as_deref(),
+            Some("std" | "ieee")
+        ) =>
+        {
+            3
+        }
+        _ => 2,
+    }
+}
+
+/// Orders items by how well their name matches the partially typed name `pattern`
+/// and by their scope distance. Items of equal rank keep their order.
+pub(crate) fn rank_completions<'a>(
+    items: Vec<CompletionOption<'a>>,
+    pattern: Option<&str>,
+    source: &Source,
+) -> Vec<CompletionOption<'a>> {
+    let mut ranked = items
+        .into_iter()
+        .map(|item| {
+            let quality = match pattern {
+                Some(pattern) => {
+                    match_quality(pattern, &name_of(&item)).unwrap_or(MatchQuality::NoMatch)
+                }
+                None => MatchQuality::Exact,
+            };
+            ((quality, scope_distance(&item, source)), item)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::completion::completion_options;
+
+    #[test]
+    fn matches_fuzzy_patterns() {
+        assert_eq!(match_quality("Foo", "foo"), Some(MatchQuality::Exact));
+        assert_eq!(
+            match_quality("std", "std_logic"),
+            Some(MatchQuality::Prefix)
+        );
+        assert_eq!(
+            match_quality("slv", "std_logic_vector"),
+            Some(MatchQuality::WordStarts)
+        );
+        assert_eq!(
+            match_quality("sgv", "std_logic_vector"),
+            Some(MatchQuality::Subsequence)
+        );
+        assert_eq!(match_quality("vls", "std_logic_vector"), None);
+    }
+
+    #[test]
+    fn ranks_completions_by_match_quality_and_scope() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal slv_data : natural;
+  signal sel_valid : natural;
+  signal fast : natural;
+begin
+  slv_data <= sv;
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let signal = |name: &str| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s1(name).start())
+                    .unwrap(),
+            )
+        };
+
+        let cursor = code.s1("<= sv").end();
+        let options = completion_options(&root, code.source(), cursor);
+        // `sel_valid` matches at word starts, `slv_data` only as a subsequence
+        let sel_valid = options.iter().position(|item| item == &signal("sel_valid"));
+        let slv_data = options.iter().position(|item| item == &signal("slv_data"));
+        let fast = options.iter().position(|item| item == &signal("fast"));
+        assert!(sel_valid.unwrap() < slv_data.unwrap());
+        assert!(slv_data.unwrap() < fast.unwrap());
+    }
+}
@@ -7,41 +7,71 @@
 use crate::analysis::DesignRoot;
 use crate::completion::entity_instantiation::get_architectures_for_entity;
 use crate::named_entity::{AsUnique, NamedEntities, Region};
-use crate::{AnyEntKind, CompletionItem, Design};
+use crate::{AnyEntKind, CompletionOption, Design, Overloaded};
 use vhdl_lang::EntRef;
 
 pub(crate) fn completion_items_from_region<'a>(
     root: &'a DesignRoot,
     region: &'a Region<'a>,
-) -> impl Iterator<Item = CompletionItem<'a>> {
+) -> impl Iterator<Item = CompletionOption<'a>> {
     region
         .entities
         .values()
-        .map(|entities| named_entities_to_completion_item(root, entities))
+        .flat_map(|entities| named_entities_to_completion_items(root, entities))
 }
 
-fn named_entities_to_completion_item<'a>(
+fn named_entities_to_completion_items<'a>(
     root: &'a DesignRoot,
     named_entities: &'a NamedEntities<'a>,
-) -> CompletionItem<'a> {
+) -> Vec<CompletionOption<'a>> {
     match named_entities {
-        NamedEntities::Single(ent) => any_ent_to_completion_item(ent, root),
-        NamedEntities::Overloaded(overloaded) => match overloaded.as_unique() {
-            None => CompletionItem::Overloaded(overloaded.designator().clone(), overloaded.len()),
-            Some(ent) => CompletionItem::Simple(ent),
-        },
+        NamedEntities::Single(ent) => vec![any_ent_to_completion_item(ent, root)],
+        NamedEntities::Overloaded(overloaded) => {
+            // Each overload of a subprogram is completed with its own signature
+            if overloaded.designator().as_identifier().is_some()
+                && overloaded.entities().all(|ent| is_subprogram(ent.into()))
+            {
+                return overloaded
+                    .sorted_entities()
+                    .into_iter()
+                    .map(|ent| CompletionOption::Subprogram(ent.into()))
+                    .collect();
+            }
+            match overloaded.as_unique() {
+                None => vec![CompletionOption::Overloaded(
+                    overloaded.designator().clone(),
+                    overloaded.len(),
+                )],
+                Some(ent) => vec![CompletionOption::Simple(ent)],
+            }
+        }
     }
 }
 
+/// Returns whether the named entity is a function or procedure
+fn is_subprogram(ent: EntRef<'_>) -> bool {
+    matches!(
+        ent.kind(),
+        AnyEntKind::Overloaded(
+            Overloaded::SubprogramDecl(_)
+                | Overloaded::Subprogram(_)
+                | Overloaded::UninstSubprogramDecl(..)
+                | Overloaded::UninstSubprogram(..)
+                | Overloaded::InterfaceSubprogram(_)
+                | Overloaded::Alias(_)
+        )
+    )
+}
+
 pub(crate) fn any_ent_to_completion_item<'a>(
     ent: EntRef<'a>,
     root: &'a DesignRoot,
-) -> CompletionItem<'a> {
+) -> CompletionOption<'a> {
     match ent.kind() {
         AnyEntKind::Design(Design::Entity(..)) | AnyEntKind::Component(_) => {
             let architectures = get_architectures_for_entity(ent, root);
-            CompletionItem::Instantiation(ent, architectures)
+            CompletionOption::Instantiation(ent, architectures)
         }
-        _ => CompletionItem::Simple(ent),
+        _ => CompletionOption::Simple(ent),
     }
 }
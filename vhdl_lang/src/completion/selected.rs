@@ -6,8 +6,9 @@
 use crate::analysis::DesignRoot;
 use crate::completion::region::completion_items_from_region;
 use crate::data::Symbol;
+use crate::named_entity::TypeEnt;
 use crate::syntax::Kind::All;
-use crate::{named_entity, CompletionItem, EntRef, HasEntityId};
+use crate::{named_entity, CompletionOption, EntRef, HasEntityId};
 use std::iter::once;
 
 /// Produces completions for a selected name, i.e.,
@@ -17,10 +18,15 @@ use std::iter::once;
 pub(crate) fn completions_for_selected_name<'b>(
     root: &'b DesignRoot,
     ent: EntRef<'b>,
-) -> Vec<CompletionItem<'b>> {
+) -> Vec<CompletionOption<'b>> {
     use crate::named_entity::AnyEntKind::*;
     match ent.kind() {
-        Object(object) => completions_for_type(root, object.subtype.type_mark().kind()),
+        Object(object) => completions_for_type(root, object.subtype.type_mark()),
+        // Nested records, i.e., `foo.bar.`
+        ElementDeclaration(subtype) | DeferredConstant(subtype) => {
+            completions_for_type(root, subtype.type_mark())
+        }
+        ObjectAlias { type_mark, .. } => completions_for_type(root, *type_mark),
         Design(design) => completions_for_design(root, design),
         Library => ent
             .library_name()
@@ -30,24 +36,46 @@ pub(crate) fn completions_for_selected_name<'b>(
     }
 }
 
+/// Produces completions for a selected name whose prefix is an indexed name, i.e.,
+/// `foo(0).`
+/// The provided `ent` is the array object that is indexed, i.e., `foo` in the example above.
+pub(crate) fn completions_for_indexed_name<'b>(
+    root: &'b DesignRoot,
+    ent: EntRef<'b>,
+) -> Vec<CompletionOption<'b>> {
+    use crate::named_entity::AnyEntKind::*;
+    let typ = match ent.kind() {
+        Object(object) => object.subtype.type_mark(),
+        ElementDeclaration(subtype) | DeferredConstant(subtype) => subtype.type_mark(),
+        ObjectAlias { type_mark, .. } => *type_mark,
+        _ => return vec![],
+    };
+    match typ.base_type().kind() {
+        named_entity::Type::Array { elem_type, .. } => completions_for_type(root, *elem_type),
+        _ => vec![],
+    }
+}
+
 /// Returns completions applicable when calling `foo.` where `foo` is amn object of some type.
-fn completions_for_type<'a>(
-    root: &'a DesignRoot,
-    typ: &'a named_entity::Type<'a>,
-) -> Vec<CompletionItem<'a>> {
+fn completions_for_type<'a>(root: &'a DesignRoot, typ: TypeEnt<'a>) -> Vec<CompletionOption<'a>> {
     use crate::named_entity::Type::*;
-    match typ {
+    match typ.kind() {
         Record(record_region) => record_region
             .iter()
-            .map(|item| CompletionItem::Simple(item.ent))
+            .map(|item| CompletionOption::Simple(item.ent))
             .collect(),
-        Alias(type_ent) => completions_for_type(root, type_ent.kind()),
+        Alias(type_ent) => completions_for_type(root, *type_ent),
         Access(subtype) => {
-            let mut completions = completions_for_type(root, subtype.type_mark().kind());
-            completions.push(CompletionItem::Keyword(All));
+            let mut completions = completions_for_type(root, subtype.type_mark());
+            completions.push(CompletionOption::Keyword(All));
             completions
         }
-        Protected(region, _) => completion_items_from_region(root, region).collect(),
+        // Only the methods of the protected type declaration can be selected,
+        // not the declarations of the protected type body
+        Protected(..) => match typ.base_type().kind() {
+            Protected(region, _) => completion_items_from_region(root, region).collect(),
+            _ => vec![],
+        },
         _ => vec![],
     }
 }
@@ -57,12 +85,12 @@ fn completions_for_type<'a>(
 fn completions_for_design<'a>(
     root: &'a DesignRoot,
     design: &'a crate::Design<'a>,
-) -> Vec<CompletionItem<'a>> {
+) -> Vec<CompletionOption<'a>> {
     use crate::named_entity::Design::*;
     match design {
-        Package(_, region) | PackageInstance(region) | InterfacePackageInstance(region) => {
+        Package(_, region) | PackageInstance(region, _) | InterfacePackageInstance(region, _) => {
             completion_items_from_region(root, region)
-                .chain(once(CompletionItem::Keyword(All)))
+                .chain(once(CompletionOption::Keyword(All)))
                 .collect()
         }
         _ => vec![],
@@ -71,22 +99,23 @@ fn completions_for_design<'a>(
 
 /// List the name of all primary units for a given library.
 /// If the library is non-resolvable, list an empty vector
-fn list_primaries_for_lib<'a>(root: &'a DesignRoot, lib: &Symbol) -> Vec<CompletionItem<'a>> {
+fn list_primaries_for_lib<'a>(root: &'a DesignRoot, lib: &Symbol) -> Vec<CompletionOption<'a>> {
     let Some(lib) = root.get_lib(lib) else {
         return vec![];
     };
     lib.primary_units()
         .filter_map(|it| it.unit.get().and_then(|unit| unit.ent_id()))
-        .map(|id| CompletionItem::Simple(root.get_ent(id)))
+        .map(|id| CompletionOption::Simple(root.get_ent(id)))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::analysis::tests::{assert_eq_unordered, LibraryBuilder};
-    use crate::ast::Designator;
+    use crate::named_entity::NamedEntities;
     use crate::syntax::Kind::All;
-    use crate::{list_completion_options, CompletionItem};
+    use crate::{completion::completion_options, CompletionOption};
+    use itertools::Itertools;
 
     #[test]
     pub fn completes_selected_names() {
@@ -108,7 +137,7 @@ end foo;
 
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s1("y.").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
         let ent1 = root
             .search_reference(code.source(), code.s1("abc").start())
@@ -120,10 +149,60 @@ end foo;
 
         assert_eq_unordered(
             &options,
-            &[CompletionItem::Simple(ent1), CompletionItem::Simple(ent2)],
+            &[
+                CompletionOption::Simple(ent1),
+                CompletionOption::Simple(ent2),
+            ],
         )
     }
 
+    #[test]
+    pub fn completes_nested_records_and_array_elements() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libA",
+            "\
+package foo is
+    type inner_t is record
+        valid: bit;
+        data: bit;
+    end record;
+
+    type outer_t is record
+        inner: inner_t;
+        last: bit;
+    end record;
+
+    type outer_array_t is array (natural range <>) of outer_t;
+
+    constant rec: outer_t := (('1', '1'), '0');
+    constant arr: outer_array_t(0 to 1) := (others => rec);
+    alias rec_alias is rec;
+    constant a: bit := rec.inner.
+    constant b: bit := arr(0).
+    constant c: bit := rec_alias.
+end foo;
+        ",
+        );
+
+        let (root, _) = builder.get_analyzed_root();
+        let ent = |name: &str| {
+            CompletionOption::Simple(
+                root.search_reference(code.source(), code.s1(&format!("{name}:")).s1(name).start())
+                    .unwrap(),
+            )
+        };
+
+        let options = completion_options(&root, code.source(), code.s1("rec.inner.").end());
+        assert_eq_unordered(&options, &[ent("valid"), ent("data")]);
+
+        let options = completion_options(&root, code.source(), code.s1("arr(0).").end());
+        assert_eq_unordered(&options, &[ent("inner"), ent("last")]);
+
+        let options = completion_options(&root, code.source(), code.s1("rec_alias.").end());
+        assert_eq_unordered(&options, &[ent("inner"), ent("last")]);
+    }
+
     #[test]
     pub fn completing_primaries() {
         let mut builder = LibraryBuilder::new();
@@ -139,13 +218,13 @@ end package x;
         );
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s1("use std.").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
         assert_eq_unordered(
             &options,
             &[
-                CompletionItem::Simple(root.find_textio_pkg()),
-                CompletionItem::Simple(root.find_standard_pkg()),
-                CompletionItem::Simple(root.find_env_pkg()),
+                CompletionOption::Simple(root.find_textio_pkg()),
+                CompletionOption::Simple(root.find_standard_pkg()),
+                CompletionOption::Simple(root.find_env_pkg()),
             ],
         );
 
@@ -162,14 +241,14 @@ end package x;
         );
         let (root, _) = builder.get_analyzed_root();
         let cursor = code.s1("use std.t").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
         // Note that the filtering only happens at client side
         assert_eq_unordered(
             &options,
             &[
-                CompletionItem::Simple(root.find_textio_pkg()),
-                CompletionItem::Simple(root.find_standard_pkg()),
-                CompletionItem::Simple(root.find_env_pkg()),
+                CompletionOption::Simple(root.find_textio_pkg()),
+                CompletionOption::Simple(root.find_standard_pkg()),
+                CompletionOption::Simple(root.find_env_pkg()),
             ],
         );
     }
@@ -189,16 +268,64 @@ end package x;
         );
         let (root, _) = input.get_analyzed_root();
         let cursor = code.s1("use std.env.").end();
-        let options = list_completion_options(&root, code.source(), cursor);
+        let options = completion_options(&root, code.source(), cursor);
 
-        assert_eq_unordered(
-            &options,
-            &[
-                CompletionItem::Overloaded(Designator::Identifier(root.symbol_utf8("stop")), 2),
-                CompletionItem::Overloaded(Designator::Identifier(root.symbol_utf8("finish")), 2),
-                CompletionItem::Simple(root.find_env_symbol("resolution_limit")),
-                CompletionItem::Keyword(All),
-            ],
+        let overloads = |name: &str| match root.find_overloaded_env_symbols(name) {
+            NamedEntities::Overloaded(overloaded) => overloaded
+                .entities()
+                .map(|ent| CompletionOption::Simple(ent.into()))
+                .collect_vec(),
+            NamedEntities::Single(ent) => vec![CompletionOption::Simple(ent)],
+        };
+        let mut expected = overloads("stop");
+        expected.extend(overloads("finish"));
+        expected.push(CompletionOption::Simple(
+            root.find_env_symbol("resolution_limit"),
+        ));
+        expected.push(CompletionOption::Keyword(All));
+        assert_eq_unordered(&options, &expected);
+    }
+
+    #[test]
+    pub fn completes_the_methods_of_protected_types() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libA",
+            "\
+package foo is
+    type counter_t is protected
+        procedure increment;
+    end protected;
+
+    type counter_t is protected body
+        variable count: natural := 0;
+
+        procedure increment is
+        begin
+            count := count + 1;
+        end procedure;
+
+        procedure private is
+        begin
+        end procedure;
+    end protected body;
+end foo;
+
+package bar is
+end bar;
+
+package body bar is
+    shared variable counter: work.foo.counter_t;
+    constant c: natural := counter.
+end bar;
+        ",
         );
+
+        let (root, _) = builder.get_analyzed_root();
+        let options = completion_options(&root, code.source(), code.s1("counter.").end());
+        let increment = root
+            .search_reference(code.source(), code.s1("increment").start())
+            .unwrap();
+        assert_eq_unordered(&options, &[CompletionOption::Subprogram(increment)]);
     }
 }
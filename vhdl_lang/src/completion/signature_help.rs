@@ -0,0 +1,415 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use crate::analysis::DesignRoot;
+use crate::ast::Designator;
+use crate::completion::generic::generic_completions;
+use crate::completion::tokenizer::tokenize_input;
+use crate::named_entity::{InterfaceEnt, ObjectEnt, ObjectInterface, OverloadedEnt};
+use crate::syntax::{Kind, Value};
+use crate::{AnyEntKind, CompletionOption, Design, EntRef, Position, Source, Token};
+use itertools::Itertools;
+use std::ops::Range;
+
+/// The signatures that apply to the call or instantiation that contains the cursor
+#[derive(Debug, PartialEq, Clone)]
+pub struct SignatureHelp<'a> {
+    pub signatures: Vec<SignatureInformation<'a>>,
+    /// The index of the signature that the call resolves to
+    pub active_signature: usize,
+}
+
+/// The signature of a subprogram or the generic or port list of an instantiated unit
+#[derive(Debug, PartialEq, Clone)]
+pub struct SignatureInformation<'a> {
+    /// The subprogram, entity or component
+    pub ent: EntRef<'a>,
+    /// The whole signature, e.g., `function foo(a : in natural) return bit`
+    pub label: String,
+    /// The byte ranges of the parameters within the label
+    pub parameters: Vec<Range<usize>>,
+    /// The parameter that the cursor associates, if any
+    pub active_parameter: Option<usize>,
+}
+
+/// The association at the cursor
+#[derive(Debug, PartialEq, Clone)]
+enum ActiveAssociation<'t> {
+    /// The n-th positional association
+    Positional(usize),
+    /// An association by name, i.e., `foo =>`
+    Named(&'t Value),
+}
+
+impl ActiveAssociation<'_> {
+    fn parameter_of(&self, formals: &[InterfaceEnt<'_>]) -> Option<usize> {
+        match self {
+            ActiveAssociation::Positional(idx) => (*idx < formals.len()).then_some(*idx),
+            ActiveAssociation::Named(Value::Identifier(name)) => formals.iter().position(|formal| {
+                matches!(formal.designator(), Designator::Identifier(formal) if formal == name)
+            }),
+            ActiveAssociation::Named(_) => None,
+        }
+    }
+}
+
+/// Returns the index of the opening parenthesis of the association list that contains the cursor
+/// as well as the association at the cursor
+fn open_association_list(tokens: &[Token]) -> Option<(usize, ActiveAssociation<'_>)> {
+    let mut depth = 0;
+    let mut commas = 0;
+    let mut argument_start = None;
+    for (idx, token) in tokens.iter().enumerate().rev() {
+        match token.kind {
+            Kind::RightPar => depth += 1,
+            Kind::LeftPar if depth == 0 => {
+                let active = match &tokens[argument_start.unwrap_or(idx + 1)..] {
+                    [name @ Token {
+                        kind: Kind::Identifier,
+                        ..
+                    }, Token {
+                        kind: Kind::RightArrow,
+                        ..
+                    }, ..] => ActiveAssociation::Named(&name.value),
+                    _ => ActiveAssociation::Positional(commas),
+                };
+                return Some((idx, active));
+            }
+            Kind::LeftPar => depth -= 1,
+            Kind::Comma if depth == 0 => {
+                if argument_start.is_none() {
+                    argument_start = Some(idx + 1);
+                }
+                commas += 1;
+            }
+            Kind::SemiColon if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the name of the unit that is instantiated by the instantiation
+/// whose generic or port map starts at `map_idx`, i.e., `child` in
+/// `inst: entity work.child(arch) generic map (...) port map (`
+fn instantiated_unit(tokens: &[Token], map_idx: usize) -> Option<&Token> {
+    let mut depth = 0;
+    let colon_idx = tokens[..map_idx].iter().rposition(|token| {
+        match token.kind {
+            Kind::RightPar => depth += 1,
+            Kind::LeftPar => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && token.kind == Kind::Colon
+    })?;
+    tokens[colon_idx + 1..map_idx]
+        .iter()
+        .take_while(|token| !matches!(token.kind, Kind::LeftPar | Kind::Generic | Kind::Port))
+        .filter(|token| token.kind == Kind::Identifier)
+        .last()
+}
+
+fn describe_formal(formal: InterfaceEnt<'_>) -> String {
+    let Some(object) = ObjectEnt::from_any(formal.inner()) else {
+        return formal.designator().to_string();
+    };
+    let type_mark = object.type_mark().designator().to_string();
+    match &object.object().iface {
+        Some(ObjectInterface::Port(mode) | ObjectInterface::Parameter(mode)) => {
+            format!("{} : {mode} {type_mark}", formal.designator())
+        }
+        _ => format!("{} : {type_mark}", formal.designator()),
+    }
+}
+
+/// Builds a label from a prefix, the formals and a suffix and
+/// records where each formal is located within the label
+fn signature_information<'a>(
+    ent: EntRef<'a>,
+    prefix: String,
+    formals: &[InterfaceEnt<'a>],
+    suffix: &str,
+    active: &ActiveAssociation<'_>,
+) -> SignatureInformation<'a> {
+    let mut label = prefix;
+    let mut parameters = Vec::with_capacity(formals.len());
+    label.push('(');
+    for (idx, formal) in formals.iter().enumerate() {
+        if idx > 0 {
+            label.push_str("; ");
+        }
+        let start = label.len();
+        label.push_str(&describe_formal(*formal));
+        parameters.push(start..label.len());
+    }
+    label.push(')');
+    label.push_str(suffix);
+    SignatureInformation {
+        ent,
+        label,
+        parameters,
+        active_parameter: active.parameter_of(formals),
+    }
+}
+
+fn subprogram_signature<'a>(
+    subprogram: OverloadedEnt<'a>,
+    active: &ActiveAssociation<'_>,
+) -> SignatureInformation<'a> {
+    let kind = if subprogram.is_function() {
+        "function"
+    } else {
+        "procedure"
+    };
+    let suffix = match subprogram.return_type() {
+        Some(return_type) => format!(" return {}", return_type.designator()),
+        None => String::new(),
+    };
+    let formals = subprogram.formals().iter().collect_vec();
+    signature_information(
+        subprogram.into(),
+        format!("{kind} {}", subprogram.designator()),
+        &formals,
+        &suffix,
+        active,
+    )
+}
+
+/// The overloads of a subprogram that is called at the cursor.
+/// The subprogram that the call resolves to is the active signature.
+fn subprogram_signature_help<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+    name: &Token,
+    active: &ActiveAssociation<'_>,
+) -> Option<SignatureHelp<'a>> {
+    let resolved = root
+        .item_at_cursor(source, name.pos.start())
+        .and_then(|(_, ent)| OverloadedEnt::from_any(ent));
+    let Value::Identifier(symbol) = &name.value else {
+        return None;
+    };
+    let mut overloads = generic_completions(root, cursor, source, false)
+        .into_iter()
+        .filter_map(|item| match item {
+            CompletionOption::Subprogram(ent) => OverloadedEnt::from_any(ent),
+            _ => None,
+        })
+        .filter(|ent| matches!(ent.designator(), Designator::Identifier(name) if name == symbol))
+        .collect_vec();
+    if let Some(resolved) = resolved {
+        if !overloads.iter().any(|ent| ent.id() == resolved.id()) {
+            overloads.push(resolved);
+        }
+    }
+    if overloads.is_empty() {
+        return None;
+    }
+    overloads.sort_by_key(|ent| ent.decl_pos().map(|pos| pos.range().start));
+
+    let signatures = overloads
+        .iter()
+        .map(|ent| subprogram_signature(*ent, active))
+        .collect_vec();
+    let active_signature = resolved
+        .and_then(|resolved| overloads.iter().position(|ent| ent.id() == resolved.id()))
+        .or_else(|| {
+            signatures
+                .iter()
+                .position(|signature| signature.active_parameter.is_some())
+        })
+        .unwrap_or(0);
+    Some(SignatureHelp {
+        signatures,
+        active_signature,
+    })
+}
+
+/// The generics or ports of a unit that is instantiated at the cursor
+fn instantiation_signature_help<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    unit: &Token,
+    map_kind: Kind,
+    active: &ActiveAssociation<'_>,
+) -> Option<SignatureHelp<'a>> {
+    let (_, ent) = root.item_at_cursor(source, unit.pos.start())?;
+    let (kind, region) = match ent.kind() {
+        AnyEntKind::Component(region) => ("component", region),
+        AnyEntKind::Design(Design::Entity(_, region)) => ("entity", region),
+        _ => return None,
+    };
+    let (ports, generics) = region.ports_and_generics();
+    let (list, formals) = match map_kind {
+        Kind::Generic => ("generic", generics),
+        _ => ("port", ports),
+    };
+    Some(SignatureHelp {
+        signatures: vec![signature_information(
+            ent,
+            format!("{kind} {} {list} ", ent.designator()),
+            &formals,
+            "",
+            active,
+        )],
+        active_signature: 0,
+    })
+}
+
+/// Lists the signatures of the subprogram that is called at the cursor or
+/// the generics or ports of the unit that is instantiated at the cursor.
+pub fn signature_help<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<SignatureHelp<'a>> {
+    let tokens = tokenize_input(root.symbols(), source, cursor);
+    let (open_idx, active) = open_association_list(&tokens)?;
+    match &tokens[..open_idx] {
+        [.., map_kind @ Token {
+            kind: Kind::Generic | Kind::Port,
+            ..
+        }, Token {
+            kind: Kind::Map, ..
+        }] => {
+            let unit = instantiated_unit(&tokens, open_idx - 2)?;
+            instantiation_signature_help(root, source, unit, map_kind.kind, &active)
+        }
+        [.., name @ Token {
+            kind: Kind::Identifier,
+            ..
+        }] => subprogram_signature_help(root, source, cursor, name, &active),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+
+    fn labels_and_active_parameters(help: &SignatureHelp<'_>) -> Vec<(String, Option<usize>)> {
+        help.signatures
+            .iter()
+            .map(|signature| (signature.label.clone(), signature.active_parameter))
+            .collect()
+    }
+
+    #[test]
+    fn signatures_of_overloaded_subprograms() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  function scale(value : natural) return natural;
+  function scale(value : natural; factor : natural) return natural;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+  constant c0 : natural := scale(1, 2);
+  constant c1 : natural := scale(factor => 2, value => 1);
+begin
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let help = signature_help(&root, code.source(), code.s1("scale(1, ").end()).unwrap();
+        assert_eq!(
+            labels_and_active_parameters(&help),
+            vec![
+                (
+                    "function scale(value : in NATURAL) return NATURAL".to_owned(),
+                    None
+                ),
+                (
+                    "function scale(value : in NATURAL; factor : in NATURAL) return NATURAL"
+                        .to_owned(),
+                    Some(1)
+                ),
+            ]
+        );
+        assert_eq!(help.active_signature, 1);
+        let signature = &help.signatures[1];
+        assert_eq!(
+            &signature.label[signature.parameters[1].clone()],
+            "factor : in NATURAL"
+        );
+
+        let help = signature_help(&root, code.source(), code.s1("value => ").end()).unwrap();
+        assert_eq!(
+            labels_and_active_parameters(&help),
+            vec![
+                (
+                    "function scale(value : in NATURAL) return NATURAL".to_owned(),
+                    Some(0)
+                ),
+                (
+                    "function scale(value : in NATURAL; factor : in NATURAL) return NATURAL"
+                        .to_owned(),
+                    Some(0)
+                ),
+            ]
+        );
+
+        // Outside of the parentheses of a call
+        assert_eq!(
+            signature_help(&root, code.source(), code.s1("c0 :").end()),
+            None
+        );
+    }
+
+    #[test]
+    fn ports_and_generics_of_instantiated_units() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity child is
+  generic (width : natural);
+  port (clk : in bit; data : out bit);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, d : bit;
+begin
+  inst: entity work.child
+    generic map (4)
+    port map (clk, data => d);
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let help = signature_help(&root, code.source(), code.s1("generic map (").end()).unwrap();
+        assert_eq!(
+            labels_and_active_parameters(&help),
+            vec![("entity child generic (width : NATURAL)".to_owned(), Some(0))]
+        );
+
+        let help = signature_help(&root, code.source(), code.s1("data => ").end()).unwrap();
+        assert_eq!(
+            labels_and_active_parameters(&help),
+            vec![(
+                "entity child port (clk : in BIT; data : out BIT)".to_owned(),
+                Some(1)
+            )]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Templates of common statements that are completed at the start of a statement.
+//! The templates use the snippet syntax of the language server protocol, i.e.,
+//! `${1:name}` is the first placeholder with the default text `name`.
+
+/// A template of a statement
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snippet {
+    /// The name that the template is completed by
+    pub label: &'static str,
+    /// A short description of the template
+    pub detail: &'static str,
+    /// The text of the template
+    pub body: &'static str,
+}
+
+/// Templates of statements that are legal in a concurrent region,
+/// i.e., the statements of an architecture
+pub(crate) const CONCURRENT_SNIPPETS: &[Snippet] = &[
+    Snippet {
+        label: "process",
+        detail: "process with a sensitivity list",
+        body: "\
+${1:name} : process (${2:sensitivity})
+begin
+  $0
+end process ${1:name};",
+    },
+    Snippet {
+        label: "clocked process",
+        detail: "clocked process with an asynchronous reset",
+        body: "\
+${1:name} : process (${2:clk}, ${3:rst})
+begin
+  if ${3:rst} = '1' then
+    $4
+  elsif rising_edge(${2:clk}) then
+    $0
+  end if;
+end process ${1:name};",
+    },
+    Snippet {
+        label: "for generate",
+        detail: "for ... generate statement",
+        body: "\
+${1:gen} : for ${2:i} in ${3:0} to ${4:7} generate
+  $0
+end generate ${1:gen};",
+    },
+    Snippet {
+        label: "component instantiation",
+        detail: "instantiation of a component",
+        body: "\
+${1:inst} : ${2:component_name}
+  port map (
+    ${3:port} => ${4:signal}$0
+  );",
+    },
+];
+
+/// Templates of statements that are legal in a sequential region,
+/// i.e., the statements of a process or subprogram
+pub(crate) const SEQUENTIAL_SNIPPETS: &[Snippet] = &[
+    Snippet {
+        label: "if elsif else",
+        detail: "if statement with elsif and else branches",
+        body: "\
+if ${1:condition} then
+  $2
+elsif ${3:condition} then
+  $4
+else
+  $0
+end if;",
+    },
+    Snippet {
+        label: "case",
+        detail: "case statement",
+        body: "\
+case ${1:expression} is
+  when ${2:choice} =>
+    $3
+  when others =>
+    $0
+end case;",
+    },
+];
@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use crate::named_entity::OverloadedEnt;
+use crate::EntRef;
+use itertools::Itertools;
+
+/// Returns a snippet that calls a subprogram and associates every parameter by name, i.e.,
+/// `foo(a => ${1:a}, b => ${2:b})`.
+/// Returns `None` if the subprogram has no parameters and is therefore called by its name only.
+pub fn subprogram_call_snippet(ent: EntRef<'_>) -> Option<String> {
+    let subprogram = OverloadedEnt::from_any(ent)?;
+    let formals = subprogram.formals();
+    if formals.is_empty() {
+        return None;
+    }
+    let associations = formals
+        .iter()
+        .enumerate()
+        .map(|(idx, formal)| {
+            format!(
+                "{} => ${{{}:{}}}",
+                formal.designator(),
+                idx + 1,
+                formal.designator()
+            )
+        })
+        .join(", ");
+    Some(format!("{}({associations})", ent.designator()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use crate::{completion::completion_options, subprogram_call_snippet, CompletionOption};
+
+    #[test]
+    fn completes_every_overload_of_a_subprogram() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  function scale(value : natural) return natural;
+  function scale(value : natural; factor : natural) return natural;
+  procedure reset;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+  constant c : natural := scale(1);
+begin
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let subprogram = |name: &str| {
+            root.search_reference(code.source(), code.s1(name).start())
+                .unwrap()
+        };
+        let scale1 = subprogram("scale(value : natural)");
+        let scale2 = subprogram("scale(value : natural; factor");
+        let reset = subprogram("reset");
+
+        let cursor = code.s1(":= scale").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Subprogram(scale1)));
+        assert!(options.contains(&CompletionOption::Subprogram(scale2)));
+        assert!(options.contains(&CompletionOption::Subprogram(reset)));
+
+        assert_eq!(
+            subprogram_call_snippet(scale2).as_deref(),
+            Some("scale(value => ${1:value}, factor => ${2:factor})")
+        );
+        assert_eq!(subprogram_call_snippet(reset), None);
+
+        // Subprograms are not called in a use clause
+        let cursor = code.s1("use work.pkg.").end();
+        let options = completion_options(&root, code.source(), cursor);
+        assert!(options.contains(&CompletionOption::Simple(scale1)));
+        assert!(!options.contains(&CompletionOption::Subprogram(scale1)));
+    }
+}
@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+use crate::analysis::DesignRoot;
+use crate::ast::Designator;
+use crate::completion::ranking::match_quality;
+use crate::data::{HasSrcPos, Symbol};
+use crate::named_entity::NamedEntities;
+use crate::{
+    AnyEntKind, CompletionOption, Design, EntRef, EntityId, HasEntityId, Position, Source, TextEdit,
+};
+use fnv::FnvHashSet;
+
+/// Produces the declarations of packages whose name matches `pattern`, but that are not
+/// visible at the cursor. Every declaration is accompanied by the use clause
+/// (and library clause, if required) that makes it visible.
+/// `visible` are the completions of the names that are already visible at the cursor.
+pub(crate) fn unimported_completions<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+    pattern: &str,
+    visible: &[CompletionOption<'a>],
+) -> Vec<CompletionOption<'a>> {
+    let Some((work, insert_pos)) = enclosing_design_unit(root, source, cursor) else {
+        return vec![];
+    };
+    let visible_ids: FnvHashSet<EntityId> = visible
+        .iter()
+        .filter_map(|item| item.entity())
+        .map(|ent| ent.id())
+        .collect();
+    let visible_names: FnvHashSet<String> = visible
+        .iter()
+        .filter_map(|item| match item {
+            CompletionOption::Overloaded(designator, _) => Some(designator.to_string()),
+            item => item.entity().map(|ent| ent.designator().to_string()),
+        })
+        .map(|name| name.to_lowercase())
+        .collect();
+
+    let mut completions = Vec::new();
+    for library in root.libraries() {
+        for unit in library.primary_units() {
+            let Some(package) = unit
+                .unit
+                .get()
+                .and_then(|data| data.ent_id())
+                .map(|id| root.get_ent(id))
+            else {
+                continue;
+            };
+            let AnyEntKind::Design(Design::Package(_, region)) = package.kind() else {
+                continue;
+            };
+            let declarations = region
+                .entities
+                .values()
+                .flat_map(|entities| match entities {
+                    NamedEntities::Single(ent) => vec![*ent],
+                    NamedEntities::Overloaded(overloaded) => {
+                        overloaded.entities().map(|ent| ent.into()).collect()
+                    }
+                })
+                .filter(|ent: &EntRef<'_>| {
+                    let Designator::Identifier(name) = ent.designator() else {
+                        return false;
+                    };
+                    let name = name.name_utf8().to_lowercase();
+                    ent.is_explicit()
+                        && match_quality(pattern, &name).is_some()
+                        && !visible_ids.contains(&ent.id())
+                        && !visible_names.contains(&name)
+                })
+                .collect::<Vec<_>>();
+            if declarations.is_empty() {
+                continue;
+            }
+            let library_name = library.name();
+            let use_clause = if library_name == work {
+                format!("use work.{}.all;\n", package.designator())
+            } else if library_name.name_utf8() == "std"
+                || visible_names.contains(&library_name.name_utf8().to_lowercase())
+            {
+                format!("use {library_name}.{}.all;\n", package.designator())
+            } else {
+                format!(
+                    "library {library_name};\nuse {library_name}.{}.all;\n",
+                    package.designator()
+                )
+            };
+            completions.extend(declarations.into_iter().map(|ent| {
+                CompletionOption::Unimported(ent, TextEdit::insert(insert_pos, use_clause.clone()))
+            }));
+        }
+    }
+    completions
+}
+
+/// Returns the library of the design unit that contains the cursor and the position
+/// in front of its context clause where a use clause can be inserted.
+fn enclosing_design_unit<'a>(
+    root: &'a DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<(&'a Symbol, Position)> {
+    for library in root.libraries() {
+        for unit in library.units() {
+            if &unit.pos().source != source {
+                continue;
+            }
+            let (Some(first), Some(last)) = (unit.tokens.first(), unit.tokens.last()) else {
+                continue;
+            };
+            if first.pos.start() <= cursor && cursor <= last.pos.end() {
+                return Some((library.name(), Position::new(first.pos.start().line, 0)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::{completion::completion_options, CompletionOption, Position, TextEdit};
+
+    #[test]
+    fn completes_declarations_that_require_a_use_clause() {
+        let mut builder = LibraryBuilder::new();
+        let pkg = builder.code(
+            "libname",
+            "\
+package pkg is
+  constant max_width : natural := 8;
+  constant max_depth : natural := 16;
+end package;",
+        );
+        let other_pkg = builder.code(
+            "otherlib",
+            "\
+package other_pkg is
+  constant max_count : natural := 4;
+end package;",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+use work.pkg.max_depth;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+  constant size : natural := max
+begin
+end architecture;",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let decl = |code: &crate::analysis::tests::Code, name: &str| {
+            root.search_reference(code.source(), code.s1(name).start())
+                .unwrap()
+        };
+
+        let cursor = code.s1(":= max").end();
+        let options = completion_options(&root, code.source(), cursor);
+        let unimported = options
+            .into_iter()
+            .filter(|item| matches!(item, CompletionOption::Unimported(..)))
+            .collect::<Vec<_>>();
+        assert_eq!(unimported.len(), 2);
+        assert!(unimported.contains(&CompletionOption::Unimported(
+            decl(&pkg, "max_width"),
+            TextEdit::insert(Position::new(5, 0), "use work.pkg.all;\n")
+        )));
+        assert!(unimported.contains(&CompletionOption::Unimported(
+            decl(&other_pkg, "max_count"),
+            TextEdit::insert(
+                Position::new(5, 0),
+                "library otherlib;\nuse otherlib.other_pkg.all;\n"
+            )
+        )));
+    }
+}
@@ -19,7 +19,11 @@ use toml::{Table, Value};
 
 use crate::data::error_codes::ErrorCode;
 use crate::data::*;
+use crate::formatting::FormatOptions;
 use crate::standard::VHDLStandard;
+use crate::syntax::ConditionalAnalysis;
+
+mod profiles;
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct Config {
@@ -28,13 +32,291 @@ pub struct Config {
     standard: VHDLStandard,
     // Defines the severity that diagnostics are displayed with
     severities: SeverityMap,
+    // Settings for code generated by code actions
+    code_actions: CodeActionConfig,
+    // Settings of the formatter, if the formatting style is configured
+    format: Option<FormatOptions>,
+    // Naming conventions that are checked by the linter, by the class of named entity
+    naming: FnvHashMap<String, NamingRule>,
+    // The values of the identifiers of conditional analysis directives, by name
+    conditional_analysis: FnvHashMap<String, String>,
+    // The top-level unit that is elaborated by the linter, if any
+    elaboration: Option<ElaborationConfig>,
+}
+
+/// The top-level unit of the design and the values of its generics,
+/// which are propagated through the instances of the design by the linter
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct ElaborationConfig {
+    /// The top-level entity, given as `library.entity` or as the name of an entity
+    pub top: String,
+    /// The values of the generics of the top-level entity, by lower case name.
+    /// Boolean values are given by the position of their literal, i.e., 0 or 1.
+    pub generics: FnvHashMap<String, i64>,
+}
+
+impl ElaborationConfig {
+    fn from_toml(elaboration: &Table) -> Result<ElaborationConfig, String> {
+        let mut config = ElaborationConfig::default();
+        for (name, value) in elaboration {
+            match name.as_str() {
+                "top" => {
+                    config.top = value
+                        .as_str()
+                        .ok_or("elaboration.top must be a string")?
+                        .to_owned()
+                }
+                "generics" => {
+                    for (generic, value) in value
+                        .as_table()
+                        .ok_or("elaboration.generics must be a table")?
+                    {
+                        let value = match value {
+                            Value::Integer(value) => *value,
+                            Value::Boolean(value) => i64::from(*value),
+                            _ => {
+                                return Err(format!(
+                                    "elaboration.generics.{generic} must be an integer or a boolean"
+                                ))
+                            }
+                        };
+                        config.generics.insert(generic.to_lowercase(), value);
+                    }
+                }
+                _ => return Err(format!("'{name}' is not a valid elaboration setting")),
+            }
+        }
+        if config.top.is_empty() {
+            return Err("missing field top for elaboration".to_owned());
+        }
+        Ok(config)
+    }
+}
+
+/// Naming conventions for code that is generated by code actions
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CodeActionConfig {
+    /// The name of generated architectures.
+    /// `{entity}` is replaced by the name of the entity.
+    pub architecture_name: String,
+    /// The name (without extension) of files that contain generated architectures.
+    /// `{entity}` and `{architecture}` are replaced by the name of the entity and architecture.
+    pub architecture_file_name: String,
+    /// A comma separated list of port groups that defines the order of reordered ports.
+    /// Ports that do not belong to any of the groups are placed last.
+    pub port_order: String,
+    /// The casing of keywords when normalizing the case of a project
+    pub keyword_case: Casing,
+    /// The casing of constants and generics when normalizing the case of a project
+    pub constant_case: Casing,
+    /// The casing of all other identifiers when normalizing the case of a project
+    pub identifier_case: Casing,
+}
+
+/// The canonical casing of words
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Casing {
+    /// Keep the casing as it is written
+    Keep,
+    Lower,
+    Upper,
+    /// Use the casing of the declaration of a named entity
+    Declaration,
+}
+
+impl Casing {
+    fn from_str(value: &str) -> Result<Casing, String> {
+        match value {
+            "keep" => Ok(Casing::Keep),
+            "lower" => Ok(Casing::Lower),
+            "upper" => Ok(Casing::Upper),
+            "declaration" => Ok(Casing::Declaration),
+            _ => Err(format!("'{value}' is not a valid casing")),
+        }
+    }
+}
+
+/// A naming convention that the names of a class of named entities must follow.
+/// Prefixes and suffixes are matched case-insensitively, as VHDL identifiers are.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct NamingRule {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    /// A regular expression that must match the name
+    pub regex: Option<String>,
+}
+
+/// The classes of named entities that naming conventions can be defined for
+pub(crate) const NAMING_CLASSES: [&str; 13] = [
+    "signal",
+    "constant",
+    "generic",
+    "port",
+    "port_in",
+    "port_out",
+    "port_inout",
+    "port_buffer",
+    "port_linkage",
+    "port_view",
+    "type",
+    "instance",
+    "process",
+];
+
+impl NamingRule {
+    fn from_toml(class: &str, rule: &Table) -> Result<NamingRule, String> {
+        let mut result = NamingRule::default();
+        for (name, value) in rule {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("naming.{class}.{name} must be a string"))?
+                .to_owned();
+            match name.as_str() {
+                "prefix" => result.prefix = Some(value),
+                "suffix" => result.suffix = Some(value),
+                "regex" => {
+                    if let Err(err) = regex::Regex::new(&value) {
+                        return Err(format!(
+                            "naming.{class}.regex is not a valid regular expression: {err}"
+                        ));
+                    }
+                    result.regex = Some(value)
+                }
+                _ => return Err(format!("'{name}' is not a valid naming rule setting")),
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// The groups that ports can be ordered by.
+/// Clocks and resets are input ports that are recognized by their name.
+pub(crate) const PORT_GROUPS: [&str; 7] =
+    ["clock", "reset", "in", "inout", "buffer", "out", "linkage"];
+
+impl Default for CodeActionConfig {
+    fn default() -> Self {
+        CodeActionConfig {
+            architecture_name: "rtl".to_owned(),
+            architecture_file_name: "{entity}_{architecture}".to_owned(),
+            port_order: "clock, reset, in, inout, buffer, out, linkage".to_owned(),
+            keyword_case: Casing::Keep,
+            constant_case: Casing::Declaration,
+            identifier_case: Casing::Declaration,
+        }
+    }
+}
+
+impl CodeActionConfig {
+    fn from_toml(code_actions: &Table) -> Result<CodeActionConfig, String> {
+        let mut config = CodeActionConfig::default();
+        for (name, value) in code_actions {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("code_actions.{name} must be a string"))?
+                .to_owned();
+            match name.as_str() {
+                "architecture_name" => config.architecture_name = value,
+                "architecture_file_name" => config.architecture_file_name = value,
+                "port_order" => {
+                    if let Some(group) = value
+                        .split(',')
+                        .map(|group| group.trim())
+                        .find(|group| !PORT_GROUPS.contains(group))
+                    {
+                        return Err(format!("'{group}' is not a valid port group"));
+                    }
+                    config.port_order = value
+                }
+                "keyword_case" => {
+                    config.keyword_case = Casing::from_str(&value)?;
+                    if config.keyword_case == Casing::Declaration {
+                        return Err("Keywords cannot use the casing of a declaration".to_owned());
+                    }
+                }
+                "constant_case" => config.constant_case = Casing::from_str(&value)?,
+                "identifier_case" => config.identifier_case = Casing::from_str(&value)?,
+                _ => return Err(format!("'{name}' is not a valid code action setting")),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Reads the formatting style of the `[format]` section
+fn format_options_from_toml(format: &Table) -> Result<FormatOptions, String> {
+    let mut options = FormatOptions::default();
+    for (name, value) in format {
+        match name.as_str() {
+            "indent_width" | "max_line_width" => {
+                let width = value
+                    .as_integer()
+                    .and_then(|width| usize::try_from(width).ok())
+                    .filter(|width| *width > 0)
+                    .ok_or_else(|| format!("format.{name} must be a positive integer"))?;
+                if name == "indent_width" {
+                    options.indent_width = width;
+                } else {
+                    options.max_line_width = Some(width);
+                }
+            }
+            "use_tabs" | "align_assignments" | "align_port_maps" => {
+                let value = value
+                    .as_bool()
+                    .ok_or_else(|| format!("format.{name} must be a boolean"))?;
+                match name.as_str() {
+                    "use_tabs" => options.use_tabs = value,
+                    "align_assignments" => options.align_assignments = value,
+                    _ => options.align_port_maps = value,
+                }
+            }
+            "keyword_case" | "identifier_case" => {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| format!("format.{name} must be a string"))?;
+                let casing = Casing::from_str(value)?;
+                if casing == Casing::Declaration {
+                    return Err(format!(
+                        "format.{name} cannot use the casing of a declaration"
+                    ));
+                }
+                if name == "keyword_case" {
+                    options.keyword_case = casing;
+                } else {
+                    options.identifier_case = casing;
+                }
+            }
+            _ => return Err(format!("'{name}' is not a valid format setting")),
+        }
+    }
+    Ok(options)
+}
+
+/// A file name or glob pattern of a library
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct FilePattern {
+    pattern: String,
+    // Overrides the encoding of the library
+    encoding: Option<Encoding>,
+}
+
+impl From<String> for FilePattern {
+    fn from(pattern: String) -> Self {
+        FilePattern {
+            pattern,
+            encoding: None,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct LibraryConfig {
     name: String,
-    patterns: Vec<String>,
+    patterns: Vec<FilePattern>,
+    /// Patterns that replace `patterns` when analyzing a specific standard
+    standard_patterns: Vec<(VHDLStandard, Vec<FilePattern>)>,
     pub(crate) is_third_party: bool,
+    encoding: Encoding,
 }
 
 impl LibraryConfig {
@@ -42,8 +324,31 @@ impl LibraryConfig {
     /// Only include files that exists
     /// Files that do not exist produce a warning message
     pub fn file_names(&self, messages: &mut dyn MessageHandler) -> Vec<PathBuf> {
+        self.file_names_and_encodings(VHDLStandard::default(), messages)
+            .into_iter()
+            .map(|(file_name, _)| file_name)
+            .collect()
+    }
+
+    /// The file patterns of the library when analyzing the given standard
+    fn patterns(&self, standard: VHDLStandard) -> &[FilePattern] {
+        self.standard_patterns
+            .iter()
+            .find(|(pattern_standard, _)| *pattern_standard == standard)
+            .map_or(&self.patterns, |(_, patterns)| patterns)
+    }
+
+    /// Return the file names together with the encoding used to read them
+    /// when analyzing the given standard.
+    /// When a file matches several patterns, the first pattern determines the encoding.
+    pub fn file_names_and_encodings(
+        &self,
+        standard: VHDLStandard,
+        messages: &mut dyn MessageHandler,
+    ) -> Vec<(PathBuf, Encoding)> {
         let mut result = Vec::new();
-        for pattern in self.patterns.iter() {
+        for FilePattern { pattern, encoding } in self.patterns(standard).iter() {
+            let encoding = encoding.unwrap_or(self.encoding);
             let stripped_pattern = if cfg!(windows) {
                 pattern.strip_prefix("\\\\?\\").unwrap_or(pattern.as_str())
             } else {
@@ -54,7 +359,7 @@ impl LibraryConfig {
                 let file_path = PathBuf::from(pattern);
 
                 if file_path.exists() {
-                    result.push(file_path);
+                    result.push((file_path, encoding));
                 } else {
                     messages.push(Message::warning(format! {"File {pattern} does not exist"}));
                 }
@@ -67,7 +372,7 @@ impl LibraryConfig {
                             empty_pattern = false;
                             match file_path_or_error {
                                 Ok(file_path) => {
-                                    result.push(file_path);
+                                    result.push((file_path, encoding));
                                 }
                                 Err(err) => {
                                     messages.push(Message::error(err.to_string()));
@@ -88,13 +393,21 @@ impl LibraryConfig {
             }
         }
         // Remove duplicate file names from the result
-        result.into_iter().unique().collect()
+        result
+            .into_iter()
+            .unique_by(|(file_name, _)| file_name.clone())
+            .collect()
     }
 
     /// Returns the name of the library
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
+
+    /// Returns whether the library contains third-party code
+    pub fn is_third_party(&self) -> bool {
+        self.is_third_party
+    }
 }
 
 impl Config {
@@ -110,12 +423,32 @@ impl Config {
             VHDLStandard::default()
         };
 
-        let libs = config
-            .get("libraries")
-            .ok_or("missing field libraries")?
-            .as_table()
-            .ok_or("libraries must be a table")?;
+        let profiles = if let Some(profiles) = config.get("profiles") {
+            profiles
+                .as_table()
+                .ok_or("profiles must be a table")?
+                .clone()
+        } else {
+            Table::new()
+        };
+
+        for (name, directory) in profiles.iter() {
+            let directory = directory
+                .as_str()
+                .ok_or_else(|| format!("the directory of profile {name} must be a string"))?;
+            let directory = substitute_environment_variables(directory, &subst::Env)?;
+            for library in profiles::profile_libraries(name, &parent.join(directory), standard)? {
+                libraries.insert(library.name.clone(), library);
+            }
+        }
+
+        let libs = match config.get("libraries") {
+            Some(libs) => libs.as_table().ok_or("libraries must be a table")?.clone(),
+            None if !profiles.is_empty() => Table::new(),
+            None => return Err("missing field libraries".to_owned()),
+        };
 
+        // Libraries that are defined explicitly take precedence over libraries of profiles
         for (name, lib) in libs.iter() {
             if name.to_lowercase() == "work" {
                 return Err(format!(
@@ -124,26 +457,27 @@ impl Config {
                 ));
             }
 
+            let encoding = match lib.get("encoding") {
+                Some(encoding) => parse_encoding(encoding)?,
+                None => Encoding::default(),
+            };
+
             let file_arr = lib
                 .get("files")
-                .ok_or_else(|| format!("missing field files for library {name}"))?
-                .as_array()
-                .ok_or_else(|| format!("files for library {name} is not array"))?;
-
-            let mut patterns = Vec::new();
-            for file in file_arr.iter() {
-                let file = file
-                    .as_str()
-                    .ok_or_else(|| format!("not a string {file}"))?;
-
-                let file = substitute_environment_variables(file, &subst::Env)?;
-
-                let path = parent.join(file);
-                let path = path
-                    .to_str()
-                    .ok_or_else(|| format!("Could not convert {path:?} to string"))?
-                    .to_owned();
-                patterns.push(path);
+                .ok_or_else(|| format!("missing field files for library {name}"))?;
+            let patterns = parse_file_patterns(name, "files", file_arr, parent)?;
+
+            // Files of a specific standard, such as 'files_1993'
+            let mut standard_patterns = Vec::new();
+            for (key, file_arr) in lib.as_table().into_iter().flatten() {
+                let Some(standard) = key.strip_prefix("files_") else {
+                    continue;
+                };
+                let standard = VHDLStandard::try_from(standard).map_err(|_| {
+                    format!("Unsupported standard '{standard}' of {key} for library {name}")
+                })?;
+                standard_patterns
+                    .push((standard, parse_file_patterns(name, key, file_arr, parent)?));
             }
 
             let mut is_third_party = false;
@@ -162,7 +496,9 @@ impl Config {
                 LibraryConfig {
                     name: name.to_owned(),
                     patterns,
+                    standard_patterns,
                     is_third_party,
+                    encoding,
                 },
             );
         }
@@ -173,10 +509,69 @@ impl Config {
             SeverityMap::default()
         };
 
+        let code_actions = if let Some(code_actions) = config.get("code_actions") {
+            CodeActionConfig::from_toml(
+                code_actions
+                    .as_table()
+                    .ok_or("code_actions must be a table")?,
+            )?
+        } else {
+            CodeActionConfig::default()
+        };
+
+        let mut naming = FnvHashMap::default();
+        if let Some(rules) = config.get("naming") {
+            for (class, rule) in rules.as_table().ok_or("naming must be a table")? {
+                if !NAMING_CLASSES.contains(&class.as_str()) {
+                    return Err(format!("'{class}' is not a valid naming rule"));
+                }
+                let rule = rule
+                    .as_table()
+                    .ok_or_else(|| format!("naming.{class} must be a table"))?;
+                naming.insert(class.clone(), NamingRule::from_toml(class, rule)?);
+            }
+        }
+
+        let mut conditional_analysis = FnvHashMap::default();
+        if let Some(identifiers) = config.get("conditional_analysis") {
+            for (name, value) in identifiers
+                .as_table()
+                .ok_or("conditional_analysis must be a table")?
+            {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| format!("conditional_analysis.{name} must be a string"))?;
+                conditional_analysis.insert(name.to_ascii_uppercase(), value.to_owned());
+            }
+        }
+
+        let elaboration = if let Some(elaboration) = config.get("elaboration") {
+            Some(ElaborationConfig::from_toml(
+                elaboration
+                    .as_table()
+                    .ok_or("elaboration must be a table")?,
+            )?)
+        } else {
+            None
+        };
+
+        let format = if let Some(format) = config.get("format") {
+            Some(format_options_from_toml(
+                format.as_table().ok_or("format must be a table")?,
+            )?)
+        } else {
+            None
+        };
+
         Ok(Config {
             libraries,
             severities,
             standard,
+            code_actions,
+            format,
+            naming,
+            conditional_analysis,
+            elaboration,
         })
     }
 
@@ -187,9 +582,11 @@ impl Config {
             let error_code = ErrorCode::try_from(name.as_str())
                 .map_err(|_| format!("'{name}' is not a valid error code"))?;
             match severity {
+                Value::String(severity) if severity == "off" => severities[error_code] = None,
                 Value::String(severity) => {
-                    let severity = Severity::try_from(severity.as_str())
-                        .map_err(|_| format!("'{severity}' is not a valid severity level"))?;
+                    let severity = Severity::try_from(severity.as_str()).map_err(|_| {
+                        format!("'{severity}' is not a valid severity level, expected 'error', 'warning', 'info', 'hint' or 'off'")
+                    })?;
                     severities[error_code] = Some(severity);
                 }
                 Value::Boolean(should_show) => {
@@ -239,6 +636,16 @@ impl Config {
             }
         }
         self.severities = config.severities;
+        self.code_actions = config.code_actions.clone();
+        if config.format.is_some() {
+            self.format = config.format.clone();
+        }
+        self.naming = config.naming.clone();
+        self.conditional_analysis
+            .extend(config.conditional_analysis.clone());
+        if config.elaboration.is_some() {
+            self.elaboration = config.elaboration.clone();
+        }
     }
 
     /// Load configuration file from installation folder
@@ -341,6 +748,38 @@ impl Config {
         &self.severities
     }
 
+    pub fn code_actions(&self) -> &CodeActionConfig {
+        &self.code_actions
+    }
+
+    /// The naming conventions of the `[naming]` section, by the class of named entity
+    pub fn naming_rules(&self) -> &FnvHashMap<String, NamingRule> {
+        &self.naming
+    }
+
+    /// The formatting style of the `[format]` section, or `None` when no style is configured
+    pub fn format(&self) -> Option<FormatOptions> {
+        self.format.clone().map(|format| FormatOptions {
+            standard: self.standard,
+            ..format
+        })
+    }
+
+    /// The identifiers of conditional analysis directives with the values of the
+    /// `[conditional_analysis]` section, which may override the predefined identifiers
+    pub fn conditional_analysis(&self) -> ConditionalAnalysis {
+        let mut conditional_analysis = ConditionalAnalysis::new(self.standard);
+        for (name, value) in self.conditional_analysis.iter() {
+            conditional_analysis.set(name, value);
+        }
+        conditional_analysis
+    }
+
+    /// The top-level unit of the `[elaboration]` section, or `None` when no top-level unit is configured
+    pub fn elaboration(&self) -> Option<&ElaborationConfig> {
+        self.elaboration.as_ref()
+    }
+
     /// The VHDL standard to use if no more specific config is present.
     /// By default, VHDL 2008 is assumed
     pub fn standard(&self) -> VHDLStandard {
@@ -348,6 +787,54 @@ impl Config {
     }
 }
 
+/// Parses the files of a library, where each file is either a name
+/// or a table with the name and the encoding of the file
+fn parse_file_patterns(
+    name: &str,
+    key: &str,
+    file_arr: &Value,
+    parent: &Path,
+) -> Result<Vec<FilePattern>, String> {
+    let file_arr = file_arr
+        .as_array()
+        .ok_or_else(|| format!("{key} for library {name} is not array"))?;
+
+    let mut patterns = Vec::new();
+    for file in file_arr.iter() {
+        let (file, file_encoding) = match file {
+            Value::String(file) => (file.as_str(), None),
+            Value::Table(table) => {
+                let path = table
+                    .get("path")
+                    .ok_or_else(|| format!("missing field path for file {file}"))?
+                    .as_str()
+                    .ok_or_else(|| format!("not a string {file}"))?;
+                let encoding = table.get("encoding").map(parse_encoding).transpose()?;
+                (path, encoding)
+            }
+            _ => return Err(format!("not a string {file}")),
+        };
+
+        let file = substitute_environment_variables(file, &subst::Env)?;
+
+        let path = parent.join(file);
+        let path = path
+            .to_str()
+            .ok_or_else(|| format!("Could not convert {path:?} to string"))?
+            .to_owned();
+        patterns.push(FilePattern {
+            pattern: path,
+            encoding: file_encoding,
+        });
+    }
+    Ok(patterns)
+}
+
+fn parse_encoding(encoding: &Value) -> Result<Encoding, String> {
+    let encoding = encoding.as_str().ok_or("encoding must be a string")?;
+    Encoding::try_from(encoding).map_err(|_| format!("Unsupported encoding '{encoding}'"))
+}
+
 fn substitute_environment_variables<'a, M>(s: &str, map: &'a M) -> Result<String, String>
 where
     M: VariableMap<'a> + ?Sized,
@@ -464,6 +951,8 @@ lib1.files = [
 [lint]
 unused = 'error'
 duplicate = false
+missing_sensitivity = 'off'
+naming_convention = 'info'
 ",
                 absolute_vhd.to_str().unwrap()
             ),
@@ -489,6 +978,8 @@ duplicate = false
         let mut expected_map = SeverityMap::default();
         expected_map[ErrorCode::Unused] = Some(Severity::Error);
         expected_map[ErrorCode::Duplicate] = None;
+        expected_map[ErrorCode::MissingSensitivity] = None;
+        expected_map[ErrorCode::NamingConvention] = Some(Severity::Info);
         assert_eq!(config.severities, expected_map)
     }
 
@@ -602,6 +1093,114 @@ lib.files = [
         assert_eq!(messages, vec![]);
     }
 
+    #[test]
+    fn test_files_of_a_standard() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = ['*.vhd']
+lib.files_1993 = ['pkg.vhd']
+",
+            parent,
+        )
+        .unwrap();
+
+        let pkg = touch(parent, "pkg.vhd");
+        let pkg2008 = touch(parent, "pkg2008.vhd");
+
+        let mut messages = vec![];
+        let library = config.get_library("lib").unwrap();
+        let file_names = |standard| -> Vec<PathBuf> {
+            library
+                .file_names_and_encodings(standard, &mut vec![])
+                .into_iter()
+                .map(|(file_name, _)| file_name)
+                .collect()
+        };
+        assert_files_eq(
+            &file_names(VHDLStandard::VHDL1993),
+            std::slice::from_ref(&pkg),
+        );
+        assert_files_eq(
+            &file_names(VHDLStandard::VHDL2008),
+            &[pkg.clone(), pkg2008.clone()],
+        );
+        assert_files_eq(&library.file_names(&mut messages), &[pkg, pkg2008]);
+        assert_eq!(messages, vec![]);
+
+        assert_eq!(
+            Config::from_str("[libraries]\nlib.files = []\nlib.files_2000 = []", parent),
+            Err("Unsupported standard '2000' of files_2000 for library lib".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_conditional_analysis_identifiers() {
+        let parent = Path::new("");
+        let config = Config::from_str(
+            "
+standard = '2019'
+
+[libraries]
+
+[conditional_analysis]
+tool_type = 'SYNTHESIS'
+MY_FLAG = 'on'
+",
+            parent,
+        )
+        .unwrap();
+        let conditional_analysis = config.conditional_analysis();
+        assert_eq!(conditional_analysis.get("TOOL_TYPE"), Some("SYNTHESIS"));
+        assert_eq!(conditional_analysis.get("my_flag"), Some("on"));
+        assert_eq!(conditional_analysis.get("VHDL_VERSION"), Some("2019"));
+
+        assert_eq!(
+            Config::from_str("[libraries]\n[conditional_analysis]\nTOOL_TYPE = 1", parent),
+            Err("conditional_analysis.TOOL_TYPE must be a string".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_elaboration() {
+        let parent = Path::new("");
+        let config = Config::from_str(
+            "
+[libraries]
+
+[elaboration]
+top = 'lib.top'
+generics = { WIDTH = 16, enable = true }
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.elaboration(),
+            Some(&ElaborationConfig {
+                top: "lib.top".to_owned(),
+                generics: FnvHashMap::from_iter([
+                    ("width".to_owned(), 16),
+                    ("enable".to_owned(), 1)
+                ]),
+            })
+        );
+
+        assert_eq!(
+            Config::from_str(
+                "[libraries]\n[elaboration]\ntop = 'top'\ngenerics = { name = 'value' }",
+                parent
+            ),
+            Err("elaboration.generics.name must be an integer or a boolean".to_owned())
+        );
+        assert_eq!(
+            Config::from_str("[libraries]\n[elaboration]\ngenerics = {}", parent),
+            Err("missing field top for elaboration".to_owned())
+        );
+    }
+
     #[test]
     fn test_file_wildcard_pattern_removes_duplicates() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -653,6 +1252,197 @@ lib.files = [
         );
     }
 
+    #[test]
+    fn code_action_config() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+
+[code_actions]
+architecture_name = 'behav'
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.code_actions(),
+            &CodeActionConfig {
+                architecture_name: "behav".to_owned(),
+                architecture_file_name: "{entity}_{architecture}".to_owned(),
+                port_order: "clock, reset, in, inout, buffer, out, linkage".to_owned(),
+                keyword_case: Casing::Keep,
+                constant_case: Casing::Declaration,
+                identifier_case: Casing::Declaration,
+            }
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+
+[code_actions]
+keyword_case = 'lower'
+constant_case = 'upper'
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(config.code_actions().keyword_case, Casing::Lower);
+        assert_eq!(config.code_actions().constant_case, Casing::Upper);
+
+        let config = Config::from_str(
+            "
+[libraries]
+
+[code_actions]
+identifier_case = 'camel'
+",
+            parent,
+        );
+        assert_eq!(
+            config.expect_err("Expected erroneous config"),
+            "'camel' is not a valid casing"
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+
+[code_actions]
+port_order = 'clock, in, clk'
+",
+            parent,
+        );
+        assert_eq!(
+            config.expect_err("Expected erroneous config"),
+            "'clk' is not a valid port group"
+        );
+
+        let config = Config::from_str(
+            "
+[libraries]
+
+[code_actions]
+architecture = 'behav'
+",
+            parent,
+        );
+        assert_eq!(
+            config.expect_err("Expected erroneous config"),
+            "'architecture' is not a valid code action setting"
+        );
+    }
+
+    #[test]
+    fn naming_config() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+
+[naming]
+signal = { suffix = '_s' }
+port_in.prefix = 'i_'
+process = { regex = '^p_[a-z_]+$' }
+",
+            parent,
+        )
+        .unwrap();
+        let rules = config.naming_rules();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(
+            rules["signal"],
+            NamingRule {
+                suffix: Some("_s".to_owned()),
+                ..NamingRule::default()
+            }
+        );
+        assert_eq!(rules["port_in"].prefix.as_deref(), Some("i_"));
+        assert_eq!(rules["process"].regex.as_deref(), Some("^p_[a-z_]+$"));
+
+        for (setting, error) in [
+            (
+                "variable = { suffix = '_v' }",
+                "'variable' is not a valid naming rule",
+            ),
+            ("signal = '_s'", "naming.signal must be a table"),
+            (
+                "signal = { ending = '_s' }",
+                "'ending' is not a valid naming rule setting",
+            ),
+        ] {
+            let config = Config::from_str(&format!("[libraries]\n[naming]\n{setting}"), parent);
+            assert_eq!(config.expect_err("Expected erroneous config"), error);
+        }
+        let config = Config::from_str("[libraries]\n[naming]\ntype.regex = '('", parent);
+        assert!(config
+            .expect_err("Expected erroneous config")
+            .starts_with("naming.type.regex is not a valid regular expression"));
+    }
+
+    #[test]
+    fn format_config() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(config.format(), None);
+
+        let config = Config::from_str(
+            "
+standard = '1993'
+
+[libraries]
+
+[format]
+indent_width = 2
+use_tabs = true
+keyword_case = 'upper'
+identifier_case = 'lower'
+align_assignments = true
+align_port_maps = true
+max_line_width = 100
+",
+            parent,
+        )
+        .unwrap();
+        assert_eq!(
+            config.format(),
+            Some(FormatOptions {
+                indent_width: 2,
+                use_tabs: true,
+                keyword_case: Casing::Upper,
+                identifier_case: Casing::Lower,
+                align_assignments: true,
+                align_port_maps: true,
+                max_line_width: Some(100),
+                standard: VHDLStandard::VHDL1993,
+            })
+        );
+
+        for (setting, error) in [
+            (
+                "indent_width = 0",
+                "format.indent_width must be a positive integer",
+            ),
+            ("use_tabs = 'yes'", "format.use_tabs must be a boolean"),
+            ("keyword_case = 'camel'", "'camel' is not a valid casing"),
+            (
+                "identifier_case = 'declaration'",
+                "format.identifier_case cannot use the casing of a declaration",
+            ),
+            ("width = 80", "'width' is not a valid format setting"),
+        ] {
+            let config = Config::from_str(&format!("[libraries]\n[format]\n{setting}"), parent);
+            assert_eq!(config.expect_err("Expected erroneous config"), error);
+        }
+    }
+
     #[test]
     fn the_work_library_is_an_illegal_library() {
         let parent = Path::new("parent_folder");
@@ -668,6 +1458,149 @@ work.files = [
         assert_eq!(config.expect_err("Expected erroneous config"), "The 'work' library is not a valid library.\nHint: To use a library that contains all files, use a common name for all libraries, i.e., 'defaultlib'")
     }
 
+    #[test]
+    fn encodings_of_libraries_and_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        let legacy = touch(parent, "legacy.vhd");
+        let latin1 = touch(parent, "latin1.vhd");
+        let modern = touch(parent, "modern.vhd");
+
+        let config = Config::from_str(
+            "
+[libraries]
+lib.files = ['legacy.vhd', { path = 'latin1.vhd', encoding = 'latin-1' }]
+lib.encoding = 'windows-1252'
+lib2.files = [{ path = 'modern.vhd', encoding = 'utf-8' }]
+",
+            parent,
+        )
+        .unwrap();
+
+        let mut messages = vec![];
+        let files = config
+            .get_library("lib")
+            .unwrap()
+            .file_names_and_encodings(VHDLStandard::default(), &mut messages);
+        assert_eq!(
+            files,
+            vec![(legacy, Encoding::Windows1252), (latin1, Encoding::Latin1)]
+        );
+        let files = config
+            .get_library("lib2")
+            .unwrap()
+            .file_names_and_encodings(VHDLStandard::default(), &mut messages);
+        assert_eq!(files, vec![(modern, Encoding::Utf8)]);
+        assert_eq!(messages, vec![]);
+
+        assert_eq!(
+            Config::from_str(
+                "
+[libraries]
+lib.files = []
+lib.encoding = 'utf-16'
+",
+                parent,
+            ),
+            Err("Unsupported encoding 'utf-16'".to_owned())
+        );
+    }
+
+    #[test]
+    fn profiles_define_third_party_libraries() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        for directory in [
+            "OsvvmLibraries/osvvm",
+            "OsvvmLibraries/Common/src",
+            "UVVM/uvvm_util/src",
+            "UVVM/uvvm_vvc_framework/src_target_dependent",
+            "UVVM/bitvis_vip_uart/src",
+        ] {
+            std::fs::create_dir_all(parent.join(directory)).unwrap();
+        }
+        let random_pkg = touch(parent, "OsvvmLibraries/osvvm/RandomPkg.vhd");
+        let common_pkg = touch(parent, "OsvvmLibraries/Common/src/ModelParametersPkg.vhd");
+        let uart_vvc = touch(parent, "UVVM/bitvis_vip_uart/src/uart_vvc.vhd");
+        let td_target = touch(
+            parent,
+            "UVVM/uvvm_vvc_framework/src_target_dependent/td_target_support_pkg.vhd",
+        );
+
+        let config = Config::from_str(
+            "
+[profiles]
+osvvm = 'OsvvmLibraries'
+uvvm = 'UVVM'
+
+[libraries]
+osvvm_common.files = ['OsvvmLibraries/Common/src/*.vhd']
+",
+            parent,
+        )
+        .unwrap();
+        let mut libraries: Vec<&str> = config.iter_libraries().map(|lib| lib.name()).collect();
+        libraries.sort_unstable();
+        assert_eq!(
+            libraries,
+            &[
+                "bitvis_vip_uart",
+                "osvvm",
+                "osvvm_common",
+                "uvvm_util",
+                "uvvm_vvc_framework"
+            ]
+        );
+
+        let osvvm = config.get_library("osvvm").unwrap();
+        assert!(osvvm.is_third_party);
+        let mut messages = vec![];
+        assert_files_eq(&osvvm.file_names(&mut messages), &[random_pkg]);
+        assert_files_eq(
+            &config
+                .get_library("bitvis_vip_uart")
+                .unwrap()
+                .file_names(&mut messages),
+            &[uart_vvc, td_target],
+        );
+        assert_eq!(messages, vec![]);
+
+        // The explicit definition takes precedence
+        let osvvm_common = config.get_library("osvvm_common").unwrap();
+        assert!(!osvvm_common.is_third_party);
+        assert_files_eq(&osvvm_common.file_names(&mut messages), &[common_pkg]);
+    }
+
+    #[test]
+    fn erroneous_profiles() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let parent = tempdir.path();
+        std::fs::create_dir_all(parent.join("OsvvmLibraries/osvvm")).unwrap();
+
+        assert_eq!(
+            Config::from_str("profiles.vunit = 'vunit'", parent),
+            Err("'vunit' is not a valid profile. Valid profiles are: osvvm, uvvm".to_owned())
+        );
+        assert_eq!(
+            Config::from_str(
+                "
+standard = '1993'
+profiles.osvvm = 'OsvvmLibraries'
+",
+                parent
+            ),
+            Err("The osvvm profile requires VHDL-2008 or newer".to_owned())
+        );
+        assert_eq!(
+            Config::from_str("profiles.uvvm = 'OsvvmLibraries'", parent),
+            Err(format!(
+                "The directory '{}' does not contain any library of the uvvm profile",
+                parent.join("OsvvmLibraries").to_string_lossy()
+            ))
+        );
+        assert!(Config::from_str("profiles.osvvm = 'OsvvmLibraries'", parent).is_ok());
+    }
+
     #[test]
     #[cfg(unix)]
     fn substitute() {
@@ -0,0 +1,196 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Library profiles of verification frameworks that consist of many libraries and files.
+//! A profile is selected in the `profiles` table with the root directory of the framework.
+
+use super::{FilePattern, LibraryConfig};
+use crate::data::Encoding;
+use crate::standard::VHDLStandard;
+use std::path::Path;
+
+/// A library of a framework
+struct ProfileLibrary {
+    /// The name of the library. When the directory contains a wildcard,
+    /// a `*` takes the name of the matched directory.
+    name: &'static str,
+    /// The directory of the library relative to the root of the framework
+    directory: &'static str,
+    /// File patterns relative to the root of the framework.
+    /// `{directory}` is replaced by the matched directory.
+    files: &'static [&'static str],
+}
+
+struct LibraryProfile {
+    name: &'static str,
+    /// The oldest standard supported by the framework
+    standard: VHDLStandard,
+    libraries: &'static [ProfileLibrary],
+}
+
+/// The layout of <https://github.com/OSVVM/OsvvmLibraries>
+const OSVVM: LibraryProfile = LibraryProfile {
+    name: "osvvm",
+    standard: VHDLStandard::VHDL2008,
+    libraries: &[
+        ProfileLibrary {
+            name: "osvvm",
+            directory: "osvvm",
+            files: &["{directory}/*.vhd"],
+        },
+        ProfileLibrary {
+            name: "osvvm_common",
+            directory: "Common",
+            files: &["{directory}/src/*.vhd"],
+        },
+        ProfileLibrary {
+            name: "osvvm_uart",
+            directory: "UART",
+            files: &["{directory}/src/*.vhd"],
+        },
+        ProfileLibrary {
+            name: "osvvm_axi4",
+            directory: "AXI4",
+            files: &[
+                "{directory}/common/src/*.vhd",
+                "{directory}/Axi4/src/*.vhd",
+                "{directory}/Axi4Lite/src/*.vhd",
+                "{directory}/AxiStream/src/*.vhd",
+            ],
+        },
+        ProfileLibrary {
+            name: "osvvm_ethernet",
+            directory: "Ethernet",
+            files: &["{directory}/src/*.vhd"],
+        },
+        ProfileLibrary {
+            name: "osvvm_dpram",
+            directory: "DpRam",
+            files: &["{directory}/src/*.vhd"],
+        },
+    ],
+};
+
+/// The layout of <https://github.com/UVVM/UVVM>.
+/// The target dependent files of the VVC framework are compiled into every VIP library.
+const UVVM: LibraryProfile = LibraryProfile {
+    name: "uvvm",
+    standard: VHDLStandard::VHDL2008,
+    libraries: &[
+        ProfileLibrary {
+            name: "uvvm_util",
+            directory: "uvvm_util",
+            files: &["{directory}/src/*.vhd"],
+        },
+        ProfileLibrary {
+            name: "uvvm_vvc_framework",
+            directory: "uvvm_vvc_framework",
+            files: &["{directory}/src/*.vhd"],
+        },
+        ProfileLibrary {
+            name: "bitvis_vip_*",
+            directory: "bitvis_vip_*",
+            files: &[
+                "{directory}/src/*.vhd",
+                "uvvm_vvc_framework/src_target_dependent/*.vhd",
+            ],
+        },
+    ],
+};
+
+const PROFILES: [LibraryProfile; 2] = [OSVVM, UVVM];
+
+/// Returns the name of a profile library for a matched directory
+fn library_name(library: &ProfileLibrary, directory: &Path) -> Option<String> {
+    if !library.name.contains('*') {
+        return Some(library.name.to_owned());
+    }
+    let directory_name = directory.file_name()?.to_str()?;
+    let (prefix, suffix) = library.directory.split_once('*')?;
+    let matched = directory_name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    Some(library.name.replace('*', matched).to_lowercase())
+}
+
+fn to_pattern(path: &Path) -> Result<String, String> {
+    path.to_str()
+        .map(|path| path.to_owned())
+        .ok_or_else(|| format!("Could not convert {path:?} to string"))
+}
+
+/// Returns the third-party libraries of the profile with the given name
+/// for a framework located in `root`.
+/// Libraries whose directory does not exist, e.g., because they were added
+/// in a newer version of the framework, are skipped.
+pub(super) fn profile_libraries(
+    name: &str,
+    root: &Path,
+    standard: VHDLStandard,
+) -> Result<Vec<LibraryConfig>, String> {
+    let profile = PROFILES
+        .iter()
+        .find(|profile| profile.name == name.to_lowercase())
+        .ok_or_else(|| {
+            format!(
+                "'{name}' is not a valid profile. Valid profiles are: {}",
+                PROFILES.map(|profile| profile.name).join(", ")
+            )
+        })?;
+    if standard < profile.standard {
+        return Err(format!(
+            "The {} profile requires VHDL-{} or newer",
+            profile.name,
+            profile.standard.as_ref()
+        ));
+    }
+    if !root.is_dir() {
+        return Err(format!(
+            "The directory '{}' of the {} profile does not exist",
+            root.to_string_lossy(),
+            profile.name
+        ));
+    }
+
+    let mut libraries = Vec::new();
+    for library in profile.libraries {
+        let directory_pattern = to_pattern(&root.join(library.directory))?;
+        let directories = glob::glob(&directory_pattern)
+            .map_err(|err| format!("Invalid pattern '{directory_pattern}' {err}"))?;
+        for directory in directories.flatten().filter(|path| path.is_dir()) {
+            let Some(name) = library_name(library, &directory) else {
+                continue;
+            };
+            let directory = directory
+                .strip_prefix(root)
+                .map_err(|err| err.to_string())?
+                .to_str()
+                .ok_or_else(|| format!("Could not convert {directory:?} to string"))?
+                .to_owned();
+            let patterns = library
+                .files
+                .iter()
+                .map(|pattern| {
+                    to_pattern(&root.join(pattern.replace("{directory}", &directory)))
+                        .map(FilePattern::from)
+                })
+                .collect::<Result<_, _>>()?;
+            libraries.push(LibraryConfig {
+                name,
+                patterns,
+                standard_patterns: Vec::new(),
+                is_third_party: true,
+                encoding: Encoding::default(),
+            });
+        }
+    }
+    if libraries.is_empty() {
+        return Err(format!(
+            "The directory '{}' does not contain any library of the {} profile",
+            root.to_string_lossy(),
+            profile.name
+        ));
+    }
+    Ok(libraries)
+}
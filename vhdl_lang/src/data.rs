@@ -7,6 +7,7 @@
 
 mod contents;
 mod diagnostic;
+mod encoding;
 pub mod error_codes;
 mod latin_1;
 mod message;
@@ -15,6 +16,7 @@ mod symbol_table;
 
 pub use contents::*;
 pub use diagnostic::*;
+pub use encoding::*;
 pub use error_codes::*;
 pub use latin_1::*;
 pub use message::*;
@@ -4,6 +4,7 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+use super::encoding::Encoding;
 use super::latin_1::{char_to_latin1, Latin1String, Utf8ToLatin1Error};
 use super::source::{Position, Range};
 use std::fs::File;
@@ -17,12 +18,14 @@ pub struct Contents {
 
 impl Contents {
     pub fn from_latin1_file(file_name: &Path) -> io::Result<Contents> {
+        Contents::from_file(file_name, Encoding::Latin1)
+    }
+
+    pub fn from_file(file_name: &Path, encoding: Encoding) -> io::Result<Contents> {
         let mut file = File::open(file_name)?;
         let mut bytes = Vec::new();
         file.read_to_end(&mut bytes)?;
-        Ok(Contents::from_str(
-            &Latin1String::from_vec(bytes).to_string(),
-        ))
+        Ok(Contents::from_str(&encoding.decode(&bytes)))
     }
 
     pub fn from_str(code: &str) -> Contents {
@@ -0,0 +1,140 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::latin_1::iso_8859_1_to_utf8;
+use std::fmt;
+
+/// The character encoding of a source file
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Encoding {
+    /// ISO-8859-1, the character set of VHDL
+    #[default]
+    Latin1,
+    /// Latin-1 with printable characters such as typographic quotes in place of the C1 controls
+    Windows1252,
+    Utf8,
+}
+
+/// The characters of Windows-1252 in the range 0x80 to 0x9F.
+/// Undefined bytes map to the C1 control character of the same value.
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+impl Encoding {
+    /// Decodes the contents of a file.
+    /// Invalid UTF-8 sequences are replaced by the replacement character.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Latin1 => iso_8859_1_to_utf8(bytes),
+            Encoding::Windows1252 => bytes
+                .iter()
+                .map(|&byte| match byte {
+                    0x80..=0x9F => WINDOWS_1252[(byte - 0x80) as usize],
+                    byte => byte as char,
+                })
+                .collect(),
+            Encoding::Utf8 => {
+                let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+        }
+    }
+
+    /// Encodes text to the contents of a file.
+    /// Characters that cannot be represented in the encoding are replaced by `?`.
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Encoding::Latin1 => text
+                .chars()
+                .map(|chr| u8::try_from(chr).unwrap_or(b'?'))
+                .collect(),
+            Encoding::Windows1252 => text
+                .chars()
+                .map(
+                    |chr| match WINDOWS_1252.iter().position(|&other| other == chr) {
+                        Some(idx) => 0x80 + idx as u8,
+                        None => u8::try_from(chr).unwrap_or(b'?'),
+                    },
+                )
+                .collect(),
+            Encoding::Utf8 => text.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Latin1 => write!(f, "latin-1"),
+            Encoding::Windows1252 => write!(f, "windows-1252"),
+            Encoding::Utf8 => write!(f, "utf-8"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Encoding {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value.to_lowercase().as_str() {
+            "latin-1" | "latin1" | "iso-8859-1" => Encoding::Latin1,
+            "windows-1252" | "cp1252" => Encoding::Windows1252,
+            "utf-8" | "utf8" => Encoding::Utf8,
+            _ => return Err(()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_accented_characters() {
+        assert_eq!(Encoding::Latin1.decode(b"-- caf\xe9"), "-- café");
+        assert_eq!(Encoding::Windows1252.decode(b"-- caf\xe9"), "-- café");
+        assert_eq!(Encoding::Utf8.decode(b"-- caf\xc3\xa9"), "-- café");
+    }
+
+    #[test]
+    fn decodes_windows_1252_characters() {
+        assert_eq!(
+            Encoding::Windows1252.decode(b"-- \x93quoted\x94 \x80"),
+            "-- \u{201C}quoted\u{201D} \u{20AC}"
+        );
+        assert_eq!(Encoding::Latin1.decode(b"\x93"), "\u{0093}");
+    }
+
+    #[test]
+    fn encodes_characters() {
+        assert_eq!(Encoding::Latin1.encode("-- café \u{201C}"), b"-- caf\xe9 ?");
+        assert_eq!(
+            Encoding::Windows1252.encode("-- café \u{201C}"),
+            b"-- caf\xe9 \x93"
+        );
+        assert_eq!(Encoding::Utf8.encode("-- café"), "-- café".as_bytes());
+    }
+
+    #[test]
+    fn skips_utf8_byte_order_mark() {
+        assert_eq!(Encoding::Utf8.decode(b"\xEF\xBB\xBFentity"), "entity");
+    }
+
+    #[test]
+    fn encoding_from_str() {
+        assert_eq!(Encoding::try_from("Latin-1"), Ok(Encoding::Latin1));
+        assert_eq!(
+            Encoding::try_from("windows-1252"),
+            Ok(Encoding::Windows1252)
+        );
+        assert_eq!(Encoding::try_from("UTF-8"), Ok(Encoding::Utf8));
+        assert_eq!(Encoding::try_from("utf-16"), Err(()));
+    }
+}
@@ -26,6 +26,30 @@ pub enum ErrorCode {
     /// ```
     SyntaxError,
 
+    /// A construct is not part of the configured VHDL standard, such as a
+    /// VHDL-2008 construct in a VHDL-1993 project or a reserved word of
+    /// VHDL-2008 that is used as an identifier
+    ///
+    /// # Example
+    /// With `standard = "1993"`
+    /// ```vhdl
+    /// process (all) is
+    /// begin
+    /// end process;
+    /// ```
+    IncompatibleStandard,
+
+    /// A region of encrypted source code, such as vendor IP, which is skipped
+    ///
+    /// # Example
+    /// ```vhdl
+    /// `protect begin_protected
+    /// `protect data_block
+    /// aGVsbG8gd29ybGQ=
+    /// `protect end_protected
+    /// ```
+    EncryptedRegion,
+
     // Analysis
     /// A circular dependency was found where one module depends on another module which
     /// (directly or indirectly) again depends on the first module.
@@ -255,6 +279,21 @@ pub enum ErrorCode {
     /// ```
     MissingProtectedBodyType,
 
+    /// A subprogram of a protected type that does not have a body in the protected type body
+    ///
+    /// # Example
+    ///
+    /// ```vhdl
+    /// type a1 is protected
+    ///     procedure proc;
+    /// end protected;
+    ///
+    /// type a1 is protected body
+    ///     -- No body for `proc`
+    /// end protected body;
+    /// ```
+    MissingSubprogramBody,
+
     /// A deferred constant is not allowed in the given context
     IllegalDeferredConstant,
 
@@ -410,6 +449,206 @@ pub enum ErrorCode {
     /// ```
     UnassociatedContext,
 
+    /// A signal that is read by a combinational process is missing from its sensitivity list
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process (a) is
+    /// begin
+    ///     c <= a and b;
+    /// end process;
+    /// ```
+    MissingSensitivity,
+
+    /// A signal in the sensitivity list of a process is never read by the process
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process (a, b) is
+    /// begin
+    ///     c <= a;
+    /// end process;
+    /// ```
+    UnnecessarySensitivity,
+
+    /// A signal of a combinational process is not assigned in all branches,
+    /// which infers a latch
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process (all) is
+    /// begin
+    ///     if en = '1' then
+    ///         q <= d;
+    ///     end if;
+    /// end process;
+    /// ```
+    InferredLatch,
+
+    /// The name of a declaration does not follow a naming rule of the `[naming]` section
+    /// of the configuration
+    ///
+    /// # Example
+    /// With the rule `signal = { suffix = "_s" }`
+    /// ```vhdl
+    /// signal counter : natural;
+    /// ```
+    NamingConvention,
+
+    /// A sequential statement follows a statement after which execution never continues,
+    /// such as a return statement or a wait statement that waits forever
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process is
+    /// begin
+    ///     wait;
+    ///     report "never reached";
+    /// end process;
+    /// ```
+    UnreachableCode,
+
+    /// A process has neither a sensitivity list nor a reachable wait statement,
+    /// so it loops forever without advancing the simulation time
+    ///
+    /// # Example
+    /// ```vhdl
+    /// process
+    /// begin
+    ///     count <= count + 1;
+    /// end process;
+    /// ```
+    MissingWait,
+
+    /// A signal of an unresolved type is driven by more than one concurrent statement,
+    /// which is an error during elaboration
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal s : bit;
+    /// -- ...
+    /// s <= a;
+    /// s <= b;
+    /// ```
+    MultipleDrivers,
+
+    /// The lengths of the target and the value of an assignment, or of a port and its actual,
+    /// are locally static and differ
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal a : bit_vector(7 downto 0);
+    /// signal b : bit_vector(15 downto 0);
+    /// -- ...
+    /// b <= a;
+    /// ```
+    WidthMismatch,
+
+    /// A declaration hides a declaration with the same designator of an enclosing
+    /// declarative region or of a package made visible by a use clause
+    ///
+    /// # Example
+    /// ```vhdl
+    /// use work.pkg.all; -- declares the constant 'width'
+    /// -- ...
+    /// architecture arch of ent is
+    ///     constant width : natural := 16;
+    /// begin
+    /// ```
+    Shadowing,
+
+    /// A signal of an architecture is read but never driven,
+    /// or an output port is never driven by the architecture
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal enable : std_logic;
+    /// -- ...
+    /// q <= d when enable = '1';
+    /// ```
+    UndrivenSignal,
+
+    /// An input port without a default value is left open at an instantiation
+    ///
+    /// # Example
+    /// ```vhdl
+    /// inst: entity work.sub
+    ///     port map (enable => open, q => q);
+    /// ```
+    UnconnectedInputPort,
+
+    /// An output port without a default value is omitted from the port map of an instantiation
+    ///
+    /// # Example
+    /// ```vhdl
+    /// inst: entity work.sub
+    ///     port map (d => d); -- the output port 'q' is omitted
+    /// ```
+    UnconnectedOutputPort,
+
+    /// A shared variable is not of a protected type, which is required since VHDL-2000
+    ///
+    /// # Example
+    /// ```vhdl
+    /// shared variable count : natural;
+    /// ```
+    UnprotectedSharedVariable,
+
+    /// A shared variable is not of a protected type when analyzing VHDL-1993,
+    /// which is not portable to newer standards
+    ///
+    /// # Example
+    /// ```vhdl
+    /// shared variable count : natural;
+    /// ```
+    SharedVariablePortability,
+
+    /// A value is covered by more than one choice of a case statement or a selected assignment
+    ///
+    /// # Example
+    /// ```vhdl
+    /// case value is
+    ///     when 0 to 3 => null;
+    ///     when 2 => null;
+    ///     when others => null;
+    /// end case;
+    /// ```
+    DuplicateChoice,
+
+    /// The choices of a case statement or a selected assignment without an `others` choice
+    /// do not cover all values of the case expression
+    ///
+    /// # Example
+    /// ```vhdl
+    /// signal value : natural range 0 to 3;
+    /// -- ...
+    /// case value is
+    ///     when 0 | 1 => null;
+    ///     when 3 => null;
+    /// end case;
+    /// ```
+    MissingChoice,
+
+    /// A branch of a generate statement is not elaborated by any instance
+    /// of the configured top-level unit, because its condition is always false
+    /// or its range is always null for the values of the generics
+    ///
+    /// # Example
+    /// ```vhdl
+    /// -- with the generic 'width' set to 8 in all instances
+    /// wide: if width > 16 generate
+    ///     -- ...
+    /// end generate;
+    /// ```
+    DeadGenerate,
+
+    // External tools
+    /// An error reported by an external tool, e.g., when elaborating the design using GHDL
+    ExternalToolError,
+
+    /// A warning or note reported by an external tool
+    ExternalToolWarning,
+
     // Misc
     /// An internal error that signifies that some precondition within vhdl_lang wasn't met.
     /// If an error with this error code occurs,
@@ -442,6 +681,7 @@ impl Default for SeverityMap {
         use Severity::*;
         let map = enum_map! {
             SyntaxError
+            | IncompatibleStandard
             | CircularDependency
             | InvalidFormal
             | InvalidFormalConversion
@@ -463,6 +703,7 @@ impl Default for SeverityMap {
             | Duplicate
             | ConflictingUseClause
             | MissingProtectedBodyType
+            | MissingSubprogramBody
             | IllegalDeferredConstant
             | SignatureMismatch
             | AmbiguousInstantiation
@@ -491,10 +732,28 @@ impl Default for SeverityMap {
             | UnexpectedSignature
             | MissingDeferredDeclaration
             | MissingFullTypeDeclaration
-            | InvalidCall => Some(Error),
+            | InvalidCall
+            | DuplicateChoice
+            | MissingChoice => Some(Error),
             Unused
             | UnnecessaryWorkLibrary
-            | UnassociatedContext => Some(Warning),
+            | UnassociatedContext
+            | MissingSensitivity
+            | UnnecessarySensitivity
+            | InferredLatch
+            | NamingConvention
+            | UnreachableCode
+            | MissingWait
+            | MultipleDrivers
+            | WidthMismatch
+            | Shadowing
+            | UndrivenSignal
+            | UnconnectedInputPort
+            | UnprotectedSharedVariable
+            | DeadGenerate => Some(Warning),
+            UnconnectedOutputPort | SharedVariablePortability | EncryptedRegion => Some(Info),
+            ExternalToolError => Some(Error),
+            ExternalToolWarning => Some(Warning),
             Internal => Some(Error),
             Related => Some(Hint)
         };
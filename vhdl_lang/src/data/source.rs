@@ -5,6 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::contents::Contents;
+use super::encoding::Encoding;
 use parking_lot::{RwLock, RwLockReadGuard};
 use std::cmp::{max, min};
 use std::collections::hash_map::DefaultHasher;
@@ -72,8 +73,8 @@ impl UniqueSource {
         }
     }
 
-    fn from_latin1_file(file_name: &Path) -> io::Result<Self> {
-        let contents = Contents::from_latin1_file(file_name)?;
+    fn from_file(file_name: &Path, encoding: Encoding) -> io::Result<Self> {
+        let contents = Contents::from_file(file_name, encoding)?;
         Ok(Self {
             file_id: FileId::new(file_name),
             contents: RwLock::new(contents),
@@ -142,7 +143,14 @@ impl Source {
     }
 
     pub fn from_latin1_file(file_name: &Path) -> io::Result<Source> {
-        Ok(Source(Arc::new(UniqueSource::from_latin1_file(file_name)?)))
+        Source::from_file(file_name, Encoding::Latin1)
+    }
+
+    /// Reads a source file and decodes it using the given encoding
+    pub fn from_file(file_name: &Path, encoding: Encoding) -> io::Result<Source> {
+        Ok(Source(Arc::new(UniqueSource::from_file(
+            file_name, encoding,
+        )?)))
     }
 
     #[cfg(test)]
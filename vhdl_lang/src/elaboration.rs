@@ -0,0 +1,339 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Elaborates a design using an external simulator to find errors that are only
+//! detected during elaboration, e.g., bound violations of generics or missing bindings.
+//! Currently, GHDL is supported.
+
+use crate::data::error_codes::ErrorCode;
+use crate::{Diagnostic, Position, Range, Source, SrcPos, VHDLStandard};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The directory relative to the project configuration where GHDL stores compiled libraries
+pub const GHDL_WORKDIR: &str = "vhdl_ls_out/ghdl";
+
+/// Libraries that are built into GHDL and must not be analyzed again
+const BUILTIN_LIBRARIES: [&str; 2] = ["std", "ieee"];
+
+/// The result of elaborating a design using an external tool
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Elaboration {
+    /// Messages of the tool that refer to files of the project
+    pub diagnostics: Vec<Diagnostic>,
+    /// Messages of the tool that cannot be mapped to a position within the project
+    pub messages: Vec<String>,
+    /// True when the tool finished without errors
+    pub success: bool,
+}
+
+/// The files of a library that are analyzed by GHDL
+pub(crate) struct LibraryFiles {
+    pub name: String,
+    pub files: Vec<PathBuf>,
+}
+
+fn std_flag(standard: VHDLStandard) -> &'static str {
+    match standard {
+        VHDLStandard::VHDL1993 => "--std=93c",
+        VHDLStandard::VHDL2008 => "--std=08",
+        VHDLStandard::VHDL2019 => "--std=19",
+    }
+}
+
+/// Returns the arguments of the GHDL invocations that import all libraries
+/// and make (i.e., analyze and elaborate) the top unit
+fn ghdl_arguments(
+    standard: VHDLStandard,
+    libraries: &[LibraryFiles],
+    top_library: &str,
+    top: &str,
+    workdir: &Path,
+) -> Vec<Vec<OsString>> {
+    let common = |command: &str, library: &str| -> Vec<OsString> {
+        let mut workdir_flag = OsString::from("--workdir=");
+        workdir_flag.push(workdir);
+        vec![
+            command.into(),
+            std_flag(standard).into(),
+            workdir_flag,
+            format!("--work={library}").into(),
+        ]
+    };
+    let mut result: Vec<Vec<OsString>> = libraries
+        .iter()
+        .filter(|library| {
+            !library.files.is_empty() && !BUILTIN_LIBRARIES.contains(&library.name.as_str())
+        })
+        .map(|library| {
+            let mut args = common("-i", &library.name);
+            args.extend(
+                library
+                    .files
+                    .iter()
+                    .map(|file| file.clone().into_os_string()),
+            );
+            args
+        })
+        .collect();
+    let mut make = common("-m", top_library);
+    let mut search_path = OsString::from("-P");
+    search_path.push(workdir);
+    make.push(search_path);
+    make.push(top.into());
+    result.push(make);
+    result
+}
+
+/// Returns the end of the word at the given position to highlight more than a single character
+fn end_of_word(source: &Source, pos: Position) -> Position {
+    let contents = source.contents();
+    let Some(line) = contents.get_line(pos.line as usize) else {
+        return pos;
+    };
+    let mut character = 0;
+    for chr in line.chars() {
+        if character >= pos.character && !(chr.is_alphanumeric() || chr == '_') {
+            break;
+        }
+        character += chr.len_utf16() as u32;
+    }
+    Position::new(pos.line, character.max(pos.character + 1))
+}
+
+/// Splits a message such as `tb.vhd:12:5:error: message` into
+/// the file name, the one-based line and column, and the remaining message.
+/// The file name may contain colons, e.g., a drive letter on Windows.
+fn split_location(line: &str) -> Option<(&str, u32, u32, &str)> {
+    line.match_indices(':').find_map(|(idx, _)| {
+        let mut parts = line[idx + 1..].splitn(3, ':');
+        let lineno = parts.next()?.parse().ok()?;
+        let column = parts.next()?.parse().ok()?;
+        Some((&line[..idx], lineno, column, parts.next()?))
+    })
+}
+
+/// Maps the output of GHDL to diagnostics within the sources of the project.
+/// Messages without a position or referring to unknown files are returned as is.
+pub(crate) fn parse_ghdl_output(
+    output: &str,
+    get_source: &dyn Fn(&Path) -> Option<Source>,
+    elaboration: &mut Elaboration,
+) {
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        let location = split_location(line).and_then(|(file_name, lineno, column, message)| {
+            let source = get_source(Path::new(file_name))?;
+            Some((source, lineno, column, message))
+        });
+        let Some((source, lineno, column, message)) = location else {
+            elaboration.messages.push(line.to_owned());
+            continue;
+        };
+        let (code, message) = if let Some(message) = message.strip_prefix("warning:") {
+            (ErrorCode::ExternalToolWarning, message)
+        } else if let Some(message) = message.strip_prefix("note:") {
+            (ErrorCode::ExternalToolWarning, message)
+        } else {
+            (
+                ErrorCode::ExternalToolError,
+                message.strip_prefix("error:").unwrap_or(message),
+            )
+        };
+        let start = Position::new(lineno.saturating_sub(1), column.saturating_sub(1));
+        let pos = SrcPos::new(
+            source.clone(),
+            Range::new(start, end_of_word(&source, start)),
+        );
+        elaboration
+            .diagnostics
+            .push(Diagnostic::new(pos, message.trim(), code));
+    }
+}
+
+/// The invocations of GHDL that import all libraries of the project and elaborate the top unit.
+/// Running them does not need the project, so that they can run on another thread.
+#[derive(Debug, Clone)]
+pub struct GhdlElaboration {
+    ghdl: PathBuf,
+    workdir: PathBuf,
+    arguments: Vec<Vec<OsString>>,
+}
+
+/// The output of running GHDL, which is mapped to the sources of the project afterwards
+#[derive(Debug, Clone, Default)]
+pub struct GhdlOutput {
+    /// True when all invocations of GHDL succeeded
+    pub success: bool,
+    /// The error and standard output of the invocations
+    pub output: String,
+}
+
+impl GhdlElaboration {
+    pub(crate) fn new(
+        ghdl: &Path,
+        standard: VHDLStandard,
+        libraries: &[LibraryFiles],
+        top_library: &str,
+        top: &str,
+        workdir: &Path,
+    ) -> GhdlElaboration {
+        GhdlElaboration {
+            ghdl: ghdl.to_owned(),
+            workdir: workdir.to_owned(),
+            arguments: ghdl_arguments(standard, libraries, top_library, top, workdir),
+        }
+    }
+
+    /// Runs GHDL, storing the compiled libraries in the work directory.
+    /// Stops at the first invocation that fails.
+    pub fn run(&self) -> io::Result<GhdlOutput> {
+        std::fs::create_dir_all(&self.workdir)?;
+        let mut result = GhdlOutput {
+            success: true,
+            ..Default::default()
+        };
+        for args in self.arguments.iter() {
+            let output = Command::new(&self.ghdl).args(args).output()?;
+            result.success &= output.status.success();
+            result
+                .output
+                .push_str(&String::from_utf8_lossy(&output.stderr));
+            result
+                .output
+                .push_str(&String::from_utf8_lossy(&output.stdout));
+            if !result.success {
+                break;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Maps the output of GHDL to the elaboration result within the sources of the project
+pub(crate) fn elaboration_of_ghdl_output(
+    output: &GhdlOutput,
+    get_source: &dyn Fn(&Path) -> Option<Source>,
+) -> Elaboration {
+    let mut elaboration = Elaboration {
+        success: output.success,
+        ..Default::default()
+    };
+    parse_ghdl_output(&output.output, get_source, &mut elaboration);
+    elaboration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::CodeBuilder;
+
+    #[test]
+    fn imports_libraries_before_making_top() {
+        let libraries = [
+            LibraryFiles {
+                name: "ieee".to_owned(),
+                files: vec![PathBuf::from("numeric_std.vhd")],
+            },
+            LibraryFiles {
+                name: "lib".to_owned(),
+                files: vec![PathBuf::from("a.vhd"), PathBuf::from("b.vhd")],
+            },
+            LibraryFiles {
+                name: "empty".to_owned(),
+                files: vec![],
+            },
+        ];
+        let arguments = ghdl_arguments(
+            VHDLStandard::VHDL2008,
+            &libraries,
+            "lib",
+            "top",
+            Path::new("out"),
+        );
+        let arguments: Vec<Vec<String>> = arguments
+            .into_iter()
+            .map(|args| {
+                args.into_iter()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            arguments,
+            vec![
+                vec![
+                    "-i",
+                    "--std=08",
+                    "--workdir=out",
+                    "--work=lib",
+                    "a.vhd",
+                    "b.vhd"
+                ],
+                vec![
+                    "-m",
+                    "--std=08",
+                    "--workdir=out",
+                    "--work=lib",
+                    "-Pout",
+                    "top"
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_output_to_diagnostics() {
+        let code = CodeBuilder::new().code(
+            "\
+entity top is
+end entity;
+architecture a of top is
+  signal s : natural range 0 to 3 := 4;
+begin
+end architecture;
+",
+        );
+        let file_name = code.source().file_name().to_string_lossy().into_owned();
+        let output = format!(
+            "\
+{file_name}:4:38:error: value 4 is out of range
+{file_name}:3:19:warning: architecture is empty
+ghdl:error: compilation error
+unknown.vhd:1:1:error: not part of the project
+"
+        );
+        let mut elaboration = Elaboration::default();
+        let get_source = |file_name: &Path| {
+            (file_name == code.source().file_name()).then(|| code.source().clone())
+        };
+        parse_ghdl_output(&output, &get_source, &mut elaboration);
+
+        assert_eq!(
+            elaboration.diagnostics,
+            vec![
+                Diagnostic::new(
+                    code.s1("4;").s1("4"),
+                    "value 4 is out of range",
+                    ErrorCode::ExternalToolError
+                ),
+                Diagnostic::new(
+                    code.s("top", 2),
+                    "architecture is empty",
+                    ErrorCode::ExternalToolWarning
+                ),
+            ]
+        );
+        assert_eq!(
+            elaboration.messages,
+            vec![
+                "ghdl:error: compilation error".to_owned(),
+                "unknown.vhd:1:1:error: not part of the project".to_owned()
+            ]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Exports the interface of analyzed entities to formats used by other tools
+
+use crate::analysis::DesignRoot;
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::text_at;
+use crate::data::HasSource;
+use crate::named_entity::ObjectEnt;
+use crate::{EntityId, HasTokenSpan, Position, Source, TokenAccess, TokenSpan, Type};
+use fnv::FnvHashMap;
+use std::ops::Deref;
+
+mod ip_xact;
+mod port_list;
+mod testbench;
+
+pub use ip_xact::{export_ip_xact_component, IpXactComponent};
+pub use port_list::{export_port_list, PortListFormat};
+pub use testbench::{generate_testbench, generate_testbench_at, Testbench};
+
+/// A constant generic of an entity
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Generic {
+    name: String,
+    type_mark: String,
+    /// The full path of the base type, e.g., `std.standard.integer`
+    resolved_type: Option<String>,
+    default: Option<String>,
+}
+
+/// The bounds of a port with a range constraint, e.g., `WIDTH-1 downto 0`
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Bounds {
+    left: String,
+    right: String,
+}
+
+/// A port of an entity
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Port {
+    name: String,
+    mode: Mode,
+    type_mark: String,
+    /// The subtype indication including its constraint, e.g., `bit_vector(WIDTH-1 downto 0)`
+    subtype: String,
+    /// The full path of the base type, e.g., `ieee.std_logic_1164.std_ulogic_vector`
+    resolved_type: Option<String>,
+    bounds: Option<Bounds>,
+    /// The number of bits when it is known statically
+    width: Option<u64>,
+    default: Option<String>,
+}
+
+/// The interface of an entity as written in the source
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct EntityInterface {
+    library: String,
+    name: String,
+    /// The library clauses, use clauses and context references of the entity
+    context: Vec<String>,
+    generics: Vec<Generic>,
+    ports: Vec<Port>,
+}
+
+fn expression_text(
+    ctx: &dyn TokenAccess,
+    source: &Source,
+    expr: &WithTokenSpan<Expression>,
+) -> String {
+    text_at(source, expr.pos(ctx).range())
+}
+
+/// Returns the range of a subtype indication that is constrained by a single range
+fn constraint_range(subtype: &SubtypeIndication) -> Option<&RangeConstraint> {
+    match &subtype.constraint.as_ref()?.item {
+        SubtypeConstraint::Range(Range::Range(range)) => Some(range),
+        SubtypeConstraint::Array(ranges, None) if ranges.len() == 1 => match &ranges[0].item {
+            DiscreteRange::Range(Range::Range(range)) => Some(range),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Evaluates integer expressions that only depend on literals and generics with known values
+fn static_value(expr: &Expression, generics: &FnvHashMap<EntityId, i64>) -> Option<i64> {
+    match expr {
+        Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) => {
+            i64::try_from(*value).ok()
+        }
+        Expression::Name(name) => match name.as_ref() {
+            Name::Designator(designator) => generics.get(&designator.reference.get()?).copied(),
+            _ => None,
+        },
+        Expression::Parenthesized(expr) => static_value(&expr.item, generics),
+        Expression::Unary(op, expr) => {
+            let value = static_value(&expr.item, generics)?;
+            match op.item.item {
+                Operator::Minus => value.checked_neg(),
+                Operator::Plus => Some(value),
+                Operator::Abs => value.checked_abs(),
+                _ => None,
+            }
+        }
+        Expression::Binary(op, lhs, rhs) => {
+            let lhs = static_value(&lhs.item, generics)?;
+            let rhs = static_value(&rhs.item, generics)?;
+            match op.item.item {
+                Operator::Plus => lhs.checked_add(rhs),
+                Operator::Minus => lhs.checked_sub(rhs),
+                Operator::Times => lhs.checked_mul(rhs),
+                Operator::Div => lhs.checked_div(rhs),
+                Operator::Pow => lhs.checked_pow(u32::try_from(rhs).ok()?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the object declarations of an interface list.
+/// Interface types, subprograms and packages cannot be expressed by other tools.
+fn interface_objects(
+    list: &Option<InterfaceList>,
+) -> impl Iterator<Item = (&WithDecl<Ident>, &SimpleModeIndication)> {
+    list.iter()
+        .flat_map(|list| list.items.iter())
+        .filter_map(|item| match item {
+            InterfaceDeclaration::Object(object) => match &object.mode {
+                ModeIndication::Simple(mode) => Some((&object.idents, mode)),
+                ModeIndication::View(_) => None,
+            },
+            _ => None,
+        })
+        .flat_map(|(idents, mode)| idents.iter().map(move |ident| (ident, mode)))
+}
+
+fn entity_interface(
+    root: &DesignRoot,
+    ctx: &dyn TokenAccess,
+    source: &Source,
+    library: String,
+    entity: &EntityDeclaration,
+) -> EntityInterface {
+    let type_mark = |mode: &SimpleModeIndication| {
+        text_at(source, mode.subtype_indication.type_mark.pos(ctx).range())
+    };
+    let subtype = |mode: &SimpleModeIndication| {
+        let subtype = &mode.subtype_indication;
+        let end_token = match &subtype.constraint {
+            Some(constraint) => constraint.span.end_token,
+            None => subtype.type_mark.span.end_token,
+        };
+        text_at(
+            source,
+            TokenSpan::new(subtype.type_mark.span.start_token, end_token)
+                .pos(ctx)
+                .range(),
+        )
+    };
+    let default = |mode: &SimpleModeIndication| {
+        mode.expression
+            .as_ref()
+            .map(|expr| expression_text(ctx, source, expr))
+    };
+    let type_of = |ident: &WithDecl<Ident>| {
+        let object = ObjectEnt::from_any(root.get_ent(ident.decl.get()?))?;
+        Some(object.type_mark().base_type())
+    };
+
+    let mut generic_values = FnvHashMap::default();
+    let generics = interface_objects(&entity.generic_clause)
+        .map(|(ident, mode)| {
+            if let (Some(id), Some(expr)) = (ident.decl.get(), &mode.expression) {
+                if let Some(value) = static_value(&expr.item, &generic_values) {
+                    generic_values.insert(id, value);
+                }
+            }
+            Generic {
+                name: ident.tree.item.to_string(),
+                type_mark: type_mark(mode),
+                resolved_type: type_of(ident).map(|typ| typ.path_name()),
+                default: default(mode),
+            }
+        })
+        .collect();
+
+    let ports = interface_objects(&entity.port_clause)
+        .map(|(ident, mode)| {
+            let range = constraint_range(&mode.subtype_indication);
+            let typ = type_of(ident);
+            let width = match range {
+                Some(range) => {
+                    let left = static_value(&range.left_expr.item, &generic_values);
+                    let right = static_value(&range.right_expr.item, &generic_values);
+                    left.zip(right)
+                        .map(|(left, right)| left.abs_diff(right) + 1)
+                }
+                None if mode.subtype_indication.constraint.is_none() => typ
+                    .filter(|typ| matches!(typ.kind(), Type::Enum(_)))
+                    .map(|_| 1),
+                None => None,
+            };
+            Port {
+                name: ident.tree.item.to_string(),
+                mode: mode.mode.as_ref().map(|mode| mode.item).unwrap_or_default(),
+                type_mark: type_mark(mode),
+                subtype: subtype(mode),
+                resolved_type: typ.map(|typ| typ.path_name()),
+                bounds: range.map(|range| Bounds {
+                    left: expression_text(ctx, source, &range.left_expr),
+                    right: expression_text(ctx, source, &range.right_expr),
+                }),
+                width,
+                default: default(mode),
+            }
+        })
+        .collect();
+
+    EntityInterface {
+        library,
+        name: entity.ident.tree.item.to_string(),
+        context: entity
+            .context_clause
+            .iter()
+            .map(|item| text_at(source, item.get_pos(ctx).range()))
+            .collect(),
+        generics,
+        ports,
+    }
+}
+
+/// Returns the interface of the entity declared at the cursor
+fn entity_interface_at(
+    root: &DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<EntityInterface> {
+    root.units_by_source(source).find_map(|unit| {
+        let data = unit.unit.get()?;
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() else {
+            return None;
+        };
+        if !entity.span.pos(&unit.tokens).range().contains(cursor) {
+            return None;
+        }
+        Some(entity_interface(
+            root,
+            &unit.tokens,
+            source,
+            unit.unit_id().library_name().to_string(),
+            entity,
+        ))
+    })
+}
+
+/// Returns the interface of an entity given as `library.entity` or as the name of an entity
+/// that is looked up in all libraries
+fn entity_interface_by_name(root: &DesignRoot, name: &str) -> Option<EntityInterface> {
+    let (library, name) = match name.split_once('.') {
+        Some((library, name)) => (Some(root.symbol_utf8(library)), name),
+        None => (None, name),
+    };
+    let name = root.symbol_utf8(name);
+    root.libraries()
+        .filter(|lib| library.as_ref().is_none_or(|library| lib.name() == library))
+        .find_map(|lib| {
+            let unit = lib.primary_unit(&name)?;
+            let data = unit.unit.get()?;
+            let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() else {
+                return None;
+            };
+            Some(entity_interface(
+                root,
+                &unit.tokens,
+                unit.source(),
+                lib.name().name_utf8(),
+                entity,
+            ))
+        })
+}
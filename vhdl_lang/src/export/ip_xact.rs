@@ -0,0 +1,368 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{entity_interface_at, EntityInterface, Generic, Port};
+use crate::analysis::DesignRoot;
+use crate::ast::Mode;
+use crate::{Position, Source};
+
+const NAMESPACE: &str = "http://www.accellera.org/XMLSchema/IPXACT/1685-2014";
+
+/// An entity described as an IP-XACT (IEEE 1685-2014) component
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IpXactComponent {
+    /// The name of the exported entity
+    pub name: String,
+    pub xml: String,
+}
+
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for chr in text.chars() {
+        match chr {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _ => result.push(chr),
+        }
+    }
+    result
+}
+
+/// The IP-XACT parameter type that corresponds to a VHDL type mark
+fn parameter_type(type_mark: &str) -> Option<&'static str> {
+    match type_mark.to_lowercase().as_str() {
+        "integer" | "natural" | "positive" => Some("longint"),
+        "real" => Some("real"),
+        "string" => Some("string"),
+        "boolean" | "bit" | "std_logic" | "std_ulogic" => Some("bit"),
+        _ => None,
+    }
+}
+
+fn direction(mode: Mode) -> &'static str {
+    match mode {
+        Mode::In => "in",
+        Mode::Out | Mode::Buffer => "out",
+        Mode::InOut | Mode::Linkage => "inout",
+    }
+}
+
+struct XmlWriter {
+    text: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.line(&format!("<ipxact:{tag}>"));
+        self.depth += 1;
+    }
+
+    fn open_with_attributes(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        let attributes: String = attributes
+            .iter()
+            .map(|(name, value)| format!(" {name}=\"{}\"", escape(value)))
+            .collect();
+        self.line(&format!("<ipxact:{tag}{attributes}>"));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</ipxact:{tag}>"));
+    }
+
+    fn element(&mut self, tag: &str, value: &str) {
+        self.line(&format!("<ipxact:{tag}>{}</ipxact:{tag}>", escape(value)));
+    }
+
+    fn parameter(&mut self, tag: &str, generic: &Generic, value: &str) {
+        let mut attributes = vec![("parameterId", generic.name.as_str()), ("resolve", "user")];
+        if let Some(typ) = parameter_type(&generic.type_mark) {
+            attributes.push(("type", typ));
+        }
+        self.open_with_attributes(tag, &attributes);
+        self.element("name", &generic.name);
+        self.element("value", value);
+        self.close(tag);
+    }
+
+    fn port(&mut self, port: &Port) {
+        self.open("port");
+        self.element("name", &port.name);
+        self.open("wire");
+        self.element("direction", direction(port.mode));
+        if let Some(bounds) = &port.bounds {
+            self.open("vectors");
+            self.open("vector");
+            self.element("left", &bounds.left);
+            self.element("right", &bounds.right);
+            self.close("vector");
+            self.close("vectors");
+        }
+        self.open("wireTypeDefs");
+        self.open("wireTypeDef");
+        self.element("typeName", &port.type_mark);
+        self.element("viewRef", "rtl");
+        self.close("wireTypeDef");
+        self.close("wireTypeDefs");
+        if let Some(default) = &port.default {
+            self.open("drivers");
+            self.open("driver");
+            self.element("defaultValue", default);
+            self.close("driver");
+            self.close("drivers");
+        }
+        self.close("wire");
+        self.close("port");
+    }
+
+    fn component(&mut self, interface: &EntityInterface) {
+        self.line(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        self.line(&format!(
+            r#"<ipxact:component xmlns:ipxact="{NAMESPACE}" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="{NAMESPACE} {NAMESPACE}/index.xsd">"#
+        ));
+        self.depth += 1;
+        self.element("vendor", "user");
+        self.element("library", &interface.library);
+        self.element("name", &interface.name);
+        self.element("version", "1.0");
+
+        self.open("model");
+        self.open("views");
+        self.open("view");
+        self.element("name", "rtl");
+        self.element("componentInstantiationRef", "vhdl_implementation");
+        self.close("view");
+        self.close("views");
+        self.open("instantiations");
+        self.open("componentInstantiation");
+        self.element("name", "vhdl_implementation");
+        self.element("language", "vhdl");
+        self.element("libraryName", &interface.library);
+        self.element("moduleName", &interface.name);
+        if !interface.generics.is_empty() {
+            // The generics of the entity are set from the parameters of the component
+            self.open("moduleParameters");
+            for generic in interface.generics.iter() {
+                self.parameter("moduleParameter", generic, &generic.name);
+            }
+            self.close("moduleParameters");
+        }
+        self.close("componentInstantiation");
+        self.close("instantiations");
+        if !interface.ports.is_empty() {
+            self.open("ports");
+            for port in interface.ports.iter() {
+                self.port(port);
+            }
+            self.close("ports");
+        }
+        self.close("model");
+
+        if !interface.generics.is_empty() {
+            self.open("parameters");
+            for generic in interface.generics.iter() {
+                self.parameter(
+                    "parameter",
+                    generic,
+                    generic.default.as_deref().unwrap_or_default(),
+                );
+            }
+            self.close("parameters");
+        }
+        self.close("component");
+    }
+}
+
+/// Describes the entity declared at the cursor as an IP-XACT component.
+/// Ports are described as wires and generics as parameters of the component.
+/// Bounds and default values are exported as written in the VHDL code
+/// so that they may refer to the parameters of the component.
+pub fn export_ip_xact_component(
+    root: &DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<IpXactComponent> {
+    let interface = entity_interface_at(root, source, cursor)?;
+    let mut writer = XmlWriter {
+        text: String::new(),
+        depth: 0,
+    };
+    writer.component(&interface);
+    Some(IpXactComponent {
+        name: interface.name,
+        xml: writer.text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+
+    #[test]
+    fn exports_entity_as_component() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let code = builder.code(
+            "libname",
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity counter is
+  generic (
+    WIDTH : natural := 8;
+    NAME : string := \"a<b\"
+  );
+  port (
+    clk : in std_logic;
+    enable : in std_logic := '1';
+    count : buffer std_logic_vector(WIDTH-1 downto 0)
+  );
+end entity;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let component =
+            export_ip_xact_component(&root, code.source(), code.s1("counter").start()).unwrap();
+        assert_eq!(component.name, "counter");
+        assert_eq!(
+            component.xml,
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ipxact:component xmlns:ipxact="{NAMESPACE}" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="{NAMESPACE} {NAMESPACE}/index.xsd">
+  <ipxact:vendor>user</ipxact:vendor>
+  <ipxact:library>libname</ipxact:library>
+  <ipxact:name>counter</ipxact:name>
+  <ipxact:version>1.0</ipxact:version>
+  <ipxact:model>
+    <ipxact:views>
+      <ipxact:view>
+        <ipxact:name>rtl</ipxact:name>
+        <ipxact:componentInstantiationRef>vhdl_implementation</ipxact:componentInstantiationRef>
+      </ipxact:view>
+    </ipxact:views>
+    <ipxact:instantiations>
+      <ipxact:componentInstantiation>
+        <ipxact:name>vhdl_implementation</ipxact:name>
+        <ipxact:language>vhdl</ipxact:language>
+        <ipxact:libraryName>libname</ipxact:libraryName>
+        <ipxact:moduleName>counter</ipxact:moduleName>
+        <ipxact:moduleParameters>
+          <ipxact:moduleParameter parameterId="WIDTH" resolve="user" type="longint">
+            <ipxact:name>WIDTH</ipxact:name>
+            <ipxact:value>WIDTH</ipxact:value>
+          </ipxact:moduleParameter>
+          <ipxact:moduleParameter parameterId="NAME" resolve="user" type="string">
+            <ipxact:name>NAME</ipxact:name>
+            <ipxact:value>NAME</ipxact:value>
+          </ipxact:moduleParameter>
+        </ipxact:moduleParameters>
+      </ipxact:componentInstantiation>
+    </ipxact:instantiations>
+    <ipxact:ports>
+      <ipxact:port>
+        <ipxact:name>clk</ipxact:name>
+        <ipxact:wire>
+          <ipxact:direction>in</ipxact:direction>
+          <ipxact:wireTypeDefs>
+            <ipxact:wireTypeDef>
+              <ipxact:typeName>std_logic</ipxact:typeName>
+              <ipxact:viewRef>rtl</ipxact:viewRef>
+            </ipxact:wireTypeDef>
+          </ipxact:wireTypeDefs>
+        </ipxact:wire>
+      </ipxact:port>
+      <ipxact:port>
+        <ipxact:name>enable</ipxact:name>
+        <ipxact:wire>
+          <ipxact:direction>in</ipxact:direction>
+          <ipxact:wireTypeDefs>
+            <ipxact:wireTypeDef>
+              <ipxact:typeName>std_logic</ipxact:typeName>
+              <ipxact:viewRef>rtl</ipxact:viewRef>
+            </ipxact:wireTypeDef>
+          </ipxact:wireTypeDefs>
+          <ipxact:drivers>
+            <ipxact:driver>
+              <ipxact:defaultValue>&apos;1&apos;</ipxact:defaultValue>
+            </ipxact:driver>
+          </ipxact:drivers>
+        </ipxact:wire>
+      </ipxact:port>
+      <ipxact:port>
+        <ipxact:name>count</ipxact:name>
+        <ipxact:wire>
+          <ipxact:direction>out</ipxact:direction>
+          <ipxact:vectors>
+            <ipxact:vector>
+              <ipxact:left>WIDTH-1</ipxact:left>
+              <ipxact:right>0</ipxact:right>
+            </ipxact:vector>
+          </ipxact:vectors>
+          <ipxact:wireTypeDefs>
+            <ipxact:wireTypeDef>
+              <ipxact:typeName>std_logic_vector</ipxact:typeName>
+              <ipxact:viewRef>rtl</ipxact:viewRef>
+            </ipxact:wireTypeDef>
+          </ipxact:wireTypeDefs>
+        </ipxact:wire>
+      </ipxact:port>
+    </ipxact:ports>
+  </ipxact:model>
+  <ipxact:parameters>
+    <ipxact:parameter parameterId="WIDTH" resolve="user" type="longint">
+      <ipxact:name>WIDTH</ipxact:name>
+      <ipxact:value>8</ipxact:value>
+    </ipxact:parameter>
+    <ipxact:parameter parameterId="NAME" resolve="user" type="string">
+      <ipxact:name>NAME</ipxact:name>
+      <ipxact:value>&quot;a&lt;b&quot;</ipxact:value>
+    </ipxact:parameter>
+  </ipxact:parameters>
+</ipxact:component>
+"#
+            )
+        );
+    }
+
+    #[test]
+    fn only_exports_entity_at_cursor() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        assert!(export_ip_xact_component(&root, code.source(), code.s1("ent").start()).is_some());
+        assert!(
+            export_ip_xact_component(&root, code.source(), code.s1("architecture").start())
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,352 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{entity_interface_by_name, EntityInterface, Generic, Port};
+use crate::analysis::DesignRoot;
+use crate::ast::Mode;
+use strum::EnumString;
+
+/// The format of an exported port list
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum PortListFormat {
+    Json,
+    Yaml,
+}
+
+/// A value of the exported document
+enum Value {
+    String(String),
+    Number(u64),
+    Null,
+    List(Vec<Value>),
+    /// The fields of an object in the order they are written
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl From<Option<String>> for Value {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(Value::Null, Value::String)
+    }
+}
+
+fn direction(mode: Mode) -> &'static str {
+    match mode {
+        Mode::In => "in",
+        Mode::Out => "out",
+        Mode::InOut => "inout",
+        Mode::Buffer => "buffer",
+        Mode::Linkage => "linkage",
+    }
+}
+
+fn generic_value(generic: &Generic) -> Value {
+    Value::Object(vec![
+        ("name", Value::String(generic.name.clone())),
+        ("type", Value::String(generic.type_mark.clone())),
+        ("resolved_type", generic.resolved_type.clone().into()),
+        ("default", generic.default.clone().into()),
+    ])
+}
+
+fn port_value(port: &Port) -> Value {
+    Value::Object(vec![
+        ("name", Value::String(port.name.clone())),
+        ("direction", Value::String(direction(port.mode).to_owned())),
+        ("type", Value::String(port.type_mark.clone())),
+        ("resolved_type", port.resolved_type.clone().into()),
+        (
+            "left",
+            port.bounds
+                .as_ref()
+                .map(|bounds| bounds.left.clone())
+                .into(),
+        ),
+        (
+            "right",
+            port.bounds
+                .as_ref()
+                .map(|bounds| bounds.right.clone())
+                .into(),
+        ),
+        ("width", port.width.map_or(Value::Null, Value::Number)),
+        ("default", port.default.clone().into()),
+    ])
+}
+
+fn interface_value(interface: &EntityInterface) -> Value {
+    Value::Object(vec![
+        ("library", Value::String(interface.library.clone())),
+        ("entity", Value::String(interface.name.clone())),
+        (
+            "generics",
+            Value::List(interface.generics.iter().map(generic_value).collect()),
+        ),
+        (
+            "ports",
+            Value::List(interface.ports.iter().map(port_value).collect()),
+        ),
+    ])
+}
+
+/// Strings are quoted the same way in JSON and YAML
+fn quoted(text: &str) -> String {
+    let mut result = String::from('"');
+    for chr in text.chars() {
+        match chr {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            chr if chr.is_control() => result.push_str(&format!("\\u{:04x}", chr as u32)),
+            chr => result.push(chr),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(quoted(text)),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Null => Some("null".to_owned()),
+        Value::List(items) if items.is_empty() => Some("[]".to_owned()),
+        Value::Object(fields) if fields.is_empty() => Some("{}".to_owned()),
+        Value::List(_) | Value::Object(_) => None,
+    }
+}
+
+fn write_json(value: &Value, indent: usize, text: &mut String) {
+    if let Some(scalar) = scalar(value) {
+        text.push_str(&scalar);
+        return;
+    }
+    let inner = "  ".repeat(indent + 1);
+    let (open, close, items): (char, char, Vec<(Option<&str>, &Value)>) = match value {
+        Value::List(items) => ('[', ']', items.iter().map(|item| (None, item)).collect()),
+        Value::Object(fields) => (
+            '{',
+            '}',
+            fields
+                .iter()
+                .map(|(key, item)| (Some(*key), item))
+                .collect(),
+        ),
+        _ => unreachable!(),
+    };
+    text.push(open);
+    text.push('\n');
+    for (i, (key, item)) in items.iter().enumerate() {
+        text.push_str(&inner);
+        if let Some(key) = key {
+            text.push_str(&format!("{}: ", quoted(key)));
+        }
+        write_json(item, indent + 1, text);
+        if i + 1 < items.len() {
+            text.push(',');
+        }
+        text.push('\n');
+    }
+    text.push_str(&"  ".repeat(indent));
+    text.push(close);
+}
+
+fn write_yaml(value: &Value, indent: usize, text: &mut String) {
+    let prefix = "  ".repeat(indent);
+    match value {
+        Value::Object(fields) => {
+            for (key, item) in fields.iter() {
+                match scalar(item) {
+                    Some(scalar) => text.push_str(&format!("{prefix}{key}: {scalar}\n")),
+                    None => {
+                        text.push_str(&format!("{prefix}{key}:\n"));
+                        write_yaml(item, indent + 1, text);
+                    }
+                }
+            }
+        }
+        Value::List(items) => {
+            for item in items.iter() {
+                match scalar(item) {
+                    Some(scalar) => text.push_str(&format!("{prefix}- {scalar}\n")),
+                    None => {
+                        // The first line of the item follows the dash
+                        let mut item_text = String::new();
+                        write_yaml(item, indent + 1, &mut item_text);
+                        let item_text = item_text.trim_start();
+                        text.push_str(&format!("{prefix}- {item_text}"));
+                    }
+                }
+            }
+        }
+        _ => {
+            if let Some(scalar) = scalar(value) {
+                text.push_str(&format!("{prefix}{scalar}\n"));
+            }
+        }
+    }
+}
+
+/// Describes the generics and ports of an entity for other tools,
+/// e.g., test generators or documentation pipelines.
+/// The entity is given as `library.entity` or as the name of an entity in any library.
+/// Types are exported as written and resolved to the full path of their base type.
+/// The width of a port is exported when its range only depends on literals
+/// and generics with a literal default value.
+pub fn export_port_list(root: &DesignRoot, entity: &str, format: PortListFormat) -> Option<String> {
+    let value = interface_value(&entity_interface_by_name(root, entity)?);
+    let mut text = String::new();
+    match format {
+        PortListFormat::Json => {
+            write_json(&value, 0, &mut text);
+            text.push('\n');
+        }
+        PortListFormat::Yaml => write_yaml(&value, 0, &mut text),
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+    use std::str::FromStr;
+
+    fn root() -> DesignRoot {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity counter is
+  generic (
+    WIDTH : natural := 4;
+    NAME : string := \"cnt\"
+  );
+  port (
+    clk : in std_logic;
+    count : out std_logic_vector(2*WIDTH-1 downto 0);
+    value : in integer range 0 to 255 := 0
+  );
+end entity;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        root
+    }
+
+    #[test]
+    fn exports_port_list_as_json() {
+        assert_eq!(
+            export_port_list(&root(), "libname.counter", PortListFormat::Json).unwrap(),
+            r#"{
+  "library": "libname",
+  "entity": "counter",
+  "generics": [
+    {
+      "name": "WIDTH",
+      "type": "natural",
+      "resolved_type": "std.standard.INTEGER",
+      "default": "4"
+    },
+    {
+      "name": "NAME",
+      "type": "string",
+      "resolved_type": "std.standard.STRING",
+      "default": "\"cnt\""
+    }
+  ],
+  "ports": [
+    {
+      "name": "clk",
+      "direction": "in",
+      "type": "std_logic",
+      "resolved_type": "ieee.std_logic_1164.STD_ULOGIC",
+      "left": null,
+      "right": null,
+      "width": 1,
+      "default": null
+    },
+    {
+      "name": "count",
+      "direction": "out",
+      "type": "std_logic_vector",
+      "resolved_type": "ieee.std_logic_1164.STD_ULOGIC_VECTOR",
+      "left": "2*WIDTH-1",
+      "right": "0",
+      "width": 8,
+      "default": null
+    },
+    {
+      "name": "value",
+      "direction": "in",
+      "type": "integer",
+      "resolved_type": "std.standard.INTEGER",
+      "left": "0",
+      "right": "255",
+      "width": 256,
+      "default": "0"
+    }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn exports_port_list_as_yaml() {
+        assert_eq!(PortListFormat::from_str("yaml"), Ok(PortListFormat::Yaml));
+        assert_eq!(
+            export_port_list(&root(), "counter", PortListFormat::Yaml).unwrap(),
+            r#"library: "libname"
+entity: "counter"
+generics:
+  - name: "WIDTH"
+    type: "natural"
+    resolved_type: "std.standard.INTEGER"
+    default: "4"
+  - name: "NAME"
+    type: "string"
+    resolved_type: "std.standard.STRING"
+    default: "\"cnt\""
+ports:
+  - name: "clk"
+    direction: "in"
+    type: "std_logic"
+    resolved_type: "ieee.std_logic_1164.STD_ULOGIC"
+    left: null
+    right: null
+    width: 1
+    default: null
+  - name: "count"
+    direction: "out"
+    type: "std_logic_vector"
+    resolved_type: "ieee.std_logic_1164.STD_ULOGIC_VECTOR"
+    left: "2*WIDTH-1"
+    right: "0"
+    width: 8
+    default: null
+  - name: "value"
+    direction: "in"
+    type: "integer"
+    resolved_type: "std.standard.INTEGER"
+    left: "0"
+    right: "255"
+    width: 256
+    default: "0"
+"#
+        );
+        assert_eq!(
+            export_port_list(&root(), "other.counter", PortListFormat::Yaml),
+            None
+        );
+    }
+}
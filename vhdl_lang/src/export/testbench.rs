@@ -0,0 +1,342 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{entity_interface_at, entity_interface_by_name, EntityInterface, Generic, Port};
+use crate::analysis::DesignRoot;
+use crate::ast::Mode;
+use crate::code_action::INDENT;
+use crate::{Position, Source};
+
+/// The skeleton of a testbench for an entity
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Testbench {
+    /// The name of the testbench entity
+    pub name: String,
+    pub text: String,
+}
+
+/// Returns true for the types of single-bit signals that can be driven by `'0'` and `'1'`
+fn is_bit(port: &Port) -> bool {
+    matches!(
+        port.type_mark.to_lowercase().as_str(),
+        "bit" | "std_logic" | "std_ulogic"
+    )
+}
+
+fn is_clock(port: &Port) -> bool {
+    let name = port.name.to_lowercase();
+    port.mode == Mode::In
+        && is_bit(port)
+        && ["clk", "clock"]
+            .iter()
+            .any(|clock| name.starts_with(clock) || name.ends_with(clock))
+}
+
+fn is_reset(port: &Port) -> bool {
+    let name = port.name.to_lowercase();
+    port.mode == Mode::In && is_bit(port) && (name.contains("rst") || name.contains("reset"))
+}
+
+fn is_active_low(port: &Port) -> bool {
+    let name = port.name.to_lowercase();
+    name.ends_with("_n") || name.ends_with("rstn") || name.ends_with("resetn")
+}
+
+/// The value of the constant that a generic is mapped to.
+/// Generics without a default value get a placeholder of their type.
+fn generic_value(generic: &Generic) -> String {
+    if let Some(default) = &generic.default {
+        return default.clone();
+    }
+    match generic.type_mark.to_lowercase().as_str() {
+        "positive" => "1".to_owned(),
+        "integer" | "natural" => "0".to_owned(),
+        "real" => "0.0".to_owned(),
+        "boolean" => "false".to_owned(),
+        "string" => "\"\"".to_owned(),
+        "time" => "0 ns".to_owned(),
+        _ => format!("{}'left", generic.type_mark),
+    }
+}
+
+fn map_aspect(keyword: &str, names: &[&str]) -> String {
+    let associations: Vec<String> = names
+        .iter()
+        .map(|name| format!("{INDENT}{INDENT}{INDENT}{name} => {name}"))
+        .collect();
+    format!(
+        "\n{INDENT}{INDENT}{keyword} map (\n{}\n{INDENT}{INDENT})",
+        associations.join(",\n")
+    )
+}
+
+fn testbench_text(interface: &EntityInterface, name: &str) -> String {
+    let clock = interface.ports.iter().find(|port| is_clock(port));
+    let reset = interface.ports.iter().find(|port| is_reset(port));
+
+    let mut text = String::new();
+    let has_library_clause = interface.context.iter().any(|item| {
+        item.to_lowercase()
+            .starts_with(&format!("library {}", interface.library.to_lowercase()))
+    });
+    if !has_library_clause {
+        text.push_str(&format!("library {};\n", interface.library));
+    }
+    for item in interface.context.iter() {
+        text.push_str(item);
+        text.push('\n');
+    }
+    text.push('\n');
+    text.push_str(&format!("entity {name} is\nend entity {name};\n\n"));
+    text.push_str(&format!("architecture tb of {name} is\n"));
+
+    if clock.is_some() {
+        text.push_str(&format!("{INDENT}constant CLK_PERIOD : time := 10 ns;\n"));
+    }
+    for generic in interface.generics.iter() {
+        text.push_str(&format!(
+            "{INDENT}constant {} : {} := {};\n",
+            generic.name,
+            generic.type_mark,
+            generic_value(generic)
+        ));
+    }
+    if !interface.ports.is_empty() {
+        if clock.is_some() || !interface.generics.is_empty() {
+            text.push('\n');
+        }
+        for port in interface.ports.iter() {
+            let default = match &port.default {
+                Some(default) => Some(default.clone()),
+                None if clock == Some(port) => Some("'0'".to_owned()),
+                None => None,
+            };
+            text.push_str(&format!("{INDENT}signal {} : {}", port.name, port.subtype));
+            if let Some(default) = default {
+                text.push_str(&format!(" := {default}"));
+            }
+            text.push_str(";\n");
+        }
+    }
+    text.push_str("begin\n");
+
+    text.push_str(&format!(
+        "{INDENT}dut : entity {}.{}",
+        interface.library, interface.name
+    ));
+    if !interface.generics.is_empty() {
+        let names: Vec<&str> = interface.generics.iter().map(|g| g.name.as_str()).collect();
+        text.push_str(&map_aspect("generic", &names));
+    }
+    if !interface.ports.is_empty() {
+        let names: Vec<&str> = interface.ports.iter().map(|p| p.name.as_str()).collect();
+        text.push_str(&map_aspect("port", &names));
+    }
+    text.push_str(";\n");
+
+    if let Some(clock) = clock {
+        text.push_str(&format!(
+            "\n{INDENT}clk_gen : process\n\
+             {INDENT}begin\n\
+             {INDENT}{INDENT}{clk} <= '0';\n\
+             {INDENT}{INDENT}wait for CLK_PERIOD / 2;\n\
+             {INDENT}{INDENT}{clk} <= '1';\n\
+             {INDENT}{INDENT}wait for CLK_PERIOD / 2;\n\
+             {INDENT}end process;\n",
+            clk = clock.name
+        ));
+    }
+    if let Some(reset) = reset {
+        let (active, inactive) = if is_active_low(reset) {
+            ("'0'", "'1'")
+        } else {
+            ("'1'", "'0'")
+        };
+        let duration = if clock.is_some() {
+            "5 * CLK_PERIOD"
+        } else {
+            "50 ns"
+        };
+        text.push_str(&format!(
+            "\n{INDENT}rst_gen : process\n\
+             {INDENT}begin\n\
+             {INDENT}{INDENT}{rst} <= {active};\n\
+             {INDENT}{INDENT}wait for {duration};\n\
+             {INDENT}{INDENT}{rst} <= {inactive};\n\
+             {INDENT}{INDENT}wait;\n\
+             {INDENT}end process;\n",
+            rst = reset.name
+        ));
+    }
+    text.push_str(&format!(
+        "\n{INDENT}stimulus : process\n{INDENT}begin\n{INDENT}{INDENT}wait;\n{INDENT}end process;\n"
+    ));
+    text.push_str("end architecture tb;\n");
+    text
+}
+
+fn testbench(interface: EntityInterface) -> Testbench {
+    let name = format!("{}_tb", interface.name);
+    Testbench {
+        text: testbench_text(&interface, &name),
+        name,
+    }
+}
+
+/// Generates a testbench for the entity declared at the cursor.
+/// The testbench declares a signal for each port and a constant for each generic,
+/// instantiates the entity with named associations, and generates a clock and a reset
+/// for ports that are recognized as such by their name.
+pub fn generate_testbench_at(
+    root: &DesignRoot,
+    source: &Source,
+    cursor: Position,
+) -> Option<Testbench> {
+    entity_interface_at(root, source, cursor).map(testbench)
+}
+
+/// Generates a testbench for an entity given as `library.entity` or as the name of an entity
+pub fn generate_testbench(root: &DesignRoot, entity: &str) -> Option<Testbench> {
+    entity_interface_by_name(root, entity).map(testbench)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+
+    #[test]
+    fn generates_testbench_skeleton() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity counter is
+  generic (
+    WIDTH : natural := 8;
+    STEP : positive
+  );
+  port (
+    clk : in std_logic;
+    rst_n : in std_logic;
+    enable : in std_logic := '1';
+    count : out std_logic_vector(WIDTH-1 downto 0)
+  );
+end entity;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let testbench = generate_testbench(&root, "libname.counter").unwrap();
+        assert_eq!(testbench.name, "counter_tb");
+        assert_eq!(
+            testbench.text,
+            "\
+library libname;
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity counter_tb is
+end entity counter_tb;
+
+architecture tb of counter_tb is
+    constant CLK_PERIOD : time := 10 ns;
+    constant WIDTH : natural := 8;
+    constant STEP : positive := 1;
+
+    signal clk : std_logic := '0';
+    signal rst_n : std_logic;
+    signal enable : std_logic := '1';
+    signal count : std_logic_vector(WIDTH-1 downto 0);
+begin
+    dut : entity libname.counter
+        generic map (
+            WIDTH => WIDTH,
+            STEP => STEP
+        )
+        port map (
+            clk => clk,
+            rst_n => rst_n,
+            enable => enable,
+            count => count
+        );
+
+    clk_gen : process
+    begin
+        clk <= '0';
+        wait for CLK_PERIOD / 2;
+        clk <= '1';
+        wait for CLK_PERIOD / 2;
+    end process;
+
+    rst_gen : process
+    begin
+        rst_n <= '0';
+        wait for 5 * CLK_PERIOD;
+        rst_n <= '1';
+        wait;
+    end process;
+
+    stimulus : process
+    begin
+        wait;
+    end process;
+end architecture tb;
+"
+        );
+
+        // The generated testbench is valid VHDL
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code(
+            "libname",
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity counter is
+  generic (WIDTH : natural := 8; STEP : positive);
+  port (
+    clk, rst_n, enable : in std_logic;
+    count : out std_logic_vector(WIDTH-1 downto 0)
+  );
+end entity;
+",
+        );
+        builder.code("libname", &testbench.text);
+        let (_, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+    }
+
+    #[test]
+    fn generates_testbench_for_entity_at_cursor() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity first is
+end entity;
+
+entity second is
+  port (a : in bit);
+end entity;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let testbench =
+            generate_testbench_at(&root, code.source(), code.s1("second").start()).unwrap();
+        assert_eq!(testbench.name, "second_tb");
+        assert!(testbench.text.contains("signal a : bit;"));
+        assert!(!testbench.text.contains("clk_gen"));
+    }
+}
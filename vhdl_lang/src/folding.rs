@@ -0,0 +1,330 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Computes the regions of a source file that an editor can fold,
+//! i.e., collapse to their first line.
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{
+    DeclarationItem, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::syntax::{Comment, Token};
+use crate::{Range, Source, SrcPos, TokenAccess, TokenSpan};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FoldingRangeKind {
+    /// A construct of the language, such as a design unit or a process
+    Region,
+    /// A block of contiguous comments
+    Comment,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FoldingRange {
+    pub range: Range,
+    pub kind: FoldingRangeKind,
+}
+
+#[derive(Default)]
+struct FoldingRegions {
+    ranges: Vec<Range>,
+}
+
+impl FoldingRegions {
+    fn add(&mut self, pos: SrcPos) {
+        self.ranges.push(pos.range());
+    }
+
+    fn add_token_span(&mut self, ctx: &dyn TokenAccess, span: TokenSpan) {
+        self.add(span.pos(ctx));
+    }
+
+    /// Declarative parts are folded from the first to the last declaration
+    fn add_declarations(&mut self, ctx: &dyn TokenAccess, decls: &[WithTokenSpan<Declaration>]) {
+        if let (Some(first), Some(last)) = (decls.first(), decls.last()) {
+            self.add(first.pos(ctx).combine(&last.pos(ctx)));
+        }
+    }
+
+    fn add_interface_list(&mut self, ctx: &dyn TokenAccess, list: &Option<InterfaceList>) {
+        if let Some(list) = list {
+            self.add_token_span(ctx, list.span);
+        }
+    }
+
+    fn add_alternatives<T>(&mut self, ctx: &dyn TokenAccess, alternatives: &[Alternative<T>]) {
+        for alternative in alternatives {
+            self.add_token_span(ctx, alternative.span);
+        }
+    }
+
+    fn add_generate_body(&mut self, ctx: &dyn TokenAccess, body: &GenerateBody) {
+        if let Some((decls, _)) = &body.decl {
+            self.add_declarations(ctx, decls);
+        }
+    }
+
+    fn add_concurrent_statement(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        statement: &LabeledConcurrentStatement,
+    ) {
+        let pos = match &statement.label.tree {
+            Some(label) => label.pos(ctx).combine(&statement.statement.pos(ctx)),
+            None => statement.statement.pos(ctx),
+        };
+        match &statement.statement.item {
+            ConcurrentStatement::Process(process) => {
+                self.add(pos);
+                self.add_declarations(ctx, &process.decl);
+            }
+            ConcurrentStatement::Block(block) => {
+                self.add(pos);
+                self.add_declarations(ctx, &block.decl);
+            }
+            ConcurrentStatement::ForGenerate(generate) => {
+                self.add(pos);
+                self.add_generate_body(ctx, &generate.body);
+            }
+            ConcurrentStatement::IfGenerate(generate) => {
+                self.add(pos);
+                for conditional in &generate.conds.conditionals {
+                    self.add_generate_body(ctx, &conditional.item);
+                }
+                if let Some((body, _)) = &generate.conds.else_item {
+                    self.add_generate_body(ctx, body);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(generate) => {
+                self.add(pos);
+                self.add_alternatives(ctx, &generate.sels.alternatives);
+                for alternative in &generate.sels.alternatives {
+                    self.add_generate_body(ctx, &alternative.item);
+                }
+            }
+            ConcurrentStatement::ProcedureCall(_)
+            | ConcurrentStatement::Assert(_)
+            | ConcurrentStatement::Assignment(_)
+            | ConcurrentStatement::Instance(_) => {}
+        }
+    }
+}
+
+impl Searcher for FoldingRegions {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Entity(entity) => {
+                self.add_token_span(ctx, entity.span);
+                self.add_interface_list(ctx, &entity.generic_clause);
+                self.add_interface_list(ctx, &entity.port_clause);
+                self.add_declarations(ctx, &entity.decl);
+            }
+            DeclarationItem::Architecture(architecture) => {
+                self.add_token_span(ctx, architecture.span);
+                self.add_declarations(ctx, &architecture.decl);
+            }
+            DeclarationItem::Package(package) => {
+                self.add_token_span(ctx, package.span);
+                self.add_interface_list(ctx, &package.generic_clause);
+                self.add_declarations(ctx, &package.decl);
+            }
+            DeclarationItem::PackageBody(body) => {
+                self.add_token_span(ctx, body.span);
+                self.add_declarations(ctx, &body.decl);
+            }
+            DeclarationItem::Configuration(configuration) => {
+                self.add_token_span(ctx, configuration.span);
+            }
+            DeclarationItem::Context(context) => {
+                self.add_token_span(ctx, context.span);
+            }
+            DeclarationItem::Component(component) => {
+                self.add_token_span(ctx, component.span);
+                self.add_interface_list(ctx, &component.generic_list);
+                self.add_interface_list(ctx, &component.port_list);
+            }
+            DeclarationItem::Subprogram(body) => {
+                self.add_token_span(ctx, body.span);
+                self.add_declarations(ctx, &body.declarations);
+            }
+            DeclarationItem::ConcurrentStatement(statement) => {
+                self.add_concurrent_statement(ctx, statement);
+            }
+            DeclarationItem::SequentialStatement(statement) => {
+                if let SequentialStatement::Case(case) = &statement.statement.item {
+                    self.add_alternatives(ctx, &case.alternatives);
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Adds the blocks of comments on consecutive lines that precede a token
+fn add_comment_blocks(token: &Token, ranges: &mut Vec<Range>) {
+    let Some(comments) = &token.comments else {
+        return;
+    };
+    let mut block: Option<Range> = None;
+    for Comment { range, .. } in &comments.leading {
+        block = match block {
+            Some(current) if range.start.line == current.end.line + 1 => {
+                Some(Range::new(current.start, range.end))
+            }
+            Some(current) => {
+                ranges.push(current);
+                Some(*range)
+            }
+            None => Some(*range),
+        }
+    }
+    ranges.extend(block);
+}
+
+/// Lists the regions of a source file that can be folded.
+/// Only regions that span multiple lines are returned.
+pub fn list_folding_ranges(root: &DesignRoot, source: &Source) -> Vec<FoldingRange> {
+    let mut regions = FoldingRegions::default();
+    let mut comments = Vec::new();
+    for unit in root.units_by_source(source) {
+        let _ = unit
+            .unit
+            .expect_analyzed()
+            .search(&unit.tokens, &mut regions);
+        for token in &unit.tokens {
+            add_comment_blocks(token, &mut comments);
+        }
+    }
+
+    let mut ranges: Vec<FoldingRange> = regions
+        .ranges
+        .into_iter()
+        .map(|range| FoldingRange {
+            range,
+            kind: FoldingRangeKind::Region,
+        })
+        .chain(comments.into_iter().map(|range| FoldingRange {
+            range,
+            kind: FoldingRangeKind::Comment,
+        }))
+        .filter(|folding| folding.range.end.line > folding.range.start.line)
+        .collect();
+    ranges.sort_by_key(|folding| (folding.range.start, folding.range.end));
+    ranges.dedup();
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+
+    fn folded_lines(text: &str, ranges: &[FoldingRange]) -> Vec<(String, FoldingRangeKind)> {
+        let lines: Vec<&str> = text.lines().collect();
+        ranges
+            .iter()
+            .map(|folding| {
+                (
+                    format!(
+                        "{} .. {}",
+                        lines[folding.range.start.line as usize].trim(),
+                        lines[folding.range.end.line as usize].trim()
+                    ),
+                    folding.kind,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn folds_units_declarative_parts_statements_and_comments() {
+        let text = "\
+-- The entity
+-- under test
+entity ent is
+  port (
+    clk : in bit
+  );
+end entity;
+
+architecture a of ent is
+  signal s0 : bit;
+  signal s1 : bit;
+
+  function f(x : natural) return natural is
+  begin
+    return x;
+  end function;
+begin
+  proc: process
+  begin
+    case s0 is
+      when '0' =>
+        s1 <= '1';
+      when others =>
+        null;
+    end case;
+    wait;
+  end process;
+
+  gen: for i in 0 to 1 generate
+    signal s2 : bit;
+  begin
+  end generate;
+end architecture;";
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code("libname", text);
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let ranges = list_folding_ranges(&root, code.source());
+        assert_eq!(
+            folded_lines(text, &ranges),
+            vec![
+                (
+                    "-- The entity .. -- under test".to_owned(),
+                    FoldingRangeKind::Comment
+                ),
+                (
+                    "entity ent is .. end entity;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                ("port ( .. );".to_owned(), FoldingRangeKind::Region),
+                (
+                    "architecture a of ent is .. end architecture;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                (
+                    "signal s0 : bit; .. end function;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                (
+                    "function f(x : natural) return natural is .. end function;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                (
+                    "proc: process .. end process;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                (
+                    "when '0' => .. s1 <= '1';".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                (
+                    "when others => .. null;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+                (
+                    "gen: for i in 0 to 1 generate .. end generate;".to_owned(),
+                    FoldingRangeKind::Region
+                ),
+            ]
+        );
+    }
+}
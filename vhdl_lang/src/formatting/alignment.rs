@@ -0,0 +1,198 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::formatting::FormatOptions;
+
+/// A line that starts with a simple name followed by an operator,
+/// e.g., the assignment `data <= '0';` or the association `clk => clk,`
+struct OperatorLine<'a> {
+    indent: &'a str,
+    target: &'a str,
+    operator: &'a str,
+    /// The text after the operator
+    value: &'a str,
+}
+
+const ASSIGNMENT_OPERATORS: [&str; 2] = ["<=", ":="];
+const ASSOCIATION_OPERATORS: [&str; 1] = ["=>"];
+
+/// Returns the position of the first of the operators in a line
+/// that is not part of a string, a character literal or a comment
+fn find_operator(code: &str, operators: &[&str]) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += 1;
+                }
+            }
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b'-' if bytes.get(i + 1) == Some(&b'-') => return None,
+            _ if operators
+                .iter()
+                .any(|operator| bytes[i..].starts_with(operator.as_bytes())) =>
+            {
+                return Some(i)
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn operator_line<'a>(line: &'a str, operators: &[&str]) -> Option<OperatorLine<'a>> {
+    let code = line.trim_start();
+    let indent = &line[..line.len() - code.len()];
+    let idx = find_operator(code, operators)?;
+    let target = code[..idx].trim_end();
+    // Excludes declarations, labeled statements and conditions such as `if a <= b then`
+    if target.is_empty() || target.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(OperatorLine {
+        indent,
+        target,
+        operator: &code[idx..idx + 2],
+        value: &code[idx + 2..],
+    })
+}
+
+fn is_aligned_with(lhs: &OperatorLine<'_>, rhs: &OperatorLine<'_>) -> bool {
+    lhs.indent == rhs.indent && lhs.operator == rhs.operator
+}
+
+/// Vertically aligns the operators of consecutive lines
+/// that start with a simple name at the same indentation
+fn align_operators(text: &str, operators: &[&str]) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut result = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(first) = operator_line(lines[i], operators) else {
+            result.push(lines[i].to_owned());
+            i += 1;
+            continue;
+        };
+        let mut group = vec![first];
+        while let Some(next) = lines
+            .get(i + group.len())
+            .and_then(|line| operator_line(line, operators))
+        {
+            if !is_aligned_with(&group[0], &next) {
+                break;
+            }
+            group.push(next);
+        }
+        i += group.len();
+        let width = group
+            .iter()
+            .map(|line| line.target.chars().count())
+            .max()
+            .unwrap_or(0);
+        for line in group {
+            result.push(format!(
+                "{}{:width$} {}{}",
+                line.indent, line.target, line.operator, line.value
+            ));
+        }
+    }
+    result.join("\n")
+}
+
+/// Vertically aligns the assignment operators of consecutive lines
+/// that assign to a simple target at the same indentation
+pub(super) fn align_assignments(text: &str) -> String {
+    align_operators(text, &ASSIGNMENT_OPERATORS)
+}
+
+/// Vertically aligns the `=>` of consecutive named associations at the same indentation
+pub(super) fn align_associations(text: &str) -> String {
+    align_operators(text, &ASSOCIATION_OPERATORS)
+}
+
+/// Applies the alignments that are enabled in the options
+pub(super) fn align(text: String, options: &FormatOptions) -> String {
+    let mut text = text;
+    if options.align_assignments {
+        text = align_assignments(&text);
+    }
+    if options.align_port_maps {
+        text = align_associations(&text);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{align_assignments, align_associations};
+
+    #[test]
+    fn aligns_consecutive_assignments() {
+        let text = "\
+a <= '0';
+data_out <= \"a <= b\"; -- x := y
+if a <= b then
+    v := 1;
+    count := 0;
+end if;
+c <= a;";
+        let aligned = align_assignments(text);
+        assert_eq!(
+            aligned,
+            "\
+a        <= '0';
+data_out <= \"a <= b\"; -- x := y
+if a <= b then
+    v     := 1;
+    count := 0;
+end if;
+c <= a;"
+        );
+        assert_eq!(align_assignments(&aligned), aligned);
+    }
+
+    #[test]
+    fn aligns_targets_with_non_ascii_characters() {
+        let text = "\
+größe <= 1;
+ab <= 2; -- é";
+        let aligned = align_assignments(text);
+        assert_eq!(
+            aligned,
+            "\
+größe <= 1;
+ab    <= 2; -- é"
+        );
+        assert_eq!(align_assignments(&aligned), aligned);
+    }
+
+    #[test]
+    fn aligns_consecutive_associations() {
+        let text = "\
+inst: entity work.ent
+    port map (
+        clk => clk,
+        data_out => \"=>\", -- a => b
+        rst => open
+    );";
+        let aligned = align_associations(text);
+        assert_eq!(
+            aligned,
+            "\
+inst: entity work.ent
+    port map (
+        clk      => clk,
+        data_out => \"=>\", -- a => b
+        rst      => open
+    );"
+        );
+        assert_eq!(align_associations(&aligned), aligned);
+    }
+}
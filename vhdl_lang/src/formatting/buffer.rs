@@ -5,11 +5,17 @@
 //
 // Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
 
+use crate::code_action::text_at;
+use crate::config::Casing;
+use crate::formatting::FormatOptions;
 use crate::syntax::{Comment, Value};
 use crate::{kind_str, Token};
 use std::cmp::max;
 use std::iter;
 
+/// The number of columns that a tab occupies when measuring the width of a line
+const TAB_WIDTH: usize = 4;
+
 /// The Buffer is the (mostly) mutable object used to write tokens to a string.
 /// It operates mostly on tokens and is capable of indenting,
 /// de-indenting and keeping the indentation level.
@@ -24,16 +30,34 @@ pub struct Buffer {
     indent_char: char,
     /// The width used at each indentation level
     indent_width: usize,
+    /// The casing of keywords
+    keyword_case: Casing,
+    /// The casing of identifiers
+    identifier_case: Casing,
+    /// The width that port and generic maps must fit in to be written on a single line
+    max_line_width: Option<usize>,
 }
 
 impl Buffer {
     pub fn new() -> Buffer {
+        Buffer::with_options(&FormatOptions::default())
+    }
+
+    pub fn with_options(options: &FormatOptions) -> Buffer {
+        let (indent_char, indent_width) = if options.use_tabs {
+            ('\t', 1)
+        } else {
+            (' ', options.indent_width)
+        };
         Buffer {
             inner: String::new(),
             insert_extra_newline: false,
             indentation: 0,
-            indent_char: ' ',
-            indent_width: 4,
+            indent_char,
+            indent_width,
+            keyword_case: options.keyword_case,
+            identifier_case: options.identifier_case,
+            max_line_width: options.max_line_width,
         }
     }
 }
@@ -122,11 +146,25 @@ impl Buffer {
                 self.format_comment(&comments.leading[0]);
                 self.push_ch(' ');
             } else if !comments.leading.is_empty() {
+                // A comment on its own line must not be pulled up behind the previous token
+                if !self.is_at_line_start() {
+                    self.trim_trailing_whitespace();
+                    self.line_break();
+                }
                 self.format_leading_comments(comments.leading.as_slice());
             }
         }
         match &token.value {
-            Value::Identifier(ident) => self.push_str(&ident.to_string()),
+            Value::Identifier(ident) => {
+                let ident = ident.to_string();
+                match self.identifier_case {
+                    // Extended identifiers are case-sensitive
+                    _ if ident.starts_with('\\') => self.push_str(&ident),
+                    Casing::Upper => self.push_str(&ident.to_uppercase()),
+                    Casing::Lower => self.push_str(&ident.to_lowercase()),
+                    Casing::Keep | Casing::Declaration => self.push_str(&ident),
+                }
+            }
             Value::String(string) => {
                 self.push_ch('"');
                 for byte in &string.bytes {
@@ -147,7 +185,11 @@ impl Buffer {
                 self.push_ch('\'');
             }
             Value::Text(text) => self.push_str(&text.to_string()),
-            Value::None => self.push_str(kind_str(token.kind)),
+            Value::None => match self.keyword_case {
+                Casing::Upper => self.push_str(&kind_str(token.kind).to_uppercase()),
+                Casing::Keep => self.push_str(&text_at(&token.pos.source, token.pos.range)),
+                Casing::Lower | Casing::Declaration => self.push_str(kind_str(token.kind)),
+            },
         }
         if let Some(comments) = &token.comments {
             if let Some(trailing_comment) = &comments.trailing {
@@ -158,6 +200,56 @@ impl Buffer {
         }
     }
 
+    /// Returns an empty buffer with the same settings and indentation as this buffer
+    pub fn empty_copy(&self) -> Buffer {
+        Buffer {
+            inner: String::new(),
+            insert_extra_newline: false,
+            ..*self
+        }
+    }
+
+    /// Appends text of a buffer that fits on the current line of this buffer
+    /// and returns whether it fits, i.e., does not contain line breaks and
+    /// does not exceed the maximum line width.
+    pub fn push_if_fits(&mut self, other: &Buffer) -> bool {
+        let Some(max_line_width) = self.max_line_width else {
+            return false;
+        };
+        if self.insert_extra_newline || other.insert_extra_newline || other.inner.contains('\n') {
+            return false;
+        }
+        let column = self
+            .current_line()
+            .chars()
+            .map(|ch| if ch == '\t' { TAB_WIDTH } else { 1 })
+            .sum::<usize>();
+        if column + other.inner.chars().count() > max_line_width {
+            return false;
+        }
+        self.push_str(&other.inner);
+        true
+    }
+
+    /// Whether maps may be written on a single line
+    pub fn has_max_line_width(&self) -> bool {
+        self.max_line_width.is_some()
+    }
+
+    /// Whether the current line contains nothing but indentation
+    fn is_at_line_start(&self) -> bool {
+        self.current_line().chars().all(char::is_whitespace)
+    }
+
+    fn current_line(&self) -> &str {
+        &self.inner[self.inner.rfind('\n').map_or(0, |idx| idx + 1)..]
+    }
+
+    fn trim_trailing_whitespace(&mut self) {
+        self.inner
+            .truncate(self.inner.trim_end_matches([' ', '\t']).len());
+    }
+
     fn push_str(&mut self, value: &str) {
         self.inner.push_str(value);
     }
@@ -180,6 +272,11 @@ impl Buffer {
         self.indentation -= 1;
     }
 
+    /// Indents the current line, e.g., when the buffer starts within an indented block
+    pub fn push_indentation(&mut self) {
+        self.indent();
+    }
+
     /// Inserts a line break (i.e., newline) at the current position
     pub fn line_break(&mut self) {
         self.insert_extra_newline = false;
@@ -202,7 +299,9 @@ impl Buffer {
 #[cfg(test)]
 mod tests {
     use crate::analysis::tests::Code;
+    use crate::config::Casing;
     use crate::formatting::buffer::Buffer;
+    use crate::formatting::FormatOptions;
     use std::iter::zip;
 
     fn check_token_formatted(input: &str, expected: &[&str]) {
@@ -227,6 +326,20 @@ mod tests {
         check_token_formatted("FooBar foobar", &["FooBar", "foobar"]);
     }
 
+    #[test]
+    fn changes_identifier_casing() {
+        let code = Code::new("FooBar \\FooBar\\");
+        let options = FormatOptions {
+            identifier_case: Casing::Upper,
+            ..FormatOptions::default()
+        };
+        let mut buffer = Buffer::with_options(&options);
+        for token in code.tokenize() {
+            buffer.push_token(&token);
+        }
+        assert_eq!(buffer.as_str(), "FOOBAR\\FooBar\\");
+    }
+
     #[test]
     fn character_formatting() {
         check_token_formatted("'a' 'Z' '''", &["'a'", "'Z'", "'''"]);
@@ -841,6 +841,14 @@ end view;",
         );
     }
 
+    #[test]
+    fn format_conditional_expression() {
+        check_declaration_std(
+            "constant c: natural := 1 when cond else 2 when not cond else 3;",
+            VHDL2019,
+        );
+    }
+
     #[test]
     fn format_configuration_specification() {
         check_declaration(
@@ -51,6 +51,11 @@ impl VHDLFormatter<'_> {
                 self.format_expression(expression.as_ref().as_ref(), buffer);
                 self.format_token_id(span.end_token, buffer);
             }
+            Conditional(conditionals) => self.format_assignment_right_hand_conditionals(
+                conditionals,
+                |formatter, expr, buffer| formatter.format_expression(expr.as_ref(), buffer),
+                buffer,
+            ),
         }
     }
 
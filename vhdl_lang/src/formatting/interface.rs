@@ -62,6 +62,13 @@ impl VHDLFormatter<'_> {
         span: TokenSpan,
         buffer: &mut Buffer,
     ) {
+        if buffer.has_max_line_width() {
+            let mut line = buffer.empty_copy();
+            self.format_map_aspect_on_line(list, span, &mut line);
+            if buffer.push_if_fits(&line) {
+                return;
+            }
+        }
         // port map (
         // generic map (
         self.format_token_span(
@@ -84,6 +91,27 @@ impl VHDLFormatter<'_> {
         self.format_token_id(span.end_token, buffer);
     }
 
+    /// Formats a map aspect on a single line, e.g., `port map (a => b, c => d)`
+    fn format_map_aspect_on_line(
+        &self,
+        list: &SeparatedList<AssociationElement>,
+        span: TokenSpan,
+        buffer: &mut Buffer,
+    ) {
+        self.format_token_span(
+            TokenSpan::new(span.start_token, span.start_token + 2),
+            buffer,
+        );
+        for (i, item) in list.items.iter().enumerate() {
+            self.format_association_element(item, buffer);
+            if let Some(token) = list.tokens.get(i) {
+                self.format_token_id(*token, buffer);
+                buffer.push_whitespace();
+            }
+        }
+        self.format_token_id(span.end_token, buffer);
+    }
+
     pub fn format_map_aspect(&self, aspect: &MapAspect, buffer: &mut Buffer) {
         self.format_map_aspect_span(&aspect.list, aspect.span, buffer);
     }
@@ -6,11 +6,13 @@
 // Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
 
 use crate::ast::DesignFile;
+use crate::config::Casing;
 use crate::formatting::buffer::Buffer;
-use crate::syntax::Kind;
-use crate::{Token, TokenAccess};
+use crate::syntax::{Kind, VHDLParser};
+use crate::{Source, Token, TokenAccess, VHDLStandard};
 use vhdl_lang::ast::HasIdent;
 
+mod alignment;
 mod architecture;
 mod buffer;
 mod concurrent_statement;
@@ -23,11 +25,71 @@ mod entity;
 mod expression;
 mod interface;
 mod name;
+mod on_type;
+mod range;
 mod sequential_statement;
 mod statement;
 mod subprogram;
 mod token;
 
+pub use on_type::format_on_type;
+pub use range::format_range;
+
+/// Options that control the output of the formatter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// The number of spaces per indentation level
+    pub indent_width: usize,
+    /// Indent with one tab per indentation level instead of spaces
+    pub use_tabs: bool,
+    /// The casing of keywords
+    pub keyword_case: Casing,
+    /// The casing of identifiers. Extended identifiers are always written as they are.
+    pub identifier_case: Casing,
+    /// Vertically align the assignment operators of consecutive assignments
+    pub align_assignments: bool,
+    /// Vertically align the `=>` of the associations of port and generic maps
+    pub align_port_maps: bool,
+    /// Port and generic maps that fit within this width are written on a single line
+    pub max_line_width: Option<usize>,
+    /// The standard that is used to parse the source
+    pub standard: VHDLStandard,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent_width: 4,
+            use_tabs: false,
+            keyword_case: Casing::Lower,
+            identifier_case: Casing::Keep,
+            align_assignments: false,
+            align_port_maps: false,
+            max_line_width: None,
+            standard: VHDLStandard::default(),
+        }
+    }
+}
+
+/// Parses a source that is formatted, or returns `None` when it contains syntax errors
+fn parse(source: &Source, options: &FormatOptions) -> Option<DesignFile> {
+    let mut diagnostics = Vec::new();
+    let file = VHDLParser::new(options.standard).parse_vhdl_source(source, &mut diagnostics);
+    diagnostics.is_empty().then_some(file)
+}
+
+/// Formats a VHDL source file.
+/// Returns `None` when the source cannot be parsed without errors,
+/// as the formatted text would then lose code.
+pub fn format_source(source: &Source, options: &FormatOptions) -> Option<String> {
+    let file = parse(source, options)?;
+    let mut text = VHDLFormatter::format_design_file_with_options(&file, options);
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    Some(text)
+}
+
 /// The formatter is the main entry point used for formatting a single
 /// Design Unit from AST representation to string representation. In that sense,
 /// the Formatter is the inverse to the Parser.
@@ -48,7 +110,12 @@ impl<'b> VHDLFormatter<'b> {
 
     /// Format a whole design file.
     pub fn format_design_file(file: &DesignFile) -> String {
-        let mut result = Buffer::new();
+        VHDLFormatter::format_design_file_with_options(file, &FormatOptions::default())
+    }
+
+    /// Format a whole design file using the given options.
+    pub fn format_design_file_with_options(file: &DesignFile, options: &FormatOptions) -> String {
+        let mut result = Buffer::with_options(options);
         for (i, (tokens, design_unit)) in file.design_units.iter().enumerate() {
             let formatter = VHDLFormatter::new(tokens);
             formatter.format_any_design_unit(
@@ -57,7 +124,7 @@ impl<'b> VHDLFormatter<'b> {
                 i == file.design_units.len() - 1,
             );
         }
-        result.into()
+        alignment::align(result.into(), options)
     }
 }
 
@@ -120,3 +187,144 @@ pub mod test_utils {
         assert_eq!(buffer.as_str(), expected);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn formats_source_with_options() {
+        let code = Code::new(
+            "\
+ENTITY ent IS
+END ENTITY;
+architecture a of ent is
+  signal a, data : bit;
+begin
+  process begin
+  a <= '1';
+      data <= a;
+  wait;
+  end process;
+end architecture;",
+        );
+        let options = FormatOptions {
+            use_tabs: true,
+            keyword_case: Casing::Upper,
+            align_assignments: true,
+            ..FormatOptions::default()
+        };
+        let formatted = format_source(code.source(), &options).unwrap();
+        assert_eq!(
+            formatted,
+            "\
+ENTITY ent IS
+END ENTITY;
+
+ARCHITECTURE a OF ent IS
+\tSIGNAL a, data: bit;
+BEGIN
+\tPROCESS
+\tBEGIN
+\t\ta    <= '1';
+\t\tdata <= a;
+\t\tWAIT;
+\tEND PROCESS;
+END ARCHITECTURE;
+"
+        );
+
+        // The output is stable
+        let code = Code::new(&formatted);
+        assert_eq!(format_source(code.source(), &options).unwrap(), formatted);
+    }
+
+    #[test]
+    fn formats_maps_with_options() {
+        let code = Code::new(
+            "\
+architecture a of ent is
+begin
+  Inst: entity work.Foo generic map (Width => 8) port map (Clk => Clk, Data_Out => Data);
+  inst2: entity work.foo port map (clk => clk, data_out => a_very_long_signal_name);
+end architecture;",
+        );
+        let options = FormatOptions {
+            identifier_case: Casing::Lower,
+            align_port_maps: true,
+            max_line_width: Some(60),
+            ..FormatOptions::default()
+        };
+        let formatted = format_source(code.source(), &options).unwrap();
+        assert_eq!(
+            formatted,
+            "\
+architecture a of ent is
+begin
+    inst: entity work.foo
+        generic map (width => 8)
+        port map (clk => clk, data_out => data);
+    inst2: entity work.foo
+        port map (
+            clk      => clk,
+            data_out => a_very_long_signal_name
+        );
+end architecture;
+"
+        );
+
+        // The output is stable
+        let code = Code::new(&formatted);
+        assert_eq!(format_source(code.source(), &options).unwrap(), formatted);
+    }
+
+    #[test]
+    fn keeps_comments_on_their_own_line() {
+        let code = Code::new(
+            "\
+package body pkg is
+  constant table : table_t := (
+    -- first row
+    ('0', '1'),
+    ('1', '0')
+  );
+  procedure proc is
+    variable v : natural;
+    -- unused
+  begin
+  end procedure;
+end package body;",
+        );
+        let options = FormatOptions::default();
+        let formatted = format_source(code.source(), &options).unwrap();
+        assert_eq!(
+            formatted,
+            "\
+package body pkg is
+    constant table: table_t := (
+    -- first row
+    ('0', '1'), ('1', '0'));
+    procedure proc is
+        variable v: natural;
+    -- unused
+    begin
+    end procedure;
+end package body;
+"
+        );
+
+        // The output is stable
+        let code = Code::new(&formatted);
+        assert_eq!(format_source(code.source(), &options).unwrap(), formatted);
+    }
+
+    #[test]
+    fn does_not_format_source_with_syntax_errors() {
+        let code = Code::new("entity ent is\nend entity");
+        assert_eq!(
+            format_source(code.source(), &FormatOptions::default()),
+            None
+        );
+    }
+}
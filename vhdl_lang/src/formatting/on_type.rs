@@ -0,0 +1,518 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::code_action::{text_at, TextEdit};
+use crate::data::{ContentReader, Contents};
+use crate::formatting::FormatOptions;
+use crate::syntax::{Kind, Symbols, Tokenizer};
+use crate::{Position, Range, Source};
+
+/// Tokens at the end of a line after which the following lines are indented
+const OPENERS: [Kind; 9] = [
+    Kind::Is,
+    Kind::Begin,
+    Kind::Then,
+    Kind::Else,
+    Kind::Loop,
+    Kind::Generate,
+    Kind::Record,
+    Kind::Units,
+    Kind::Protected,
+];
+
+/// The kinds of the tokens of a line
+struct Line {
+    line: u32,
+    kinds: Vec<Kind>,
+}
+
+impl Line {
+    fn first(&self) -> Option<Kind> {
+        self.kinds.first().copied()
+    }
+
+    fn last(&self) -> Option<Kind> {
+        self.kinds.last().copied()
+    }
+
+    /// The first token after a label
+    fn first_keyword(&self) -> Option<Kind> {
+        match self.kinds.as_slice() {
+            [Kind::Identifier, Kind::Colon, kind, ..] => Some(*kind),
+            [kind, ..] => Some(*kind),
+            [] => None,
+        }
+    }
+
+    fn paren_delta(&self) -> i32 {
+        self.kinds
+            .iter()
+            .map(|kind| match kind {
+                Kind::LeftPar => 1,
+                Kind::RightPar => -1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Lines that continue a block that was opened before, e.g., `begin` or `else`
+    fn is_middle(&self) -> bool {
+        matches!(
+            self.first(),
+            Some(Kind::Begin | Kind::Else | Kind::Elsif | Kind::When)
+        )
+    }
+
+    fn is_close(&self) -> bool {
+        self.first() == Some(Kind::End)
+    }
+
+    /// Lines that start a block that is closed by an `end`
+    fn opens_block(&self) -> bool {
+        if self.is_close() || self.is_middle() {
+            return false;
+        }
+        self.last().is_some_and(|kind| OPENERS.contains(&kind))
+            || (self.last() != Some(Kind::SemiColon)
+                && (matches!(self.first_keyword(), Some(Kind::Process | Kind::Block))
+                    || self.first() == Some(Kind::Component)))
+    }
+
+    /// Returns true when the lines that follow are indented
+    fn opens_next(&self) -> bool {
+        self.last().is_some_and(|kind| OPENERS.contains(&kind))
+            || (self.first() == Some(Kind::When) && self.last() == Some(Kind::RightArrow))
+            || self.opens_block()
+    }
+
+    /// Returns true when the statement or declaration of the line ends on the line
+    fn terminates(&self) -> bool {
+        matches!(self.last(), Some(Kind::SemiColon | Kind::Comma))
+            || self.opens_next()
+            || self.paren_delta() != 0
+    }
+}
+
+/// Groups the tokens up to and including a line by their line
+fn lines_until(source: &Source, symbols: &Symbols, last_line: u32) -> Vec<Line> {
+    let contents = source.contents();
+    let mut tokenizer = Tokenizer::new(symbols, source, ContentReader::new(&contents));
+    let mut lines: Vec<Line> = Vec::new();
+    // Tokens after an error cannot be trusted, but the lines before are still indented
+    while let Ok(Some(token)) = tokenizer.pop() {
+        let line = token.pos.start().line;
+        if line > last_line {
+            break;
+        }
+        match lines.last_mut() {
+            Some(last) if last.line == line => last.kinds.push(token.kind),
+            _ => lines.push(Line {
+                line,
+                kinds: vec![token.kind],
+            }),
+        }
+    }
+    lines
+}
+
+/// Indents the lines of a source file using the tokens of the lines before,
+/// without requiring the code to be syntactically correct
+struct IndentEngine<'a> {
+    contents: &'a Contents,
+    lines: Vec<Line>,
+    step: usize,
+    options: &'a FormatOptions,
+    /// The new indentation of lines that were re-indented
+    reindented: Vec<(u32, usize)>,
+}
+
+impl IndentEngine<'_> {
+    /// The width of the indentation of a line, where a tab is one indentation level
+    fn indent_of(&self, line: u32) -> usize {
+        if let Some((_, width)) = self.reindented.iter().find(|(other, _)| *other == line) {
+            return *width;
+        }
+        self.contents
+            .get_line(line as usize)
+            .unwrap_or("")
+            .chars()
+            .take_while(|chr| *chr == ' ' || *chr == '\t')
+            .map(|chr| if chr == '\t' { self.step } else { 1 })
+            .sum()
+    }
+
+    fn indent_of_idx(&self, idx: usize) -> usize {
+        self.indent_of(self.lines[idx].line)
+    }
+
+    /// The line of the block that is continued or closed by the line at `idx`
+    fn find_opener(&self, idx: usize) -> Option<usize> {
+        let mut depth = 0;
+        for j in (0..idx).rev() {
+            let line = &self.lines[j];
+            if line.is_close() {
+                depth += 1;
+            } else if line.opens_block() {
+                if depth == 0 {
+                    return Some(j);
+                }
+                depth -= 1;
+            }
+        }
+        None
+    }
+
+    /// The line of an opening parenthesis that is not closed before the line at `idx`,
+    /// skipping `depth` unclosed parentheses
+    fn find_open_paren(&self, idx: usize, mut depth: usize) -> Option<usize> {
+        for j in (0..idx).rev() {
+            for kind in self.lines[j].kinds.iter().rev() {
+                match kind {
+                    Kind::RightPar => depth += 1,
+                    Kind::LeftPar if depth == 0 => return Some(j),
+                    Kind::LeftPar => depth -= 1,
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// The first line of the statement or declaration that ends at the line at `idx`
+    fn statement_start(&self, mut idx: usize) -> usize {
+        while idx > 0 && !self.lines[idx - 1].terminates() {
+            idx -= 1;
+        }
+        idx
+    }
+
+    /// The indentation of the line at `idx` that follows from the lines before.
+    /// Returns `None` for lines that continue a statement of the line before.
+    fn indentation(&self, idx: usize) -> Option<usize> {
+        let current = &self.lines[idx];
+        match current.first() {
+            Some(Kind::End | Kind::Begin | Kind::Else | Kind::Elsif) => {
+                return self.find_opener(idx).map(|j| self.indent_of_idx(j));
+            }
+            Some(Kind::When) => {
+                if let Some(j) = self.find_opener(idx) {
+                    if self.lines[j].first_keyword() == Some(Kind::Case) {
+                        return Some(self.indent_of_idx(j) + self.step);
+                    }
+                }
+            }
+            Some(Kind::RightPar) => {
+                return self.find_open_paren(idx, 0).map(|j| self.indent_of_idx(j));
+            }
+            _ => {}
+        }
+
+        let Some(prev) = idx.checked_sub(1) else {
+            return Some(0);
+        };
+        let previous = &self.lines[prev];
+        let delta = previous.paren_delta();
+        let base = if delta < 0 {
+            let j = self.find_open_paren(prev, (-delta - 1) as usize)?;
+            self.indent_of_idx(self.statement_start(j))
+        } else if delta > 0 {
+            return Some(self.indent_of_idx(prev) + self.step);
+        } else if previous.terminates() {
+            self.indent_of_idx(self.statement_start(prev))
+        } else {
+            return None;
+        };
+        if previous.opens_next() {
+            Some(base + self.step)
+        } else {
+            Some(base)
+        }
+    }
+
+    fn whitespace(&self, width: usize) -> String {
+        if self.options.use_tabs {
+            format!(
+                "{}{}",
+                "\t".repeat(width / self.step),
+                " ".repeat(width % self.step)
+            )
+        } else {
+            " ".repeat(width)
+        }
+    }
+
+    /// Replaces the indentation of a line when it differs from the computed indentation
+    fn reindent(&mut self, line: u32) -> Option<TextEdit> {
+        let idx = match self.lines.iter().position(|other| other.line == line) {
+            Some(idx) => idx,
+            None => {
+                // Empty lines are indented like the first token that would be written on them
+                let idx = self.lines.partition_point(|other| other.line < line);
+                self.lines.insert(
+                    idx,
+                    Line {
+                        line,
+                        kinds: Vec::new(),
+                    },
+                );
+                idx
+            }
+        };
+        let width = self.indentation(idx)?;
+        // Lines that are indented later depend on the new indentation
+        self.reindented.push((line, width));
+        let text = self.contents.get_line(line as usize).unwrap_or("");
+        let old: String = text
+            .chars()
+            .take_while(|chr| *chr == ' ' || *chr == '\t')
+            .collect();
+        let new = self.whitespace(width);
+        if old == new {
+            return None;
+        }
+        Some(TextEdit::new(
+            Range::new(
+                Position::new(line, 0),
+                Position::new(line, old.encode_utf16().count() as u32),
+            ),
+            new,
+        ))
+    }
+}
+
+/// Re-indents the current line after a character was typed.
+/// - `;` and `end` re-indent the line that they are typed on, e.g., to dedent `end if;`
+/// - A newline indents the new line, e.g., after `then` or `begin`, and re-indents
+///   the previous line when it continues or closes a block.
+///
+/// The indentation is derived from the tokens of the lines before,
+/// so that code that is being typed does not have to be syntactically correct.
+pub fn format_on_type(
+    source: &Source,
+    position: Position,
+    ch: &str,
+    options: &FormatOptions,
+) -> Vec<TextEdit> {
+    let lines_to_indent = match ch {
+        ";" => vec![position.line],
+        "\n" => {
+            let mut lines = Vec::new();
+            if let Some(previous) = position.line.checked_sub(1) {
+                lines.push(previous);
+            }
+            lines.push(position.line);
+            lines
+        }
+        "d" => {
+            // The character of the position counts UTF-16 code units
+            let typed = text_at(
+                source,
+                Range::new(Position::new(position.line, 0), position),
+            );
+            if !typed.trim().eq_ignore_ascii_case("end") {
+                return vec![];
+            }
+            vec![position.line]
+        }
+        _ => return vec![],
+    };
+
+    let symbols = Symbols::from_standard(options.standard);
+    let lines = lines_until(source, &symbols, position.line);
+    let contents = source.contents();
+    let mut engine = IndentEngine {
+        contents: &contents,
+        lines,
+        step: options.indent_width.max(1),
+        options,
+        reindented: Vec::new(),
+    };
+    let mut edits = Vec::new();
+    for line in lines_to_indent {
+        // The previous line is only changed when it is determined by a block or parenthesis
+        if line != position.line
+            && !engine.lines.iter().any(|other| {
+                other.line == line
+                    && (other.is_close()
+                        || other.is_middle()
+                        || other.first() == Some(Kind::RightPar))
+            })
+        {
+            continue;
+        }
+        edits.extend(engine.reindent(line));
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{CodeAction, CodeActionKind, WorkspaceEdit};
+    use crate::syntax::test::Code;
+
+    fn typed(code: &str, ch: &str) -> String {
+        typed_with(code, ch, &FormatOptions::default())
+    }
+
+    /// Types a character at the cursor `|` and applies the edits
+    fn typed_with(code: &str, ch: &str, options: &FormatOptions) -> String {
+        let offset = code.find('|').unwrap();
+        let before = &code[..offset];
+        let line = before.matches('\n').count() as u32;
+        let character = before.rsplit('\n').next().unwrap().encode_utf16().count() as u32;
+        let code = Code::new(&code.replace('|', ""));
+        let mut edit = WorkspaceEdit::default();
+        for text_edit in format_on_type(code.source(), Position::new(line, character), ch, options)
+        {
+            edit.add(code.source().file_name(), text_edit);
+        }
+        apply_edits(
+            code.source(),
+            &CodeAction {
+                title: String::new(),
+                kind: CodeActionKind::RefactorRewrite,
+                edit,
+            },
+        )
+    }
+
+    #[test]
+    fn dedents_end_of_block() {
+        assert_eq!(
+            typed(
+                "\
+process
+begin
+    if a then
+        b := 1;
+        end if;|
+",
+                ";"
+            ),
+            "\
+process
+begin
+    if a then
+        b := 1;
+    end if;
+"
+        );
+        assert_eq!(
+            typed(
+                "\
+architecture a of e is
+begin
+    end|
+",
+                "d"
+            ),
+            "\
+architecture a of e is
+begin
+end
+"
+        );
+    }
+
+    #[test]
+    fn dedents_end_on_lines_with_non_ascii_text() {
+        assert_eq!(
+            typed(
+                "\
+process
+begin
+    if a then -- 😀
+        b := \"ü\";
+        end| -- 😀 ü
+",
+                "d"
+            ),
+            "\
+process
+begin
+    if a then -- 😀
+        b := \"ü\";
+    end -- 😀 ü
+"
+        );
+    }
+
+    #[test]
+    fn indents_new_line_after_block_start() {
+        assert_eq!(
+            typed("begin\n    if a then\n|\n", "\n"),
+            "begin\n    if a then\n        \n"
+        );
+        assert_eq!(typed("process (clk)\n|", "\n"), "process (clk)\n    ");
+        let options = FormatOptions {
+            use_tabs: true,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            typed_with("begin\n\tx := 1;\n\tloop\n|", "\n", &options),
+            "begin\n\tx := 1;\n\tloop\n\t\t"
+        );
+    }
+
+    #[test]
+    fn indents_case_alternatives_and_closing_parenthesis() {
+        assert_eq!(
+            typed(
+                "\
+case s is
+    when a =>
+        null;
+        when b =>
+|",
+                "\n"
+            ),
+            "\
+case s is
+    when a =>
+        null;
+    when b =>
+        "
+        );
+        assert_eq!(
+            typed(
+                "\
+port map (
+    a => b,
+    c => d
+    );|",
+                ";"
+            ),
+            "\
+port map (
+    a => b,
+    c => d
+);"
+        );
+    }
+
+    #[test]
+    fn keeps_continuation_lines() {
+        let code = "\
+x <= a or
+         b;|";
+        assert_eq!(typed(code, ";"), code.replace('|', ""));
+        assert_eq!(
+            typed(
+                "\
+x <= a or
+     b;
+    y <= c;|",
+                ";"
+            ),
+            "\
+x <= a or
+     b;
+y <= c;"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::{
+    AnyDesignUnit, AnyPrimaryUnit, AnySecondaryUnit, ConcurrentStatement, Declaration,
+    LabeledConcurrentStatement, LabeledSequentialStatement,
+};
+use crate::code_action::TextEdit;
+use crate::formatting::buffer::Buffer;
+use crate::formatting::{alignment, parse, FormatOptions, VHDLFormatter};
+use crate::{HasTokenSpan, Position, Range, Source, TokenAccess};
+
+/// A list of declarations or statements that is formatted on its own
+#[derive(Clone, Copy)]
+enum Items<'a> {
+    Declarations(&'a [WithTokenSpan<Declaration>]),
+    Concurrent(&'a [LabeledConcurrentStatement]),
+    Sequential(&'a [LabeledSequentialStatement]),
+}
+
+impl<'a> Items<'a> {
+    fn len(&self) -> usize {
+        match self {
+            Items::Declarations(items) => items.len(),
+            Items::Concurrent(items) => items.len(),
+            Items::Sequential(items) => items.len(),
+        }
+    }
+
+    fn span(&self, i: usize) -> crate::TokenSpan {
+        match self {
+            Items::Declarations(items) => items[i].span(),
+            Items::Concurrent(items) => items[i].span(),
+            Items::Sequential(items) => items[i].span(),
+        }
+    }
+
+    fn slice(self, first: usize, last: usize) -> Items<'a> {
+        match self {
+            Items::Declarations(items) => Items::Declarations(&items[first..=last]),
+            Items::Concurrent(items) => Items::Concurrent(&items[first..=last]),
+            Items::Sequential(items) => Items::Sequential(&items[first..=last]),
+        }
+    }
+
+    /// The lists of declarations and statements that are nested in an item
+    fn nested(&self, i: usize) -> Vec<Items<'a>> {
+        match self {
+            Items::Declarations(items) => match &items[i].item {
+                Declaration::SubprogramBody(body) => vec![
+                    Items::Declarations(&body.declarations),
+                    Items::Sequential(&body.statements),
+                ],
+                _ => vec![],
+            },
+            Items::Concurrent(items) => match &items[i].statement.item {
+                ConcurrentStatement::Process(process) => vec![
+                    Items::Declarations(&process.decl),
+                    Items::Sequential(&process.statements),
+                ],
+                ConcurrentStatement::Block(block) => vec![
+                    Items::Declarations(&block.decl),
+                    Items::Concurrent(&block.statements),
+                ],
+                _ => vec![],
+            },
+            Items::Sequential(_) => vec![],
+        }
+    }
+}
+
+/// The range of the code of an item, including its comments
+fn full_range(ctx: &dyn TokenAccess, items: &Items<'_>, i: usize) -> Range {
+    let span = items.span(i);
+    Range::new(
+        ctx.get_token(span.start_token)
+            .map(|token| token.full_range().start)
+            .unwrap_or_default(),
+        ctx.get_token(span.end_token)
+            .map(|token| token.full_range().end)
+            .unwrap_or_default(),
+    )
+}
+
+/// Selects the innermost items that intersect the range, together with their indentation level
+fn select<'a>(
+    ctx: &dyn TokenAccess,
+    lists: Vec<Items<'a>>,
+    level: usize,
+    range: Range,
+) -> Option<(Items<'a>, usize)> {
+    for items in lists {
+        let intersecting: Vec<usize> = (0..items.len())
+            .filter(|i| {
+                let item = full_range(ctx, &items, *i);
+                item.start <= range.end && range.start <= item.end
+            })
+            .collect();
+        let (Some(first), Some(last)) = (intersecting.first(), intersecting.last()) else {
+            continue;
+        };
+        if first == last {
+            let item = full_range(ctx, &items, *first);
+            if item.start <= range.start && range.end <= item.end {
+                if let Some(nested) = select(ctx, items.nested(*first), level + 1, range) {
+                    return Some(nested);
+                }
+            }
+        }
+        return Some((items.slice(*first, *last), level));
+    }
+    None
+}
+
+fn unit_items(unit: &AnyDesignUnit) -> Vec<Items<'_>> {
+    match unit {
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) => vec![
+            Items::Declarations(&entity.decl),
+            Items::Concurrent(&entity.statements),
+        ],
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)) => {
+            vec![Items::Declarations(&package.decl)]
+        }
+        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(architecture)) => vec![
+            Items::Declarations(&architecture.decl),
+            Items::Concurrent(&architecture.statements),
+        ],
+        AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(body)) => {
+            vec![Items::Declarations(&body.decl)]
+        }
+        _ => vec![],
+    }
+}
+
+impl VHDLFormatter<'_> {
+    fn format_items(&self, items: Items<'_>, buffer: &mut Buffer) {
+        for i in 0..items.len() {
+            match items {
+                Items::Declarations(items) => self.format_declaration(&items[i], buffer),
+                Items::Concurrent(items) => {
+                    self.format_labeled_concurrent_statement(&items[i], buffer)
+                }
+                Items::Sequential(items) => {
+                    self.format_labeled_sequential_statement(&items[i], buffer)
+                }
+            }
+            if i + 1 < items.len() {
+                self.line_break_preserve_whitespace(items.span(i).end_token, buffer);
+            }
+        }
+    }
+}
+
+/// Formats the declarations or statements that intersect the range.
+/// Only the smallest list of declarations or statements that contains the range is changed,
+/// e.g., the statements of a process when the range is within the process,
+/// and the surrounding text stays as it is.
+/// Returns `None` when the source contains syntax errors or when nothing changes.
+pub fn format_range(source: &Source, range: Range, options: &FormatOptions) -> Option<TextEdit> {
+    let file = parse(source, options)?;
+    let (tokens, items, level) = file.design_units.iter().find_map(|(tokens, unit)| {
+        let (items, level) = select(tokens, unit_items(unit), 1, range)?;
+        Some((tokens, items, level))
+    })?;
+
+    let first = full_range(tokens, &items, 0);
+    let last = full_range(tokens, &items, items.len() - 1);
+    let contents = source.contents();
+    let line = contents.get_line(first.start.line as usize)?;
+    let prefix: String = line
+        .chars()
+        .scan(0, |character, chr| {
+            *character += chr.len_utf16() as u32;
+            Some((*character, chr))
+        })
+        .take_while(|(character, _)| *character <= first.start.character)
+        .map(|(_, chr)| chr)
+        .collect();
+    drop(contents);
+
+    let formatter = VHDLFormatter::new(tokens);
+    let mut buffer = Buffer::with_options(options);
+    for _ in 0..level {
+        buffer.increase_indent();
+    }
+    // Items on a line of their own are re-indented, others keep the code before them
+    let start = if prefix.trim().is_empty() {
+        buffer.push_indentation();
+        Position::new(first.start.line, 0)
+    } else {
+        first.start
+    };
+    formatter.format_items(items, &mut buffer);
+    let new_text = alignment::align(buffer.into(), options);
+
+    let range = Range::new(start, last.end);
+    if new_text == crate::code_action::text_at(source, range) {
+        return None;
+    }
+    Some(TextEdit::new(range, new_text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code_action::tests::apply_edits;
+    use crate::code_action::{CodeAction, CodeActionKind, WorkspaceEdit};
+    use crate::syntax::test::Code;
+
+    fn format_range_of(code: &Code, range: Range) -> String {
+        let edit = format_range(code.source(), range, &FormatOptions::default()).unwrap();
+        let mut workspace_edit = WorkspaceEdit::default();
+        workspace_edit.add(code.source().file_name(), edit);
+        apply_edits(
+            code.source(),
+            &CodeAction {
+                title: String::new(),
+                kind: CodeActionKind::RefactorRewrite,
+                edit: workspace_edit,
+            },
+        )
+    }
+
+    #[test]
+    fn formats_statements_in_range() {
+        let code = Code::new(
+            "\
+architecture a of ent is
+  signal   x : bit;
+begin
+  process
+  begin
+    x<='0';
+        x<= '1';   -- Set
+  wait;
+  end process;
+  x   <=   '1';
+end architecture;",
+        );
+        let range = Range::new(code.s1("x<='0'").start(), code.s1("x<= '1'").end());
+        assert_eq!(
+            format_range_of(&code, range),
+            "\
+architecture a of ent is
+  signal   x : bit;
+begin
+  process
+  begin
+        x <= '0';
+        x <= '1'; -- Set
+  wait;
+  end process;
+  x   <=   '1';
+end architecture;"
+        );
+
+        // The whole process is formatted when the range is not within its statements
+        let cursor = code.s1("process").start();
+        assert_eq!(
+            format_range_of(&code, Range::new(cursor, cursor)),
+            "\
+architecture a of ent is
+  signal   x : bit;
+begin
+    process
+    begin
+        x <= '0';
+        x <= '1'; -- Set
+        wait;
+    end process;
+  x   <=   '1';
+end architecture;"
+        );
+    }
+
+    #[test]
+    fn formats_declarations_in_range() {
+        let code = Code::new(
+            "\
+package pkg is
+constant  c : natural := 0; constant d : natural := 1;
+end package;",
+        );
+        let cursor = code.s1("d :").start();
+        assert_eq!(
+            format_range_of(&code, Range::new(cursor, cursor)),
+            "\
+package pkg is
+constant  c : natural := 0; constant d: natural := 1;
+end package;"
+        );
+    }
+}
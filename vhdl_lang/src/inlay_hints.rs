@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Computes hints that are displayed inline with the code,
+//! e.g., the formal of a positional association.
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::*;
+use crate::named_entity::{InterfaceEnt, OverloadedEnt};
+use crate::{AnyEntKind, Design, Position, Range, Source, TokenAccess};
+
+/// A label that is displayed in front of a position of the code
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InlayHint {
+    pub pos: Position,
+    pub label: String,
+}
+
+struct InlayHintSearcher<'a> {
+    root: &'a DesignRoot,
+    range: Range,
+    hints: Vec<InlayHint>,
+}
+
+/// Returns true when the actual is just the name of the formal,
+/// in which case the hint adds no information
+fn is_named_like(actual: &ActualPart, formal: InterfaceEnt<'_>) -> bool {
+    match actual {
+        ActualPart::Expression(Expression::Name(name)) => match name.as_ref() {
+            Name::Designator(designator) => designator.item == *formal.designator(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl InlayHintSearcher<'_> {
+    /// Adds the formals of the leading positional associations of an association list
+    fn add_positional<'e>(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        associations: &SeparatedList<AssociationElement>,
+        formals: impl Iterator<Item = InterfaceEnt<'e>>,
+    ) {
+        for (assoc, formal) in associations
+            .items
+            .iter()
+            .take_while(|assoc| assoc.formal.is_none())
+            .zip(formals)
+        {
+            let pos = assoc.actual.pos(ctx).start();
+            if !self.range.contains(pos) || is_named_like(&assoc.actual.item, formal) {
+                continue;
+            }
+            self.hints.push(InlayHint {
+                pos,
+                label: format!("{} =>", formal.designator()),
+            });
+        }
+    }
+}
+
+impl Searcher for InlayHintSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let DeclarationItem::ConcurrentStatement(stmt) = decl.ast else {
+            return NotFinished;
+        };
+        let ConcurrentStatement::Instance(inst) = &stmt.statement.item else {
+            return NotFinished;
+        };
+        let Some(id) = inst.entity_reference() else {
+            return NotFinished;
+        };
+        let region = match self.root.get_ent(id).kind() {
+            AnyEntKind::Component(region) => region,
+            AnyEntKind::Design(Design::Entity(_, region)) => region,
+            _ => return NotFinished,
+        };
+        let (ports, generics) = region.ports_and_generics();
+        if let Some(generic_map) = &inst.generic_map {
+            self.add_positional(ctx, &generic_map.list, generics.into_iter());
+        }
+        if let Some(port_map) = &inst.port_map {
+            self.add_positional(ctx, &port_map.list, ports.into_iter());
+        }
+        NotFinished
+    }
+
+    fn search_call(&mut self, ctx: &dyn TokenAccess, call: &CallOrIndexed) -> SearchState {
+        if let Some(subprogram) = call
+            .name
+            .item
+            .get_suffix_reference()
+            .and_then(|id| OverloadedEnt::from_any(self.root.get_ent(id)))
+        {
+            self.add_positional(ctx, &call.parameters, subprogram.formals().iter());
+        }
+        NotFinished
+    }
+}
+
+/// Lists the formals of positional associations of instantiations and calls within a range
+pub fn list_inlay_hints(root: &DesignRoot, source: &Source, range: Range) -> Vec<InlayHint> {
+    let mut searcher = InlayHintSearcher {
+        root,
+        range,
+        hints: Vec::new(),
+    };
+    let _ = root.search_source(source, &mut searcher);
+    searcher.hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+
+    #[test]
+    fn hints_formals_of_positional_associations() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity child is
+  generic (width : natural);
+  port (clk : in bit; data : in bit_vector(width - 1 downto 0));
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : bit;
+  signal d : bit_vector(0 to 0);
+
+  function f(value, shift : natural) return natural is
+  begin
+    return value;
+  end function;
+begin
+  inst: entity work.child
+    generic map (f(1, 2))
+    port map (clk, data => d);
+end architecture;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let hints = list_inlay_hints(&root, code.source(), code.source().contents().range());
+        assert_eq!(
+            hints,
+            vec![
+                InlayHint {
+                    pos: code.s1("f(1, 2)").start(),
+                    label: "width =>".to_owned(),
+                },
+                InlayHint {
+                    pos: code.s1("f(1, 2)").s1("1").start(),
+                    label: "value =>".to_owned(),
+                },
+                InlayHint {
+                    pos: code.s1("f(1, 2)").s1("2").start(),
+                    label: "shift =>".to_owned(),
+                },
+            ]
+        );
+    }
+}
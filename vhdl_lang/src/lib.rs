@@ -21,27 +21,49 @@ mod named_entity;
 mod project;
 mod syntax;
 
+mod code_action;
 mod completion;
+mod elaboration;
+mod export;
+mod folding;
 mod formatting;
+mod inlay_hints;
+mod rename;
 mod standard;
+mod vunit;
 
-pub use crate::config::Config;
+pub use crate::config::{Casing, CodeActionConfig, Config, ElaborationConfig, NamingRule};
 pub use crate::data::{
-    Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
+    Diagnostic, Encoding, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
     NullDiagnostics, NullMessages, Position, Range, Severity, SeverityMap, Source, SrcPos,
 };
-pub use formatting::VHDLFormatter;
+pub use formatting::{format_on_type, format_range, format_source, FormatOptions, VHDLFormatter};
 
-pub use crate::analysis::EntHierarchy;
+pub use crate::analysis::{EntHierarchy, HoverInfo, ReferenceKind};
 pub use crate::named_entity::{
-    AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, HasEntityId, InterfaceEnt, Object,
-    Overloaded, Reference, Related, Sequential, Type,
+    AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, HasEntityId, InterfaceEnt,
+    InterfaceMode, Object, Overloaded, Reference, Related, Sequential, Type,
 };
 
 pub use crate::project::{Project, SourceFile};
 pub use crate::syntax::{
-    kind_str, HasTokenSpan, ParserResult, Token, TokenAccess, TokenId, TokenSpan, VHDLParser,
+    kind_str, ConditionalAnalysis, HasTokenSpan, ParserResult, Token, TokenAccess, TokenId,
+    TokenSpan, VHDLParser,
 };
 
-pub use completion::{list_completion_options, CompletionItem};
+pub use code_action::{
+    list_code_actions, normalize_case, synchronize_instantiations, CodeAction, CodeActionKind,
+    InstanceSynchronization, StaleAssociation, TextEdit, WorkspaceEdit,
+};
+pub use completion::{
+    formal_detail, instantiation_snippet, list_completion_options, signature_help,
+    subprogram_call_snippet, CompletionItem, CompletionKind, CompletionOption, SignatureHelp,
+    SignatureInformation, Snippet,
+};
+pub use elaboration::{Elaboration, GhdlElaboration, GhdlOutput, GHDL_WORKDIR};
+pub use export::{IpXactComponent, PortListFormat, Testbench};
+pub use folding::{list_folding_ranges, FoldingRange, FoldingRangeKind};
+pub use inlay_hints::{list_inlay_hints, InlayHint};
+pub use rename::{check_rename, RenameError};
 pub use standard::VHDLStandard;
+pub use vunit::{parse_vunit_output, VUnitFailure, VUnitTestCase};
@@ -4,4 +4,219 @@
 //
 // Copyright (c) 2022, Olof Kraigher olof.kraigher@gmail.com
 
+pub mod choices;
 pub mod dead_code;
+pub mod drivers;
+pub mod elaborate;
+pub mod fold;
+pub mod latch;
+pub mod naming;
+pub mod sensitivity;
+pub mod shadowing;
+pub mod shared;
+pub mod unconnected;
+pub mod undriven;
+pub mod unreachable;
+pub mod wait;
+pub mod width;
+
+use crate::analysis::{DesignRoot, Library, LockedUnit};
+use crate::ast::search::{Search, Searcher};
+use crate::ast::UnitId;
+use crate::data::error_codes::ErrorCode;
+use crate::data::{DiagnosticHandler, Symbol};
+use crate::{Config, Diagnostic};
+use choices::case_choice_diagnostics;
+use dead_code::unused_declaration_diagnostics;
+use drivers::multiple_driver_diagnostics;
+use elaborate::elaboration_diagnostics;
+use fnv::{FnvHashMap, FnvHashSet};
+use latch::latch_diagnostics;
+use naming::{naming_diagnostics, NamingRules};
+use sensitivity::sensitivity_list_diagnostics;
+use shadowing::shadowing_diagnostics;
+use shared::shared_variable_diagnostics;
+use unconnected::unconnected_port_diagnostics;
+use undriven::undriven_signal_diagnostics;
+use unreachable::unreachable_code_diagnostics;
+use wait::missing_wait_diagnostics;
+use width::width_mismatch_diagnostics;
+
+/// Searches a design unit after its analysis
+pub(crate) fn search_unit(unit: &LockedUnit, searcher: &mut impl Searcher) {
+    if let Some(analyzed) = unit.unit.get() {
+        let _ = analyzed.search(&unit.tokens, searcher);
+    }
+}
+
+/// Searches a primary unit and its secondary units
+pub(crate) fn search_unit_and_secondaries(
+    lib: &Library,
+    primary_unit_name: &Symbol,
+    searcher: &mut impl Searcher,
+) {
+    for unit in lib
+        .primary_unit(primary_unit_name)
+        .into_iter()
+        .chain(lib.secondary_units(primary_unit_name))
+    {
+        search_unit(unit, searcher);
+    }
+}
+
+/// The diagnostics of all lints of a primary unit and its secondary units.
+/// Lints whose error codes are all turned off are not run.
+fn lint_unit(
+    root: &DesignRoot,
+    library: &Library,
+    primary_name: &Symbol,
+    config: &Config,
+    naming_rules: &NamingRules<'_>,
+) -> Vec<Diagnostic> {
+    let severities = config.severities();
+    let enabled = |codes: &[ErrorCode]| codes.iter().any(|code| severities[*code].is_some());
+    let mut diagnostics = Vec::new();
+    if enabled(&[ErrorCode::Unused]) {
+        diagnostics.extend(unused_declaration_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[
+        ErrorCode::MissingSensitivity,
+        ErrorCode::UnnecessarySensitivity,
+    ]) {
+        diagnostics.extend(sensitivity_list_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::InferredLatch]) {
+        diagnostics.extend(latch_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::UnreachableCode]) {
+        diagnostics.extend(unreachable_code_diagnostics(library, primary_name));
+    }
+    if enabled(&[ErrorCode::MissingWait]) {
+        diagnostics.extend(missing_wait_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::MultipleDrivers]) {
+        diagnostics.extend(multiple_driver_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::WidthMismatch]) {
+        diagnostics.extend(width_mismatch_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::MissingChoice, ErrorCode::DuplicateChoice]) {
+        diagnostics.extend(case_choice_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::Shadowing]) {
+        diagnostics.extend(shadowing_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[ErrorCode::UndrivenSignal]) {
+        diagnostics.extend(undriven_signal_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[
+        ErrorCode::UnconnectedInputPort,
+        ErrorCode::UnconnectedOutputPort,
+    ]) {
+        diagnostics.extend(unconnected_port_diagnostics(root, library, primary_name));
+    }
+    if enabled(&[
+        ErrorCode::UnprotectedSharedVariable,
+        ErrorCode::SharedVariablePortability,
+    ]) {
+        diagnostics.extend(shared_variable_diagnostics(
+            root,
+            library,
+            primary_name,
+            config.standard(),
+        ));
+    }
+    if enabled(&[ErrorCode::NamingConvention]) {
+        diagnostics.extend(naming_diagnostics(
+            root,
+            library,
+            primary_name,
+            naming_rules,
+        ));
+    }
+    diagnostics
+}
+
+/// Use a struct to keep state of units that do not need to be re-scanned
+#[derive(Default)]
+pub(crate) struct Linter {
+    // library name, primary name
+    diagnostics: FnvHashMap<(Symbol, Symbol), Vec<Diagnostic>>,
+}
+
+impl Linter {
+    pub fn lint(
+        &mut self,
+        root: &DesignRoot,
+        config: &Config,
+        analyzed_units: &[UnitId],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        // Prune diagnostics that need to be re-computed
+        for unit in analyzed_units {
+            let key = (unit.library_name().clone(), unit.primary_name().clone());
+            self.diagnostics.remove(&key);
+        }
+
+        // Prune diagnostics for units that no longer exist
+        self.diagnostics.retain(|(library_name, primary_name), _| {
+            if let Some(library) = root.get_lib(library_name) {
+                if library.primary_unit(primary_name).is_some() {
+                    return true;
+                }
+            }
+            false
+        });
+
+        let naming_rules = NamingRules::new(config);
+        for unit in analyzed_units {
+            let key = (unit.library_name().clone(), unit.primary_name().clone());
+
+            // The diagnostics of third-party libraries are never reported
+            let is_linted = config
+                .get_library(&unit.library_name().name_utf8())
+                .is_some_and(|library| !library.is_third_party);
+            if !is_linted {
+                continue;
+            }
+            if let Some(library) = root.get_lib(unit.library_name()) {
+                self.diagnostics.entry(key).or_insert_with(|| {
+                    lint_unit(root, library, unit.primary_name(), config, &naming_rules)
+                });
+            }
+        }
+
+        for unit_diagnostics in self.diagnostics.values() {
+            diagnostics.append(unit_diagnostics.iter().cloned());
+        }
+
+        // The elaborated design depends on all units, so it is not kept between runs
+        if let Some(elaboration) = config.elaboration() {
+            let mut reported: FnvHashSet<&Diagnostic> =
+                self.diagnostics.values().flatten().collect();
+            for diagnostic in elaboration_diagnostics(root, config, elaboration).iter() {
+                if reported.insert(diagnostic) {
+                    diagnostics.push(diagnostic.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::check_no_diagnostics;
+
+    /// Lints the entity `ent` of the library `libname` of a design that is analyzed without errors
+    pub fn lint_ent(
+        builder: LibraryBuilder,
+        lint: impl FnOnce(&DesignRoot, &Library, &Symbol) -> Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        lint(&root, lib, &root.symbol_utf8("ent"))
+    }
+}
@@ -0,0 +1,609 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{bit_string_to_string, DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::fold::{Folder, StaticRange};
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::{OverloadedEnt, Type, TypeEnt};
+use crate::{AnyEntKind, Diagnostic, EntityId, SrcPos, TokenAccess};
+use itertools::Itertools;
+
+/// The maximum number of uncovered values or ranges that are listed in a diagnostic
+const MAX_LISTED: usize = 8;
+
+/// The maximum number of array values that are enumerated to find the uncovered ones
+const MAX_ENUMERATED: i64 = 1 << 16;
+
+/// The values that the choices of a case statement or a selected assignment must cover
+enum Domain {
+    /// Integer values or the positions of enumeration literals
+    Discrete {
+        range: StaticRange,
+        literals: Option<Vec<EnumerationLiteral>>,
+    },
+    /// One-dimensional arrays with elements of an enumeration type
+    Array {
+        length: i64,
+        literals: Vec<EnumerationLiteral>,
+    },
+}
+
+/// The values that a single choice covers
+#[derive(PartialEq, Eq, Hash, Clone)]
+enum Covered {
+    /// The values from low to high
+    Discrete(i64, i64),
+    /// The positions of the elements of an array value
+    Array(Vec<usize>),
+}
+
+impl Domain {
+    fn describe_discrete(&self, value: i64) -> String {
+        match self {
+            Domain::Discrete {
+                literals: Some(literals),
+                ..
+            } => usize::try_from(value)
+                .ok()
+                .and_then(|position| literals.get(position))
+                .map(|literal| literal.to_string())
+                .unwrap_or_else(|| value.to_string()),
+            _ => value.to_string(),
+        }
+    }
+
+    fn describe_array(&self, value: &[usize]) -> String {
+        let Domain::Array { literals, .. } = self else {
+            return String::new();
+        };
+        if literals
+            .iter()
+            .all(|literal| matches!(literal, EnumerationLiteral::Character(_)))
+        {
+            let chars: String = value
+                .iter()
+                .map(|position| match literals[*position] {
+                    EnumerationLiteral::Character(chr) => chr as char,
+                    EnumerationLiteral::Identifier(_) => '?',
+                })
+                .collect();
+            format!("\"{chars}\"")
+        } else {
+            format!(
+                "({})",
+                value
+                    .iter()
+                    .map(|position| literals[*position].to_string())
+                    .join(", ")
+            )
+        }
+    }
+
+    fn describe(&self, covered: &Covered) -> String {
+        match covered {
+            Covered::Discrete(low, high) if low == high => self.describe_discrete(*low),
+            Covered::Discrete(low, high) => format!(
+                "{} to {}",
+                self.describe_discrete(*low),
+                self.describe_discrete(*high)
+            ),
+            Covered::Array(value) => self.describe_array(value),
+        }
+    }
+}
+
+/// Returns the values that are covered by both choices
+fn overlap(left: &Covered, right: &Covered) -> Option<Covered> {
+    match (left, right) {
+        (Covered::Discrete(llow, lhigh), Covered::Discrete(rlow, rhigh)) => {
+            let low = *llow.max(rlow);
+            let high = *lhigh.min(rhigh);
+            (low <= high).then_some(Covered::Discrete(low, high))
+        }
+        (Covered::Array(left), Covered::Array(right)) => {
+            (left == right).then(|| Covered::Array(left.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn describe_list(items: Vec<String>, total: usize) -> String {
+    let mut result = items.into_iter().take(MAX_LISTED).join(", ");
+    if total > MAX_LISTED {
+        result.push_str(&format!(" and {} more", total - MAX_LISTED));
+    }
+    result
+}
+
+struct ChoiceSearcher<'a> {
+    root: &'a DesignRoot,
+    folder: Folder<'a>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ChoiceSearcher<'a> {
+    /// The values of the case expression.
+    /// For the name of an object or a qualified expression, these are the values of its subtype
+    /// and otherwise the values of the base type of the expression.
+    fn domain(&mut self, expr: &Expression) -> Option<Domain> {
+        let (typ, range) = match expr {
+            Expression::Name(name) => match name.as_ref() {
+                Name::Designator(_) | Name::Selected(..) => {
+                    let id = name.get_suffix_reference()?;
+                    match self.root.get_ent(id).actual_kind() {
+                        AnyEntKind::Object(object) => {
+                            (object.subtype.type_mark(), self.folder.range_of(id)?)
+                        }
+                        AnyEntKind::LoopParameter(Some(typ)) => {
+                            (TypeEnt::from(*typ), self.folder.range_of(id)?)
+                        }
+                        AnyEntKind::Overloaded(_) => self.return_type(id)?,
+                        _ => return None,
+                    }
+                }
+                Name::CallOrIndexed(call) => {
+                    self.return_type(call.name.item.get_suffix_reference()?)?
+                }
+                _ => return None,
+            },
+            Expression::Qualified(qexpr) => {
+                let id = qexpr.type_mark.item.get_suffix_reference()?;
+                (
+                    TypeEnt::from_any(self.root.get_ent(id))?,
+                    self.folder.range_of(id)?,
+                )
+            }
+            _ => return None,
+        };
+
+        let base = typ.base_type();
+        match base.kind() {
+            Type::Integer => Some(Domain::Discrete {
+                range,
+                literals: None,
+            }),
+            Type::Enum(_) => Some(Domain::Discrete {
+                range,
+                literals: Some(self.folder.literals_of(base.id())?),
+            }),
+            Type::Array {
+                indexes, elem_type, ..
+            } if indexes.len() == 1 => Some(Domain::Array {
+                length: range.length(),
+                literals: self.folder.literals_of(elem_type.base_type().id())?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The base type of the return type of a function together with its range
+    fn return_type(&mut self, id: EntityId) -> Option<(TypeEnt<'a>, StaticRange)> {
+        let function = OverloadedEnt::from_any(self.root.get_ent(id))?;
+        let base = function.return_type()?.base_type();
+        Some((base, self.folder.range_of(base.id())?))
+    }
+
+    /// The value of a bound of a choice
+    fn bound(&mut self, domain: &Domain, expr: &Expression) -> Option<i64> {
+        match (expr, domain) {
+            (
+                Expression::Literal(Literal::Character(chr)),
+                Domain::Discrete {
+                    literals: Some(literals),
+                    ..
+                },
+            ) => literals
+                .iter()
+                .position(|literal| *literal == EnumerationLiteral::Character(*chr))
+                .map(|position| position as i64),
+            _ => self.folder.integer(expr),
+        }
+    }
+
+    /// The positions of the elements of an array valued choice
+    fn array_value(&mut self, domain: &Domain, expr: &Expression) -> Option<Vec<usize>> {
+        let Domain::Array { literals, length } = domain else {
+            return None;
+        };
+        let chars = match expr {
+            Expression::Literal(Literal::String(value)) => value.bytes.clone(),
+            Expression::Literal(Literal::BitString(value)) => {
+                bit_string_to_string(value).ok()?.bytes
+            }
+            _ => return None,
+        };
+        if chars.len() as i64 != *length {
+            return None;
+        }
+        chars
+            .iter()
+            .map(|chr| {
+                literals
+                    .iter()
+                    .position(|literal| *literal == EnumerationLiteral::Character(*chr))
+            })
+            .collect()
+    }
+
+    /// The values that a choice covers, or `None` when they are not locally static
+    fn covered(&mut self, domain: &Domain, choice: &Choice) -> Option<Covered> {
+        match (choice, domain) {
+            (Choice::Expression(expr), Domain::Array { .. }) => {
+                Some(Covered::Array(self.array_value(domain, expr)?))
+            }
+            (Choice::Expression(Expression::Name(name)), Domain::Discrete { .. })
+                if name.get_suffix_reference().is_some_and(|id| {
+                    matches!(self.root.get_ent(id).actual_kind(), AnyEntKind::Type(_))
+                }) =>
+            {
+                // A subtype name covers all values of the subtype
+                let range = self.folder.range_of(name.get_suffix_reference()?)?;
+                Some(Covered::Discrete(range.low(), range.high()))
+            }
+            (Choice::Expression(expr), Domain::Discrete { .. }) => {
+                let value = self.bound(domain, expr)?;
+                Some(Covered::Discrete(value, value))
+            }
+            (Choice::DiscreteRange(drange), Domain::Discrete { .. }) => {
+                let range = match drange {
+                    DiscreteRange::Range(Range::Range(constraint)) => StaticRange {
+                        left: self.bound(domain, &constraint.left_expr.item)?,
+                        right: self.bound(domain, &constraint.right_expr.item)?,
+                        direction: constraint.direction,
+                    },
+                    _ => self.folder.discrete_range(drange)?,
+                };
+                Some(Covered::Discrete(range.low(), range.high()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The values of a discrete domain that are not covered by any choice
+    fn uncovered_ranges(range: StaticRange, mut covered: Vec<(i64, i64)>) -> Vec<Covered> {
+        covered.sort();
+        let mut uncovered = Vec::new();
+        let mut next = range.low();
+        for (low, high) in covered {
+            if next > range.high() {
+                break;
+            }
+            if low > next {
+                uncovered.push(Covered::Discrete(next, (low - 1).min(range.high())));
+            }
+            next = next.max(high.saturating_add(1));
+        }
+        if next <= range.high() {
+            uncovered.push(Covered::Discrete(next, range.high()));
+        }
+        uncovered
+    }
+
+    fn missing_choices(&self, domain: &Domain, covered: &[Covered]) -> Option<String> {
+        match domain {
+            Domain::Discrete { range, .. } => {
+                let covered = covered
+                    .iter()
+                    .filter_map(|covered| match covered {
+                        Covered::Discrete(low, high) => Some((*low, *high)),
+                        Covered::Array(_) => None,
+                    })
+                    .collect();
+                let uncovered = Self::uncovered_ranges(*range, covered);
+                if uncovered.is_empty() {
+                    return None;
+                }
+                let total = uncovered.len();
+                Some(describe_list(
+                    uncovered
+                        .iter()
+                        .take(MAX_LISTED)
+                        .map(|value| domain.describe(value))
+                        .collect(),
+                    total,
+                ))
+            }
+            Domain::Array { length, literals } => {
+                let count = i64::try_from(literals.len())
+                    .ok()
+                    .and_then(|count| count.checked_pow(u32::try_from(*length).ok()?));
+                let distinct = covered.iter().unique().count() as i64;
+                match count {
+                    Some(count) if count <= distinct => None,
+                    Some(count) if count <= MAX_ENUMERATED => {
+                        let mut uncovered = Vec::new();
+                        for index in 0..count {
+                            // The elements of the value are the digits of the index
+                            let mut value = vec![0; *length as usize];
+                            let mut rest = index as usize;
+                            for element in value.iter_mut().rev() {
+                                *element = rest % literals.len();
+                                rest /= literals.len();
+                            }
+                            let value = Covered::Array(value);
+                            if !covered.contains(&value) {
+                                uncovered.push(value);
+                            }
+                        }
+                        let total = uncovered.len();
+                        Some(describe_list(
+                            uncovered
+                                .iter()
+                                .take(MAX_LISTED)
+                                .map(|value| domain.describe(value))
+                                .collect(),
+                            total,
+                        ))
+                    }
+                    _ => Some("all values".to_owned()),
+                }
+            }
+        }
+    }
+
+    fn check_choices<'c>(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        expr: &WithTokenSpan<Expression>,
+        choices: impl Iterator<Item = &'c WithTokenSpan<Choice>>,
+    ) {
+        let Some(domain) = self.domain(&expr.item) else {
+            return;
+        };
+        let mut has_others = false;
+        let mut is_static = true;
+        let mut covered: Vec<(SrcPos, Covered)> = Vec::new();
+        for choice in choices {
+            if let Choice::Others = choice.item {
+                has_others = true;
+                continue;
+            }
+            let Some(values) = self.covered(&domain, &choice.item) else {
+                is_static = false;
+                continue;
+            };
+            if let Covered::Discrete(low, high) = values {
+                if low > high {
+                    // A null range covers no values
+                    continue;
+                }
+            }
+            let pos = choice.pos(ctx);
+            if let Some((previous, duplicate)) = covered
+                .iter()
+                .find_map(|(previous, other)| Some((previous, overlap(other, &values)?)))
+            {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        &pos,
+                        format!(
+                            "Duplicate choice, {} is already covered",
+                            domain.describe(&duplicate)
+                        ),
+                        ErrorCode::DuplicateChoice,
+                    )
+                    .related(previous, "Previously covered here"),
+                );
+            }
+            covered.push((pos, values));
+        }
+
+        if has_others || !is_static {
+            return;
+        }
+        let covered: Vec<_> = covered.into_iter().map(|(_, values)| values).collect();
+        if let Some(missing) = self.missing_choices(&domain, &covered) {
+            self.diagnostics.push(Diagnostic::new(
+                expr.pos(ctx),
+                format!("The choices do not cover {missing}"),
+                ErrorCode::MissingChoice,
+            ));
+        }
+    }
+
+    fn check_selection<T>(&mut self, ctx: &dyn TokenAccess, rhs: &AssignmentRightHand<T>) {
+        if let AssignmentRightHand::Selected(selection) = rhs {
+            // Matching selected assignments may use don't care values in their choices
+            if selection.is_matching {
+                return;
+            }
+            self.check_choices(
+                ctx,
+                &selection.expression,
+                selection
+                    .alternatives
+                    .iter()
+                    .flat_map(|alternative| alternative.choices.iter()),
+            );
+        }
+    }
+}
+
+impl Searcher for ChoiceSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::SequentialStatement(stmt) => match &stmt.statement.item {
+                // Matching case statements may use don't care values in their choices
+                SequentialStatement::Case(case_stmt) if !case_stmt.is_matching => self
+                    .check_choices(
+                        ctx,
+                        &case_stmt.expression,
+                        case_stmt
+                            .alternatives
+                            .iter()
+                            .flat_map(|alternative| alternative.choices.iter()),
+                    ),
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.check_selection(ctx, &assignment.rhs)
+                }
+                SequentialStatement::VariableAssignment(assignment) => {
+                    self.check_selection(ctx, &assignment.rhs)
+                }
+                _ => {}
+            },
+            DeclarationItem::ConcurrentStatement(stmt) => {
+                if let ConcurrentStatement::Assignment(assignment) = &stmt.statement.item {
+                    self.check_selection(ctx, &assignment.assignment.rhs)
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the choices of case statements and selected assignments
+/// of a primary unit and its secondary units that are duplicated or that do not cover
+/// all values of the case expression
+pub(crate) fn case_choice_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = ChoiceSearcher {
+        root,
+        folder: Folder::new(root),
+        diagnostics: Vec::new(),
+    };
+    searcher
+        .folder
+        .declare_unit_and_secondaries(lib, primary_unit_name);
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::{check_diagnostics, Code};
+
+    fn missing(code: Code, missing: &str) -> Diagnostic {
+        Diagnostic::new(
+            code,
+            format!("The choices do not cover {missing}"),
+            ErrorCode::MissingChoice,
+        )
+    }
+
+    fn duplicate(code: Code, previous: Code, duplicate: &str) -> Diagnostic {
+        Diagnostic::new(
+            code,
+            format!("Duplicate choice, {duplicate} is already covered"),
+            ErrorCode::DuplicateChoice,
+        )
+        .related(previous, "Previously covered here")
+    }
+
+    #[test]
+    fn integer_and_enumeration_choices() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  constant last : natural := 15;
+  subtype idx_t is natural range 0 to last;
+  type state_t is (idle, load, run, done);
+  signal idx : idx_t;
+  signal state : state_t;
+  signal b : bit;
+  signal i : integer;
+begin
+  process (idx, state, b, i)
+  begin
+    case idx is
+      when 0 | 2 to 5 => null;
+      when 4 to 7 => null;
+      when 9 => null;
+      when last => null;
+    end case;
+
+    case state is
+      when idle to load => null;
+      when done => null;
+    end case;
+
+    case b is
+      when '0' => null;
+      when '1' => null;
+    end case;
+
+    case i is
+      when 0 => null;
+      when others => null;
+    end case;
+
+    case idx is
+      when 0 to last - 1 => null;
+      when last => null;
+    end case;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, case_choice_diagnostics),
+            vec![
+                duplicate(code.s1("4 to 7"), code.s1("2 to 5"), "4 to 5"),
+                missing(code.s1("case idx").s1("idx"), "1, 8, 10 to 14"),
+                missing(code.s1("case state").s1("state"), "run"),
+            ],
+        );
+    }
+
+    #[test]
+    fn array_choices_and_selected_assignments() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal sel : bit_vector(1 downto 0);
+  signal q : bit;
+begin
+  with sel select
+    q <= '0' when \"00\" | \"11\",
+         '1' when b\"01\";
+
+  process (sel)
+  begin
+    case sel is
+      when \"00\" => null;
+      when \"01\" => null;
+      when \"10\" => null;
+      when b\"00\" => null;
+      when others => null;
+    end case;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, case_choice_diagnostics),
+            vec![
+                missing(code.s1("with sel").s1("sel"), "\"10\""),
+                duplicate(
+                    code.s1("b\"00\""),
+                    code.s(" \"00\"", 2).s1("\"00\""),
+                    "\"00\"",
+                ),
+            ],
+        );
+    }
+}
@@ -6,24 +6,19 @@
 
 use crate::analysis::DesignRoot;
 use crate::analysis::Library;
-use crate::analysis::LockedUnit;
-use crate::ast::search::Search;
 use crate::ast::search::SearchState;
 use crate::ast::search::Searcher;
-use crate::ast::UnitId;
 use crate::data::error_codes::ErrorCode;
-use crate::data::DiagnosticHandler;
 use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
 use crate::named_entity::{HasEntityId, Reference, Related};
-use crate::syntax::TokenAccess;
+use crate::syntax::{is_verilog_file, TokenAccess};
 use crate::AnyEntKind;
-use crate::Config;
 use crate::Design;
 use crate::Diagnostic;
 use crate::EntRef;
 use crate::Overloaded;
 use crate::SrcPos;
-use fnv::FnvHashMap;
 use fnv::FnvHashSet;
 use itertools::Itertools;
 
@@ -72,10 +67,6 @@ impl<'a> Searcher for DeadCodeSearcher<'a> {
     }
 }
 
-fn search_unit(unit: &LockedUnit, searcher: &mut impl Searcher) {
-    let _ = unit.unit.write().search(&unit.tokens, searcher);
-}
-
 fn is_package_header(ent: EntRef<'_>) -> bool {
     matches!(
         ent.kind(),
@@ -141,6 +132,14 @@ fn can_be_locally_unused(ent: EntRef<'_>) -> bool {
             return false;
         }
 
+        // The implementation of an entity translated from a Verilog module is not analyzed
+        if parent
+            .decl_pos()
+            .is_some_and(|pos| is_verilog_file(pos.file_name()))
+        {
+            return false;
+        }
+
         // Everything in protected types inside of package header
         if matches!(parent.kind(), AnyEntKind::Type(crate::Type::Protected(..))) {
             if let Some(grand_parent) = parent.parent {
@@ -163,20 +162,14 @@ fn can_be_locally_unused(ent: EntRef<'_>) -> bool {
 }
 
 /// Find *local* unused declarations
-fn find_unused_declarations<'a>(
+pub(crate) fn find_unused_declarations<'a>(
     root: &'a DesignRoot,
     lib: &Library,
     primary_unit_name: &Symbol,
 ) -> FnvHashSet<EntRef<'a>> {
     let mut searcher = DeadCodeSearcher::new(root);
 
-    if let Some(unit) = lib.primary_unit(primary_unit_name) {
-        search_unit(unit, &mut searcher);
-    }
-
-    for unit in lib.secondary_units(primary_unit_name) {
-        search_unit(unit, &mut searcher);
-    }
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
 
     searcher
         .declarations
@@ -195,64 +188,22 @@ fn find_unused_declarations<'a>(
         .collect()
 }
 
-/// Use a struct to keep state of units that do not need to be re-scanned
-#[derive(Default)]
-pub(crate) struct UnusedDeclarationsLinter {
-    // library name, primary name
-    diagnostics: FnvHashMap<(Symbol, Symbol), Vec<Diagnostic>>,
-}
-
-impl UnusedDeclarationsLinter {
-    pub fn lint(
-        &mut self,
-        root: &DesignRoot,
-        config: &Config,
-        analyzed_units: &[UnitId],
-        diagnostics: &mut dyn DiagnosticHandler,
-    ) {
-        // Prune diagnostics that need to be re-computed
-        for unit in analyzed_units {
-            let key = (unit.library_name().clone(), unit.primary_name().clone());
-            self.diagnostics.remove(&key);
-        }
-
-        // Prune diagnostics for units that no longer exist
-        self.diagnostics.retain(|(library_name, primary_name), _| {
-            if let Some(library) = root.get_lib(library_name) {
-                if library.primary_unit(primary_name).is_some() {
-                    return true;
-                }
-            }
-            false
-        });
-
-        for unit in analyzed_units {
-            let key = (unit.library_name().clone(), unit.primary_name().clone());
-
-            if let Some(library) = root.get_lib(unit.library_name()) {
-                self.diagnostics.entry(key).or_insert_with(|| {
-                    find_unused_declarations(root, library, unit.primary_name())
-                        .into_iter()
-                        .filter_map(|ent| {
-                            Some(Diagnostic::new(
-                                ent.decl_pos()?,
-                                format!("Unused declaration of {}", ent.describe()),
-                                ErrorCode::Unused,
-                            ))
-                        })
-                        .collect_vec()
-                });
-            }
-        }
-
-        for ((library_name, _), unit_diagnostics) in self.diagnostics.iter() {
-            if let Some(library_config) = config.get_library(&library_name.name_utf8()) {
-                if !library_config.is_third_party {
-                    diagnostics.append(unit_diagnostics.iter().cloned());
-                }
-            }
-        }
-    }
+/// Diagnostics for the *local* unused declarations of a primary unit and its secondary units
+pub(crate) fn unused_declaration_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    find_unused_declarations(root, lib, primary_unit_name)
+        .into_iter()
+        .filter_map(|ent| {
+            Some(Diagnostic::new(
+                ent.decl_pos()?,
+                format!("Unused declaration of {}", ent.describe()),
+                ErrorCode::Unused,
+            ))
+        })
+        .collect_vec()
 }
 
 #[cfg(test)]
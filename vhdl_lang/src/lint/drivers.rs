@@ -0,0 +1,382 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::base_reference;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::{InterfaceMode, ObjectInterface};
+use crate::{AnyEntKind, Diagnostic, EntityId, SrcPos, TokenAccess};
+use fnv::FnvHashMap;
+
+/// A signal that is driven by a concurrent statement
+struct Driver {
+    signal: EntityId,
+    /// True when the driver drives the whole signal rather than a subelement
+    whole: bool,
+    pos: SrcPos,
+}
+
+struct DriverSearcher<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DriverSearcher<'_> {
+    /// Returns the signal of unresolved type that is referenced by a name
+    fn unresolved_signal(&self, name: &Name) -> Option<EntityId> {
+        let id = base_reference(name)?;
+        match self.root.get_ent(id).kind() {
+            AnyEntKind::Object(object)
+                if object.class == ObjectClass::Signal && !object.subtype.is_resolved() =>
+            {
+                Some(id)
+            }
+            _ => None,
+        }
+    }
+
+    fn name_driver(
+        &self,
+        ctx: &dyn TokenAccess,
+        name: &WithTokenSpan<Name>,
+        result: &mut Vec<Driver>,
+    ) {
+        if let Some(signal) = self.unresolved_signal(&name.item) {
+            result.push(Driver {
+                signal,
+                whole: matches!(name.item, Name::Designator(_)),
+                pos: name.pos(ctx),
+            });
+        }
+    }
+
+    fn target_drivers(
+        &self,
+        ctx: &dyn TokenAccess,
+        target: &WithTokenSpan<Target>,
+        result: &mut Vec<Driver>,
+    ) {
+        match &target.item {
+            Target::Name(name) => {
+                self.name_driver(ctx, &WithTokenSpan::new(name.clone(), target.span), result)
+            }
+            Target::Aggregate(assocs) => {
+                for assoc in assocs {
+                    let (ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr)) =
+                        &assoc.item;
+                    if let Expression::Name(name) = &expr.item {
+                        self.name_driver(
+                            ctx,
+                            &WithTokenSpan::new(name.as_ref().clone(), expr.span),
+                            result,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn sequential_drivers(
+        &self,
+        ctx: &dyn TokenAccess,
+        statements: &[LabeledSequentialStatement],
+        result: &mut Vec<Driver>,
+    ) {
+        for stmt in statements {
+            match &stmt.statement.item {
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.target_drivers(ctx, &assignment.target, result)
+                }
+                SequentialStatement::If(if_stmt) => {
+                    for cond in if_stmt.conds.conditionals.iter() {
+                        self.sequential_drivers(ctx, &cond.item, result);
+                    }
+                    if let Some((statements, _)) = &if_stmt.conds.else_item {
+                        self.sequential_drivers(ctx, statements, result);
+                    }
+                }
+                SequentialStatement::Case(case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        self.sequential_drivers(ctx, &alternative.item, result);
+                    }
+                }
+                SequentialStatement::Loop(loop_stmt) => {
+                    self.sequential_drivers(ctx, &loop_stmt.statements, result)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns true when a formal of a port map is a port that drives its actual
+    fn is_driving_port(&self, formal: &Name) -> bool {
+        let Some(id) = base_reference(formal) else {
+            return false;
+        };
+        matches!(
+            self.root.get_ent(id).kind(),
+            AnyEntKind::Object(object) if matches!(
+                object.iface,
+                Some(ObjectInterface::Port(InterfaceMode::Simple(
+                    Mode::Out | Mode::InOut | Mode::Buffer
+                )))
+            )
+        )
+    }
+
+    /// Collects the drivers of each concurrent statement of an architecture and its blocks.
+    /// Generate statements are not considered, as their drivers depend on elaboration.
+    fn concurrent_drivers(
+        &self,
+        ctx: &dyn TokenAccess,
+        statements: &[LabeledConcurrentStatement],
+        result: &mut Vec<Vec<Driver>>,
+    ) {
+        for stmt in statements {
+            let mut drivers = Vec::new();
+            match &stmt.statement.item {
+                ConcurrentStatement::Process(process) => {
+                    self.sequential_drivers(ctx, &process.statements, &mut drivers)
+                }
+                ConcurrentStatement::Assignment(assignment) => {
+                    self.target_drivers(ctx, &assignment.assignment.target, &mut drivers)
+                }
+                ConcurrentStatement::Instance(instance) => {
+                    for assoc in instance
+                        .port_map
+                        .iter()
+                        .flat_map(|map| map.list.items.iter())
+                    {
+                        let (Some(formal), ActualPart::Expression(Expression::Name(name))) =
+                            (&assoc.formal, &assoc.actual.item)
+                        else {
+                            continue;
+                        };
+                        if self.is_driving_port(&formal.item) {
+                            self.name_driver(
+                                ctx,
+                                &WithTokenSpan::new(name.as_ref().clone(), assoc.actual.span),
+                                &mut drivers,
+                            );
+                        }
+                    }
+                }
+                ConcurrentStatement::Block(block) => {
+                    self.concurrent_drivers(ctx, &block.statements, result)
+                }
+                _ => {}
+            }
+            result.push(drivers);
+        }
+    }
+
+    fn check_architecture(&mut self, ctx: &dyn TokenAccess, arch: &ArchitectureBody) {
+        let mut statements = Vec::new();
+        self.concurrent_drivers(ctx, &arch.statements, &mut statements);
+
+        // The drivers of each signal, grouped by the statement that contains them
+        let mut signals: Vec<(EntityId, Vec<Vec<Driver>>)> = Vec::new();
+        for drivers in statements {
+            let mut by_signal: FnvHashMap<EntityId, Vec<Driver>> = FnvHashMap::default();
+            let mut order = Vec::new();
+            for driver in drivers {
+                if !by_signal.contains_key(&driver.signal) {
+                    order.push(driver.signal);
+                }
+                by_signal.entry(driver.signal).or_default().push(driver);
+            }
+            for signal in order {
+                let drivers = by_signal.remove(&signal).unwrap_or_default();
+                match signals.iter_mut().find(|(other, _)| *other == signal) {
+                    Some((_, statements)) => statements.push(drivers),
+                    None => signals.push((signal, vec![drivers])),
+                }
+            }
+        }
+
+        for (signal, statements) in signals {
+            // Statements that drive different subelements of a signal do not conflict,
+            // which cannot be decided without evaluating the indexes
+            if statements.len() < 2 || !statements.iter().flatten().any(|driver| driver.whole) {
+                continue;
+            }
+            let ent = self.root.get_ent(signal);
+            let type_name = match ent.kind() {
+                AnyEntKind::Object(object) => object.subtype.type_mark().designator().to_string(),
+                _ => continue,
+            };
+            let mut drivers = statements.iter().map(|drivers| &drivers[0]);
+            let Some(first) = drivers.next() else {
+                continue;
+            };
+            let mut diagnostic = Diagnostic::new(
+                &first.pos,
+                format!(
+                    "Signal '{}' of unresolved type '{type_name}' is driven by multiple concurrent statements",
+                    ent.designator()
+                ),
+                ErrorCode::MultipleDrivers,
+            );
+            for driver in drivers {
+                diagnostic.add_related(
+                    &driver.pos,
+                    format!("'{}' is also driven here", ent.designator()),
+                );
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+impl Searcher for DriverSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::Architecture(arch) = decl.ast {
+            self.check_architecture(ctx, arch);
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the signals of unresolved types that are driven by more than one
+/// concurrent statement of an architecture of a primary unit
+pub(crate) fn multiple_driver_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = DriverSearcher {
+        root,
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+
+    #[test]
+    fn unresolved_signals_driven_by_multiple_statements() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity sub is
+  port (
+    i : in bit;
+    o : out bit
+  );
+end entity;
+
+architecture arch of sub is
+begin
+  o <= i;
+end architecture;
+
+entity ent is
+  port (
+    a : in bit;
+    q : out bit
+  );
+end entity;
+
+architecture arch of ent is
+  signal s : bit;
+  signal v : bit_vector(0 to 1);
+begin
+  process (a)
+  begin
+    s <= a;
+    q <= a;
+  end process;
+
+  s <= not a;
+
+  inst: entity work.sub port map (i => a, o => q);
+
+  v(0) <= a;
+  v <= \"00\";
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, multiple_driver_diagnostics),
+            vec![
+                Diagnostic::new(
+                    code.s1("s <= a").s1("s"),
+                    "Signal 's' of unresolved type 'BIT' is driven by multiple concurrent statements",
+                    ErrorCode::MultipleDrivers,
+                )
+                .related(code.s1("s <= not").s1("s"), "'s' is also driven here"),
+                Diagnostic::new(
+                    code.s1("q <= a").s1("q"),
+                    "Signal 'q' of unresolved type 'BIT' is driven by multiple concurrent statements",
+                    ErrorCode::MultipleDrivers,
+                )
+                .related(code.s1("o => q").s1("q"), "'q' is also driven here"),
+                Diagnostic::new(
+                    code.s1("v(0)"),
+                    "Signal 'v' of unresolved type 'BIT_VECTOR' is driven by multiple concurrent statements",
+                    ErrorCode::MultipleDrivers,
+                )
+                .related(code.s1("v <=").s1("v"), "'v' is also driven here"),
+            ],
+        );
+    }
+
+    #[test]
+    fn resolved_signals_and_distinct_subelements_may_have_multiple_drivers() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+  port (
+    a : in bit
+  );
+end entity;
+
+architecture arch of ent is
+  function resolve(values : bit_vector) return bit is
+  begin
+    return values(values'left);
+  end function;
+
+  subtype rbit is resolve bit;
+  type rbit_vector is array (natural range <>) of rbit;
+
+  signal r : rbit;
+  signal rv : rbit_vector(0 to 1);
+  signal ri : resolve bit;
+  signal v : bit_vector(0 to 1);
+begin
+  r <= a;
+  r <= not a;
+  rv <= (others => a);
+  rv <= (others => '0');
+  ri <= a;
+  ri <= '0';
+
+  process (a)
+  begin
+    v(0) <= a;
+  end process;
+  v(1) <= a;
+end architecture;",
+        );
+
+        check_diagnostics(lint_ent(builder, multiple_driver_diagnostics), vec![]);
+    }
+}
@@ -0,0 +1,571 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::Search;
+use crate::ast::*;
+use crate::config::ElaborationConfig;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::fold::Folder;
+use crate::lint::width::WidthSearcher;
+use crate::named_entity::{Design, InterfaceEnt, Region};
+use crate::{AnyEntKind, Config, Diagnostic, EntRef, EntityId, SrcPos, TokenAccess};
+use fnv::{FnvHashMap, FnvHashSet};
+use std::ops::Deref;
+
+/// The maximum depth of nested instances, which ends recursive instantiations
+const MAX_DEPTH: usize = 64;
+
+/// The values of the generics of an elaborated entity,
+/// where `None` is the value of a generic without an actual
+type Generics = FnvHashMap<EntityId, Option<i64>>;
+
+/// The values of generics in a canonical order, which identify an elaboration of an architecture
+type GenericsKey = Vec<(usize, Option<i64>)>;
+
+/// A branch of a generate statement and whether any instance elaborates it
+struct GenerateBranch {
+    pos: SrcPos,
+    message: String,
+    elaborated: bool,
+}
+
+/// The architecture that is elaborated
+struct Scope<'s> {
+    ctx: &'s dyn TokenAccess,
+    library_name: &'s Symbol,
+    is_third_party: bool,
+    depth: usize,
+}
+
+struct Elaborator<'a> {
+    root: &'a DesignRoot,
+    config: &'a Config,
+    /// The architectures that have been elaborated, with the values of their generics
+    visited: FnvHashSet<(UnitId, GenericsKey)>,
+    branches: Vec<GenerateBranch>,
+    branch_index: FnvHashMap<SrcPos, usize>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The entity that an instance is bound to, with the architecture if it is given
+struct Binding<'a> {
+    entity: EntRef<'a>,
+    architecture: Option<Symbol>,
+    /// The generics and ports of the entity
+    region: &'a Region<'a>,
+    /// The generics and ports of the instantiated unit
+    formals: &'a Region<'a>,
+}
+
+/// The generics and ports of an entity
+fn entity_region<'a>(entity: EntRef<'a>) -> Option<&'a Region<'a>> {
+    match entity.kind() {
+        AnyEntKind::Design(Design::Entity(_, region)) => Some(region),
+        _ => None,
+    }
+}
+
+/// The interface of the instantiated unit with the designator of an interface of the entity
+fn formal_of(formals: &[InterfaceEnt<'_>], actual: &InterfaceEnt<'_>) -> Option<EntityId> {
+    formals
+        .iter()
+        .find(|formal| formal.designator() == actual.designator())
+        .map(|formal| formal.id())
+}
+
+impl<'a> Elaborator<'a> {
+    fn elaborate(
+        &mut self,
+        entity: EntRef<'a>,
+        architecture: Option<&Symbol>,
+        generics: Generics,
+        depth: usize,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let (Some(library_name), Designator::Identifier(name)) =
+            (entity.library_name(), entity.designator())
+        else {
+            return;
+        };
+        let Some(library) = self.root.get_lib(library_name) else {
+            return;
+        };
+        let is_third_party = self
+            .config
+            .get_library(&library_name.name_utf8())
+            .is_some_and(|library| library.is_third_party);
+        let mut key: GenericsKey = generics
+            .iter()
+            .map(|(id, value)| (id.to_raw(), *value))
+            .collect();
+        key.sort();
+
+        // Without an explicit architecture, each architecture of the entity may be bound
+        for unit in library.secondary_units(name) {
+            if architecture.is_some_and(|name| unit.unit_id().secondary_name() != Some(name)) {
+                continue;
+            }
+            let Some(analyzed) = unit.unit.get() else {
+                continue;
+            };
+            let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(body)) = analyzed.deref()
+            else {
+                continue;
+            };
+            if !self.visited.insert((unit.unit_id().clone(), key.clone())) {
+                continue;
+            }
+            let mut searcher =
+                WidthSearcher::new(self.root, Folder::with_generics(self.root, &generics));
+            searcher.folder.declare_unit(unit);
+            let scope = Scope {
+                ctx: &unit.tokens,
+                library_name,
+                is_third_party,
+                depth,
+            };
+            self.statements(&scope, &mut searcher, &body.statements);
+            if !is_third_party {
+                self.diagnostics.extend(searcher.diagnostics);
+            }
+        }
+    }
+
+    fn statements(
+        &mut self,
+        scope: &Scope<'_>,
+        searcher: &mut WidthSearcher<'a>,
+        statements: &[LabeledConcurrentStatement],
+    ) {
+        for stmt in statements {
+            match &stmt.statement.item {
+                ConcurrentStatement::Block(block) => {
+                    self.statements(scope, searcher, &block.statements)
+                }
+                ConcurrentStatement::IfGenerate(generate) => {
+                    self.if_generate(scope, searcher, generate)
+                }
+                ConcurrentStatement::ForGenerate(generate) => {
+                    let is_null = searcher
+                        .folder
+                        .discrete_range(&generate.discrete_range)
+                        .map(|range| range.length() == 0);
+                    if let Some(label) = &stmt.label.tree {
+                        self.branch(
+                            scope,
+                            scope.ctx.get_pos(label.token).clone(),
+                            format!(
+                                "Generate statement '{}' has a null range and is never elaborated",
+                                label.item
+                            ),
+                            is_null != Some(true),
+                        );
+                    }
+                    if is_null != Some(true) {
+                        self.statements(scope, searcher, &generate.body.statements);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(generate) => {
+                    for alternative in generate.sels.alternatives.iter() {
+                        self.statements(scope, searcher, &alternative.item.statements);
+                    }
+                }
+                ConcurrentStatement::Instance(instance) => {
+                    self.instance(scope, searcher, stmt, instance)
+                }
+                _ => {
+                    let _ = stmt.search(scope.ctx, searcher);
+                }
+            }
+        }
+    }
+
+    fn if_generate(
+        &mut self,
+        scope: &Scope<'_>,
+        searcher: &mut WidthSearcher<'a>,
+        generate: &IfGenerateStatement,
+    ) {
+        // Whether an earlier alternative is elaborated, which is None when it is not known
+        let mut done = Some(false);
+        for conditional in generate.conds.conditionals.iter() {
+            let elaborated = match done {
+                Some(true) => Some(false),
+                Some(false) => searcher.folder.condition(&conditional.condition.item),
+                None => match searcher.folder.condition(&conditional.condition.item) {
+                    Some(false) => Some(false),
+                    _ => None,
+                },
+            };
+            self.alternative(
+                scope,
+                searcher,
+                conditional.condition.pos(scope.ctx),
+                elaborated,
+                &conditional.item,
+            );
+            done = match (done, elaborated) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (None, _) | (_, None) => None,
+                _ => Some(false),
+            };
+        }
+        if let Some((body, else_token)) = &generate.conds.else_item {
+            let pos = scope.ctx.get_pos(*else_token).clone();
+            self.alternative(scope, searcher, pos, done.map(|done| !done), body);
+        }
+    }
+
+    fn alternative(
+        &mut self,
+        scope: &Scope<'_>,
+        searcher: &mut WidthSearcher<'a>,
+        pos: SrcPos,
+        elaborated: Option<bool>,
+        body: &GenerateBody,
+    ) {
+        let elaborated = elaborated != Some(false);
+        self.branch(
+            scope,
+            pos,
+            "Generate alternative is never elaborated".to_owned(),
+            elaborated,
+        );
+        if elaborated {
+            self.statements(scope, searcher, &body.statements);
+        }
+    }
+
+    fn branch(&mut self, scope: &Scope<'_>, pos: SrcPos, message: String, elaborated: bool) {
+        // Branches of third-party libraries are never reported
+        let elaborated = elaborated || scope.is_third_party;
+        match self.branch_index.get(&pos) {
+            Some(idx) => self.branches[*idx].elaborated |= elaborated,
+            None => {
+                self.branch_index.insert(pos.clone(), self.branches.len());
+                self.branches.push(GenerateBranch {
+                    pos,
+                    message,
+                    elaborated,
+                });
+            }
+        }
+    }
+
+    /// The entity that an instance is bound to.
+    /// Components are bound to the entity with the same name in the library of the instance.
+    fn binding(&self, library_name: &Symbol, unit: &InstantiatedUnit) -> Option<Binding<'a>> {
+        match unit {
+            InstantiatedUnit::Entity(name, architecture) => {
+                let entity = self.root.get_ent(name.item.get_suffix_reference()?);
+                let region = entity_region(entity)?;
+                Some(Binding {
+                    entity,
+                    architecture: architecture
+                        .as_ref()
+                        .map(|architecture| architecture.item.item.clone()),
+                    region,
+                    formals: region,
+                })
+            }
+            InstantiatedUnit::Component(name) => {
+                let component = self.root.get_ent(name.item.get_suffix_reference()?);
+                let AnyEntKind::Component(formals) = component.kind() else {
+                    return None;
+                };
+                let Designator::Identifier(name) = component.designator() else {
+                    return None;
+                };
+                let entity = self.root.get_design_entity(library_name, name)?.0;
+                Some(Binding {
+                    entity,
+                    architecture: None,
+                    region: entity_region(entity)?,
+                    formals,
+                })
+            }
+            InstantiatedUnit::Configuration(_) => None,
+        }
+    }
+
+    fn instance(
+        &mut self,
+        scope: &Scope<'_>,
+        searcher: &mut WidthSearcher<'a>,
+        stmt: &LabeledConcurrentStatement,
+        instance: &InstantiationStatement,
+    ) {
+        let Some(binding) = self.binding(scope.library_name, &instance.unit) else {
+            let _ = stmt.search(scope.ctx, searcher);
+            return;
+        };
+        let (ports, generics) = binding.region.ports_and_generics();
+        let (formal_ports, formal_generics) = binding.formals.ports_and_generics();
+
+        // The values of the actuals by formal, which are None when they cannot be folded
+        let mut actuals: FnvHashMap<EntityId, Option<i64>> = FnvHashMap::default();
+        for (idx, assoc) in instance
+            .generic_map
+            .iter()
+            .flat_map(|map| map.list.items.iter())
+            .enumerate()
+        {
+            let formal = match &assoc.formal {
+                Some(formal) => formal.item.get_suffix_reference(),
+                None => formal_generics.get(idx).map(|formal| formal.id()),
+            };
+            let (Some(formal), ActualPart::Expression(expr)) = (formal, &assoc.actual.item) else {
+                continue;
+            };
+            actuals.insert(formal, searcher.folder.integer(expr));
+        }
+
+        // Generics without an actual have the value of their default,
+        // where the default of a component is not considered
+        let mut values = Generics::default();
+        for generic in generics.iter() {
+            match formal_of(&formal_generics, generic).and_then(|formal| actuals.get(&formal)) {
+                Some(Some(value)) => {
+                    values.insert(generic.id(), Some(*value));
+                }
+                Some(None) => {}
+                None => {
+                    values.insert(generic.id(), None);
+                }
+            }
+        }
+
+        let mut folder = Folder::with_generics(self.root, &values);
+        for port in ports.iter() {
+            if let (Some(formal), Some(length)) =
+                (formal_of(&formal_ports, port), folder.length_of(port.id()))
+            {
+                searcher.port_lengths.insert(formal, length);
+            }
+        }
+        let _ = stmt.search(scope.ctx, searcher);
+        searcher.port_lengths.clear();
+
+        self.elaborate(
+            binding.entity,
+            binding.architecture.as_ref(),
+            values,
+            scope.depth + 1,
+        );
+    }
+}
+
+/// The top-level entity, given as `library.entity` or as the name of an entity
+/// that is looked up in all libraries
+fn find_top<'a>(root: &'a DesignRoot, top: &str) -> Option<EntRef<'a>> {
+    let design = match top.split_once('.') {
+        Some((library_name, name)) => {
+            root.get_design_entity(&root.symbol_utf8(library_name), &root.symbol_utf8(name))
+        }
+        None => {
+            let name = root.symbol_utf8(top);
+            root.libraries()
+                .find_map(|library| root.get_design_entity(library.name(), &name))
+        }
+    };
+    design.map(|design| design.0)
+}
+
+/// Diagnostics for the design that is elaborated from the configured top-level entity,
+/// where the values of generics are propagated through the instances of the design.
+/// These are the width mismatches that depend on the values of generics
+/// and the branches of generate statements that no instance elaborates.
+pub(crate) fn elaboration_diagnostics(
+    root: &DesignRoot,
+    config: &Config,
+    elaboration: &ElaborationConfig,
+) -> Vec<Diagnostic> {
+    let Some(top) = find_top(root, &elaboration.top) else {
+        return Vec::new();
+    };
+    let Some(region) = entity_region(top) else {
+        return Vec::new();
+    };
+    let generics: Generics = region
+        .ports_and_generics()
+        .1
+        .iter()
+        .map(|generic| {
+            let name = generic.designator().to_string().to_lowercase();
+            (generic.id(), elaboration.generics.get(&name).copied())
+        })
+        .collect();
+
+    let mut elaborator = Elaborator {
+        root,
+        config,
+        visited: FnvHashSet::default(),
+        branches: Vec::new(),
+        branch_index: FnvHashMap::default(),
+        diagnostics: Vec::new(),
+    };
+    elaborator.elaborate(top, None, generics, 0);
+
+    // Instances with different generics may report the same diagnostic
+    let mut reported = FnvHashSet::default();
+    let mut diagnostics: Vec<_> = elaborator
+        .diagnostics
+        .into_iter()
+        .filter(|diagnostic| reported.insert(diagnostic.clone()))
+        .collect();
+    for branch in elaborator.branches {
+        if !branch.elaborated {
+            diagnostics.push(Diagnostic::new(
+                branch.pos,
+                branch.message,
+                ErrorCode::DeadGenerate,
+            ));
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::{check_diagnostics, check_no_diagnostics};
+
+    #[test]
+    fn generics_are_propagated_through_instances() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity sub is
+  generic (
+    width : natural := 4;
+    enable : boolean := false
+  );
+  port (
+    d : in bit_vector(width - 1 downto 0);
+    q : out bit_vector(width - 1 downto 0)
+  );
+end entity;
+
+architecture arch of sub is
+  signal s : bit_vector(width - 1 downto 0);
+begin
+  s <= \"0000\";
+  q <= d;
+
+  gen_mode: if enable generate
+  elsif width > 16 generate
+  else generate
+  end generate;
+
+  gen_loop: for i in width to 7 generate
+  end generate;
+end architecture;
+
+entity top is
+  generic (w : natural);
+end entity;
+
+architecture arch of top is
+  signal a : bit_vector(7 downto 0);
+  signal b : bit_vector(3 downto 0);
+
+  component sub is
+    generic (width : natural);
+    port (
+      d : in bit_vector(width - 1 downto 0);
+      q : out bit_vector(width - 1 downto 0)
+    );
+  end component;
+begin
+  inst0: entity work.sub generic map (width => w) port map (d => a, q => a);
+  inst1: entity work.sub generic map (width => 8, enable => true) port map (d => b, q => open);
+  inst2: sub generic map (width => 2 * w) port map (d => a, q => open);
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let elaboration = ElaborationConfig {
+            top: "libname.top".to_owned(),
+            generics: FnvHashMap::from_iter([("w".to_owned(), 8)]),
+        };
+
+        check_diagnostics(
+            elaboration_diagnostics(&root, &Config::default(), &elaboration),
+            vec![
+                Diagnostic::new(
+                    code.s1("d => b").s1("b"),
+                    "Width mismatch in association of port 'd': the port has 8 elements, but the actual has 4 elements",
+                    ErrorCode::WidthMismatch,
+                ),
+                Diagnostic::new(
+                    code.s1("(d => a, q => open)").s1("a"),
+                    "Width mismatch in association of port 'd': the port has 16 elements, but the actual has 8 elements",
+                    ErrorCode::WidthMismatch,
+                ),
+                Diagnostic::new(
+                    code.s1("\"0000\""),
+                    "Width mismatch in assignment: the target has 8 elements, but the value has 4 elements",
+                    ErrorCode::WidthMismatch,
+                ),
+                Diagnostic::new(
+                    code.s1("\"0000\""),
+                    "Width mismatch in assignment: the target has 16 elements, but the value has 4 elements",
+                    ErrorCode::WidthMismatch,
+                ),
+                Diagnostic::new(
+                    code.s1("width > 16"),
+                    "Generate alternative is never elaborated",
+                    ErrorCode::DeadGenerate,
+                ),
+                Diagnostic::new(
+                    code.s1("gen_loop"),
+                    "Generate statement 'gen_loop' has a null range and is never elaborated",
+                    ErrorCode::DeadGenerate,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn unknown_generics_are_not_folded() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity top is
+  generic (w : natural);
+end entity;
+
+architecture arch of top is
+  signal s : bit_vector(w - 1 downto 0);
+begin
+  s <= \"0000\";
+
+  gen: if w > 4 generate
+  end generate;
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let elaboration = ElaborationConfig {
+            top: "top".to_owned(),
+            generics: FnvHashMap::default(),
+        };
+        check_no_diagnostics(&elaboration_diagnostics(
+            &root,
+            &Config::default(),
+            &elaboration,
+        ));
+    }
+}
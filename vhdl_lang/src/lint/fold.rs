@@ -0,0 +1,581 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{bit_string_to_string, DesignRoot, Library, LockedUnit};
+use crate::ast::search::{
+    DeclarationItem, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::*;
+use crate::data::Symbol;
+use crate::named_entity::{OverloadedEnt, TypeEnt};
+use crate::{AnyEntKind, EntRef, EntityId, TokenAccess};
+use fnv::{FnvHashMap, FnvHashSet};
+use std::fmt;
+
+/// A discrete range whose bounds are locally static
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) struct StaticRange {
+    pub left: i64,
+    pub right: i64,
+    pub direction: Direction,
+}
+
+impl StaticRange {
+    pub fn low(&self) -> i64 {
+        match self.direction {
+            Direction::Ascending => self.left,
+            Direction::Descending => self.right,
+        }
+    }
+
+    pub fn high(&self) -> i64 {
+        match self.direction {
+            Direction::Ascending => self.right,
+            Direction::Descending => self.left,
+        }
+    }
+
+    pub fn length(&self) -> i64 {
+        self.high()
+            .saturating_sub(self.low())
+            .saturating_add(1)
+            .max(0)
+    }
+
+    pub fn reversed(&self) -> StaticRange {
+        StaticRange {
+            left: self.right,
+            right: self.left,
+            direction: match self.direction {
+                Direction::Ascending => Direction::Descending,
+                Direction::Descending => Direction::Ascending,
+            },
+        }
+    }
+}
+
+impl fmt::Display for StaticRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.direction {
+            Direction::Ascending => write!(f, "{} to {}", self.left, self.right),
+            Direction::Descending => write!(f, "{} downto {}", self.left, self.right),
+        }
+    }
+}
+
+/// Returns true when an object or a type is of an array type
+fn is_array(ent: EntRef<'_>) -> bool {
+    match ent.actual_kind() {
+        AnyEntKind::Object(object) => object.subtype.type_mark().base_type().kind().is_array(),
+        AnyEntKind::Type(_) => {
+            TypeEnt::from_any(ent).is_some_and(|typ| typ.base_type().kind().is_array())
+        }
+        _ => false,
+    }
+}
+
+/// Folds locally static expressions of analyzed design units.
+///
+/// The declarations of a design unit are folded the first time that one of them is referenced,
+/// so the values of constants declared in packages and the ranges of ports declared by
+/// entities and components are known to the units that use them.
+pub(crate) struct Folder<'a> {
+    root: &'a DesignRoot,
+    /// The design units whose declarations have been folded
+    visited: FnvHashSet<UnitId>,
+    /// The values of integer constants and the positions of enumeration literals
+    values: FnvHashMap<EntityId, i64>,
+    /// The range of scalar subtypes and objects and the first index range of array types and objects
+    ranges: FnvHashMap<EntityId, StaticRange>,
+    /// The literals of enumeration types in the order of their positions
+    literals: FnvHashMap<EntityId, Vec<EnumerationLiteral>>,
+    /// The length of array objects whose bounds are not known,
+    /// such as constants of an unconstrained type
+    lengths: FnvHashMap<EntityId, i64>,
+    /// The generics of an elaborated design unit that have no actual,
+    /// whose values are the values of their defaults
+    defaults: FnvHashSet<EntityId>,
+}
+
+impl<'a> Folder<'a> {
+    pub fn new(root: &'a DesignRoot) -> Folder<'a> {
+        Folder {
+            root,
+            visited: FnvHashSet::default(),
+            values: FnvHashMap::default(),
+            ranges: FnvHashMap::default(),
+            literals: FnvHashMap::default(),
+            lengths: FnvHashMap::default(),
+            defaults: FnvHashSet::default(),
+        }
+    }
+
+    /// Folds the declarations of an elaborated design unit with the values of its generics,
+    /// where `None` is the value of a generic without an actual, which has the value of its default
+    pub fn with_generics(
+        root: &'a DesignRoot,
+        generics: &FnvHashMap<EntityId, Option<i64>>,
+    ) -> Folder<'a> {
+        let mut folder = Folder::new(root);
+        for (id, value) in generics.iter() {
+            match value {
+                Some(value) => {
+                    folder.values.insert(*id, *value);
+                }
+                None => {
+                    folder.defaults.insert(*id);
+                }
+            }
+        }
+        folder
+    }
+
+    /// Folds the declarations of a design unit.
+    /// Declarations of secondary units cannot be found through the primary unit,
+    /// so these have to be declared before their values are needed.
+    pub fn declare_unit(&mut self, unit: &LockedUnit) {
+        if !self.visited.insert(unit.unit_id().clone()) {
+            return;
+        }
+        if let Some(analyzed) = unit.unit.get() {
+            let _ = analyzed.search(&unit.tokens, &mut DeclarationFolder { folder: self });
+        }
+    }
+
+    /// Folds the declarations of a primary unit and its secondary units
+    pub fn declare_unit_and_secondaries(&mut self, lib: &Library, primary_unit_name: &Symbol) {
+        for unit in lib
+            .primary_unit(primary_unit_name)
+            .into_iter()
+            .chain(lib.secondary_units(primary_unit_name))
+        {
+            self.declare_unit(unit);
+        }
+    }
+
+    /// Folds the declarations of the primary unit that declares an entity
+    fn declare(&mut self, id: EntityId) {
+        let mut design = self.root.get_ent(id);
+        while let Some(parent) = design.parent {
+            if matches!(parent.kind(), AnyEntKind::Library) {
+                break;
+            }
+            design = parent;
+        }
+        let (Some(library_name), Designator::Identifier(name)) =
+            (design.library_name(), design.designator())
+        else {
+            return;
+        };
+        if let Some(unit) = self
+            .root
+            .get_lib(library_name)
+            .and_then(|library| library.primary_unit(name))
+        {
+            self.declare_unit(unit);
+        }
+    }
+
+    fn value_of(&mut self, id: EntityId) -> Option<i64> {
+        if !self.values.contains_key(&id) {
+            self.declare(id);
+        }
+        self.values.get(&id).copied()
+    }
+
+    /// The range of a scalar subtype or object or the first index range of an array type or object
+    pub fn range_of(&mut self, id: EntityId) -> Option<StaticRange> {
+        if !self.ranges.contains_key(&id) {
+            self.declare(id);
+        }
+        self.ranges.get(&id).copied()
+    }
+
+    /// The literals of an enumeration type
+    pub fn literals_of(&mut self, typ: EntityId) -> Option<Vec<EnumerationLiteral>> {
+        if !self.literals.contains_key(&typ) {
+            self.declare(typ);
+        }
+        self.literals.get(&typ).cloned()
+    }
+
+    /// The length of the first index range of an array type or object
+    pub fn length_of(&mut self, id: EntityId) -> Option<i64> {
+        if !is_array(self.root.get_ent(id)) {
+            return None;
+        }
+        if let Some(range) = self.range_of(id) {
+            return Some(range.length());
+        }
+        self.lengths.get(&id).copied()
+    }
+
+    /// Returns true when an operator denotes an implicitly declared operation
+    fn is_implicit(&self, op: &WithRef<Operator>) -> bool {
+        op.reference
+            .get()
+            .is_some_and(|id| self.root.get_ent(id).is_implicit())
+    }
+
+    /// The value of a locally static integer expression
+    pub fn integer(&mut self, expr: &Expression) -> Option<i64> {
+        match expr {
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) => {
+                i64::try_from(*value).ok()
+            }
+            Expression::Parenthesized(expr) => self.integer(&expr.item),
+            Expression::Qualified(qexpr) => self.integer(&qexpr.expr.item),
+            Expression::Unary(op, expr) if self.is_implicit(&op.item) => {
+                let value = self.integer(&expr.item)?;
+                match op.item.item {
+                    Operator::Plus => Some(value),
+                    Operator::Minus => value.checked_neg(),
+                    Operator::Abs => value.checked_abs(),
+                    _ => None,
+                }
+            }
+            Expression::Binary(op, left, right) if self.is_implicit(&op.item) => {
+                let left = self.integer(&left.item)?;
+                let right = self.integer(&right.item)?;
+                match op.item.item {
+                    Operator::Plus => left.checked_add(right),
+                    Operator::Minus => left.checked_sub(right),
+                    Operator::Times => left.checked_mul(right),
+                    Operator::Div => left.checked_div(right),
+                    Operator::Rem => left.checked_rem(right),
+                    Operator::Mod => {
+                        // The result of mod has the sign of the right operand
+                        let rem = left.checked_rem(right)?;
+                        if rem != 0 && (rem < 0) != (right < 0) {
+                            Some(rem + right)
+                        } else {
+                            Some(rem)
+                        }
+                    }
+                    Operator::Pow => left.checked_pow(u32::try_from(right).ok()?),
+                    _ => None,
+                }
+            }
+            Expression::Name(name) => self.name_integer(name),
+            _ => None,
+        }
+    }
+
+    /// The value of a locally static boolean expression
+    pub fn condition(&mut self, expr: &Expression) -> Option<bool> {
+        match expr {
+            Expression::Parenthesized(expr) => self.condition(&expr.item),
+            Expression::Unary(op, expr)
+                if op.item.item == Operator::Not && self.is_implicit(&op.item) =>
+            {
+                Some(!self.condition(&expr.item)?)
+            }
+            Expression::Binary(op, left, right) if self.is_implicit(&op.item) => match op.item.item
+            {
+                Operator::And
+                | Operator::Or
+                | Operator::Nand
+                | Operator::Nor
+                | Operator::Xor
+                | Operator::Xnor => {
+                    let left = self.condition(&left.item)?;
+                    let right = self.condition(&right.item)?;
+                    match op.item.item {
+                        Operator::And => Some(left && right),
+                        Operator::Or => Some(left || right),
+                        Operator::Nand => Some(!(left && right)),
+                        Operator::Nor => Some(!(left || right)),
+                        Operator::Xor => Some(left != right),
+                        _ => Some(left == right),
+                    }
+                }
+                Operator::EQ
+                | Operator::NE
+                | Operator::LT
+                | Operator::LTE
+                | Operator::GT
+                | Operator::GTE => {
+                    // Enumeration values are compared by their position
+                    let left = self.integer(&left.item)?;
+                    let right = self.integer(&right.item)?;
+                    match op.item.item {
+                        Operator::EQ => Some(left == right),
+                        Operator::NE => Some(left != right),
+                        Operator::LT => Some(left < right),
+                        Operator::LTE => Some(left <= right),
+                        Operator::GT => Some(left > right),
+                        _ => Some(left >= right),
+                    }
+                }
+                _ => None,
+            },
+            // The literals of boolean are false and true at the positions 0 and 1
+            Expression::Name(name) => Some(self.name_integer(name)? != 0),
+            _ => None,
+        }
+    }
+
+    fn name_integer(&mut self, name: &Name) -> Option<i64> {
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                self.value_of(name.get_suffix_reference()?)
+            }
+            Name::Attribute(attr) => {
+                if attr.signature.is_some() || !self.is_first_dimension(attr) {
+                    return None;
+                }
+                match attr.attr.item {
+                    AttributeDesignator::Length => self.name_length(&attr.name.item),
+                    AttributeDesignator::Left => Some(self.prefix_range(&attr.name.item)?.left),
+                    AttributeDesignator::Right => Some(self.prefix_range(&attr.name.item)?.right),
+                    AttributeDesignator::Low => Some(self.prefix_range(&attr.name.item)?.low()),
+                    AttributeDesignator::High => Some(self.prefix_range(&attr.name.item)?.high()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true when an array attribute does not select a dimension other than the first
+    fn is_first_dimension(&mut self, attr: &AttributeName) -> bool {
+        match &attr.expr {
+            Some(expr) => self.integer(&expr.item) == Some(1),
+            None => true,
+        }
+    }
+
+    /// The range denoted by the prefix of an attribute name
+    fn prefix_range(&mut self, name: &Name) -> Option<StaticRange> {
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                self.range_of(name.get_suffix_reference()?)
+            }
+            Name::Slice(_, drange) => self.discrete_range(drange),
+            _ => None,
+        }
+    }
+
+    pub fn range(&mut self, range: &Range) -> Option<StaticRange> {
+        match range {
+            Range::Range(constraint) => Some(StaticRange {
+                left: self.integer(&constraint.left_expr.item)?,
+                right: self.integer(&constraint.right_expr.item)?,
+                direction: constraint.direction,
+            }),
+            Range::Attribute(attr) => {
+                if attr.signature.is_some() || !self.is_first_dimension(attr) {
+                    return None;
+                }
+                let range = self.prefix_range(&attr.name.item)?;
+                match attr.attr.item {
+                    AttributeDesignator::Range(RangeAttribute::Range) => Some(range),
+                    AttributeDesignator::Range(RangeAttribute::ReverseRange) => {
+                        Some(range.reversed())
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    pub fn discrete_range(&mut self, drange: &DiscreteRange) -> Option<StaticRange> {
+        match drange {
+            DiscreteRange::Range(range) | DiscreteRange::Discrete(_, Some(range)) => {
+                self.range(range)
+            }
+            DiscreteRange::Discrete(type_mark, None) => {
+                self.range_of(type_mark.item.get_suffix_reference()?)
+            }
+        }
+    }
+
+    /// The range of a scalar subtype indication
+    /// or the first index range of an array subtype indication
+    pub fn subtype_range(&mut self, subtype: &SubtypeIndication) -> Option<StaticRange> {
+        match subtype
+            .constraint
+            .as_ref()
+            .map(|constraint| &constraint.item)
+        {
+            Some(SubtypeConstraint::Range(range)) => self.range(range),
+            Some(SubtypeConstraint::Array(ranges, _)) => self.discrete_range(&ranges.first()?.item),
+            Some(SubtypeConstraint::Record(_)) => None,
+            None => self.range_of(subtype.type_mark.item.get_suffix_reference()?),
+        }
+    }
+
+    /// The length of an array valued expression when it is locally static
+    pub fn length(&mut self, expr: &Expression) -> Option<i64> {
+        match expr {
+            Expression::Literal(Literal::String(value)) => i64::try_from(value.len()).ok(),
+            Expression::Literal(Literal::BitString(value)) => {
+                i64::try_from(bit_string_to_string(value).ok()?.len()).ok()
+            }
+            Expression::Aggregate(assocs) => {
+                // Aggregates with named associations may depend on the context
+                if assocs
+                    .iter()
+                    .all(|assoc| matches!(assoc.item, ElementAssociation::Positional(_)))
+                {
+                    i64::try_from(assocs.len()).ok()
+                } else {
+                    None
+                }
+            }
+            Expression::Parenthesized(expr) => self.length(&expr.item),
+            Expression::Qualified(qexpr) => self.length(&qexpr.expr.item),
+            Expression::Binary(op, left, right)
+                if op.item.item == Operator::Concat && self.is_implicit(&op.item) =>
+            {
+                let concat = OverloadedEnt::from_any(self.root.get_ent(op.item.reference.get()?))?;
+                let array = concat.return_type()?.base();
+                let mut length: i64 = 0;
+                for (idx, operand) in [left, right].into_iter().enumerate() {
+                    // An operand of the element type adds a single element
+                    if concat.nth_base(idx)? == array {
+                        length = length.checked_add(self.length(&operand.item)?)?;
+                    } else {
+                        length = length.checked_add(1)?;
+                    }
+                }
+                Some(length)
+            }
+            Expression::Name(name) => self.name_length(name),
+            _ => None,
+        }
+    }
+
+    /// The length of an array valued name when it is locally static
+    pub fn name_length(&mut self, name: &Name) -> Option<i64> {
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                self.length_of(name.get_suffix_reference()?)
+            }
+            Name::Slice(_, drange) => Some(self.discrete_range(drange)?.length()),
+            Name::CallOrIndexed(call) => {
+                // A type conversion has the length of its operand
+                let id = call.name.item.get_suffix_reference()?;
+                if !matches!(self.root.get_ent(id).actual_kind(), AnyEntKind::Type(_)) {
+                    return None;
+                }
+                let [assoc] = call.parameters.items.as_slice() else {
+                    return None;
+                };
+                match (&assoc.formal, &assoc.actual.item) {
+                    (None, ActualPart::Expression(expr)) => self.length(expr),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn declare_object(&mut self, id: Option<EntityId>, range: Option<StaticRange>) {
+        if let (Some(id), Some(range)) = (id, range) {
+            self.ranges.insert(id, range);
+        }
+    }
+
+    fn declare_type(&mut self, decl: &TypeDeclaration) {
+        let Some(id) = decl.ident.decl.get() else {
+            return;
+        };
+        let range = match &decl.def {
+            TypeDefinition::Enumeration(literals) => {
+                for (position, literal) in literals.iter().enumerate() {
+                    if let Some(literal_id) = literal.decl.get() {
+                        self.values.insert(literal_id, position as i64);
+                    }
+                }
+                self.literals.insert(
+                    id,
+                    literals
+                        .iter()
+                        .map(|literal| literal.tree.item.clone())
+                        .collect(),
+                );
+                Some(StaticRange {
+                    left: 0,
+                    right: literals.len() as i64 - 1,
+                    direction: Direction::Ascending,
+                })
+            }
+            TypeDefinition::Numeric(range) => self.range(range),
+            TypeDefinition::Array(indexes, _, _) => match indexes.first() {
+                Some(ArrayIndex::Discrete(drange)) => self.discrete_range(&drange.item),
+                _ => None,
+            },
+            TypeDefinition::Subtype(subtype) => self.subtype_range(subtype),
+            _ => None,
+        };
+        if let Some(range) = range {
+            self.ranges.insert(id, range);
+        }
+    }
+}
+
+struct DeclarationFolder<'f, 'a> {
+    folder: &'f mut Folder<'a>,
+}
+
+impl Searcher for DeclarationFolder<'_, '_> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Object(object) => {
+                let range = self.folder.subtype_range(&object.subtype_indication);
+                for ident in object.idents.iter() {
+                    self.folder.declare_object(ident.decl.get(), range);
+                }
+                if let (ObjectClass::Constant, Some(expr)) = (object.class, &object.expression) {
+                    let value = self.folder.integer(&expr.item);
+                    let length = range
+                        .is_none()
+                        .then(|| self.folder.length(&expr.item))
+                        .flatten();
+                    for id in object.idents.iter().filter_map(|ident| ident.decl.get()) {
+                        if let Some(value) = value {
+                            self.folder.values.insert(id, value);
+                        }
+                        if let Some(length) = length {
+                            self.folder.lengths.insert(id, length);
+                        }
+                    }
+                }
+            }
+            DeclarationItem::InterfaceObject(object) => {
+                if let ModeIndication::Simple(mode) = &object.mode {
+                    let range = self.folder.subtype_range(&mode.subtype_indication);
+                    for ident in object.idents.iter() {
+                        self.folder.declare_object(ident.decl.get(), range);
+                    }
+                    if let Some(expr) = &mode.expression {
+                        for id in object.idents.iter().filter_map(|ident| ident.decl.get()) {
+                            if !self.folder.defaults.contains(&id) {
+                                continue;
+                            }
+                            if let Some(value) = self.folder.integer(&expr.item) {
+                                self.folder.values.insert(id, value);
+                            }
+                        }
+                    }
+                }
+            }
+            DeclarationItem::Type(typ) => self.folder.declare_type(typ),
+            DeclarationItem::ForIndex(ident, drange) => {
+                let range = self.folder.discrete_range(drange);
+                self.folder.declare_object(ident.decl.get(), range);
+            }
+            DeclarationItem::ForGenerateIndex(_, generate) => {
+                let range = self.folder.discrete_range(&generate.discrete_range);
+                self.folder
+                    .declare_object(generate.index_name.decl.get(), range);
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
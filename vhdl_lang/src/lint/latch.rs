@@ -0,0 +1,387 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::base_reference;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::lint::sensitivity::is_clocked;
+use crate::{AnyEntKind, Diagnostic, EntityId, SrcPos, TokenAccess};
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// The signals that are assigned by a list of sequential statements
+#[derive(Default)]
+struct Assignments {
+    /// The signals that are assigned on every path through the statements
+    definite: FnvHashSet<EntityId>,
+    /// The first assignment of each signal that is assigned on some path
+    first: Vec<(EntityId, SrcPos)>,
+    /// The branches that miss the assignment of a signal that is assigned in another branch,
+    /// together with a description of the branch
+    missing: FnvHashMap<EntityId, Vec<(SrcPos, &'static str)>>,
+}
+
+impl Assignments {
+    fn is_assigned(&self, id: EntityId) -> bool {
+        self.first.iter().any(|(other, _)| *other == id)
+    }
+
+    fn assign(&mut self, id: EntityId, pos: SrcPos) {
+        if !self.is_assigned(id) {
+            self.first.push((id, pos));
+        }
+    }
+
+    /// Adds the assignments of statements that are executed after the statements of self
+    fn then(&mut self, next: Assignments) {
+        self.definite.extend(next.definite);
+        for (id, pos) in next.first {
+            self.assign(id, pos);
+        }
+        for (id, missing) in next.missing {
+            self.missing.entry(id).or_default().extend(missing);
+        }
+    }
+
+    /// Combines the assignments of alternative branches, of which exactly one is executed
+    fn branches(branches: Vec<(Assignments, SrcPos, &'static str)>) -> Assignments {
+        let mut result = Assignments::default();
+        for (branch, _, _) in branches.iter() {
+            for (id, pos) in branch.first.iter() {
+                result.assign(*id, pos.clone());
+            }
+        }
+        result.definite = result
+            .first
+            .iter()
+            .map(|(id, _)| *id)
+            .filter(|id| {
+                branches
+                    .iter()
+                    .all(|(branch, _, _)| branch.definite.contains(id))
+            })
+            .collect();
+        for (branch, pos, description) in branches {
+            for (id, _) in result.first.iter() {
+                if !branch.is_assigned(*id) {
+                    result
+                        .missing
+                        .entry(*id)
+                        .or_default()
+                        .push((pos.clone(), description));
+                }
+            }
+            for (id, missing) in branch.missing {
+                result.missing.entry(id).or_default().extend(missing);
+            }
+        }
+        result
+    }
+}
+
+struct LatchSearcher<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl LatchSearcher<'_> {
+    fn is_signal(&self, id: EntityId) -> bool {
+        matches!(self.root.get_ent(id).kind(), AnyEntKind::Object(object) if object.class == ObjectClass::Signal)
+    }
+
+    /// The signals that are assigned to by a target
+    fn target_signals(&self, target: &Target, result: &mut Vec<EntityId>) {
+        match target {
+            Target::Name(name) => result.extend(base_reference(name)),
+            Target::Aggregate(assocs) => {
+                for assoc in assocs {
+                    let (ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr)) =
+                        &assoc.item;
+                    if let Expression::Name(name) = &expr.item {
+                        self.target_signals(&Target::Name(name.as_ref().clone()), result);
+                    }
+                }
+            }
+        }
+        result.retain(|id| self.is_signal(*id));
+    }
+
+    fn signal_assignment(
+        &self,
+        ctx: &dyn TokenAccess,
+        target: &WithTokenSpan<Target>,
+        complete: bool,
+    ) -> Assignments {
+        let mut signals = Vec::new();
+        self.target_signals(&target.item, &mut signals);
+        let mut result = Assignments::default();
+        let pos = target.pos(ctx);
+        for id in signals {
+            result.assign(id, pos.clone());
+            if complete {
+                result.definite.insert(id);
+            } else {
+                result
+                    .missing
+                    .entry(id)
+                    .or_default()
+                    .push((pos.clone(), "not assigned when none of the conditions hold"));
+            }
+        }
+        result
+    }
+
+    fn statement(&self, ctx: &dyn TokenAccess, stmt: &LabeledSequentialStatement) -> Assignments {
+        match &stmt.statement.item {
+            SequentialStatement::SignalAssignment(assignment) => {
+                // A conditional assignment without an else does not assign the target
+                // when none of the conditions hold
+                let complete = !matches!(
+                    &assignment.rhs,
+                    AssignmentRightHand::Conditional(conds) if conds.else_item.is_none()
+                );
+                self.signal_assignment(ctx, &assignment.target, complete)
+            }
+            SequentialStatement::SignalForceAssignment(assignment) => {
+                self.signal_assignment(ctx, &assignment.target, true)
+            }
+            SequentialStatement::If(if_stmt) => {
+                let mut branches: Vec<_> = if_stmt
+                    .conds
+                    .conditionals
+                    .iter()
+                    .map(|cond| {
+                        (
+                            self.statements(ctx, &cond.item),
+                            cond.condition.pos(ctx),
+                            "not assigned in this branch",
+                        )
+                    })
+                    .collect();
+                match &if_stmt.conds.else_item {
+                    Some((statements, else_token)) => branches.push((
+                        self.statements(ctx, statements),
+                        ctx.get_pos(*else_token).clone(),
+                        "not assigned in this branch",
+                    )),
+                    None => branches.push((
+                        Assignments::default(),
+                        ctx.get_pos(stmt.statement.span.start_token).clone(),
+                        "not assigned when none of the conditions hold, as there is no else branch",
+                    )),
+                }
+                Assignments::branches(branches)
+            }
+            SequentialStatement::Case(case_stmt) => Assignments::branches(
+                case_stmt
+                    .alternatives
+                    .iter()
+                    .map(|alternative| {
+                        (
+                            self.statements(ctx, &alternative.item),
+                            ctx.get_pos(alternative.span.start_token).clone(),
+                            "not assigned in this branch",
+                        )
+                    })
+                    .collect(),
+            ),
+            // Loops are assumed to be executed, e.g., when assigning the elements of a vector
+            SequentialStatement::Loop(loop_stmt) => self.statements(ctx, &loop_stmt.statements),
+            _ => Assignments::default(),
+        }
+    }
+
+    fn statements(
+        &self,
+        ctx: &dyn TokenAccess,
+        statements: &[LabeledSequentialStatement],
+    ) -> Assignments {
+        let mut result = Assignments::default();
+        for stmt in statements {
+            result.then(self.statement(ctx, stmt));
+        }
+        // A signal that is assigned on every path does not miss any assignment
+        let definite = &result.definite;
+        result.missing.retain(|id, _| !definite.contains(id));
+        result
+    }
+
+    fn check_process(&mut self, ctx: &dyn TokenAccess, process: &ProcessStatement) {
+        // Processes without a sensitivity list are not combinational
+        if process.sensitivity_list.is_none() || is_clocked(ctx, process) {
+            return;
+        }
+        let mut assignments = self.statements(ctx, &process.statements);
+        for (id, pos) in assignments.first.iter() {
+            if assignments.definite.contains(id) {
+                continue;
+            }
+            let designator = self.root.get_ent(*id).designator();
+            let mut diagnostic = Diagnostic::new(
+                pos,
+                format!(
+                    "Latch inferred for '{designator}' as it is not assigned in all branches of the combinational process"
+                ),
+                ErrorCode::InferredLatch,
+            );
+            for (branch, description) in assignments.missing.remove(id).unwrap_or_default() {
+                diagnostic.add_related(branch, format!("'{designator}' is {description}"));
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+impl Searcher for LatchSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::ConcurrentStatement(stmt) = decl.ast {
+            if let ConcurrentStatement::Process(process) = &stmt.statement.item {
+                self.check_process(ctx, process);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the signals of combinational processes of a primary unit and its
+/// secondary units that are not assigned in all branches, which infers latches
+pub(crate) fn latch_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = LatchSearcher {
+        root,
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+
+    #[test]
+    fn signals_not_assigned_in_all_branches_infer_latches() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  port (
+    a, b : in bit;
+    sel : in natural;
+    q, r, s : out bit
+  );
+end entity;
+
+architecture arch of ent is
+begin
+  process (all)
+  begin
+    if a = '1' then
+      q <= b;
+      r <= b;
+    elsif b = '1' then
+      r <= a;
+    end if;
+
+    case sel is
+      when 0 => s <= a;
+      when others => null;
+    end case;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, latch_diagnostics),
+            vec![
+                Diagnostic::new(
+                    code.s1("q <= b").s1("q"),
+                    "Latch inferred for 'q' as it is not assigned in all branches of the combinational process",
+                    ErrorCode::InferredLatch,
+                )
+                .related(code.s1("b = '1'"), "'q' is not assigned in this branch")
+                .related(
+                    code.s1("if a").s1("if"),
+                    "'q' is not assigned when none of the conditions hold, as there is no else branch",
+                ),
+                Diagnostic::new(
+                    code.s1("r <= b").s1("r"),
+                    "Latch inferred for 'r' as it is not assigned in all branches of the combinational process",
+                    ErrorCode::InferredLatch,
+                )
+                .related(
+                    code.s1("if a").s1("if"),
+                    "'r' is not assigned when none of the conditions hold, as there is no else branch",
+                ),
+                Diagnostic::new(
+                    code.s1("s <= a").s1("s"),
+                    "Latch inferred for 's' as it is not assigned in all branches of the combinational process",
+                    ErrorCode::InferredLatch,
+                )
+                .related(code.s1("others"), "'s' is not assigned in this branch"),
+            ],
+        );
+    }
+
+    #[test]
+    fn default_assignments_and_clocked_processes_do_not_infer_latches() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+  port (
+    clk, a, b : in bit;
+    q, r, c : out bit;
+    v : out bit_vector(0 to 1)
+  );
+end entity;
+
+architecture arch of ent is
+begin
+  process (a, b)
+  begin
+    q <= '0';
+    if a = '1' then
+      q <= b;
+      if b = '1' then
+        r <= a;
+      else
+        r <= b;
+      end if;
+    else
+      r <= '0';
+    end if;
+    for i in v'range loop
+      v(i) <= a;
+    end loop;
+  end process;
+
+  process (clk)
+  begin
+    if rising_edge(clk) then
+      if a = '1' then
+        c <= b;
+      end if;
+    end if;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(lint_ent(builder, latch_diagnostics), vec![]);
+    }
+}
@@ -0,0 +1,253 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::{Designator, Mode, ObjectClass};
+use crate::config::NamingRule;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::{Concurrent, HasEntityId, InterfaceMode, ObjectInterface, Related, Type};
+use crate::{AnyEntKind, Config, Diagnostic, EntRef, EntityId, TokenAccess};
+use fnv::FnvHashSet;
+use regex::Regex;
+
+/// The naming conventions of a project, with compiled regular expressions
+pub(crate) struct NamingRules<'a> {
+    rules: Vec<(&'a str, &'a NamingRule, Option<Regex>)>,
+}
+
+impl<'a> NamingRules<'a> {
+    pub fn new(config: &'a Config) -> NamingRules<'a> {
+        NamingRules {
+            rules: config
+                .naming_rules()
+                .iter()
+                .map(|(class, rule)| {
+                    // The regular expressions are validated when reading the configuration
+                    let regex = rule.regex.as_ref().and_then(|regex| Regex::new(regex).ok());
+                    (class.as_str(), rule, regex)
+                })
+                .collect(),
+        }
+    }
+
+    fn get(&self, class: &str) -> Option<&(&'a str, &'a NamingRule, Option<Regex>)> {
+        self.rules.iter().find(|(other, _, _)| *other == class)
+    }
+
+    /// The rule for a class of named entities.
+    /// The rule for all ports applies to the ports of a mode without a rule of its own.
+    fn rule_for(&self, class: &str) -> Option<&(&'a str, &'a NamingRule, Option<Regex>)> {
+        self.get(class).or_else(|| {
+            if class.starts_with("port_") {
+                self.get("port")
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The class of a named entity that a naming convention can be defined for
+fn naming_class(ent: EntRef<'_>) -> Option<&'static str> {
+    match ent.kind() {
+        AnyEntKind::Object(object) => match (object.class, &object.iface) {
+            (_, Some(ObjectInterface::Generic)) => Some("generic"),
+            (ObjectClass::Signal, Some(ObjectInterface::Port(mode))) => Some(match mode {
+                InterfaceMode::Simple(Mode::In) => "port_in",
+                InterfaceMode::Simple(Mode::Out) => "port_out",
+                InterfaceMode::Simple(Mode::InOut) => "port_inout",
+                InterfaceMode::Simple(Mode::Buffer) => "port_buffer",
+                InterfaceMode::Simple(Mode::Linkage) => "port_linkage",
+                InterfaceMode::View(_) => "port_view",
+            }),
+            (ObjectClass::Signal, None) => Some("signal"),
+            (ObjectClass::Constant, None) => Some("constant"),
+            _ => None,
+        },
+        AnyEntKind::Type(Type::Alias(_) | Type::Interface | Type::Universal(_)) => None,
+        AnyEntKind::Type(_) => Some("type"),
+        AnyEntKind::Concurrent(Some(Concurrent::Instance)) => Some("instance"),
+        AnyEntKind::Concurrent(Some(Concurrent::Process)) => Some("process"),
+        _ => None,
+    }
+}
+
+/// Returns the requirement of a rule that a name does not fulfill
+fn violated_requirement(name: &str, rule: &NamingRule, regex: Option<&Regex>) -> Option<String> {
+    let lowercase = name.to_lowercase();
+    if let Some(prefix) = &rule.prefix {
+        if !lowercase.starts_with(&prefix.to_lowercase()) {
+            return Some(format!("the prefix '{prefix}'"));
+        }
+    }
+    if let Some(suffix) = &rule.suffix {
+        if !lowercase.ends_with(&suffix.to_lowercase()) {
+            return Some(format!("the suffix '{suffix}'"));
+        }
+    }
+    if let (Some(pattern), Some(regex)) = (&rule.regex, regex) {
+        if !regex.is_match(name) {
+            return Some(format!("a match of the regular expression '{pattern}'"));
+        }
+    }
+    None
+}
+
+struct NamingSearcher<'a, 'r> {
+    root: &'a DesignRoot,
+    rules: &'r NamingRules<'r>,
+    checked: FnvHashSet<EntityId>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Searcher for NamingSearcher<'_, '_> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let Some(id) = decl.ent_id() else {
+            return NotFinished;
+        };
+        let ent = self.root.get_ent(id);
+        // Checks deferred constants and the bodies of protected types only once
+        if matches!(ent.related, Related::DeclaredBy(_)) || !self.checked.insert(id) {
+            return NotFinished;
+        }
+        let Designator::Identifier(name) = ent.designator() else {
+            return NotFinished;
+        };
+        let Some((rule_id, rule, regex)) =
+            naming_class(ent).and_then(|class| self.rules.rule_for(class))
+        else {
+            return NotFinished;
+        };
+        let name = name.name_utf8();
+        if let (Some(requirement), Some(pos)) = (
+            violated_requirement(&name, rule, regex.as_ref()),
+            ent.decl_pos(),
+        ) {
+            self.diagnostics.push(Diagnostic::new(
+                pos,
+                format!("'{name}' does not follow the naming rule '{rule_id}', which requires {requirement}"),
+                ErrorCode::NamingConvention,
+            ));
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the declarations of a primary unit and its secondary units
+/// whose names do not follow the configured naming conventions
+pub(crate) fn naming_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+    rules: &NamingRules<'_>,
+) -> Vec<Diagnostic> {
+    if rules.rules.is_empty() {
+        return Vec::new();
+    }
+    let mut searcher = NamingSearcher {
+        root,
+        rules,
+        checked: FnvHashSet::default(),
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+    use std::path::Path;
+
+    #[test]
+    fn names_that_do_not_follow_the_rules() {
+        let config = Config::from_str(
+            "
+[libraries]
+
+[naming]
+signal = { suffix = '_s' }
+constant = { regex = '^[A-Z][A-Z0-9_]*$' }
+port = { prefix = 'p_' }
+port_in = { prefix = 'i_' }
+type = { suffix = '_t' }
+instance = { prefix = 'u_' }
+process = { prefix = 'proc_' }
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  generic (width : natural := 8);
+  port (
+    I_Clk, data : in bit;
+    p_q, result : out bit
+  );
+end entity;
+
+architecture arch of ent is
+  type state_t is (idle, busy);
+  type mode is (a, b);
+  constant MAX_COUNT : natural := width;
+  constant min_count : natural := 0;
+  signal state_s : state_t;
+  signal counter : mode;
+begin
+  u_inst: entity work.ent port map (I_Clk => I_Clk, data => data, p_q => open, result => open);
+  inst: entity work.ent port map (I_Clk => I_Clk, data => data, p_q => open, result => open);
+  proc_main: process
+  begin
+    wait;
+  end process;
+  main: process
+  begin
+    wait;
+  end process;
+end architecture;",
+        );
+
+        // The labels of instances and processes also occur earlier in the code
+        let naming_occurrence = |name: &str, occurrence: usize, rule: &str, requirement: &str| {
+            Diagnostic::new(
+                code.s(name, occurrence),
+                format!("'{name}' does not follow the naming rule '{rule}', which requires {requirement}"),
+                ErrorCode::NamingConvention,
+            )
+        };
+        let naming = |name: &str, rule: &str, requirement: &str| {
+            naming_occurrence(name, 1, rule, requirement)
+        };
+        check_diagnostics(
+            lint_ent(builder, |root, lib, name| {
+                naming_diagnostics(root, lib, name, &NamingRules::new(&config))
+            }),
+            vec![
+                naming("data", "port_in", "the prefix 'i_'"),
+                naming("result", "port", "the prefix 'p_'"),
+                naming("mode", "type", "the suffix '_t'"),
+                naming(
+                    "min_count",
+                    "constant",
+                    "a match of the regular expression '^[A-Z][A-Z0-9_]*$'",
+                ),
+                naming("counter", "signal", "the suffix '_s'"),
+                naming_occurrence("inst", 2, "instance", "the prefix 'u_'"),
+                naming_occurrence("main", 2, "process", "the prefix 'proc_'"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,351 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{
+    DeclarationItem, Finished, Found, FoundDeclaration, NotFinished, Search, SearchState, Searcher,
+};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::base_reference;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::{InterfaceMode, Reference};
+use crate::{AnyEntKind, Diagnostic, EntityId, Position, SrcPos, TokenAccess};
+use fnv::FnvHashSet;
+
+/// Collects the start positions of the targets of signal assignments.
+/// The base name of a target starts at the same position as the target itself.
+struct TargetCollector {
+    starts: FnvHashSet<Position>,
+}
+
+impl TargetCollector {
+    fn add(&mut self, ctx: &dyn TokenAccess, target: &WithTokenSpan<Target>) {
+        self.starts.insert(target.pos(ctx).start());
+    }
+}
+
+impl Searcher for TargetCollector {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::SequentialStatement(stmt) = decl.ast {
+            match &stmt.statement.item {
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.add(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalForceAssignment(assignment) => {
+                    self.add(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalReleaseAssignment(assignment) => {
+                    self.add(ctx, &assignment.target)
+                }
+                _ => {}
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Collects the signals that are read by sequential statements in order of their first read
+struct ReadSignals<'a> {
+    root: &'a DesignRoot,
+    targets: FnvHashSet<Position>,
+    seen: FnvHashSet<EntityId>,
+    signals: Vec<(EntityId, SrcPos)>,
+}
+
+impl Searcher for ReadSignals<'_> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        let Some(id) = reference.get() else {
+            return NotFinished;
+        };
+        if self.targets.contains(&pos.start()) || self.seen.contains(&id) {
+            return NotFinished;
+        }
+        if let AnyEntKind::Object(object) = self.root.get_ent(id).kind() {
+            // Ports of mode `out` are not allowed in a sensitivity list
+            if object.class == ObjectClass::Signal
+                && object.mode() != Some(&InterfaceMode::Simple(Mode::Out))
+            {
+                self.seen.insert(id);
+                self.signals.push((id, pos.clone()));
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Returns the signals that are implicitly contained in a sensitivity list of `all`,
+/// i.e., the signals that are read by the statements of a process.
+pub(crate) fn read_signals(
+    root: &DesignRoot,
+    ctx: &dyn TokenAccess,
+    process: &ProcessStatement,
+) -> Vec<(EntityId, SrcPos)> {
+    let mut targets = TargetCollector {
+        starts: FnvHashSet::default(),
+    };
+    let _ = process.statements.search(ctx, &mut targets);
+    let mut searcher = ReadSignals {
+        root,
+        targets: targets.starts,
+        seen: FnvHashSet::default(),
+        signals: Vec::new(),
+    };
+    let _ = process.statements.search(ctx, &mut searcher);
+    searcher.signals
+}
+
+/// Returns true when an expression detects the edge of a clock,
+/// e.g., `rising_edge(clk)` or `clk'event and clk = '1'`
+fn is_edge_condition(expr: &Expression) -> bool {
+    match expr {
+        Expression::Binary(_, lhs, rhs) => {
+            is_edge_condition(&lhs.item) || is_edge_condition(&rhs.item)
+        }
+        Expression::Unary(_, expr) | Expression::Parenthesized(expr) => {
+            is_edge_condition(&expr.item)
+        }
+        Expression::Name(name) => match name.as_ref() {
+            Name::Attribute(attr) => matches!(
+                attr.attr.item,
+                AttributeDesignator::Signal(SignalAttribute::Event)
+            ),
+            Name::CallOrIndexed(call) => {
+                let designator = match &call.name.item {
+                    Name::Designator(designator) => &designator.item,
+                    Name::Selected(_, suffix) => &suffix.item.item,
+                    _ => return false,
+                };
+                matches!(designator, Designator::Identifier(symbol)
+                    if ["rising_edge", "falling_edge"]
+                        .contains(&symbol.name_utf8().to_lowercase().as_str()))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Detects whether a process is clocked.
+/// The signals that are read by a clocked process are not expected in its sensitivity list.
+struct ClockedProcessSearcher {
+    clocked: bool,
+}
+
+impl Searcher for ClockedProcessSearcher {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::SequentialStatement(stmt) = decl.ast {
+            if let SequentialStatement::If(if_stmt) = &stmt.statement.item {
+                if if_stmt
+                    .conds
+                    .conditionals
+                    .iter()
+                    .any(|cond| is_edge_condition(&cond.condition.item))
+                {
+                    self.clocked = true;
+                    return Finished(Found);
+                }
+            }
+        }
+        NotFinished
+    }
+}
+
+pub(crate) fn is_clocked(ctx: &dyn TokenAccess, process: &ProcessStatement) -> bool {
+    let mut searcher = ClockedProcessSearcher { clocked: false };
+    let _ = process.statements.search(ctx, &mut searcher);
+    searcher.clocked
+}
+
+fn is_signal(root: &DesignRoot, id: EntityId) -> bool {
+    matches!(root.get_ent(id).kind(), AnyEntKind::Object(object) if object.class == ObjectClass::Signal)
+}
+
+/// Checks the explicit sensitivity lists of the processes of a design unit
+struct SensitivityListSearcher<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl SensitivityListSearcher<'_> {
+    fn check_process(&mut self, ctx: &dyn TokenAccess, process: &ProcessStatement) {
+        let Some(WithTokenSpan {
+            item: SensitivityList::Names(names),
+            ..
+        }) = &process.sensitivity_list
+        else {
+            return;
+        };
+        let read = read_signals(self.root, ctx, process);
+        let listed: FnvHashSet<EntityId> = names
+            .iter()
+            .filter_map(|name| base_reference(&name.item))
+            .collect();
+
+        // Clocked processes only need to be sensitive to the clock and asynchronous signals
+        if !is_clocked(ctx, process) {
+            for (id, pos) in read.iter().filter(|(id, _)| !listed.contains(id)) {
+                self.diagnostics.push(Diagnostic::new(
+                    pos,
+                    format!(
+                        "'{}' is read by the process but is missing from the sensitivity list",
+                        self.root.get_ent(*id).designator()
+                    ),
+                    ErrorCode::MissingSensitivity,
+                ));
+            }
+        }
+
+        let read: FnvHashSet<EntityId> = read.into_iter().map(|(id, _)| id).collect();
+        for name in names {
+            let Some(id) = base_reference(&name.item) else {
+                continue;
+            };
+            if !read.contains(&id) && is_signal(self.root, id) {
+                self.diagnostics.push(Diagnostic::new(
+                    name.pos(ctx),
+                    format!(
+                        "'{}' is in the sensitivity list but is never read by the process",
+                        self.root.get_ent(id).designator()
+                    ),
+                    ErrorCode::UnnecessarySensitivity,
+                ));
+            }
+        }
+    }
+}
+
+impl Searcher for SensitivityListSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::ConcurrentStatement(stmt) = decl.ast {
+            if let ConcurrentStatement::Process(process) = &stmt.statement.item {
+                self.check_process(ctx, process);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the processes of a primary unit and its secondary units whose sensitivity
+/// list misses signals that are read or contains signals that are never read
+pub(crate) fn sensitivity_list_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = SensitivityListSearcher {
+        root,
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+
+    #[test]
+    fn missing_and_unnecessary_signals() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  port (
+    a, b, c : in bit;
+    q : out bit
+  );
+end entity;
+
+architecture arch of ent is
+  signal tmp : bit;
+begin
+  comb: process (a, c, tmp)
+  begin
+    tmp <= a;
+    q <= tmp and b;
+  end process;
+
+  complete: process (a, b)
+    variable v : bit;
+  begin
+    v := a;
+    q <= v or b;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, sensitivity_list_diagnostics),
+            vec![
+                Diagnostic::new(
+                    code.s1("and b").s1("b"),
+                    "'b' is read by the process but is missing from the sensitivity list",
+                    ErrorCode::MissingSensitivity,
+                ),
+                Diagnostic::new(
+                    code.s1("a, c").s1("c"),
+                    "'c' is in the sensitivity list but is never read by the process",
+                    ErrorCode::UnnecessarySensitivity,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn clocked_processes_are_only_checked_for_unnecessary_signals() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+  port (
+    clk, rst, d, en : in bit;
+    q : out bit
+  );
+end entity;
+
+architecture arch of ent is
+begin
+  gen: if true generate
+    process (clk, rst, en)
+    begin
+      if rst = '1' then
+        q <= '0';
+      elsif rising_edge(clk) then
+        q <= d;
+      end if;
+    end process;
+  end generate;
+
+  process (all)
+  begin
+    q <= d;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, sensitivity_list_diagnostics),
+            vec![Diagnostic::new(
+                code.s1("en)").s1("en"),
+                "'en' is in the sensitivity list but is never read by the process",
+                ErrorCode::UnnecessarySensitivity,
+            )],
+        );
+    }
+}
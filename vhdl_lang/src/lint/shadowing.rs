@@ -0,0 +1,267 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::Designator;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::{
+    Design, NamedEntities, Overloaded, Region, Related, Visibility, Visible,
+};
+use crate::{AnyEntKind, Diagnostic, EntRef, EntityId, HasEntityId, TokenAccess};
+use fnv::FnvHashMap;
+
+/// Returns true when a declaration can hide or be hidden by another declaration
+fn can_hide(ent: EntRef<'_>) -> bool {
+    ent.decl_pos().is_some()
+        && !ent.is_implicit()
+        && !matches!(ent.related, Related::DeclaredBy(_))
+        && matches!(
+            ent.kind(),
+            AnyEntKind::Object(_)
+                | AnyEntKind::ObjectAlias { .. }
+                | AnyEntKind::ExternalAlias { .. }
+                | AnyEntKind::File(_)
+                | AnyEntKind::InterfaceFile(_)
+                | AnyEntKind::Component(_)
+                | AnyEntKind::Type(_)
+                | AnyEntKind::LoopParameter(_)
+                | AnyEntKind::DeferredConstant(_)
+        )
+}
+
+/// Returns true when the declarations of a region are not visible anywhere else,
+/// such as the ports of a component or the parameters of a subprogram declaration
+fn is_closed_region(ent: EntRef<'_>) -> bool {
+    matches!(
+        ent.kind(),
+        AnyEntKind::Component(_)
+            | AnyEntKind::Overloaded(
+                Overloaded::SubprogramDecl(_)
+                    | Overloaded::UninstSubprogramDecl(..)
+                    | Overloaded::InterfaceSubprogram(_)
+            )
+    )
+}
+
+/// The declarative region and the visibility of the context clause of a design unit
+fn design_regions<'a>(ent: EntRef<'a>) -> Option<(&'a Visibility<'a>, &'a Region<'a>)> {
+    match ent.kind() {
+        AnyEntKind::Design(
+            Design::Entity(visibility, region)
+            | Design::Architecture(visibility, region, _)
+            | Design::Package(visibility, region)
+            | Design::PackageBody(visibility, region)
+            | Design::UninstPackage(visibility, region),
+        ) => Some((visibility, region)),
+        _ => None,
+    }
+}
+
+struct ShadowingSearcher<'a> {
+    root: &'a DesignRoot,
+    /// The declarations of the units, in order
+    declarations: Vec<EntRef<'a>>,
+}
+
+impl Searcher for ShadowingSearcher<'_> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            let ent = self.root.get_ent(id);
+            if can_hide(ent) {
+                self.declarations.push(ent);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Returns true when a declaration is visible at the position of another declaration
+fn is_declared_before(hidden: EntRef<'_>, ent: EntRef<'_>) -> bool {
+    match (hidden.decl_pos(), ent.decl_pos()) {
+        (Some(hidden_pos), Some(pos)) if hidden_pos.source == pos.source => {
+            hidden_pos.start() < pos.start()
+        }
+        _ => true,
+    }
+}
+
+fn hidden_in_regions<'a>(
+    ent: EntRef<'a>,
+    declared: &FnvHashMap<(EntityId, Designator), EntRef<'a>>,
+) -> Option<EntRef<'a>> {
+    let immediate = ent.parent?;
+    let mut ancestor = immediate.parent;
+    while let Some(region) = ancestor {
+        let hidden = match design_regions(region) {
+            Some((_, declarations)) => match declarations.lookup_immediate(ent.designator()) {
+                Some(NamedEntities::Single(hidden)) => Some(*hidden),
+                _ => None,
+            },
+            None => declared
+                .get(&(region.id(), ent.designator().clone()))
+                .copied(),
+        };
+        if let Some(hidden) = hidden {
+            if hidden.id() != ent.id() && can_hide(hidden) && is_declared_before(hidden, ent) {
+                return Some(hidden);
+            }
+        }
+        ancestor = region.parent;
+    }
+    None
+}
+
+fn hidden_by_use_clauses<'a>(ent: EntRef<'a>) -> Option<EntRef<'a>> {
+    let mut visible = Visible::default();
+    let mut ancestor = ent.parent;
+    while let Some(region) = ancestor {
+        if let Some((visibility, declarations)) = design_regions(region) {
+            visibility.lookup_into(ent.designator(), &mut visible);
+            declarations
+                .visibility
+                .lookup_into(ent.designator(), &mut visible);
+        }
+        ancestor = region.parent;
+    }
+    match visible.into_unambiguous(ent.designator()) {
+        Ok(Some(NamedEntities::Single(hidden))) if hidden.id() != ent.id() && can_hide(hidden) => {
+            Some(hidden)
+        }
+        _ => None,
+    }
+}
+
+/// Diagnostics for declarations of a primary unit and its secondary units
+/// that hide a declaration of an enclosing region or of a package made visible by a use clause
+pub(crate) fn shadowing_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = ShadowingSearcher {
+        root,
+        declarations: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+
+    let mut declared = FnvHashMap::default();
+    for ent in searcher.declarations.iter() {
+        if let Some(parent) = ent.parent {
+            declared
+                .entry((parent.id(), ent.designator().clone()))
+                .or_insert(*ent);
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for ent in searcher.declarations.iter() {
+        if ent.parent.is_some_and(is_closed_region) {
+            continue;
+        }
+        let Some(decl_pos) = ent.decl_pos() else {
+            continue;
+        };
+        let (hidden, origin) = if let Some(hidden) = hidden_in_regions(ent, &declared) {
+            (hidden, "of an enclosing declarative region")
+        } else if let Some(hidden) = hidden_by_use_clauses(ent) {
+            (hidden, "made visible by a use clause")
+        } else {
+            continue;
+        };
+        let mut diagnostic = Diagnostic::new(
+            decl_pos,
+            format!(
+                "Declaration of '{}' hides the {} '{}' {origin}",
+                ent.designator(),
+                hidden.kind().describe(),
+                hidden.designator()
+            ),
+            ErrorCode::Shadowing,
+        );
+        if let Some(hidden_pos) = hidden.decl_pos() {
+            diagnostic.add_related(hidden_pos, "Hidden declaration");
+        }
+        diagnostics.push(diagnostic);
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::{check_diagnostics, Code};
+
+    fn hides(code: Code, hidden: Code, message: &str) -> Diagnostic {
+        Diagnostic::new(code, message, ErrorCode::Shadowing).related(hidden, "Hidden declaration")
+    }
+
+    #[test]
+    fn declarations_hiding_outer_and_used_declarations() {
+        let mut builder = LibraryBuilder::new();
+        let pkg = builder.code(
+            "libname",
+            "
+package pkg is
+  constant width : natural := 8;
+end package;",
+        );
+        let code = builder.code(
+            "libname",
+            "
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal count : natural;
+  constant width : natural := 16;
+
+  component comp is
+    port (
+      count : in natural
+    );
+  end component;
+
+  procedure proc(count : natural);
+begin
+  process
+    variable count : natural;
+  begin
+    for count in 0 to 3 loop
+    end loop;
+    wait;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, shadowing_diagnostics),
+            vec![
+                hides(
+                    code.s1("width"),
+                    pkg.s1("width"),
+                    "Declaration of 'width' hides the constant 'width' made visible by a use clause",
+                ),
+                hides(
+                    code.s("count", 4),
+                    code.s1("count"),
+                    "Declaration of 'count' hides the signal 'count' of an enclosing declarative region",
+                ),
+                hides(
+                    code.s("count", 5),
+                    code.s("count", 4),
+                    "Declaration of 'count' hides the variable 'count' of an enclosing declarative region",
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::ObjectClass;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::Type;
+use crate::standard::VHDLStandard;
+use crate::{AnyEntKind, Diagnostic, HasEntityId, TokenAccess};
+
+struct SharedVariableSearcher<'a> {
+    root: &'a DesignRoot,
+    standard: VHDLStandard,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Searcher for SharedVariableSearcher<'_> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        let (DeclarationItem::Object(_), Some(id)) = (&decl.ast, decl.ent_id()) else {
+            return NotFinished;
+        };
+        let ent = self.root.get_ent(id);
+        let AnyEntKind::Object(object) = ent.kind() else {
+            return NotFinished;
+        };
+        let type_mark = object.subtype.type_mark();
+        if object.class != ObjectClass::SharedVariable
+            || matches!(type_mark.base_type().kind(), Type::Protected(..))
+        {
+            return NotFinished;
+        }
+        let Some(decl_pos) = ent.decl_pos() else {
+            return NotFinished;
+        };
+        // Shared variables must be of a protected type since VHDL-2000,
+        // but VHDL-1993 has no protected types
+        let diagnostic = if self.standard < VHDLStandard::VHDL2008 {
+            Diagnostic::new(
+                decl_pos,
+                format!(
+                    "Shared variable '{}' of the non-protected type '{}' is not allowed in VHDL-2000 and newer",
+                    ent.designator(),
+                    type_mark.designator()
+                ),
+                ErrorCode::SharedVariablePortability,
+            )
+        } else {
+            Diagnostic::new(
+                decl_pos,
+                format!(
+                    "Shared variable '{}' is of the non-protected type '{}'",
+                    ent.designator(),
+                    type_mark.designator()
+                ),
+                ErrorCode::UnprotectedSharedVariable,
+            )
+        };
+        self.diagnostics.push(diagnostic);
+        NotFinished
+    }
+}
+
+/// Diagnostics for the shared variables of a primary unit and its secondary units
+/// that are not of a protected type
+pub(crate) fn shared_variable_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+    standard: VHDLStandard,
+) -> Vec<Diagnostic> {
+    let mut searcher = SharedVariableSearcher {
+        root,
+        standard,
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::syntax::test::{check_diagnostics, check_no_diagnostics};
+
+    #[test]
+    fn shared_variables_of_non_protected_types() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  type counter_t is protected
+    procedure increment;
+  end protected;
+
+  type counter_t is protected body
+    variable count : natural := 0;
+    procedure increment is
+    begin
+      count := count + 1;
+    end procedure;
+  end protected body;
+
+  shared variable counter : counter_t;
+  shared variable count : natural;
+begin
+end architecture;",
+        );
+
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+        let lib = root.get_lib(&root.symbol_utf8("libname")).unwrap();
+        let name = root.symbol_utf8("ent");
+
+        check_diagnostics(
+            shared_variable_diagnostics(&root, lib, &name, VHDLStandard::VHDL2008),
+            vec![Diagnostic::new(
+                code.s1("count : natural;").s1("count"),
+                "Shared variable 'count' is of the non-protected type 'NATURAL'",
+                ErrorCode::UnprotectedSharedVariable,
+            )],
+        );
+        check_diagnostics(
+            shared_variable_diagnostics(&root, lib, &name, VHDLStandard::VHDL1993),
+            vec![Diagnostic::new(
+                code.s1("count : natural;").s1("count"),
+                "Shared variable 'count' of the non-protected type 'NATURAL' is not allowed in VHDL-2000 and newer",
+                ErrorCode::SharedVariablePortability,
+            )],
+        );
+    }
+}
@@ -0,0 +1,254 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::*;
+use crate::code_action::base_reference;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::named_entity::{InterfaceEnt, InterfaceMode};
+use crate::{AnyEntKind, Design, Diagnostic, EntityId, TokenAccess};
+use fnv::FnvHashMap;
+
+/// Formats a list of port names, e.g. `'a', 'b' and 'c'`
+fn port_list(ports: &[InterfaceEnt<'_>]) -> String {
+    let names: Vec<_> = ports
+        .iter()
+        .map(|port| format!("'{}'", port.designator()))
+        .collect();
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+struct UnconnectedSearcher<'a> {
+    root: &'a DesignRoot,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> UnconnectedSearcher<'a> {
+    /// The ports of the instantiated entity or component
+    fn ports(&self, unit: &InstantiatedUnit) -> Option<Vec<InterfaceEnt<'a>>> {
+        let name = match unit {
+            InstantiatedUnit::Entity(name, _) | InstantiatedUnit::Component(name) => name,
+            InstantiatedUnit::Configuration(_) => return None,
+        };
+        let ent = self.root.get_ent(name.item.get_suffix_reference()?);
+        match ent.kind() {
+            AnyEntKind::Design(Design::Entity(_, region)) | AnyEntKind::Component(region) => {
+                Some(region.ports_and_generics().0)
+            }
+            _ => None,
+        }
+    }
+
+    /// The port that is associated by a formal, which may be a conversion of the port
+    fn formal_port(formal: &Name, ports: &[InterfaceEnt<'_>]) -> Option<EntityId> {
+        let id = base_reference(formal)?;
+        if ports.iter().any(|port| port.id() == id) {
+            return Some(id);
+        }
+        let Name::CallOrIndexed(call) = formal else {
+            return None;
+        };
+        match &call.parameters.items.first()?.actual.item {
+            ActualPart::Expression(Expression::Name(name)) => base_reference(name),
+            _ => None,
+        }
+    }
+
+    fn check_instance(&mut self, ctx: &dyn TokenAccess, instance: &InstantiationStatement) {
+        let Some(ports) = self.ports(&instance.unit) else {
+            return;
+        };
+
+        // Whether each associated port is left open by all of its associations
+        let mut associated: FnvHashMap<EntityId, bool> = FnvHashMap::default();
+        let mut positional = ports.iter();
+        for assoc in instance
+            .port_map
+            .iter()
+            .flat_map(|map| map.list.items.iter())
+        {
+            let port = match &assoc.formal {
+                Some(formal) => Self::formal_port(&formal.item, &ports),
+                None => positional.next().map(|port| port.id()),
+            };
+            if let Some(port) = port {
+                let is_open = matches!(assoc.actual.item, ActualPart::Open);
+                *associated.entry(port).or_insert(true) &= is_open;
+            }
+        }
+
+        let mut open_inputs = Vec::new();
+        let mut unconnected_outputs = Vec::new();
+        for port in ports {
+            if port.has_default() {
+                continue;
+            }
+            let AnyEntKind::Object(object) = port.kind() else {
+                continue;
+            };
+            match (object.mode(), associated.get(&port.id())) {
+                (Some(InterfaceMode::Simple(Mode::In)), Some(true)) => open_inputs.push(port),
+                (Some(InterfaceMode::Simple(Mode::Out | Mode::InOut | Mode::Buffer)), None) => {
+                    unconnected_outputs.push(port)
+                }
+                _ => {}
+            }
+        }
+
+        let pos = match &instance.unit {
+            InstantiatedUnit::Entity(name, _)
+            | InstantiatedUnit::Component(name)
+            | InstantiatedUnit::Configuration(name) => name.pos(ctx),
+        };
+        for (ports, message, code) in [
+            (open_inputs, "left open", ErrorCode::UnconnectedInputPort),
+            (
+                unconnected_outputs,
+                "not connected",
+                ErrorCode::UnconnectedOutputPort,
+            ),
+        ] {
+            if ports.is_empty() {
+                continue;
+            }
+            let (kind, verb) = if ports.len() == 1 {
+                ("port", "is")
+            } else {
+                ("ports", "are")
+            };
+            let direction = if code == ErrorCode::UnconnectedInputPort {
+                "Input"
+            } else {
+                "Output"
+            };
+            let mut diagnostic = Diagnostic::new(
+                &pos,
+                format!("{direction} {kind} {} {verb} {message}", port_list(&ports)),
+                code,
+            );
+            for port in ports.iter() {
+                if let Some(decl_pos) = port.decl_pos() {
+                    diagnostic.add_related(
+                        decl_pos,
+                        format!("'{}' is declared here", port.designator()),
+                    );
+                }
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+impl Searcher for UnconnectedSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        if let DeclarationItem::ConcurrentStatement(stmt) = decl.ast {
+            if let ConcurrentStatement::Instance(instance) = &stmt.statement.item {
+                self.check_instance(ctx, instance);
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the instances of a primary unit and its secondary units
+/// that leave input ports without a default open or do not connect output ports
+pub(crate) fn unconnected_port_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = UnconnectedSearcher {
+        root,
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+
+    #[test]
+    fn open_inputs_and_unconnected_outputs() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity sub is
+  port (
+    a : in bit;
+    b : in bit;
+    c : in bit := '0';
+    x : out bit;
+    y : out bit;
+    z : out bit := '0'
+  );
+end entity;
+
+architecture arch of sub is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal s : bit;
+
+  component comp is
+    port (
+      d : in bit;
+      q : out bit
+    );
+  end component;
+begin
+  inst0: entity work.sub
+    port map (a => open, b => open, c => open, x => s);
+
+  inst1: entity work.sub
+    port map (s, s, open, s, open);
+
+  inst2: comp port map (d => open, q => s);
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, unconnected_port_diagnostics),
+            vec![
+                Diagnostic::new(
+                    code.s("work.sub", 1),
+                    "Input ports 'a' and 'b' are left open",
+                    ErrorCode::UnconnectedInputPort,
+                )
+                .related(code.s1("a : in").s1("a"), "'a' is declared here")
+                .related(code.s1("b : in").s1("b"), "'b' is declared here"),
+                Diagnostic::new(
+                    code.s("work.sub", 1),
+                    "Output port 'y' is not connected",
+                    ErrorCode::UnconnectedOutputPort,
+                )
+                .related(code.s1("y : out").s1("y"), "'y' is declared here"),
+                Diagnostic::new(
+                    code.s1("comp port map").s1("comp"),
+                    "Input port 'd' is left open",
+                    ErrorCode::UnconnectedInputPort,
+                )
+                .related(code.s1("d : in").s1("d"), "'d' is declared here"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,323 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::code_action::base_reference;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit;
+use crate::named_entity::{InterfaceMode, ObjectInterface, Reference};
+use crate::{AnyEntKind, Diagnostic, EntRef, EntityId, HasEntityId, SrcPos, TokenAccess};
+use fnv::{FnvHashMap, FnvHashSet};
+
+struct UndrivenSearcher<'a> {
+    root: &'a DesignRoot,
+    /// True when the searched unit is an architecture
+    is_architecture: bool,
+    /// The signals declared by the architecture and the output ports of its entity, in order
+    candidates: Vec<EntRef<'a>>,
+    driven: FnvHashSet<EntityId>,
+    /// The positions of the names that are driven, which are not reads of the signal
+    driver_positions: Vec<SrcPos>,
+    references: Vec<(EntityId, SrcPos)>,
+}
+
+impl<'a> UndrivenSearcher<'a> {
+    fn new(root: &'a DesignRoot) -> Self {
+        UndrivenSearcher {
+            root,
+            is_architecture: false,
+            candidates: Vec::new(),
+            driven: FnvHashSet::default(),
+            driver_positions: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    /// Marks the signal referenced by a name as driven, following aliases of the signal
+    fn drive(&mut self, name: &Name) {
+        if let Some(id) = base_reference(name) {
+            self.driven.insert(self.root.get_ent(id).as_actual().id());
+        }
+    }
+
+    fn drive_target(&mut self, ctx: &dyn TokenAccess, target: &WithTokenSpan<Target>) {
+        match &target.item {
+            Target::Name(name) => {
+                self.drive(name);
+                self.driver_positions.push(target.pos(ctx));
+            }
+            Target::Aggregate(assocs) => {
+                for assoc in assocs {
+                    let (ElementAssociation::Positional(expr) | ElementAssociation::Named(_, expr)) =
+                        &assoc.item;
+                    if let Expression::Name(name) = &expr.item {
+                        self.drive(name);
+                        self.driver_positions.push(expr.pos(ctx));
+                    }
+                }
+            }
+        }
+    }
+
+    /// The actuals of a procedure call may be associated with signal parameters of mode out,
+    /// which cannot be decided without resolving the formals, so every name is considered driven
+    fn drive_call(&mut self, call: &CallOrIndexed) {
+        for assoc in call.parameters.items.iter() {
+            if let ActualPart::Expression(Expression::Name(name)) = &assoc.actual.item {
+                self.drive(name);
+            }
+        }
+    }
+
+    /// Returns true when the formal of an association is known to be a port of mode in
+    fn is_input_port(&self, formal: Option<&WithTokenSpan<Name>>) -> bool {
+        let Some(id) = formal.and_then(|formal| base_reference(&formal.item)) else {
+            return false;
+        };
+        matches!(
+            self.root.get_ent(id).kind(),
+            AnyEntKind::Object(object) if matches!(
+                object.iface,
+                Some(ObjectInterface::Port(InterfaceMode::Simple(Mode::In)))
+            )
+        )
+    }
+
+    fn drive_port_map(&mut self, ctx: &dyn TokenAccess, port_map: &MapAspect) {
+        for assoc in port_map.list.items.iter() {
+            let ActualPart::Expression(Expression::Name(name)) = &assoc.actual.item else {
+                continue;
+            };
+            if self.is_input_port(assoc.formal.as_ref()) {
+                continue;
+            }
+            self.drive(name);
+            if assoc.formal.is_some() {
+                self.driver_positions.push(assoc.actual.pos(ctx));
+            }
+        }
+    }
+
+    fn declare(&mut self, id: EntityId) {
+        let ent = self.root.get_ent(id);
+        let AnyEntKind::Object(object) = ent.kind() else {
+            return;
+        };
+        if object.class != ObjectClass::Signal || object.has_default {
+            return;
+        }
+        let is_candidate = match object.iface {
+            None => self.is_architecture,
+            Some(ObjectInterface::Port(InterfaceMode::Simple(mode))) => {
+                !self.is_architecture && matches!(mode, Mode::Out | Mode::Buffer)
+            }
+            Some(_) => false,
+        };
+        if is_candidate {
+            self.candidates.push(ent);
+        }
+    }
+
+    fn is_driver_position(&self, pos: &SrcPos) -> bool {
+        self.driver_positions
+            .iter()
+            .any(|driver| driver.source == pos.source && driver.contains(pos.start()))
+    }
+
+    fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut reads: FnvHashMap<EntityId, &SrcPos> = FnvHashMap::default();
+        for (id, pos) in self.references.iter() {
+            if !self.is_driver_position(pos) {
+                reads.entry(*id).or_insert(pos);
+            }
+        }
+
+        let mut diagnostics = Vec::new();
+        for ent in self.candidates.iter() {
+            if self.driven.contains(&ent.id()) {
+                continue;
+            }
+            let Some(decl_pos) = ent.decl_pos() else {
+                continue;
+            };
+            let is_port = matches!(ent.kind(), AnyEntKind::Object(object) if object.is_port());
+            if is_port {
+                diagnostics.push(Diagnostic::new(
+                    decl_pos,
+                    format!("Output port '{}' is never driven", ent.designator()),
+                    ErrorCode::UndrivenSignal,
+                ));
+            } else if let Some(read_pos) = reads.get(&ent.id()) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        decl_pos,
+                        format!("Signal '{}' is read but never driven", ent.designator()),
+                        ErrorCode::UndrivenSignal,
+                    )
+                    .related(*read_pos, format!("'{}' is read here", ent.designator())),
+                );
+            }
+        }
+        diagnostics
+    }
+}
+
+impl Searcher for UndrivenSearcher<'_> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.get() {
+            self.references
+                .push((self.root.get_ent(id).as_actual().id(), pos.clone()));
+        }
+        NotFinished
+    }
+
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::Architecture(_) => self.is_architecture = true,
+            DeclarationItem::Object(_) | DeclarationItem::InterfaceObject(_) => {
+                if let Some(id) = decl.ent_id() {
+                    self.declare(id);
+                }
+            }
+            DeclarationItem::SequentialStatement(stmt) => match &stmt.statement.item {
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.drive_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalForceAssignment(assignment) => {
+                    self.drive_target(ctx, &assignment.target)
+                }
+                SequentialStatement::SignalReleaseAssignment(assignment) => {
+                    self.drive_target(ctx, &assignment.target)
+                }
+                SequentialStatement::ProcedureCall(call) => self.drive_call(&call.item),
+                _ => {}
+            },
+            DeclarationItem::ConcurrentStatement(stmt) => match &stmt.statement.item {
+                ConcurrentStatement::Assignment(assignment) => {
+                    self.drive_target(ctx, &assignment.assignment.target)
+                }
+                ConcurrentStatement::ProcedureCall(call) => self.drive_call(&call.call.item),
+                ConcurrentStatement::Instance(instance) => {
+                    if let Some(port_map) = &instance.port_map {
+                        self.drive_port_map(ctx, port_map);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the signals of the architectures of a primary unit that are read but
+/// never driven, and for the output ports of the entity that an architecture never drives
+pub(crate) fn undriven_signal_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut ports = UndrivenSearcher::new(root);
+    if let Some(unit) = lib.primary_unit(primary_unit_name) {
+        search_unit(unit, &mut ports);
+    }
+
+    let mut diagnostics = Vec::new();
+    for unit in lib.secondary_units(primary_unit_name) {
+        let mut searcher = UndrivenSearcher::new(root);
+        searcher.candidates.clone_from(&ports.candidates);
+        search_unit(unit, &mut searcher);
+        if searcher.is_architecture {
+            diagnostics.extend(searcher.diagnostics());
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+
+    #[test]
+    fn signals_and_ports_that_are_never_driven() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity sub is
+  port (
+    i : in bit;
+    o : out bit
+  );
+end entity;
+
+architecture arch of sub is
+begin
+  o <= i;
+end architecture;
+
+entity ent is
+  port (
+    a : in bit;
+    q : out bit;
+    r : out bit;
+    z : out bit := '0'
+  );
+end entity;
+
+architecture arch of ent is
+  signal undriven : bit;
+  signal unread : bit;
+  signal by_process : bit;
+  signal by_instance : bit;
+  signal by_alias : bit_vector(0 to 1);
+  alias first : bit is by_alias(0);
+  signal constant_value : bit := '1';
+  signal indexes : bit_vector(0 to 1);
+begin
+  process (a)
+  begin
+    by_process <= a;
+  end process;
+
+  inst: entity work.sub port map (i => undriven, o => by_instance);
+  first <= a;
+  indexes(0) <= a;
+
+  q <= by_process and by_instance and by_alias(1) and constant_value and indexes(1);
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, undriven_signal_diagnostics),
+            vec![
+                Diagnostic::new(
+                    code.s1("r : out").s1("r"),
+                    "Output port 'r' is never driven",
+                    ErrorCode::UndrivenSignal,
+                ),
+                Diagnostic::new(
+                    code.s1("undriven"),
+                    "Signal 'undriven' is read but never driven",
+                    ErrorCode::UndrivenSignal,
+                )
+                .related(code.s("undriven", 2), "'undriven' is read here"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,284 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::Library;
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::{Diagnostic, TokenAccess};
+
+/// Describes why execution never continues after a statement,
+/// or returns `None` when it may continue with the next statement
+pub(super) fn ends_execution(stmt: &LabeledSequentialStatement) -> Option<&'static str> {
+    match &stmt.statement.item {
+        SequentialStatement::Return(_) => Some("return statement"),
+        SequentialStatement::Exit(exit_stmt) if exit_stmt.condition.is_none() => {
+            Some("exit statement")
+        }
+        SequentialStatement::Next(next_stmt) if next_stmt.condition.is_none() => {
+            Some("next statement")
+        }
+        SequentialStatement::Wait(WaitStatement {
+            sensitivity_clause: None,
+            condition_clause: None,
+            timeout_clause: None,
+        }) => Some("wait statement, which waits forever"),
+        SequentialStatement::If(if_stmt) => {
+            let Some((else_statements, _)) = &if_stmt.conds.else_item else {
+                return None;
+            };
+            if if_stmt
+                .conds
+                .conditionals
+                .iter()
+                .map(|cond| &cond.item)
+                .chain(std::iter::once(else_statements))
+                .all(|statements| never_reaches_end(statements))
+            {
+                Some("if statement, as none of its branches continue")
+            } else {
+                None
+            }
+        }
+        SequentialStatement::Case(case_stmt) => {
+            if case_stmt
+                .alternatives
+                .iter()
+                .all(|alternative| never_reaches_end(&alternative.item))
+            {
+                Some("case statement, as none of its alternatives continue")
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns true when execution never reaches the end of a list of statements
+fn never_reaches_end(statements: &[LabeledSequentialStatement]) -> bool {
+    statements.iter().any(|stmt| ends_execution(stmt).is_some())
+}
+
+struct UnreachableSearcher {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl UnreachableSearcher {
+    fn check_statements(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        statements: &[LabeledSequentialStatement],
+    ) {
+        for (i, stmt) in statements.iter().enumerate() {
+            self.check_nested(ctx, stmt);
+            if let Some(reason) = ends_execution(stmt) {
+                // Only the first unreachable statement is reported, as all following are unreachable as well
+                if let Some(next) = statements.get(i + 1) {
+                    self.diagnostics.push(
+                        Diagnostic::new(
+                            next.statement.span.pos(ctx),
+                            "Statement can never be executed",
+                            ErrorCode::UnreachableCode,
+                        )
+                        .related(
+                            ctx.get_pos(stmt.statement.span.start_token),
+                            format!("Execution does not continue after this {reason}"),
+                        ),
+                    );
+                }
+                return;
+            }
+        }
+    }
+
+    fn check_nested(&mut self, ctx: &dyn TokenAccess, stmt: &LabeledSequentialStatement) {
+        match &stmt.statement.item {
+            SequentialStatement::If(if_stmt) => {
+                for cond in if_stmt.conds.conditionals.iter() {
+                    self.check_statements(ctx, &cond.item);
+                }
+                if let Some((statements, _)) = &if_stmt.conds.else_item {
+                    self.check_statements(ctx, statements);
+                }
+            }
+            SequentialStatement::Case(case_stmt) => {
+                for alternative in case_stmt.alternatives.iter() {
+                    self.check_statements(ctx, &alternative.item);
+                }
+            }
+            SequentialStatement::Loop(loop_stmt) => {
+                self.check_statements(ctx, &loop_stmt.statements);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Searcher for UnreachableSearcher {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::ConcurrentStatement(stmt) => {
+                if let ConcurrentStatement::Process(process) = &stmt.statement.item {
+                    self.check_statements(ctx, &process.statements);
+                }
+            }
+            DeclarationItem::Subprogram(body) => {
+                self.check_statements(ctx, &body.statements);
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the sequential statements of a primary unit and its secondary units
+/// that can never be executed as they follow a statement that does not continue
+pub(crate) fn unreachable_code_diagnostics(
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = UnreachableSearcher {
+        diagnostics: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::check_diagnostics;
+
+    #[test]
+    fn statements_after_return_exit_next_and_wait() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  function fun(x : natural) return natural is
+  begin
+    if x = 0 then
+      return 1;
+    else
+      return 2;
+    end if;
+    report \"after if\";
+  end function;
+
+  signal s : bit;
+begin
+  process
+  begin
+    for i in 0 to 3 loop
+      exit;
+      s <= '0';
+    end loop;
+    for i in 0 to 3 loop
+      next;
+      s <= '1';
+      s <= '0';
+    end loop;
+    wait;
+    report \"after wait\";
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, |_, lib, name| unreachable_code_diagnostics(lib, name)),
+            vec![
+                Diagnostic::new(
+                    code.s1("report \"after if\";"),
+                    "Statement can never be executed",
+                    ErrorCode::UnreachableCode,
+                )
+                .related(
+                    code.s1("if x").s1("if"),
+                    "Execution does not continue after this if statement, as none of its branches continue",
+                ),
+                Diagnostic::new(
+                    code.s1("s <= '0';"),
+                    "Statement can never be executed",
+                    ErrorCode::UnreachableCode,
+                )
+                .related(
+                    code.s1("exit"),
+                    "Execution does not continue after this exit statement",
+                ),
+                Diagnostic::new(
+                    code.s1("s <= '1';"),
+                    "Statement can never be executed",
+                    ErrorCode::UnreachableCode,
+                )
+                .related(
+                    code.s1("next"),
+                    "Execution does not continue after this next statement",
+                ),
+                Diagnostic::new(
+                    code.s1("report \"after wait\";"),
+                    "Statement can never be executed",
+                    ErrorCode::UnreachableCode,
+                )
+                .related(
+                    code.s1("wait;").s1("wait"),
+                    "Execution does not continue after this wait statement, which waits forever",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn statements_after_conditional_exits_are_reachable() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  function fun(x : natural) return natural is
+  begin
+    if x = 0 then
+      return 1;
+    end if;
+    return 2;
+  end function;
+
+  signal s, clk : bit;
+begin
+  process
+  begin
+    for i in 0 to 3 loop
+      exit when s = '1';
+      next when s = '0';
+      s <= '0';
+    end loop;
+    wait until clk = '1';
+    s <= '1';
+    wait for 1 ns;
+    wait on s;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, |_, lib, name| {
+                unreachable_code_diagnostics(lib, name)
+            }),
+            vec![],
+        );
+    }
+}
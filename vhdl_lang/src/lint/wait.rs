@@ -0,0 +1,235 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::search_unit_and_secondaries;
+use crate::lint::unreachable::ends_execution;
+use crate::named_entity::Related;
+use crate::{Diagnostic, EntityId, SrcPos, TokenAccess};
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// The reachable wait statements and procedure calls of a process or a procedure
+#[derive(Default, Clone)]
+struct Waits {
+    has_wait: bool,
+    calls: Vec<EntityId>,
+}
+
+impl Waits {
+    fn scan(&mut self, statements: &[LabeledSequentialStatement]) {
+        for stmt in statements {
+            match &stmt.statement.item {
+                SequentialStatement::Wait(_) => self.has_wait = true,
+                SequentialStatement::ProcedureCall(call) => {
+                    if let Some(id) = call.item.name.item.get_suffix_reference() {
+                        self.calls.push(id);
+                    }
+                }
+                SequentialStatement::If(if_stmt) => {
+                    for cond in if_stmt.conds.conditionals.iter() {
+                        self.scan(&cond.item);
+                    }
+                    if let Some((statements, _)) = &if_stmt.conds.else_item {
+                        self.scan(statements);
+                    }
+                }
+                SequentialStatement::Case(case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        self.scan(&alternative.item);
+                    }
+                }
+                SequentialStatement::Loop(loop_stmt) => self.scan(&loop_stmt.statements),
+                _ => {}
+            }
+            if ends_execution(stmt).is_some() {
+                return;
+            }
+        }
+    }
+}
+
+struct WaitSearcher<'a> {
+    root: &'a DesignRoot,
+    /// The procedures with a body in the searched units
+    procedures: FnvHashMap<EntityId, Waits>,
+    /// The processes without a sensitivity list
+    processes: Vec<(SrcPos, Waits)>,
+}
+
+impl WaitSearcher<'_> {
+    /// Returns true when a call of a procedure may wait.
+    /// Procedures without a body in the searched units are assumed to wait.
+    fn may_wait(&self, procedure: EntityId, visited: &mut FnvHashSet<EntityId>) -> bool {
+        if !visited.insert(procedure) {
+            return false;
+        }
+        match self.procedures.get(&procedure) {
+            Some(waits) => self.waits(waits, visited),
+            None => true,
+        }
+    }
+
+    fn waits(&self, waits: &Waits, visited: &mut FnvHashSet<EntityId>) -> bool {
+        waits.has_wait
+            || waits
+                .calls
+                .iter()
+                .any(|procedure| self.may_wait(*procedure, visited))
+    }
+}
+
+impl Searcher for WaitSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::ConcurrentStatement(stmt) => {
+                if let ConcurrentStatement::Process(process) = &stmt.statement.item {
+                    if process.sensitivity_list.is_none() {
+                        let mut waits = Waits::default();
+                        waits.scan(&process.statements);
+                        self.processes
+                            .push((ctx.get_pos(stmt.statement.span.start_token).clone(), waits));
+                    }
+                }
+            }
+            DeclarationItem::Subprogram(body) => {
+                let SubprogramSpecification::Procedure(procedure) = &body.specification else {
+                    return NotFinished;
+                };
+                let Some(id) = procedure.designator.decl.get() else {
+                    return NotFinished;
+                };
+                let mut waits = Waits::default();
+                waits.scan(&body.statements);
+                // Calls may refer to the declaration rather than the body
+                if let Related::DeclaredBy(declaration) = self.root.get_ent(id).related {
+                    self.procedures.insert(declaration.id(), waits.clone());
+                }
+                self.procedures.insert(id, waits);
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for the processes of a primary unit and its secondary units
+/// that have neither a sensitivity list nor a reachable wait statement
+pub(crate) fn missing_wait_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = WaitSearcher {
+        root,
+        procedures: FnvHashMap::default(),
+        processes: Vec::new(),
+    };
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+
+    let mut diagnostics = Vec::new();
+    for (pos, waits) in searcher.processes.iter() {
+        if !searcher.waits(waits, &mut FnvHashSet::default()) {
+            diagnostics.push(Diagnostic::new(
+                pos,
+                "Process has neither a sensitivity list nor a reachable wait statement and never suspends",
+                ErrorCode::MissingWait,
+            ));
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::{check_diagnostics, Code};
+
+    fn missing_wait(code: Code) -> Diagnostic {
+        Diagnostic::new(
+            code,
+            "Process has neither a sensitivity list nor a reachable wait statement and never suspends",
+            ErrorCode::MissingWait,
+        )
+    }
+
+    #[test]
+    fn processes_without_wait_statements() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal s : bit;
+
+  procedure wait_cycle is
+  begin
+    wait for 1 ns;
+  end procedure;
+
+  procedure toggle;
+
+  procedure toggle is
+  begin
+    s <= not s;
+  end procedure;
+
+  procedure toggle_and_wait is
+  begin
+    toggle;
+    wait_cycle;
+  end procedure;
+begin
+  sensitive: process (s)
+  begin
+    toggle;
+  end process;
+
+  waits: process
+  begin
+    if s = '1' then
+      wait until s = '0';
+    end if;
+  end process;
+
+  calls: process
+  begin
+    toggle_and_wait;
+  end process;
+
+  never: process
+  begin
+    toggle;
+  end process;
+
+  unreachable: process
+  begin
+    s <= '1';
+    return_loop: loop
+      exit return_loop;
+      wait;
+    end loop;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, missing_wait_diagnostics),
+            vec![
+                missing_wait(code.s1("never: process").s1("process")),
+                missing_wait(code.s1("unreachable: process").s1("process")),
+            ],
+        );
+    }
+}
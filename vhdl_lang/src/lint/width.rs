@@ -0,0 +1,321 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::analysis::{DesignRoot, Library};
+use crate::ast::search::{DeclarationItem, FoundDeclaration, NotFinished, SearchState, Searcher};
+use crate::ast::token_range::WithTokenSpan;
+use crate::ast::*;
+use crate::data::error_codes::ErrorCode;
+use crate::data::Symbol;
+use crate::lint::fold::Folder;
+use crate::lint::search_unit_and_secondaries;
+use crate::{Diagnostic, EntityId, TokenAccess};
+use fnv::FnvHashMap;
+
+pub(crate) struct WidthSearcher<'a> {
+    root: &'a DesignRoot,
+    pub folder: Folder<'a>,
+    /// The lengths of the ports of instantiated units whose generics are known,
+    /// which take precedence over the lengths that are folded without the generics
+    pub port_lengths: FnvHashMap<EntityId, i64>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> WidthSearcher<'a> {
+    pub fn new(root: &'a DesignRoot, folder: Folder<'a>) -> Self {
+        WidthSearcher {
+            root,
+            folder,
+            port_lengths: FnvHashMap::default(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn target_length(&mut self, target: &Target) -> Option<i64> {
+        match target {
+            Target::Name(name) => self.folder.name_length(name),
+            Target::Aggregate(_) => None,
+        }
+    }
+
+    fn check_value(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        target_length: i64,
+        value: &WithTokenSpan<Expression>,
+    ) {
+        let Some(length) = self.folder.length(&value.item) else {
+            return;
+        };
+        if length != target_length {
+            self.diagnostics.push(Diagnostic::new(
+                value.pos(ctx),
+                format!(
+                    "Width mismatch in assignment: the target has {target_length} elements, but the value has {length} elements"
+                ),
+                ErrorCode::WidthMismatch,
+            ));
+        }
+    }
+
+    fn check_waveform(&mut self, ctx: &dyn TokenAccess, target_length: i64, waveform: &Waveform) {
+        if let Waveform::Elements(elements) = waveform {
+            for element in elements {
+                self.check_value(ctx, target_length, &element.value);
+            }
+        }
+    }
+
+    fn check_signal_assignment(&mut self, ctx: &dyn TokenAccess, assignment: &SignalAssignment) {
+        let Some(target_length) = self.target_length(&assignment.target.item) else {
+            return;
+        };
+        match &assignment.rhs {
+            AssignmentRightHand::Simple(waveform) => {
+                self.check_waveform(ctx, target_length, waveform)
+            }
+            AssignmentRightHand::Conditional(conditionals) => {
+                for conditional in conditionals.conditionals.iter() {
+                    self.check_waveform(ctx, target_length, &conditional.item);
+                }
+                if let Some((waveform, _)) = &conditionals.else_item {
+                    self.check_waveform(ctx, target_length, waveform);
+                }
+            }
+            AssignmentRightHand::Selected(selection) => {
+                for alternative in selection.alternatives.iter() {
+                    self.check_waveform(ctx, target_length, &alternative.item);
+                }
+            }
+        }
+    }
+
+    fn check_variable_assignment(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        assignment: &VariableAssignment,
+    ) {
+        let Some(target_length) = self.target_length(&assignment.target.item) else {
+            return;
+        };
+        match &assignment.rhs {
+            AssignmentRightHand::Simple(value) => self.check_value(ctx, target_length, value),
+            AssignmentRightHand::Conditional(conditionals) => {
+                for conditional in conditionals.conditionals.iter() {
+                    self.check_value(ctx, target_length, &conditional.item);
+                }
+                if let Some((value, _)) = &conditionals.else_item {
+                    self.check_value(ctx, target_length, value);
+                }
+            }
+            AssignmentRightHand::Selected(selection) => {
+                for alternative in selection.alternatives.iter() {
+                    self.check_value(ctx, target_length, &alternative.item);
+                }
+            }
+        }
+    }
+
+    fn check_port_map(&mut self, ctx: &dyn TokenAccess, port_map: &MapAspect) {
+        for assoc in port_map.list.items.iter() {
+            // Only associations of whole ports are checked
+            let Some(WithTokenSpan {
+                item: Name::Designator(formal),
+                ..
+            }) = &assoc.formal
+            else {
+                continue;
+            };
+            let ActualPart::Expression(actual) = &assoc.actual.item else {
+                continue;
+            };
+            let Some(port) = formal.reference.get() else {
+                continue;
+            };
+            let port_length = match self.port_lengths.get(&port) {
+                Some(length) => Some(*length),
+                None => self.folder.length_of(port),
+            };
+            let (Some(port_length), Some(length)) = (port_length, self.folder.length(actual))
+            else {
+                continue;
+            };
+            if port_length != length {
+                self.diagnostics.push(Diagnostic::new(
+                    assoc.actual.pos(ctx),
+                    format!(
+                        "Width mismatch in association of port '{}': the port has {port_length} elements, but the actual has {length} elements",
+                        self.root.get_ent(port).designator()
+                    ),
+                    ErrorCode::WidthMismatch,
+                ));
+            }
+        }
+    }
+}
+
+impl Searcher for WidthSearcher<'_> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration<'_>) -> SearchState {
+        match decl.ast {
+            DeclarationItem::SequentialStatement(stmt) => match &stmt.statement.item {
+                SequentialStatement::SignalAssignment(assignment) => {
+                    self.check_signal_assignment(ctx, assignment)
+                }
+                SequentialStatement::VariableAssignment(assignment) => {
+                    self.check_variable_assignment(ctx, assignment)
+                }
+                _ => {}
+            },
+            DeclarationItem::ConcurrentStatement(stmt) => match &stmt.statement.item {
+                ConcurrentStatement::Assignment(assignment) => {
+                    self.check_signal_assignment(ctx, &assignment.assignment)
+                }
+                ConcurrentStatement::Instance(instance) => {
+                    if let Some(port_map) = &instance.port_map {
+                        self.check_port_map(ctx, port_map);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Diagnostics for assignments and port associations of a primary unit and its secondary units
+/// where the lengths of the arrays are locally static and differ
+pub(crate) fn width_mismatch_diagnostics(
+    root: &DesignRoot,
+    lib: &Library,
+    primary_unit_name: &Symbol,
+) -> Vec<Diagnostic> {
+    let mut searcher = WidthSearcher::new(root, Folder::new(root));
+    searcher
+        .folder
+        .declare_unit_and_secondaries(lib, primary_unit_name);
+    search_unit_and_secondaries(lib, primary_unit_name, &mut searcher);
+    searcher.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use crate::lint::tests::lint_ent;
+    use crate::syntax::test::{check_diagnostics, Code};
+
+    fn assignment(code: Code, target: i64, value: i64) -> Diagnostic {
+        Diagnostic::new(
+            code,
+            format!("Width mismatch in assignment: the target has {target} elements, but the value has {value} elements"),
+            ErrorCode::WidthMismatch,
+        )
+    }
+
+    #[test]
+    fn assignments_of_values_with_a_different_width() {
+        let mut builder = LibraryBuilder::new();
+        builder.code(
+            "libname",
+            "
+package pkg is
+  constant width : natural := 8;
+  subtype word_t is bit_vector(2 * width - 1 downto 0);
+end package;",
+        );
+        let code = builder.code(
+            "libname",
+            "
+use work.pkg.all;
+
+entity ent is
+  port (
+    a : in bit_vector(width - 1 downto 0);
+    q : out word_t
+  );
+end entity;
+
+architecture arch of ent is
+  constant init : bit_vector := \"0101\";
+  signal s : bit_vector(0 to 3);
+begin
+  q <= a;
+  q <= a & a;
+  q(7 downto 0) <= a;
+  s <= init;
+  s <= a(a'high downto 5);
+  s <= x\"0F\" when a(0) = '1' else \"011\";
+
+  process
+    variable v : bit_vector(1 to 2);
+  begin
+    v := (a(0), a(1), '0');
+    v := s(1 to 2);
+    wait;
+  end process;
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, width_mismatch_diagnostics),
+            vec![
+                assignment(code.s1("q <= a;").s1("a"), 16, 8),
+                assignment(code.s1("a(a'high downto 5)"), 4, 3),
+                assignment(code.s1("x\"0F\""), 4, 8),
+                assignment(code.s1("\"011\""), 4, 3),
+                assignment(code.s1("(a(0), a(1), '0')"), 2, 3),
+            ],
+        );
+    }
+
+    #[test]
+    fn port_associations_with_a_different_width() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+entity sub is
+  generic (
+    width : natural
+  );
+  port (
+    d : in bit_vector(15 downto 0);
+    g : in bit_vector(width - 1 downto 0)
+  );
+end entity;
+
+architecture arch of sub is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture arch of ent is
+  signal s8 : bit_vector(7 downto 0);
+  signal s16 : bit_vector(15 downto 0);
+begin
+  inst0: entity work.sub
+    generic map (width => 4)
+    port map (d => s8, g => s8);
+
+  inst1: entity work.sub
+    generic map (width => 16)
+    port map (d => s16, g => s16);
+end architecture;",
+        );
+
+        check_diagnostics(
+            lint_ent(builder, width_mismatch_diagnostics),
+            vec![Diagnostic::new(
+                code.s1("d => s8").s1("s8"),
+                "Width mismatch in association of port 'd': the port has 16 elements, but the actual has 8 elements",
+                ErrorCode::WidthMismatch,
+            )],
+        );
+    }
+}
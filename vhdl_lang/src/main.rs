@@ -10,8 +10,8 @@ use std::iter::zip;
 use std::path::{Path, PathBuf};
 use vhdl_lang::ast::DesignFile;
 use vhdl_lang::{
-    Config, Diagnostic, MessagePrinter, Project, Severity, SeverityMap, Source, VHDLFormatter,
-    VHDLParser, VHDLStandard,
+    format_source, Config, Diagnostic, FormatOptions, MessagePrinter, PortListFormat, Project,
+    Severity, SeverityMap, Source, VHDLFormatter, VHDLParser, VHDLStandard, GHDL_WORKDIR,
 };
 
 #[derive(Debug, clap::Args)]
@@ -41,6 +41,38 @@ struct Args {
     #[arg(short = 'l', long)]
     libraries: Option<String>,
 
+    /// Elaborate the given top unit (`library.entity` or `entity`) using GHDL after the analysis
+    /// to detect errors that are only found during elaboration
+    #[arg(long, requires = "config")]
+    elaborate: Option<String>,
+
+    /// The GHDL executable used for elaboration
+    #[arg(long, default_value = "ghdl")]
+    ghdl: String,
+
+    /// Print the generics and ports of the given entity (`library.entity` or `entity`)
+    /// instead of the diagnostics of the analysis
+    #[arg(long, requires = "config")]
+    export_ports: Option<String>,
+
+    /// The format of the exported port list, either `json` or `yaml`
+    #[arg(long, default_value = "json")]
+    export_format: PortListFormat,
+
+    /// Print a testbench skeleton for the given entity (`library.entity` or `entity`)
+    /// instead of the diagnostics of the analysis
+    #[arg(long, requires = "config")]
+    generate_testbench: Option<String>,
+
+    /// Format the files of the project in place using the `[format]` section of the config file.
+    /// Files of third-party libraries are left as they are.
+    #[arg(long, requires = "config")]
+    fmt: bool,
+
+    /// Together with `--fmt`, only list the files that are not formatted instead of changing them
+    #[arg(long, requires = "fmt")]
+    check: bool,
+
     #[clap(flatten)]
     group: Group,
 }
@@ -48,7 +80,19 @@ struct Args {
 fn main() {
     let args = Args::parse();
     if let Some(config_path) = args.group.config {
-        parse_and_analyze_project(config_path, args.num_threads, args.libraries);
+        if args.fmt {
+            format_project(&config_path, args.check);
+        }
+        let elaboration = args.elaborate.map(|top| (top, args.ghdl));
+        let export = args.export_ports.map(|entity| (entity, args.export_format));
+        parse_and_analyze_project(
+            config_path,
+            args.num_threads,
+            args.libraries,
+            elaboration,
+            export,
+            args.generate_testbench,
+        );
     } else if let Some(format) = args.group.format {
         format_file(format);
     }
@@ -67,7 +111,9 @@ fn format_file(format: String) {
             }
             let result = VHDLFormatter::format_design_file(&design_file);
             println!("{result}");
-            check_formatted_file(&path, parser, design_file, &result);
+            if !check_formatted_file(&path, &parser, &design_file, &result) {
+                std::process::exit(1);
+            }
             std::process::exit(0);
         }
         Err(err) => {
@@ -77,17 +123,93 @@ fn format_file(format: String) {
     }
 }
 
-fn check_formatted_file(path: &Path, parser: VHDLParser, design_file: DesignFile, result: &str) {
+/// Formats the files of the project, or only lists the files that are not formatted.
+/// Exits with a non-zero status when a file could not be formatted or,
+/// when checking, a file is not formatted.
+fn format_project(config_path: &str, check: bool) -> ! {
+    let mut msg_printer = MessagePrinter::default();
+    let config =
+        Config::read_file_path(Path::new(config_path)).expect("Failed to read config file");
+    let options = config.format().unwrap_or_else(|| FormatOptions {
+        standard: config.standard(),
+        ..FormatOptions::default()
+    });
+
+    let mut success = true;
+    for library in config.iter_libraries() {
+        if library.is_third_party() {
+            continue;
+        }
+        for (file_name, encoding) in
+            library.file_names_and_encodings(config.standard(), &mut msg_printer)
+        {
+            let bytes = match std::fs::read(&file_name) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    println!("Could not read {}: {err}", file_name.display());
+                    success = false;
+                    continue;
+                }
+            };
+            let text = encoding.decode(&bytes);
+            let source = Source::inline(&file_name, &text);
+            let Some(formatted) = format_source(&source, &options) else {
+                println!(
+                    "{} was not formatted as it contains syntax errors",
+                    file_name.display()
+                );
+                success = false;
+                continue;
+            };
+            if formatted == text {
+                continue;
+            }
+            if check {
+                println!("{} is not formatted", file_name.display());
+                success = false;
+                continue;
+            }
+            let parser = VHDLParser::new(options.standard);
+            let design_file = parser.parse_design_source(&source, &mut Vec::new());
+            if !check_formatted_file(&file_name, &parser, &design_file, &formatted) {
+                println!(
+                    "{} was not formatted as the result would not be equivalent",
+                    file_name.display()
+                );
+                success = false;
+            } else if let Err(err) = std::fs::write(&file_name, encoding.encode(&formatted)) {
+                println!("Could not write {}: {err}", file_name.display());
+                success = false;
+            } else {
+                println!("Formatted {}", file_name.display());
+            }
+        }
+    }
+    std::process::exit(if success { 0 } else { 1 })
+}
+
+/// Checks that the formatted file parses to the same tokens as the original file
+/// and prints the first difference otherwise.
+fn check_formatted_file(
+    path: &Path,
+    parser: &VHDLParser,
+    design_file: &DesignFile,
+    result: &str,
+) -> bool {
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
     let new_file = parser.parse_design_source(&Source::inline(path, result), &mut diagnostics);
     if !diagnostics.is_empty() {
         println!("Formatting failed as it resulted in a syntactically incorrect file.");
         show_diagnostics(&diagnostics, &SeverityMap::default());
-        std::process::exit(1);
+        return false;
     }
-    for ((tokens_a, _), (tokens_b, _)) in zip(new_file.design_units, design_file.design_units) {
+    if new_file.design_units.len() != design_file.design_units.len() {
+        println!("Formatting changed the number of design units");
+        return false;
+    }
+    for ((tokens_a, _), (tokens_b, _)) in zip(&new_file.design_units, &design_file.design_units) {
         for (a, b) in zip(tokens_a, tokens_b) {
-            if !a.equal_format(&b) {
+            if !a.equal_format(b) {
                 println!("Token mismatch");
                 println!("New Token={a:#?}");
                 let contents = a.pos.source.contents();
@@ -96,16 +218,28 @@ fn check_formatted_file(path: &Path, parser: VHDLParser, design_file: DesignFile
                 println!("Old Token={b:#?}");
                 let b_line = result.lines().nth(b.pos.range.start.line as usize).unwrap();
                 println!("    {b_line}");
-                break;
+                return false;
             }
         }
+        if tokens_a.len() != tokens_b.len() {
+            println!(
+                "Token mismatch: {} tokens were formatted as {} tokens",
+                tokens_b.len(),
+                tokens_a.len()
+            );
+            return false;
+        }
     }
+    true
 }
 
 fn parse_and_analyze_project(
     config_path: String,
     num_threads: Option<usize>,
     libraries: Option<String>,
+    elaboration: Option<(String, String)>,
+    export: Option<(String, PortListFormat)>,
+    testbench: Option<String>,
 ) {
     rayon::ThreadPoolBuilder::new()
         .num_threads(num_threads.unwrap_or(0))
@@ -123,7 +257,57 @@ fn parse_and_analyze_project(
     let severity_map = *config.severities();
     let mut project = Project::from_config(config, &mut msg_printer);
     project.enable_unused_declaration_detection();
-    let diagnostics = project.analyse();
+    let mut diagnostics = project.analyse();
+
+    if let Some((entity, format)) = export {
+        match project.export_port_list(&entity, format) {
+            Some(port_list) => {
+                print!("{port_list}");
+                std::process::exit(0);
+            }
+            None => {
+                println!("No entity named '{entity}' was found");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(entity) = testbench {
+        match project.generate_testbench(&entity) {
+            Some(testbench) => {
+                print!("{}", testbench.text);
+                std::process::exit(0);
+            }
+            None => {
+                println!("No entity named '{entity}' was found");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some((top, ghdl)) = elaboration {
+        let workdir = Path::new(&config_path)
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(GHDL_WORKDIR);
+        match project.elaborate_with_ghdl(Path::new(&ghdl), &top, &workdir) {
+            Ok(elaboration) => {
+                for message in elaboration.messages {
+                    println!("{message}");
+                }
+                diagnostics.extend(elaboration.diagnostics);
+                if !elaboration.success {
+                    show_diagnostics(&diagnostics, &severity_map);
+                    println!("Elaboration of '{top}' failed");
+                    std::process::exit(1);
+                }
+            }
+            Err(err) => {
+                println!("Failed to run {ghdl}: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
 
     show_diagnostics(&diagnostics, &severity_map);
 
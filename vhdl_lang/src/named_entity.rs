@@ -18,9 +18,9 @@ pub use types::{BaseType, Subtype, Type, TypeEnt, TypedSelection, UniversalType}
 mod overloaded;
 pub use overloaded::{Overloaded, OverloadedEnt, Signature, SignatureKey, SubprogramKey};
 mod object;
-pub use object::{InterfaceMode, Object, ObjectEnt, ObjectInterface, ViewEnt};
+pub use object::{InterfaceMode, Object, ObjectEnt, ObjectInterface, ViewElementMode, ViewEnt};
 mod design;
-pub use design::{Design, DesignEnt};
+pub use design::{Design, DesignEnt, GenericActuals};
 mod attribute;
 pub use attribute::AttributeEnt;
 mod arena;
@@ -122,8 +122,9 @@ pub enum AnyEntKind<'a> {
     /// the individual named entity.
     Design(Design<'a>),
     /// A VHDL 2019 View.
-    /// The [Subtype] data is the type of the associated record.
-    View(Subtype<'a>),
+    /// The [Subtype] data is the type of the associated record
+    /// and the map contains the mode of each record element.
+    View(Subtype<'a>, FnvHashMap<Designator, ViewElementMode<'a>>),
 }
 
 impl<'a> AnyEntKind<'a> {
@@ -224,6 +225,7 @@ impl<'a> std::fmt::Debug for AnyEnt<'a> {
             src_span,
             source,
             attrs,
+            documentation,
         } = self;
 
         let mut s = f.debug_struct(stringify!(AnyEnt));
@@ -237,6 +239,7 @@ impl<'a> std::fmt::Debug for AnyEnt<'a> {
         s.field(stringify!(src_span), src_span);
         s.field(stringify!(source), source);
         s.field(stringify!(attrs), attrs);
+        s.field(stringify!(documentation), documentation);
         s.finish()
     }
 }
@@ -283,6 +286,10 @@ pub struct AnyEnt<'a> {
 
     /// Custom attributes on this entity
     pub attrs: FnvHashMap<Symbol, (SrcPos, AttributeEnt<'a>)>,
+
+    /// The comments directly above the declaration.
+    /// This is set once the design unit that declares the entity has been analyzed.
+    pub(crate) documentation: Option<String>,
 }
 
 impl Arena {
@@ -350,6 +357,12 @@ impl<'a> AnyEnt<'a> {
         self.id
     }
 
+    /// The documentation of this entity, i.e., the comments directly above its declaration
+    /// without an empty line in between.
+    pub fn documentation(&self) -> Option<&str> {
+        self.documentation.as_deref()
+    }
+
     pub fn declaration(&'a self) -> EntRef<'a> {
         if let Related::DeclaredBy(other) = self.related {
             other
@@ -188,6 +188,7 @@ impl Arena {
             src_span,
             source,
             attrs: Default::default(),
+            documentation: None,
         };
 
         unsafe {
@@ -224,6 +225,7 @@ impl Arena {
                 src_span,
                 source,
                 attrs: Default::default(),
+                documentation: None,
             };
             &*eref as EntRef<'a>
         }
@@ -254,6 +256,15 @@ impl Arena {
         }
     }
 
+    /// Sets the documentation of all entities that have been allocated in the local arena
+    pub(crate) fn set_documentation(&self, documentation: impl Fn(&AnyEnt<'_>) -> Option<String>) {
+        let mut local = self.local.borrow_mut();
+        for idx in 0..local.items.len() {
+            let ent = std::pin::Pin::into_inner(local.items.get_mut(idx).unwrap());
+            ent.documentation = documentation(ent);
+        }
+    }
+
     pub fn get<'a>(&'a self, id: EntityId) -> EntRef<'a> {
         // Since local uses PinnedVec we do not have to worry about
         // returning a pure reference here since allocating new
@@ -4,6 +4,7 @@
 //!
 //! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
+use fnv::FnvHashMap;
 use std::ops::Deref;
 
 use super::*;
@@ -26,17 +27,25 @@ pub enum Design<'a> {
     /// ```vhdl
     /// package foo is new bar generic map (...);
     /// ```
-    PackageInstance(Region<'a>),
+    PackageInstance(Region<'a>, GenericActuals<'a>),
     /// An instantiated package that is part of some generic interface, i.e.,
     /// ```vhdl
     /// generic (
     ///     package foo is new bar generic map (<>)
     /// )
     /// ```
-    InterfacePackageInstance(Region<'a>),
+    InterfacePackageInstance(Region<'a>, GenericActuals<'a>),
     Context(Region<'a>),
 }
 
+/// The uninstantiated package of a package instance
+/// and the actual types of its generic types
+#[derive(Clone, Default)]
+pub struct GenericActuals<'a> {
+    pub package: Option<EntityId>,
+    pub types: FnvHashMap<EntityId, TypeEnt<'a>>,
+}
+
 impl<'a> Design<'a> {
     pub fn describe(&self) -> &'static str {
         use Design::*;
@@ -47,7 +56,7 @@ impl<'a> Design<'a> {
             Package(..) => "package",
             PackageBody(..) => "package body",
             UninstPackage(..) => "uninstantiated package",
-            PackageInstance(_) | InterfacePackageInstance(_) => "package instance",
+            PackageInstance(..) | InterfacePackageInstance(..) => "package instance",
             Context(..) => "context",
         }
     }
@@ -82,8 +91,8 @@ impl<'a> DesignEnt<'a> {
     ) -> Result<NamedEntities<'a>, Diagnostic> {
         match self.kind() {
             Design::Package(_, ref region)
-            | Design::PackageInstance(ref region)
-            | Design::InterfacePackageInstance(ref region) => {
+            | Design::PackageInstance(ref region, _)
+            | Design::InterfacePackageInstance(ref region, _) => {
                 if let Some(decl) = region.lookup_immediate(suffix.designator()) {
                     Ok(decl.clone())
                 } else {
@@ -45,7 +45,7 @@ impl<'a> InterfaceEnt<'a> {
         match ent.kind() {
             AnyEntKind::Object(Object { iface: Some(_), .. })
             | AnyEntKind::InterfaceFile(..)
-            | AnyEntKind::Design(Design::InterfacePackageInstance(_))
+            | AnyEntKind::Design(Design::InterfacePackageInstance(..))
             | AnyEntKind::Type(Type::Interface)
             | AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(_)) => {
                 Some(InterfaceEnt { ent })
@@ -139,7 +139,7 @@ impl<'a> GpkgInterfaceEnt<'a> {
             AnyEntKind::Overloaded(Overloaded::InterfaceSubprogram(_)) => Some(
                 GpkgInterfaceEnt::Subprogram(OverloadedEnt::from_any(ent).unwrap()),
             ),
-            AnyEntKind::Design(Design::InterfacePackageInstance(_)) => {
+            AnyEntKind::Design(Design::InterfacePackageInstance(..)) => {
                 Some(GpkgInterfaceEnt::Package(ent))
             }
             _ => None,
@@ -344,4 +344,8 @@ impl<'a> GpkgRegion<'a> {
     pub fn nth(&self, idx: usize) -> Option<GpkgInterfaceEnt<'a>> {
         self.entities.get(idx).cloned()
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = GpkgInterfaceEnt<'a>> + '_ {
+        self.entities.iter().cloned()
+    }
 }
@@ -11,6 +11,7 @@ use super::*;
 use crate::ast::InterfaceType;
 use crate::ast::Mode;
 use crate::ast::ObjectClass;
+use fnv::FnvHashMap;
 
 // A named entity that is known to be an object
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -204,10 +205,65 @@ impl<'a> ViewEnt<'a> {
     }
 
     pub fn subtype(&self) -> &'a Subtype<'a> {
-        if let AnyEntKind::View(subtype) = self.ent.actual_kind() {
+        if let AnyEntKind::View(subtype, _) = self.ent.actual_kind() {
             subtype
         } else {
             unreachable!("ViewEnt type invariant broken")
         }
     }
+
+    /// The modes of the record elements, by the designator of the element
+    pub fn elements(&self) -> &'a FnvHashMap<Designator, ViewElementMode<'a>> {
+        if let AnyEntKind::View(_, elements) = self.ent.actual_kind() {
+            elements
+        } else {
+            unreachable!("ViewEnt type invariant broken")
+        }
+    }
+
+    pub fn element_mode(&self, designator: &Designator) -> Option<ViewElementMode<'a>> {
+        self.elements().get(designator).copied()
+    }
+
+    /// Returns true when every element of the view may be driven
+    pub fn is_writable(&self) -> bool {
+        self.elements().values().all(ViewElementMode::is_writable)
+    }
+}
+
+/// The mode of an element of a view declaration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewElementMode<'a> {
+    Simple(Mode),
+    /// A record element with the modes of another view
+    Record(ViewEnt<'a>),
+    /// An array element whose elements have the modes of another view
+    Array(ViewEnt<'a>),
+}
+
+impl<'a> ViewElementMode<'a> {
+    pub fn view(&self) -> Option<ViewEnt<'a>> {
+        match self {
+            ViewElementMode::Simple(_) => None,
+            ViewElementMode::Record(view) | ViewElementMode::Array(view) => Some(*view),
+        }
+    }
+
+    /// Returns true when the element and all of its subelements may be driven
+    pub fn is_writable(&self) -> bool {
+        match self {
+            ViewElementMode::Simple(mode) => *mode != Mode::In,
+            ViewElementMode::Record(view) | ViewElementMode::Array(view) => view.is_writable(),
+        }
+    }
+}
+
+impl Display for ViewElementMode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViewElementMode::Simple(mode) => write!(f, "{mode}"),
+            ViewElementMode::Record(view) => write!(f, "view {}", view.ent.designator),
+            ViewElementMode::Array(view) => write!(f, "view ({})", view.ent.designator),
+        }
+    }
 }
@@ -4,7 +4,7 @@
 //!
 //! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 use super::*;
-use crate::ast::Designator;
+use crate::ast::{Designator, Mode};
 use std::fmt::{Debug, Formatter};
 
 pub enum Overloaded<'a> {
@@ -314,6 +314,67 @@ impl<'a> OverloadedEnt<'a> {
             self.signature().describe()
         )
     }
+
+    /// Describes an implicitly declared subprogram in the notation of package STANDARD
+    /// (LRM 16.3), i.e., `function "=" (anonymous, anonymous: BOOLEAN) return BOOLEAN;`.
+    /// Consecutive formals of the same class, mode and type are grouped.
+    /// Returns None when a formal has a default, as the default expression is not known.
+    pub fn describe_implicit_declaration(&self) -> Option<String> {
+        let mut groups: Vec<(Vec<String>, String)> = Vec::new();
+        for formal in self.formals().iter() {
+            if formal.has_default() {
+                return None;
+            }
+            let name = match formal.designator() {
+                Designator::Anonymous(_) => "anonymous".to_owned(),
+                designator => designator.to_string(),
+            };
+            let (class, mode) = match formal.kind() {
+                AnyEntKind::InterfaceFile(_) => ("file ", None),
+                AnyEntKind::Object(object) => (
+                    match object.class {
+                        ObjectClass::Signal => "signal ",
+                        ObjectClass::Variable | ObjectClass::SharedVariable => "variable ",
+                        ObjectClass::Constant => "",
+                    },
+                    object
+                        .mode()
+                        .filter(|mode| !matches!(mode, InterfaceMode::Simple(Mode::In))),
+                ),
+                _ => return None,
+            };
+            let subtype = match mode {
+                Some(mode) => format!("{mode} {}", formal.type_mark().designator()),
+                None => formal.type_mark().designator().to_string(),
+            };
+            match groups.last_mut() {
+                Some((names, last)) if class.is_empty() && *last == subtype => names.push(name),
+                _ => groups.push((vec![format!("{class}{name}")], subtype)),
+            }
+        }
+
+        let mut result = format!(
+            "{} {}",
+            if self.is_function() {
+                "function"
+            } else {
+                "procedure"
+            },
+            self.designator()
+        );
+        if !groups.is_empty() {
+            let formals: Vec<_> = groups
+                .iter()
+                .map(|(names, subtype)| format!("{}: {subtype}", names.join(", ")))
+                .collect();
+            result.push_str(&format!(" ({})", formals.join("; ")));
+        }
+        if let Some(return_type) = self.return_type() {
+            result.push_str(&format!(" return {}", return_type.designator()));
+        }
+        result.push(';');
+        Some(result)
+    }
 }
 
 impl<'a> std::ops::Deref for OverloadedEnt<'a> {
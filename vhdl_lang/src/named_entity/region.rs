@@ -131,6 +131,30 @@ impl<'a> Region<'a> {
         }
     }
 
+    /// Checks that the subprograms declared by a protected type and its body have a body
+    pub(crate) fn check_protected_subprograms_have_body(
+        &self,
+        ptype: EntRef<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for ent in self.immediates() {
+            if let AnyEntKind::Overloaded(
+                Overloaded::SubprogramDecl(_) | Overloaded::UninstSubprogramDecl(..),
+            ) = ent.kind()
+            {
+                ent.error(
+                    diagnostics,
+                    format!(
+                        "Missing body for {} of protected type '{}'",
+                        ent.describe(),
+                        ptype.designator()
+                    ),
+                    ErrorCode::MissingSubprogramBody,
+                );
+            }
+        }
+    }
+
     pub(crate) fn close(&self, diagnostics: &mut dyn DiagnosticHandler) {
         self.check_deferred_constant_pairs(diagnostics);
         self.check_protected_types_have_body(diagnostics);
@@ -166,6 +166,20 @@ impl<'a> TypeEnt<'a> {
         }
     }
 
+    /// Returns true when all scalar subelements of the type are resolved
+    pub fn is_resolved(&self) -> bool {
+        match self.kind() {
+            Type::Alias(alias) => alias.is_resolved(),
+            Type::Subtype(subtype) => subtype.is_resolved(),
+            Type::Array { elem_type, .. } => elem_type.is_resolved(),
+            Type::Record(region) => region.elems.iter().all(|elem| match elem.ent.kind() {
+                AnyEntKind::ElementDeclaration(subtype) => subtype.is_resolved(),
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+
     pub fn base_type(&self) -> TypeEnt<'a> {
         match self.kind() {
             Type::Alias(alias) => alias.base_type(),
@@ -231,6 +245,11 @@ impl<'a> TypeEnt<'a> {
                     ))
                 }
             }
+            // Only the methods of the protected type declaration can be selected,
+            // not the declarations of the protected type body
+            Type::Protected(_, true) if self.base_type().id() != self.id() => {
+                self.base_type().selected(ctx, prefix_pos, suffix)
+            }
             Type::Protected(region, _) => {
                 if let Some(decl) = region.lookup_immediate(suffix.designator()) {
                     match decl {
@@ -471,11 +490,26 @@ impl<'a> Deref for BaseType<'a> {
 #[derive(Clone, Copy)]
 pub struct Subtype<'a> {
     pub(crate) type_mark: TypeEnt<'a>,
+    /// True when the subtype indication has a resolution indication
+    pub(crate) resolved: bool,
 }
 
 impl<'a> Subtype<'a> {
     pub fn new(type_mark: TypeEnt<'a>) -> Subtype<'a> {
-        Subtype { type_mark }
+        Subtype {
+            type_mark,
+            resolved: false,
+        }
+    }
+
+    pub fn with_resolution(self, resolved: bool) -> Subtype<'a> {
+        Subtype { resolved, ..self }
+    }
+
+    /// Returns true when all scalar subelements of the subtype are resolved,
+    /// either by the subtype indication itself or by its type mark
+    pub fn is_resolved(&self) -> bool {
+        self.resolved || self.type_mark.is_resolved()
     }
 
     pub fn type_mark(&self) -> TypeEnt<'a> {
@@ -200,6 +200,13 @@ impl<'a> Visibility<'a> {
             }
         }
     }
+
+    /// Returns `true` when the named entity is visible by its designator
+    pub fn is_visible(&self, ent: EntRef<'a>) -> bool {
+        let mut visible = Visible::default();
+        self.lookup_into(ent.designator(), &mut visible);
+        visible.visible_entities.contains_key(&ent.as_actual().id())
+    }
 }
 
 #[derive(Default, Debug)]
@@ -7,15 +7,32 @@
 use crate::analysis::DesignRoot;
 use crate::ast::search::Searcher;
 use crate::ast::DesignFile;
-use crate::completion::{list_completion_options, CompletionItem};
+use crate::code_action::{
+    list_code_actions, normalize_case, synchronize_instantiations, CodeAction,
+    InstanceSynchronization, TextEdit, WorkspaceEdit,
+};
+use crate::completion::{list_completion_options, signature_help, CompletionItem, SignatureHelp};
 use crate::config::Config;
-use crate::lint::dead_code::UnusedDeclarationsLinter;
+use crate::elaboration::{
+    elaboration_of_ghdl_output, Elaboration, GhdlElaboration, GhdlOutput, LibraryFiles,
+};
+use crate::export::{
+    export_ip_xact_component, export_port_list, generate_testbench, generate_testbench_at,
+    IpXactComponent, PortListFormat, Testbench,
+};
+use crate::folding::{list_folding_ranges, FoldingRange};
+use crate::formatting::{format_on_type, format_range, format_source, FormatOptions};
+use crate::inlay_hints::{list_inlay_hints, InlayHint};
+use crate::lint::Linter;
 use crate::named_entity::EntRef;
+use crate::rename::{check_rename, RenameError};
 use crate::standard::VHDLStandard;
-use crate::syntax::VHDLParser;
-use crate::{data::*, EntHierarchy, EntityId};
+use crate::syntax::{is_verilog_file, Suppressions, VHDLParser};
+use crate::vunit::{vunit_test_cases, VUnitTestCase};
+use crate::{data::*, EntHierarchy, EntityId, HoverInfo, ReferenceKind};
 use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
+use std::io;
 use std::path::Path;
 use vhdl_lang::Token;
 
@@ -25,14 +42,14 @@ pub struct Project {
     root: DesignRoot,
     files: FnvHashMap<FilePath, SourceFile>,
     empty_libraries: FnvHashSet<Symbol>,
-    lint: Option<UnusedDeclarationsLinter>,
+    lint: Option<Linter>,
 }
 
 impl Project {
     pub fn new(vhdl_standard: VHDLStandard) -> Project {
         let parser = VHDLParser::new(vhdl_standard);
         Project {
-            root: DesignRoot::new(parser.symbols.clone()),
+            root: DesignRoot::new(parser.symbols.clone(), vhdl_standard),
             files: FnvHashMap::default(),
             empty_libraries: FnvHashSet::default(),
             parser,
@@ -41,14 +58,16 @@ impl Project {
         }
     }
 
+    /// Enables the lints, such as the detection of unused declarations
     pub fn enable_unused_declaration_detection(&mut self) {
-        self.lint = Some(UnusedDeclarationsLinter::default());
+        self.lint = Some(Linter::default());
     }
 
     /// Create instance from given configuration.
     /// Files referred by configuration are parsed into corresponding libraries.
     pub fn from_config(config: Config, messages: &mut dyn MessageHandler) -> Project {
         let mut project = Project::new(config.standard());
+        project.parser.conditional_analysis = config.conditional_analysis();
         let files = project.load_files_from_config(&config, messages);
         project.parse_and_add_files(files, messages);
         project.config = config;
@@ -60,7 +79,8 @@ impl Project {
     /// kept and parsed from in-memory source (required for incremental document updates).
     pub fn update_config(&mut self, config: Config, messages: &mut dyn MessageHandler) {
         self.parser = VHDLParser::new(config.standard());
-        self.root = DesignRoot::new(self.parser.symbols.clone());
+        self.parser.conditional_analysis = config.conditional_analysis();
+        self.root = DesignRoot::new(self.parser.symbols.clone(), config.standard());
 
         // Reset library associations for known files,
         // all project files are added to the corresponding libraries later on.
@@ -69,13 +89,23 @@ impl Project {
             .for_each(|source_file| source_file.library_names.clear());
 
         // Files might already be part of self.files, these have to be parsed
-        // from in-memory source. New files can be parsed as usual.
-        let (known_files, new_files) = self
+        // from in-memory source. New files and files whose encoding changed
+        // are read from disk.
+        let (known_files, new_files): (FnvHashMap<_, _>, FnvHashMap<_, _>) = self
             .load_files_from_config(&config, messages)
             .into_iter()
-            .partition(|(file_name, _library_names)| self.files.contains_key(file_name));
+            .partition(|(file_name, (_, encoding))| {
+                self.files.get(file_name).is_some_and(|source_file| {
+                    source_file
+                        .encoding
+                        .is_none_or(|old_encoding| old_encoding == *encoding)
+                })
+            });
+        for file_name in new_files.keys() {
+            self.files.remove(file_name);
+        }
 
-        for (file_name, library_names) in known_files {
+        for (file_name, (library_names, _encoding)) in known_files {
             if let Some(source_file) = self.files.get_mut(&file_name) {
                 source_file.parser_diagnostics.clear();
                 source_file.library_names = library_names;
@@ -93,8 +123,8 @@ impl Project {
         &mut self,
         config: &Config,
         messages: &mut dyn MessageHandler,
-    ) -> FnvHashMap<FilePath, FnvHashSet<Symbol>> {
-        let mut files: FnvHashMap<FilePath, FnvHashSet<Symbol>> = FnvHashMap::default();
+    ) -> FnvHashMap<FilePath, (FnvHashSet<Symbol>, Encoding)> {
+        let mut files: FnvHashMap<FilePath, (FnvHashSet<Symbol>, Encoding)> = FnvHashMap::default();
         self.empty_libraries.clear();
 
         for library in config.iter_libraries() {
@@ -103,17 +133,27 @@ impl Project {
             let library_name = self.parser.symbol(&library_name);
 
             let mut empty_library = true;
-            for file_name in library.file_names(messages) {
+            for (file_name, encoding) in
+                library.file_names_and_encodings(config.standard(), messages)
+            {
                 empty_library = false;
 
                 match files.entry(FilePath::new(&file_name)) {
                     Entry::Occupied(mut entry) => {
-                        entry.get_mut().insert(library_name.clone());
+                        let (library_names, first_encoding) = entry.get_mut();
+                        library_names.insert(library_name.clone());
+                        if *first_encoding != encoding {
+                            messages.push(Message::warning(format!(
+                                "File {} is read as {first_encoding} although library {} lists it as {encoding}",
+                                file_name.display(),
+                                library.name()
+                            )));
+                        }
                     }
                     Entry::Vacant(entry) => {
                         let mut set = FnvHashSet::default();
                         set.insert(library_name.clone());
-                        entry.insert(set);
+                        entry.insert((set, encoding));
                     }
                 }
             }
@@ -127,7 +167,7 @@ impl Project {
 
     fn parse_and_add_files(
         &mut self,
-        files_to_parse: FnvHashMap<FilePath, FnvHashSet<Symbol>>,
+        files_to_parse: FnvHashMap<FilePath, (FnvHashSet<Symbol>, Encoding)>,
         messages: &mut dyn MessageHandler,
     ) {
         use rayon::prelude::*;
@@ -136,15 +176,19 @@ impl Project {
             .into_par_iter()
             .map_init(
                 || &self.parser,
-                |parser, (file_name, library_names)| {
+                |parser, (file_name, (library_names, encoding))| {
                     let mut diagnostics = Vec::new();
-                    let result = parser.parse_design_file(&file_name, &mut diagnostics);
-                    (file_name, library_names, diagnostics, result)
+                    let result = parser.parse_design_file_with_encoding(
+                        &file_name,
+                        encoding,
+                        &mut diagnostics,
+                    );
+                    (file_name, library_names, encoding, diagnostics, result)
                 },
             )
             .collect();
 
-        for (file_name, library_names, parser_diagnostics, result) in parsed.into_iter() {
+        for (file_name, library_names, encoding, parser_diagnostics, result) in parsed.into_iter() {
             let (source, design_file) = match result {
                 Ok(result) => result,
                 Err(err) => {
@@ -157,8 +201,10 @@ impl Project {
                 FilePath::new(source.file_name()),
                 SourceFile {
                     source,
+                    encoding: Some(encoding),
                     library_names,
                     parser_diagnostics,
+                    suppressions: suppressions_of(&design_file),
                     design_file,
                 },
             );
@@ -202,9 +248,11 @@ impl Project {
 
                 SourceFile {
                     source: source.clone(),
+                    encoding: None,
                     library_names,
                     parser_diagnostics: vec![],
                     design_file: DesignFile::default(),
+                    suppressions: Suppressions::default(),
                 }
             }
         };
@@ -212,6 +260,7 @@ impl Project {
         source_file.design_file = self
             .parser
             .parse_design_source(source, &mut source_file.parser_diagnostics);
+        source_file.suppressions = suppressions_of(&source_file.design_file);
         self.files
             .insert(source.file_path().to_owned(), source_file);
     }
@@ -229,7 +278,18 @@ impl Project {
                 self.root.add_design_file(library_name.clone(), design_file);
             }
 
-            diagnostics.extend(source_file.parser_diagnostics.iter().cloned());
+            // Third-party libraries, such as the bundled IEEE libraries,
+            // may use constructs of a newer standard than the configured one
+            let is_third_party = is_third_party(&self.config, &source_file.library_names);
+            diagnostics.extend(
+                source_file
+                    .parser_diagnostics
+                    .iter()
+                    .filter(|diagnostic| {
+                        !(is_third_party && diagnostic.code == ErrorCode::IncompatibleStandard)
+                    })
+                    .cloned(),
+            );
         }
 
         for library_name in self.empty_libraries.iter() {
@@ -238,10 +298,26 @@ impl Project {
 
         let analyzed_units = self.root.analyze(&mut diagnostics);
 
+        // Before VHDL-2008, the standard package lacks declarations that
+        // third-party libraries written for VHDL-2008 depend on
+        if self.parser.standard < VHDLStandard::VHDL2008 {
+            diagnostics.retain(|diagnostic| {
+                self.files
+                    .get(diagnostic.pos.source.file_path())
+                    .is_none_or(|file| !is_third_party(&self.config, &file.library_names))
+            });
+        }
+
         if let Some(ref mut lint) = self.lint {
             lint.lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
         }
 
+        // Remove the diagnostics that are suppressed by pragmas such as '-- vhdl_lang: off unused'
+        diagnostics.retain(|diagnostic| {
+            self.files
+                .get(diagnostic.pos.source.file_path())
+                .is_none_or(|file| !file.suppressions.is_suppressed(diagnostic))
+        });
         diagnostics
     }
 
@@ -253,8 +329,7 @@ impl Project {
     /// If the character value is greater than the line length it defaults back to the
     /// line length.
     pub fn find_definition(&self, source: &Source, cursor: Position) -> Option<EntRef<'_>> {
-        let ent = self.root.search_reference(source, cursor)?;
-        self.root.find_definition_of(ent)
+        self.root.find_definition_at(source, cursor)
     }
 
     pub fn find_declaration(&self, source: &Source, cursor: Position) -> Option<EntRef<'_>> {
@@ -301,16 +376,95 @@ impl Project {
         self.root.format_declaration(ent)
     }
 
+    /// Returns what is shown when hovering over the name at the cursor
+    pub fn hover(&self, source: &Source, cursor: Position) -> Option<HoverInfo<'_>> {
+        self.root.hover(source, cursor)
+    }
+
+    pub fn get_ent(&self, id: EntityId) -> EntRef<'_> {
+        self.root.get_ent(id)
+    }
+
     pub fn format_entity(&self, id: EntityId) -> Option<String> {
         let ent = self.root.get_ent(id);
         self.format_declaration(ent)
     }
 
+    /// Returns the comments directly above the declaration of a named entity
+    pub fn doc_comment(&self, id: EntityId) -> Option<String> {
+        self.root.get_ent(id).documentation().map(str::to_owned)
+    }
+
     /// Search for all references to the declaration at decl_pos
     pub fn find_all_references(&self, ent: EntRef<'_>) -> Vec<SrcPos> {
         self.root.find_all_references(ent)
     }
 
+    /// Find the references to a named entity that are not declarations
+    pub fn find_all_usages(&self, id: EntityId) -> Vec<SrcPos> {
+        self.root.find_all_usages(self.root.get_ent(id))
+    }
+
+    /// Find the instantiations of an entity or a component
+    pub fn find_instantiations(&self, id: EntityId) -> Vec<SrcPos> {
+        self.root.find_instantiations(self.root.get_ent(id))
+    }
+
+    pub fn find_all_declarations_in_source(&self, source: &Source) -> Vec<EntRef<'_>> {
+        self.root.find_all_declarations_in_source(source)
+    }
+
+    /// Find the type that a subtype or an alias of a type is declared from
+    pub fn find_supertypes(&self, id: EntityId) -> Vec<EntRef<'_>> {
+        self.root.find_supertypes(self.root.get_ent(id))
+    }
+
+    /// Find the subtypes and aliases that are declared from a type
+    pub fn find_subtypes(&self, id: EntityId) -> Vec<EntRef<'_>> {
+        self.root.find_subtypes(self.root.get_ent(id))
+    }
+
+    /// Find the calls of a subprogram, grouped by the construct that they are called from
+    pub fn find_incoming_calls(&self, id: EntityId) -> Vec<(EntRef<'_>, Vec<SrcPos>)> {
+        self.root.find_incoming_calls(self.root.get_ent(id))
+    }
+
+    /// Find the subprograms that are called from within a construct,
+    /// grouped by the called subprogram
+    pub fn find_outgoing_calls(&self, id: EntityId) -> Vec<(EntRef<'_>, Vec<SrcPos>)> {
+        self.root.find_outgoing_calls(self.root.get_ent(id))
+    }
+
+    /// Checks that a named entity can be renamed to the new name
+    pub fn check_rename(&self, ent: EntRef<'_>, new_name: &str) -> Result<(), RenameError> {
+        check_rename(&self.root, ent, new_name)
+    }
+
+    pub fn find_all_rename_references(&self, ent: EntRef<'_>) -> Vec<SrcPos> {
+        self.root.find_all_rename_references(ent)
+    }
+
+    pub fn find_all_references_in_source_with_kind(
+        &self,
+        source: &Source,
+        ent: EntRef<'_>,
+    ) -> Vec<(SrcPos, ReferenceKind)> {
+        self.root
+            .find_all_references_in_source_with_kind(source, ent)
+    }
+
+    pub fn find_all_identifiers_in_source(&self, source: &Source) -> Vec<(SrcPos, EntRef<'_>)> {
+        self.root.find_all_identifiers_in_source(source)
+    }
+
+    pub fn find_enclosing_spans(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        self.root.find_enclosing_spans(source, cursor)
+    }
+
+    pub fn find_linked_names(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        self.root.find_linked_names(source, cursor)
+    }
+
     pub fn find_all_references_in_source(&self, source: &Source, ent: EntRef<'_>) -> Vec<SrcPos> {
         self.root.find_all_references_in_source(source, ent)
     }
@@ -333,6 +487,187 @@ impl Project {
         list_completion_options(&self.root, source, cursor)
     }
 
+    /// Lists the regions of the source file that can be folded
+    pub fn list_folding_ranges(&self, source: &Source) -> Vec<FoldingRange> {
+        list_folding_ranges(&self.root, source)
+    }
+
+    /// Lists the inlay hints of the source file within a range
+    pub fn list_inlay_hints(&self, source: &Source, range: Range) -> Vec<InlayHint> {
+        list_inlay_hints(&self.root, source, range)
+    }
+
+    /// Lists the signatures of the call or instantiation at the cursor
+    pub fn signature_help(&self, source: &Source, cursor: Position) -> Option<SignatureHelp<'_>> {
+        signature_help(&self.root, source, cursor)
+    }
+
+    /// Lists all refactorings and quick fixes applicable to the selected range
+    pub fn list_code_actions(&self, source: &Source, range: Range) -> Vec<CodeAction> {
+        list_code_actions(&self.root, &self.config, source, range)
+    }
+
+    /// Adds missing ports to and reports stale associations of all instantiations
+    pub fn synchronize_instantiations(&self) -> InstanceSynchronization {
+        synchronize_instantiations(&self.root)
+    }
+
+    /// Rewrites keywords and identifiers of the project to the configured casing
+    pub fn normalize_case(&self) -> WorkspaceEdit {
+        normalize_case(&self.root, &self.config)
+    }
+
+    /// The formatting style of the `[format]` section of the configuration, if any
+    pub fn format_options(&self) -> Option<FormatOptions> {
+        self.config.format()
+    }
+
+    /// Formats a source file using the VHDL standard of the project.
+    /// Returns `None` when the source contains syntax errors.
+    pub fn format_source(&self, source: &Source, options: &FormatOptions) -> Option<String> {
+        let options = FormatOptions {
+            standard: self.config.standard(),
+            ..options.clone()
+        };
+        format_source(source, &options)
+    }
+
+    /// Formats the declarations and statements of a source file that intersect the range
+    pub fn format_range(
+        &self,
+        source: &Source,
+        range: Range,
+        options: &FormatOptions,
+    ) -> Option<TextEdit> {
+        let options = FormatOptions {
+            standard: self.config.standard(),
+            ..options.clone()
+        };
+        format_range(source, range, &options)
+    }
+
+    /// Re-indents the lines around the cursor after a character was typed
+    pub fn format_on_type(
+        &self,
+        source: &Source,
+        cursor: Position,
+        ch: &str,
+        options: &FormatOptions,
+    ) -> Vec<TextEdit> {
+        let options = FormatOptions {
+            standard: self.config.standard(),
+            ..options.clone()
+        };
+        format_on_type(source, cursor, ch, &options)
+    }
+
+    /// Describes the entity declared at the cursor as an IP-XACT component
+    pub fn export_ip_xact_component(
+        &self,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<IpXactComponent> {
+        export_ip_xact_component(&self.root, source, cursor)
+    }
+
+    /// Describes the generics and ports of an entity as JSON or YAML.
+    /// The entity is given as `library.entity` or as the name of an entity
+    /// that is looked up in all libraries.
+    pub fn export_port_list(&self, entity: &str, format: PortListFormat) -> Option<String> {
+        export_port_list(&self.root, entity, format)
+    }
+
+    /// Generates a testbench for the entity declared at the cursor
+    pub fn generate_testbench_at(&self, source: &Source, cursor: Position) -> Option<Testbench> {
+        generate_testbench_at(&self.root, source, cursor)
+    }
+
+    /// Generates a testbench for an entity given as `library.entity` or as the name of an entity
+    pub fn generate_testbench(&self, entity: &str) -> Option<Testbench> {
+        generate_testbench(&self.root, entity)
+    }
+
+    /// Analyzes the VHDL files of all libraries using GHDL and elaborates the `top` unit.
+    /// The top unit is given as `library.entity` or as the name of an entity
+    /// that is looked up in all libraries.
+    /// The compiled libraries are stored in `workdir`.
+    pub fn elaborate_with_ghdl(
+        &self,
+        ghdl: &Path,
+        top: &str,
+        workdir: &Path,
+    ) -> io::Result<Elaboration> {
+        let output = self.ghdl_elaboration(ghdl, top, workdir)?.run()?;
+        Ok(self.elaboration_of_ghdl_output(&output))
+    }
+
+    /// Returns the invocations of GHDL that elaborate the `top` unit like `elaborate_with_ghdl`,
+    /// such that they can be run without the project, e.g. on another thread.
+    pub fn ghdl_elaboration(
+        &self,
+        ghdl: &Path,
+        top: &str,
+        workdir: &Path,
+    ) -> io::Result<GhdlElaboration> {
+        let (top_library, top) = match top.split_once('.') {
+            Some((library, entity)) => (library.to_owned(), entity),
+            None => {
+                let symbol = self.root.symbol_utf8(top);
+                let library = self
+                    .root
+                    .libraries()
+                    .find(|library| library.primary_unit(&symbol).is_some())
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("No design unit '{top}' within any library"),
+                        )
+                    })?;
+                (library.name().name_utf8(), top)
+            }
+        };
+
+        let mut libraries: FnvHashMap<String, Vec<_>> = FnvHashMap::default();
+        for file in self.files.values() {
+            if is_verilog_file(file.source.file_name()) {
+                continue;
+            }
+            for library_name in file.library_names.iter() {
+                libraries
+                    .entry(library_name.name_utf8())
+                    .or_default()
+                    .push(file.source.file_name().to_owned());
+            }
+        }
+        let mut libraries: Vec<LibraryFiles> = libraries
+            .into_iter()
+            .map(|(name, mut files)| {
+                files.sort();
+                LibraryFiles { name, files }
+            })
+            .collect();
+        libraries.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+
+        Ok(GhdlElaboration::new(
+            ghdl,
+            self.config.standard(),
+            &libraries,
+            &top_library,
+            top,
+            workdir,
+        ))
+    }
+
+    /// Maps the output of GHDL to diagnostics within the sources of the project
+    pub fn elaboration_of_ghdl_output(&self, output: &GhdlOutput) -> Elaboration {
+        elaboration_of_ghdl_output(output, &|file_name| self.get_source(file_name))
+    }
+
+    /// Lists the test cases of all VUnit testbenches of the project
+    pub fn vunit_test_cases(&self) -> Vec<VUnitTestCase> {
+        vunit_test_cases(&self.root)
+    }
+
     pub fn entity_id_from_raw(&self, raw: usize) -> Option<EntityId> {
         self.root.entity_id_from_raw(raw)
     }
@@ -355,11 +690,35 @@ fn multiply<T: Clone>(value: T, n: usize) -> Vec<T> {
     }
 }
 
+/// The diagnostics that are suppressed by the pragmas of a design file
+fn suppressions_of(design_file: &DesignFile) -> Suppressions {
+    Suppressions::from_tokens(
+        design_file
+            .design_units
+            .iter()
+            .flat_map(|(tokens, _)| tokens.iter()),
+    )
+}
+
+/// Returns true when all libraries of a file are third-party libraries
+fn is_third_party(config: &Config, library_names: &FnvHashSet<Symbol>) -> bool {
+    !library_names.is_empty()
+        && library_names.iter().all(|library_name| {
+            config
+                .get_library(&library_name.name_utf8())
+                .is_some_and(|library| library.is_third_party())
+        })
+}
+
 pub struct SourceFile {
     library_names: FnvHashSet<Symbol>,
     source: Source,
+    /// The encoding the file was read from disk with,
+    /// `None` when the source only comes from the editor
+    encoding: Option<Encoding>,
     design_file: DesignFile,
     parser_diagnostics: Vec<Diagnostic>,
+    suppressions: Suppressions,
 }
 
 impl SourceFile {
@@ -443,6 +802,219 @@ end architecture;
         assert_eq!(diag.message, "Duplicate architecture 'rtl' of entity 'ent'")
     }
 
+    #[test]
+    fn diagnostics_are_suppressed_by_pragmas() {
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            &vhdl_file_path,
+            "
+entity ent is
+end ent;
+
+architecture rtl of ent is
+  -- vhdl_lang: off duplicate
+  component s is end component;
+  component s is end component;
+  -- vhdl_lang: on duplicate
+  component x is end component;
+  component x is end component; -- vhdl_lang: ignore duplicate
+  component y is end component;
+  component y is end component;
+begin
+end architecture;
+",
+        )
+        .unwrap();
+        let source = Source::from_latin1_file(&vhdl_file_path).unwrap();
+
+        project.update_source(&source);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        let diag = diagnostics.first().unwrap();
+        assert_eq!(diag.message, "Duplicate declaration of 'y'")
+    }
+
+    #[test]
+    fn analysis_diagnostics_of_third_party_libraries_are_ignored_before_vhdl2008() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["third_party.vhd", "file.vhd"] {
+            std::fs::write(
+                root.path().join(name),
+                "
+package pkg is
+  constant c : string := to_string(0);
+end package;
+",
+            )
+            .unwrap();
+        }
+
+        let config_str = "
+standard = '1993'
+[libraries]
+third_party.files = ['third_party.vhd']
+third_party.is_third_party = true
+lib.files = ['file.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let diagnostics = project.analyse();
+        assert!(!diagnostics.is_empty());
+        for diagnostic in diagnostics {
+            assert_eq!(
+                diagnostic.pos.source.file_path(),
+                &FilePath::new(&root.path().join("file.vhd"))
+            );
+        }
+    }
+
+    #[test]
+    fn lints_are_not_run_for_third_party_libraries_or_turned_off_codes() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["third_party.vhd", "file.vhd"] {
+            std::fs::write(
+                root.path().join(name),
+                "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal unused : bit;
+begin
+  main: process
+  begin
+  end process;
+end architecture;
+",
+            )
+            .unwrap();
+        }
+
+        let config_str = format!(
+            "
+[libraries]
+std.files = ['{}/../vhdl_libraries/std/*.vhd']
+std.is_third_party = true
+third_party.files = ['third_party.vhd']
+third_party.is_third_party = true
+lib.files = ['file.vhd']
+
+[lint]
+unused = 'off'
+        ",
+            env!("CARGO_MANIFEST_DIR")
+        );
+
+        let config = Config::from_str(&config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        project.enable_unused_declaration_detection();
+        assert_eq!(messages, vec![]);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].code, ErrorCode::MissingWait);
+        assert_eq!(
+            diagnostics[0].pos.source.file_path(),
+            &FilePath::new(&root.path().join("file.vhd"))
+        );
+    }
+
+    #[test]
+    fn files_are_decoded_using_the_configured_encoding() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("utf8.vhd"),
+            "-- Zähler für\nentity ent1 is\nend entity;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.path().join("cp1252.vhd"),
+            b"-- \x93Z\xe4hler\x94\nentity ent2 is\nend entity;\n",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['utf8.vhd', { path = 'cp1252.vhd', encoding = 'windows-1252' }]
+lib.encoding = 'utf-8'
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+
+        let utf8 = project.get_source(&root.path().join("utf8.vhd")).unwrap();
+        assert_eq!(utf8.contents().get_line(0), Some("-- Zähler für\n"));
+        let cp1252 = project.get_source(&root.path().join("cp1252.vhd")).unwrap();
+        assert_eq!(
+            cp1252.contents().get_line(0),
+            Some("-- \u{201C}Zähler\u{201D}\n")
+        );
+    }
+
+    #[test]
+    fn update_config_rereads_files_whose_encoding_changed() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("file.vhd"),
+            b"-- Z\xe4hler\nentity ent is\nend entity;\n",
+        )
+        .unwrap();
+
+        let config =
+            Config::from_str("[libraries]\nlib.files = ['file.vhd']", root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        let source = project.get_source(&root.path().join("file.vhd")).unwrap();
+        assert_eq!(source.contents().get_line(0), Some("-- Zähler\n"));
+
+        let config = Config::from_str(
+            "[libraries]\nlib.files = [{ path = 'file.vhd', encoding = 'utf-8' }]",
+            root.path(),
+        )
+        .unwrap();
+        project.update_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let source = project.get_source(&root.path().join("file.vhd")).unwrap();
+        assert_eq!(source.contents().get_line(0), Some("-- Z\u{FFFD}hler\n"));
+        assert_eq!(
+            project.library_mapping_of(&source),
+            vec![project.parser.symbol(&Latin1String::new(b"lib"))]
+        );
+    }
+
+    #[test]
+    fn conflicting_encodings_of_a_file_are_reported() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("file.vhd"), "entity ent is\nend entity;\n").unwrap();
+
+        let config_str = "
+[libraries]
+lib1.files = ['file.vhd']
+lib2.files = [{ path = 'file.vhd', encoding = 'utf-8' }]
+        ";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        Project::from_config(config, &mut messages);
+        assert_eq!(
+            messages,
+            vec![Message::warning(format!(
+                "File {} is read as latin-1 although library lib2 lists it as utf-8",
+                root.path().join("file.vhd").display()
+            ))]
+        );
+    }
+
     /// Test that the same file can be added to several libraries
     #[test]
     fn test_same_file_in_multiple_libraries() {
@@ -0,0 +1,164 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Checks whether a named entity can be renamed without changing the meaning of the code.
+
+use crate::analysis::DesignRoot;
+use crate::ast::search::{is_reference, FindAllEnt};
+use crate::ast::Designator;
+use crate::data::ContentReader;
+use crate::named_entity::{EntityId, OverloadedEnt};
+use crate::syntax::{Kind, Token, Tokenizer, Value};
+use crate::{AnyEntKind, Design, EntRef, Source, SrcPos};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RenameError {
+    /// The new name is not an identifier, e.g., a reserved word
+    InvalidName(String),
+    /// A declaration with the new name already exists in the same declarative region
+    Clash { name: String, pos: Option<SrcPos> },
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::InvalidName(name) => write!(f, "'{name}' is not a valid identifier"),
+            RenameError::Clash {
+                name,
+                pos: Some(pos),
+            } => write!(
+                f,
+                "'{name}' is already declared at {}:{}",
+                pos.file_name().to_string_lossy(),
+                pos.range().start.line + 1
+            ),
+            RenameError::Clash { name, pos: None } => write!(f, "'{name}' is already declared"),
+        }
+    }
+}
+
+/// The region that a named entity is declared in.
+/// Architectures and bodies extend the region of their entity or declaration.
+fn declarative_region(ent: EntRef<'_>) -> Option<EntityId> {
+    let parent = ent.parent?;
+    Some(match parent.kind() {
+        AnyEntKind::Design(Design::Architecture(.., entity)) => entity.id(),
+        _ => parent.declaration().id(),
+    })
+}
+
+/// Subprograms with different parameter and result types may share a name
+fn is_homograph(ent: EntRef<'_>, other: EntRef<'_>) -> bool {
+    match (OverloadedEnt::from_any(ent), OverloadedEnt::from_any(other)) {
+        (Some(ent), Some(other)) => ent.signature().key() == other.signature().key(),
+        _ => true,
+    }
+}
+
+/// Checks that the new name is an identifier and that no other declaration
+/// of the declarative region of the named entity already has that name
+pub fn check_rename(root: &DesignRoot, ent: EntRef<'_>, new_name: &str) -> Result<(), RenameError> {
+    let source = Source::inline(Path::new("rename"), new_name);
+    let contents = source.contents();
+    let mut tokenizer = Tokenizer::new(root.symbols(), &source, ContentReader::new(&contents));
+    // The new name must be a single identifier, which excludes reserved words
+    let symbol = match (tokenizer.pop(), tokenizer.pop()) {
+        (
+            Ok(Some(Token {
+                kind: Kind::Identifier,
+                value: Value::Identifier(symbol),
+                ..
+            })),
+            Ok(None),
+        ) => symbol,
+        _ => return Err(RenameError::InvalidName(new_name.to_owned())),
+    };
+
+    let designator = Designator::Identifier(symbol);
+    if ent.designator() == &designator {
+        return Ok(());
+    }
+    let region = declarative_region(ent);
+    let mut searcher = FindAllEnt::new(root, |other| {
+        other.designator() == &designator
+            && declarative_region(other) == region
+            && !is_reference(ent, other)
+            && is_homograph(ent, other)
+    });
+    let _ = root.search(&mut searcher);
+    match searcher.result.first() {
+        Some(other) => Err(RenameError::Clash {
+            name: new_name.to_owned(),
+            pos: other.decl_pos().cloned(),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_no_diagnostics, LibraryBuilder};
+
+    #[test]
+    fn refuses_invalid_names_and_clashes() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+package pkg is
+  constant c0 : natural := 0;
+  function f(x : natural) return natural;
+  function g(x : bit) return natural;
+end package;
+
+package body pkg is
+  constant c1 : natural := 1;
+  function f(x : natural) return natural is
+  begin
+    return x;
+  end function;
+  function g(x : bit) return natural is
+  begin
+    return 0;
+  end function;
+end package body;",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+
+        let c0 = root
+            .search_reference(code.source(), code.s1("c0").start())
+            .unwrap();
+        let f = root
+            .search_reference(code.source(), code.s1("function f").s1(" f").end())
+            .unwrap();
+
+        assert_eq!(check_rename(&root, c0, "c2"), Ok(()));
+        assert_eq!(check_rename(&root, c0, "C0"), Ok(()));
+        assert_eq!(
+            check_rename(&root, c0, "signal"),
+            Err(RenameError::InvalidName("signal".to_owned()))
+        );
+        assert_eq!(
+            check_rename(&root, c0, "c 2"),
+            Err(RenameError::InvalidName("c 2".to_owned()))
+        );
+        // The package body extends the region of the package
+        assert_eq!(
+            check_rename(&root, c0, "c1"),
+            Err(RenameError::Clash {
+                name: "c1".to_owned(),
+                pos: Some(code.s1("c1").pos()),
+            })
+        );
+        assert!(check_rename(&root, c0, "f").is_err());
+        // Subprograms with different signatures are overloaded
+        assert_eq!(check_rename(&root, f, "g"), Ok(()));
+    }
+}
@@ -27,6 +27,7 @@ mod sequential_statement;
 mod subprogram;
 mod subtype_indication;
 mod type_declaration;
+mod verilog;
 mod waveform;
 
 mod recover;
@@ -36,3 +37,4 @@ mod view;
 
 pub use parser::{ParserResult, VHDLParser};
 pub use tokens::*;
+pub(crate) use verilog::{is_verilog_file, parse_verilog_source};
@@ -14,7 +14,7 @@ use super::names::parse_name;
 use super::names::{expression_to_ident, parse_association_list, parse_selected_name};
 use super::range::parse_discrete_range;
 use super::sequential_statement::{
-    parse_assert_statement, parse_labeled_sequential_statements, parse_selection,
+    parse_assert_statement, parse_labeled_sequential_statements, parse_select, parse_selection,
     parse_signal_assignment_right_hand, parse_target,
 };
 use super::tokens::Kind::*;
@@ -22,6 +22,7 @@ use super::waveform::{parse_delay_mechanism, parse_waveform};
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard::VHDL2008;
 use crate::syntax::recover::{expect_semicolon, expect_semicolon_or_last};
 use crate::syntax::{Kind, TokenAccess};
 use crate::TokenId;
@@ -186,11 +187,6 @@ pub fn parse_process_statement(
     let process_token = ctx.stream.expect_kind(Process)?;
     let sensitivity_list = if let Some(left_par) = ctx.stream.pop_if_kind(LeftPar) {
         peek_token!(ctx.stream, token,
-        All => {
-            ctx.stream.skip();
-            let right_par = ctx.stream.expect_kind(RightPar)?;
-            Some(WithTokenSpan::new(SensitivityList::All, TokenSpan::new(left_par, right_par)))
-        },
         RightPar => {
             let right_par = ctx.stream.get_current_token_id();
             ctx.stream.skip();
@@ -199,15 +195,32 @@ pub fn parse_process_statement(
             );
             Some(WithTokenSpan::new(SensitivityList::Names(Vec::new()), TokenSpan::new(left_par, right_par)))
         },
-        Identifier => {
+        All | Identifier => {
             let mut names = Vec::with_capacity(1);
+            let mut all = None;
             loop {
-                names.push(parse_name(ctx)?);
+                if let Some(all_token) = ctx.stream.pop_if_kind(All) {
+                    ctx.require_standard(VHDL2008, ctx.stream.get_pos(all_token).clone(), "The sensitivity list 'all'");
+                    all.get_or_insert(all_token);
+                } else {
+                    names.push(parse_name(ctx)?);
+                }
                 peek_token!(ctx.stream, token,
                     RightPar => {
                         let right_par = ctx.stream.get_current_token_id();
                         ctx.stream.skip();
-                        break Some(WithTokenSpan::new(SensitivityList::Names(names), TokenSpan::new(left_par, right_par)));
+                        let span = TokenSpan::new(left_par, right_par);
+                        let Some(all) = all else {
+                            break Some(WithTokenSpan::new(SensitivityList::Names(names), span));
+                        };
+                        // The names are dropped as 'all' already contains every signal that is read
+                        if !names.is_empty() {
+                            ctx.diagnostics.push(Diagnostic::syntax_error(
+                                ctx.stream.get_pos(all),
+                                "'all' cannot be combined with other names in a sensitivity list",
+                            ));
+                        }
+                        break Some(WithTokenSpan::new(SensitivityList::All, span));
                     },
                     Comma => {
                         ctx.stream.skip();
@@ -289,7 +302,7 @@ fn parse_assignment_known_target(
     // @TODO guarded
     let guarded = false;
     let delay_mechanism = parse_delay_mechanism(ctx)?;
-    let rhs = parse_signal_assignment_right_hand(ctx)?;
+    let rhs = parse_signal_assignment_right_hand(ctx, false)?;
     Ok(ConcurrentStatement::Assignment(
         ConcurrentSignalAssignment {
             postponed,
@@ -322,13 +335,18 @@ fn parse_selected_signal_assignment(
 ) -> ParseResult<ConcurrentSignalAssignment> {
     ctx.stream.expect_kind(With)?;
     let expression = parse_expression(ctx)?;
-    ctx.stream.expect_kind(Select)?;
+    let is_matching = parse_select(ctx)?;
     let target = parse_target(ctx)?;
     ctx.stream.expect_kind(LTE)?;
     // @TODO guarded
     let guarded = false;
     let delay_mechanism = parse_delay_mechanism(ctx)?;
-    let rhs = AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_waveform)?);
+    let rhs = AssignmentRightHand::Selected(parse_selection(
+        ctx,
+        is_matching,
+        expression,
+        parse_waveform,
+    )?);
     Ok(ConcurrentSignalAssignment {
         postponed,
         guarded,
@@ -590,6 +608,7 @@ fn parse_case_generate_statement(
 
     Ok(CaseGenerateStatement {
         sels: Selection {
+            is_matching: false,
             expression,
             alternatives,
         },
@@ -1233,6 +1252,34 @@ end process;",
         );
     }
 
+    #[test]
+    fn test_process_statement_mixing_all_and_names() {
+        let code = Code::new(
+            "\
+process (clk, all) is
+begin
+end process;",
+        );
+        let (stmt, diagnostics) = code.with_stream_diagnostics(parse_labeled_concurrent_statement);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::syntax_error(
+                code.s1("all"),
+                "'all' cannot be combined with other names in a sensitivity list"
+            )]
+        );
+        let ConcurrentStatement::Process(process) = stmt.statement.item else {
+            panic!("Expected a process");
+        };
+        assert_eq!(
+            process.sensitivity_list,
+            Some(WithTokenSpan::new(
+                SensitivityList::All,
+                code.s1("(clk, all)").token_span()
+            ))
+        );
+    }
+
     #[test]
     fn test_process_statement_full() {
         let code = Code::new(
@@ -1359,6 +1406,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![Alternative {
                 choices: code.s1("0|1").choices(),
@@ -2125,6 +2173,7 @@ end generate;",
         );
         let gen = CaseGenerateStatement {
             sels: Selection {
+                is_matching: false,
                 expression: code.s1("expr(0) + 2").expr(),
                 alternatives: vec![
                     Alternative {
@@ -2179,6 +2228,7 @@ end generate gen1;",
         );
         let gen = CaseGenerateStatement {
             sels: Selection {
+                is_matching: false,
                 expression: code.s1("expr(0) + 2").expr(),
                 alternatives: vec![
                     Alternative {
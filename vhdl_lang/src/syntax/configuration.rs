@@ -35,7 +35,7 @@ pub(crate) fn parse_entity_aspect(ctx: &mut ParsingContext<'_>) -> ParseResult<E
                     None
                 }
             };
-            EntityAspect::Entity(entity_name, arch_name)
+            EntityAspect::Entity(entity_name, arch_name.map(WithRef::new))
         }
     );
     Ok(entity_aspect)
@@ -977,7 +977,7 @@ end configuration cfg;
             code.with_stream(parse_entity_aspect),
             EntityAspect::Entity(
                 code.s1("lib.foo.name").name(),
-                Some(code.s1("arch").ident())
+                Some(WithRef::new(code.s1("arch").ident()))
             )
         );
     }
@@ -1014,7 +1014,7 @@ end configuration cfg;
                 bind_ind: BindingIndication {
                     entity_aspect: Some(EntityAspect::Entity(
                         code.s1("work.foo").name(),
-                        Some(code.s1("rtl").ident())
+                        Some(WithRef::new(code.s1("rtl").ident()))
                     )),
                     generic_map: None,
                     port_map: None,
@@ -1043,7 +1043,7 @@ end configuration cfg;
                 bind_ind: BindingIndication {
                     entity_aspect: Some(EntityAspect::Entity(
                         code.s1("work.foo").name(),
-                        Some(code.s1("rtl").ident())
+                        Some(WithRef::new(code.s1("rtl").ident()))
                     )),
                     generic_map: None,
                     port_map: None,
@@ -1074,7 +1074,7 @@ end configuration cfg;
                 bind_ind: BindingIndication {
                     entity_aspect: Some(EntityAspect::Entity(
                         code.s1("work.foo").name(),
-                        Some(code.s1("rtl").ident())
+                        Some(WithRef::new(code.s1("rtl").ident()))
                     )),
                     generic_map: None,
                     port_map: None,
@@ -11,6 +11,7 @@ use super::tokens::{Kind, Kind::*};
 use crate::ast::token_range::{WithToken, WithTokenSpan};
 use crate::ast::{Literal, *};
 use crate::data::Diagnostic;
+use crate::standard::VHDLStandard::{VHDL2008, VHDL2019};
 use crate::syntax::TokenAccess;
 use crate::{ast, HasTokenSpan, TokenId, TokenSpan};
 use vhdl_lang::syntax::parser::ParsingContext;
@@ -131,6 +132,19 @@ fn kind_to_operator(kind: Kind) -> Option<Operator> {
     })
 }
 
+/// The matching relational operators of VHDL-2008
+fn is_matching_operator(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::QueEQ
+            | Operator::QueNE
+            | Operator::QueLT
+            | Operator::QueLTE
+            | Operator::QueGT
+            | Operator::QueGTE
+    )
+}
+
 fn kind_to_prefix_unary_op(kind: Kind) -> Option<(Operator, usize)> {
     let op = kind_to_operator(kind)?;
     let prec = op.unary_precedence()?;
@@ -481,6 +495,16 @@ fn parse_primary(ctx: &mut ParsingContext<'_>) -> ParseResult<WithTokenSpan<Expr
         kind => {
             // Prefix unary operation
             if let Some((unary_op, op_precedence)) = kind_to_prefix_unary_op(kind) {
+                if !matches!(
+                    unary_op,
+                    Operator::Plus | Operator::Minus | Operator::Abs | Operator::Not
+                ) {
+                    ctx.require_standard(
+                        VHDL2008,
+                        ctx.stream.get_pos(token_id).clone(),
+                        &format!("The unary operator '{unary_op}'"),
+                    );
+                }
                 ctx.stream.skip();
 
                 let expr = parse_expr(ctx, op_precedence)?;
@@ -517,6 +541,13 @@ fn parse_expr(
         if let Some((binary_op, op_precedence)) = kind_to_binary_op(token.kind) {
             // Binary operation
             if op_precedence > min_precedence {
+                if is_matching_operator(binary_op) {
+                    ctx.require_standard(
+                        VHDL2008,
+                        ctx.stream.get_pos(token_id).clone(),
+                        &format!("The matching operator '{binary_op}'"),
+                    );
+                }
                 ctx.stream.skip();
                 let rhs = parse_expr(ctx, op_precedence)?;
                 let pos = lhs.span.combine(rhs.span);
@@ -560,6 +591,44 @@ pub fn parse_expression(ctx: &mut ParsingContext<'_>) -> ParseResult<WithTokenSp
     })
 }
 
+/// Parses an expression that may be a VHDL-2019 conditional expression,
+/// i.e., the value `a when cond else b` of an object declaration or return statement
+pub fn parse_conditional_expression(
+    ctx: &mut ParsingContext<'_>,
+) -> ParseResult<WithTokenSpan<Expression>> {
+    let mut item = parse_expression(ctx)?;
+    if ctx.stream.peek_kind() != Some(When) {
+        return Ok(item);
+    }
+    ctx.require_standard(
+        VHDL2019,
+        ctx.stream
+            .get_pos(ctx.stream.get_current_token_id())
+            .clone(),
+        "A conditional expression",
+    );
+    let start_token = item.span.start_token;
+    let mut conditionals = Vec::new();
+    loop {
+        ctx.stream.expect_kind(When)?;
+        let condition = parse_expression(ctx)?;
+        let else_token = ctx.stream.expect_kind(Else)?;
+        conditionals.push(Conditional { condition, item });
+        item = parse_expression(ctx)?;
+        if ctx.stream.peek_kind() != Some(When) {
+            let span = TokenSpan::new(start_token, item.span.end_token);
+            let conditionals = Conditionals {
+                conditionals,
+                else_item: Some((item, else_token)),
+            };
+            return Ok(WithTokenSpan::new(
+                Expression::Conditional(Box::new(conditionals)),
+                span,
+            ));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
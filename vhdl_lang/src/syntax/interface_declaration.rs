@@ -18,7 +18,7 @@ use crate::ast::*;
 use crate::data::*;
 use itertools::Itertools;
 use vhdl_lang::syntax::parser::ParsingContext;
-use vhdl_lang::VHDLStandard::VHDL2019;
+use vhdl_lang::VHDLStandard::{VHDL2008, VHDL2019};
 
 pub(crate) fn parse_optional_mode(
     ctx: &mut ParsingContext<'_>,
@@ -295,10 +295,12 @@ fn parse_interface_declaration(
         File => parse_interface_file_declaration(ctx),
         Type => {
             ctx.stream.skip();
+            ctx.require_standard(VHDL2008, ctx.stream.get_pos(start_token).clone(), "A generic type");
             let ident = ctx.stream.expect_ident()?;
             Ok(InterfaceDeclaration::Type(WithDecl::new(ident)))
         },
         Function | Procedure | Impure | Pure => {
+            ctx.require_standard(VHDL2008, ctx.stream.get_pos(start_token).clone(), "A generic subprogram");
             let spec = parse_subprogram_specification(ctx)?;
             let default = parse_subprogram_default(ctx)?;
 
@@ -307,6 +309,7 @@ fn parse_interface_declaration(
             Ok(InterfaceDeclaration::Subprogram(InterfaceSubprogramDeclaration { specification: spec, default, span: TokenSpan::new(start_token, end_token)}))
         },
         Package => {
+            ctx.require_standard(VHDL2008, ctx.stream.get_pos(start_token).clone(), "A generic package");
             Ok(InterfaceDeclaration::Package (parse_interface_package(ctx)?))
         }
     )
@@ -15,6 +15,7 @@ use crate::ast::token_range::{WithToken, WithTokenSpan};
 use crate::ast::{Literal, *};
 use crate::data::error_codes::ErrorCode;
 use crate::data::Diagnostic;
+use crate::standard::VHDLStandard::VHDL2008;
 use crate::syntax::separated_list::parse_list_with_separator_or_recover;
 use crate::syntax::TokenId;
 use vhdl_lang::syntax::parser::ParsingContext;
@@ -390,6 +391,11 @@ fn parse_inner_external_name(ctx: &mut ParsingContext<'_>) -> ParseResult<Extern
 fn _parse_name(ctx: &mut ParsingContext<'_>) -> ParseResult<WithTokenSpan<Name>> {
     let mut name = {
         if let Some(token) = ctx.stream.pop_if_kind(LtLt) {
+            ctx.require_standard(
+                VHDL2008,
+                ctx.stream.get_pos(token).clone(),
+                "An external name",
+            );
             let external_name = Name::External(Box::new(parse_inner_external_name(ctx)?));
             let end_token = ctx.stream.expect_kind(GtGt)?;
             WithTokenSpan::from(external_name, TokenSpan::new(token, end_token))
@@ -6,7 +6,7 @@ use itertools::Itertools;
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 /// LRM 6.4.2 Object Declarations
 use super::common::ParseResult;
-use super::expression::parse_expression;
+use super::expression::{parse_conditional_expression, parse_expression};
 use super::names::parse_identifier_list;
 use super::subtype_indication::parse_subtype_indication;
 use super::tokens::{Kind::*, TokenSpan};
@@ -20,7 +20,7 @@ pub fn parse_optional_assignment(
     ctx: &mut ParsingContext<'_>,
 ) -> ParseResult<Option<WithTokenSpan<Expression>>> {
     if ctx.stream.pop_if_kind(ColonEq).is_some() {
-        let expr = parse_expression(ctx)?;
+        let expr = parse_conditional_expression(ctx)?;
         Ok(Some(expr))
     } else {
         Ok(None)
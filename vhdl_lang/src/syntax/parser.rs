@@ -4,11 +4,12 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::tokens::{Symbols, TokenStream, Tokenizer};
+use super::tokens::{ConditionalAnalysis, Symbols, TokenStream, Tokenizer};
 use crate::ast::DesignFile;
 use crate::data::*;
 use crate::standard::VHDLStandard;
 use crate::syntax::design_unit::parse_design_file;
+use crate::syntax::{is_verilog_file, parse_verilog_source};
 use crate::{Token, TokenId};
 use std::io;
 use std::sync::Arc;
@@ -17,6 +18,8 @@ use vhdl_lang::TokenAccess;
 pub struct VHDLParser {
     pub symbols: Arc<Symbols>,
     pub standard: VHDLStandard,
+    /// The values of the identifiers of conditional analysis directives
+    pub conditional_analysis: ConditionalAnalysis,
 }
 
 pub(crate) struct ParsingContext<'a> {
@@ -25,6 +28,29 @@ pub(crate) struct ParsingContext<'a> {
     pub standard: VHDLStandard,
 }
 
+impl ParsingContext<'_> {
+    /// Reports a construct that requires a newer standard than the configured one.
+    /// The construct is described in the singular, such as "An external name".
+    pub fn require_standard(
+        &mut self,
+        standard: VHDLStandard,
+        pos: impl AsRef<SrcPos>,
+        construct: &str,
+    ) {
+        if self.standard < standard {
+            self.diagnostics.add(
+                pos,
+                format!(
+                    "{construct} requires VHDL-{} or newer, but the configured standard is VHDL-{}",
+                    standard.as_ref(),
+                    self.standard.as_ref()
+                ),
+                ErrorCode::IncompatibleStandard,
+            );
+        }
+    }
+}
+
 impl TokenAccess for ParsingContext<'_> {
     fn get_token(&self, id: TokenId) -> Option<&Token> {
         self.stream.get_token(id)
@@ -46,6 +72,7 @@ impl VHDLParser {
         VHDLParser {
             symbols: Arc::new(Symbols::from_standard(vhdl_standard)),
             standard: vhdl_standard,
+            conditional_analysis: ConditionalAnalysis::new(vhdl_standard),
         }
     }
 
@@ -57,10 +84,26 @@ impl VHDLParser {
         &self,
         source: &Source,
         diagnostics: &mut dyn DiagnosticHandler,
+    ) -> DesignFile {
+        if is_verilog_file(source.file_name()) {
+            return parse_verilog_source(self, source, diagnostics);
+        }
+        self.parse_vhdl_source(source, diagnostics)
+    }
+
+    /// Parses a source as VHDL regardless of its file extension
+    pub(crate) fn parse_vhdl_source(
+        &self,
+        source: &Source,
+        diagnostics: &mut dyn DiagnosticHandler,
     ) -> DesignFile {
         let contents = source.contents();
         let tokenizer = Tokenizer::new(&self.symbols, source, ContentReader::new(&contents));
-        let stream = TokenStream::new(tokenizer, diagnostics);
+        let stream = TokenStream::with_conditional_analysis(
+            tokenizer,
+            &self.conditional_analysis,
+            diagnostics,
+        );
 
         let mut ctx = ParsingContext {
             stream: &stream,
@@ -68,13 +111,30 @@ impl VHDLParser {
             standard: self.standard,
         };
 
-        match parse_design_file(&mut ctx) {
+        let design_file = match parse_design_file(&mut ctx) {
             Ok(design_file) => design_file,
             Err(diagnostic) => {
-                diagnostics.push(diagnostic);
+                ctx.diagnostics.push(diagnostic);
                 DesignFile::default()
             }
+        };
+
+        // Block comments are accepted by the tokenizer regardless of the standard
+        let block_comments = design_file
+            .design_units
+            .iter()
+            .flat_map(|(tokens, _)| tokens)
+            .filter_map(|token| token.comments.as_ref())
+            .flat_map(|comments| comments.leading.iter().chain(&comments.trailing))
+            .filter(|comment| comment.multi_line);
+        for comment in block_comments {
+            ctx.require_standard(
+                VHDLStandard::VHDL2008,
+                SrcPos::new(source.clone(), comment.range),
+                "A block comment",
+            );
         }
+        design_file
     }
 
     pub fn parse_design_file(
@@ -82,8 +142,186 @@ impl VHDLParser {
         file_name: &Path,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> ParserResult {
-        let source = Source::from_latin1_file(file_name)?;
+        self.parse_design_file_with_encoding(file_name, Encoding::default(), diagnostics)
+    }
+
+    pub fn parse_design_file_with_encoding(
+        &self,
+        file_name: &Path,
+        encoding: Encoding,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> ParserResult {
+        let source = Source::from_file(file_name, encoding)?;
         let design_file = self.parse_design_source(&source, diagnostics);
         Ok((source, design_file))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::{check_diagnostics, Code};
+
+    fn parse(code: &Code, standard: VHDLStandard) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        VHDLParser::new(standard).parse_vhdl_source(code.source(), &mut diagnostics);
+        diagnostics
+    }
+
+    fn incompatible(code: Code, construct: &str) -> Diagnostic {
+        Diagnostic::new(
+            code,
+            format!(
+                "{construct} requires VHDL-2008 or newer, but the configured standard is VHDL-1993"
+            ),
+            ErrorCode::IncompatibleStandard,
+        )
+    }
+
+    #[test]
+    fn vhdl_2008_constructs_in_vhdl_1993() {
+        let source = "
+entity ent is
+  generic (type typ);
+end entity;
+
+architecture arch of ent is
+  signal s : bit;
+begin
+  /* A block comment */
+  -- A concurrent conditional signal assignment is legal in VHDL-1993
+  s <= '1' when s = '0' else '0';
+  process (all)
+    variable v : natural;
+  begin
+    case? s is
+      when others => null;
+    end case?;
+    s <= << signal .tb.x : bit >>;
+    s <= and s;
+    if s ?= '1' then
+    end if;
+    s <= '0' when s = '1' else '1';
+    v := 1 when s = '1' else 0;
+  end process;
+end architecture;
+";
+        let code = Code::with_standard(source, VHDLStandard::VHDL1993);
+        check_diagnostics(
+            parse(&code, VHDLStandard::VHDL1993),
+            vec![
+                incompatible(code.s1("type"), "A generic type"),
+                incompatible(code.s1("all"), "The sensitivity list 'all'"),
+                incompatible(code.s1("?"), "The matching case statement"),
+                incompatible(code.s1("<<"), "An external name"),
+                incompatible(code.s1("and"), "The unary operator 'and'"),
+                incompatible(code.s1("?="), "The matching operator '?='"),
+                incompatible(code.s1("/* A block comment */"), "A block comment"),
+                incompatible(
+                    code.s1("'0' when").s1("when"),
+                    "A sequential conditional signal assignment",
+                ),
+                incompatible(
+                    code.s1("1 when").s1("when"),
+                    "A conditional variable assignment",
+                ),
+            ],
+        );
+
+        let code = Code::with_standard(source, VHDLStandard::VHDL2008);
+        check_diagnostics(parse(&code, VHDLStandard::VHDL2008), vec![]);
+    }
+
+    #[test]
+    fn vhdl_2019_conditional_expressions_in_vhdl_2008() {
+        let source = "
+package pkg is
+  constant c : natural := 1 when true else 2;
+  function f(x : integer) return natural;
+end package;
+
+package body pkg is
+  function f(x : integer) return natural is
+  begin
+    return x when x > 0 else -x;
+  end function;
+end package body;
+";
+        let code = Code::with_standard(source, VHDLStandard::VHDL2008);
+        check_diagnostics(
+            parse(&code, VHDLStandard::VHDL2008),
+            vec![
+                Diagnostic::new(
+                    code.s1("when true").s1("when"),
+                    "A conditional expression requires VHDL-2019 or newer, but the configured standard is VHDL-2008",
+                    ErrorCode::IncompatibleStandard,
+                ),
+                Diagnostic::new(
+                    code.s1("when x").s1("when"),
+                    "A conditional expression requires VHDL-2019 or newer, but the configured standard is VHDL-2008",
+                    ErrorCode::IncompatibleStandard,
+                ),
+            ],
+        );
+
+        let code = Code::with_standard(source, VHDLStandard::VHDL2019);
+        check_diagnostics(parse(&code, VHDLStandard::VHDL2019), vec![]);
+    }
+
+    #[test]
+    fn reserved_words_of_newer_standards_as_identifiers() {
+        let source = "
+architecture arch of ent is
+  signal force : bit;
+begin
+end architecture;
+";
+        let code = Code::with_standard(source, VHDLStandard::VHDL1993);
+        check_diagnostics(parse(&code, VHDLStandard::VHDL1993), vec![]);
+
+        let code = Code::with_standard(source, VHDLStandard::VHDL2008);
+        let diagnostics = parse(&code, VHDLStandard::VHDL2008);
+        assert_eq!(
+            diagnostics.first(),
+            Some(&Diagnostic::new(
+                code.s1("force"),
+                "'force' is a reserved word in VHDL-2008 and cannot be used as an identifier",
+                ErrorCode::IncompatibleStandard,
+            ))
+        );
+    }
+
+    #[test]
+    fn design_units_around_encrypted_regions_are_parsed() {
+        let code = Code::new(
+            "\
+entity ent is
+end entity;
+
+`protect begin_protected
+`protect version = 1
+`protect data_method = \"aes128-cbc\"
+`protect data_block
+ZW50aXR5IGVuY3J5cHRlZCBpcw0KZW5kIGVudGl0eTs=
+end (\"
+`protect end_protected
+
+architecture arch of ent is
+begin
+end architecture;
+",
+        );
+        let mut diagnostics = Vec::new();
+        let design_file = VHDLParser::new(VHDLStandard::default())
+            .parse_vhdl_source(code.source(), &mut diagnostics);
+        check_diagnostics(
+            diagnostics,
+            vec![Diagnostic::new(
+                code.s1("`protect begin_protected"),
+                "Encrypted region is not analyzed",
+                ErrorCode::EncryptedRegion,
+            )],
+        );
+        assert_eq!(design_file.design_units.len(), 2);
+    }
+}
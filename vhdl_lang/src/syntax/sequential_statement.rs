@@ -7,7 +7,7 @@
 use super::common::parse_optional;
 use super::common::ParseResult;
 use super::expression::parse_aggregate;
-use super::expression::{parse_choices, parse_expression};
+use super::expression::{parse_choices, parse_conditional_expression, parse_expression};
 use super::names::parse_name;
 use super::range::parse_discrete_range;
 use super::tokens::Kind::*;
@@ -15,10 +15,11 @@ use super::waveform::{parse_delay_mechanism, parse_waveform};
 use crate::ast::token_range::WithTokenSpan;
 use crate::ast::*;
 use crate::data::*;
+use crate::standard::VHDLStandard::VHDL2008;
 use crate::syntax::common::check_label_identifier_mismatch;
-use crate::syntax::kinds_error;
 use crate::syntax::recover::{expect_semicolon, expect_semicolon_or_last};
 use crate::syntax::separated_list::parse_name_list;
+use crate::syntax::{kinds_error, TokenAccess};
 use crate::HasTokenSpan;
 use vhdl_lang::syntax::parser::ParsingContext;
 use vhdl_lang::TokenSpan;
@@ -158,7 +159,15 @@ fn parse_case_statement(
     label: Option<&Ident>,
 ) -> ParseResult<CaseStatement> {
     ctx.stream.expect_kind(Case)?;
-    let is_matching = ctx.stream.pop_if_kind(Que).is_some();
+    let matching_token = ctx.stream.pop_if_kind(Que);
+    if let Some(token) = matching_token {
+        ctx.require_standard(
+            VHDL2008,
+            ctx.stream.get_pos(token).clone(),
+            "The matching case statement",
+        );
+    }
+    let is_matching = matching_token.is_some();
     let expression = parse_expression(ctx)?;
     ctx.stream.expect_kind(Is)?;
     ctx.stream.expect_kind(When)?;
@@ -280,7 +289,7 @@ fn parse_return_statement(ctx: &mut ParsingContext<'_>) -> ParseResult<ReturnSta
         if ctx.stream.peek_kind() == Some(SemiColon) {
             None
         } else {
-            Some(parse_expression(ctx)?)
+            Some(parse_conditional_expression(ctx)?)
         }
     };
     expect_semicolon(ctx);
@@ -288,22 +297,32 @@ fn parse_return_statement(ctx: &mut ParsingContext<'_>) -> ParseResult<ReturnSta
 }
 
 /// LRM 10.5 Signal assignment statement
+/// A conditional signal assignment is only a sequential statement since VHDL-2008
 pub fn parse_signal_assignment_right_hand(
     ctx: &mut ParsingContext<'_>,
+    is_sequential: bool,
 ) -> ParseResult<AssignmentRightHand<Waveform>> {
-    parse_assignment_right_hand(ctx, parse_waveform)
+    let conditional_construct =
+        is_sequential.then_some("A sequential conditional signal assignment");
+    parse_assignment_right_hand(ctx, parse_waveform, conditional_construct)
 }
 
 /// LRM 10.6 Variable assignment statement
 fn parse_variable_assignment_right_hand(
     ctx: &mut ParsingContext<'_>,
 ) -> ParseResult<AssignmentRightHand<WithTokenSpan<Expression>>> {
-    parse_assignment_right_hand(ctx, parse_expression)
+    parse_assignment_right_hand(
+        ctx,
+        parse_expression,
+        Some("A conditional variable assignment"),
+    )
 }
 
+/// `conditional_construct` describes a conditional assignment that requires VHDL-2008
 fn parse_assignment_right_hand<T, F>(
     ctx: &mut ParsingContext<'_>,
     parse_item: F,
+    conditional_construct: Option<&str>,
 ) -> ParseResult<AssignmentRightHand<T>>
 where
     F: Fn(&mut ParsingContext<'_>) -> ParseResult<T>,
@@ -312,7 +331,11 @@ where
     let token = ctx.stream.peek_expect()?;
     match token.kind {
         When => {
+            let when_pos = token.pos.clone();
             ctx.stream.skip();
+            if let Some(construct) = conditional_construct {
+                ctx.require_standard(VHDL2008, when_pos, construct);
+            }
             Ok(AssignmentRightHand::Conditional(parse_conditonals(
                 ctx, item, parse_item,
             )?))
@@ -390,8 +413,24 @@ where
     })
 }
 
+/// Parses the `select` keyword of a selected assignment.
+/// Returns true for the matching `select?` of VHDL-2008
+pub fn parse_select(ctx: &mut ParsingContext<'_>) -> ParseResult<bool> {
+    ctx.stream.expect_kind(Select)?;
+    let matching_token = ctx.stream.pop_if_kind(Que);
+    if let Some(token) = matching_token {
+        ctx.require_standard(
+            VHDL2008,
+            ctx.stream.get_pos(token).clone(),
+            "The matching selected assignment",
+        );
+    }
+    Ok(matching_token.is_some())
+}
+
 pub fn parse_selection<T, F>(
     ctx: &mut ParsingContext<'_>,
+    is_matching: bool,
     expression: WithTokenSpan<Expression>,
     parse_item: F,
 ) -> ParseResult<Selection<T>>
@@ -421,6 +460,7 @@ where
     }
 
     Ok(Selection {
+        is_matching,
         expression,
         alternatives,
     })
@@ -484,7 +524,7 @@ fn parse_assignment_or_procedure_call(
                     SequentialStatement::SignalAssignment(SignalAssignment {
                         target,
                         delay_mechanism,
-                        rhs: parse_signal_assignment_right_hand(ctx)?
+                        rhs: parse_signal_assignment_right_hand(ctx, true)?
                     })
                 }
             }
@@ -519,13 +559,13 @@ pub fn parse_target(ctx: &mut ParsingContext<'_>) -> ParseResult<WithTokenSpan<T
 
 fn parse_selected_assignment(ctx: &mut ParsingContext<'_>) -> ParseResult<SequentialStatement> {
     let expression = parse_expression(ctx)?;
-    ctx.stream.expect_kind(Select)?;
+    let is_matching = parse_select(ctx)?;
     let target = parse_target(ctx)?;
     expect_token!(
         ctx.stream,
         token,
         ColonEq => {
-            let rhs = AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_expression)?);
+            let rhs = AssignmentRightHand::Selected(parse_selection(ctx, is_matching, expression, parse_expression)?);
             Ok(SequentialStatement::VariableAssignment(VariableAssignment {
                 target,
                 rhs,
@@ -536,13 +576,13 @@ fn parse_selected_assignment(ctx: &mut ParsingContext<'_>) -> ParseResult<Sequen
                 Ok(SequentialStatement::SignalForceAssignment(SignalForceAssignment {
                     target,
                     force_mode: parse_optional_force_mode(ctx)?,
-                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_expression)?)
+                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, is_matching, expression, parse_expression)?)
                 }))
             } else {
                 Ok(SequentialStatement::SignalAssignment(SignalAssignment {
                     target,
                     delay_mechanism: parse_delay_mechanism(ctx)?,
-                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, expression, parse_waveform)?)
+                    rhs: AssignmentRightHand::Selected(parse_selection(ctx, is_matching, expression, parse_waveform)?)
                 }))
             }
         }
@@ -1096,6 +1136,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![
                 Alternative {
@@ -1126,6 +1167,47 @@ with x(0) + 1 select
         );
     }
 
+    #[test]
+    fn parse_matching_selected_variable_assignment() {
+        let (code, statement) = parse(
+            "\
+with x select?
+   foo := bar when \"1-\",
+          def when others;",
+        );
+
+        let selection = Selection {
+            is_matching: true,
+            expression: code.s1("x").expr(),
+            alternatives: vec![
+                Alternative {
+                    choices: code.s1("\"1-\"").choices(),
+                    item: code.s1("bar").expr(),
+                    span: code.s1("bar when \"1-\"").token_span(),
+                },
+                Alternative {
+                    choices: code.s1("others").choices(),
+                    item: code.s1("def").expr(),
+                    span: code.s1("def when others").token_span(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            statement,
+            with_label(
+                None,
+                WithTokenSpan::new(
+                    SequentialStatement::VariableAssignment(VariableAssignment {
+                        target: code.s1("foo").name().map_into(Target::Name),
+                        rhs: AssignmentRightHand::Selected(selection),
+                    }),
+                    code.token_span()
+                )
+            )
+        );
+    }
+
     #[test]
     fn parse_conditional_variable_assignment_several() {
         let (code, statement) = parse("foo(0) := bar(1,2) when cond = true else expr2 when cond2;");
@@ -1245,6 +1327,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![
                 Alternative {
@@ -1289,6 +1372,7 @@ with x(0) + 1 select
         );
 
         let selection = Selection {
+            is_matching: false,
             expression: code.s1("x(0) + 1").expr(),
             alternatives: vec![
                 Alternative {
@@ -6,9 +6,13 @@
 
 #[macro_use]
 mod tokenizer;
+mod conditional;
 /// Contains constant keywords for different versions of VHDL.
 mod keywords;
+mod pragma;
 mod tokenstream;
 
+pub use conditional::ConditionalAnalysis;
+pub use pragma::*;
 pub use tokenizer::*;
 pub use tokenstream::*;
@@ -0,0 +1,395 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::data::{Diagnostic, SrcPos};
+use crate::standard::VHDLStandard;
+use fnv::FnvHashMap;
+use std::cmp::Ordering;
+
+/// The values of the identifiers of conditional analysis directives (LRM 24.2), such as
+/// ```vhdl
+/// `if TOOL_TYPE = "SIMULATION" `then
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConditionalAnalysis {
+    /// The values by the upper case name of the identifier
+    identifiers: FnvHashMap<String, String>,
+}
+
+impl ConditionalAnalysis {
+    /// The predefined identifiers, where `VHDL_VERSION` is the given standard
+    pub fn new(standard: VHDLStandard) -> ConditionalAnalysis {
+        let identifiers = [
+            ("VHDL_VERSION", standard.as_ref()),
+            ("TOOL_TYPE", "SIMULATION"),
+            ("TOOL_VENDOR", "VHDL-LS"),
+            ("TOOL_NAME", "vhdl_ls"),
+            ("TOOL_EDITION", ""),
+            ("TOOL_VERSION", env!("CARGO_PKG_VERSION")),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect();
+        ConditionalAnalysis { identifiers }
+    }
+
+    /// Sets the value of a predefined or an additional identifier
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.identifiers
+            .insert(name.to_ascii_uppercase(), value.to_owned());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.identifiers
+            .get(&name.to_ascii_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Evaluates the condition of an `` `if `` or `` `elsif `` directive,
+    /// which is the text of the directive up to and including `` `then ``
+    pub(crate) fn evaluate(&self, text: &str, pos: &SrcPos) -> Result<bool, Diagnostic> {
+        let error = |message: &str| Diagnostic::syntax_error(pos, message);
+        let mut words = lex(text).map_err(|message| error(&message))?;
+        if !words.pop().is_some_and(|word| word.is_keyword("`then")) {
+            return Err(error("Expecting `then"));
+        }
+        let mut parser = ConditionParser {
+            identifiers: self,
+            words: words.into_iter().peekable(),
+        };
+        let value = parser.expression().map_err(|message| error(&message))?;
+        if let Some(word) = parser.words.next() {
+            return Err(error(&format!(
+                "Unexpected '{}' in conditional analysis expression",
+                word.text()
+            )));
+        }
+        Ok(value)
+    }
+}
+
+impl Default for ConditionalAnalysis {
+    fn default() -> Self {
+        ConditionalAnalysis::new(VHDLStandard::default())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Word {
+    Identifier(String),
+    String(String),
+    Delimiter(&'static str),
+}
+
+impl Word {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Word::Identifier(name) if name.eq_ignore_ascii_case(keyword))
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Word::Identifier(name) => name.clone(),
+            Word::String(value) => format!("\"{value}\""),
+            Word::Delimiter(delimiter) => delimiter.to_string(),
+        }
+    }
+}
+
+/// Splits the text of a directive into identifiers, string literals and delimiters
+fn lex(text: &str) -> Result<Vec<Word>, String> {
+    let mut words = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(chr) = chars.next() {
+        let word = match chr {
+            chr if chr.is_whitespace() => continue,
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        // Two quotes represent a quote within the string
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            value.push('"');
+                        }
+                        Some('"') => break,
+                        Some(chr) => value.push(chr),
+                        None => return Err("Unterminated string literal".to_owned()),
+                    }
+                }
+                Word::String(value)
+            }
+            '(' => Word::Delimiter("("),
+            ')' => Word::Delimiter(")"),
+            '=' => Word::Delimiter("="),
+            '/' if chars.peek() == Some(&'=') => {
+                chars.next();
+                Word::Delimiter("/=")
+            }
+            '<' | '>' => {
+                let is_equal = chars.peek() == Some(&'=');
+                if is_equal {
+                    chars.next();
+                }
+                Word::Delimiter(match (chr, is_equal) {
+                    ('<', false) => "<",
+                    ('<', true) => "<=",
+                    (_, false) => ">",
+                    (_, true) => ">=",
+                })
+            }
+            chr if chr == '`' || chr.is_ascii_alphabetic() => {
+                let mut name = chr.to_string();
+                while let Some(&chr) = chars.peek() {
+                    if !(chr.is_ascii_alphanumeric() || chr == '_') {
+                        break;
+                    }
+                    name.push(chr);
+                    chars.next();
+                }
+                Word::Identifier(name)
+            }
+            chr => {
+                return Err(format!(
+                    "Unexpected '{chr}' in conditional analysis expression"
+                ))
+            }
+        };
+        words.push(word);
+    }
+    Ok(words)
+}
+
+struct ConditionParser<'a, I: Iterator<Item = Word>> {
+    identifiers: &'a ConditionalAnalysis,
+    words: std::iter::Peekable<I>,
+}
+
+impl<I: Iterator<Item = Word>> ConditionParser<'_, I> {
+    /// A sequence of relations that are combined by the same logical operator
+    fn expression(&mut self) -> Result<bool, String> {
+        let mut value = self.relation()?;
+        let mut operator: Option<String> = None;
+        while let Some(Word::Identifier(name)) = self.words.peek() {
+            let name = name.to_ascii_lowercase();
+            if !matches!(name.as_str(), "and" | "or" | "xor" | "xnor") {
+                break;
+            }
+            if operator.as_ref().is_some_and(|operator| *operator != name) {
+                return Err("Mixed logical operators must be separated by parentheses".to_owned());
+            }
+            self.words.next();
+            let rhs = self.relation()?;
+            value = match name.as_str() {
+                "and" => value && rhs,
+                "or" => value || rhs,
+                "xor" => value != rhs,
+                _ => value == rhs,
+            };
+            operator = Some(name);
+        }
+        Ok(value)
+    }
+
+    fn parenthesized(&mut self) -> Result<bool, String> {
+        self.expect(&Word::Delimiter("("))?;
+        let value = self.expression()?;
+        self.expect(&Word::Delimiter(")"))?;
+        Ok(value)
+    }
+
+    fn relation(&mut self) -> Result<bool, String> {
+        match self.words.peek() {
+            Some(Word::Delimiter("(")) => self.parenthesized(),
+            Some(word) if word.is_keyword("not") => {
+                self.words.next();
+                Ok(!self.parenthesized()?)
+            }
+            _ => {
+                let name = match self.words.next() {
+                    Some(Word::Identifier(name)) => name,
+                    _ => return Err("Expecting conditional analysis identifier".to_owned()),
+                };
+                let Some(value) = self.identifiers.get(&name) else {
+                    return Err(format!("Unknown conditional analysis identifier '{name}'"));
+                };
+                let operator = match self.words.next() {
+                    Some(Word::Delimiter(operator)) if operator != "(" && operator != ")" => {
+                        operator
+                    }
+                    _ => return Err("Expecting relational operator".to_owned()),
+                };
+                let Some(Word::String(literal)) = self.words.next() else {
+                    return Err("Expecting string literal".to_owned());
+                };
+                let ordering = value.cmp(literal.as_str());
+                Ok(match operator {
+                    "=" => ordering == Ordering::Equal,
+                    "/=" => ordering != Ordering::Equal,
+                    "<" => ordering == Ordering::Less,
+                    "<=" => ordering != Ordering::Greater,
+                    ">" => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                })
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: &Word) -> Result<(), String> {
+        match self.words.next() {
+            Some(word) if word == *expected => Ok(()),
+            _ => Err(format!("Expecting '{}'", expected.text())),
+        }
+    }
+}
+
+/// A conditional analysis directive that is not yet closed by an `` `end `` directive
+struct Conditional {
+    pos: SrcPos,
+    /// The source of the current branch is analyzed
+    is_active: bool,
+    /// One of the branches so far has a true condition
+    is_taken: bool,
+    has_else: bool,
+}
+
+/// The nesting of conditional analysis directives while tokenizing a source
+#[derive(Default)]
+pub(crate) struct ConditionalRegions {
+    stack: Vec<Conditional>,
+}
+
+impl ConditionalRegions {
+    /// The source at the current position is analyzed
+    pub fn is_active(&self) -> bool {
+        self.stack.last().is_none_or(|top| top.is_active)
+    }
+
+    /// An `` `if `` directive where the condition is `None` when it is not evaluated
+    pub fn push_if(&mut self, pos: SrcPos, condition: Option<bool>) {
+        let is_active = self.is_active() && condition.unwrap_or(false);
+        self.stack.push(Conditional {
+            pos,
+            is_active,
+            is_taken: is_active,
+            has_else: false,
+        });
+    }
+
+    /// Returns true when the condition of an `` `elsif `` directive must be evaluated
+    pub fn needs_elsif_condition(&self) -> bool {
+        let parent_is_active = self.stack.len() < 2 || self.stack[self.stack.len() - 2].is_active;
+        self.stack
+            .last()
+            .is_some_and(|top| parent_is_active && !top.is_taken && !top.has_else)
+    }
+
+    pub fn elsif(&mut self, pos: &SrcPos, condition: Option<bool>) -> Result<(), Diagnostic> {
+        let Some(top) = self.stack.last_mut() else {
+            return Err(Diagnostic::syntax_error(pos, "`elsif without `if"));
+        };
+        if top.has_else {
+            return Err(Diagnostic::syntax_error(pos, "`elsif after `else"));
+        }
+        top.is_active = condition.unwrap_or(false);
+        top.is_taken |= top.is_active;
+        Ok(())
+    }
+
+    pub fn else_branch(&mut self, pos: &SrcPos) -> Result<(), Diagnostic> {
+        let parent_is_active = self.stack.len() < 2 || self.stack[self.stack.len() - 2].is_active;
+        let Some(top) = self.stack.last_mut() else {
+            return Err(Diagnostic::syntax_error(pos, "`else without `if"));
+        };
+        if top.has_else {
+            return Err(Diagnostic::syntax_error(pos, "Duplicate `else"));
+        }
+        top.is_active = parent_is_active && !top.is_taken;
+        top.is_taken = true;
+        top.has_else = true;
+        Ok(())
+    }
+
+    pub fn end(&mut self, pos: &SrcPos) -> Result<(), Diagnostic> {
+        match self.stack.pop() {
+            Some(_) => Ok(()),
+            None => Err(Diagnostic::syntax_error(pos, "`end without `if")),
+        }
+    }
+
+    /// Diagnostics for the directives that are not closed at the end of the source
+    pub fn finish(self) -> Vec<Diagnostic> {
+        self.stack
+            .into_iter()
+            .map(|conditional| {
+                Diagnostic::syntax_error(
+                    conditional.pos,
+                    "Missing `end of conditional analysis directive",
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    fn evaluate(text: &str) -> Result<bool, String> {
+        let mut identifiers = ConditionalAnalysis::new(VHDLStandard::VHDL2019);
+        identifiers.set("tool_type", "SYNTHESIS");
+        let code = Code::new(text);
+        identifiers
+            .evaluate(text, &code.pos())
+            .map_err(|diagnostic| diagnostic.message)
+    }
+
+    #[test]
+    fn evaluates_relations() {
+        assert_eq!(evaluate("TOOL_TYPE = \"SYNTHESIS\" `then"), Ok(true));
+        assert_eq!(evaluate("tool_type /= \"SYNTHESIS\" `then"), Ok(false));
+        assert_eq!(evaluate("VHDL_VERSION >= \"2008\" `then"), Ok(true));
+        assert_eq!(evaluate("VHDL_VERSION < \"2008\" `THEN"), Ok(false));
+    }
+
+    #[test]
+    fn evaluates_logical_operators() {
+        assert_eq!(
+            evaluate("TOOL_TYPE = \"SYNTHESIS\" and VHDL_VERSION = \"2019\" `then"),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate("not (TOOL_TYPE = \"SYNTHESIS\") or VHDL_VERSION = \"2008\" `then"),
+            Ok(false)
+        );
+        assert_eq!(
+            evaluate(
+                "(TOOL_TYPE = \"A\" or TOOL_TYPE = \"SYNTHESIS\") and TOOL_NAME = \"vhdl_ls\" `then"
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate("TOOL_TYPE = \"A\" or TOOL_TYPE = \"B\" and TOOL_TYPE = \"C\" `then"),
+            Err("Mixed logical operators must be separated by parentheses".to_owned())
+        );
+    }
+
+    #[test]
+    fn errors_in_conditions() {
+        assert_eq!(
+            evaluate("TOOL_TYPE = \"SYNTHESIS\""),
+            Err("Expecting `then".to_owned())
+        );
+        assert_eq!(
+            evaluate("UNKNOWN = \"SYNTHESIS\" `then"),
+            Err("Unknown conditional analysis identifier 'UNKNOWN'".to_owned())
+        );
+        assert_eq!(
+            evaluate("TOOL_TYPE = SYNTHESIS `then"),
+            Err("Expecting string literal".to_owned())
+        );
+    }
+}
@@ -450,6 +450,19 @@ const ATTRIBUTES_2019: &[AttributeDesignator] = &[
     AttributeDesignator::Converse,
 ];
 
+/// Returns the standard that introduced a reserved word that is an identifier in VHDL-1993
+pub fn reserved_since(kind: Kind) -> Option<VHDLStandard> {
+    if KEYWORDS_1993.contains(&kind) {
+        None
+    } else if KEYWORDS_2008.contains(&kind) {
+        Some(VHDLStandard::VHDL2008)
+    } else if KEYWORDS_2019.contains(&kind) {
+        Some(VHDLStandard::VHDL2019)
+    } else {
+        None
+    }
+}
+
 impl VHDLStandard {
     /// Get all keywords that this VHDL standard defines
     pub fn keywords(&self) -> &'static [Kind] {
@@ -0,0 +1,191 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{Comment, Token};
+use crate::data::{Diagnostic, ErrorCode, Position};
+
+/// A comment that controls which diagnostics are reported, such as `-- vhdl_lang: off unused`.
+/// Without an error code, the pragma applies to all diagnostics.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Pragma {
+    /// Suppresses diagnostics until the matching `on` pragma
+    Off(Option<ErrorCode>),
+    /// Reports diagnostics again after an `off` pragma
+    On(Option<ErrorCode>),
+    /// Suppresses diagnostics on the same line
+    Ignore(Option<ErrorCode>),
+}
+
+impl Pragma {
+    /// Parses the pragma of a single line comment.
+    /// Comments with an unknown command or error code are not pragmas.
+    pub fn parse(comment: &Comment) -> Option<Pragma> {
+        if comment.multi_line {
+            return None;
+        }
+        let mut words = comment
+            .value
+            .trim()
+            .strip_prefix("vhdl_lang:")?
+            .split_whitespace();
+        let command = words.next()?;
+        let code = match words.next() {
+            Some(code) => Some(ErrorCode::try_from(code).ok()?),
+            None => None,
+        };
+        if words.next().is_some() {
+            return None;
+        }
+        match command {
+            "off" => Some(Pragma::Off(code)),
+            "on" => Some(Pragma::On(code)),
+            "ignore" => Some(Pragma::Ignore(code)),
+            _ => None,
+        }
+    }
+}
+
+/// A part of a source where diagnostics are suppressed
+#[derive(Clone, Debug)]
+struct Suppression {
+    code: Option<ErrorCode>,
+    start: Position,
+    /// The region ends at the end of the source when there is no matching `on` pragma
+    end: Option<Position>,
+}
+
+impl Suppression {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        let start = diagnostic.pos.start();
+        self.code.is_none_or(|code| code == diagnostic.code)
+            && start >= self.start
+            && self.end.is_none_or(|end| start < end)
+    }
+}
+
+/// The diagnostics that are suppressed by the pragmas of a source
+#[derive(Clone, Debug, Default)]
+pub struct Suppressions {
+    regions: Vec<Suppression>,
+}
+
+impl Suppressions {
+    pub fn from_tokens<'a>(tokens: impl IntoIterator<Item = &'a Token>) -> Suppressions {
+        let mut regions: Vec<Suppression> = Vec::new();
+        let comments = tokens
+            .into_iter()
+            .filter_map(|token| token.comments.as_ref())
+            .flat_map(|comments| comments.leading.iter().chain(comments.trailing.iter()));
+        for comment in comments {
+            let Some(pragma) = Pragma::parse(comment) else {
+                continue;
+            };
+            match pragma {
+                Pragma::Off(code) => {
+                    if !regions
+                        .iter()
+                        .any(|region| region.end.is_none() && region.code == code)
+                    {
+                        regions.push(Suppression {
+                            code,
+                            start: comment.range.start,
+                            end: None,
+                        });
+                    }
+                }
+                Pragma::On(code) => {
+                    for region in regions.iter_mut() {
+                        // An 'on' pragma without an error code ends all regions
+                        if region.end.is_none() && (code.is_none() || region.code == code) {
+                            region.end = Some(comment.range.start);
+                        }
+                    }
+                }
+                Pragma::Ignore(code) => {
+                    let line = comment.range.start.line;
+                    regions.push(Suppression {
+                        code,
+                        start: Position::new(line, 0),
+                        end: Some(Position::new(line + 1, 0)),
+                    });
+                }
+            }
+        }
+        Suppressions { regions }
+    }
+
+    pub fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+        self.regions.iter().any(|region| region.matches(diagnostic))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Range;
+    use crate::syntax::test::Code;
+
+    fn suppressions(code: &Code) -> Suppressions {
+        let (tokens, _) = code.tokenize_result();
+        Suppressions::from_tokens(tokens.iter().filter_map(|token| token.as_ref().ok()))
+    }
+
+    #[test]
+    fn parses_pragmas() {
+        let comment = |value: &str| Comment {
+            value: value.to_owned(),
+            range: Range::new(Position::default(), Position::default()),
+            multi_line: false,
+        };
+        assert_eq!(
+            Pragma::parse(&comment(" vhdl_lang: off unused")),
+            Some(Pragma::Off(Some(ErrorCode::Unused)))
+        );
+        assert_eq!(
+            Pragma::parse(&comment(" vhdl_lang: on")),
+            Some(Pragma::On(None))
+        );
+        assert_eq!(
+            Pragma::parse(&comment("vhdl_lang: ignore missing_sensitivity ")),
+            Some(Pragma::Ignore(Some(ErrorCode::MissingSensitivity)))
+        );
+        assert_eq!(
+            Pragma::parse(&comment(" vhdl_lang: off no_such_code")),
+            None
+        );
+        assert_eq!(Pragma::parse(&comment(" vhdl_lang: enable unused")), None);
+        assert_eq!(Pragma::parse(&comment(" just a comment")), None);
+    }
+
+    #[test]
+    fn suppresses_diagnostics_in_regions_and_lines() {
+        let code = Code::new(
+            "\
+signal a : bit;
+-- vhdl_lang: off unused
+signal b : bit;
+-- vhdl_lang: on unused
+signal c : bit; -- vhdl_lang: ignore
+signal d : bit;
+-- vhdl_lang: off
+signal e : bit;",
+        );
+        let suppressions = suppressions(&code);
+        let unused =
+            |name: &str| Diagnostic::new(code.s1(&format!("signal {name}")), "", ErrorCode::Unused);
+        let duplicate = |name: &str| {
+            Diagnostic::new(code.s1(&format!("signal {name}")), "", ErrorCode::Duplicate)
+        };
+
+        assert!(!suppressions.is_suppressed(&unused("a")));
+        assert!(suppressions.is_suppressed(&unused("b")));
+        assert!(!suppressions.is_suppressed(&duplicate("b")));
+        assert!(suppressions.is_suppressed(&unused("c")));
+        assert!(suppressions.is_suppressed(&duplicate("c")));
+        assert!(!suppressions.is_suppressed(&unused("d")));
+        assert!(suppressions.is_suppressed(&duplicate("e")));
+    }
+}
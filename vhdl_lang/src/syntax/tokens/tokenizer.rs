@@ -1529,6 +1529,74 @@ fn read_until_newline(
     Ok(())
 }
 
+/// Skips the lines of an encrypted region up to and including the line of the
+/// `` `protect end_protected `` directive, or up to the end of the source.
+///
+/// Returns true when lines other than tool directives, i.e. encrypted data, were skipped
+fn skip_protected_lines(
+    buffer: &mut Latin1String,
+    reader: &mut ContentReader<'_>,
+) -> Result<bool, TokenError> {
+    let mut has_data = false;
+    loop {
+        if reader.peek()? == Some(b'\n') {
+            reader.skip();
+        }
+        if reader.peek()?.is_none() {
+            return Ok(has_data);
+        }
+        read_until_newline(buffer, reader)?;
+        let line = buffer.to_string();
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some(word)
+                if word.eq_ignore_ascii_case("`protect")
+                    && words
+                        .next()
+                        .is_some_and(|word| word.eq_ignore_ascii_case("end_protected")) =>
+            {
+                return Ok(has_data);
+            }
+            Some(word) if word.starts_with('`') => {}
+            Some(_) => has_data = true,
+            None => {}
+        }
+    }
+}
+
+/// Skips lines up to the next line that starts with one of the given tool directives,
+/// such as `` `end ``, or up to the end of the source
+fn skip_lines_until_directive(
+    buffer: &mut Latin1String,
+    reader: &mut ContentReader<'_>,
+    directives: &[&str],
+) -> Result<(), TokenError> {
+    loop {
+        if reader.peek()? == Some(b'\n') {
+            reader.skip();
+        }
+        if reader.peek()?.is_none() {
+            return Ok(());
+        }
+        let state = reader.state();
+        read_until_newline(buffer, reader)?;
+        let line = buffer.to_string();
+        let is_directive = line.trim_start().strip_prefix('`').is_some_and(|line| {
+            let name = line
+                .split(|chr: char| !(chr.is_ascii_alphanumeric() || chr == '_'))
+                .next()
+                .unwrap_or_default();
+            directives
+                .iter()
+                .any(|directive| directive.eq_ignore_ascii_case(name))
+        });
+        if is_directive {
+            reader.set_state(state);
+            return Ok(());
+        }
+    }
+}
+
 fn get_leading_comments(reader: &mut ContentReader<'_>) -> Result<Vec<Comment>, TokenError> {
     let mut comments: Vec<Comment> = Vec::new();
 
@@ -2007,6 +2075,29 @@ impl<'a> Tokenizer<'a> {
             comments: None,
         })
     }
+
+    /// Skips the source up to the next line that starts with one of the given tool directives
+    pub fn skip_until_directive(&mut self, directives: &[&str]) -> DiagnosticResult<()> {
+        skip_lines_until_directive(&mut self.buffer, &mut self.reader, directives).map_err(|err| {
+            self.state.start = self.reader.state();
+            Diagnostic::syntax_error(self.source.pos(err.range.start, err.range.end), err.message)
+        })
+    }
+
+    /// Skips an encrypted region that follows a `` `protect begin_protected `` directive.
+    /// Returns true when the region contains encrypted data
+    pub fn skip_protected_region(&mut self) -> DiagnosticResult<bool> {
+        match skip_protected_lines(&mut self.buffer, &mut self.reader) {
+            Ok(has_data) => Ok(has_data),
+            Err(err) => {
+                self.state.start = self.reader.state();
+                Err(Diagnostic::syntax_error(
+                    self.source.pos(err.range.start, err.range.end),
+                    err.message,
+                ))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
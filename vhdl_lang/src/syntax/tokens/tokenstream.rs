@@ -7,11 +7,13 @@
 use std::cell::Cell;
 use vhdl_lang::syntax::parser::ParsingContext;
 
+use super::conditional::{ConditionalAnalysis, ConditionalRegions};
+use super::keywords::reserved_since;
 use super::tokenizer::Kind::*;
 use super::tokenizer::*;
 use crate::ast::token_range::WithToken;
 use crate::ast::{AttributeDesignator, Ident, RangeAttribute, TypeAttribute};
-use crate::data::{DiagnosticHandler, DiagnosticResult};
+use crate::data::{DiagnosticHandler, DiagnosticResult, ErrorCode};
 use crate::{Diagnostic, SrcPos};
 
 pub struct TokenStream<'a> {
@@ -30,47 +32,173 @@ impl<'a> TokenStream<'a> {
     /// `identifier { any chars until newline }
     /// ```
     /// This needs special handling as the text that follows the identifier is arbitrary.
+    ///
+    /// The encrypted region between `` `protect begin_protected `` and
+    /// `` `protect end_protected `` is not VHDL and is skipped up to the end of the source
+    /// when there is no `` `protect end_protected `` directive.
     fn handle_tool_directive(
         grave_accent: Token,
         tokenizer: &mut Tokenizer<'_>,
+        conditions: &ConditionalAnalysis,
+        regions: &mut ConditionalRegions,
         diagnostics: &mut dyn DiagnosticHandler,
     ) {
         let start_pos = grave_accent.pos.clone();
-        match tokenizer.pop() {
-            Ok(Some(tok)) => {
-                if tok.kind != Identifier {
-                    diagnostics.push(Diagnostic::syntax_error(tok, "Expecting identifier"));
+        let (kind, is_protect) = match tokenizer.pop() {
+            Ok(Some(tok)) => match tok.kind {
+                If | Elsif | Else | End => (Some(tok.kind), false),
+                Identifier => (
+                    None,
+                    matches!(
+                        tok.value,
+                        Value::Identifier(ref sym) if sym.name_utf8().eq_ignore_ascii_case("protect")
+                    ),
+                ),
+                _ => {
+                    if regions.is_active() {
+                        diagnostics.push(Diagnostic::syntax_error(tok, "Expecting identifier"));
+                    }
                     let _ = tokenizer.text_until_newline(); // skip potentially invalid tokens
                     return;
                 }
+            },
+            Err(err) => {
+                if regions.is_active() {
+                    diagnostics.push(err);
+                }
+                (None, false)
             }
-            Err(err) => diagnostics.push(err),
             Ok(None) => {
-                diagnostics.push(Diagnostic::syntax_error(start_pos, "Expecting identifier"));
+                if regions.is_active() {
+                    diagnostics.push(Diagnostic::syntax_error(start_pos, "Expecting identifier"));
+                }
                 return;
             }
+        };
+        let text = match tokenizer.text_until_newline() {
+            Ok(text) => text,
+            Err(err) => {
+                if regions.is_active() {
+                    diagnostics.push(err);
+                }
+                return;
+            }
+        };
+        let Value::Text(ref value) = text.value else {
+            return;
+        };
+        let value = value.to_string();
+        let pos = start_pos.combine(&text.pos);
+
+        if let Some(kind) = kind {
+            let mut evaluate = || match conditions.evaluate(&value, &pos) {
+                Ok(condition) => Some(condition),
+                Err(err) => {
+                    diagnostics.push(err);
+                    None
+                }
+            };
+            let result = match kind {
+                If => {
+                    let condition = if regions.is_active() {
+                        evaluate()
+                    } else {
+                        None
+                    };
+                    regions.push_if(pos, condition);
+                    Ok(())
+                }
+                Elsif => {
+                    let condition = if regions.needs_elsif_condition() {
+                        evaluate()
+                    } else {
+                        None
+                    };
+                    regions.elsif(&pos, condition)
+                }
+                Else => regions.else_branch(&pos),
+                _ => regions.end(&pos),
+            };
+            if let Err(err) = result {
+                diagnostics.push(err);
+            }
+            return;
         }
-        match tokenizer.text_until_newline() {
-            Ok(_) => {}
+
+        let is_begin_protected = value
+            .split_whitespace()
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case("begin_protected"));
+        if !(is_protect && is_begin_protected && regions.is_active()) {
+            return;
+        }
+        match tokenizer.skip_protected_region() {
+            Ok(true) => diagnostics.push(Diagnostic::new(
+                pos,
+                "Encrypted region is not analyzed",
+                ErrorCode::EncryptedRegion,
+            )),
+            Ok(false) => {}
             Err(err) => diagnostics.push(err),
         }
     }
 
+    #[cfg(test)]
     pub fn new(
+        tokenizer: Tokenizer<'a>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> TokenStream<'a> {
+        TokenStream::with_conditional_analysis(
+            tokenizer,
+            &ConditionalAnalysis::default(),
+            diagnostics,
+        )
+    }
+
+    /// Tokenizes a source where the source between conditional analysis directives
+    /// with a false condition is excluded
+    pub fn with_conditional_analysis(
         mut tokenizer: Tokenizer<'a>,
+        conditions: &ConditionalAnalysis,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> TokenStream<'a> {
         let mut tokens = Vec::new();
+        let mut regions = ConditionalRegions::default();
         loop {
             match tokenizer.pop() {
                 Ok(Some(token)) if token.kind == GraveAccent => {
-                    TokenStream::handle_tool_directive(token, &mut tokenizer, diagnostics)
+                    TokenStream::handle_tool_directive(
+                        token,
+                        &mut tokenizer,
+                        conditions,
+                        &mut regions,
+                        diagnostics,
+                    );
+                    // The source of an inactive region might not even be tokenizable
+                    if !regions.is_active() {
+                        if let Err(err) =
+                            tokenizer.skip_until_directive(&["if", "elsif", "else", "end"])
+                        {
+                            diagnostics.push(err);
+                        }
+                    }
+                }
+                Ok(Some(token)) => {
+                    if regions.is_active() {
+                        tokens.push(token)
+                    }
                 }
-                Ok(Some(token)) => tokens.push(token),
                 Ok(None) => break,
-                Err(err) => diagnostics.push(err),
+                Err(err) => {
+                    if regions.is_active() {
+                        diagnostics.push(err)
+                    }
+                }
             }
         }
+        for diagnostic in regions.finish() {
+            diagnostics.push(diagnostic);
+        }
         TokenStream {
             tokenizer,
             idx: Cell::new(0),
@@ -242,6 +370,20 @@ impl<'a> TokenStream<'a> {
     }
 
     pub fn expect_ident(&self) -> DiagnosticResult<Ident> {
+        // Reserved words of newer standards are identifiers in older code
+        if let Some(token) = self.peek() {
+            if let Some(standard) = reserved_since(token.kind) {
+                return Err(Diagnostic::new(
+                    token,
+                    format!(
+                        "'{}' is a reserved word in VHDL-{} and cannot be used as an identifier",
+                        kind_str(token.kind),
+                        standard.as_ref()
+                    ),
+                    ErrorCode::IncompatibleStandard,
+                ));
+            }
+        }
         expect_token!(self, token, token_id, Identifier => token.to_identifier_value(token_id))
     }
 
@@ -336,6 +478,7 @@ impl<T> Recover<T> for DiagnosticResult<T> {
 mod tests {
     use super::*;
     use crate::data::{ContentReader, Diagnostic, NoDiagnostics};
+    use crate::standard::VHDLStandard;
     use crate::syntax::test::Code;
     use itertools::Itertools;
 
@@ -522,6 +665,129 @@ mod tests {
         )
     }
 
+    #[test]
+    fn skips_encrypted_region() {
+        let code = Code::new(
+            "\
+`protect begin_protected
+`protect key_block
+Zm9vIGJhciAnYmF6ICIgKysr
+`protect data_block
+entity \"garbage
+`protect end_protected
+entity",
+        );
+        let mut diagnostics: Vec<Diagnostic> = vec![];
+        new_stream!(code, stream, diagnostics);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::new(
+                code.s1("`protect begin_protected"),
+                "Encrypted region is not analyzed",
+                ErrorCode::EncryptedRegion,
+            )]
+        );
+        assert_eq!(
+            stream.peek().map(|tok| &tok.pos),
+            Some(&code.s("entity", 2).pos())
+        );
+        stream.skip();
+        assert_eq!(stream.peek(), None);
+    }
+
+    #[test]
+    fn skips_unterminated_encrypted_region() {
+        let code = Code::new(
+            "\
+`protect begin_protected
+`protect data_block
+Zm9vIGJhcg==
+entity",
+        );
+        let mut diagnostics: Vec<Diagnostic> = vec![];
+        new_stream!(code, stream, diagnostics);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::new(
+                code.s1("`protect begin_protected"),
+                "Encrypted region is not analyzed",
+                ErrorCode::EncryptedRegion,
+            )]
+        );
+        assert_eq!(stream.peek(), None);
+    }
+
+    fn active_identifiers(code: &str) -> (Vec<String>, Vec<Diagnostic>) {
+        let code = Code::new(code);
+        let mut conditions = ConditionalAnalysis::new(VHDLStandard::VHDL2019);
+        conditions.set("TOOL_TYPE", "SYNTHESIS");
+        let source = code.source();
+        let contents = source.contents();
+        let tokenizer = Tokenizer::new(&code.symbols, source, ContentReader::new(&contents));
+        let mut diagnostics = Vec::new();
+        let stream =
+            TokenStream::with_conditional_analysis(tokenizer, &conditions, &mut diagnostics);
+        let mut identifiers = Vec::new();
+        while let Some(token) = stream.peek() {
+            if let Value::Identifier(sym) = &token.value {
+                identifiers.push(sym.name_utf8());
+            }
+            stream.skip();
+        }
+        (identifiers, diagnostics)
+    }
+
+    #[test]
+    fn excludes_inactive_conditional_analysis_regions() {
+        let (identifiers, diagnostics) = active_identifiers(
+            "\
+`if TOOL_TYPE = \"SIMULATION\" `then
+  sim
+`elsif TOOL_TYPE = \"SYNTHESIS\" `then
+  synth
+  `if VHDL_VERSION < \"2019\" `then
+    before_2019 \" unterminated
+  `else
+    since_2019
+  `end if
+`elsif TOOL_TYPE /= \"SIMULATION\" `then
+  not_sim
+`else
+  other
+`end
+last",
+        );
+        assert_eq!(diagnostics, vec![]);
+        assert_eq!(identifiers, vec!["synth", "since_2019", "last"]);
+    }
+
+    #[test]
+    fn errors_in_conditional_analysis_directives() {
+        let (identifiers, diagnostics) = active_identifiers(
+            "\
+`if UNKNOWN = \"X\" `then
+  a
+`end
+`else
+`end
+`if TOOL_TYPE = \"SYNTHESIS\" `then
+  b",
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect_vec(),
+            vec![
+                "Unknown conditional analysis identifier 'UNKNOWN'",
+                "`else without `if",
+                "`end without `if",
+                "Missing `end of conditional analysis directive",
+            ]
+        );
+        assert_eq!(identifiers, vec!["b"]);
+    }
+
     #[test]
     fn pop_tokens() {
         let code = Code::new(
@@ -0,0 +1,1109 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! A minimal parser for the headers of Verilog and SystemVerilog modules.
+//!
+//! Mixed-language projects instantiate Verilog modules from VHDL.
+//! To resolve and check such instantiations, every module header is translated
+//! into an equivalent VHDL entity without architecture (a blackbox).
+//! Ports use the types of `ieee.std_logic_1164` and parameters become generics.
+//! The translated entity is parsed by the VHDL parser and all of its tokens are mapped
+//! back to the positions of the module header in the Verilog file.
+//!
+//! Only the module headers are parsed; the bodies are skipped except for port and
+//! parameter declarations of non-ANSI style modules.
+
+use super::parser::VHDLParser;
+use super::tokens::kind_str;
+use crate::ast::DesignFile;
+use crate::data::*;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::path::Path;
+
+/// Returns `true` for files that contain Verilog or SystemVerilog code
+pub(crate) fn is_verilog_file(file_name: &Path) -> bool {
+    file_name
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_ascii_lowercase().as_str(), "v" | "vh" | "sv" | "svh"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Identifier,
+    Number,
+    String,
+    Punctuation,
+}
+
+#[derive(Debug, Clone)]
+struct VerilogToken {
+    kind: TokenKind,
+    text: String,
+    range: Range,
+}
+
+impl VerilogToken {
+    fn is(&self, text: &str) -> bool {
+        self.kind != TokenKind::String && self.text == text
+    }
+}
+
+struct Lexer<'a> {
+    chars: Vec<char>,
+    idx: usize,
+    pos: Position,
+    source: &'a Source,
+}
+
+impl Lexer<'_> {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.idx + offset).copied()
+    }
+
+    fn pop(&mut self) -> Option<char> {
+        let chr = self.peek(0)?;
+        self.idx += 1;
+        self.pos.move_after_char(chr);
+        Some(chr)
+    }
+
+    fn pop_while(&mut self, text: &mut String, cond: impl Fn(char) -> bool) {
+        while let Some(chr) = self.peek(0) {
+            if !cond(chr) {
+                break;
+            }
+            text.push(chr);
+            self.pop();
+        }
+    }
+
+    fn skip_until(&mut self, end: &str) {
+        let end: Vec<char> = end.chars().collect();
+        while self.peek(0).is_some() {
+            if (0..end.len()).all(|i| self.peek(i) == Some(end[i])) {
+                for _ in 0..end.len() {
+                    self.pop();
+                }
+                return;
+            }
+            self.pop();
+        }
+    }
+
+    /// Compiler directives are skipped until the end of the line,
+    /// including lines that are continued with a backslash
+    fn skip_directive(&mut self) {
+        while let Some(chr) = self.pop() {
+            if chr == '\\' && self.peek(0) == Some('\n') {
+                self.pop();
+            } else if chr == '\n' {
+                return;
+            }
+        }
+    }
+
+    fn tokenize(mut self, diagnostics: &mut dyn DiagnosticHandler) -> Vec<VerilogToken> {
+        let mut tokens = Vec::new();
+        while let Some(chr) = self.peek(0) {
+            let start = self.pos;
+            let mut text = String::new();
+            let kind = match chr {
+                _ if chr.is_whitespace() => {
+                    self.pop();
+                    continue;
+                }
+                '/' if self.peek(1) == Some('/') => {
+                    self.skip_until("\n");
+                    continue;
+                }
+                '/' if self.peek(1) == Some('*') => {
+                    self.skip_until("*/");
+                    continue;
+                }
+                '(' if self.peek(1) == Some('*') && self.peek(2) != Some(')') => {
+                    self.skip_until("*)");
+                    continue;
+                }
+                '`' => {
+                    self.skip_directive();
+                    continue;
+                }
+                '"' => {
+                    self.pop();
+                    loop {
+                        match self.pop() {
+                            Some('\\') => {
+                                text.push('\\');
+                                text.extend(self.pop());
+                            }
+                            Some('"') => break,
+                            Some('\n') | None => {
+                                diagnostics.push(Diagnostic::syntax_error(
+                                    self.source.pos(start, self.pos),
+                                    "Unterminated string literal",
+                                ));
+                                break;
+                            }
+                            Some(chr) => text.push(chr),
+                        }
+                    }
+                    TokenKind::String
+                }
+                '\\' => {
+                    // Escaped identifiers end with white space
+                    self.pop();
+                    self.pop_while(&mut text, |chr| !chr.is_whitespace());
+                    TokenKind::Identifier
+                }
+                _ if chr.is_ascii_alphabetic() || chr == '_' || chr == '$' => {
+                    self.pop_while(&mut text, |chr| {
+                        chr.is_ascii_alphanumeric() || chr == '_' || chr == '$'
+                    });
+                    TokenKind::Identifier
+                }
+                _ if chr.is_ascii_digit() || (chr == '\'' && self.peek(1).is_some()) => {
+                    self.pop_while(&mut text, |chr| {
+                        chr.is_ascii_alphanumeric() || matches!(chr, '_' | '\'' | '.' | '?')
+                    });
+                    TokenKind::Number
+                }
+                _ => {
+                    text.push(chr);
+                    self.pop();
+                    let next = self.peek(0);
+                    if matches!(
+                        (chr, next),
+                        ('*', Some('*')) | ('<', Some('<')) | ('>', Some('>')) | (':', Some(':'))
+                    ) {
+                        text.extend(self.pop());
+                    }
+                    TokenKind::Punctuation
+                }
+            };
+            tokens.push(VerilogToken {
+                kind,
+                text,
+                range: Range::new(start, self.pos),
+            });
+        }
+        tokens
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    In,
+    Out,
+    InOut,
+}
+
+impl Direction {
+    fn from_keyword(keyword: &str) -> Option<Direction> {
+        Some(match keyword {
+            "input" => Direction::In,
+            "output" => Direction::Out,
+            "inout" | "ref" => Direction::InOut,
+            _ => return None,
+        })
+    }
+}
+
+/// The VHDL type that corresponds to a port or parameter
+#[derive(Debug, Clone)]
+enum DataType {
+    Scalar,
+    /// A packed range, i.e., `[msb:lsb]`.
+    /// The bounds are `None` when they cannot be translated to VHDL.
+    Vector(Option<(Vec<VerilogToken>, Vec<VerilogToken>)>),
+    Integer,
+    Real,
+    String,
+}
+
+#[derive(Debug, Clone)]
+struct Port {
+    name: VerilogToken,
+    direction: Direction,
+    data_type: DataType,
+}
+
+#[derive(Debug, Clone)]
+struct Parameter {
+    name: VerilogToken,
+    data_type: DataType,
+    default: Vec<VerilogToken>,
+}
+
+#[derive(Debug)]
+struct Module {
+    keyword: VerilogToken,
+    name: VerilogToken,
+    parameters: Vec<Parameter>,
+    ports: Vec<Port>,
+}
+
+/// Keywords that may be part of the data type of a port or parameter
+/// but do not influence the translated VHDL type
+const IGNORED_TYPE_KEYWORDS: &[&str] = &[
+    "wire",
+    "reg",
+    "logic",
+    "bit",
+    "var",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "wand",
+    "wor",
+    "uwire",
+    "supply0",
+    "supply1",
+    "signed",
+    "unsigned",
+    "byte",
+    "shortint",
+    "longint",
+    "time",
+    "realtime",
+    "shortreal",
+    "parameter",
+    "localparam",
+];
+
+struct Parser<'a> {
+    tokens: &'a [VerilogToken],
+    idx: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a VerilogToken> {
+        self.tokens.get(self.idx)
+    }
+
+    fn peek_is(&self, text: &str) -> bool {
+        self.peek().is_some_and(|token| token.is(text))
+    }
+
+    fn pop(&mut self) -> Option<&'a VerilogToken> {
+        let token = self.tokens.get(self.idx)?;
+        self.idx += 1;
+        Some(token)
+    }
+
+    fn skip_if(&mut self, text: &str) -> bool {
+        if self.peek_is(text) {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the tokens between a pair of matching brackets,
+    /// where the current token is the opening bracket
+    fn bracketed(&mut self, open: &str, close: &str) -> Option<&'a [VerilogToken]> {
+        if !self.skip_if(open) {
+            return None;
+        }
+        let start = self.idx;
+        let mut depth = 1;
+        while let Some(token) = self.pop() {
+            if token.is(open) {
+                depth += 1;
+            } else if token.is(close) {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&self.tokens[start..self.idx - 1]);
+                }
+            }
+        }
+        None
+    }
+
+    /// Skips to the token after the next `;` at the current nesting level
+    fn skip_statement(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.pop() {
+            if token.is("(") || token.is("[") || token.is("{") {
+                depth += 1;
+            } else if token.is(")") || token.is("]") || token.is("}") {
+                depth -= 1;
+            } else if token.is(";") && depth <= 0 {
+                return;
+            }
+        }
+    }
+
+    fn module(&mut self) -> Option<Module> {
+        let keyword = self.pop()?;
+        let name = self
+            .pop()
+            .filter(|token| token.kind == TokenKind::Identifier)?;
+        let mut module = Module {
+            keyword: keyword.clone(),
+            name: name.clone(),
+            parameters: Vec::new(),
+            ports: Vec::new(),
+        };
+        // Package imports in the header
+        while self.peek_is("import") {
+            self.skip_statement();
+        }
+        if self.skip_if("#") {
+            let items = self.bracketed("(", ")")?;
+            module.parameters = parameter_list(items);
+        }
+        let mut non_ansi_names = Vec::new();
+        if self.peek_is("(") {
+            let items = self.bracketed("(", ")")?;
+            if items.iter().any(|token| {
+                Direction::from_keyword(&token.text).is_some()
+                    && token.kind == TokenKind::Identifier
+            }) {
+                module.ports = ansi_port_list(items);
+            } else {
+                non_ansi_names = split_at_commas(items)
+                    .into_iter()
+                    .filter_map(|item| item.first().cloned())
+                    .filter(|token| token.kind == TokenKind::Identifier)
+                    .collect();
+            }
+        }
+        if !self.skip_if(";") {
+            return None;
+        }
+        self.module_items(&mut module, non_ansi_names);
+        Some(module)
+    }
+
+    /// Parses the port and parameter declarations in the body of a module
+    fn module_items(&mut self, module: &mut Module, non_ansi_names: Vec<VerilogToken>) {
+        let mut declared: FnvHashMap<String, Port> = FnvHashMap::default();
+        let has_parameter_list = !module.parameters.is_empty();
+        while let Some(token) = self.peek() {
+            if token.is("endmodule") {
+                self.pop();
+                break;
+            } else if Direction::from_keyword(&token.text).is_some() {
+                let start = self.idx;
+                self.skip_statement();
+                let end = self.idx.saturating_sub(1).max(start);
+                for port in ansi_port_list(&self.tokens[start..end]) {
+                    declared.insert(port.name.text.clone(), port);
+                }
+            } else if token.is("parameter") && !has_parameter_list {
+                let start = self.idx;
+                self.skip_statement();
+                let end = self.idx.saturating_sub(1).max(start);
+                module
+                    .parameters
+                    .extend(parameter_list(&self.tokens[start..end]));
+            } else if token.is("begin") || token.is("function") || token.is("task") {
+                // Declarations in nested scopes are not part of the interface
+                let end = match token.text.as_str() {
+                    "begin" => "end",
+                    "function" => "endfunction",
+                    _ => "endtask",
+                };
+                self.skip_block(&token.text, end);
+            } else {
+                self.pop();
+            }
+        }
+        for name in non_ansi_names {
+            let port = declared.remove(&name.text).unwrap_or(Port {
+                name: name.clone(),
+                direction: Direction::InOut,
+                data_type: DataType::Scalar,
+            });
+            module.ports.push(Port { name, ..port });
+        }
+    }
+
+    fn skip_block(&mut self, start: &str, end: &str) {
+        let mut depth = 0;
+        while let Some(token) = self.pop() {
+            if token.is(start) {
+                depth += 1;
+            } else if token.is(end) {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            } else if token.is("endmodule") {
+                self.idx -= 1;
+                return;
+            }
+        }
+    }
+}
+
+fn split_at_commas(tokens: &[VerilogToken]) -> Vec<&[VerilogToken]> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is("(") || token.is("[") || token.is("{") {
+            depth += 1;
+        } else if token.is(")") || token.is("]") || token.is("}") {
+            depth -= 1;
+        } else if token.is(",") && depth == 0 {
+            items.push(&tokens[start..i]);
+            start = i + 1;
+        }
+    }
+    if start < tokens.len() {
+        items.push(&tokens[start..]);
+    }
+    items
+}
+
+/// Parses the type keywords and packed range that precede a name.
+/// Returns `None` when no type information is given.
+fn data_type(parser: &mut Parser<'_>) -> Option<DataType> {
+    let mut result = None;
+    while let Some(token) = parser.peek() {
+        match token.text.as_str() {
+            "integer" | "int" => result = Some(DataType::Integer),
+            "real" => result = Some(DataType::Real),
+            "string" => result = Some(DataType::String),
+            "[" => {
+                let range = parser.bracketed("[", "]").unwrap_or_default();
+                // Only the first packed dimension is translated
+                if !matches!(result, Some(DataType::Vector(_))) {
+                    result = Some(DataType::Vector(packed_range(range)));
+                }
+                continue;
+            }
+            text if IGNORED_TYPE_KEYWORDS.contains(&text) => {
+                result = result.or(Some(DataType::Scalar));
+            }
+            _ => break,
+        }
+        parser.pop();
+    }
+    result
+}
+
+fn packed_range(tokens: &[VerilogToken]) -> Option<(Vec<VerilogToken>, Vec<VerilogToken>)> {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is("(") {
+            depth += 1;
+        } else if token.is(")") {
+            depth -= 1;
+        } else if token.is(":") && depth == 0 {
+            return Some((tokens[..i].to_vec(), tokens[i + 1..].to_vec()));
+        }
+    }
+    None
+}
+
+/// Parses ANSI style port declarations, e.g., `input wire [7:0] a, b, output c`.
+/// Ports without direction and type inherit these from the previous port.
+fn ansi_port_list(tokens: &[VerilogToken]) -> Vec<Port> {
+    let mut ports = Vec::new();
+    let mut direction = Direction::InOut;
+    let mut current_type = DataType::Scalar;
+    for item in split_at_commas(tokens) {
+        let mut parser = Parser {
+            tokens: item,
+            idx: 0,
+        };
+        let explicit_direction = parser
+            .peek()
+            .and_then(|token| Direction::from_keyword(&token.text));
+        if let Some(explicit_direction) = explicit_direction {
+            parser.pop();
+            direction = explicit_direction;
+            current_type = DataType::Scalar;
+        }
+        if let Some(data_type) = data_type(&mut parser) {
+            current_type = data_type;
+        }
+        // The name is preceded by a user-defined type, if any, and followed by
+        // unpacked dimensions or a default value
+        let rest = &item[parser.idx..];
+        let declarator = rest
+            .iter()
+            .position(|token| token.is("[") || token.is("="))
+            .map_or(rest, |end| &rest[..end]);
+        // Interface ports (`intf.modport name`) cannot be expressed in VHDL
+        if declarator.iter().any(|token| token.is(".")) {
+            continue;
+        }
+        let Some(name) = declarator.last() else {
+            continue;
+        };
+        if name.kind != TokenKind::Identifier {
+            continue;
+        }
+        if declarator.len() > 1 {
+            current_type = DataType::Scalar;
+        }
+        ports.push(Port {
+            name: name.clone(),
+            direction,
+            data_type: current_type.clone(),
+        });
+    }
+    ports
+}
+
+/// Parses parameter declarations, e.g., `parameter int W = 8, D = 2`.
+/// Type parameters are skipped since VHDL generic types are not supported by all tools.
+fn parameter_list(tokens: &[VerilogToken]) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    let mut current_type = None;
+    for item in split_at_commas(tokens) {
+        let mut parser = Parser {
+            tokens: item,
+            idx: 0,
+        };
+        if parser.peek_is("localparam") {
+            current_type = None;
+            continue;
+        }
+        if parser.skip_if("parameter") {
+            current_type = None;
+        }
+        if parser.peek_is("type") {
+            continue;
+        }
+        if let Some(data_type) = data_type(&mut parser) {
+            current_type = Some(data_type);
+        }
+        let Some(name) = parser.pop() else {
+            continue;
+        };
+        if name.kind != TokenKind::Identifier {
+            continue;
+        }
+        let default = if parser.skip_if("=") {
+            item[parser.idx..].to_vec()
+        } else {
+            Vec::new()
+        };
+        let data_type = match &current_type {
+            Some(DataType::Real) => DataType::Real,
+            Some(DataType::String) => DataType::String,
+            _ if default
+                .first()
+                .is_some_and(|token| token.kind == TokenKind::String) =>
+            {
+                DataType::String
+            }
+            _ if default.len() == 1 && verilog_real(&default[0].text).is_some() => DataType::Real,
+            _ => DataType::Integer,
+        };
+        parameters.push(Parameter {
+            name: name.clone(),
+            data_type,
+            default,
+        });
+    }
+    parameters
+}
+
+fn verilog_integer(text: &str) -> Option<i64> {
+    let text: String = text.chars().filter(|chr| *chr != '_').collect();
+    let Some((_, value)) = text.split_once('\'') else {
+        return text.parse().ok();
+    };
+    let value = value.trim_start_matches(['s', 'S']);
+    let mut chars = value.chars();
+    let radix = match chars.next()?.to_ascii_lowercase() {
+        'd' => 10,
+        'h' => 16,
+        'o' => 8,
+        'b' => 2,
+        _ => return None,
+    };
+    i64::from_str_radix(chars.as_str(), radix).ok()
+}
+
+fn verilog_real(text: &str) -> Option<f64> {
+    if text.contains('\'') || !(text.contains('.') || text.contains(['e', 'E'])) {
+        return None;
+    }
+    text.replace('_', "").parse().ok()
+}
+
+/// Generates the VHDL text of the blackbox entities and remembers where
+/// the names of the Verilog code were placed
+struct Generator<'a> {
+    keywords: FnvHashSet<&'static str>,
+    text: String,
+    pos: Position,
+    origins: FnvHashMap<Position, Range>,
+    parameters: FnvHashSet<&'a str>,
+}
+
+impl<'a> Generator<'a> {
+    fn push(&mut self, text: &str) {
+        for chr in text.chars() {
+            self.pos.move_after_char(chr);
+        }
+        self.text.push_str(text);
+    }
+
+    /// Pushes a Verilog identifier, using an extended identifier if it is not
+    /// a valid basic identifier in VHDL
+    fn push_name(&mut self, name: &VerilogToken) {
+        let text = &name.text;
+        let is_basic = text.starts_with(|chr: char| chr.is_ascii_alphabetic())
+            && text
+                .chars()
+                .all(|chr| chr.is_ascii_alphanumeric() || chr == '_')
+            && !text.ends_with('_')
+            && !text.contains("__")
+            && !self.keywords.contains(text.to_ascii_lowercase().as_str());
+        self.origins.insert(self.pos, name.range);
+        if is_basic {
+            self.push(text);
+        } else {
+            self.push(&format!("\\{}\\", text.replace('\\', "\\\\")));
+        }
+    }
+
+    /// Translates a constant expression that only consists of integers, parameters
+    /// and arithmetic operators. Returns `None` for all other expressions.
+    fn expression<'t>(
+        &self,
+        tokens: &'t [VerilogToken],
+    ) -> Option<Vec<(String, Option<&'t VerilogToken>)>> {
+        if tokens.is_empty() {
+            return None;
+        }
+        let mut result = Vec::new();
+        for token in tokens {
+            let piece = match token.kind {
+                TokenKind::Identifier if self.parameters.contains(token.text.as_str()) => {
+                    (String::new(), Some(token))
+                }
+                TokenKind::Number => (verilog_integer(&token.text)?.to_string(), None),
+                TokenKind::Punctuation => {
+                    let operator = match token.text.as_str() {
+                        "+" | "-" | "*" | "/" | "**" | "(" | ")" => token.text.clone(),
+                        "%" => " rem ".to_owned(),
+                        _ => return None,
+                    };
+                    (operator, None)
+                }
+                _ => return None,
+            };
+            result.push(piece);
+        }
+        Some(result)
+    }
+
+    fn push_expression(&mut self, pieces: Vec<(String, Option<&VerilogToken>)>) {
+        for (text, name) in pieces {
+            match name {
+                Some(name) => self.push_name(name),
+                None => self.push(&text),
+            }
+        }
+    }
+
+    fn push_type(&mut self, data_type: &DataType) {
+        match data_type {
+            DataType::Scalar => self.push("std_logic"),
+            DataType::Integer => self.push("integer"),
+            DataType::Real => self.push("real"),
+            DataType::String => self.push("string"),
+            DataType::Vector(range) => {
+                self.push("std_logic_vector");
+                let Some((left, right)) = range else {
+                    return;
+                };
+                let (Some(left_expr), Some(right_expr)) =
+                    (self.expression(left), self.expression(right))
+                else {
+                    return;
+                };
+                let direction = match (verilog_integer_expr(left), verilog_integer_expr(right)) {
+                    (Some(left), Some(right)) if left < right => " to ",
+                    _ => " downto ",
+                };
+                self.push("(");
+                self.push_expression(left_expr);
+                self.push(direction);
+                self.push_expression(right_expr);
+                self.push(")");
+            }
+        }
+    }
+
+    fn push_default(&mut self, parameter: &Parameter) {
+        self.push(" := ");
+        let default = &parameter.default;
+        match parameter.data_type {
+            DataType::String => match default.first() {
+                Some(token) if token.kind == TokenKind::String && default.len() == 1 => {
+                    self.push(&format!("\"{}\"", token.text.replace('"', "\"\"")))
+                }
+                _ => self.push("\"\""),
+            },
+            DataType::Real => match default.first().and_then(|token| verilog_real(&token.text)) {
+                Some(value) if default.len() == 1 => self.push(&format!("{value:?}")),
+                _ => self.push("0.0"),
+            },
+            _ => match self.expression(default) {
+                Some(pieces) => self.push_expression(pieces),
+                // Verilog parameters always have a default value,
+                // so a placeholder keeps instantiations without this generic legal
+                None => self.push("0"),
+            },
+        }
+    }
+
+    fn push_entity(&mut self, module: &'a Module) {
+        self.parameters = module
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.text.as_str())
+            .collect();
+        self.push("library ieee;\nuse ieee.std_logic_1164.all;\nentity ");
+        self.push_name(&module.name);
+        self.push(" is\n");
+        if !module.parameters.is_empty() {
+            self.push("generic (\n");
+            for (i, parameter) in module.parameters.iter().enumerate() {
+                if i > 0 {
+                    self.push(";\n");
+                }
+                self.push_name(&parameter.name);
+                self.push(" : ");
+                self.push_type(&parameter.data_type);
+                self.push_default(parameter);
+            }
+            self.push("\n);\n");
+        }
+        if !module.ports.is_empty() {
+            self.push("port (\n");
+            for (i, port) in module.ports.iter().enumerate() {
+                if i > 0 {
+                    self.push(";\n");
+                }
+                self.push_name(&port.name);
+                self.push(match port.direction {
+                    Direction::In => " : in ",
+                    Direction::Out => " : out ",
+                    Direction::InOut => " : inout ",
+                });
+                self.push_type(&port.data_type);
+            }
+            self.push("\n);\n");
+        }
+        self.push("end entity;\n");
+    }
+}
+
+/// Evaluates an expression that consists of a single integer literal
+fn verilog_integer_expr(tokens: &[VerilogToken]) -> Option<i64> {
+    match tokens {
+        [token] if token.kind == TokenKind::Number => verilog_integer(&token.text),
+        [sign, token] if sign.is("-") && token.kind == TokenKind::Number => {
+            verilog_integer(&token.text).map(|value| -value)
+        }
+        _ => None,
+    }
+}
+
+fn parse_modules(source: &Source, diagnostics: &mut dyn DiagnosticHandler) -> Vec<Module> {
+    let chars: Vec<char> = {
+        let contents = source.contents();
+        (0..contents.num_lines())
+            .filter_map(|lineno| contents.get_line(lineno))
+            .flat_map(|line| line.chars())
+            .collect()
+    };
+    let tokens = Lexer {
+        chars,
+        idx: 0,
+        pos: Position::new(0, 0),
+        source,
+    }
+    .tokenize(diagnostics);
+
+    let mut parser = Parser {
+        tokens: &tokens,
+        idx: 0,
+    };
+    let mut modules = Vec::new();
+    while let Some(token) = parser.peek() {
+        if token.is("module") || token.is("macromodule") {
+            let start = parser.idx;
+            match parser.module() {
+                Some(module) => modules.push(module),
+                None => {
+                    diagnostics.push(Diagnostic::syntax_error(
+                        source.pos(token.range.start, token.range.end),
+                        "Could not parse the header of this module",
+                    ));
+                    parser.idx = start + 1;
+                }
+            }
+        } else {
+            parser.pop();
+        }
+    }
+    modules
+}
+
+/// Parses the module headers of a Verilog file into VHDL entities
+pub(crate) fn parse_verilog_source(
+    parser: &VHDLParser,
+    source: &Source,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> DesignFile {
+    let modules = parse_modules(source, diagnostics);
+    let mut design_file = DesignFile::default();
+    for module in modules.iter() {
+        let mut generator = Generator {
+            keywords: parser
+                .standard
+                .keywords()
+                .iter()
+                .map(|kind| kind_str(*kind))
+                .collect(),
+            text: String::new(),
+            pos: Position::new(0, 0),
+            origins: FnvHashMap::default(),
+            parameters: FnvHashSet::default(),
+        };
+        generator.push_entity(module);
+        let generated = Source::inline(source.file_name(), &generator.text);
+        // The generated code is known to be valid, any errors are due to unsupported constructs
+        let mut ignored = Vec::new();
+        let entity = parser.parse_vhdl_source(&generated, &mut ignored);
+        let fallback = Range::new(module.keyword.range.start, module.keyword.range.start);
+        for (mut tokens, unit) in entity.design_units {
+            for token in tokens.iter_mut() {
+                let range = generator
+                    .origins
+                    .get(&token.pos.start())
+                    .copied()
+                    .unwrap_or(fallback);
+                token.pos = SrcPos::new(source.clone(), range);
+            }
+            design_file.design_units.push((tokens, unit));
+        }
+    }
+    design_file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::{check_diagnostics, check_no_diagnostics, LibraryBuilder};
+    use crate::VHDLStandard;
+
+    fn generated_vhdl(code: &str) -> String {
+        let source = Source::inline(Path::new("module.v"), code);
+        let mut diagnostics = Vec::new();
+        let modules = parse_modules(&source, &mut diagnostics);
+        assert_eq!(diagnostics, vec![]);
+        let mut text = String::new();
+        for module in modules.iter() {
+            let mut generator = Generator {
+                keywords: VHDLStandard::default()
+                    .keywords()
+                    .iter()
+                    .map(|kind| kind_str(*kind))
+                    .collect(),
+                text: String::new(),
+                pos: Position::new(0, 0),
+                origins: FnvHashMap::default(),
+                parameters: FnvHashSet::default(),
+            };
+            generator.push_entity(module);
+            text.push_str(&generator.text);
+        }
+        text
+    }
+
+    #[test]
+    fn detects_verilog_files() {
+        assert!(is_verilog_file(Path::new("dir/counter.v")));
+        assert!(is_verilog_file(Path::new("dir/counter.SV")));
+        assert!(!is_verilog_file(Path::new("dir/counter.vhd")));
+    }
+
+    #[test]
+    fn translates_ansi_module_header() {
+        assert_eq!(
+            generated_vhdl(
+                "\
+`timescale 1ns/1ps
+// A counter
+module counter #(
+  parameter int WIDTH = 8,
+  parameter real FREQ = 1.5e6,
+  parameter NAME = \"cnt\"
+) (
+  input wire clk,
+  input [WIDTH-1:0] load_value, init,
+  output logic [0:3] \\out ,
+  inout type_t bus
+);
+  assign \\out = 0;
+endmodule
+"
+            ),
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+entity counter is
+generic (
+WIDTH : integer := 8;
+FREQ : real := 1500000.0;
+NAME : string := \"cnt\"
+);
+port (
+clk : in std_logic;
+load_value : in std_logic_vector(WIDTH-1 downto 0);
+init : in std_logic_vector(WIDTH-1 downto 0);
+\\out\\ : out std_logic_vector(0 to 3);
+\\bus\\ : inout std_logic
+);
+end entity;
+"
+        );
+    }
+
+    #[test]
+    fn translates_non_ansi_module_header() {
+        assert_eq!(
+            generated_vhdl(
+                "\
+module adder(a, b, sum);
+  parameter W = 4'd4;
+  input [W-1:0] a, b;
+  output reg [$clog2(W):0] sum;
+  localparam X = 2;
+  always @(*) begin
+    sum = a + b;
+  end
+endmodule
+
+module empty;
+endmodule
+"
+            ),
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+entity adder is
+generic (
+W : integer := 4
+);
+port (
+a : in std_logic_vector(W-1 downto 0);
+b : in std_logic_vector(W-1 downto 0);
+sum : out std_logic_vector
+);
+end entity;
+library ieee;
+use ieee.std_logic_1164.all;
+entity empty is
+end entity;
+"
+        );
+    }
+
+    #[test]
+    fn reports_unparsable_module_header() {
+        let source = Source::inline(Path::new("module.v"), "module (a, b);\nendmodule\n");
+        let mut diagnostics = Vec::new();
+        let modules = parse_modules(&source, &mut diagnostics);
+        assert!(modules.is_empty());
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::syntax_error(
+                source.pos(Position::new(0, 0), Position::new(0, 6)),
+                "Could not parse the header of this module"
+            )]
+        );
+    }
+
+    #[test]
+    fn vhdl_instantiates_verilog_module() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        let verilog = builder.code_with_file_name(
+            "libname",
+            Path::new("counter.v"),
+            "\
+module counter #(parameter WIDTH = 8) (
+  input clk,
+  output [WIDTH-1:0] count
+);
+endmodule
+",
+        );
+        let code = builder.code(
+            "libname",
+            "\
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk : std_logic;
+  signal count : std_logic_vector(3 downto 0);
+begin
+  inst: entity work.counter
+    generic map (WIDTH => 4)
+    port map (clk => clk, count => count, missing => open);
+end architecture;
+",
+        );
+        let (root, diagnostics) = builder.get_analyzed_root();
+        check_diagnostics(
+            diagnostics,
+            vec![Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of 'missing'",
+                crate::data::error_codes::ErrorCode::Unresolved,
+            )],
+        );
+
+        let ent = root
+            .search_reference(code.source(), code.s1("counter").start())
+            .unwrap();
+        assert_eq!(ent.decl_pos(), Some(&verilog.s1("counter").pos()));
+        let port = root
+            .search_reference(code.source(), code.s1("count =>").start())
+            .unwrap();
+        assert_eq!(port.decl_pos(), Some(&verilog.s("count", 2).pos()));
+    }
+
+    #[test]
+    fn verilog_parameters_may_refer_to_other_parameters() {
+        let mut builder = LibraryBuilder::new();
+        builder.add_std_logic_1164();
+        builder.code_with_file_name(
+            "libname",
+            Path::new("fifo.sv"),
+            "\
+module fifo #(parameter int DEPTH = 16, parameter int AW = DEPTH / 4) (
+  input logic [AW-1:0] addr
+);
+endmodule
+",
+        );
+        let (_, diagnostics) = builder.get_analyzed_root();
+        check_no_diagnostics(&diagnostics);
+    }
+}
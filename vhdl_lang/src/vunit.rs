@@ -0,0 +1,320 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2024, Olof Kraigher olof.kraigher@gmail.com
+
+//! Discovers VUnit test cases in the analyzed design
+//! and interprets the output of the VUnit test runner.
+
+use crate::analysis::DesignRoot;
+use crate::ast::*;
+use crate::data::Symbol;
+use crate::syntax::{Kind, Value};
+use crate::SrcPos;
+use std::ops::Deref;
+use std::path::PathBuf;
+
+/// The generic that identifies an entity as a VUnit testbench
+const RUNNER_CFG: &str = "runner_cfg";
+
+/// A test case of a VUnit testbench, i.e., a `run("name")` call
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VUnitTestCase {
+    pub library: String,
+    pub testbench: String,
+    pub name: String,
+    /// The position of the name of the test case
+    pub pos: SrcPos,
+}
+
+impl VUnitTestCase {
+    /// The name used to select the test case when invoking the VUnit runner
+    pub fn full_name(&self) -> String {
+        format!("{}.{}.{}", self.library, self.testbench, self.name)
+    }
+}
+
+/// A test case that failed when running the VUnit runner
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VUnitFailure {
+    /// The full name of the test case
+    pub test: String,
+    pub message: String,
+    /// The file and zero-based line where the failure was reported.
+    /// When the output does not reference any VHDL file,
+    /// this is the position of the test case.
+    pub location: Option<(PathBuf, u32)>,
+}
+
+fn has_runner_cfg(entity: &EntityDeclaration) -> bool {
+    entity
+        .generic_clause
+        .iter()
+        .flat_map(|list| list.items.iter())
+        .any(|item| match item {
+            InterfaceDeclaration::Object(object) => object
+                .idents
+                .iter()
+                .any(|ident| ident.tree.item.name_utf8() == RUNNER_CFG),
+            _ => false,
+        })
+}
+
+/// Finds all `run("name")` calls in the tokens of an architecture
+fn run_calls(tokens: &[crate::Token], run: &Symbol) -> Vec<(String, SrcPos)> {
+    tokens
+        .windows(4)
+        .filter_map(|window| match window {
+            [name, left, string, right]
+                if name.kind == Kind::Identifier
+                    && left.kind == Kind::LeftPar
+                    && string.kind == Kind::StringLiteral
+                    && right.kind == Kind::RightPar =>
+            {
+                let Value::Identifier(symbol) = &name.value else {
+                    return None;
+                };
+                let Value::String(value) = &string.value else {
+                    return None;
+                };
+                (symbol == run).then(|| (value.to_string(), string.pos.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the test cases of all entities with a `runner_cfg` generic.
+/// The test cases are found from the `run("name")` calls of their architectures.
+pub fn vunit_test_cases(root: &DesignRoot) -> Vec<VUnitTestCase> {
+    let run = root.symbol_utf8("run");
+    let mut result = Vec::new();
+    for library in root.libraries() {
+        for unit in library.primary_units() {
+            let Some(data) = unit.unit.get() else {
+                continue;
+            };
+            let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) = data.deref() else {
+                continue;
+            };
+            if !has_runner_cfg(entity) {
+                continue;
+            }
+            for architecture in library.secondary_units(&entity.ident.tree.item) {
+                for (name, pos) in run_calls(&architecture.tokens, &run) {
+                    result.push(VUnitTestCase {
+                        library: library.name().name_utf8(),
+                        testbench: entity.ident.tree.item.name_utf8(),
+                        name,
+                        pos,
+                    });
+                }
+            }
+        }
+    }
+    result.sort_by(|lhs, rhs| lhs.pos.cmp(&rhs.pos));
+    result
+}
+
+/// Returns the file and zero-based line of a reference such as `tb_foo.vhd:42`
+fn source_reference(line: &str) -> Option<(PathBuf, u32)> {
+    line.split(|chr: char| chr.is_whitespace() || "()[],'\"".contains(chr))
+        .find_map(|word| {
+            let (file_name, lineno) = word.rsplit_once(':')?;
+            let lineno: u32 = lineno.parse().ok()?;
+            let extension = file_name.rsplit_once('.')?.1.to_lowercase();
+            if lineno == 0 || !matches!(extension.as_str(), "vhd" | "vhdl") {
+                return None;
+            }
+            Some((PathBuf::from(file_name), lineno - 1))
+        })
+}
+
+/// Returns the failed test cases from the output of the VUnit runner.
+/// The output of a test case is printed between `Starting <test>` and `fail ... <test>`.
+/// Failures are mapped to the first VHDL source reference in the output of the test case.
+pub fn parse_vunit_output(output: &str, test_cases: &[VUnitTestCase]) -> Vec<VUnitFailure> {
+    let mut failures = Vec::new();
+    let mut test_output: Vec<&str> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("Starting ") {
+            test_output.clear();
+            continue;
+        }
+        let Some(status) = line.strip_prefix("fail (") else {
+            test_output.push(line);
+            continue;
+        };
+        // The name of a test case may contain spaces and is followed by the duration
+        let Some(test) = status.split_once(')').map(|(_, rest)| {
+            let rest = rest.trim();
+            rest.rsplit_once(" (").map_or(rest, |(test, _)| test)
+        }) else {
+            continue;
+        };
+        let location = test_output
+            .iter()
+            .find_map(|line| source_reference(line))
+            .or_else(|| {
+                test_cases
+                    .iter()
+                    .find(|test_case| test_case.full_name().eq_ignore_ascii_case(test))
+                    .map(|test_case| {
+                        (
+                            test_case.pos.file_name().to_owned(),
+                            test_case.pos.start().line,
+                        )
+                    })
+            });
+        let message = test_output
+            .iter()
+            .find(|line| {
+                let line = line.to_lowercase();
+                line.contains("error") || line.contains("failure")
+            })
+            .map_or_else(|| format!("Test '{test}' failed"), |line| line.to_string());
+        failures.push(VUnitFailure {
+            test: test.to_owned(),
+            message,
+            location,
+        });
+        test_output.clear();
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::tests::LibraryBuilder;
+    use std::path::Path;
+
+    #[test]
+    fn finds_test_cases_of_testbenches() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity tb_counter is
+  generic (runner_cfg : string);
+end entity;
+
+architecture tb of tb_counter is
+  function run(name : string) return boolean is
+  begin
+    return true;
+  end function;
+begin
+  main: process
+  begin
+    if run(\"counts up\") then
+      null;
+    elsif RUN(\"wraps around\") then
+      null;
+    end if;
+    wait;
+  end process;
+end architecture;
+
+entity not_a_testbench is
+end entity;
+
+architecture a of not_a_testbench is
+  function run(name : string) return boolean is
+  begin
+    return true;
+  end function;
+  constant c : boolean := run(\"ignored\");
+begin
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+
+        let test_cases = vunit_test_cases(&root);
+        assert_eq!(
+            test_cases,
+            vec![
+                VUnitTestCase {
+                    library: "libname".to_owned(),
+                    testbench: "tb_counter".to_owned(),
+                    name: "counts up".to_owned(),
+                    pos: code.s1("\"counts up\"").pos(),
+                },
+                VUnitTestCase {
+                    library: "libname".to_owned(),
+                    testbench: "tb_counter".to_owned(),
+                    name: "wraps around".to_owned(),
+                    pos: code.s1("\"wraps around\"").pos(),
+                },
+            ]
+        );
+        assert_eq!(
+            test_cases[0].full_name(),
+            "libname.tb_counter.counts up".to_owned()
+        );
+    }
+
+    #[test]
+    fn maps_failures_to_source_positions() {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "\
+entity tb is
+  generic (runner_cfg : string);
+end entity;
+
+architecture a of tb is
+  function run(name : string) return boolean is
+  begin
+    return true;
+  end function;
+begin
+  assert run(\"first\") and run(\"second\") and run(\"third\");
+end architecture;
+",
+        );
+        let (root, _) = builder.get_analyzed_root();
+        let test_cases = vunit_test_cases(&root);
+
+        let output = "\
+Starting libname.tb.first
+Output file: vunit_out/test_output/libname.tb.first_1/output.txt
+pass (P=1 S=0 F=0 T=3) libname.tb.first (0.5 s)
+
+Starting libname.tb.second
+Output file: vunit_out/test_output/libname.tb.second_2/output.txt
+      1000 ps - check - ERROR - Equality check failed - Got 1. Expected 2. (/work/tb.vhd:42)
+fail (P=1 S=0 F=1 T=3) libname.tb.second (0.5 s)
+
+Starting libname.tb.third
+Output file: vunit_out/test_output/libname.tb.third_3/output.txt
+fail (P=1 S=0 F=2 T=3) libname.tb.third (0.5 s)
+
+==== Summary ====
+pass libname.tb.first  (0.5 s)
+fail libname.tb.second (0.5 s)
+";
+        assert_eq!(
+            parse_vunit_output(output, &test_cases),
+            vec![
+                VUnitFailure {
+                    test: "libname.tb.second".to_owned(),
+                    message: "1000 ps - check - ERROR - Equality check failed - Got 1. Expected 2. (/work/tb.vhd:42)".to_owned(),
+                    location: Some((Path::new("/work/tb.vhd").to_owned(), 41)),
+                },
+                VUnitFailure {
+                    test: "libname.tb.third".to_owned(),
+                    message: "Test 'libname.tb.third' failed".to_owned(),
+                    location: Some((
+                        code.source().file_name().to_owned(),
+                        code.s1("\"third\"").start().line
+                    )),
+                },
+            ]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use predicates::prelude::*;
 use std::error::Error;
 use std::path::PathBuf;
 use std::process::Command;
-use vhdl_lang::{Config, MessagePrinter, Project, Severity};
+use vhdl_lang::{Config, MessagePrinter, Project, Severity, SeverityMap};
 
 #[test]
 pub fn parses_example_project_without_errors() {
@@ -44,6 +44,89 @@ pub fn parses_example_project_without_errors() {
     }
 }
 
+/// The standard libraries and a library `lib` with the given file when analyzing VHDL-1993
+fn vhdl_1993_project(
+    libraries_are_third_party: bool,
+    file_name: &str,
+    contents: &str,
+) -> (Project, SeverityMap) {
+    let mut msg_printer = MessagePrinter::default();
+
+    let vhdl_libraries_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../vhdl_libraries");
+    let mut vhdl_libraries_config =
+        std::fs::read_to_string(vhdl_libraries_path.join("vhdl_ls.toml")).unwrap();
+    if !libraries_are_third_party {
+        vhdl_libraries_config =
+            vhdl_libraries_config.replace("is_third_party = true", "is_third_party = false");
+    }
+    let mut config = Config::from_str(&vhdl_libraries_config, &vhdl_libraries_path).unwrap();
+
+    let root = tempfile::tempdir().unwrap();
+    std::fs::write(root.path().join(file_name), contents).unwrap();
+    config.append(
+        &Config::from_str(
+            &format!("standard = '1993'\n[libraries]\nlib.files = ['{file_name}']\n"),
+            root.path(),
+        )
+        .unwrap(),
+        &mut msg_printer,
+    );
+
+    let severity_map = *config.severities();
+    (Project::from_config(config, &mut msg_printer), severity_map)
+}
+
+#[test]
+pub fn vhdl_1993_libraries_are_analyzed_without_errors() {
+    let (mut project, severity_map) = vhdl_1993_project(false, "empty.vhd", "");
+
+    let diagnostics = project.analyse();
+    let diagnostics_with_errors = diagnostics
+        .iter()
+        .filter(|diag| severity_map[diag.code] == Some(Severity::Error))
+        .collect_vec();
+    if !diagnostics_with_errors.is_empty() {
+        for diagnostic in diagnostics_with_errors {
+            println!("{}", diagnostic.show(&severity_map).unwrap())
+        }
+        panic!("Found diagnostics with severity error in the VHDL-1993 libraries");
+    }
+}
+
+#[test]
+pub fn vhdl_1993_std_logic_vector_is_distinct_from_std_ulogic_vector() {
+    let (mut project, _) = vhdl_1993_project(
+        true,
+        "ent.vhd",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal slv : std_logic_vector(7 downto 0);
+  signal sulv : std_ulogic_vector(7 downto 0);
+begin
+  slv <= sulv;
+end architecture;
+",
+    );
+
+    let messages = project
+        .analyse()
+        .into_iter()
+        .map(|diag| diag.message)
+        .collect_vec();
+    assert_eq!(
+        messages,
+        vec![
+            "signal 'sulv' of array type 'STD_ULOGIC_VECTOR' does not match array type 'STD_LOGIC_VECTOR'"
+        ]
+    );
+}
+
 #[test]
 fn unused_function_gets_detected() -> Result<(), Box<dyn Error>> {
     let mut cmd = Command::cargo_bin("vhdl_lang")?;
@@ -58,3 +141,44 @@ fn unused_function_gets_detected() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(unix)]
+fn elaboration_errors_are_mapped_to_the_source() -> Result<(), Box<dyn Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = tempfile::tempdir()?;
+    let vhdl_file = root.path().join("top.vhd");
+    std::fs::write(&vhdl_file, "entity top is\nend entity;\n")?;
+    std::fs::write(
+        root.path().join("vhdl_ls.toml"),
+        "[libraries]\nlib.files = ['top.vhd']\n",
+    )?;
+    // Emulates GHDL failing to elaborate the top unit
+    let ghdl = root.path().join("ghdl");
+    std::fs::write(
+        &ghdl,
+        format!(
+            "#!/bin/sh\nif [ \"$1\" = \"-m\" ]; then\n  echo '{}:1:8:error: cannot elaborate top' >&2\n  exit 1\nfi\n",
+            vhdl_file.to_string_lossy()
+        ),
+    )?;
+    std::fs::set_permissions(&ghdl, std::fs::Permissions::from_mode(0o755))?;
+
+    let mut cmd = Command::cargo_bin("vhdl_lang")?;
+    cmd.arg("--config")
+        .arg(root.path().join("vhdl_ls.toml"))
+        .arg("--libraries")
+        .arg("../vhdl_libraries/vhdl_ls.toml")
+        .arg("--elaborate")
+        .arg("lib.top")
+        .arg("--ghdl")
+        .arg(&ghdl);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("error: cannot elaborate top"))
+        .stdout(predicate::str::contains("top.vhd:1"))
+        .stdout(predicate::str::contains("Elaboration of 'lib.top' failed"));
+
+    Ok(())
+}
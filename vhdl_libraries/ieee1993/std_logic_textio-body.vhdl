@@ -0,0 +1,681 @@
+-- -----------------------------------------------------------------
+-- 
+-- Copyright 2019 IEEE P1076 WG Authors
+-- 
+-- See the LICENSE file distributed with this work for copyright and
+-- licensing information and the AUTHORS file.
+-- 
+-- This file to you under the Apache License, Version 2.0 (the "License").
+-- You may obtain a copy of the License at
+-- 
+--     http://www.apache.org/licenses/LICENSE-2.0
+-- 
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or
+-- implied.  See the License for the specific language governing
+-- permissions and limitations under the License.
+--
+--   Title     :  Standard multivalue logic package
+--             :  (STD_LOGIC_TEXTIO package body, VHDL-93 edition)
+--             :
+--   Library   :  This package shall be compiled into a library
+--             :  symbolically named IEEE.
+--             :
+--   Developers:  IEEE model standards group (PAR 1164),
+--             :  Accellera VHDL-TC, and IEEE P1076 Working Group
+--             :
+--   Purpose   :  This package overloads the procedures READ and WRITE
+--             :  of package TEXTIO for the types of package
+--             :  STD_LOGIC_1164, and defines the procedures HREAD,
+--             :  HWRITE, OREAD and OWRITE for hexadecimal and octal
+--             :  values.
+--             :
+--   Edition   :  This is the version of the package used with
+--             :  IEEE Std 1164-1993, derived from the text I/O
+--             :  procedures of the VHDL-2008 STD_LOGIC_1164 sources for
+--             :  analysis with VHDL-93. In VHDL-2008, these procedures
+--             :  are part of package STD_LOGIC_1164.
+--             :
+-- --------------------------------------------------------------------
+
+use STD.TEXTIO.all;
+
+library IEEE;
+use IEEE.STD_LOGIC_1164.all;
+
+package body STD_LOGIC_TEXTIO is
+
+  -------------------------------------------------------------------
+  -- string conversion
+  -------------------------------------------------------------------
+
+  function TO_OSTRING (value : STD_ULOGIC_VECTOR) return STRING is
+    constant result_length : NATURAL := (value'length+2)/3;
+    variable pad           : STD_ULOGIC_VECTOR(1 to result_length*3 - value'length);
+    variable padded_value  : STD_ULOGIC_VECTOR(1 to result_length*3);
+    variable result        : STRING(1 to result_length);
+    variable tri           : STD_ULOGIC_VECTOR(1 to 3);
+  begin
+    if value (value'left) = 'Z' then
+      pad := (others => 'Z');
+    else
+      pad := (others => '0');
+    end if;
+    padded_value := pad & value;
+    for i in 1 to result_length loop
+      tri := To_X01Z(padded_value(3*i-2 to 3*i));
+      case tri is
+        when o"0"   => result(i) := '0';
+        when o"1"   => result(i) := '1';
+        when o"2"   => result(i) := '2';
+        when o"3"   => result(i) := '3';
+        when o"4"   => result(i) := '4';
+        when o"5"   => result(i) := '5';
+        when o"6"   => result(i) := '6';
+        when o"7"   => result(i) := '7';
+        when "ZZZ"  => result(i) := 'Z';
+        when others => result(i) := 'X';
+      end case;
+    end loop;
+    return result;
+  end function TO_OSTRING;
+
+  function TO_HSTRING (value : STD_ULOGIC_VECTOR) return STRING is
+    constant result_length : NATURAL := (value'length+3)/4;
+    variable pad           : STD_ULOGIC_VECTOR(1 to result_length*4 - value'length);
+    variable padded_value  : STD_ULOGIC_VECTOR(1 to result_length*4);
+    variable result        : STRING(1 to result_length);
+    variable quad          : STD_ULOGIC_VECTOR(1 to 4);
+  begin
+    if value (value'left) = 'Z' then
+      pad := (others => 'Z');
+    else
+      pad := (others => '0');
+    end if;
+    padded_value := pad & value;
+    for i in 1 to result_length loop
+      quad := To_X01Z(padded_value(4*i-3 to 4*i));
+      case quad is
+        when x"0"   => result(i) := '0';
+        when x"1"   => result(i) := '1';
+        when x"2"   => result(i) := '2';
+        when x"3"   => result(i) := '3';
+        when x"4"   => result(i) := '4';
+        when x"5"   => result(i) := '5';
+        when x"6"   => result(i) := '6';
+        when x"7"   => result(i) := '7';
+        when x"8"   => result(i) := '8';
+        when x"9"   => result(i) := '9';
+        when x"A"   => result(i) := 'A';
+        when x"B"   => result(i) := 'B';
+        when x"C"   => result(i) := 'C';
+        when x"D"   => result(i) := 'D';
+        when x"E"   => result(i) := 'E';
+        when x"F"   => result(i) := 'F';
+        when "ZZZZ" => result(i) := 'Z';
+        when others => result(i) := 'X';
+      end case;
+    end loop;
+    return result;
+  end function TO_HSTRING;
+
+  -- In VHDL-93, the logical operators are not predefined as unary operators
+  function or_reduce (arg : STD_ULOGIC_VECTOR) return STD_ULOGIC is
+    variable result : STD_ULOGIC := '0';
+  begin
+    for i in arg'range loop
+      result := result or arg(i);
+    end loop;
+    return result;
+  end function or_reduce;
+
+  -- Type and constant definitions used to map STD_ULOGIC values
+  -- into/from character values.
+  type MVL9plus is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-', error);
+  type char_indexed_by_MVL9 is array (STD_ULOGIC) of CHARACTER;
+  type MVL9_indexed_by_char is array (CHARACTER) of STD_ULOGIC;
+  type MVL9plus_indexed_by_char is array (CHARACTER) of MVL9plus;
+  constant MVL9_to_char : char_indexed_by_MVL9 := "UX01ZWLH-";
+  constant char_to_MVL9 : MVL9_indexed_by_char :=
+    ('U' => 'U', 'X' => 'X', '0' => '0', '1' => '1', 'Z' => 'Z',
+     'W' => 'W', 'L' => 'L', 'H' => 'H', '-' => '-', others => 'U');
+  constant char_to_MVL9plus : MVL9plus_indexed_by_char :=
+    ('U' => 'U', 'X' => 'X', '0' => '0', '1' => '1', 'Z' => 'Z',
+     'W' => 'W', 'L' => 'L', 'H' => 'H', '-' => '-', others => error);
+
+  constant NBSP : CHARACTER := CHARACTER'val(160);  -- space character
+
+  -- purpose: Skips white space
+  procedure skip_whitespace (
+    L : inout LINE) is
+    variable c : CHARACTER;
+    variable left : positive;
+  begin
+    while L /= null and L.all'length /= 0 loop
+      left := L.all'left;
+      c := L.all(left);
+      if (c = ' ' or c = NBSP or c = HT) then
+        read (L, c);
+      else
+        exit;
+      end if;
+    end loop;
+  end procedure skip_whitespace;
+
+  procedure READ (L    : inout LINE; VALUE : out STD_ULOGIC;
+                  GOOD : out   BOOLEAN) is
+    variable c      : CHARACTER;
+    variable readOk : BOOLEAN;
+  begin
+    VALUE := 'U';                       -- initialize to a "U"
+    skip_whitespace (L);
+    read (L, c, readOk);
+    if not readOk then
+      GOOD := false;
+    else
+      if char_to_MVL9plus(c) = error then
+        GOOD := false;
+      else
+        VALUE := char_to_MVL9(c);
+        GOOD  := true;
+      end if;
+    end if;
+  end procedure READ;
+
+  procedure READ (L    : inout LINE; VALUE : out STD_ULOGIC_VECTOR;
+                  GOOD : out   BOOLEAN) is
+    variable c      : CHARACTER;
+    variable mv     : STD_ULOGIC_VECTOR(0 to VALUE'length-1);
+    variable readOk : BOOLEAN;
+    variable i      : INTEGER;
+    variable lastu  : BOOLEAN := false;       -- last character was an "_"
+  begin
+    VALUE := (VALUE'range => 'U'); -- initialize to a "U"
+    skip_whitespace (L);
+    if VALUE'length > 0 then
+      read (L, c, readOk);
+      i := 0;
+      GOOD := true;
+      while i < VALUE'length loop
+        if not readOk then     -- Bail out if there was a bad read
+          GOOD := false;
+          return;
+        elsif c = '_' then
+          if i = 0 then
+            GOOD := false;                -- Begins with an "_"
+            return;
+          elsif lastu then
+            GOOD := false;                -- "__" detected
+            return;
+          else
+            lastu := true;
+          end if;
+        elsif (char_to_MVL9plus(c) = error) then
+          GOOD := false;                  -- Illegal character
+          return;
+        else
+          mv(i) := char_to_MVL9(c);
+          i := i + 1;
+          if i > mv'high then             -- reading done
+            VALUE := mv;
+            return;
+          end if;
+          lastu := false;
+        end if;
+        read(L, c, readOk);
+      end loop;
+    else
+      GOOD := true;                   -- read into a null array
+    end if;
+  end procedure READ;
+
+  procedure READ (L : inout LINE; VALUE : out STD_ULOGIC) is
+    variable c      : CHARACTER;
+    variable readOk : BOOLEAN;
+  begin
+    VALUE := 'U';                       -- initialize to a "U"
+    skip_whitespace (L);
+    read (L, c, readOk);
+    if not readOk then
+      report "STD_LOGIC_TEXTIO.READ(STD_ULOGIC) "
+        & "End of string encountered"
+        severity error;
+      return;
+    elsif char_to_MVL9plus(c) = error then
+      report
+        "STD_LOGIC_TEXTIO.READ(STD_ULOGIC) Error: Character '" &
+        c & "' read, expected STD_ULOGIC literal."
+        severity error;
+    else
+      VALUE := char_to_MVL9(c);
+    end if;
+  end procedure READ;
+
+  procedure READ (L : inout LINE; VALUE : out STD_ULOGIC_VECTOR) is
+    variable c      : CHARACTER;
+    variable readOk : BOOLEAN;
+    variable mv     : STD_ULOGIC_VECTOR(0 to VALUE'length-1);
+    variable i      : INTEGER;
+    variable lastu  : BOOLEAN := false;       -- last character was an "_"
+  begin
+    VALUE := (VALUE'range => 'U'); -- initialize to a "U"
+    skip_whitespace (L);
+    if VALUE'length > 0 then            -- non Null input string
+      read (L, c, readOk);
+      i := 0;
+      while i < VALUE'length loop
+        if readOk = false then              -- Bail out if there was a bad read
+          report "STD_LOGIC_TEXTIO.READ(STD_ULOGIC_VECTOR) "
+            & "End of string encountered"
+            severity error;
+          return;
+        elsif c = '_' then
+          if i = 0 then
+            report "STD_LOGIC_TEXTIO.READ(STD_ULOGIC_VECTOR) "
+              & "String begins with an ""_""" severity error;
+            return;
+          elsif lastu then
+            report "STD_LOGIC_TEXTIO.READ(STD_ULOGIC_VECTOR) "
+              & "Two underscores detected in input string ""__"""
+              severity error;
+            return;
+          else
+            lastu := true;
+          end if;
+        elsif char_to_MVL9plus(c) = error then
+          report
+            "STD_LOGIC_TEXTIO.READ(STD_ULOGIC_VECTOR) Error: Character '" &
+            c & "' read, expected STD_ULOGIC literal."
+            severity error;
+          return;
+        else
+          mv(i) := char_to_MVL9(c);
+          i := i + 1;
+          if i > mv'high then
+            VALUE := mv;
+            return;
+          end if;
+          lastu := false;
+        end if;
+        read(L, c, readOk);
+      end loop;
+    end if;
+  end procedure READ;
+
+  procedure WRITE (L         : inout LINE; VALUE : in STD_ULOGIC;
+                   JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+  begin
+    write(L, MVL9_to_char(VALUE), JUSTIFIED, FIELD);
+  end procedure WRITE;
+
+  procedure WRITE (L         : inout LINE; VALUE : in STD_ULOGIC_VECTOR;
+                   JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+    variable s : STRING(1 to VALUE'length);
+    alias m    : STD_ULOGIC_VECTOR(1 to VALUE'length) is VALUE;
+  begin
+    for i in 1 to VALUE'length loop
+      s(i) := MVL9_to_char(m(i));
+    end loop;
+    write(L, s, JUSTIFIED, FIELD);
+  end procedure WRITE;
+
+  procedure Char2TriBits (C           : in  CHARACTER;
+                          RESULT      : out STD_ULOGIC_VECTOR(2 downto 0);
+                          GOOD        : out BOOLEAN;
+                          ISSUE_ERROR : in  BOOLEAN) is
+  begin
+    case C is
+      when '0' => RESULT := o"0"; GOOD := true;
+      when '1' => RESULT := o"1"; GOOD := true;
+      when '2' => RESULT := o"2"; GOOD := true;
+      when '3' => RESULT := o"3"; GOOD := true;
+      when '4' => RESULT := o"4"; GOOD := true;
+      when '5' => RESULT := o"5"; GOOD := true;
+      when '6' => RESULT := o"6"; GOOD := true;
+      when '7' => RESULT := o"7"; GOOD := true;
+      when 'Z' => RESULT := "ZZZ"; GOOD := true;
+      when 'X' => RESULT := "XXX"; GOOD := true;
+      when others =>
+        assert not ISSUE_ERROR
+          report
+          "STD_LOGIC_TEXTIO.OREAD Error: Read a '" & C &
+          "', expected an Octal character (0-7)."
+          severity error;
+        GOOD := false;
+    end case;
+  end procedure Char2TriBits;
+
+  procedure OREAD (L    : inout LINE; VALUE : out STD_ULOGIC_VECTOR;
+                   GOOD : out   BOOLEAN) is
+    variable ok  : BOOLEAN;
+    variable c   : CHARACTER;
+    constant ne  : INTEGER := (VALUE'length+2)/3;
+    constant pad : INTEGER := ne*3 - VALUE'length;
+    variable sv  : STD_ULOGIC_VECTOR(0 to ne*3 - 1);
+    variable i   : INTEGER;
+    variable lastu  : BOOLEAN := false;       -- last character was an "_"
+  begin
+    VALUE := (VALUE'range => 'U'); -- initialize to a "U"
+    skip_whitespace (L);
+    if VALUE'length > 0 then
+      read (L, c, ok);
+      i := 0;
+      while i < ne loop
+        -- Bail out if there was a bad read
+        if not ok then
+          GOOD := false;
+          return;
+        elsif c = '_' then
+          if i = 0 then
+            GOOD := false;                -- Begins with an "_"
+            return;
+          elsif lastu then
+            GOOD := false;                -- "__" detected
+            return;
+          else
+            lastu := true;
+          end if;
+        else
+          Char2TriBits(c, sv(3*i to 3*i+2), ok, false);
+          if not ok then
+            GOOD := false;
+            return;
+          end if;
+          i := i + 1;
+          lastu := false;
+        end if;
+        if i < ne then
+          read(L, c, ok);
+        end if;
+      end loop;
+      if or_reduce (sv (0 to pad-1)) = '1' then
+        GOOD := false;                           -- vector was truncated.
+      else
+        GOOD  := true;
+        VALUE := sv (pad to sv'high);
+      end if;
+    else
+      GOOD := true;                  -- read into a null array
+    end if;
+  end procedure OREAD;
+
+  procedure OREAD (L : inout LINE; VALUE : out STD_ULOGIC_VECTOR) is
+    variable c   : CHARACTER;
+    variable ok  : BOOLEAN;
+    constant ne  : INTEGER := (VALUE'length+2)/3;
+    constant pad : INTEGER := ne*3 - VALUE'length;
+    variable sv  : STD_ULOGIC_VECTOR(0 to ne*3 - 1);
+    variable i   : INTEGER;
+    variable lastu  : BOOLEAN := false;       -- last character was an "_"
+  begin
+    VALUE := (VALUE'range => 'U'); -- initialize to a "U"
+    skip_whitespace (L);
+    if VALUE'length > 0 then
+      read (L, c, ok);
+      i := 0;
+      while i < ne loop
+        -- Bail out if there was a bad read
+        if not ok then
+          report "STD_LOGIC_TEXTIO.OREAD "
+            & "End of string encountered"
+            severity error;
+          return;
+        elsif c = '_' then
+          if i = 0 then
+            report "STD_LOGIC_TEXTIO.OREAD "
+              & "String begins with an ""_""" severity error;
+            return;
+          elsif lastu then
+            report "STD_LOGIC_TEXTIO.OREAD "
+              & "Two underscores detected in input string ""__"""
+              severity error;
+            return;
+          else
+            lastu := true;
+          end if;
+        else
+          Char2TriBits(c, sv(3*i to 3*i+2), ok, true);
+          if not ok then
+            return;
+          end if;
+          i := i + 1;
+          lastu := false;
+        end if;
+        if i < ne then
+          read(L, c, ok);
+        end if;
+      end loop;
+      if or_reduce (sv (0 to pad-1)) = '1' then
+        report "STD_LOGIC_TEXTIO.OREAD Vector truncated"
+          severity error;
+      else
+        VALUE := sv (pad to sv'high);
+      end if;
+    end if;
+  end procedure OREAD;
+
+  procedure Char2QuadBits (C           :     CHARACTER;
+                           RESULT      : out STD_ULOGIC_VECTOR(3 downto 0);
+                           GOOD        : out BOOLEAN;
+                           ISSUE_ERROR : in  BOOLEAN) is
+  begin
+    case C is
+      when '0'       => RESULT := x"0"; GOOD := true;
+      when '1'       => RESULT := x"1"; GOOD := true;
+      when '2'       => RESULT := x"2"; GOOD := true;
+      when '3'       => RESULT := x"3"; GOOD := true;
+      when '4'       => RESULT := x"4"; GOOD := true;
+      when '5'       => RESULT := x"5"; GOOD := true;
+      when '6'       => RESULT := x"6"; GOOD := true;
+      when '7'       => RESULT := x"7"; GOOD := true;
+      when '8'       => RESULT := x"8"; GOOD := true;
+      when '9'       => RESULT := x"9"; GOOD := true;
+      when 'A' | 'a' => RESULT := x"A"; GOOD := true;
+      when 'B' | 'b' => RESULT := x"B"; GOOD := true;
+      when 'C' | 'c' => RESULT := x"C"; GOOD := true;
+      when 'D' | 'd' => RESULT := x"D"; GOOD := true;
+      when 'E' | 'e' => RESULT := x"E"; GOOD := true;
+      when 'F' | 'f' => RESULT := x"F"; GOOD := true;
+      when 'Z'       => RESULT := "ZZZZ"; GOOD := true;
+      when 'X'       => RESULT := "XXXX"; GOOD := true;
+      when others =>
+        assert not ISSUE_ERROR
+          report
+          "STD_LOGIC_TEXTIO.HREAD Error: Read a '" & C &
+          "', expected a Hex character (0-F)."
+          severity error;
+        GOOD := false;
+    end case;
+  end procedure Char2QuadBits;
+
+  procedure HREAD (L    : inout LINE; VALUE : out STD_ULOGIC_VECTOR;
+                   GOOD : out   BOOLEAN) is
+    variable ok  : BOOLEAN;
+    variable c   : CHARACTER;
+    constant ne  : INTEGER := (VALUE'length+3)/4;
+    constant pad : INTEGER := ne*4 - VALUE'length;
+    variable sv  : STD_ULOGIC_VECTOR(0 to ne*4 - 1);
+    variable i   : INTEGER;
+    variable lastu  : BOOLEAN := false;       -- last character was an "_"
+  begin
+    VALUE := (VALUE'range => 'U'); -- initialize to a "U"
+    skip_whitespace (L);
+    if VALUE'length > 0 then
+      read (L, c, ok);
+      i := 0;
+      while i < ne loop
+        -- Bail out if there was a bad read
+        if not ok then
+          GOOD := false;
+          return;
+        elsif c = '_' then
+          if i = 0 then
+            GOOD := false;                -- Begins with an "_"
+            return;
+          elsif lastu then
+            GOOD := false;                -- "__" detected
+            return;
+          else
+            lastu := true;
+          end if;
+        else
+          Char2QuadBits(c, sv(4*i to 4*i+3), ok, false);
+          if not ok then
+            GOOD := false;
+            return;
+          end if;
+          i := i + 1;
+          lastu := false;
+        end if;
+        if i < ne then
+          read(L, c, ok);
+        end if;
+      end loop;
+      if or_reduce (sv (0 to pad-1)) = '1' then
+        GOOD := false;                           -- vector was truncated.
+      else
+        GOOD  := true;
+        VALUE := sv (pad to sv'high);
+      end if;
+    else
+      GOOD := true;                     -- Null input string, skips whitespace
+    end if;
+  end procedure HREAD;
+
+  procedure HREAD (L : inout LINE; VALUE : out STD_ULOGIC_VECTOR) is
+    variable ok  : BOOLEAN;
+    variable c   : CHARACTER;
+    constant ne  : INTEGER := (VALUE'length+3)/4;
+    constant pad : INTEGER := ne*4 - VALUE'length;
+    variable sv  : STD_ULOGIC_VECTOR(0 to ne*4 - 1);
+    variable i   : INTEGER;
+    variable lastu  : BOOLEAN := false;       -- last character was an "_"
+  begin
+    VALUE := (VALUE'range => 'U'); -- initialize to a "U"
+    skip_whitespace (L);
+    if VALUE'length > 0 then           -- non Null input string
+      read (L, c, ok);
+      i := 0;
+      while i < ne loop
+        -- Bail out if there was a bad read
+        if not ok then
+          report "STD_LOGIC_TEXTIO.HREAD "
+            & "End of string encountered"
+            severity error;
+          return;
+        end if;
+        if c = '_' then
+          if i = 0 then
+            report "STD_LOGIC_TEXTIO.HREAD "
+              & "String begins with an ""_""" severity error;
+            return;
+          elsif lastu then
+            report "STD_LOGIC_TEXTIO.HREAD "
+              & "Two underscores detected in input string ""__"""
+              severity error;
+            return;
+          else
+            lastu := true;
+          end if;
+        else
+          Char2QuadBits(c, sv(4*i to 4*i+3), ok, true);
+          if not ok then
+            return;
+          end if;
+          i := i + 1;
+          lastu := false;
+        end if;
+        if i < ne then
+          read(L, c, ok);
+        end if;
+      end loop;
+      if or_reduce (sv (0 to pad-1)) = '1' then
+        report "STD_LOGIC_TEXTIO.HREAD Vector truncated"
+          severity error;
+      else
+        VALUE := sv (pad to sv'high);
+      end if;
+    end if;
+  end procedure HREAD;
+
+  procedure OWRITE (L         : inout LINE; VALUE : in STD_ULOGIC_VECTOR;
+                    JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+  begin
+    write (L, TO_OSTRING(VALUE), JUSTIFIED, FIELD);
+  end procedure OWRITE;
+
+  procedure HWRITE (L         : inout LINE; VALUE : in STD_ULOGIC_VECTOR;
+                    JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+  begin
+    write (L, TO_HSTRING (VALUE), JUSTIFIED, FIELD);
+  end procedure HWRITE;
+
+  -------------------------------------------------------------------
+  -- read and write operations of STD_LOGIC_VECTOR
+  -------------------------------------------------------------------
+
+  procedure READ (L    : inout LINE; VALUE : out STD_LOGIC_VECTOR;
+                  GOOD : out   BOOLEAN) is
+    variable ivalue : STD_ULOGIC_VECTOR (VALUE'range);
+  begin
+    READ (L, ivalue, GOOD);
+    VALUE := To_StdLogicVector (ivalue);
+  end procedure READ;
+
+  procedure READ (L : inout LINE; VALUE : out STD_LOGIC_VECTOR) is
+    variable ivalue : STD_ULOGIC_VECTOR (VALUE'range);
+  begin
+    READ (L, ivalue);
+    VALUE := To_StdLogicVector (ivalue);
+  end procedure READ;
+
+  procedure OREAD (L    : inout LINE; VALUE : out STD_LOGIC_VECTOR;
+                   GOOD : out   BOOLEAN) is
+    variable ivalue : STD_ULOGIC_VECTOR (VALUE'range);
+  begin
+    OREAD (L, ivalue, GOOD);
+    VALUE := To_StdLogicVector (ivalue);
+  end procedure OREAD;
+
+  procedure OREAD (L : inout LINE; VALUE : out STD_LOGIC_VECTOR) is
+    variable ivalue : STD_ULOGIC_VECTOR (VALUE'range);
+  begin
+    OREAD (L, ivalue);
+    VALUE := To_StdLogicVector (ivalue);
+  end procedure OREAD;
+
+  procedure HREAD (L    : inout LINE; VALUE : out STD_LOGIC_VECTOR;
+                   GOOD : out   BOOLEAN) is
+    variable ivalue : STD_ULOGIC_VECTOR (VALUE'range);
+  begin
+    HREAD (L, ivalue, GOOD);
+    VALUE := To_StdLogicVector (ivalue);
+  end procedure HREAD;
+
+  procedure HREAD (L : inout LINE; VALUE : out STD_LOGIC_VECTOR) is
+    variable ivalue : STD_ULOGIC_VECTOR (VALUE'range);
+  begin
+    HREAD (L, ivalue);
+    VALUE := To_StdLogicVector (ivalue);
+  end procedure HREAD;
+
+  procedure WRITE (L         : inout LINE; VALUE : in STD_LOGIC_VECTOR;
+                   JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+  begin
+    WRITE (L, To_StdULogicVector (VALUE), JUSTIFIED, FIELD);
+  end procedure WRITE;
+
+  procedure OWRITE (L         : inout LINE; VALUE : in STD_LOGIC_VECTOR;
+                    JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+  begin
+    OWRITE (L, To_StdULogicVector (VALUE), JUSTIFIED, FIELD);
+  end procedure OWRITE;
+
+  procedure HWRITE (L         : inout LINE; VALUE : in STD_LOGIC_VECTOR;
+                    JUSTIFIED : in    SIDE := right; FIELD : in WIDTH := 0) is
+  begin
+    HWRITE (L, To_StdULogicVector (VALUE), JUSTIFIED, FIELD);
+  end procedure HWRITE;
+
+end package body STD_LOGIC_TEXTIO;
@@ -8,6 +8,7 @@
 //! It also contains the main event loop for handling incoming messages from the LSP client and
 //! dispatching them to the appropriate server methods.
 
+use crossbeam_channel::select;
 use lsp_server::{Connection, ExtractError, Request, RequestId};
 use lsp_types::{notification, request, InitializeParams};
 use serde_json::Value;
@@ -15,8 +16,8 @@ use serde_json::Value;
 use std::{cell::RefCell, rc::Rc};
 
 use crate::rpc_channel::{RpcChannel, SharedRpcChannel};
-use crate::vhdl_server::VHDLServer;
 use crate::vhdl_server::VHDLServerSettings;
+use crate::vhdl_server::{VHDLServer, VUNIT_TEST_CASES};
 
 /// Set up the IO channel for `stdio` and start the VHDL language server.
 pub fn start(settings: VHDLServerSettings) {
@@ -83,7 +84,20 @@ impl ConnectionRpcChannel {
     /// Main event loop handling incoming messages from the client.
     fn main_event_loop(&self, mut server: VHDLServer) {
         info!("Language server initialized, waiting for messages ...");
-        while let Ok(message) = self.connection.receiver.recv() {
+        let completed_tasks = server.completed_tasks();
+        loop {
+            let message = select! {
+                recv(self.connection.receiver) -> message => match message {
+                    Ok(message) => message,
+                    Err(_) => break,
+                },
+                recv(completed_tasks) -> task => {
+                    if let Ok((name, completed)) = task {
+                        server.complete_task(name, completed);
+                    }
+                    continue;
+                }
+            };
             trace!("Received message: {:?}", message);
             match message {
                 lsp_server::Message::Request(request) => {
@@ -190,6 +204,54 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<request::CallHierarchyPrepare>(request) {
+            Ok((id, params)) => {
+                let result = server.prepare_call_hierarchy(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CallHierarchyIncomingCalls>(request) {
+            Ok((id, params)) => {
+                let result = server.call_hierarchy_incoming_calls(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CallHierarchyOutgoingCalls>(request) {
+            Ok((id, params)) => {
+                let result = server.call_hierarchy_outgoing_calls(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::TypeHierarchyPrepare>(request) {
+            Ok((id, params)) => {
+                let result = server.prepare_type_hierarchy(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::TypeHierarchySupertypes>(request) {
+            Ok((id, params)) => {
+                let result = server.type_hierarchy_supertypes(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::TypeHierarchySubtypes>(request) {
+            Ok((id, params)) => {
+                let result = server.type_hierarchy_subtypes(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
         let request = match extract::<request::DocumentHighlightRequest>(request) {
             Ok((id, params)) => {
                 let result = server.document_highlight(&params.text_document_position_params);
@@ -198,6 +260,70 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<request::SelectionRangeRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.selection_range(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::FoldingRangeRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.folding_range(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::SemanticTokensFullRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.semantic_tokens_full(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::SemanticTokensFullDeltaRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.semantic_tokens_full_delta(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::InlayHintRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.inlay_hint(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::SignatureHelpRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.signature_help(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CodeLensResolve>(request) {
+            Ok((id, params)) => {
+                let result = server.code_lens_resolve(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::LinkedEditingRange>(request) {
+            Ok((id, params)) => {
+                let result = server.linked_editing_range(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
         let request = match extract::<request::HoverRequest>(request) {
             Ok((id, params)) => {
                 let result = server.text_document_hover(&params.text_document_position_params);
@@ -214,6 +340,59 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<request::CodeActionRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.code_action(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::ExecuteCommand>(request) {
+            Ok((id, params)) => {
+                let result = server.execute_command(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::Formatting>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_formatting(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::RangeFormatting>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_range_formatting(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::OnTypeFormatting>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_on_type_formatting(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CodeLensRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.code_lens(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        if request.method == VUNIT_TEST_CASES {
+            let result = server.vunit_test_cases();
+            self.send_response(lsp_server::Response::new_ok(request.id, result));
+            return;
+        }
         let request = match extract::<request::Completion>(request) {
             Ok((id, params)) => {
                 let res = server.request_completion(&params);
@@ -267,6 +446,11 @@ impl ConnectionRpcChannel {
             Ok(params) => return server.text_document_did_open_notification(&params),
             Err(notification) => notification,
         };
+        // textDocument/didClose
+        let notification = match extract::<notification::DidCloseTextDocument>(notification) {
+            Ok(params) => return server.text_document_did_close_notification(&params),
+            Err(notification) => notification,
+        };
         // workspace.didChangeWatchedFiles
         let notification = match extract::<notification::DidChangeWatchedFiles>(notification) {
             Ok(params) => return server.workspace_did_change_watched_files(&params),
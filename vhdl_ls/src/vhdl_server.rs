@@ -4,13 +4,24 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
+mod background;
+mod call_hierarchy;
+mod code_action;
+mod code_lens;
 mod completion;
 mod diagnostics;
+mod elaboration;
+mod formatting;
 mod lifecycle;
 mod rename;
+mod semantic_tokens;
 mod text_document;
+mod type_hierarchy;
+mod vunit;
 mod workspace;
 
+pub use vunit::VUNIT_TEST_CASES;
+
 use lsp_types::*;
 
 use fnv::FnvHashMap;
@@ -22,8 +33,8 @@ use std::io;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use vhdl_lang::{
-    AnyEntKind, Concurrent, Config, EntHierarchy, EntRef, Message, MessageHandler, Object,
-    Overloaded, Project, SeverityMap, SrcPos, Token, Type, VHDLStandard,
+    AnyEntKind, Concurrent, Config, EntHierarchy, EntRef, EntityId, Message, MessageHandler,
+    Object, Overloaded, Project, SeverityMap, SrcPos, Token, Type, VHDLStandard,
 };
 
 /// Defines how the language server handles files
@@ -61,11 +72,17 @@ pub struct VHDLServer {
     // To have well defined unit tests that are not affected by environment
     use_external_config: bool,
     project: Project,
+    /// Diagnostics of the last elaboration by an external tool
+    elaboration_diagnostics: Vec<vhdl_lang::Diagnostic>,
     diagnostic_cache: FnvHashMap<Url, Vec<vhdl_lang::Diagnostic>>,
     init_params: Option<InitializeParams>,
     config_file: Option<PathBuf>,
     severity_map: SeverityMap,
     string_matcher: SkimMatcherV2,
+    /// The semantic tokens last sent for each open document, to compute deltas
+    semantic_tokens_cache: FnvHashMap<Url, SemanticTokens>,
+    semantic_tokens_result_id: u64,
+    background: background::BackgroundTasks,
 }
 
 impl VHDLServer {
@@ -75,11 +92,15 @@ impl VHDLServer {
             settings,
             use_external_config: true,
             project: Project::new(VHDLStandard::default()),
+            elaboration_diagnostics: Vec::new(),
             diagnostic_cache: FnvHashMap::default(),
             init_params: None,
             config_file: None,
             severity_map: SeverityMap::default(),
             string_matcher: SkimMatcherV2::default().use_cache(true).ignore_case(),
+            semantic_tokens_cache: FnvHashMap::default(),
+            semantic_tokens_result_id: 0,
+            background: Default::default(),
         }
     }
 
@@ -90,11 +111,15 @@ impl VHDLServer {
             settings: Default::default(),
             use_external_config,
             project: Project::new(VHDLStandard::default()),
+            elaboration_diagnostics: Vec::new(),
             diagnostic_cache: Default::default(),
             init_params: None,
             config_file: None,
             severity_map: SeverityMap::default(),
-            string_matcher: SkimMatcherV2::default(),
+            string_matcher: SkimMatcherV2::default().use_cache(true).ignore_case(),
+            semantic_tokens_cache: FnvHashMap::default(),
+            semantic_tokens_result_id: 0,
+            background: Default::default(),
         }
     }
 
@@ -186,6 +211,27 @@ impl VHDLServer {
         try_fun().unwrap_or(false)
     }
 
+    fn client_supports_type_hierarchy(&self) -> bool {
+        let try_fun = || {
+            self.init_params
+                .as_ref()?
+                .capabilities
+                .text_document
+                .as_ref()?
+                .type_hierarchy
+                .as_ref()?
+                .dynamic_registration
+        };
+        try_fun().unwrap_or(false)
+    }
+
+    /// Items that are resolved lazily, such as completion items or the items of a hierarchy,
+    /// carry the id of their named entity as data
+    fn entity_id_from_data(&self, data: Option<&serde_json::Value>) -> Option<EntityId> {
+        let raw = serde_json::from_value::<usize>(data?.clone()).ok()?;
+        self.project.entity_id_from_raw(raw)
+    }
+
     fn client_supports_did_change_watched_files(&self) -> bool {
         let try_fun = || {
             self.init_params
@@ -200,6 +246,27 @@ impl VHDLServer {
         try_fun().unwrap_or(false)
     }
 
+    fn client_supports_rename_file(&self) -> bool {
+        let try_fun = || {
+            let workspace_edit = self
+                .init_params
+                .as_ref()?
+                .capabilities
+                .workspace
+                .as_ref()?
+                .workspace_edit
+                .as_ref()?;
+            Some(
+                workspace_edit.document_changes?
+                    && workspace_edit
+                        .resource_operations
+                        .as_ref()?
+                        .contains(&ResourceOperationKind::Rename),
+            )
+        };
+        try_fun().unwrap_or(false)
+    }
+
     fn client_supports_snippets(&self) -> bool {
         let try_fun = || {
             self.init_params
@@ -252,12 +319,28 @@ impl VHDLServer {
                 // The latter is applicable for unnamed elements, e.g., processes or loops.
                 let selection_pos = ent.decl_pos().unwrap_or(ent.src_span.start_token.pos(ctx));
                 let src_range = ent.src_span.pos(ctx).range();
+                // The outline shows the name of the symbol, followed by its kind,
+                // e.g., `sig` followed by `signal`.
+                // Unnamed elements are only shown by their kind.
+                let kind = match ent.kind() {
+                    AnyEntKind::Overloaded(overloaded) => format!(
+                        "{} {}",
+                        overloaded.describe(),
+                        overloaded.signature().describe()
+                    ),
+                    kind => kind.describe().to_owned(),
+                };
+                let (name, detail) = if ent.is_anonymous() {
+                    (kind, None)
+                } else {
+                    (ent.designator().to_string(), Some(kind))
+                };
                 #[allow(deprecated)]
                 DocumentSymbol {
-                    name: ent.describe(),
+                    name,
                     kind: to_symbol_kind(ent.kind()),
                     tags: None,
-                    detail: None,
+                    detail,
                     selection_range: to_lsp_range(selection_pos.range),
                     range: to_lsp_range(src_range),
                     children: if !children.is_empty() {
@@ -478,7 +561,7 @@ fn to_symbol_kind(kind: &AnyEntKind) -> SymbolKind {
         AnyEntKind::Concurrent(Some(Concurrent::Instance)) => SymbolKind::MODULE,
         AnyEntKind::Concurrent(_) => SymbolKind::NAMESPACE,
         AnyEntKind::Library => SymbolKind::NAMESPACE,
-        AnyEntKind::View(_) => SymbolKind::INTERFACE,
+        AnyEntKind::View(..) => SymbolKind::INTERFACE,
         AnyEntKind::Design(d) => match d {
             vhdl_lang::Design::Entity(_, _) => SymbolKind::MODULE,
             vhdl_lang::Design::Architecture(..) => SymbolKind::MODULE,
@@ -486,8 +569,8 @@ fn to_symbol_kind(kind: &AnyEntKind) -> SymbolKind {
             vhdl_lang::Design::Package(_, _) => SymbolKind::PACKAGE,
             vhdl_lang::Design::PackageBody(..) => SymbolKind::PACKAGE,
             vhdl_lang::Design::UninstPackage(_, _) => SymbolKind::PACKAGE,
-            vhdl_lang::Design::PackageInstance(_) => SymbolKind::PACKAGE,
-            vhdl_lang::Design::InterfacePackageInstance(_) => SymbolKind::PACKAGE,
+            vhdl_lang::Design::PackageInstance(..) => SymbolKind::PACKAGE,
+            vhdl_lang::Design::InterfacePackageInstance(..) => SymbolKind::PACKAGE,
             vhdl_lang::Design::Context(_) => SymbolKind::NAMESPACE,
         },
     }
@@ -499,6 +582,12 @@ mod tests {
 
     use super::*;
     use crate::rpc_channel::test_support::*;
+    use crate::vhdl_server::code_action::{
+        EXPORT_IP_XACT_COMPONENT, GENERATE_TESTBENCH, NORMALIZE_CASE, SYNCHRONIZE_INSTANTIATIONS,
+    };
+    use crate::vhdl_server::elaboration::ELABORATE;
+    use crate::vhdl_server::vunit::{RUN_VUNIT_TEST, VUNIT_TEST_RESULT};
+    use std::collections::HashMap;
 
     pub(crate) fn initialize_server(server: &mut VHDLServer, root_uri: Url) {
         let capabilities = ClientCapabilities::default();
@@ -856,6 +945,79 @@ lib.files = [
         assert_eq!(response, Some(expected));
     }
 
+    #[test]
+    fn workspace_symbol_ignores_case_and_ranks_best_matches_first() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_url = write_file(
+            &root_uri,
+            "file.vhd",
+            "\
+entity my_entity is
+end entity;
+
+package my_pkg is
+  type my_type_t is (first, second);
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  'file.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let symbol = |query: &str| {
+            let Some(WorkspaceSymbolResponse::Nested(symbols)) =
+                server.workspace_symbol(&WorkspaceSymbolParams {
+                    query: query.to_owned(),
+                    ..Default::default()
+                })
+            else {
+                panic!("Expected nested workspace symbols");
+            };
+            symbols.into_iter().next().unwrap()
+        };
+
+        let entity = symbol("MY_ENT");
+        assert_eq!(entity.name, "entity 'my_entity'");
+        assert_eq!(entity.kind, SymbolKind::MODULE);
+        assert_eq!(
+            entity.location,
+            OneOf::Left(Location {
+                uri: file_url.clone(),
+                range: Range {
+                    start: lsp_types::Position {
+                        line: 0,
+                        character: "entity ".len() as u32,
+                    },
+                    end: lsp_types::Position {
+                        line: 0,
+                        character: "entity my_entity".len() as u32,
+                    },
+                },
+            })
+        );
+
+        let typ = symbol("mytype");
+        assert_eq!(typ.name, "type 'my_type_t'");
+        assert_eq!(typ.container_name, Some("lib.my_pkg".to_owned()));
+    }
+
     #[test]
     fn client_register_capability() {
         let (mock, mut server) = setup_server();
@@ -906,6 +1068,1155 @@ lib.files = [
         server.initialized_notification();
     }
 
+    #[test]
+    fn semantic_tokens_are_classified_by_entity_and_sent_as_delta() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_url = write_file(
+            &root_uri,
+            "file.vhd",
+            "\
+entity ent is
+  port (clk : in bit);
+end entity;
+
+architecture a of ent is
+begin
+  assert clk = '1';
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  'file.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let token =
+            |delta_line, delta_start, length, token_type, token_modifiers_bitset| SemanticToken {
+                delta_line,
+                delta_start,
+                length,
+                token_type,
+                token_modifiers_bitset,
+            };
+        let Some(SemanticTokensResult::Tokens(tokens)) =
+            server.semantic_tokens_full(&SemanticTokensParams {
+                text_document: TextDocumentIdentifier::new(file_url.clone()),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+        else {
+            panic!("Expected semantic tokens");
+        };
+        assert_eq!(
+            tokens.data,
+            vec![
+                // entity ent
+                token(0, 7, 3, 2, 0b1),
+                // clk : in bit
+                token(1, 8, 3, 7, 0b11001),
+                token(0, 9, 3, 3, 0b100),
+                // architecture a of ent
+                token(3, 13, 1, 2, 0b1),
+                token(0, 5, 3, 2, 0),
+                // assert clk = '1'
+                token(2, 9, 3, 7, 0b11000),
+            ]
+        );
+
+        let delta = server.semantic_tokens_full_delta(&SemanticTokensDeltaParams {
+            text_document: TextDocumentIdentifier::new(file_url),
+            previous_result_id: tokens.result_id.unwrap(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let Some(SemanticTokensFullDeltaResult::TokensDelta(delta)) = delta else {
+            panic!("Expected semantic tokens delta");
+        };
+        assert!(delta.edits.is_empty());
+    }
+
+    #[test]
+    fn semantic_tokens_are_forgotten_on_close_and_reload() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_url = write_file(
+            &root_uri,
+            "file.vhd",
+            "\
+entity ent is
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = [
+  'file.vhd'
+]
+",
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let request_tokens = |server: &mut VHDLServer| {
+            server.semantic_tokens_full(&SemanticTokensParams {
+                text_document: TextDocumentIdentifier::new(file_url.clone()),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+        };
+
+        request_tokens(&mut server);
+        assert!(server.semantic_tokens_cache.contains_key(&file_url));
+        server.text_document_did_close_notification(&DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier::new(file_url.clone()),
+        });
+        assert!(server.semantic_tokens_cache.is_empty());
+
+        request_tokens(&mut server);
+        assert!(server.semantic_tokens_cache.contains_key(&file_url));
+        mock.expect_message_contains("Configuration file has changed, reloading project...");
+        expect_loaded_config_messages(&mock, &config_uri);
+        server.workspace_did_change_watched_files(&DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                typ: FileChangeType::CHANGED,
+                uri: config_uri,
+            }],
+        });
+        assert!(server.semantic_tokens_cache.is_empty());
+    }
+
+    #[test]
+    fn document_symbols_show_designator_kind_and_children() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let file_url = write_file(
+            &root_uri,
+            "file.vhd",
+            "\
+entity ent is
+  port (clk : in bit);
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  process
+  begin
+    sig <= clk;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  'file.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                document_symbol: Some(DocumentSymbolClientCapabilities {
+                    hierarchical_document_symbol_support: Some(true),
+                    ..DocumentSymbolClientCapabilities::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+
+        let Some(DocumentSymbolResponse::Nested(symbols)) =
+            server.document_symbol(&DocumentSymbolParams {
+                text_document: TextDocumentIdentifier::new(file_url),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+        else {
+            panic!("Expected nested document symbols");
+        };
+
+        // One line per symbol with its name, its detail and its children indented below it
+        fn outline(symbols: &[DocumentSymbol], indent: usize, lines: &mut Vec<String>) {
+            for symbol in symbols {
+                let detail = symbol
+                    .detail
+                    .as_ref()
+                    .map_or(String::new(), |detail| format!(": {detail}"));
+                lines.push(format!("{}{}{detail}", " ".repeat(indent), symbol.name));
+                outline(
+                    symbol.children.as_deref().unwrap_or_default(),
+                    indent + 2,
+                    lines,
+                );
+            }
+        }
+        let mut lines = Vec::new();
+        outline(&symbols, 0, &mut lines);
+        assert_eq!(
+            lines,
+            vec![
+                "ent: entity",
+                "  clk: signal",
+                "a: architecture",
+                "  sig: signal",
+                "  process",
+            ]
+        );
+    }
+
+    #[test]
+    fn client_register_type_hierarchy_capability() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+        ",
+        );
+
+        let register_capability = RegistrationParams {
+            registrations: vec![Registration {
+                id: "textDocument/prepareTypeHierarchy".to_owned(),
+                method: "textDocument/prepareTypeHierarchy".to_owned(),
+                register_options: serde_json::to_value(TypeHierarchyRegistrationOptions::default())
+                    .ok(),
+            }],
+        };
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_request("client/registerCapability", register_capability);
+
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                type_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                    dynamic_registration: Some(true),
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+    }
+
+    #[test]
+    fn rename_entity_renames_file_with_same_name() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture rtl of ent is
+begin
+end;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                workspace_edit: Some(WorkspaceEditClientCapabilities {
+                    document_changes: Some(true),
+                    resource_operations: Some(vec![ResourceOperationKind::Rename]),
+                    ..WorkspaceEditClientCapabilities::default()
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+
+        let edit = server
+            .rename(&RenameParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: file_uri.clone(),
+                    },
+                    position: lsp_types::Position {
+                        line: 0,
+                        character: "entity e".len() as u32,
+                    },
+                },
+                new_name: "top".to_owned(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .unwrap();
+
+        let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+            panic!("Expected document change operations");
+        };
+        assert_eq!(operations.len(), 2);
+        let DocumentChangeOperation::Edit(text_edit) = &operations[0] else {
+            panic!("Expected text edits first");
+        };
+        assert_eq!(text_edit.text_document.uri, file_uri);
+        assert_eq!(text_edit.edits.len(), 2);
+        assert_eq!(
+            operations[1],
+            DocumentChangeOperation::Op(ResourceOp::Rename(RenameFile {
+                new_uri: file_uri.join("top.vhd").unwrap(),
+                old_uri: file_uri.clone(),
+                options: None,
+                annotation_id: None,
+            }))
+        );
+
+        mock.expect_error_contains("Cannot rename: 'end' is not a valid identifier");
+        assert_eq!(
+            server.rename(&RenameParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: file_uri },
+                    position: lsp_types::Position {
+                        line: 0,
+                        character: "entity e".len() as u32,
+                    },
+                },
+                new_name: "end".to_owned(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn synchronize_instantiations_command() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "top.vhd",
+            "\
+entity child is
+  port (a : in bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  signal s : bit;
+begin
+  inst: entity work.child port map (removed => s);
+end;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No declaration of 'removed'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        mock.expect_warning_contains("top.vhd:11: 'removed' is not a formal of 'child'");
+        let position = lsp_types::Position {
+            line: 10,
+            character: "  inst: entity work.child port map (removed => s".len() as u32,
+        };
+        mock.expect_request(
+            "workspace/applyEdit",
+            ApplyWorkspaceEditParams {
+                label: Some("Synchronize instantiations".to_owned()),
+                edit: WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        file_uri,
+                        vec![TextEdit {
+                            range: lsp_types::Range::new(position, position),
+                            new_text: ", a => open".to_owned(),
+                        }],
+                    )])),
+                    ..Default::default()
+                },
+            },
+        );
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: SYNCHRONIZE_INSTANTIATIONS.to_owned(),
+                arguments: vec![],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn normalize_case_command() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+ENTITY ent IS
+END ENTITY;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+
+[code_actions]
+keyword_case = 'lower'
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let edit = |line: u32, start: u32, end: u32, new_text: &str| TextEdit {
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(line, start),
+                lsp_types::Position::new(line, end),
+            ),
+            new_text: new_text.to_owned(),
+        };
+        mock.expect_request(
+            "workspace/applyEdit",
+            ApplyWorkspaceEditParams {
+                label: Some("Normalize case".to_owned()),
+                edit: WorkspaceEdit {
+                    changes: Some(HashMap::from([(
+                        file_uri,
+                        vec![
+                            edit(0, 0, 6, "entity"),
+                            edit(0, 11, 13, "is"),
+                            edit(1, 0, 3, "end"),
+                            edit(1, 4, 10, "entity"),
+                        ],
+                    )])),
+                    ..Default::default()
+                },
+            },
+        );
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: NORMALIZE_CASE.to_owned(),
+                arguments: vec![],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn document_formatting() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+ENTITY ent IS
+port (a : in bit);
+END ENTITY;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "Unused declaration of port 'a'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let edits = server
+            .text_document_formatting(&DocumentFormattingParams {
+                text_document: TextDocumentIdentifier::new(file_uri),
+                options: FormattingOptions {
+                    tab_size: 2,
+                    insert_spaces: true,
+                    ..Default::default()
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(0, 0),
+                    lsp_types::Position::new(3, 0),
+                ),
+                new_text: "\
+entity ent is
+  port (
+    a: in bit
+  );
+end entity;
+"
+                .to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn document_formatting_uses_configured_style() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+port (a : in bit);
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+
+[format]
+use_tabs = true
+keyword_case = 'upper'
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "Unused declaration of port 'a'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        // The configured style takes precedence over the options of the client
+        let edits = server
+            .text_document_formatting(&DocumentFormattingParams {
+                text_document: TextDocumentIdentifier::new(file_uri),
+                options: FormattingOptions {
+                    tab_size: 2,
+                    insert_spaces: true,
+                    ..Default::default()
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            edits[0].new_text,
+            "ENTITY ent IS\n\tPORT (\n\t\ta: IN bit\n\t);\nEND ENTITY;\n"
+        );
+    }
+
+    #[test]
+    fn document_range_and_on_type_formatting() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal x, y : bit;
+begin
+  x<='0';
+  y   <=   '1';
+    end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let edits = server
+            .text_document_range_formatting(&DocumentRangeFormattingParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(6, 0),
+                    lsp_types::Position::new(6, 3),
+                ),
+                options: FormattingOptions {
+                    tab_size: 2,
+                    insert_spaces: true,
+                    ..Default::default()
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(6, 0),
+                    lsp_types::Position::new(6, 9),
+                ),
+                new_text: "  x <= '0';".to_owned(),
+            }]
+        );
+
+        let edits = server
+            .text_document_on_type_formatting(&DocumentOnTypeFormattingParams {
+                text_document_position: TextDocumentPositionParams::new(
+                    TextDocumentIdentifier::new(file_uri),
+                    lsp_types::Position::new(8, 21),
+                ),
+                ch: ";".to_owned(),
+                options: FormattingOptions {
+                    tab_size: 2,
+                    insert_spaces: true,
+                    ..Default::default()
+                },
+            })
+            .unwrap();
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(8, 0),
+                    lsp_types::Position::new(8, 4),
+                ),
+                new_text: String::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn export_ip_xact_component_command() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "counter.vhd",
+            "\
+entity counter is
+  generic (WIDTH : natural := 8);
+  port (clk : in bit);
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "Unused declaration of generic 'WIDTH'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let xml_uri = file_uri.join("counter.xml").unwrap();
+        let source = server
+            .project
+            .get_source(&uri_to_file_name(&file_uri))
+            .unwrap();
+        let xml = server
+            .project
+            .export_ip_xact_component(&source, vhdl_lang::Position::new(0, 8))
+            .unwrap()
+            .xml;
+        assert!(xml.contains("<ipxact:name>counter</ipxact:name>"));
+        assert!(xml.contains("<ipxact:parameter parameterId=\"WIDTH\""));
+
+        mock.expect_request(
+            "workspace/applyEdit",
+            ApplyWorkspaceEditParams {
+                label: Some("Export 'counter' as IP-XACT component".to_owned()),
+                edit: WorkspaceEdit {
+                    document_changes: Some(DocumentChanges::Operations(vec![
+                        DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                            uri: xml_uri.clone(),
+                            options: None,
+                            annotation_id: None,
+                        })),
+                        DocumentChangeOperation::Edit(TextDocumentEdit {
+                            text_document: OptionalVersionedTextDocumentIdentifier {
+                                uri: xml_uri,
+                                version: None,
+                            },
+                            edits: vec![OneOf::Left(TextEdit {
+                                range: lsp_types::Range::default(),
+                                new_text: xml.clone(),
+                            })],
+                        }),
+                    ])),
+                    ..Default::default()
+                },
+            },
+        );
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: EXPORT_IP_XACT_COMPONENT.to_owned(),
+                arguments: vec![serde_json::to_value(TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier::new(file_uri),
+                    position: lsp_types::Position::new(0, 8),
+                })
+                .unwrap()],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            Some(serde_json::Value::String(xml))
+        );
+    }
+
+    #[test]
+    fn generate_testbench_command() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "counter.vhd",
+            "\
+entity counter is
+  port (clk : in bit);
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "Unused declaration of port 'clk'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let tb_uri = file_uri.join("counter_tb.vhd").unwrap();
+        let text = server.project.generate_testbench("counter").unwrap().text;
+        assert!(text.contains("dut : entity lib.counter"));
+        assert!(text.contains("clk_gen : process"));
+
+        mock.expect_request(
+            "workspace/applyEdit",
+            ApplyWorkspaceEditParams {
+                label: Some("Generate testbench 'counter_tb'".to_owned()),
+                edit: WorkspaceEdit {
+                    document_changes: Some(DocumentChanges::Operations(vec![
+                        DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                            uri: tb_uri.clone(),
+                            options: None,
+                            annotation_id: None,
+                        })),
+                        DocumentChangeOperation::Edit(TextDocumentEdit {
+                            text_document: OptionalVersionedTextDocumentIdentifier {
+                                uri: tb_uri,
+                                version: None,
+                            },
+                            edits: vec![OneOf::Left(TextEdit {
+                                range: lsp_types::Range::default(),
+                                new_text: text.clone(),
+                            })],
+                        }),
+                    ])),
+                    ..Default::default()
+                },
+            },
+        );
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: GENERATE_TESTBENCH.to_owned(),
+                arguments: vec![serde_json::to_value(TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier::new(file_uri),
+                    position: lsp_types::Position::new(0, 8),
+                })
+                .unwrap()],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            Some(serde_json::Value::String(text))
+        );
+    }
+
+    #[test]
+    fn vunit_test_cases_and_code_lenses() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "tb.vhd",
+            "\
+entity tb is
+  generic (runner_cfg : string);
+end entity;
+
+architecture a of tb is
+  function run(name : string) return boolean is
+  begin
+    return runner_cfg = name;
+  end function;
+begin
+  assert run(\"test\");
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let location = Location {
+            uri: file_uri.clone(),
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(10, 13),
+                lsp_types::Position::new(10, 19),
+            ),
+        };
+        assert_eq!(
+            server.vunit_test_cases(),
+            serde_json::json!([{
+                "library": "lib",
+                "testbench": "tb",
+                "name": "test",
+                "fullName": "lib.tb.test",
+                "location": location,
+            }])
+        );
+        let lenses = server
+            .code_lens(&CodeLensParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            lenses[0],
+            CodeLens {
+                range: location.range,
+                command: Some(Command {
+                    title: "Run test".to_owned(),
+                    command: RUN_VUNIT_TEST.to_owned(),
+                    arguments: Some(vec![serde_json::Value::String("lib.tb.test".to_owned())]),
+                }),
+                data: None,
+            }
+        );
+
+        // The lenses of the entity and the function are resolved lazily
+        assert_eq!(
+            lenses[1..]
+                .iter()
+                .map(|lens| (lens.range.start, lens.command.is_none()))
+                .collect::<Vec<_>>(),
+            vec![
+                (lsp_types::Position::new(0, 7), true),
+                (lsp_types::Position::new(5, 11), true),
+            ]
+        );
+        let resolved: Vec<Command> = lenses[1..]
+            .iter()
+            .map(|lens| server.code_lens_resolve(lens).command.unwrap())
+            .collect();
+        assert_eq!(resolved[0].title, "0 instantiations");
+        assert_eq!(resolved[1].title, "1 reference");
+        assert_eq!(resolved[1].command, "editor.action.showReferences");
+        assert_eq!(
+            resolved[1].arguments.as_ref().unwrap()[2],
+            serde_json::json!([Location {
+                uri: file_uri,
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(10, 9),
+                    lsp_types::Position::new(10, 12),
+                ),
+            }])
+        );
+
+        mock.expect_error_contains("Cannot run VUnit, 'run.py' does not exist");
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: RUN_VUNIT_TEST.to_owned(),
+                arguments: vec![serde_json::Value::String("lib.tb.test".to_owned())],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn vunit_test_runs_in_the_background() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "tb.vhd",
+            "\
+entity tb is
+  generic (runner_cfg : string);
+end entity;
+
+architecture a of tb is
+begin
+  assert runner_cfg /= \"\";
+end architecture;
+",
+        );
+        write_file(
+            &root_uri,
+            "run.py",
+            "\
+import sys
+print('Starting lib.tb.test')
+print('1000 ps - check - ERROR - Equality check failed (tb.vhd:6)')
+print('fail (P=0 S=0 F=1 T=1) lib.tb.test (0.5 s)')
+sys.exit(1)
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        // The command returns before the test has finished
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: RUN_VUNIT_TEST.to_owned(),
+                arguments: vec![serde_json::Value::String("lib.tb.test".to_owned())],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            None
+        );
+
+        mock.expect_error_contains("tb.vhd:6: 1000 ps - check - ERROR - Equality check failed");
+        mock.expect_notification(
+            VUNIT_TEST_RESULT,
+            serde_json::json!({
+                "test": "lib.tb.test",
+                "passed": false,
+                "failures": [{
+                    "test": "lib.tb.test",
+                    "message": "1000 ps - check - ERROR - Equality check failed (tb.vhd:6)",
+                    "location": Location {
+                        uri: file_uri,
+                        range: lsp_types::Range::new(
+                            lsp_types::Position::new(5, 0),
+                            lsp_types::Position::new(5, 0),
+                        ),
+                    },
+                }],
+            }),
+        );
+        server.wait_for_background_tasks();
+    }
+
+    #[test]
+    fn elaboration_runs_in_the_background() {
+        if std::process::Command::new("ghdl")
+            .arg("--version")
+            .output()
+            .is_ok()
+        {
+            // The outcome depends on the installed version of GHDL
+            return;
+        }
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(&root_uri, "top.vhd", "entity top is\nend entity;\n");
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/*.vhd',
+]
+lib.files = [
+  '*.vhd'
+]
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        // The command returns before GHDL has finished
+        assert_eq!(
+            server.execute_command(&ExecuteCommandParams {
+                command: ELABORATE.to_owned(),
+                arguments: vec![serde_json::Value::String("lib.top".to_owned())],
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            }),
+            None
+        );
+
+        mock.expect_error_contains("Cannot run GHDL");
+        server.wait_for_background_tasks();
+    }
+
     #[test]
     fn update_config_file() {
         let (mock, mut server) = setup_server();
@@ -0,0 +1,69 @@
+use crate::vhdl_server::VHDLServer;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use fnv::FnvHashSet;
+use vhdl_lang::Message;
+
+/// The completion of a background task, which is run by the main loop of the server
+pub type CompletedTask = Box<dyn FnOnce(&mut VHDLServer) + Send>;
+
+/// Long-running work, such as running a simulator, is done on a separate thread
+/// so that the server keeps answering requests in the meantime.
+/// The result is handed back to the main loop to update the server.
+pub struct BackgroundTasks {
+    sender: Sender<(&'static str, CompletedTask)>,
+    receiver: Receiver<(&'static str, CompletedTask)>,
+    running: FnvHashSet<&'static str>,
+}
+
+impl Default for BackgroundTasks {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded();
+        BackgroundTasks {
+            sender,
+            receiver,
+            running: FnvHashSet::default(),
+        }
+    }
+}
+
+impl VHDLServer {
+    /// Runs `work` on a separate thread and `complete` with its result on the main loop.
+    /// Only one task of each name runs at the same time.
+    pub(super) fn spawn_background<T: Send + 'static>(
+        &mut self,
+        name: &'static str,
+        work: impl FnOnce() -> T + Send + 'static,
+        complete: impl FnOnce(&mut VHDLServer, T) + Send + 'static,
+    ) {
+        if !self.background.running.insert(name) {
+            self.message(Message::warning(format!("'{name}' is already running")));
+            return;
+        }
+        let sender = self.background.sender.clone();
+        std::thread::spawn(move || {
+            let result = work();
+            let completed: CompletedTask = Box::new(move |server| complete(server, result));
+            // The server may have shut down in the meantime
+            let _ = sender.send((name, completed));
+        });
+    }
+
+    /// The tasks that finished on a background thread and must be completed on the main loop
+    pub fn completed_tasks(&self) -> Receiver<(&'static str, CompletedTask)> {
+        self.background.receiver.clone()
+    }
+
+    pub fn complete_task(&mut self, name: &'static str, completed: CompletedTask) {
+        self.background.running.remove(name);
+        completed(self);
+    }
+
+    /// Blocks until all background tasks have completed
+    #[cfg(test)]
+    pub fn wait_for_background_tasks(&mut self) {
+        while !self.background.running.is_empty() {
+            let (name, completed) = self.background.receiver.recv().unwrap();
+            self.complete_task(name, completed);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::vhdl_server::{
+    from_lsp_pos, srcpos_to_location, to_lsp_range, to_symbol_kind, uri_to_file_name, VHDLServer,
+};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+};
+use vhdl_lang::{AnyEntKind, EntRef, Overloaded};
+
+impl VHDLServer {
+    /// Returns the subprogram at the cursor as the root of the call hierarchy
+    pub fn prepare_call_hierarchy(
+        &self,
+        params: &CallHierarchyPrepareParams,
+    ) -> Option<Vec<CallHierarchyItem>> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let ent = self
+            .project
+            .find_declaration(&source, from_lsp_pos(position.position))?;
+        if !matches!(
+            ent.kind(),
+            AnyEntKind::Overloaded(overloaded)
+                if !matches!(overloaded, Overloaded::EnumLiteral(_) | Overloaded::Alias(_))
+        ) {
+            return None;
+        }
+        Some(vec![call_hierarchy_item(ent)?])
+    }
+
+    pub fn call_hierarchy_incoming_calls(
+        &self,
+        params: &CallHierarchyIncomingCallsParams,
+    ) -> Option<Vec<CallHierarchyIncomingCall>> {
+        let id = self.entity_id_from_data(params.item.data.as_ref())?;
+        Some(
+            self.project
+                .find_incoming_calls(id)
+                .into_iter()
+                .filter_map(|(caller, positions)| {
+                    Some(CallHierarchyIncomingCall {
+                        from: call_hierarchy_item(caller)?,
+                        from_ranges: positions
+                            .iter()
+                            .map(|pos| to_lsp_range(pos.range()))
+                            .collect(),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn call_hierarchy_outgoing_calls(
+        &self,
+        params: &CallHierarchyOutgoingCallsParams,
+    ) -> Option<Vec<CallHierarchyOutgoingCall>> {
+        let id = self.entity_id_from_data(params.item.data.as_ref())?;
+        Some(
+            self.project
+                .find_outgoing_calls(id)
+                .into_iter()
+                .filter_map(|(callee, positions)| {
+                    Some(CallHierarchyOutgoingCall {
+                        to: call_hierarchy_item(callee)?,
+                        from_ranges: positions
+                            .iter()
+                            .map(|pos| to_lsp_range(pos.range()))
+                            .collect(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+fn call_hierarchy_item(ent: EntRef<'_>) -> Option<CallHierarchyItem> {
+    let location = srcpos_to_location(ent.decl_pos()?);
+    let name = if ent.is_anonymous() {
+        ent.kind().describe().to_owned()
+    } else {
+        ent.designator().to_string()
+    };
+    let detail = match ent.kind() {
+        AnyEntKind::Overloaded(overloaded) => Some(overloaded.signature().describe()),
+        _ => None,
+    };
+    Some(CallHierarchyItem {
+        name,
+        kind: to_symbol_kind(ent.kind()),
+        tags: None,
+        detail,
+        uri: location.uri,
+        range: location.range,
+        selection_range: location.range,
+        data: Some(serde_json::Value::from(ent.id().to_raw())),
+    })
+}
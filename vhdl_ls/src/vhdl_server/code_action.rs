@@ -0,0 +1,282 @@
+use crate::vhdl_server::elaboration::ELABORATE;
+use crate::vhdl_server::vunit::RUN_VUNIT_TEST;
+use crate::vhdl_server::{
+    file_name_to_uri, from_lsp_pos, from_lsp_range, to_lsp_range, uri_to_file_name, VHDLServer,
+};
+use lsp_types::{
+    ApplyWorkspaceEditParams, CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionResponse, CreateFile, DocumentChangeOperation, DocumentChanges, ExecuteCommandParams,
+    OneOf, OptionalVersionedTextDocumentIdentifier, ResourceOp, TextDocumentEdit,
+    TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+};
+use std::collections::HashMap;
+use vhdl_lang::Message;
+
+/// Command that adds missing ports to all instantiations of the project
+/// and reports associations with formals that no longer exist
+pub const SYNCHRONIZE_INSTANTIATIONS: &str = "vhdl_ls.synchronizeInstantiations";
+
+/// Command that rewrites keywords and identifiers of the project to the configured casing
+pub const NORMALIZE_CASE: &str = "vhdl_ls.normalizeCase";
+
+/// Command that writes the interface of the entity at a position
+/// as an IP-XACT component description
+pub const EXPORT_IP_XACT_COMPONENT: &str = "vhdl_ls.exportIpXactComponent";
+
+/// Command that writes a testbench skeleton for the entity at a position
+pub const GENERATE_TESTBENCH: &str = "vhdl_ls.generateTestbench";
+
+fn to_lsp_code_action_kind(kind: vhdl_lang::CodeActionKind) -> CodeActionKind {
+    match kind {
+        vhdl_lang::CodeActionKind::RefactorMove => CodeActionKind::from("refactor.move"),
+        vhdl_lang::CodeActionKind::RefactorInline => CodeActionKind::REFACTOR_INLINE,
+        vhdl_lang::CodeActionKind::RefactorExtract => CodeActionKind::REFACTOR_EXTRACT,
+        vhdl_lang::CodeActionKind::RefactorRewrite => CodeActionKind::REFACTOR_REWRITE,
+        vhdl_lang::CodeActionKind::QuickFix => CodeActionKind::QUICKFIX,
+        vhdl_lang::CodeActionKind::SourceOrganizeImports => CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+    }
+}
+
+fn to_lsp_text_edits(edits: Vec<vhdl_lang::TextEdit>) -> Vec<TextEdit> {
+    edits
+        .into_iter()
+        .map(|edit| TextEdit {
+            range: to_lsp_range(edit.range),
+            new_text: edit.new_text,
+        })
+        .collect()
+}
+
+/// Files can only be created using document changes.
+/// When no file is created, the simpler `changes` are used as not all clients
+/// support document changes.
+fn to_lsp_workspace_edit(edit: vhdl_lang::WorkspaceEdit) -> WorkspaceEdit {
+    if edit.created_files.is_empty() {
+        let changes: HashMap<Url, Vec<TextEdit>> = edit
+            .changes
+            .into_iter()
+            .map(|(file_name, edits)| (file_name_to_uri(&file_name), to_lsp_text_edits(edits)))
+            .collect();
+        return WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        };
+    }
+
+    let mut operations: Vec<DocumentChangeOperation> = edit
+        .created_files
+        .iter()
+        .map(|file_name| {
+            DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                uri: file_name_to_uri(file_name),
+                options: None,
+                annotation_id: None,
+            }))
+        })
+        .collect();
+    operations.extend(edit.changes.into_iter().map(|(file_name, edits)| {
+        DocumentChangeOperation::Edit(TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier {
+                uri: file_name_to_uri(&file_name),
+                version: None,
+            },
+            edits: to_lsp_text_edits(edits)
+                .into_iter()
+                .map(OneOf::Left)
+                .collect(),
+        })
+    }));
+    WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(operations)),
+        ..Default::default()
+    }
+}
+
+impl VHDLServer {
+    pub fn code_action(&mut self, params: &CodeActionParams) -> Option<CodeActionResponse> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+
+        let actions = self
+            .project
+            .list_code_actions(&source, from_lsp_range(params.range))
+            .into_iter()
+            .map(|action| {
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: action.title,
+                    kind: Some(to_lsp_code_action_kind(action.kind)),
+                    edit: Some(to_lsp_workspace_edit(action.edit)),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Some(actions)
+    }
+
+    pub fn execute_command(&mut self, params: &ExecuteCommandParams) -> Option<serde_json::Value> {
+        match params.command.as_str() {
+            SYNCHRONIZE_INSTANTIATIONS => {
+                self.synchronize_instantiations();
+                None
+            }
+            NORMALIZE_CASE => {
+                self.normalize_case();
+                None
+            }
+            EXPORT_IP_XACT_COMPONENT => self.export_ip_xact_component(&params.arguments),
+            GENERATE_TESTBENCH => self.generate_testbench(&params.arguments),
+            RUN_VUNIT_TEST => {
+                self.run_vunit_test(&params.arguments);
+                None
+            }
+            ELABORATE => {
+                self.elaborate(&params.arguments);
+                None
+            }
+            command => {
+                self.message(Message::error(format!("Unknown command '{command}'")));
+                None
+            }
+        }
+    }
+
+    fn synchronize_instantiations(&mut self) {
+        let result = self.project.synchronize_instantiations();
+        for stale in result.stale.iter() {
+            self.message(Message::warning(format!(
+                "{}:{}: {}",
+                stale.pos.file_name().to_string_lossy(),
+                stale.pos.start().line + 1,
+                stale.message
+            )));
+        }
+        if !result.edit.changes.is_empty() {
+            self.rpc.send_request(
+                "workspace/applyEdit",
+                ApplyWorkspaceEditParams {
+                    label: Some("Synchronize instantiations".to_owned()),
+                    edit: to_lsp_workspace_edit(result.edit),
+                },
+            );
+        }
+    }
+
+    fn normalize_case(&mut self) {
+        let edit = self.project.normalize_case();
+        if edit.changes.is_empty() {
+            self.message(Message::info(
+                "The casing of the project is already normalized",
+            ));
+        } else {
+            self.rpc.send_request(
+                "workspace/applyEdit",
+                ApplyWorkspaceEditParams {
+                    label: Some("Normalize case".to_owned()),
+                    edit: to_lsp_workspace_edit(edit),
+                },
+            );
+        }
+    }
+
+    /// Writes the IP-XACT description of the entity at the position given as argument
+    /// to a file next to the source and returns the XML
+    fn export_ip_xact_component(
+        &mut self,
+        arguments: &[serde_json::Value],
+    ) -> Option<serde_json::Value> {
+        let Some(params) = arguments
+            .first()
+            .and_then(|arg| serde_json::from_value::<TextDocumentPositionParams>(arg.clone()).ok())
+        else {
+            self.message(Message::error(format!(
+                "Expected a text document position as argument of '{EXPORT_IP_XACT_COMPONENT}'"
+            )));
+            return None;
+        };
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let Some(component) = self
+            .project
+            .export_ip_xact_component(&source, from_lsp_pos(params.position))
+        else {
+            self.message(Message::warning("No entity found at the cursor"));
+            return None;
+        };
+
+        let file_name = source
+            .file_name()
+            .with_file_name(format!("{}.xml", component.name));
+        if file_name.exists() {
+            self.message(Message::error(format!(
+                "Cannot export IP-XACT component, '{}' already exists",
+                file_name.to_string_lossy()
+            )));
+        } else {
+            let mut edit = vhdl_lang::WorkspaceEdit::default();
+            edit.create(&file_name);
+            edit.add(
+                &file_name,
+                vhdl_lang::TextEdit::insert(vhdl_lang::Position::new(0, 0), component.xml.clone()),
+            );
+            self.rpc.send_request(
+                "workspace/applyEdit",
+                ApplyWorkspaceEditParams {
+                    label: Some(format!("Export '{}' as IP-XACT component", component.name)),
+                    edit: to_lsp_workspace_edit(edit),
+                },
+            );
+        }
+        Some(serde_json::Value::String(component.xml))
+    }
+
+    /// Writes a testbench for the entity at the position given as argument
+    /// to a file next to the source and returns the VHDL code of the testbench
+    fn generate_testbench(&mut self, arguments: &[serde_json::Value]) -> Option<serde_json::Value> {
+        let Some(params) = arguments
+            .first()
+            .and_then(|arg| serde_json::from_value::<TextDocumentPositionParams>(arg.clone()).ok())
+        else {
+            self.message(Message::error(format!(
+                "Expected a text document position as argument of '{GENERATE_TESTBENCH}'"
+            )));
+            return None;
+        };
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let Some(testbench) = self
+            .project
+            .generate_testbench_at(&source, from_lsp_pos(params.position))
+        else {
+            self.message(Message::warning("No entity found at the cursor"));
+            return None;
+        };
+
+        let file_name = source
+            .file_name()
+            .with_file_name(format!("{}.vhd", testbench.name));
+        if file_name.exists() {
+            self.message(Message::error(format!(
+                "Cannot generate testbench, '{}' already exists",
+                file_name.to_string_lossy()
+            )));
+        } else {
+            let mut edit = vhdl_lang::WorkspaceEdit::default();
+            edit.create(&file_name);
+            edit.add(
+                &file_name,
+                vhdl_lang::TextEdit::insert(vhdl_lang::Position::new(0, 0), testbench.text.clone()),
+            );
+            self.rpc.send_request(
+                "workspace/applyEdit",
+                ApplyWorkspaceEditParams {
+                    label: Some(format!("Generate testbench '{}'", testbench.name)),
+                    edit: to_lsp_workspace_edit(edit),
+                },
+            );
+        }
+        Some(serde_json::Value::String(testbench.text))
+    }
+}
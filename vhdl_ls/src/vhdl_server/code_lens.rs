@@ -0,0 +1,101 @@
+use crate::vhdl_server::{srcpos_to_location, to_lsp_range, uri_to_file_name, VHDLServer};
+use lsp_types::{CodeLens, CodeLensParams, Command};
+use vhdl_lang::{AnyEntKind, Design, EntRef, Overloaded};
+
+/// What the code lens above a declaration counts
+enum LensKind {
+    Instantiations,
+    References,
+}
+
+fn lens_kind(ent: EntRef<'_>) -> Option<LensKind> {
+    // Bodies share the lens of their declaration
+    if ent.declaration().id() != ent.id() {
+        return None;
+    }
+    match ent.kind() {
+        AnyEntKind::Design(Design::Entity(..)) => Some(LensKind::Instantiations),
+        AnyEntKind::Design(Design::Package(..) | Design::UninstPackage(..)) => {
+            Some(LensKind::References)
+        }
+        AnyEntKind::Overloaded(
+            Overloaded::SubprogramDecl(_)
+            | Overloaded::Subprogram(_)
+            | Overloaded::UninstSubprogramDecl(..)
+            | Overloaded::UninstSubprogram(..),
+        ) => Some(LensKind::References),
+        _ => None,
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+impl VHDLServer {
+    /// Returns the lenses to run VUnit test cases and unresolved lenses above the entities,
+    /// packages and subprograms of a document.
+    /// The counts of the latter are computed when the lens is resolved.
+    pub fn code_lens(&self, params: &CodeLensParams) -> Option<Vec<CodeLens>> {
+        let file_name = uri_to_file_name(&params.text_document.uri);
+        let mut lenses = self.vunit_code_lenses(&file_name);
+        if let Some(source) = self.project.get_source(&file_name) {
+            lenses.extend(
+                self.project
+                    .find_all_declarations_in_source(&source)
+                    .into_iter()
+                    .filter(|ent| lens_kind(ent).is_some())
+                    .filter_map(|ent| {
+                        Some(CodeLens {
+                            range: to_lsp_range(ent.decl_pos()?.range()),
+                            command: None,
+                            data: Some(serde_json::Value::from(ent.id().to_raw())),
+                        })
+                    }),
+            );
+        }
+        Some(lenses)
+    }
+
+    /// Counts the instantiations or references of the declaration of a code lens.
+    /// Clicking the lens shows the counted locations.
+    pub fn code_lens_resolve(&self, lens: &CodeLens) -> CodeLens {
+        let Some(id) = self.entity_id_from_data(lens.data.as_ref()) else {
+            return lens.clone();
+        };
+        let ent = self.project.get_ent(id);
+        let Some(decl_pos) = ent.decl_pos() else {
+            return lens.clone();
+        };
+        let (title, positions) = match lens_kind(ent) {
+            Some(LensKind::Instantiations) => {
+                let positions = self.project.find_instantiations(id);
+                (plural(positions.len(), "instantiation"), positions)
+            }
+            Some(LensKind::References) => {
+                let positions = self.project.find_all_usages(id);
+                (plural(positions.len(), "reference"), positions)
+            }
+            None => return lens.clone(),
+        };
+        let location = srcpos_to_location(decl_pos);
+        let locations: Vec<_> = positions.iter().map(srcpos_to_location).collect();
+        CodeLens {
+            range: lens.range,
+            command: Some(Command {
+                title,
+                command: "editor.action.showReferences".to_owned(),
+                arguments: Some(vec![
+                    serde_json::to_value(location.uri).unwrap(),
+                    serde_json::to_value(location.range.start).unwrap(),
+                    serde_json::to_value(locations).unwrap(),
+                ]),
+            }),
+            data: lens.data.clone(),
+        }
+    }
+}
@@ -1,130 +1,84 @@
-use crate::vhdl_server::{from_lsp_pos, uri_to_file_name, VHDLServer};
+use crate::vhdl_server::{from_lsp_pos, to_lsp_range, uri_to_file_name, VHDLServer};
 use lsp_types::{
     CompletionItem, CompletionItemKind, CompletionList, CompletionParams, Documentation,
-    InsertTextFormat, MarkupContent, MarkupKind,
+    InsertTextFormat, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel,
+    SignatureHelp, SignatureHelpParams, SignatureInformation, TextEdit,
 };
-use vhdl_lang::ast::{Designator, ObjectClass};
-use vhdl_lang::{kind_str, AnyEntKind, Design, EntRef, InterfaceEnt, Overloaded};
+use vhdl_lang::{instantiation_snippet, subprogram_call_snippet, CompletionKind};
 
 impl VHDLServer {
     fn completion_item_to_lsp_item(
         &self,
         item: vhdl_lang::CompletionItem,
+        work_libraries: &[String],
     ) -> lsp_types::CompletionItem {
-        match item {
-            vhdl_lang::CompletionItem::Simple(ent) => entity_to_completion_item(ent),
-            vhdl_lang::CompletionItem::Work => CompletionItem {
-                label: "work".to_string(),
-                detail: Some("work library".to_string()),
-                kind: Some(CompletionItemKind::MODULE),
-                insert_text: Some("work".to_string()),
-                ..Default::default()
-            },
-            vhdl_lang::CompletionItem::Formal(ent) => {
-                let mut item = entity_to_completion_item(ent);
+        let mut lsp_item = CompletionItem {
+            insert_text: Some(item.label.clone()),
+            label: item.label,
+            kind: Some(to_lsp_completion_kind(item.kind)),
+            detail: Some(item.detail),
+            documentation: item.documentation.map(|doc| {
+                Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: doc,
+                })
+            }),
+            // Resolves the declaration of the entity once the item is selected
+            data: item.id.map(|id| serde_json::Value::from(id.to_raw())),
+            ..Default::default()
+        };
+        match item.option {
+            vhdl_lang::CompletionOption::Formal(ent) => {
                 if self.client_supports_snippets() {
-                    item.insert_text_format = Some(InsertTextFormat::SNIPPET);
-                    item.insert_text = Some(format!("{} => $1,", item.insert_text.unwrap()));
+                    lsp_item.insert_text_format = Some(InsertTextFormat::SNIPPET);
+                    lsp_item.insert_text = Some(format!("{} => $1,", ent.designator));
                 }
-                item
             }
-            vhdl_lang::CompletionItem::Overloaded(desi, count) => CompletionItem {
-                label: desi.to_string(),
-                detail: Some(format!("+{count} overloaded")),
-                kind: match desi {
-                    Designator::Identifier(_) => Some(CompletionItemKind::FUNCTION),
-                    Designator::OperatorSymbol(_) => Some(CompletionItemKind::OPERATOR),
-                    _ => None,
-                },
-                insert_text: Some(desi.to_string()),
-                ..Default::default()
-            },
-            vhdl_lang::CompletionItem::Keyword(kind) => CompletionItem {
-                label: kind_str(kind).to_string(),
-                detail: Some(kind_str(kind).to_string()),
-                insert_text: Some(kind_str(kind).to_string()),
-                kind: Some(CompletionItemKind::KEYWORD),
-                ..Default::default()
-            },
-            vhdl_lang::CompletionItem::Instantiation(ent, architectures) => {
-                let work_name = "work";
-
-                let library_names = if let Some(lib_name) = ent.library_name() {
-                    vec![work_name.to_string(), lib_name.name().to_string()]
-                } else {
-                    vec![work_name.to_string()]
-                };
-                let (region, is_component_instantiation) = match ent.kind() {
-                    AnyEntKind::Design(Design::Entity(_, region)) => (region, false),
-                    AnyEntKind::Component(region) => (region, true),
-                    // should never happen but better return some value instead of crashing
-                    _ => return entity_to_completion_item(ent),
-                };
-                let template = if self.client_supports_snippets() {
-                    let mut line = if is_component_instantiation {
-                        format!("${{1:{}_inst}}: {}", ent.designator, ent.designator)
-                    } else {
-                        format!(
-                            "${{1:{}_inst}}: entity ${{2|{}|}}.{}",
-                            ent.designator,
-                            library_names.join(","),
-                            ent.designator
-                        )
-                    };
-                    if architectures.len() > 1 {
-                        line.push_str("(${3|");
-                        for (i, architecture) in architectures.iter().enumerate() {
-                            line.push_str(&architecture.designator().to_string());
-                            if i != architectures.len() - 1 {
-                                line.push(',')
-                            }
-                        }
-                        line.push_str("|})");
-                    }
-                    let (ports, generics) = region.ports_and_generics();
-                    let mut idx = 4;
-                    let mut interface_ent = |elements: Vec<InterfaceEnt>, purpose: &str| {
-                        line += &*format!("\n {} map(\n", purpose);
-                        for (i, generic) in elements.iter().enumerate() {
-                            line += &*format!(
-                                "    {} => ${{{}:{}}}",
-                                generic.designator, idx, generic.designator
-                            );
-                            idx += 1;
-                            if i != elements.len() - 1 {
-                                line += ","
-                            }
-                            line += "\n";
-                        }
-                        line += ")";
-                    };
-                    if !generics.is_empty() {
-                        interface_ent(generics, "generic");
+            vhdl_lang::CompletionOption::Instantiation(ent, architectures) => {
+                // Entities of the library of the current file can be referred to using 'work'
+                let library_names = match ent.library_name() {
+                    Some(lib_name) if work_libraries.contains(&lib_name.name_utf8()) => {
+                        vec!["work".to_owned(), lib_name.name_utf8()]
                     }
-                    if !ports.is_empty() {
-                        interface_ent(ports, "port");
-                    }
-                    line += ";";
-                    line
+                    Some(lib_name) => vec![lib_name.name_utf8()],
+                    None => vec!["work".to_owned()],
+                };
+                lsp_item.insert_text = if self.client_supports_snippets() {
+                    // should never be `None` but better return some value instead of crashing
+                    instantiation_snippet(ent, &architectures, &library_names)
                 } else {
-                    format!("{}", ent.designator)
+                    None
                 };
-                CompletionItem {
-                    label: format!("{} instantiation", ent.designator),
-                    insert_text: Some(template),
-                    insert_text_format: Some(InsertTextFormat::SNIPPET),
-                    kind: Some(CompletionItemKind::MODULE),
-                    ..Default::default()
+                match lsp_item.insert_text {
+                    Some(_) => lsp_item.insert_text_format = Some(InsertTextFormat::SNIPPET),
+                    None => lsp_item.insert_text = Some(ent.designator.to_string()),
                 }
             }
-            vhdl_lang::CompletionItem::Attribute(attribute) => CompletionItem {
-                label: format!("{attribute}"),
-                detail: Some(format!("{attribute}")),
-                insert_text: Some(format!("{attribute}")),
-                kind: Some(CompletionItemKind::REFERENCE),
-                ..Default::default()
-            },
+            vhdl_lang::CompletionOption::Subprogram(ent) => {
+                if self.client_supports_snippets() {
+                    if let Some(snippet) = subprogram_call_snippet(ent) {
+                        lsp_item.insert_text_format = Some(InsertTextFormat::SNIPPET);
+                        lsp_item.insert_text = Some(snippet);
+                    }
+                }
+            }
+            vhdl_lang::CompletionOption::Unimported(_, edit) => {
+                lsp_item.additional_text_edits = Some(vec![TextEdit {
+                    range: to_lsp_range(edit.range),
+                    new_text: edit.new_text,
+                }]);
+            }
+            vhdl_lang::CompletionOption::Snippet(snippet) => {
+                lsp_item.insert_text = Some(snippet.body.to_string());
+                lsp_item.insert_text_format = Some(InsertTextFormat::SNIPPET);
+            }
+            vhdl_lang::CompletionOption::Simple(_)
+            | vhdl_lang::CompletionOption::Overloaded(..)
+            | vhdl_lang::CompletionOption::Keyword(_)
+            | vhdl_lang::CompletionOption::Work
+            | vhdl_lang::CompletionOption::Attribute(_) => {}
         }
+        lsp_item
     }
 
     /// Called when the client requests a completion.
@@ -147,11 +101,28 @@ impl VHDLServer {
         //      => keyword `architecture`, ...
 
         // 3) Run the parser until the point of the cursor. Then exit with possible completions
+        let work_libraries = self
+            .project
+            .library_mapping_of(&source)
+            .iter()
+            .map(|library| library.name_utf8())
+            .collect::<Vec<_>>();
         let options = self
             .project
             .list_completion_options(&source, cursor)
             .into_iter()
-            .map(|item| self.completion_item_to_lsp_item(item))
+            // Templates cannot be inserted without snippet support
+            .filter(|item| {
+                self.client_supports_snippets()
+                    || !matches!(item.option, vhdl_lang::CompletionOption::Snippet(_))
+            })
+            .enumerate()
+            .map(|(idx, item)| {
+                let mut item = self.completion_item_to_lsp_item(item, &work_libraries);
+                // The options are ordered by relevance
+                item.sort_text = Some(format!("{idx:05}"));
+                item
+            })
             .collect();
 
         CompletionList {
@@ -162,65 +133,95 @@ impl VHDLServer {
 
     pub fn resolve_completion_item(&mut self, params: &CompletionItem) -> CompletionItem {
         let mut params = params.clone();
-        let eid = params
-            .data
-            .clone()
-            .and_then(|val| serde_json::from_value::<usize>(val).ok())
-            .and_then(|raw| self.project.entity_id_from_raw(raw));
-        if let Some(id) = eid {
+        if let Some(id) = self.entity_id_from_data(params.data.as_ref()) {
             if let Some(text) = self.project.format_entity(id) {
+                let mut value = format!("```vhdl\n{text}\n```");
+                // The documentation of the item is the comment above the declaration
+                if let Some(Documentation::MarkupContent(MarkupContent { value: doc, .. })) =
+                    &params.documentation
+                {
+                    value = format!("{doc}\n\n{value}");
+                }
                 params.documentation = Some(Documentation::MarkupContent(MarkupContent {
                     kind: MarkupKind::Markdown,
-                    value: format!("```vhdl\n{text}\n```"),
+                    value,
                 }));
             }
         }
         params
     }
+
+    /// Shows the signatures of the subprogram that is called at the cursor
+    /// or the generics or ports of the unit that is instantiated at the cursor
+    pub fn signature_help(&self, params: &SignatureHelpParams) -> Option<SignatureHelp> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let help = self
+            .project
+            .signature_help(&source, from_lsp_pos(position.position))?;
+        let active_parameter = help
+            .signatures
+            .get(help.active_signature)
+            .and_then(|signature| signature.active_parameter)
+            .map(|idx| idx as u32);
+        Some(SignatureHelp {
+            signatures: help
+                .signatures
+                .into_iter()
+                .map(to_signature_information)
+                .collect(),
+            active_signature: Some(help.active_signature as u32),
+            active_parameter,
+        })
+    }
 }
 
-fn entity_to_completion_item(ent: EntRef) -> CompletionItem {
-    CompletionItem {
-        label: ent.designator.to_string(),
-        detail: Some(ent.describe()),
-        kind: Some(entity_kind_to_completion_kind(ent.kind())),
-        data: serde_json::to_value(ent.id.to_raw()).ok(),
-        insert_text: Some(ent.designator.to_string()),
-        ..Default::default()
+fn to_signature_information(signature: vhdl_lang::SignatureInformation) -> SignatureInformation {
+    // Offsets within the label are counted in UTF-16 code units
+    let offset = |idx: usize| signature.label[..idx].encode_utf16().count() as u32;
+    SignatureInformation {
+        parameters: Some(
+            signature
+                .parameters
+                .iter()
+                .map(|range| ParameterInformation {
+                    label: ParameterLabel::LabelOffsets([offset(range.start), offset(range.end)]),
+                    documentation: None,
+                })
+                .collect(),
+        ),
+        documentation: signature.ent.documentation().map(|doc| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: doc.to_owned(),
+            })
+        }),
+        active_parameter: signature.active_parameter.map(|idx| idx as u32),
+        label: signature.label,
     }
 }
 
-fn entity_kind_to_completion_kind(kind: &AnyEntKind) -> CompletionItemKind {
+fn to_lsp_completion_kind(kind: CompletionKind) -> CompletionItemKind {
     match kind {
-        AnyEntKind::ExternalAlias { .. } | AnyEntKind::ObjectAlias { .. } => {
-            CompletionItemKind::FIELD
-        }
-        AnyEntKind::File(_) | AnyEntKind::InterfaceFile(_) => CompletionItemKind::FILE,
-        AnyEntKind::Component(_) => CompletionItemKind::MODULE,
-        AnyEntKind::Attribute(_) => CompletionItemKind::REFERENCE,
-        AnyEntKind::Overloaded(overloaded) => match overloaded {
-            Overloaded::SubprogramDecl(_)
-            | Overloaded::Subprogram(_)
-            | Overloaded::UninstSubprogramDecl(..)
-            | Overloaded::UninstSubprogram(..)
-            | Overloaded::InterfaceSubprogram(_) => CompletionItemKind::FUNCTION,
-            Overloaded::EnumLiteral(_) => CompletionItemKind::ENUM_MEMBER,
-            Overloaded::Alias(_) => CompletionItemKind::FIELD,
-        },
-        AnyEntKind::Type(_) => CompletionItemKind::TYPE_PARAMETER,
-        AnyEntKind::ElementDeclaration(_) => CompletionItemKind::FIELD,
-        AnyEntKind::Concurrent(_) => CompletionItemKind::MODULE,
-        AnyEntKind::Sequential(_) => CompletionItemKind::MODULE,
-        AnyEntKind::Object(object) => match object.class {
-            ObjectClass::Signal => CompletionItemKind::EVENT,
-            ObjectClass::Constant => CompletionItemKind::CONSTANT,
-            ObjectClass::Variable | ObjectClass::SharedVariable => CompletionItemKind::VARIABLE,
-        },
-        AnyEntKind::LoopParameter(_) => CompletionItemKind::MODULE,
-        AnyEntKind::PhysicalLiteral(_) => CompletionItemKind::UNIT,
-        AnyEntKind::DeferredConstant(_) => CompletionItemKind::CONSTANT,
-        AnyEntKind::Library => CompletionItemKind::MODULE,
-        AnyEntKind::Design(_) => CompletionItemKind::MODULE,
-        AnyEntKind::View(_) => CompletionItemKind::INTERFACE,
+        CompletionKind::Keyword => CompletionItemKind::KEYWORD,
+        CompletionKind::Library
+        | CompletionKind::DesignUnit
+        | CompletionKind::Component
+        | CompletionKind::Label => CompletionItemKind::MODULE,
+        CompletionKind::Type => CompletionItemKind::TYPE_PARAMETER,
+        CompletionKind::Signal => CompletionItemKind::EVENT,
+        CompletionKind::Constant => CompletionItemKind::CONSTANT,
+        CompletionKind::Variable => CompletionItemKind::VARIABLE,
+        CompletionKind::File => CompletionItemKind::FILE,
+        CompletionKind::Subprogram => CompletionItemKind::FUNCTION,
+        CompletionKind::Operator => CompletionItemKind::OPERATOR,
+        CompletionKind::EnumLiteral => CompletionItemKind::ENUM_MEMBER,
+        CompletionKind::PhysicalUnit => CompletionItemKind::UNIT,
+        CompletionKind::Alias | CompletionKind::RecordElement => CompletionItemKind::FIELD,
+        CompletionKind::Attribute => CompletionItemKind::REFERENCE,
+        CompletionKind::View => CompletionItemKind::INTERFACE,
+        CompletionKind::Snippet => CompletionItemKind::SNIPPET,
     }
 }
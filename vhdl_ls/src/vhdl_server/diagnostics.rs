@@ -9,7 +9,8 @@ use vhdl_lang::{Diagnostic, Severity, SeverityMap};
 
 impl VHDLServer {
     pub fn publish_diagnostics(&mut self) {
-        let diagnostics = self.project.analyse();
+        let mut diagnostics = self.project.analyse();
+        diagnostics.extend(self.elaboration_diagnostics.iter().cloned());
 
         if self.settings.no_lint {
             return;
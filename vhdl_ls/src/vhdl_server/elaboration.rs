@@ -0,0 +1,68 @@
+use crate::vhdl_server::VHDLServer;
+use serde_json::Value;
+use std::io;
+use std::path::Path;
+use vhdl_lang::{GhdlOutput, Message, GHDL_WORKDIR};
+
+/// Command that elaborates a top unit given as `library.entity` using GHDL
+pub const ELABORATE: &str = "vhdl_ls.elaborate";
+
+impl VHDLServer {
+    /// Elaborates the top unit using GHDL in the background and publishes the errors of GHDL
+    /// as diagnostics when it has finished.
+    /// The diagnostics are kept until the next elaboration or until a file is changed
+    /// as their positions would be outdated.
+    pub fn elaborate(&mut self, arguments: &[Value]) {
+        let Some(top) = arguments.first().and_then(|arg| arg.as_str()) else {
+            self.message(Message::error(format!(
+                "Expected the top unit as argument of '{ELABORATE}'"
+            )));
+            return;
+        };
+        let Some(directory) = self.config_file.as_ref().and_then(|file| file.parent()) else {
+            self.message(Message::error(
+                "Cannot elaborate without a vhdl_ls.toml file in the workspace root",
+            ));
+            return;
+        };
+        let workdir = directory.join(GHDL_WORKDIR);
+        let elaboration = match self
+            .project
+            .ghdl_elaboration(Path::new("ghdl"), top, &workdir)
+        {
+            Ok(elaboration) => elaboration,
+            Err(err) => {
+                self.elaboration_finished(top, Err(err));
+                return;
+            }
+        };
+        let top = top.to_owned();
+        self.spawn_background(
+            ELABORATE,
+            move || elaboration.run(),
+            move |server, output| server.elaboration_finished(&top, output),
+        );
+    }
+
+    fn elaboration_finished(&mut self, top: &str, output: io::Result<GhdlOutput>) {
+        match output {
+            Ok(output) => {
+                let elaboration = self.project.elaboration_of_ghdl_output(&output);
+                for message in elaboration.messages.iter() {
+                    self.message(Message::warning(message));
+                }
+                if elaboration.success {
+                    self.message(Message::info(format!("Elaborated '{top}' successfully")));
+                } else {
+                    self.message(Message::error(format!("Elaboration of '{top}' failed")));
+                }
+                self.elaboration_diagnostics = elaboration.diagnostics;
+            }
+            Err(err) => {
+                self.message(Message::error(format!("Cannot run GHDL: {err}")));
+                self.elaboration_diagnostics.clear();
+            }
+        }
+        self.publish_diagnostics();
+    }
+}
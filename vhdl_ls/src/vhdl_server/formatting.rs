@@ -0,0 +1,96 @@
+use crate::vhdl_server::{
+    from_lsp_pos, from_lsp_range, to_lsp_range, uri_to_file_name, VHDLServer,
+};
+use lsp_types::{
+    DocumentFormattingParams, DocumentOnTypeFormattingParams, DocumentRangeFormattingParams,
+    FormattingOptions, TextEdit,
+};
+use vhdl_lang::{FormatOptions, Position, Range, Source};
+
+/// The range of the whole document, including a final line break
+fn document_range(source: &Source) -> Range {
+    let contents = source.contents();
+    let num_lines = contents.num_lines();
+    let end = match contents.get_line(num_lines.saturating_sub(1)) {
+        Some(line) if line.ends_with('\n') => Position::new(num_lines as u32, 0),
+        _ => contents.end(),
+    };
+    Range::new(contents.start(), end)
+}
+
+impl VHDLServer {
+    /// The formatting style of the project configuration,
+    /// or the indentation settings of the client when no style is configured
+    fn format_options(&self, options: &FormattingOptions) -> FormatOptions {
+        self.project
+            .format_options()
+            .unwrap_or_else(|| FormatOptions {
+                indent_width: options.tab_size as usize,
+                use_tabs: !options.insert_spaces,
+                ..FormatOptions::default()
+            })
+    }
+
+    /// Formats a whole document.
+    /// Documents with syntax errors are left as they are.
+    pub fn text_document_formatting(
+        &self,
+        params: &DocumentFormattingParams,
+    ) -> Option<Vec<TextEdit>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let new_text = self
+            .project
+            .format_source(&source, &self.format_options(&params.options))?;
+        Some(vec![TextEdit {
+            range: to_lsp_range(document_range(&source)),
+            new_text,
+        }])
+    }
+
+    /// Formats the declarations and statements that intersect the range of a document
+    pub fn text_document_range_formatting(
+        &self,
+        params: &DocumentRangeFormattingParams,
+    ) -> Option<Vec<TextEdit>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let edit = self.project.format_range(
+            &source,
+            from_lsp_range(params.range),
+            &self.format_options(&params.options),
+        )?;
+        Some(vec![TextEdit {
+            range: to_lsp_range(edit.range),
+            new_text: edit.new_text,
+        }])
+    }
+
+    /// Re-indents the lines around the cursor after `;`, a newline or `end` was typed
+    pub fn text_document_on_type_formatting(
+        &self,
+        params: &DocumentOnTypeFormattingParams,
+    ) -> Option<Vec<TextEdit>> {
+        let position = &params.text_document_position;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let edits = self.project.format_on_type(
+            &source,
+            from_lsp_pos(position.position),
+            &params.ch,
+            &self.format_options(&params.options),
+        );
+        Some(
+            edits
+                .into_iter()
+                .map(|edit| TextEdit {
+                    range: to_lsp_range(edit.range),
+                    new_text: edit.new_text,
+                })
+                .collect(),
+        )
+    }
+}
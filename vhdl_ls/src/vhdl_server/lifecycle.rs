@@ -1,3 +1,9 @@
+use crate::vhdl_server::code_action::{
+    EXPORT_IP_XACT_COMPONENT, GENERATE_TESTBENCH, NORMALIZE_CASE, SYNCHRONIZE_INSTANTIATIONS,
+};
+use crate::vhdl_server::elaboration::ELABORATE;
+use crate::vhdl_server::semantic_tokens::semantic_tokens_legend;
+use crate::vhdl_server::vunit::RUN_VUNIT_TEST;
 use crate::vhdl_server::{NonProjectFileHandling, VHDLServer};
 use lsp_types::*;
 use serde_json::Value;
@@ -21,6 +27,7 @@ impl VHDLServer {
 
     /// Register capabilities on the client side:
     /// - watch workspace config file for changes
+    /// - provide the type hierarchy
     fn register_capabilities(&mut self) {
         if self.client_supports_did_change_watched_files() {
             let register_options = DidChangeWatchedFilesRegistrationOptions {
@@ -38,6 +45,21 @@ impl VHDLServer {
             };
             self.rpc.send_request("client/registerCapability", params);
         }
+        // The server capabilities of this version of the protocol types
+        // cannot announce the type hierarchy, it is registered dynamically instead
+        if self.client_supports_type_hierarchy() {
+            let params = RegistrationParams {
+                registrations: vec![Registration {
+                    id: "textDocument/prepareTypeHierarchy".to_owned(),
+                    method: "textDocument/prepareTypeHierarchy".to_owned(),
+                    register_options: serde_json::to_value(
+                        TypeHierarchyRegistrationOptions::default(),
+                    )
+                    .ok(),
+                }],
+            };
+            self.rpc.send_request("client/registerCapability", params);
+        }
     }
 
     pub fn initialized_notification(&mut self) {
@@ -73,6 +95,56 @@ impl VHDLServer {
             workspace_symbol_provider: Some(OneOf::Left(true)),
             document_symbol_provider: Some(OneOf::Left(true)),
             document_highlight_provider: Some(OneOf::Left(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
+                retrigger_characters: None,
+                work_done_progress_options: Default::default(),
+            }),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: semantic_tokens_legend(),
+                    range: None,
+                    full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                    work_done_progress_options: Default::default(),
+                }),
+            ),
+            call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+            linked_editing_range_provider: Some(LinkedEditingRangeServerCapabilities::Simple(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![
+                    CodeActionKind::from("refactor.move"),
+                    CodeActionKind::REFACTOR_INLINE,
+                    CodeActionKind::REFACTOR_EXTRACT,
+                    CodeActionKind::REFACTOR_REWRITE,
+                    CodeActionKind::QUICKFIX,
+                    CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                ]),
+                ..Default::default()
+            })),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![
+                    SYNCHRONIZE_INSTANTIATIONS.to_owned(),
+                    NORMALIZE_CASE.to_owned(),
+                    EXPORT_IP_XACT_COMPONENT.to_owned(),
+                    GENERATE_TESTBENCH.to_owned(),
+                    RUN_VUNIT_TEST.to_owned(),
+                    ELABORATE.to_owned(),
+                ],
+                ..Default::default()
+            }),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                first_trigger_character: ";".to_owned(),
+                // `d` completes the keyword `end`
+                more_trigger_character: Some(vec!["\n".to_owned(), "d".to_owned()]),
+            }),
+            code_lens_provider: Some(CodeLensOptions {
+                resolve_provider: Some(true),
+            }),
             completion_provider: Some(CompletionOptions {
                 resolve_provider: Some(true),
                 trigger_characters: Some(trigger_chars),
@@ -1,11 +1,45 @@
 use crate::vhdl_server::{
-    from_lsp_pos, srcpos_to_location, to_lsp_range, uri_to_file_name, VHDLServer,
+    file_name_to_uri, from_lsp_pos, srcpos_to_location, to_lsp_range, uri_to_file_name, VHDLServer,
 };
 use lsp_types::{
-    PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+    DocumentChangeOperation, DocumentChanges, OneOf, OptionalVersionedTextDocumentIdentifier,
+    PrepareRenameResponse, RenameFile, RenameParams, ResourceOp, TextDocumentEdit,
+    TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
 };
 use std::collections::HashMap;
+use std::path::PathBuf;
 use vhdl_lang::ast::Designator;
+use vhdl_lang::{AnyEntKind, Design, EntRef, Message};
+
+/// When renaming a design unit that is declared in a file with the same name,
+/// e.g., entity `foo` in `foo.vhd`, the file is renamed as well.
+fn renamed_file_name(ent: EntRef<'_>, new_name: &str) -> Option<(PathBuf, PathBuf)> {
+    if !matches!(
+        ent.kind(),
+        AnyEntKind::Design(
+            Design::Entity(..)
+                | Design::Package(..)
+                | Design::UninstPackage(..)
+                | Design::PackageInstance(..)
+                | Design::Configuration
+        )
+    ) {
+        return None;
+    }
+    let file_name = ent.decl_pos()?.source.file_name();
+    let stem = file_name.file_stem()?.to_str()?;
+    if !stem.eq_ignore_ascii_case(&ent.designator().to_string()) {
+        return None;
+    }
+    let mut new_file_name = file_name.with_file_name(new_name);
+    if let Some(extension) = file_name.extension() {
+        new_file_name.set_extension(extension);
+    }
+    if new_file_name.exists() {
+        return None;
+    }
+    Some((file_name.to_owned(), new_file_name))
+}
 
 impl VHDLServer {
     pub fn prepare_rename(
@@ -40,9 +74,14 @@ impl VHDLServer {
             from_lsp_pos(params.text_document_position.position),
         )?;
 
+        if let Err(err) = self.project.check_rename(ent, &params.new_name) {
+            self.message(Message::error(format!("Cannot rename: {err}")));
+            return None;
+        }
+
         let mut changes: HashMap<Url, Vec<TextEdit>> = Default::default();
 
-        for srcpos in self.project.find_all_references(ent) {
+        for srcpos in self.project.find_all_rename_references(ent) {
             let loc = srcpos_to_location(&srcpos);
             changes.entry(loc.uri).or_default().push(TextEdit {
                 range: loc.range,
@@ -50,8 +89,38 @@ impl VHDLServer {
             });
         }
 
+        let renamed_file = if self.client_supports_rename_file() {
+            renamed_file_name(ent, &params.new_name)
+        } else {
+            None
+        };
+        let Some((old_file_name, new_file_name)) = renamed_file else {
+            return Some(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            });
+        };
+
+        // The text edits refer to the old file name and must be applied before the file is renamed
+        let mut operations: Vec<DocumentChangeOperation> = changes
+            .into_iter()
+            .map(|(uri, edits)| {
+                DocumentChangeOperation::Edit(TextDocumentEdit {
+                    text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                    edits: edits.into_iter().map(OneOf::Left).collect(),
+                })
+            })
+            .collect();
+        operations.push(DocumentChangeOperation::Op(ResourceOp::Rename(
+            RenameFile {
+                old_uri: file_name_to_uri(&old_file_name),
+                new_uri: file_name_to_uri(&new_file_name),
+                options: None,
+                annotation_id: None,
+            },
+        )));
         Some(WorkspaceEdit {
-            changes: Some(changes),
+            document_changes: Some(DocumentChanges::Operations(operations)),
             ..Default::default()
         })
     }
@@ -0,0 +1,291 @@
+use crate::vhdl_server::{uri_to_file_name, VHDLServer};
+use lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensDelta,
+    SemanticTokensDeltaParams, SemanticTokensEdit, SemanticTokensFullDeltaResult,
+    SemanticTokensLegend, SemanticTokensParams, SemanticTokensResult, Url,
+};
+use vhdl_lang::ast::{Designator, Mode, ObjectClass};
+use vhdl_lang::{
+    AnyEntKind, Concurrent, Design, EntRef, InterfaceMode, Object, Overloaded, Source, SrcPos, Type,
+};
+
+const TOKEN_TYPES: [SemanticTokenType; 13] = [
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::CLASS,
+    SemanticTokenType::ENUM,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::INTERFACE,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::ENUM_MEMBER,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::DECORATOR,
+];
+
+const TOKEN_MODIFIERS: [SemanticTokenModifier; 3] = [
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::READONLY,
+    SemanticTokenModifier::DEFAULT_LIBRARY,
+];
+
+/// Modifiers that are specific to VHDL,
+/// i.e., the class of an object and the mode of a port
+const VHDL_TOKEN_MODIFIERS: [&str; 6] = ["signal", "in", "out", "inout", "buffer", "linkage"];
+
+pub fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS
+            .iter()
+            .cloned()
+            .chain(
+                VHDL_TOKEN_MODIFIERS
+                    .iter()
+                    .map(|modifier| SemanticTokenModifier::new(modifier)),
+            )
+            .collect(),
+    }
+}
+
+fn token_type(typ: SemanticTokenType) -> u32 {
+    TOKEN_TYPES.iter().position(|t| *t == typ).unwrap() as u32
+}
+
+fn modifier_bit(name: &str) -> u32 {
+    let index = TOKEN_MODIFIERS
+        .iter()
+        .map(|modifier| modifier.as_str())
+        .chain(VHDL_TOKEN_MODIFIERS)
+        .position(|modifier| modifier == name)
+        .unwrap();
+    1 << index
+}
+
+fn mode_modifier(mode: Option<&InterfaceMode<'_>>) -> u32 {
+    match mode {
+        Some(InterfaceMode::Simple(mode)) => modifier_bit(match mode {
+            Mode::In => "in",
+            Mode::Out => "out",
+            Mode::InOut => "inout",
+            Mode::Buffer => "buffer",
+            Mode::Linkage => "linkage",
+        }),
+        Some(InterfaceMode::View(_)) | None => 0,
+    }
+}
+
+fn object_token(object: &Object<'_>) -> (SemanticTokenType, u32) {
+    let class = match object.class {
+        ObjectClass::Signal => modifier_bit("signal"),
+        ObjectClass::Constant => modifier_bit(SemanticTokenModifier::READONLY.as_str()),
+        ObjectClass::Variable | ObjectClass::SharedVariable => 0,
+    };
+    if object.is_port() {
+        (
+            SemanticTokenType::PARAMETER,
+            class | mode_modifier(object.mode()),
+        )
+    } else if object.is_generic() || object.is_param() {
+        (SemanticTokenType::PARAMETER, class)
+    } else {
+        (SemanticTokenType::VARIABLE, class)
+    }
+}
+
+/// The type and modifiers of an identifier that refers to a named entity
+fn classify(ent: EntRef<'_>) -> Option<(SemanticTokenType, u32)> {
+    let readonly = modifier_bit(SemanticTokenModifier::READONLY.as_str());
+    Some(match ent.kind() {
+        AnyEntKind::Object(object) => object_token(object),
+        AnyEntKind::ObjectAlias { base_object, .. } => object_token(base_object.object()),
+        AnyEntKind::ExternalAlias { .. } | AnyEntKind::File(_) => (SemanticTokenType::VARIABLE, 0),
+        AnyEntKind::InterfaceFile(_) => (SemanticTokenType::PARAMETER, 0),
+        AnyEntKind::LoopParameter(_)
+        | AnyEntKind::DeferredConstant(_)
+        | AnyEntKind::PhysicalLiteral(_) => (SemanticTokenType::VARIABLE, readonly),
+        AnyEntKind::Overloaded(Overloaded::EnumLiteral(_)) => (SemanticTokenType::ENUM_MEMBER, 0),
+        AnyEntKind::Overloaded(_) => (SemanticTokenType::FUNCTION, 0),
+        AnyEntKind::Type(typ) => (
+            match typ {
+                Type::Enum(_) => SemanticTokenType::ENUM,
+                Type::Record(_) => SemanticTokenType::STRUCT,
+                Type::Protected(..) => SemanticTokenType::CLASS,
+                Type::Interface => SemanticTokenType::TYPE_PARAMETER,
+                _ => SemanticTokenType::TYPE,
+            },
+            0,
+        ),
+        AnyEntKind::ElementDeclaration(_) => (SemanticTokenType::PROPERTY, 0),
+        AnyEntKind::Attribute(_) => (SemanticTokenType::DECORATOR, 0),
+        AnyEntKind::Component(_) => (SemanticTokenType::CLASS, 0),
+        AnyEntKind::View(..) => (SemanticTokenType::INTERFACE, 0),
+        AnyEntKind::Design(design) => (
+            match design {
+                Design::Entity(..) | Design::Architecture(..) | Design::Configuration => {
+                    SemanticTokenType::CLASS
+                }
+                _ => SemanticTokenType::NAMESPACE,
+            },
+            0,
+        ),
+        AnyEntKind::Library => (SemanticTokenType::NAMESPACE, 0),
+        AnyEntKind::Concurrent(Some(Concurrent::Instance)) => (SemanticTokenType::NAMESPACE, 0),
+        AnyEntKind::Concurrent(_) | AnyEntKind::Sequential(_) => return None,
+    })
+}
+
+fn is_default_library(ent: EntRef<'_>) -> bool {
+    ent.library_name()
+        .is_some_and(|library| matches!(library.name_utf8().as_str(), "std" | "ieee"))
+}
+
+/// Encodes the identifiers of a source relative to each other
+fn encode(identifiers: Vec<(SrcPos, EntRef<'_>)>) -> Vec<SemanticToken> {
+    let mut tokens = Vec::with_capacity(identifiers.len());
+    let mut previous = lsp_types::Position::default();
+    for (pos, ent) in identifiers {
+        let range = pos.range();
+        // Operator symbols and multi-line positions are not identifiers
+        if range.start.line != range.end.line
+            || matches!(ent.designator(), Designator::OperatorSymbol(_))
+        {
+            continue;
+        }
+        let Some((typ, mut modifiers)) = classify(ent) else {
+            continue;
+        };
+        if ent.decl_pos() == Some(&pos) {
+            modifiers |= modifier_bit(SemanticTokenModifier::DECLARATION.as_str());
+        }
+        if is_default_library(ent) {
+            modifiers |= modifier_bit(SemanticTokenModifier::DEFAULT_LIBRARY.as_str());
+        }
+
+        let delta_line = range.start.line - previous.line;
+        let delta_start = if delta_line == 0 {
+            range.start.character - previous.character
+        } else {
+            range.start.character
+        };
+        tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end.character - range.start.character,
+            token_type: token_type(typ),
+            token_modifiers_bitset: modifiers,
+        });
+        previous = lsp_types::Position::new(range.start.line, range.start.character);
+    }
+    tokens
+}
+
+/// Returns the edit that transforms the previous tokens into the current tokens.
+/// Positions and lengths of edits count integers, each token consists of five integers.
+fn token_edits(previous: &[SemanticToken], current: &[SemanticToken]) -> Vec<SemanticTokensEdit> {
+    let prefix = previous
+        .iter()
+        .zip(current)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix == previous.len() && prefix == current.len() {
+        return Vec::new();
+    }
+    vec![SemanticTokensEdit {
+        start: 5 * prefix as u32,
+        delete_count: 5 * (previous.len() - prefix - suffix) as u32,
+        data: Some(current[prefix..current.len() - suffix].to_vec()),
+    }]
+}
+
+impl VHDLServer {
+    fn compute_semantic_tokens(&mut self, uri: &Url, source: &Source) -> SemanticTokens {
+        let data = encode(self.project.find_all_identifiers_in_source(source));
+        self.semantic_tokens_result_id += 1;
+        let tokens = SemanticTokens {
+            result_id: Some(self.semantic_tokens_result_id.to_string()),
+            data,
+        };
+        self.semantic_tokens_cache
+            .insert(uri.clone(), tokens.clone());
+        tokens
+    }
+
+    pub fn semantic_tokens_full(
+        &mut self,
+        params: &SemanticTokensParams,
+    ) -> Option<SemanticTokensResult> {
+        let uri = &params.text_document.uri;
+        let source = self.project.get_source(&uri_to_file_name(uri))?;
+        Some(SemanticTokensResult::Tokens(
+            self.compute_semantic_tokens(uri, &source),
+        ))
+    }
+
+    /// Only sends the tokens that changed since the previous result, if it is still known
+    pub fn semantic_tokens_full_delta(
+        &mut self,
+        params: &SemanticTokensDeltaParams,
+    ) -> Option<SemanticTokensFullDeltaResult> {
+        let uri = &params.text_document.uri;
+        let source = self.project.get_source(&uri_to_file_name(uri))?;
+        let previous = self
+            .semantic_tokens_cache
+            .remove(uri)
+            .filter(|previous| previous.result_id.as_ref() == Some(&params.previous_result_id));
+        let current = self.compute_semantic_tokens(uri, &source);
+        Some(match previous {
+            Some(previous) => SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+                result_id: current.result_id,
+                edits: token_edits(&previous.data, &current.data),
+            }),
+            None => SemanticTokensFullDeltaResult::Tokens(current),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(delta_line: u32, delta_start: u32) -> SemanticToken {
+        SemanticToken {
+            delta_line,
+            delta_start,
+            length: 1,
+            token_type: 0,
+            token_modifiers_bitset: 0,
+        }
+    }
+
+    #[test]
+    fn token_edits_replace_changed_tokens_between_common_prefix_and_suffix() {
+        let previous = [token(0, 1), token(1, 2), token(1, 3), token(1, 4)];
+        let current = [token(0, 1), token(2, 2), token(1, 4)];
+        assert_eq!(
+            token_edits(&previous, &current),
+            vec![SemanticTokensEdit {
+                start: 5,
+                delete_count: 10,
+                data: Some(vec![token(2, 2)]),
+            }]
+        );
+        assert_eq!(token_edits(&current, &current), vec![]);
+        assert_eq!(
+            token_edits(&current, &current[..2]),
+            vec![SemanticTokensEdit {
+                start: 10,
+                delete_count: 5,
+                data: Some(vec![]),
+            }]
+        );
+    }
+}
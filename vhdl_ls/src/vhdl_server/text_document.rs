@@ -1,13 +1,16 @@
 use crate::vhdl_server::{
-    from_lsp_pos, from_lsp_range, srcpos_to_location, to_lsp_range, uri_to_file_name,
+    from_lsp_pos, from_lsp_range, srcpos_to_location, to_lsp_pos, to_lsp_range, uri_to_file_name,
     NonProjectFileHandling, VHDLServer,
 };
 use lsp_types::{
-    DidChangeTextDocumentParams, DidOpenTextDocumentParams, DocumentHighlight,
-    DocumentHighlightKind, GotoDefinitionResponse, Hover, HoverContents, Location, MarkupContent,
-    MarkupKind, ReferenceParams, TextDocumentItem, TextDocumentPositionParams,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentHighlight, DocumentHighlightKind, FoldingRange, FoldingRangeParams,
+    GotoDefinitionResponse, Hover, HoverContents, InlayHint, InlayHintKind, InlayHintLabel,
+    InlayHintParams, LinkedEditingRangeParams, LinkedEditingRanges, Location, MarkupContent,
+    MarkupKind, Range, ReferenceParams, SelectionRange, SelectionRangeParams, TextDocumentItem,
+    TextDocumentPositionParams,
 };
-use vhdl_lang::{Message, Source};
+use vhdl_lang::{FoldingRangeKind, Message, ReferenceKind, Source};
 
 impl VHDLServer {
     pub fn text_document_did_open_notification(&mut self, params: &DidOpenTextDocumentParams) {
@@ -40,6 +43,7 @@ impl VHDLServer {
                 let range = content_change.range.map(from_lsp_range);
                 source.change(range.as_ref(), &content_change.text);
             }
+            self.elaboration_diagnostics.clear();
             self.project.update_source(&source);
             self.publish_diagnostics();
         } else if self.settings.non_project_file_handling != NonProjectFileHandling::Ignore {
@@ -50,6 +54,10 @@ impl VHDLServer {
         }
     }
 
+    pub fn text_document_did_close_notification(&mut self, params: &DidCloseTextDocumentParams) {
+        self.semantic_tokens_cache.remove(&params.text_document.uri);
+    }
+
     pub fn text_document_declaration(
         &mut self,
         params: &TextDocumentPositionParams,
@@ -101,16 +109,23 @@ impl VHDLServer {
         let source = self
             .project
             .get_source(&uri_to_file_name(&params.text_document.uri))?;
-        let ent = self
-            .project
-            .find_declaration(&source, from_lsp_pos(params.position))?;
+        let hover = self.project.hover(&source, from_lsp_pos(params.position))?;
 
-        let value = self.project.format_declaration(ent)?;
+        let mut value = format!("{} `{}`", hover.kind, hover.ent.designator());
+        if let Some(type_mark) = hover.type_mark {
+            value.push_str(&format!(" : `{type_mark}`"));
+        }
+        if let Some(declaration) = hover.declaration {
+            value.push_str(&format!("\n```vhdl\n{declaration}\n```"));
+        }
+        if let Some(documentation) = hover.ent.documentation() {
+            value.push_str(&format!("\n\n{documentation}"));
+        }
 
         Some(Hover {
             contents: HoverContents::Markup(MarkupContent {
                 kind: MarkupKind::Markdown,
-                value: format!("```vhdl\n{value}\n```"),
+                value,
             }),
             range: None,
         })
@@ -154,11 +169,115 @@ impl VHDLServer {
 
         Some(
             self.project
-                .find_all_references_in_source(&source, ent)
-                .iter()
-                .map(|pos| DocumentHighlight {
+                .find_all_references_in_source_with_kind(&source, ent)
+                .into_iter()
+                .map(|(pos, kind)| DocumentHighlight {
                     range: to_lsp_range(pos.range()),
-                    kind: Some(DocumentHighlightKind::TEXT),
+                    kind: Some(match kind {
+                        ReferenceKind::Text => DocumentHighlightKind::TEXT,
+                        ReferenceKind::Read => DocumentHighlightKind::READ,
+                        ReferenceKind::Write => DocumentHighlightKind::WRITE,
+                    }),
+                })
+                .collect(),
+        )
+    }
+
+    /// The name of the construct at the cursor and its end name are edited together
+    pub fn linked_editing_range(
+        &self,
+        params: &LinkedEditingRangeParams,
+    ) -> Option<LinkedEditingRanges> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let names = self
+            .project
+            .find_linked_names(&source, from_lsp_pos(position.position));
+        if names.is_empty() {
+            return None;
+        }
+        Some(LinkedEditingRanges {
+            ranges: names.iter().map(|pos| to_lsp_range(pos.range())).collect(),
+            word_pattern: None,
+        })
+    }
+
+    pub fn selection_range(
+        &mut self,
+        params: &SelectionRangeParams,
+    ) -> Option<Vec<SelectionRange>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+
+        Some(
+            params
+                .positions
+                .iter()
+                .map(|position| {
+                    // Each span is the parent of the next, smaller span
+                    self.project
+                        .find_enclosing_spans(&source, from_lsp_pos(*position))
+                        .into_iter()
+                        .fold(None, |parent, span| {
+                            Some(SelectionRange {
+                                range: to_lsp_range(span.range()),
+                                parent: parent.map(Box::new),
+                            })
+                        })
+                        .unwrap_or(SelectionRange {
+                            range: Range::new(*position, *position),
+                            parent: None,
+                        })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn folding_range(&mut self, params: &FoldingRangeParams) -> Option<Vec<FoldingRange>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+
+        Some(
+            self.project
+                .list_folding_ranges(&source)
+                .into_iter()
+                .map(|folding| FoldingRange {
+                    start_line: folding.range.start.line,
+                    start_character: Some(folding.range.start.character),
+                    end_line: folding.range.end.line,
+                    end_character: Some(folding.range.end.character),
+                    kind: Some(match folding.kind {
+                        FoldingRangeKind::Region => lsp_types::FoldingRangeKind::Region,
+                        FoldingRangeKind::Comment => lsp_types::FoldingRangeKind::Comment,
+                    }),
+                    collapsed_text: None,
+                })
+                .collect(),
+        )
+    }
+
+    pub fn inlay_hint(&mut self, params: &InlayHintParams) -> Option<Vec<InlayHint>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+
+        Some(
+            self.project
+                .list_inlay_hints(&source, from_lsp_range(params.range))
+                .into_iter()
+                .map(|hint| InlayHint {
+                    position: to_lsp_pos(hint.pos),
+                    label: InlayHintLabel::String(hint.label),
+                    kind: Some(InlayHintKind::PARAMETER),
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: None,
+                    padding_right: Some(true),
+                    data: None,
                 })
                 .collect(),
         )
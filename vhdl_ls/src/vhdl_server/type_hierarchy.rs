@@ -0,0 +1,70 @@
+use crate::vhdl_server::{
+    from_lsp_pos, srcpos_to_location, to_symbol_kind, uri_to_file_name, VHDLServer,
+};
+use lsp_types::{
+    TypeHierarchyItem, TypeHierarchyPrepareParams, TypeHierarchySubtypesParams,
+    TypeHierarchySupertypesParams,
+};
+use vhdl_lang::{AnyEntKind, EntRef};
+
+impl VHDLServer {
+    /// Returns the type or subtype at the cursor as the root of the type hierarchy
+    pub fn prepare_type_hierarchy(
+        &self,
+        params: &TypeHierarchyPrepareParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let ent = self
+            .project
+            .find_declaration(&source, from_lsp_pos(position.position))?;
+        if !matches!(ent.kind(), AnyEntKind::Type(_)) {
+            return None;
+        }
+        Some(vec![type_hierarchy_item(ent)?])
+    }
+
+    pub fn type_hierarchy_supertypes(
+        &self,
+        params: &TypeHierarchySupertypesParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let id = self.entity_id_from_data(params.item.data.as_ref())?;
+        Some(
+            self.project
+                .find_supertypes(id)
+                .into_iter()
+                .filter_map(type_hierarchy_item)
+                .collect(),
+        )
+    }
+
+    pub fn type_hierarchy_subtypes(
+        &self,
+        params: &TypeHierarchySubtypesParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let id = self.entity_id_from_data(params.item.data.as_ref())?;
+        Some(
+            self.project
+                .find_subtypes(id)
+                .into_iter()
+                .filter_map(type_hierarchy_item)
+                .collect(),
+        )
+    }
+}
+
+fn type_hierarchy_item(ent: EntRef<'_>) -> Option<TypeHierarchyItem> {
+    let location = srcpos_to_location(ent.decl_pos()?);
+    Some(TypeHierarchyItem {
+        name: ent.designator().to_string(),
+        kind: to_symbol_kind(ent.kind()),
+        tags: None,
+        detail: Some(ent.kind().describe().to_owned()),
+        uri: location.uri,
+        range: location.range,
+        selection_range: location.range,
+        data: Some(serde_json::Value::from(ent.id().to_raw())),
+    })
+}
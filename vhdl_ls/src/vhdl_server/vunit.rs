@@ -0,0 +1,158 @@
+use crate::vhdl_server::{file_name_to_uri, srcpos_to_location, VHDLServer};
+use lsp_types::{CodeLens, Command, Location, Position, Range};
+use serde_json::{json, Value};
+use std::io;
+use std::path::Path;
+use std::process::{self, Output};
+use vhdl_lang::{parse_vunit_output, Message};
+
+/// Custom request that lists the test cases of all VUnit testbenches of the project
+pub const VUNIT_TEST_CASES: &str = "vhdl_ls/vunitTestCases";
+
+/// Command that runs a single VUnit test case given by its full name
+pub const RUN_VUNIT_TEST: &str = "vhdl_ls.runVUnitTest";
+
+/// Notification with the outcome of a test case run by `RUN_VUNIT_TEST`
+pub const VUNIT_TEST_RESULT: &str = "vhdl_ls/vunitTestResult";
+
+/// The VUnit run script that is expected next to the `vhdl_ls.toml` file
+const RUN_SCRIPT: &str = "run.py";
+
+impl VHDLServer {
+    pub fn vunit_test_cases(&self) -> Value {
+        self.project
+            .vunit_test_cases()
+            .iter()
+            .map(|test_case| {
+                json!({
+                    "library": test_case.library,
+                    "testbench": test_case.testbench,
+                    "name": test_case.name,
+                    "fullName": test_case.full_name(),
+                    "location": srcpos_to_location(&test_case.pos),
+                })
+            })
+            .collect()
+    }
+
+    /// Adds a lens to run each test case of a VUnit testbench
+    pub fn vunit_code_lenses(&self, file_name: &Path) -> Vec<CodeLens> {
+        self.project
+            .vunit_test_cases()
+            .into_iter()
+            .filter(|test_case| test_case.pos.file_name() == file_name)
+            .map(|test_case| CodeLens {
+                range: srcpos_to_location(&test_case.pos).range,
+                command: Some(Command {
+                    title: "Run test".to_owned(),
+                    command: RUN_VUNIT_TEST.to_owned(),
+                    arguments: Some(vec![Value::String(test_case.full_name())]),
+                }),
+                data: None,
+            })
+            .collect()
+    }
+
+    /// Runs a test case using the VUnit run script of the project.
+    /// The test runs in the background, and its failures are reported to the user
+    /// and published with the location where they were reported in the VHDL code
+    /// when it has finished.
+    pub fn run_vunit_test(&mut self, arguments: &[Value]) {
+        let Some(test) = arguments.first().and_then(|arg| arg.as_str()) else {
+            self.message(Message::error(format!(
+                "Expected the name of a test case as argument of '{RUN_VUNIT_TEST}'"
+            )));
+            return;
+        };
+        let Some(directory) = self.config_file.as_ref().and_then(|file| file.parent()) else {
+            self.message(Message::error(
+                "Cannot run VUnit without a vhdl_ls.toml file in the workspace root",
+            ));
+            return;
+        };
+        let directory = directory.to_owned();
+        if !directory.join(RUN_SCRIPT).exists() {
+            self.message(Message::error(format!(
+                "Cannot run VUnit, '{RUN_SCRIPT}' does not exist in '{}'",
+                directory.to_string_lossy()
+            )));
+            return;
+        }
+
+        let test = test.to_owned();
+        let python = if cfg!(windows) { "python" } else { "python3" };
+        let mut command = process::Command::new(python);
+        command.arg(RUN_SCRIPT).arg(&test).current_dir(&directory);
+        self.spawn_background(
+            RUN_VUNIT_TEST,
+            move || command.output(),
+            move |server, output| server.vunit_test_finished(&test, &directory, output),
+        );
+    }
+
+    fn vunit_test_finished(&self, test: &str, directory: &Path, output: io::Result<Output>) {
+        let output = match output {
+            Ok(output) => output,
+            Err(err) => {
+                self.message(Message::error(format!("Cannot run VUnit: {err}")));
+                self.publish_vunit_test_result(test, false, vec![]);
+                return;
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let failures = parse_vunit_output(&stdout, &self.project.vunit_test_cases());
+        let passed = output.status.success() && failures.is_empty();
+        if passed {
+            self.message(Message::info(format!("Test '{test}' passed")));
+        } else if failures.is_empty() {
+            self.message(Message::error(format!(
+                "VUnit exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let failures = failures
+            .into_iter()
+            .map(|failure| {
+                // Paths in the output of VUnit are relative to the run script
+                let location = failure
+                    .location
+                    .map(|(file_name, line)| (directory.join(file_name), line));
+                match &location {
+                    Some((file_name, line)) => self.message(Message::error(format!(
+                        "{}:{}: {}",
+                        file_name.to_string_lossy(),
+                        line + 1,
+                        failure.message
+                    ))),
+                    None => self.message(Message::error(failure.message.clone())),
+                }
+                let location = location.map(|(file_name, line)| {
+                    let pos = Position::new(line, 0);
+                    Location {
+                        uri: file_name_to_uri(&file_name),
+                        range: Range::new(pos, pos),
+                    }
+                });
+                json!({
+                    "test": failure.test,
+                    "message": failure.message,
+                    "location": location,
+                })
+            })
+            .collect();
+        self.publish_vunit_test_result(test, passed, failures);
+    }
+
+    fn publish_vunit_test_result(&self, test: &str, passed: bool, failures: Vec<Value>) {
+        self.rpc.send_notification(
+            VUNIT_TEST_RESULT,
+            json!({
+                "test": test,
+                "passed": passed,
+                "failures": failures,
+            }),
+        );
+    }
+}
@@ -25,6 +25,7 @@ impl VHDLServer {
 
                 self.project
                     .update_config(config, &mut self.message_filter());
+                self.semantic_tokens_cache.clear();
                 self.publish_diagnostics();
             }
         }
@@ -99,12 +100,13 @@ impl VHDLServer {
                     }
                 })
             })
-            .take(trunc_limit)
             .collect();
+        // Only keep the best matches
         symbols_with_scores
             .into_sorted_vec()
             .into_iter()
             .rev()
+            .take(trunc_limit)
             .map(|wsws| wsws.symbol)
             .collect()
     }